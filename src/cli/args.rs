@@ -5,12 +5,14 @@ use std::path::PathBuf;
 
 use crate::cli::commands::{
     asm::AsmCommands,
+    audit::AuditCommands,
     baseline::BaselineCommands,
     blame::BlameArgs,
     bulk::BulkCommands,
     cache::CacheCommands,
     capa::CapaCommands,
     cmp::CmpCommands,
+    cmpl::CmplCommands,
     completions::CompletionsArgs,
     config::ConfigCommands,
     ctrl::CtrlCommands,
@@ -28,8 +30,10 @@ use crate::cli::commands::{
     lot::LotCommands,
     mate::MateCommands,
     ncr::NcrCommands,
+    plan::PlanArgs,
     proc::ProcCommands,
     quote::QuoteCommands,
+    rcv::RcvCommands,
     recent::RecentArgs,
     report::ReportCommands,
     req::ReqCommands,
@@ -76,6 +80,7 @@ BILL OF MATERIALS:
 PROCUREMENT:
   quote       Quote management (new, list, show, edit)
   sup         Supplier management (new, list, show, edit)
+  plan        Material requirements plan from planned lots (purchase plan)
 
 MANUFACTURING:
   proc        Manufacturing process management (new, list, show, edit)
@@ -83,10 +88,13 @@ MANUFACTURING:
   work        Work instruction management (new, list, show, edit)
   lot         Production lot/batch management (new, list, show, step, complete)
   dev         Process deviation management (new, list, show, approve, expire)
+  rcv         Receiving inspection of supplier lots (new, inspect, accept, reject)
 
 QUALITY:
   ncr         Non-conformance report management (new, list, show, edit)
   capa        Corrective/preventive action management (new, list, show, edit)
+  cmpl        Customer complaint / field return management (new, list, assess)
+  audit       Internal / supplier audit management (new, list, schedule, promote)
 
 TOLERANCE ANALYSIS:
   feat        Feature management - dimensional features on components
@@ -232,6 +240,9 @@ pub enum Commands {
     #[command(subcommand)]
     Sup(SupCommands),
 
+    /// Material requirements plan from planned lots (purchase plan)
+    Plan(PlanArgs),
+
     // ─────────────────────────────────────────────────────────────────────
     // MANUFACTURING
     // ─────────────────────────────────────────────────────────────────────
@@ -255,6 +266,10 @@ pub enum Commands {
     #[command(subcommand)]
     Dev(DevCommands),
 
+    /// Receiving inspection of supplier lots (new, inspect, accept, reject)
+    #[command(subcommand)]
+    Rcv(RcvCommands),
+
     // ─────────────────────────────────────────────────────────────────────
     // QUALITY
     // ─────────────────────────────────────────────────────────────────────
@@ -266,6 +281,14 @@ pub enum Commands {
     #[command(subcommand)]
     Capa(CapaCommands),
 
+    /// Customer complaint / field return management (new, list, assess)
+    #[command(subcommand)]
+    Cmpl(CmplCommands),
+
+    /// Internal / supplier audit management (new, list, schedule, promote)
+    #[command(subcommand)]
+    Audit(AuditCommands),
+
     // ─────────────────────────────────────────────────────────────────────
     // TOLERANCE ANALYSIS
     // ─────────────────────────────────────────────────────────────────────
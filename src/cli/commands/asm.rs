@@ -11,12 +11,19 @@ use crate::cli::filters::StatusFilter;
 use crate::cli::helpers::{escape_csv, truncate_str};
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::bom_diff::{compare, explode, BomDiff, BomSnapshot, BomView, ChangeKind};
+use crate::core::currency::Converter;
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::line_balance::{analyze_routing, Demand, DemandPeriod, ShiftPattern};
+use crate::core::mass_properties::{rollup as mass_rollup, MassIssue};
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
+use crate::core::should_cost::{estimate_make_parts, ShouldCost};
 use crate::core::Config;
-use crate::entities::assembly::Assembly;
+use crate::core::Git;
+use crate::entities::assembly::{Assembly, MassBudget};
 use crate::entities::component::Component;
+use crate::entities::quote::Currency;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -43,6 +50,9 @@ pub enum AsmCommands {
     /// Show expanded BOM for an assembly
     Bom(BomArgs),
 
+    /// Compare BOMs between two assemblies or git refs/baselines
+    Diff(DiffArgs),
+
     /// Add a component to an assembly's BOM
     #[command(name = "add")]
     AddComponent(AddComponentArgs),
@@ -54,7 +64,7 @@ pub enum AsmCommands {
     /// Calculate total cost for an assembly (recursive BOM)
     Cost(CostArgs),
 
-    /// Calculate total mass for an assembly (recursive BOM)
+    /// Calculate mass, center of gravity and inertia for an assembly (recursive BOM)
     Mass(MassArgs),
 
     /// Manage manufacturing routing for assembly
@@ -76,6 +86,9 @@ pub enum RoutingCommands {
 
     /// Set complete routing (replaces existing)
     Set(RoutingSetArgs),
+
+    /// Analyze line balance: labor content, bottleneck, takt and efficiency
+    Analyze(RoutingAnalyzeArgs),
 }
 
 #[derive(clap::Args, Debug)]
@@ -119,6 +132,56 @@ pub struct RoutingSetArgs {
     pub procs: Vec<String>,
 }
 
+/// Period for demand in routing analysis
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliDemandPeriod {
+    Day,
+    Week,
+}
+
+impl From<CliDemandPeriod> for DemandPeriod {
+    fn from(p: CliDemandPeriod) -> Self {
+        match p {
+            CliDemandPeriod::Day => DemandPeriod::Day,
+            CliDemandPeriod::Week => DemandPeriod::Week,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RoutingAnalyzeArgs {
+    /// Assembly ID (ASM-xxx or short ID like ASM@1)
+    pub asm: String,
+
+    /// Customer demand in units per period (enables takt analysis)
+    #[arg(long)]
+    pub demand: Option<f64>,
+
+    /// Period the demand is expressed in
+    #[arg(long, default_value = "day")]
+    pub per: CliDemandPeriod,
+
+    /// Batch size for setup amortization
+    #[arg(long, default_value = "1")]
+    pub batch: u32,
+
+    /// Shifts per day
+    #[arg(long, default_value = "1")]
+    pub shifts: u32,
+
+    /// Hours per shift
+    #[arg(long, default_value = "8")]
+    pub shift_hours: f64,
+
+    /// Break minutes per shift
+    #[arg(long, default_value = "30")]
+    pub break_minutes: f64,
+
+    /// Working days per week
+    #[arg(long, default_value = "5")]
+    pub days: u32,
+}
+
 /// List column types
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum ListColumn {
@@ -313,6 +376,27 @@ pub struct BomArgs {
     pub flat: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    /// Assembly ID or short ID (ASM@N)
+    pub assembly: String,
+
+    /// Assembly to compare against (default: the same assembly)
+    pub other: Option<String>,
+
+    /// Git ref or baseline for the first BOM (default: working tree)
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// Git ref or baseline for the second BOM (default: working tree)
+    #[arg(long)]
+    pub to: Option<String>,
+
+    /// Compare flattened BOMs (components rolled up across sub-assemblies)
+    #[arg(long)]
+    pub flat: bool,
+}
+
 #[derive(clap::Args, Debug)]
 pub struct AddComponentArgs {
     /// Assembly ID or short ID (ASM@N)
@@ -370,6 +454,15 @@ pub struct CostArgs {
     /// Warn about expired quotes (enabled by default)
     #[arg(long, default_value = "true")]
     pub warn_expired: bool,
+
+    /// Report in this currency instead of the configured reporting currency
+    #[arg(long)]
+    pub currency: Option<Currency>,
+
+    /// Batch size that make-part setup time is amortized over
+    /// (default: costing.batch_size from config)
+    #[arg(long)]
+    pub batch: Option<u32>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -380,6 +473,10 @@ pub struct MassArgs {
     /// Show breakdown by component
     #[arg(long)]
     pub breakdown: bool,
+
+    /// Mass budget in kg (overrides the assembly's mass_budget)
+    #[arg(long)]
+    pub budget: Option<f64>,
 }
 
 /// Parse an ID:QTY pair (e.g., "CMP@1:2" or "CMP-xxx:3")
@@ -405,20 +502,22 @@ pub fn run(cmd: AsmCommands, global: &GlobalOpts) -> Result<()> {
         AsmCommands::Delete(args) => run_delete(args),
         AsmCommands::Archive(args) => run_archive(args),
         AsmCommands::Bom(args) => run_bom(args, global),
+        AsmCommands::Diff(args) => run_diff(args, global),
         AsmCommands::AddComponent(args) => run_add_component(args),
         AsmCommands::RemoveComponent(args) => run_remove_component(args),
         AsmCommands::Cost(args) => run_cost(args),
-        AsmCommands::Mass(args) => run_mass(args),
-        AsmCommands::Routing(cmd) => run_routing(cmd),
+        AsmCommands::Mass(args) => run_mass(args, global),
+        AsmCommands::Routing(cmd) => run_routing(cmd, global),
     }
 }
 
-fn run_routing(cmd: RoutingCommands) -> Result<()> {
+fn run_routing(cmd: RoutingCommands, global: &GlobalOpts) -> Result<()> {
     match cmd {
         RoutingCommands::Add(args) => run_routing_add(args),
         RoutingCommands::Rm(args) => run_routing_rm(args),
         RoutingCommands::List(args) => run_routing_list(args),
         RoutingCommands::Set(args) => run_routing_set(args),
+        RoutingCommands::Analyze(args) => run_routing_analyze(args, global),
     }
 }
 
@@ -769,6 +868,7 @@ fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
                 quantity: qty,
                 reference_designators: Vec::new(),
                 notes: None,
+                placements: Vec::new(),
             });
             added_count += 1;
         }
@@ -1101,6 +1201,305 @@ fn run_bom(args: BomArgs, global: &GlobalOpts) -> Result<()> {
     Ok(())
 }
 
+fn run_diff(args: DiffArgs, global: &GlobalOpts) -> Result<()> {
+    if args.other.is_none() && args.from.is_none() && args.to.is_none() {
+        return Err(miette::miette!(
+            "Nothing to compare: give a second assembly, --from or --to"
+        ));
+    }
+
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolve = |id: &str| short_ids.resolve(id).unwrap_or_else(|| id.to_string());
+    let old_id = resolve(&args.assembly);
+    let new_id = args.other.as_deref().map(resolve).unwrap_or(old_id.clone());
+
+    let old_ref = args
+        .from
+        .as_deref()
+        .map(|r| resolve_bom_ref(&project, r))
+        .transpose()?;
+    let new_ref = args
+        .to
+        .as_deref()
+        .map(|r| resolve_bom_ref(&project, r))
+        .transpose()?;
+    let old_snapshot = load_bom_snapshot(&project, old_ref.as_deref())?;
+    let new_snapshot = load_bom_snapshot(&project, new_ref.as_deref())?;
+    let old_label = old_ref.as_deref().unwrap_or("working tree");
+    let new_label = new_ref.as_deref().unwrap_or("working tree");
+
+    let old_asm = old_snapshot
+        .assembly(&old_id)
+        .ok_or_else(|| miette::miette!("Assembly {} not found in {}", args.assembly, old_label))?;
+    let new_asm = new_snapshot.assembly(&new_id).ok_or_else(|| {
+        miette::miette!(
+            "Assembly {} not found in {}",
+            args.other.as_deref().unwrap_or(&args.assembly),
+            new_label
+        )
+    })?;
+
+    let view = if args.flat {
+        BomView::Flat
+    } else {
+        BomView::Indented
+    };
+    let diff = compare(
+        &explode(&old_snapshot, old_asm, view),
+        &explode(&new_snapshot, new_asm, view),
+        view,
+    );
+
+    match global.output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&diff).into_diagnostic()?);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&diff).into_diagnostic()?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("change,path,id,part_number,title,old_quantity,new_quantity,old_revision,new_revision,refs_added,refs_removed,cost_delta,mass_delta");
+            for c in &diff.changes {
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{:.4},{:.4}",
+                    c.kind,
+                    c.path.join("/"),
+                    c.id,
+                    escape_csv(&c.part_number),
+                    escape_csv(&c.title),
+                    c.old_quantity.map(|q| q.to_string()).unwrap_or_default(),
+                    c.new_quantity.map(|q| q.to_string()).unwrap_or_default(),
+                    escape_csv(c.old_revision.as_deref().unwrap_or("")),
+                    escape_csv(c.new_revision.as_deref().unwrap_or("")),
+                    escape_csv(&c.refs_added.join(";")),
+                    escape_csv(&c.refs_removed.join(";")),
+                    c.cost_delta,
+                    c.mass_delta
+                );
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let describe = |asm: &Assembly, label: &str| {
+        format!(
+            "{}{} ({})",
+            asm.part_number,
+            asm.revision
+                .as_deref()
+                .filter(|r| !r.is_empty())
+                .map(|r| format!(" rev {}", r))
+                .unwrap_or_default(),
+            label
+        )
+    };
+    println!(
+        "{} {} {} {}",
+        style("BOM diff").bold(),
+        style(describe(old_asm, old_label)).yellow(),
+        style("→").dim(),
+        style(describe(new_asm, new_label)).yellow()
+    );
+    println!(
+        "   {} view",
+        if args.flat { "Flattened" } else { "Indented" }
+    );
+    println!();
+
+    print_bom_diff(&diff, &old_snapshot, &new_snapshot);
+
+    Ok(())
+}
+
+/// Print a BOM diff as a change table with cost and mass totals
+pub(crate) fn print_bom_diff(
+    diff: &BomDiff,
+    old_snapshot: &BomSnapshot,
+    new_snapshot: &BomSnapshot,
+) {
+    if diff.changes.is_empty() {
+        println!("{}", style("No BOM differences.").green());
+    } else {
+        println!(
+            "{:<9} {:<40} {:>11} {:>9} {:<18} {:>10} {:>11}",
+            style("CHANGE").bold(),
+            style("ITEM").bold(),
+            style("QTY").bold(),
+            style("REV").bold(),
+            style("REFS").bold(),
+            style("COST Δ").bold(),
+            style("MASS Δ kg").bold()
+        );
+        let mut last_path: Vec<String> = Vec::new();
+        for c in &diff.changes {
+            // Sub-assembly headings for the indented view
+            if c.path != last_path {
+                for (depth, parent) in c.path.iter().enumerate() {
+                    if last_path.get(depth) == Some(parent) {
+                        continue;
+                    }
+                    let name = new_snapshot
+                        .assemblies
+                        .get(parent)
+                        .or_else(|| old_snapshot.assemblies.get(parent))
+                        .map(|a| format!("{} {}", a.part_number, a.title))
+                        .unwrap_or_else(|| parent.clone());
+                    println!(
+                        "{:<9} {}",
+                        "",
+                        style(format!("{}{}", "  ".repeat(depth), name)).dim()
+                    );
+                }
+                last_path = c.path.clone();
+            }
+
+            let mut item = format!(
+                "{}{} {}",
+                "  ".repeat(c.path.len()),
+                if c.part_number.is_empty() {
+                    &c.id
+                } else {
+                    &c.part_number
+                },
+                c.title
+            );
+            if let Some(ref old) = c.replaces {
+                let old_pn = old_snapshot
+                    .components
+                    .get(old)
+                    .map(|cmp| cmp.part_number.clone())
+                    .unwrap_or_else(|| old.clone());
+                item.push_str(&format!(" (was {})", old_pn));
+            }
+            let arrow = |changed: bool, old: Option<String>, new: Option<String>| {
+                let dash = || "-".to_string();
+                if changed {
+                    format!("{}→{}", old.unwrap_or_else(dash), new.unwrap_or_else(dash))
+                } else {
+                    new.or(old).unwrap_or_else(dash)
+                }
+            };
+            let qty = arrow(
+                c.quantity_changed(),
+                c.old_quantity.map(|q| q.to_string()),
+                c.new_quantity.map(|q| q.to_string()),
+            );
+            let rev = arrow(
+                c.revision_changed(),
+                c.old_revision.clone(),
+                c.new_revision.clone(),
+            );
+            let refs: Vec<String> = c
+                .refs_added
+                .iter()
+                .map(|r| format!("+{}", r))
+                .chain(c.refs_removed.iter().map(|r| format!("-{}", r)))
+                .collect();
+            let kind = format!("{:<9}", c.kind.to_string());
+            let kind = match c.kind {
+                ChangeKind::Added => style(kind).green(),
+                ChangeKind::Removed => style(kind).red(),
+                ChangeKind::Replaced => style(kind).cyan(),
+                ChangeKind::Changed => style(kind).yellow(),
+            };
+            println!(
+                "{} {:<40} {:>11} {:>9} {:<18} {:>10} {:>11}",
+                kind,
+                truncate_str(&item, 40),
+                qty,
+                truncate_str(&rev, 9),
+                truncate_str(&refs.join(" "), 18),
+                signed_amount(c.cost_delta, 2, "$"),
+                signed_amount(c.mass_delta, 3, "")
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{} {} added, {} removed, {} replaced, {} changed, {} unchanged",
+        style("Summary:").bold(),
+        style(diff.count(ChangeKind::Added)).green(),
+        style(diff.count(ChangeKind::Removed)).red(),
+        style(diff.count(ChangeKind::Replaced)).cyan(),
+        style(diff.count(ChangeKind::Changed)).yellow(),
+        diff.unchanged
+    );
+    println!(
+        "{} ${:.2} → ${:.2} ({:+.2})",
+        style("Cost:   ").bold(),
+        diff.old_cost,
+        diff.new_cost,
+        diff.new_cost - diff.old_cost
+    );
+    println!(
+        "{} {:.3} kg → {:.3} kg ({:+.3} kg)",
+        style("Mass:   ").bold(),
+        diff.old_mass,
+        diff.new_mass,
+        diff.new_mass - diff.old_mass
+    );
+}
+
+/// Format a delta with an explicit sign, blank when zero at the precision
+fn signed_amount(value: f64, precision: usize, unit: &str) -> String {
+    let rounded = format!("{:.*}", precision, value.abs());
+    if rounded.trim_start_matches(['0', '.']).is_empty() {
+        return String::new();
+    }
+    format!("{}{}{}", if value < 0.0 { "-" } else { "+" }, unit, rounded)
+}
+
+/// Resolve a git ref or baseline name (with or without the `tdt-` prefix)
+fn resolve_bom_ref(project: &Project, reference: &str) -> Result<String> {
+    let git = Git::new(project.root());
+    let candidates = if reference.starts_with("tdt-") {
+        vec![reference.to_string()]
+    } else {
+        vec![reference.to_string(), format!("tdt-{}", reference)]
+    };
+    candidates
+        .into_iter()
+        .find(|r| git.rev_parse(&format!("{}^{{commit}}", r)).is_ok())
+        .ok_or_else(|| miette::miette!("Unknown git ref or baseline: {}", reference))
+}
+
+/// Load the assemblies, components and quotes of the working tree or a git ref
+pub(crate) fn load_bom_snapshot(project: &Project, reference: Option<&str>) -> Result<BomSnapshot> {
+    let Some(reference) = reference else {
+        return Ok(BomSnapshot::new(
+            load_all_assemblies(project),
+            load_all_components(project),
+            load_all_quotes(project),
+        ));
+    };
+
+    fn parse_all<T: serde::de::DeserializeOwned>(
+        git: &Git,
+        reference: &str,
+        dir: &str,
+    ) -> Result<Vec<T>> {
+        let files = git
+            .files_at(reference, dir)
+            .map_err(|e| miette::miette!("Failed to read {} at {}: {}", dir, reference, e))?;
+        Ok(files
+            .iter()
+            .filter_map(|content| serde_yml::from_str(content).ok())
+            .collect())
+    }
+
+    let git = Git::new(project.root());
+    Ok(BomSnapshot::new(
+        parse_all(&git, reference, "bom/assemblies")?,
+        parse_all(&git, reference, "bom/components")?,
+        parse_all(&git, reference, "bom/quotes")?,
+    ))
+}
+
 fn run_add_component(args: AddComponentArgs) -> Result<()> {
     use crate::entities::assembly::BomItem;
 
@@ -1241,6 +1640,7 @@ fn run_add_component(args: AddComponentArgs) -> Result<()> {
                 } else {
                     None
                 },
+                placements: Vec::new(),
             };
             assembly.bom.push(bom_item);
             added_count += 1;
@@ -1416,8 +1816,13 @@ fn run_cost(args: CostArgs) -> Result<()> {
         }
     }
 
+    // Should-cost of make parts from their routing and material
+    let should_costs = estimate_make_parts(&project, &components, args.batch);
+
     let production_qty = args.qty;
     let include_nre = !args.no_nre;
+    let mut converter = Converter::from_config(args.currency);
+    let sym = converter.target.symbol();
 
     // Track components with quotes but no selection (for user feedback)
     let mut unselected_quote_warnings: Vec<(String, String, usize)> = Vec::new(); // (id, title, quote_count)
@@ -1440,6 +1845,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
         assembly_map: &std::collections::HashMap<String, &Assembly>,
         quote_map: &std::collections::HashMap<String, &Quote>,
         component_quotes: &std::collections::HashMap<String, Vec<&Quote>>,
+        should_costs: &std::collections::HashMap<String, ShouldCost>,
         breakdown: &mut Vec<(String, String, u32, f64, f64, String, f64)>,
         unselected_warnings: &mut Vec<(String, String, usize)>,
         expired_warnings: &mut Vec<(String, String, String)>,
@@ -1447,6 +1853,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
         visited: &mut std::collections::HashSet<String>,
         production_qty: u32,
         warn_expired: bool,
+        converter: &mut Converter,
     ) -> f64 {
         let mut total = 0.0;
         for item in bom {
@@ -1459,8 +1866,10 @@ fn run_cost(args: CostArgs) -> Result<()> {
                         cmp,
                         quote_map,
                         component_quotes,
+                        should_costs,
                         purchase_qty,
                         unselected_warnings,
+                        converter,
                     );
 
                 // Track expired quote warning
@@ -1509,6 +1918,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
                         assembly_map,
                         quote_map,
                         component_quotes,
+                        should_costs,
                         breakdown,
                         unselected_warnings,
                         expired_warnings,
@@ -1516,6 +1926,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
                         visited,
                         production_qty,
                         warn_expired,
+                        converter,
                     );
                     let line_cost = sub_cost * item.quantity as f64;
                     total += line_cost;
@@ -1539,8 +1950,10 @@ fn run_cost(args: CostArgs) -> Result<()> {
         cmp: &Component,
         quote_map: &std::collections::HashMap<String, &Quote>,
         component_quotes: &std::collections::HashMap<String, Vec<&Quote>>,
+        should_costs: &std::collections::HashMap<String, ShouldCost>,
         purchase_qty: u32,
         unselected_warnings: &mut Vec<(String, String, usize)>,
+        converter: &mut Converter,
     ) -> (f64, String, f64, bool, String) {
         // Returns: (unit_price, source, nre_total, is_expired, valid_until)
 
@@ -1548,21 +1961,30 @@ fn run_cost(args: CostArgs) -> Result<()> {
         if let Some(ref quote_id) = cmp.selected_quote {
             if let Some(quote) = quote_map.get(quote_id) {
                 if let Some(price) = quote.price_for_qty(purchase_qty) {
-                    let nre = quote.total_nre();
+                    let price = converter.convert(price, quote.currency);
+                    let nre = converter.convert(quote.total_nre(), quote.currency);
                     let is_expired = quote.is_expired();
                     let valid_until = quote.valid_until.map(|d| d.to_string()).unwrap_or_default();
-                    return (
-                        price,
-                        format!("quote@{}", purchase_qty),
-                        nre,
-                        is_expired,
-                        valid_until,
-                    );
+                    let source = if quote.currency == converter.target {
+                        format!("quote@{}", purchase_qty)
+                    } else {
+                        format!("quote@{} {}", purchase_qty, quote.currency)
+                    };
+                    return (price, source, nre, is_expired, valid_until);
                 }
             }
         }
 
-        // Priority 2: Fall back to manual unit_cost
+        // Priority 2: Should-cost of a make part
+        if let Some(estimate) = should_costs
+            .get(&cmp.id.to_string())
+            .filter(|e| e.is_estimated())
+        {
+            let cost = converter.convert_unit_cost(estimate.total);
+            return (cost, "should-cost".to_string(), 0.0, false, String::new());
+        }
+
+        // Priority 3: Fall back to manual unit_cost
         if let Some(cost) = cmp.unit_cost {
             // Check if there are quotes available but none selected
             if let Some(quotes) = component_quotes.get(&cmp.id.to_string()) {
@@ -1580,6 +2002,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
                     }
                 }
             }
+            let cost = converter.convert_unit_cost(cost);
             return (cost, "unit_cost".to_string(), 0.0, false, String::new());
         }
 
@@ -1604,6 +2027,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
         &assembly_map,
         &quote_map,
         &component_quotes,
+        &should_costs,
         &mut breakdown,
         &mut unselected_quote_warnings,
         &mut expired_quote_warnings,
@@ -1611,6 +2035,7 @@ fn run_cost(args: CostArgs) -> Result<()> {
         &mut visited,
         production_qty,
         args.warn_expired,
+        &mut converter,
     );
 
     // Calculate total NRE
@@ -1684,28 +2109,28 @@ fn run_cost(args: CostArgs) -> Result<()> {
             if *line_cost > 0.0 || *unit_price > 0.0 {
                 if show_nre_col {
                     let nre_str = if *nre > 0.0 {
-                        format!("${:.0}", nre)
+                        format!("{}{:.0}", sym, nre)
                     } else {
                         "-".to_string()
                     };
                     println!(
-                        "{:<10} {:<24} {:<5} ${:<9.2} ${:<9.2} {:<10} {}",
+                        "{:<10} {:<24} {:<5} {:<10} {:<10} {:<10} {}",
                         id_short,
                         truncate_str(title, 22),
                         qty,
-                        unit_price,
-                        line_cost,
+                        format!("{}{:.2}", sym, unit_price),
+                        format!("{}{:.2}", sym, line_cost),
                         nre_str,
                         style(source).dim()
                     );
                 } else {
                     println!(
-                        "{:<10} {:<26} {:<5} ${:<9.2} ${:<9.2} {}",
+                        "{:<10} {:<26} {:<5} {:<10} {:<10} {}",
                         id_short,
                         truncate_str(title, 24),
                         qty,
-                        unit_price,
-                        line_cost,
+                        format!("{}{:.2}", sym, unit_price),
+                        format!("{}{:.2}", sym, line_cost),
                         style(source).dim()
                     );
                 }
@@ -1738,31 +2163,71 @@ fn run_cost(args: CostArgs) -> Result<()> {
     }
 
     // Cost summary
-    println!("{} ${:.2}", style("Piece Cost:").bold(), total_piece_cost);
+    println!(
+        "{} {}{:.2}",
+        style("Piece Cost:").bold(),
+        sym,
+        total_piece_cost
+    );
 
     if include_nre && total_nre > 0.0 {
-        println!("{} ${:.2}", style("Total NRE:").bold(), total_nre);
+        println!("{} {}{:.2}", style("Total NRE:").bold(), sym, total_nre);
         if let Some(amort_qty) = args.amortize {
             println!(
-                "{} ${:.4} (NRE / {} units)",
+                "{} {}{:.4} (NRE / {} units)",
                 style("NRE per Unit:").bold(),
+                sym,
                 nre_per_unit,
                 amort_qty
             );
             let effective_unit = total_piece_cost + nre_per_unit;
             println!(
-                "{} ${:.4}",
+                "{} {}{:.4}",
                 style("Effective Unit Cost:").green().bold(),
+                sym,
                 effective_unit
             );
         }
     } else {
         println!(
-            "{} ${:.2}",
+            "{} {}{:.2}",
             style("Total Cost:").green().bold(),
+            sym,
             total_piece_cost
         );
     }
+    print_rate_warnings(&converter);
+
+    // Show make parts whose should-cost is missing inputs
+    let mut incomplete: Vec<&ShouldCost> = should_costs
+        .values()
+        .filter(|e| e.is_estimated() && !e.issues.is_empty())
+        .filter(|e| {
+            breakdown
+                .iter()
+                .any(|(id, _, _, _, _, source, _)| *id == e.component && source == "should-cost")
+        })
+        .collect();
+    if !incomplete.is_empty() {
+        incomplete.sort_by(|a, b| a.title.cmp(&b.title));
+        println!();
+        println!(
+            "{} Some should-cost estimates are incomplete:",
+            style("Note:").yellow().bold()
+        );
+        for estimate in incomplete {
+            println!(
+                "   {} {} - {}",
+                style("•").dim(),
+                style(truncate_str(&estimate.title, 30)).cyan(),
+                estimate.issues.join("; ")
+            );
+        }
+        println!(
+            "   {}",
+            style("Run 'tdt cmp should-cost <component>' for the breakdown").dim()
+        );
+    }
 
     // Show warnings about expired quotes
     if !expired_quote_warnings.is_empty() && args.warn_expired {
@@ -1819,6 +2284,21 @@ fn run_cost(args: CostArgs) -> Result<()> {
     Ok(())
 }
 
+/// Print exchange-rate problems met during a cost rollup
+pub(crate) fn print_rate_warnings(converter: &Converter) {
+    if converter.issues().is_empty() {
+        return;
+    }
+    println!();
+    for issue in converter.issues() {
+        println!("{} {}", style("⚠ Exchange rate:").yellow().bold(), issue);
+    }
+    println!(
+        "   {}",
+        style("Update the rates under 'currency' in .tdt/config.yaml").dim()
+    );
+}
+
 /// Load all quotes from the project
 fn load_all_quotes(project: &Project) -> Vec<crate::entities::quote::Quote> {
     let mut quotes = Vec::new();
@@ -1842,7 +2322,7 @@ fn load_all_quotes(project: &Project) -> Vec<crate::entities::quote::Quote> {
     quotes
 }
 
-fn run_mass(args: MassArgs) -> Result<()> {
+fn run_mass(args: MassArgs, global: &GlobalOpts) -> Result<()> {
     let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
     let short_ids = ShortIdIndex::load(&project);
 
@@ -1856,66 +2336,35 @@ fn run_mass(args: MassArgs) -> Result<()> {
 
     // Load all components and assemblies for lookup
     let components = load_all_components(&project);
-    let component_map: std::collections::HashMap<String, &Component> =
-        components.iter().map(|c| (c.id.to_string(), c)).collect();
-
     let assemblies = load_all_assemblies(&project);
-    let assembly_map: std::collections::HashMap<String, &Assembly> =
-        assemblies.iter().map(|a| (a.id.to_string(), a)).collect();
 
-    // Calculate mass recursively
-    let mut breakdown: Vec<(String, String, u32, f64)> = Vec::new(); // (id, title, qty, mass)
-    let mut visited = std::collections::HashSet::new();
-    visited.insert(assembly.id.to_string());
+    // A budget on the command line overrides the assembly's own
+    let budget_override = args.budget.map(|max_kg| MassBudget {
+        max_kg,
+        requirement: None,
+    });
+    let rollup = mass_rollup(
+        &assembly,
+        &assemblies,
+        &components,
+        budget_override.as_ref(),
+    );
 
-    fn calculate_bom_mass(
-        bom: &[crate::entities::assembly::BomItem],
-        component_map: &std::collections::HashMap<String, &Component>,
-        assembly_map: &std::collections::HashMap<String, &Assembly>,
-        breakdown: &mut Vec<(String, String, u32, f64)>,
-        visited: &mut std::collections::HashSet<String>,
-    ) -> f64 {
-        let mut total = 0.0;
-        for item in bom {
-            let item_id = item.component_id.to_string();
-            if let Some(cmp) = component_map.get(&item_id) {
-                let line_mass = cmp.mass_kg.unwrap_or(0.0) * item.quantity as f64;
-                total += line_mass;
-                breakdown.push((item_id, cmp.title.clone(), item.quantity, line_mass));
-            } else if let Some(sub_asm) = assembly_map.get(&item_id) {
-                if !visited.contains(&item_id) {
-                    visited.insert(item_id.clone());
-                    let sub_mass = calculate_bom_mass(
-                        &sub_asm.bom,
-                        component_map,
-                        assembly_map,
-                        breakdown,
-                        visited,
-                    );
-                    let line_mass = sub_mass * item.quantity as f64;
-                    total += line_mass;
-                    breakdown.push((
-                        item_id.clone(),
-                        sub_asm.title.clone(),
-                        item.quantity,
-                        line_mass,
-                    ));
-                    visited.remove(&item_id);
-                }
-            }
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&rollup).into_diagnostic()?
+            );
+            return Ok(());
         }
-        total
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&rollup).into_diagnostic()?);
+            return Ok(());
+        }
+        _ => {}
     }
 
-    let total_mass = calculate_bom_mass(
-        &assembly.bom,
-        &component_map,
-        &assembly_map,
-        &mut breakdown,
-        &mut visited,
-    );
-
-    // Output
     println!(
         "{} {}",
         style("Assembly:").bold(),
@@ -1927,38 +2376,123 @@ fn run_mass(args: MassArgs) -> Result<()> {
         assembly.part_number
     );
 
-    if args.breakdown && !breakdown.is_empty() {
+    let short = |id: &str| {
+        short_ids
+            .get_short_id(id)
+            .unwrap_or_else(|| truncate_str(id, 10))
+    };
+    let fmt_cg = |cg: &[f64; 3]| format!("({:.1}, {:.1}, {:.1})", cg[0], cg[1], cg[2]);
+
+    if args.breakdown && !rollup.lines.is_empty() {
         println!(
-            "{:<12} {:<30} {:<6} {:<12}",
+            "{:<12} {:<30} {:<6} {:<12} {}",
             style("ID").bold(),
             style("TITLE").bold(),
             style("QTY").bold(),
-            style("MASS (kg)").bold()
-        );
-        println!("{}", "-".repeat(65));
-        for (id, title, qty, mass) in &breakdown {
-            let id_short = short_ids
-                .get_short_id(id)
-                .unwrap_or_else(|| truncate_str(id, 10));
-            if *mass > 0.0 {
-                println!(
-                    "{:<12} {:<30} {:<6} {:.3}",
-                    id_short,
-                    truncate_str(title, 28),
-                    qty,
-                    mass
-                );
+            style("MASS (kg)").bold(),
+            if rollup.has_mass_properties {
+                style("CG (mm)").bold().to_string()
+            } else {
+                String::new()
             }
+        );
+        println!(
+            "{}",
+            "-".repeat(if rollup.has_mass_properties { 90 } else { 65 })
+        );
+        for line in &rollup.lines {
+            let title = format!("{}{}", "  ".repeat(line.depth), line.title);
+            println!(
+                "{:<12} {:<30} {:<6} {:<12.3} {}",
+                short(&line.id),
+                truncate_str(&title, 28),
+                line.quantity,
+                line.mass_kg,
+                match line.cg_mm {
+                    Some(ref cg) if rollup.has_mass_properties => fmt_cg(cg),
+                    _ => String::new(),
+                }
+            );
         }
-        println!("{}", "-".repeat(65));
+        println!(
+            "{}",
+            "-".repeat(if rollup.has_mass_properties { 90 } else { 65 })
+        );
     }
 
     println!(
         "{} {:.3} kg",
         style("Total Mass:").green().bold(),
-        total_mass
+        rollup.mass_kg
     );
 
+    if rollup.has_mass_properties {
+        println!(
+            "{} {} mm",
+            style("Center of Gravity:").bold(),
+            fmt_cg(&rollup.cg_mm)
+        );
+        for (label, inertia) in [
+            ("Inertia about CG", &rollup.inertia_cg),
+            ("Inertia about origin", &rollup.inertia_origin),
+        ] {
+            println!("{} (kg·mm²):", style(label).bold());
+            println!(
+                "  Ixx {:.1}  Iyy {:.1}  Izz {:.1}",
+                inertia.ixx, inertia.iyy, inertia.izz
+            );
+            println!(
+                "  Ixy {:.1}  Ixz {:.1}  Iyz {:.1}",
+                inertia.ixy, inertia.ixz, inertia.iyz
+            );
+        }
+    }
+
+    if let Some(ref budget) = rollup.budget {
+        let source = budget
+            .requirement
+            .as_ref()
+            .map(|r| format!(" ({})", short(r)))
+            .unwrap_or_default();
+        let margin = format!(
+            "margin {:.3} kg ({:.1}%)",
+            budget.margin_kg, budget.margin_percent
+        );
+        println!(
+            "{} {:.3} kg{} - {}",
+            style("Mass Budget:").bold(),
+            budget.max_kg,
+            source,
+            if budget.within_budget {
+                style(format!("within budget, {}", margin)).green()
+            } else {
+                style(format!("OVER BUDGET, {}", margin)).red()
+            }
+        );
+    }
+
+    // CG and inertia gaps only matter once some component declares them
+    let warnings: Vec<_> = rollup
+        .warnings
+        .iter()
+        .filter(|w| {
+            rollup.has_mass_properties
+                || matches!(w.issue, MassIssue::NoMass | MassIssue::PlacementCount)
+        })
+        .collect();
+    if !warnings.is_empty() {
+        println!();
+        println!("{} {} warning(s):", style("!").yellow(), warnings.len());
+        for warning in warnings {
+            println!(
+                "  {} {} - {}",
+                short(&warning.id),
+                warning.part_number,
+                warning.issue
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -2335,6 +2869,276 @@ fn run_routing_list(args: RoutingListArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_routing_analyze(args: RoutingAnalyzeArgs, global: &GlobalOpts) -> Result<()> {
+    use crate::entities::process::Process;
+
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+
+    let asm_id = short_ids
+        .resolve(&args.asm)
+        .unwrap_or_else(|| args.asm.clone());
+    let (assembly, _path) = find_assembly_file(&project, &asm_id)?;
+
+    let routing = assembly
+        .manufacturing
+        .as_ref()
+        .map(|m| m.routing.clone())
+        .unwrap_or_default();
+    if routing.is_empty() {
+        return Err(miette::miette!(
+            "No routing configured for assembly {}",
+            args.asm
+        ));
+    }
+
+    let all_procs: Vec<Process> =
+        crate::core::loader::load_all(&project.root().join("manufacturing/processes"))?;
+    let mut procs: Vec<&Process> = Vec::new();
+    for proc_id in &routing {
+        match all_procs.iter().find(|p| p.id.to_string() == *proc_id) {
+            Some(p) => procs.push(p),
+            None => eprintln!(
+                "{} Warning: routing process {} not found, skipping",
+                style("!").yellow(),
+                proc_id
+            ),
+        }
+    }
+
+    let shift = ShiftPattern {
+        shifts_per_day: args.shifts,
+        hours_per_shift: args.shift_hours,
+        break_minutes: args.break_minutes,
+        days_per_week: args.days,
+    };
+    let demand = args.demand.map(|units| Demand {
+        units,
+        period: args.per.into(),
+    });
+    let analysis = analyze_routing(&procs, args.batch, shift, demand);
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&analysis).into_diagnostic()?
+            );
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&analysis).into_diagnostic()?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("position,process,operation,title,cycle_minutes,setup_minutes,unit_minutes,takt_utilization,stations_needed");
+            for (i, st) in analysis.stations.iter().enumerate() {
+                println!(
+                    "{},{},{},{},{:.2},{:.2},{:.2},{},{}",
+                    i + 1,
+                    st.process,
+                    escape_csv(st.operation_number.as_deref().unwrap_or("")),
+                    escape_csv(&st.title),
+                    st.cycle_minutes,
+                    st.setup_minutes,
+                    st.unit_minutes,
+                    st.takt_utilization
+                        .map(|u| format!("{:.3}", u))
+                        .unwrap_or_default(),
+                    st.stations_needed
+                        .map(|n| n.to_string())
+                        .unwrap_or_default()
+                );
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    println!(
+        "{} for {} ({})",
+        style("Routing Analysis").bold(),
+        style(&assembly.part_number).yellow(),
+        style(&assembly.title).dim()
+    );
+    println!(
+        "   Batch size {} | {} shift(s) x {}h, {} min breaks | {} min available/day",
+        args.batch.max(1),
+        shift.shifts_per_day,
+        shift.hours_per_shift,
+        shift.break_minutes,
+        shift.minutes_per_day()
+    );
+    println!();
+
+    // Station load chart, scaled to the larger of bottleneck and takt
+    const BAR_WIDTH: usize = 40;
+    let scale = analysis
+        .bottleneck_minutes
+        .max(analysis.takt_minutes.unwrap_or(0.0));
+    let takt_col = analysis
+        .takt_minutes
+        .filter(|_| scale > 0.0)
+        .map(|t| ((t / scale) * BAR_WIDTH as f64).round() as usize);
+
+    println!(
+        "{:<4} {:<8} {:<24} {:>7} {:>7} {:>8}  {}",
+        style("#").bold(),
+        style("OP").bold(),
+        style("PROCESS").bold(),
+        style("CYCLE").bold(),
+        style("SETUP").bold(),
+        style("MIN/UNIT").bold(),
+        style("STATION LOAD").bold()
+    );
+    for (i, st) in analysis.stations.iter().enumerate() {
+        let filled = if scale > 0.0 {
+            ((st.unit_minutes / scale) * BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        let mut bar = String::new();
+        for col in 0..=BAR_WIDTH {
+            if Some(col) == takt_col {
+                bar.push('|');
+            } else if col < filled {
+                bar.push('█');
+            } else {
+                bar.push(' ');
+            }
+        }
+        let is_bottleneck = analysis.bottleneck == Some(i);
+        let over_takt = st.takt_utilization.is_some_and(|u| u > 1.0);
+        let bar = if over_takt {
+            style(bar).red()
+        } else if is_bottleneck {
+            style(bar).yellow()
+        } else {
+            style(bar).green()
+        };
+        println!(
+            "{:<4} {:<8} {:<24} {:>7.2} {:>7.2} {:>8.2}  {}{}",
+            i + 1,
+            truncate_str(st.operation_number.as_deref().unwrap_or("-"), 8),
+            truncate_str(&st.title, 24),
+            st.cycle_minutes,
+            st.setup_minutes,
+            st.unit_minutes,
+            bar,
+            if st.missing_cycle_time {
+                format!(" {}", style("(no cycle time)").dim())
+            } else if is_bottleneck {
+                format!(" {}", style("bottleneck").yellow())
+            } else {
+                String::new()
+            }
+        );
+    }
+    if let Some(col) = takt_col {
+        // Bar starts after the 65 characters of fixed columns
+        println!("{}{}", " ".repeat(65 + col), style("^ takt").dim());
+    }
+
+    println!();
+    println!(
+        "Labor content:   {:.2} min/unit ({:.2} with setup over batch of {})",
+        analysis.labor_content_minutes, analysis.unit_minutes, analysis.batch_size
+    );
+    if let Some(b) = analysis.bottleneck {
+        let st = &analysis.stations[b];
+        println!(
+            "Bottleneck:      {}{} ({:.2} min/unit)",
+            st.operation_number
+                .as_ref()
+                .map(|op| format!("{} ", op))
+                .unwrap_or_default(),
+            style(&st.title).yellow(),
+            st.unit_minutes
+        );
+        println!(
+            "Line efficiency: {:.1}% (balance delay {:.1}%)",
+            analysis.line_efficiency * 100.0,
+            analysis.balance_delay * 100.0
+        );
+        println!(
+            "Capacity:        {:.1} units/day, {:.1} units/week",
+            analysis.capacity_per_day,
+            analysis.capacity_per_day * shift.days_per_week as f64
+        );
+    }
+    if let (Some(takt), Some(demand)) = (analysis.takt_minutes, analysis.demand) {
+        println!(
+            "Takt time:       {:.2} min/unit for {} units/{}",
+            takt, demand.units, demand.period
+        );
+        if let Some(min) = analysis.min_stations {
+            println!(
+                "Min. stations:   {} (routing has {})",
+                min,
+                analysis.stations.len()
+            );
+        }
+        match analysis.meets_takt {
+            Some(true) => println!("{} Line meets takt", style("✓").green()),
+            _ => {
+                println!(
+                    "{} Bottleneck exceeds takt by {:.2} min/unit",
+                    style("✗").red(),
+                    analysis.bottleneck_minutes - takt
+                );
+                for st in analysis
+                    .stations
+                    .iter()
+                    .filter(|s| s.stations_needed.is_some_and(|n| n > 1))
+                {
+                    println!(
+                        "   {} needs {} parallel stations",
+                        st.title,
+                        st.stations_needed.unwrap_or(1)
+                    );
+                }
+            }
+        }
+    }
+
+    if analysis.setup_minutes > 0.0 {
+        println!();
+        println!("{}", style("Setup Amortization").bold());
+        println!(
+            "{:>8} {:>12} {:>12}",
+            style("BATCH").bold(),
+            style("MIN/UNIT").bold(),
+            style("BOTTLENECK").bold()
+        );
+        for row in &analysis.amortization {
+            let line = format!(
+                "{:>8} {:>12.2} {:>12.2}",
+                row.batch_size, row.unit_minutes, row.bottleneck_minutes
+            );
+            if row.batch_size == analysis.batch_size {
+                println!("{}", style(line).cyan());
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+
+    let missing = analysis
+        .stations
+        .iter()
+        .filter(|s| s.missing_cycle_time)
+        .count();
+    if missing > 0 {
+        eprintln!(
+            "{} Warning: {} process(es) have no cycle_time_minutes; counted as 0",
+            style("!").yellow(),
+            missing
+        );
+    }
+
+    Ok(())
+}
+
 fn run_routing_set(args: RoutingSetArgs) -> Result<()> {
     use crate::entities::assembly::ManufacturingConfig;
 
@@ -0,0 +1,1349 @@
+//! `tdt audit` command - Internal / supplier audit management
+
+use clap::{Subcommand, ValueEnum};
+use console::style;
+use miette::{IntoDiagnostic, Result};
+use std::fs;
+
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::audit_schedule::{self, ScheduleState};
+use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
+use crate::core::project::Project;
+use crate::core::shortid::ShortIdIndex;
+use crate::core::Config;
+use crate::entities::audit::{Audit, AuditFinding, AuditStatus, AuditType, FindingSeverity};
+use crate::entities::capa::{Capa, CapaType, Source, SourceType};
+use crate::entities::ncr::{Ncr, NcrCategory, NcrSeverity, NcrType};
+use crate::schema::template::{TemplateContext, TemplateGenerator};
+use crate::schema::wizard::SchemaWizard;
+
+/// CLI-friendly audit type enum
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliAuditType {
+    Internal,
+    Supplier,
+    External,
+}
+
+impl From<CliAuditType> for AuditType {
+    fn from(cli: CliAuditType) -> Self {
+        match cli {
+            CliAuditType::Internal => AuditType::Internal,
+            CliAuditType::Supplier => AuditType::Supplier,
+            CliAuditType::External => AuditType::External,
+        }
+    }
+}
+
+/// CLI-friendly finding severity enum
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliFindingSeverity {
+    Major,
+    Minor,
+    Observation,
+    /// Opportunity for improvement
+    Opportunity,
+}
+
+impl From<CliFindingSeverity> for FindingSeverity {
+    fn from(cli: CliFindingSeverity) -> Self {
+        match cli {
+            CliFindingSeverity::Major => FindingSeverity::Major,
+            CliFindingSeverity::Minor => FindingSeverity::Minor,
+            CliFindingSeverity::Observation => FindingSeverity::Observation,
+            CliFindingSeverity::Opportunity => FindingSeverity::Opportunity,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// List audits with filtering
+    List(ListArgs),
+
+    /// Create a new audit
+    New(NewArgs),
+
+    /// Show an audit's details
+    Show(ShowArgs),
+
+    /// Edit an audit in your editor
+    Edit(EditArgs),
+
+    /// Delete an audit
+    Delete(DeleteArgs),
+
+    /// Archive an audit (soft delete)
+    Archive(ArchiveArgs),
+
+    /// Record a finding on an audit
+    Finding(FindingArgs),
+
+    /// Promote a finding into an NCR or CAPA
+    Promote(PromoteArgs),
+
+    /// Mark an audit completed (optionally planning the next one)
+    Complete(CompleteArgs),
+
+    /// Show upcoming and overdue audits
+    Schedule(ScheduleArgs),
+}
+
+/// Audit status filter
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AuditStatusFilter {
+    Planned,
+    InProgress,
+    Completed,
+    Cancelled,
+    /// Planned or in progress
+    Open,
+    All,
+}
+
+/// List column for display and sorting
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListColumn {
+    #[value(name = "id")]
+    Id,
+    #[value(name = "title")]
+    Title,
+    #[value(name = "type")]
+    Type,
+    #[value(name = "standard")]
+    Standard,
+    #[value(name = "scheduled")]
+    Scheduled,
+    #[value(name = "lead")]
+    Lead,
+    #[value(name = "findings")]
+    Findings,
+    #[value(name = "audit-status")]
+    AuditStatus,
+    #[value(name = "author")]
+    Author,
+    #[value(name = "created")]
+    Created,
+}
+
+impl std::fmt::Display for ListColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListColumn::Id => write!(f, "id"),
+            ListColumn::Title => write!(f, "title"),
+            ListColumn::Type => write!(f, "type"),
+            ListColumn::Standard => write!(f, "standard"),
+            ListColumn::Scheduled => write!(f, "scheduled"),
+            ListColumn::Lead => write!(f, "lead"),
+            ListColumn::Findings => write!(f, "findings"),
+            ListColumn::AuditStatus => write!(f, "audit-status"),
+            ListColumn::Author => write!(f, "author"),
+            ListColumn::Created => write!(f, "created"),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ListArgs {
+    /// Filter by audit status
+    #[arg(long, short = 's', default_value = "all")]
+    pub status: AuditStatusFilter,
+
+    /// Filter by audit type
+    #[arg(long, short = 'T')]
+    pub r#type: Option<CliAuditType>,
+
+    /// Show only audits with findings not yet promoted to an NCR or CAPA
+    #[arg(long)]
+    pub open_findings: bool,
+
+    /// Search in title, audit number and standard
+    #[arg(long)]
+    pub search: Option<String>,
+
+    /// Columns to display
+    #[arg(long, value_delimiter = ',', default_values_t = vec![
+        ListColumn::Id,
+        ListColumn::Title,
+        ListColumn::Type,
+        ListColumn::Scheduled,
+        ListColumn::Findings,
+        ListColumn::AuditStatus
+    ])]
+    pub columns: Vec<ListColumn>,
+
+    /// Sort by column
+    #[arg(long, default_value = "scheduled")]
+    pub sort: ListColumn,
+
+    /// Reverse sort order
+    #[arg(long, short = 'r')]
+    pub reverse: bool,
+
+    /// Limit number of results
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Show only count
+    #[arg(long)]
+    pub count: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NewArgs {
+    /// Audit title (required)
+    #[arg(long, short = 't')]
+    pub title: Option<String>,
+
+    /// Audit type
+    #[arg(long, short = 'T', default_value = "internal")]
+    pub r#type: CliAuditType,
+
+    /// Standard audited against (e.g., "ISO 13485:2016")
+    #[arg(long)]
+    pub standard: Option<String>,
+
+    /// Scheduled date (YYYY-MM-DD)
+    #[arg(long, short = 'd')]
+    pub date: Option<chrono::NaiveDate>,
+
+    /// Open in editor after creation
+    #[arg(long, short = 'e')]
+    pub edit: bool,
+
+    /// Skip opening in editor
+    #[arg(long)]
+    pub no_edit: bool,
+
+    /// Interactive mode (prompt for fields)
+    #[arg(long, short = 'i')]
+    pub interactive: bool,
+
+    /// Link to another entity (auto-infers link type)
+    #[arg(long, short = 'L')]
+    pub link: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ShowArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct EditArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DeleteArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+
+    /// Force deletion even if entity is linked
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ArchiveArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+
+    /// Force archival even if entity is linked
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FindingArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+
+    /// Statement of the finding
+    #[arg(long, short = 'd')]
+    pub description: String,
+
+    /// Finding severity
+    #[arg(long, short = 's', default_value = "minor")]
+    pub severity: CliFindingSeverity,
+
+    /// Standard clause the finding is raised against (e.g., "7.5.1")
+    #[arg(long, short = 'c')]
+    pub clause: Option<String>,
+
+    /// Objective evidence
+    #[arg(long)]
+    pub evidence: Option<String>,
+}
+
+/// Entity a finding is promoted to
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PromoteTarget {
+    Ncr,
+    Capa,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct PromoteArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+
+    /// Finding number (e.g., F1)
+    pub finding: String,
+
+    /// Create an NCR or a CAPA
+    #[arg(long, default_value = "capa")]
+    pub to: PromoteTarget,
+
+    /// Create a preventive rather than corrective CAPA
+    #[arg(long)]
+    pub preventive: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CompleteArgs {
+    /// Audit ID (full or short)
+    pub id: String,
+
+    /// Completion date (YYYY-MM-DD, default: today)
+    #[arg(long)]
+    pub date: Option<chrono::NaiveDate>,
+
+    /// Summary of the audit outcome
+    #[arg(long)]
+    pub summary: Option<String>,
+
+    /// Plan the next audit of this scope (requires interval_months)
+    #[arg(long)]
+    pub schedule_next: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ScheduleArgs {
+    /// Show audits due within this many days (overdue audits are always shown)
+    #[arg(long, default_value = "90")]
+    pub days: i64,
+
+    /// Show all scheduled audits regardless of due date
+    #[arg(long)]
+    pub all: bool,
+
+    /// Evaluate the schedule as of this date (YYYY-MM-DD, default: today)
+    #[arg(long)]
+    pub as_of: Option<chrono::NaiveDate>,
+}
+
+/// Directories where audits are stored
+const AUDIT_DIRS: &[&str] = &["quality/audits"];
+
+/// Entity configuration for audit commands
+const ENTITY_CONFIG: crate::cli::EntityConfig = crate::cli::EntityConfig {
+    prefix: EntityPrefix::Aud,
+    dirs: AUDIT_DIRS,
+    name: "audit",
+    name_plural: "audits",
+};
+
+/// Run an audit command
+pub fn run(cmd: AuditCommands, global: &GlobalOpts) -> Result<()> {
+    match cmd {
+        AuditCommands::List(args) => run_list(args, global),
+        AuditCommands::New(args) => run_new(args, global),
+        AuditCommands::Show(args) => run_show(args, global),
+        AuditCommands::Edit(args) => run_edit(args),
+        AuditCommands::Delete(args) => run_delete(args),
+        AuditCommands::Archive(args) => run_archive(args),
+        AuditCommands::Finding(args) => run_finding(args, global),
+        AuditCommands::Promote(args) => run_promote(args, global),
+        AuditCommands::Complete(args) => run_complete(args, global),
+        AuditCommands::Schedule(args) => run_schedule(args, global),
+    }
+}
+
+/// Finding counts as "major/minor/other"
+fn findings_label(audit: &Audit) -> String {
+    if audit.findings.is_empty() {
+        return "-".to_string();
+    }
+    let other = audit.count_findings(FindingSeverity::Observation)
+        + audit.count_findings(FindingSeverity::Opportunity);
+    format!(
+        "{}/{}/{}",
+        audit.count_findings(FindingSeverity::Major),
+        audit.count_findings(FindingSeverity::Minor),
+        other
+    )
+}
+
+/// Load an audit by full or short ID
+fn load_audit(
+    project: &Project,
+    short_ids: &ShortIdIndex,
+    id: &str,
+) -> Result<(std::path::PathBuf, Audit)> {
+    let resolved_id = short_ids.resolve(id).unwrap_or_else(|| id.to_string());
+    let audit_dir = project.root().join("quality/audits");
+    loader::load_entity::<Audit>(&audit_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No audit found matching '{}'", id))
+}
+
+/// List audits
+fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let audit_dir = project.root().join("quality/audits");
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let audits: Vec<Audit> = loader::load_all(&audit_dir)?;
+
+    let mut audits: Vec<Audit> = audits
+        .into_iter()
+        .filter(|a| match args.status {
+            AuditStatusFilter::Planned => a.audit_status == AuditStatus::Planned,
+            AuditStatusFilter::InProgress => a.audit_status == AuditStatus::InProgress,
+            AuditStatusFilter::Completed => a.audit_status == AuditStatus::Completed,
+            AuditStatusFilter::Cancelled => a.audit_status == AuditStatus::Cancelled,
+            AuditStatusFilter::Open => a.is_open(),
+            AuditStatusFilter::All => true,
+        })
+        .filter(|a| {
+            args.r#type
+                .map(|t| a.audit_type == AuditType::from(t))
+                .unwrap_or(true)
+        })
+        .filter(|a| !args.open_findings || a.findings.iter().any(|f| f.needs_action()))
+        .filter(|a| {
+            args.search
+                .as_ref()
+                .map(|s| {
+                    let search = s.to_lowercase();
+                    a.title.to_lowercase().contains(&search)
+                        || [&a.audit_number, &a.standard].iter().any(|n| {
+                            n.as_ref()
+                                .is_some_and(|n| n.to_lowercase().contains(&search))
+                        })
+                })
+                .unwrap_or(true)
+        })
+        .collect();
+
+    // Sort
+    audits.sort_by(|a, b| match args.sort {
+        ListColumn::Id => a.id.to_string().cmp(&b.id.to_string()),
+        ListColumn::Title => a.title.cmp(&b.title),
+        ListColumn::Type => a.audit_type.to_string().cmp(&b.audit_type.to_string()),
+        ListColumn::Standard => a.standard.cmp(&b.standard),
+        ListColumn::Scheduled => a.scheduled_date.cmp(&b.scheduled_date),
+        ListColumn::Lead => a.lead_auditor.cmp(&b.lead_auditor),
+        ListColumn::Findings => a.findings.len().cmp(&b.findings.len()),
+        ListColumn::AuditStatus => a.audit_status.to_string().cmp(&b.audit_status.to_string()),
+        ListColumn::Author => a.author.cmp(&b.author),
+        ListColumn::Created => a.created.cmp(&b.created),
+    });
+
+    if args.reverse {
+        audits.reverse();
+    }
+
+    if let Some(limit) = args.limit {
+        audits.truncate(limit);
+    }
+
+    if args.count {
+        println!("{}", audits.len());
+        return Ok(());
+    }
+
+    if audits.is_empty() {
+        println!("No audits found.");
+        return Ok(());
+    }
+
+    // Update short ID index
+    let mut short_ids = ShortIdIndex::load(&project);
+    short_ids.ensure_all(audits.iter().map(|a| a.id.to_string()));
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    let column_value = |audit: &Audit, col: &ListColumn, short_id: &str| -> String {
+        match col {
+            ListColumn::Id => short_id.to_string(),
+            ListColumn::Title => audit.title.clone(),
+            ListColumn::Type => audit.audit_type.to_string(),
+            ListColumn::Standard => audit.standard.clone().unwrap_or_default(),
+            ListColumn::Scheduled => audit
+                .scheduled_date
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            ListColumn::Lead => audit.lead_auditor.clone().unwrap_or_default(),
+            ListColumn::Findings => findings_label(audit),
+            ListColumn::AuditStatus => audit.audit_status.to_string(),
+            ListColumn::Author => audit.author.clone(),
+            ListColumn::Created => audit.created.format("%Y-%m-%d").to_string(),
+        }
+    };
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&audits).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yml::to_string(&audits).into_diagnostic()?;
+            print!("{}", yaml);
+        }
+        OutputFormat::Csv => {
+            println!("short_id,id,title,type,standard,scheduled,lead,findings,audit_status,author");
+            for audit in &audits {
+                let short_id = short_ids
+                    .get_short_id(&audit.id.to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    short_id,
+                    audit.id,
+                    escape_csv(&audit.title),
+                    audit.audit_type,
+                    escape_csv(audit.standard.as_deref().unwrap_or("")),
+                    column_value(audit, &ListColumn::Scheduled, &short_id),
+                    escape_csv(audit.lead_auditor.as_deref().unwrap_or("")),
+                    audit.findings.len(),
+                    audit.audit_status,
+                    escape_csv(&audit.author)
+                );
+            }
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            let mut headers = vec![];
+            let mut widths = vec![];
+
+            for col in &args.columns {
+                let (header, width) = match col {
+                    ListColumn::Id => ("ID", 17),
+                    ListColumn::Title => ("TITLE", 30),
+                    ListColumn::Type => ("TYPE", 10),
+                    ListColumn::Standard => ("STANDARD", 18),
+                    ListColumn::Scheduled => ("SCHEDULED", 12),
+                    ListColumn::Lead => ("LEAD", 16),
+                    ListColumn::Findings => ("MAJ/MIN/OBS", 12),
+                    ListColumn::AuditStatus => ("STATUS", 12),
+                    ListColumn::Author => ("AUTHOR", 16),
+                    ListColumn::Created => ("CREATED", 12),
+                };
+                headers.push((header, *col));
+                widths.push(width);
+            }
+
+            print!("{:<8} ", style("SHORT").bold().dim());
+            for (i, (header, _)) in headers.iter().enumerate() {
+                print!("{:<width$} ", style(header).bold(), width = widths[i]);
+            }
+            println!();
+
+            for audit in &audits {
+                let short_id = short_ids
+                    .get_short_id(&audit.id.to_string())
+                    .unwrap_or_default();
+
+                print!("{:<8} ", style(&short_id).cyan());
+
+                for (i, (_, col)) in headers.iter().enumerate() {
+                    let value = match col {
+                        ListColumn::Title => truncate_str(&audit.title, widths[i]),
+                        ListColumn::Id => audit.id.to_string(),
+                        _ => column_value(audit, col, &short_id),
+                    };
+                    print!("{:<width$} ", value, width = widths[i]);
+                }
+                println!();
+            }
+        }
+        OutputFormat::Md => {
+            let headers: Vec<&str> = args
+                .columns
+                .iter()
+                .map(|c| match c {
+                    ListColumn::Id => "ID",
+                    ListColumn::Title => "Title",
+                    ListColumn::Type => "Type",
+                    ListColumn::Standard => "Standard",
+                    ListColumn::Scheduled => "Scheduled",
+                    ListColumn::Lead => "Lead",
+                    ListColumn::Findings => "Maj/Min/Obs",
+                    ListColumn::AuditStatus => "Status",
+                    ListColumn::Author => "Author",
+                    ListColumn::Created => "Created",
+                })
+                .collect();
+            println!("| {} |", headers.join(" | "));
+            println!(
+                "| {} |",
+                headers
+                    .iter()
+                    .map(|_| "---")
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            );
+
+            for audit in &audits {
+                let short_id = short_ids
+                    .get_short_id(&audit.id.to_string())
+                    .unwrap_or_default();
+                let values: Vec<String> = args
+                    .columns
+                    .iter()
+                    .map(|c| match c {
+                        ListColumn::Title => truncate_str(&audit.title, 40),
+                        _ => column_value(audit, c, &short_id),
+                    })
+                    .collect();
+                println!("| {} |", values.join(" | "));
+            }
+        }
+        OutputFormat::Id => {
+            for audit in &audits {
+                println!("{}", audit.id);
+            }
+        }
+        OutputFormat::ShortId => {
+            for audit in &audits {
+                let short_id = short_ids
+                    .get_short_id(&audit.id.to_string())
+                    .unwrap_or_default();
+                println!("{}", short_id);
+            }
+        }
+        OutputFormat::Path => {
+            for audit in &audits {
+                let path = audit_dir.join(format!("{}.tdt.yaml", audit.id));
+                println!("{}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a new audit
+fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+
+    let title: String;
+    let audit_type: AuditType;
+    let standard: Option<String>;
+
+    if args.interactive {
+        let wizard = SchemaWizard::new();
+        let result = wizard.run(EntityPrefix::Aud)?;
+
+        title = result
+            .get_string("title")
+            .map(String::from)
+            .unwrap_or_else(|| "New Audit".to_string());
+        audit_type = result
+            .get_string("audit_type")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default();
+        standard = result.get_string("standard").map(String::from);
+    } else {
+        title = args.title.unwrap_or_else(|| "New Audit".to_string());
+        audit_type = AuditType::from(args.r#type);
+        standard = args.standard;
+    }
+
+    let id = EntityId::new(EntityPrefix::Aud);
+
+    let generator = TemplateGenerator::new().map_err(|e| miette::miette!("{}", e))?;
+    let mut ctx = TemplateContext::new(id.clone(), config.author())
+        .with_title(&title)
+        .with_audit_type(audit_type.to_string());
+
+    if let Some(ref standard) = standard {
+        ctx = ctx.with_standard(standard);
+    }
+    if let Some(date) = args.date {
+        ctx = ctx.with_scheduled_date(date.to_string());
+    }
+
+    let yaml_content = generator
+        .generate_audit(&ctx)
+        .map_err(|e| miette::miette!("{}", e))?;
+
+    let output_dir = project.root().join("quality/audits");
+    if !output_dir.exists() {
+        fs::create_dir_all(&output_dir).into_diagnostic()?;
+    }
+
+    let file_path = output_dir.join(format!("{}.tdt.yaml", id));
+    fs::write(&file_path, &yaml_content).into_diagnostic()?;
+
+    let mut short_ids = ShortIdIndex::load(&project);
+    let short_id = short_ids.add(id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    // Handle --link flags
+    let _added_links = crate::cli::entity_cmd::process_link_flags(
+        &file_path,
+        EntityPrefix::Aud,
+        &args.link,
+        &short_ids,
+    );
+
+    if !global.quiet {
+        let id_str = id.to_string();
+        let display_id = short_id.as_deref().unwrap_or(&id_str);
+        println!(
+            "{} Created audit {}",
+            style("✓").green(),
+            style(display_id).cyan()
+        );
+        println!("  {}", file_path.display());
+    }
+
+    if args.edit && !args.no_edit {
+        println!(
+            "Opening {} in {}...",
+            style(file_path.display()).cyan(),
+            style(config.editor()).yellow()
+        );
+        config.run_editor(&file_path).into_diagnostic()?;
+    }
+
+    Ok(())
+}
+
+/// Show audit details
+fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, audit) = load_audit(&project, &short_ids, &args.id)?;
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let short_id = short_ids
+        .get_short_id(&audit.id.to_string())
+        .unwrap_or_default();
+    let display = |id: &EntityId| {
+        short_ids
+            .get_short_id(&id.to_string())
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&audit).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let content = fs::read_to_string(&path).into_diagnostic()?;
+            println!("{}", content);
+        }
+        OutputFormat::Csv => {
+            println!(
+                "id,title,type,standard,scheduled,completed,findings,audit_status,author,created"
+            );
+            println!(
+                "{},{},{},{},{},{},{},{},{},{}",
+                escape_csv(&short_id),
+                escape_csv(&audit.title),
+                audit.audit_type,
+                escape_csv(audit.standard.as_deref().unwrap_or("")),
+                audit
+                    .scheduled_date
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                audit
+                    .completed_date
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                audit.findings.len(),
+                audit.audit_status,
+                escape_csv(&audit.author),
+                audit.created.format("%Y-%m-%d")
+            );
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Md
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            let today = chrono::Local::now().date_naive();
+
+            println!("{}", style("Audit").bold());
+            println!("{}", style("─".repeat(60)).dim());
+            println!("  {} {}", style("ID:").dim(), style(&short_id).cyan());
+            println!("  {} {}", style("Title:").dim(), audit.title);
+            if let Some(ref num) = audit.audit_number {
+                println!("  {} {}", style("Number:").dim(), num);
+            }
+            println!("  {} {}", style("Type:").dim(), audit.audit_type);
+            if let Some(ref standard) = audit.standard {
+                println!("  {} {}", style("Standard:").dim(), standard);
+            }
+            if !audit.clauses.is_empty() {
+                println!("  {} {}", style("Clauses:").dim(), audit.clauses.join(", "));
+            }
+            if let Some(date) = audit.scheduled_date {
+                if audit.is_overdue(today) {
+                    println!(
+                        "  {} {}",
+                        style("Scheduled:").dim(),
+                        style(format!("{} (overdue)", date)).red()
+                    );
+                } else {
+                    println!("  {} {}", style("Scheduled:").dim(), date);
+                }
+            }
+            if let Some(date) = audit.completed_date {
+                println!("  {} {}", style("Completed:").dim(), date);
+            }
+            if let Some(next) = audit.next_due() {
+                println!("  {} {}", style("Next Due:").dim(), next);
+            }
+            if let Some(ref lead) = audit.lead_auditor {
+                println!("  {} {}", style("Lead Auditor:").dim(), lead);
+            }
+            if !audit.auditors.is_empty() {
+                println!(
+                    "  {} {}",
+                    style("Auditors:").dim(),
+                    audit.auditors.join(", ")
+                );
+            }
+            if !audit.auditees.is_empty() {
+                println!(
+                    "  {} {}",
+                    style("Auditees:").dim(),
+                    audit.auditees.join(", ")
+                );
+            }
+            println!("  {} {}", style("Status:").dim(), audit.audit_status);
+
+            if let Some(ref scope) = audit.scope {
+                if !scope.trim().is_empty() && !scope.trim_start().starts_with('#') {
+                    println!();
+                    println!("{}", style("Scope").bold());
+                    println!("{}", style("─".repeat(60)).dim());
+                    for line in scope.lines() {
+                        println!("  {}", line);
+                    }
+                }
+            }
+
+            if !audit.findings.is_empty() {
+                println!();
+                println!("{} ({})", style("Findings").bold(), audit.findings.len());
+                println!("{}", style("─".repeat(60)).dim());
+                for finding in &audit.findings {
+                    let severity = match finding.severity {
+                        FindingSeverity::Major => style(finding.severity.to_string()).red(),
+                        FindingSeverity::Minor => style(finding.severity.to_string()).yellow(),
+                        _ => style(finding.severity.to_string()).dim(),
+                    };
+                    let clause = finding
+                        .clause
+                        .as_ref()
+                        .map(|c| format!(" [{}]", c))
+                        .unwrap_or_default();
+                    println!(
+                        "  {} {}{}: {}",
+                        style(&finding.id).cyan(),
+                        severity,
+                        clause,
+                        finding.description
+                    );
+                    if let Some(ref evidence) = finding.evidence {
+                        println!("     {} {}", style("Evidence:").dim(), evidence);
+                    }
+                    let promoted: Vec<String> = finding
+                        .ncr
+                        .iter()
+                        .chain(finding.capa.iter())
+                        .map(&display)
+                        .collect();
+                    if !promoted.is_empty() {
+                        println!("     {} {}", style("→").blue(), promoted.join(", "));
+                    } else if finding.needs_action() {
+                        println!(
+                            "     {} not yet promoted to an NCR or CAPA",
+                            style("!").yellow()
+                        );
+                    }
+                }
+            }
+
+            let links = &audit.links;
+            let linked: Vec<(&str, Vec<String>)> = vec![
+                ("Supplier:", links.supplier.iter().map(&display).collect()),
+                ("Processes:", links.processes.iter().map(&display).collect()),
+                ("NCRs:", links.ncrs.iter().map(&display).collect()),
+                ("CAPAs:", links.capas.iter().map(&display).collect()),
+            ];
+            if linked.iter().any(|(_, ids)| !ids.is_empty()) {
+                println!();
+                println!("{}", style("Links").bold());
+                println!("{}", style("─".repeat(60)).dim());
+                for (label, ids) in linked.iter().filter(|(_, ids)| !ids.is_empty()) {
+                    println!("  {} {}", style(label).dim(), ids.join(", "));
+                }
+            }
+
+            if let Some(ref summary) = audit.summary {
+                println!();
+                println!("{}", style("Summary").bold());
+                println!("{}", style("─".repeat(60)).dim());
+                for line in summary.lines() {
+                    println!("  {}", line);
+                }
+            }
+
+            println!();
+            println!("{}", style("─".repeat(60)).dim());
+            println!(
+                "{}: {} | {}: {} | {}: {}",
+                style("Author").dim(),
+                audit.author,
+                style("Created").dim(),
+                audit.created.format("%Y-%m-%d %H:%M"),
+                style("Revision").dim(),
+                audit.entity_revision
+            );
+        }
+        OutputFormat::Id => {
+            println!("{}", audit.id);
+        }
+        OutputFormat::ShortId => {
+            println!("{}", short_id);
+        }
+        OutputFormat::Path => {
+            println!("{}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Edit an audit
+fn run_edit(args: EditArgs) -> Result<()> {
+    crate::cli::entity_cmd::run_edit_generic(&args.id, &ENTITY_CONFIG)
+}
+
+/// Delete an audit
+fn run_delete(args: DeleteArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, AUDIT_DIRS, args.force, false, args.quiet)
+}
+
+/// Archive an audit
+fn run_archive(args: ArchiveArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, AUDIT_DIRS, args.force, true, args.quiet)
+}
+
+/// Record a finding
+fn run_finding(args: FindingArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, mut audit) = load_audit(&project, &short_ids, &args.id)?;
+
+    let finding = AuditFinding {
+        id: audit.next_finding_id(),
+        severity: args.severity.into(),
+        clause: args.clause,
+        description: args.description,
+        evidence: args.evidence,
+        ncr: None,
+        capa: None,
+    };
+    let finding_id = finding.id.clone();
+    let severity = finding.severity;
+    audit.findings.push(finding);
+
+    // Findings are recorded while the audit is being performed
+    if audit.audit_status == AuditStatus::Planned {
+        audit.audit_status = AuditStatus::InProgress;
+    }
+    audit.entity_revision += 1;
+
+    fs::write(&path, serde_yml::to_string(&audit).into_diagnostic()?).into_diagnostic()?;
+
+    if !global.quiet {
+        let short_id = short_ids
+            .get_short_id(&audit.id.to_string())
+            .unwrap_or_default();
+        println!(
+            "{} Added {} finding {} to audit {}",
+            style("✓").green(),
+            severity,
+            style(&finding_id).cyan(),
+            style(&short_id).cyan()
+        );
+        if matches!(severity, FindingSeverity::Major | FindingSeverity::Minor) {
+            println!(
+                "  Promote with: tdt audit promote {} {} --to capa",
+                short_id, finding_id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Promote a finding into an NCR or CAPA
+fn run_promote(args: PromoteArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+    let (path, mut audit) = load_audit(&project, &short_ids, &args.id)?;
+    let audit_display = short_ids
+        .get_short_id(&audit.id.to_string())
+        .unwrap_or_else(|| audit.id.to_string());
+    let audit_id = audit.id.clone();
+    let audit_title = audit.title.clone();
+    let standard = audit.standard.clone();
+
+    let finding = audit.finding_mut(&args.finding).ok_or_else(|| {
+        miette::miette!("No finding '{}' on audit {}", args.finding, audit_display)
+    })?;
+
+    let against = match (&standard, &finding.clause) {
+        (Some(s), Some(c)) => format!(" against {} clause {}", s, c),
+        (None, Some(c)) => format!(" against clause {}", c),
+        (Some(s), None) => format!(" against {}", s),
+        (None, None) => String::new(),
+    };
+    let mut statement = format!(
+        "{} audit finding {}{} from {} \"{}\":\n{}",
+        finding.severity, finding.id, against, audit_id, audit_title, finding.description
+    );
+    if let Some(ref evidence) = finding.evidence {
+        statement.push_str(&format!("\n\nEvidence: {}", evidence));
+    }
+    let title = truncate_str(&finding.description, 120);
+
+    let created_id = match args.to {
+        PromoteTarget::Capa => {
+            if let Some(ref capa) = finding.capa {
+                return Err(miette::miette!(
+                    "Finding {} was already promoted to {}",
+                    finding.id,
+                    short_ids
+                        .get_short_id(&capa.to_string())
+                        .unwrap_or_else(|| capa.to_string())
+                ));
+            }
+            let capa_type = if args.preventive {
+                CapaType::Preventive
+            } else {
+                CapaType::Corrective
+            };
+            let mut capa = Capa::new(title, capa_type, config.author());
+            capa.source = Some(Source {
+                source_type: SourceType::Audit,
+                reference: Some(audit_id.to_string()),
+            });
+            capa.problem_statement = Some(statement);
+            if let Some(ref ncr) = finding.ncr {
+                capa.links.ncrs.push(ncr.clone());
+            }
+
+            let capa_dir = project.root().join("manufacturing/capas");
+            fs::create_dir_all(&capa_dir).into_diagnostic()?;
+            fs::write(
+                capa_dir.join(format!("{}.tdt.yaml", capa.id)),
+                serde_yml::to_string(&capa).into_diagnostic()?,
+            )
+            .into_diagnostic()?;
+
+            finding.capa = Some(capa.id.clone());
+            capa.id
+        }
+        PromoteTarget::Ncr => {
+            if let Some(ref ncr) = finding.ncr {
+                return Err(miette::miette!(
+                    "Finding {} was already promoted to {}",
+                    finding.id,
+                    short_ids
+                        .get_short_id(&ncr.to_string())
+                        .unwrap_or_else(|| ncr.to_string())
+                ));
+            }
+            let severity = match finding.severity {
+                FindingSeverity::Major => NcrSeverity::Major,
+                _ => NcrSeverity::Minor,
+            };
+            let mut ncr = Ncr::new(title, NcrType::Internal, severity, config.author());
+            ncr.category = NcrCategory::Process;
+            ncr.description = Some(statement);
+
+            let ncr_dir = project.root().join("manufacturing/ncrs");
+            fs::create_dir_all(&ncr_dir).into_diagnostic()?;
+            fs::write(
+                ncr_dir.join(format!("{}.tdt.yaml", ncr.id)),
+                serde_yml::to_string(&ncr).into_diagnostic()?,
+            )
+            .into_diagnostic()?;
+
+            finding.ncr = Some(ncr.id.clone());
+            ncr.id
+        }
+    };
+    let finding_id = finding.id.clone();
+
+    match args.to {
+        PromoteTarget::Capa => audit.links.capas.push(created_id.clone()),
+        PromoteTarget::Ncr => audit.links.ncrs.push(created_id.clone()),
+    }
+    audit.entity_revision += 1;
+    fs::write(&path, serde_yml::to_string(&audit).into_diagnostic()?).into_diagnostic()?;
+
+    let created_short = short_ids.add(created_id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+    let created_display = created_short.unwrap_or_else(|| created_id.to_string());
+
+    match global.output {
+        OutputFormat::Json => {
+            let result = serde_json::json!({
+                "audit": audit_id.to_string(),
+                "finding": finding_id,
+                "id": created_id.to_string(),
+                "short_id": created_display,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result).unwrap_or_default()
+            );
+        }
+        OutputFormat::Id => println!("{}", created_id),
+        OutputFormat::ShortId => println!("{}", created_display),
+        _ => {
+            if !global.quiet {
+                println!(
+                    "{} Created {} from audit {} finding {}",
+                    style("✓").green(),
+                    style(&created_display).cyan(),
+                    style(&audit_display).cyan(),
+                    finding_id
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Mark an audit completed
+fn run_complete(args: CompleteArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+    let (path, mut audit) = load_audit(&project, &short_ids, &args.id)?;
+    let short_id = short_ids
+        .get_short_id(&audit.id.to_string())
+        .unwrap_or_else(|| audit.id.to_string());
+
+    if !audit.is_open() {
+        return Err(miette::miette!(
+            "Audit {} is already {}",
+            short_id,
+            audit.audit_status
+        ));
+    }
+    if args.schedule_next && audit.interval_months.is_none() {
+        return Err(miette::miette!(
+            "Audit {} has no interval_months; set it with 'tdt audit edit {}' to plan the next audit",
+            short_id,
+            short_id
+        ));
+    }
+
+    audit.audit_status = AuditStatus::Completed;
+    audit.completed_date = Some(
+        args.date
+            .unwrap_or_else(|| chrono::Local::now().date_naive()),
+    );
+    if args.summary.is_some() {
+        audit.summary = args.summary;
+    }
+    audit.entity_revision += 1;
+    fs::write(&path, serde_yml::to_string(&audit).into_diagnostic()?).into_diagnostic()?;
+
+    // Plan the next audit of the same scope
+    let mut next_display = None;
+    if args.schedule_next {
+        let mut next = Audit::new(audit.title.clone(), audit.audit_type, config.author());
+        next.standard = audit.standard.clone();
+        next.clauses = audit.clauses.clone();
+        next.scope = audit.scope.clone();
+        next.scheduled_date = audit.next_due();
+        next.interval_months = audit.interval_months;
+        next.lead_auditor = audit.lead_auditor.clone();
+        next.auditors = audit.auditors.clone();
+        next.links.supplier = audit.links.supplier.clone();
+        next.links.processes = audit.links.processes.clone();
+        next.tags = audit.tags.clone();
+
+        let next_path = project
+            .root()
+            .join("quality/audits")
+            .join(format!("{}.tdt.yaml", next.id));
+        fs::write(&next_path, serde_yml::to_string(&next).into_diagnostic()?).into_diagnostic()?;
+        next_display = Some((
+            short_ids
+                .add(next.id.to_string())
+                .unwrap_or_else(|| next.id.to_string()),
+            next.scheduled_date,
+        ));
+        super::utils::save_short_ids(&mut short_ids, &project);
+    }
+
+    if !global.quiet {
+        println!(
+            "{} Completed audit {} ({} finding(s))",
+            style("✓").green(),
+            style(&short_id).cyan(),
+            audit.findings.len()
+        );
+        let unpromoted: Vec<&str> = audit
+            .findings
+            .iter()
+            .filter(|f| f.needs_action())
+            .map(|f| f.id.as_str())
+            .collect();
+        if !unpromoted.is_empty() {
+            println!(
+                "  {} Findings not yet promoted to an NCR or CAPA: {}",
+                style("!").yellow(),
+                unpromoted.join(", ")
+            );
+        }
+        match next_display {
+            Some((next_id, Some(date))) => println!(
+                "  Planned next audit {} for {}",
+                style(next_id).cyan(),
+                date
+            ),
+            _ => {
+                if let Some(next) = audit.next_due() {
+                    println!("  Next audit due {}", next);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Show upcoming and overdue audits
+fn run_schedule(args: ScheduleArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let audits: Vec<Audit> = loader::load_all(&project.root().join("quality/audits"))?;
+    let as_of = args
+        .as_of
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+    let horizon = (!args.all).then_some(args.days);
+
+    let entries = audit_schedule::schedule(&audits, as_of, horizon);
+
+    let mut short_ids = ShortIdIndex::load(&project);
+    short_ids.ensure_all(entries.iter().map(|e| e.audit.clone()));
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    match global.output {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&entries).into_diagnostic()?;
+            println!("{}", json);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yml::to_string(&entries).into_diagnostic()?;
+            print!("{}", yaml);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if entries.is_empty() {
+        if args.all {
+            println!("No scheduled audits.");
+        } else {
+            println!("No audits due in the next {} days.", args.days);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:<30} {:<10} {:<18} {:<12} {:>6}  {}",
+        style("SHORT").bold().dim(),
+        style("TITLE").bold(),
+        style("TYPE").bold(),
+        style("STANDARD").bold(),
+        style("DUE").bold(),
+        style("DAYS").bold(),
+        style("STATE").bold()
+    );
+    for entry in &entries {
+        let short_id = short_ids
+            .get_short_id(&entry.audit)
+            .unwrap_or_else(|| entry.audit.clone());
+        let label = if entry.recurrence {
+            format!("{} (next)", entry.state)
+        } else {
+            entry.state.to_string()
+        };
+        let state = match entry.state {
+            ScheduleState::Overdue => style(label).red().bold(),
+            ScheduleState::InProgress => style(label).cyan(),
+            ScheduleState::Upcoming => style(label).green(),
+        };
+        println!(
+            "{:<8} {:<30} {:<10} {:<18} {:<12} {:>6}  {}",
+            style(&short_id).cyan(),
+            truncate_str(&entry.title, 30),
+            entry.audit_type.to_string(),
+            truncate_str(entry.standard.as_deref().unwrap_or("-"), 18),
+            entry.due,
+            entry.days,
+            state
+        );
+    }
+
+    let overdue = entries
+        .iter()
+        .filter(|e| e.state == ScheduleState::Overdue)
+        .count();
+    if overdue > 0 {
+        println!();
+        println!("{} {} overdue audit(s)", style("!").red(), overdue);
+    }
+    if entries.iter().any(|e| e.recurrence) {
+        println!();
+        println!(
+            "{} (next) rows are recurring audits not yet planned; plan them with 'tdt audit complete --schedule-next' or 'tdt audit new'",
+            style("→").blue()
+        );
+    }
+
+    Ok(())
+}
@@ -4,19 +4,25 @@ use clap::{Subcommand, ValueEnum};
 use console::style;
 use miette::{IntoDiagnostic, Result};
 use std::fs;
+use std::path::PathBuf;
 
 use crate::cli::commands::utils::format_link_with_title;
 use crate::cli::helpers::format_short_id;
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::{CachedCapa, EntityCache};
+use crate::core::eight_d;
 use crate::core::identity::{EntityId, EntityPrefix};
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
 use crate::core::Config;
 use crate::entities::capa::{
-    Capa, CapaStatus, CapaType, Effectiveness, EffectivenessResult, SourceType,
+    Capa, CapaStatus, CapaType, Effectiveness, EffectivenessResult, FishboneCategory,
+    FishboneCause, RcaMethod, RootCauseAnalysis, SourceType, WhyStep,
 };
+use crate::entities::control::Control;
+use crate::entities::ncr::Ncr;
+use crate::entities::process::Process;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -42,6 +48,13 @@ pub enum CapaCommands {
 
     /// Record effectiveness verification
     Verify(VerifyArgs),
+
+    /// Record structured root cause analysis (5-Why chain, fishbone causes)
+    Rca(RcaArgs),
+
+    /// Generate an 8D report (D0-D8) from the CAPA and its linked entities
+    #[command(name = "report-8d")]
+    ReportEightD(ReportEightDArgs),
 }
 
 /// CAPA type filter
@@ -269,6 +282,82 @@ pub struct VerifyArgs {
     pub yes: bool,
 }
 
+/// RCA method CLI option
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliRcaMethod {
+    FiveWhy,
+    Fishbone,
+    FaultTree,
+    EightD,
+}
+
+impl From<CliRcaMethod> for RcaMethod {
+    fn from(m: CliRcaMethod) -> Self {
+        match m {
+            CliRcaMethod::FiveWhy => RcaMethod::FiveWhy,
+            CliRcaMethod::Fishbone => RcaMethod::Fishbone,
+            CliRcaMethod::FaultTree => RcaMethod::FaultTree,
+            CliRcaMethod::EightD => RcaMethod::EightD,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RcaArgs {
+    /// CAPA ID or short ID (CAPA@N)
+    pub capa: String,
+
+    /// Analysis method
+    #[arg(long, short = 'm')]
+    pub method: Option<CliRcaMethod>,
+
+    /// Append an answer to the 5-Why chain (repeatable, in order)
+    #[arg(long)]
+    pub why: Vec<String>,
+
+    /// Add a fishbone cause as CATEGORY:TEXT (man, machine, method, material, measurement, environment)
+    #[arg(long)]
+    pub cause: Vec<String>,
+
+    /// Mark the causes added by this command as confirmed
+    #[arg(long)]
+    pub confirmed: bool,
+
+    /// Set the root cause statement
+    #[arg(long)]
+    pub root_cause: Option<String>,
+
+    /// Risk implicated by the findings added by this command (repeatable)
+    #[arg(long)]
+    pub risk: Vec<String>,
+
+    /// Process implicated by the findings added by this command (repeatable)
+    #[arg(long)]
+    pub process: Vec<String>,
+}
+
+/// 8D report document format
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum EightDFormat {
+    #[default]
+    Md,
+    Html,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ReportEightDArgs {
+    /// CAPA ID or short ID (CAPA@N)
+    pub capa: String,
+
+    /// Document format
+    #[arg(long, default_value = "md")]
+    pub format: EightDFormat,
+
+    /// Write the report to a file instead of stdout
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
 /// Run a CAPA subcommand
 pub fn run(cmd: CapaCommands, global: &GlobalOpts) -> Result<()> {
     match cmd {
@@ -279,6 +368,8 @@ pub fn run(cmd: CapaCommands, global: &GlobalOpts) -> Result<()> {
         CapaCommands::Delete(args) => run_delete(args),
         CapaCommands::Archive(args) => run_archive(args),
         CapaCommands::Verify(args) => run_verify(args, global),
+        CapaCommands::Rca(args) => run_rca(args, global),
+        CapaCommands::ReportEightD(args) => run_report_8d(args, global),
     }
 }
 
@@ -813,14 +904,19 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
 
             // Root Cause Analysis
             if let Some(ref rca) = capa.root_cause_analysis {
-                if let Some(ref rc) = rca.root_cause {
-                    if !rc.is_empty() && !rc.starts_with('#') {
-                        println!();
-                        println!("{}: {}", style("RCA Method").bold(), rca.method);
-                        println!("{}", style("Root Cause:").bold());
-                        println!("{}", rc);
-                    }
+                let root_cause = rca
+                    .root_cause
+                    .as_ref()
+                    .filter(|rc| !rc.is_empty() && !rc.starts_with('#'));
+                if root_cause.is_some() || !rca.five_whys.is_empty() || !rca.fishbone.is_empty() {
+                    println!();
+                    println!("{}: {}", style("RCA Method").bold(), rca.method);
                 }
+                if let Some(rc) = root_cause {
+                    println!("{}", style("Root Cause:").bold());
+                    println!("{}", rc);
+                }
+                print_rca_details(&project, rca, &short_ids);
             }
 
             // Actions
@@ -1180,3 +1276,352 @@ fn run_verify(args: VerifyArgs, global: &GlobalOpts) -> Result<()> {
 
     Ok(())
 }
+
+/// Short ID list for implicated entities, e.g. " [RISK@1, PROC@2]"
+fn format_implicated(ids: &[&EntityId], short_ids: &ShortIdIndex) -> String {
+    if ids.is_empty() {
+        return String::new();
+    }
+    let shorts: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            short_ids
+                .get_short_id(&id.to_string())
+                .unwrap_or_else(|| format_short_id(id))
+        })
+        .collect();
+    format!(" [{}]", shorts.join(", "))
+}
+
+/// Print the 5-Why tree, fishbone diagram and FMEA impact of an RCA
+fn print_rca_details(project: &Project, rca: &RootCauseAnalysis, short_ids: &ShortIdIndex) {
+    if !rca.five_whys.is_empty() {
+        println!();
+        println!("{}", style("5-Why Analysis:").bold());
+        for (i, step) in rca.five_whys.iter().enumerate() {
+            let indent = "   ".repeat(i);
+            let question = step.why.as_deref().unwrap_or("Why?");
+            let implicated: Vec<&EntityId> = step.risks.iter().chain(&step.processes).collect();
+            println!(
+                "  {}└─ {} {} {}{}",
+                indent,
+                style(format!("{}.", i + 1)).dim(),
+                style(question).dim(),
+                step.because,
+                style(format_implicated(&implicated, short_ids)).cyan()
+            );
+            if let Some(ref evidence) = step.evidence {
+                println!("  {}     {} {}", indent, style("evidence:").dim(), evidence);
+            }
+        }
+    }
+
+    if !rca.fishbone.is_empty() {
+        println!();
+        println!("{}", style("Fishbone (Ishikawa):").bold());
+        let categories: Vec<FishboneCategory> = FishboneCategory::ALL
+            .into_iter()
+            .filter(|c| rca.fishbone.iter().any(|f| f.category == *c))
+            .collect();
+        for (ci, category) in categories.iter().enumerate() {
+            let last_category = ci + 1 == categories.len();
+            let (branch, rail) = if last_category {
+                ("└─", "   ")
+            } else {
+                ("├─", "│  ")
+            };
+            println!("  {} {}", branch, style(category.to_string()).yellow());
+            let causes: Vec<&FishboneCause> = rca
+                .fishbone
+                .iter()
+                .filter(|f| f.category == *category)
+                .collect();
+            for (i, cause) in causes.iter().enumerate() {
+                let last_cause = i + 1 == causes.len();
+                let (cbranch, crail) = if last_cause {
+                    ("└─", "   ")
+                } else {
+                    ("├─", "│  ")
+                };
+                let implicated: Vec<&EntityId> =
+                    cause.risks.iter().chain(&cause.processes).collect();
+                println!(
+                    "  {}{} {}{}{}",
+                    rail,
+                    cbranch,
+                    cause.cause,
+                    if cause.confirmed {
+                        style(" ✓ confirmed").green().to_string()
+                    } else {
+                        String::new()
+                    },
+                    style(format_implicated(&implicated, short_ids)).cyan()
+                );
+                for (si, sub) in cause.sub_causes.iter().enumerate() {
+                    let sbranch = if si + 1 == cause.sub_causes.len() {
+                        "└─"
+                    } else {
+                        "├─"
+                    };
+                    println!("  {}{}{} {}", rail, crail, sbranch, style(sub).dim());
+                }
+            }
+        }
+    }
+
+    let risks = rca.implicated_risks();
+    let processes = rca.implicated_processes();
+    if risks.is_empty() && processes.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}", style("FMEA Impact:").bold());
+    let all_risks = crate::cli::commands::report::load_all_risks(project);
+    for (id, findings) in &risks {
+        let short = short_ids
+            .get_short_id(&id.to_string())
+            .unwrap_or_else(|| format_short_id(id));
+        let risk = all_risks.iter().find(|r| r.id == **id);
+        let title = risk.map(|r| r.title.as_str()).unwrap_or("(not found)");
+        let rpn = risk
+            .and_then(|r| r.rpn.or_else(|| r.calculate_rpn()))
+            .map(|r| format!(" RPN {}", r))
+            .unwrap_or_default();
+        let sources: Vec<&str> = findings.iter().map(|f| f.source.as_str()).collect();
+        println!(
+            "  {}{} {} {}",
+            style(format!("{} ({})", short, title)).cyan(),
+            style(rpn).yellow(),
+            style("←").dim(),
+            sources.join(", ")
+        );
+    }
+    let cache = EntityCache::open(project).ok();
+    for (id, findings) in &processes {
+        let display = format_link_with_title(&id.to_string(), short_ids, &cache);
+        let sources: Vec<&str> = findings.iter().map(|f| f.source.as_str()).collect();
+        println!(
+            "  {} {} {}",
+            style(&display).cyan(),
+            style("←").dim(),
+            sources.join(", ")
+        );
+    }
+    if !risks.is_empty() {
+        println!(
+            "  {}",
+            style("Review occurrence/detection ratings of implicated risks with `tdt risk edit`")
+                .dim()
+        );
+    }
+}
+
+/// Resolve a risk or process reference and check its prefix
+fn resolve_implicated(
+    reference: &str,
+    prefix: EntityPrefix,
+    short_ids: &ShortIdIndex,
+) -> Result<EntityId> {
+    let resolved = short_ids
+        .resolve(reference)
+        .unwrap_or_else(|| reference.to_string());
+    let id: EntityId = resolved
+        .parse()
+        .map_err(|_| miette::miette!("Invalid entity reference '{}'", reference))?;
+    if id.prefix() != prefix {
+        return Err(miette::miette!("'{}' is not a {} ID", reference, prefix));
+    }
+    Ok(id)
+}
+
+/// Record structured root cause analysis on a CAPA
+fn run_rca(args: RcaArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.capa)
+        .unwrap_or_else(|| args.capa.clone());
+
+    let capa_dir = project.root().join("manufacturing/capas");
+    let (path, mut capa) = crate::core::loader::load_entity::<Capa>(&capa_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No CAPA found matching '{}'", args.capa))?;
+    let display_id = short_ids
+        .get_short_id(&capa.id.to_string())
+        .unwrap_or_else(|| format_short_id(&capa.id));
+
+    let risks: Vec<EntityId> = args
+        .risk
+        .iter()
+        .map(|r| resolve_implicated(r, EntityPrefix::Risk, &short_ids))
+        .collect::<Result<_>>()?;
+    let processes: Vec<EntityId> = args
+        .process
+        .iter()
+        .map(|p| resolve_implicated(p, EntityPrefix::Proc, &short_ids))
+        .collect::<Result<_>>()?;
+    if (!risks.is_empty() || !processes.is_empty()) && args.why.is_empty() && args.cause.is_empty()
+    {
+        return Err(miette::miette!(
+            "--risk/--process attach to the findings added by this command; add a --why or --cause"
+        ));
+    }
+
+    let causes: Vec<FishboneCause> = args
+        .cause
+        .iter()
+        .map(|c| {
+            let (category, text) = c.split_once(':').ok_or_else(|| {
+                miette::miette!(
+                    "Invalid cause '{}'. Use CATEGORY:TEXT, e.g. machine:Spindle runout",
+                    c
+                )
+            })?;
+            let category: FishboneCategory = category
+                .trim()
+                .parse()
+                .map_err(|e: String| miette::miette!("{}", e))?;
+            Ok(FishboneCause {
+                category,
+                cause: text.trim().to_string(),
+                sub_causes: Vec::new(),
+                confirmed: args.confirmed,
+                risks: risks.clone(),
+                processes: processes.clone(),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let rca = capa
+        .root_cause_analysis
+        .get_or_insert_with(RootCauseAnalysis::default);
+    if let Some(method) = args.method {
+        rca.method = method.into();
+    }
+    if let Some(ref root_cause) = args.root_cause {
+        rca.root_cause = Some(root_cause.clone());
+    }
+    for because in &args.why {
+        rca.five_whys.push(WhyStep {
+            because: because.clone(),
+            risks: risks.clone(),
+            processes: processes.clone(),
+            ..Default::default()
+        });
+    }
+    let added_causes = causes.len();
+    rca.fishbone.extend(causes);
+
+    // Implicated risks are also related risks of the CAPA
+    for risk in &risks {
+        if !capa.links.risks.contains(risk) {
+            capa.links.risks.push(risk.clone());
+        }
+    }
+
+    capa.entity_revision += 1;
+    fs::write(&path, serde_yml::to_string(&capa).into_diagnostic()?).into_diagnostic()?;
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&capa.root_cause_analysis).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!(
+                "{}",
+                serde_yml::to_string(&capa.root_cause_analysis).into_diagnostic()?
+            );
+        }
+        _ => {
+            println!(
+                "{} Updated root cause analysis for {}",
+                style("✓").green(),
+                style(&display_id).cyan()
+            );
+            if !args.why.is_empty() {
+                println!("  5-Why steps added: {}", args.why.len());
+            }
+            if added_causes > 0 {
+                println!("  Fishbone causes added: {}", added_causes);
+            }
+            if !risks.is_empty() || !processes.is_empty() {
+                let implicated: Vec<&EntityId> = risks.iter().chain(&processes).collect();
+                println!(
+                    "  Implicated:{}",
+                    style(format_implicated(&implicated, &short_ids)).cyan()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_report_8d(args: ReportEightDArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.capa)
+        .unwrap_or_else(|| args.capa.clone());
+
+    let capa_dir = project.root().join("manufacturing/capas");
+    let (_, capa) = crate::core::loader::load_entity::<Capa>(&capa_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No CAPA found matching '{}'", args.capa))?;
+
+    let root = project.root();
+    let ncrs: Vec<Ncr> = crate::core::loader::load_all(&root.join("manufacturing/ncrs"))?;
+    let risks = crate::cli::commands::report::load_all_risks(&project);
+    let processes: Vec<Process> =
+        crate::core::loader::load_all(&root.join("manufacturing/processes"))?;
+    let controls: Vec<Control> =
+        crate::core::loader::load_all(&root.join("manufacturing/controls"))?;
+
+    let report = eight_d::build_report(&eight_d::EightDInputs {
+        capa: &capa,
+        ncrs: &ncrs,
+        risks: &risks,
+        processes: &processes,
+        controls: &controls,
+    });
+
+    let document = match global.output {
+        OutputFormat::Json => serde_json::to_string_pretty(&report).into_diagnostic()? + "\n",
+        OutputFormat::Yaml => serde_yml::to_string(&report).into_diagnostic()?,
+        _ => match args.format {
+            EightDFormat::Md => report.to_markdown(),
+            EightDFormat::Html => report.to_html(),
+        },
+    };
+
+    let gaps = report.gaps();
+    for gap in &gaps {
+        eprintln!("{} {}", style("!").yellow(), gap);
+    }
+
+    match args.file {
+        Some(path) => {
+            fs::write(&path, &document).into_diagnostic()?;
+            let display_id = short_ids
+                .get_short_id(&capa.id.to_string())
+                .unwrap_or_else(|| format_short_id(&capa.id));
+            let incomplete = report
+                .disciplines
+                .iter()
+                .filter(|d| !d.is_complete())
+                .count();
+            eprintln!(
+                "{} Wrote 8D report for {} to {} ({} of 9 disciplines incomplete)",
+                style("✓").green(),
+                style(&display_id).cyan(),
+                style(path.display()).yellow(),
+                incomplete
+            );
+        }
+        None => print!("{}", document),
+    }
+
+    Ok(())
+}
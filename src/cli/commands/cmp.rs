@@ -1,23 +1,31 @@
 //! `tdt cmp` command - Component management
 
+use chrono::NaiveDate;
 use clap::{Subcommand, ValueEnum};
 use console::style;
 use miette::{IntoDiagnostic, Result};
 use std::collections::HashSet;
 use std::fs;
 
+use crate::cli::commands::asm::print_rate_warnings;
 use crate::cli::commands::utils::format_link_with_title;
 use crate::cli::filters::StatusFilter;
-use crate::cli::helpers::resolve_id_arg;
+use crate::cli::helpers::{resolve_id_arg, truncate_str};
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::EntityCache;
+use crate::core::currency::Converter;
 use crate::core::identity::{EntityId, EntityPrefix};
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
+use crate::core::should_cost::ShouldCost;
 use crate::core::Config;
 use crate::entities::assembly::{Assembly, ManufacturingConfig};
-use crate::entities::component::{Component, ComponentCategory, MakeBuy};
+use crate::entities::component::{
+    AmlApproval, AmlEntry, ComplianceDeclaration, ComplianceStatus, Component, ComponentCategory,
+    Lifecycle, LifecycleStatus, MakeBuy, QuoteSelection, Regulation, Substance,
+};
+use crate::entities::quote::Currency;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -47,9 +55,23 @@ pub enum CmpCommands {
     /// Clear the selected quote (revert to manual unit_cost)
     ClearQuote(ClearQuoteArgs),
 
+    /// Estimate a make part's cost from its routing and material
+    ShouldCost(ShouldCostArgs),
+
     /// Manage manufacturing routing for component
     #[command(subcommand)]
     Routing(RoutingCommands),
+
+    /// Manage the approved manufacturer list (AML)
+    #[command(subcommand)]
+    Aml(AmlCommands),
+
+    /// Set the lifecycle status of a component or one of its MPNs
+    Lifecycle(LifecycleArgs),
+
+    /// Manage material compliance declarations (RoHS, REACH, Prop 65, conflict minerals)
+    #[command(subcommand)]
+    Compliance(ComplianceCommands),
 }
 
 /// Routing subcommands for manufacturing
@@ -101,6 +123,118 @@ pub struct RoutingSetArgs {
     pub procs: Vec<String>,
 }
 
+/// Approved manufacturer list subcommands
+#[derive(Subcommand, Debug)]
+pub enum AmlCommands {
+    /// Add a manufacturer part number (or update an existing one)
+    Add(AmlAddArgs),
+    /// Remove a manufacturer part number
+    Rm(AmlRmArgs),
+    /// List manufacturer part numbers
+    List(AmlListArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AmlAddArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+    /// Manufacturer part number
+    pub mpn: String,
+    /// Manufacturer name
+    #[arg(long, short = 'M')]
+    pub manufacturer: String,
+    /// Approval state (approved, pending, disqualified)
+    #[arg(long, default_value = "approved")]
+    pub approval: AmlApproval,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AmlRmArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+    /// Manufacturer part number to remove
+    pub mpn: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AmlListArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct LifecycleArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+    /// Lifecycle status (active, nrnd, last_time_buy, obsolete)
+    pub status: LifecycleStatus,
+    /// Apply to this AML manufacturer part number instead of the component
+    #[arg(long)]
+    pub mpn: Option<String>,
+    /// Last-time-buy date (YYYY-MM-DD)
+    #[arg(long)]
+    pub last_time_buy: Option<NaiveDate>,
+    /// End-of-life date (YYYY-MM-DD)
+    #[arg(long)]
+    pub end_of_life: Option<NaiveDate>,
+    /// Source of the status (PCN number, distributor notice, etc.)
+    #[arg(long)]
+    pub notice: Option<String>,
+}
+
+/// Material compliance subcommands
+#[derive(Subcommand, Debug)]
+pub enum ComplianceCommands {
+    /// Record a declaration (replaces any existing one for the regulation)
+    Set(ComplianceSetArgs),
+    /// Remove a declaration
+    Rm(ComplianceRmArgs),
+    /// List declarations
+    List(ComplianceListArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ComplianceSetArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+    /// Regulation (rohs, reach, prop65, conflict_minerals)
+    pub regulation: Regulation,
+    /// Declared state (compliant, exempt, non_compliant)
+    pub status: ComplianceStatus,
+    /// Exemption claimed, e.g. "7(a)" (repeatable)
+    #[arg(long = "exemption")]
+    pub exemptions: Vec<String>,
+    /// Declared substance as NAME[:CAS[:PERCENT]] (repeatable)
+    #[arg(long = "substance")]
+    pub substances: Vec<String>,
+    /// Supporting document path (repeatable)
+    #[arg(long = "document")]
+    pub documents: Vec<String>,
+    /// Who issued the declaration (supplier, lab)
+    #[arg(long)]
+    pub declared_by: Option<String>,
+    /// Declaration date (YYYY-MM-DD, default: today)
+    #[arg(long)]
+    pub date: Option<NaiveDate>,
+    /// Date the declaration must be renewed by (YYYY-MM-DD)
+    #[arg(long)]
+    pub expires: Option<NaiveDate>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ComplianceRmArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+    /// Regulation to remove
+    pub regulation: Regulation,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ComplianceListArgs {
+    /// Component ID (CMP-xxx or short ID like CMP@1)
+    pub cmp: String,
+}
+
 /// Make/buy filter for list command
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum MakeBuyFilter {
@@ -390,6 +524,10 @@ pub struct SetQuoteArgs {
 
     /// Quote ID or short ID (QUOT@N) to use for pricing
     pub quote: String,
+
+    /// Why this quote was chosen (recorded on the component)
+    #[arg(long, short = 'r')]
+    pub rationale: Option<String>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -398,6 +536,17 @@ pub struct ClearQuoteArgs {
     pub component: String,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct ShouldCostArgs {
+    /// Component ID or short ID (CMP@N)
+    pub component: String,
+
+    /// Batch size that setup time is amortized over, and quotes are priced at
+    /// (default: costing.batch_size from config)
+    #[arg(long)]
+    pub batch: Option<u32>,
+}
+
 /// Run a component subcommand
 pub fn run(cmd: CmpCommands, global: &GlobalOpts) -> Result<()> {
     match cmd {
@@ -409,7 +558,11 @@ pub fn run(cmd: CmpCommands, global: &GlobalOpts) -> Result<()> {
         CmpCommands::Archive(args) => run_archive(args),
         CmpCommands::SetQuote(args) => run_set_quote(args),
         CmpCommands::ClearQuote(args) => run_clear_quote(args),
+        CmpCommands::ShouldCost(args) => run_should_cost(args, global),
         CmpCommands::Routing(cmd) => run_routing(cmd),
+        CmpCommands::Aml(cmd) => run_aml(cmd),
+        CmpCommands::Lifecycle(args) => run_lifecycle(args),
+        CmpCommands::Compliance(cmd) => run_compliance(cmd),
     }
 }
 
@@ -1158,6 +1311,83 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                 }
             }
 
+            // Approved manufacturers and lifecycle
+            if cmp.lifecycle.is_some() || !cmp.aml.is_empty() {
+                let status = cmp.lifecycle_status();
+                println!();
+                println!("{}: {}", style("Lifecycle").bold(), status);
+                if let Some(ref lifecycle) = cmp.lifecycle {
+                    if let Some(date) = lifecycle.last_time_buy {
+                        println!("  {}: {}", style("Last-time-buy").dim(), date);
+                    }
+                    if let Some(date) = lifecycle.end_of_life {
+                        println!("  {}: {}", style("End-of-life").dim(), date);
+                    }
+                    if let Some(ref notice) = lifecycle.notice {
+                        println!("  {}: {}", style("Notice").dim(), notice);
+                    }
+                }
+                if !cmp.aml.is_empty() {
+                    println!("{}", style("Approved Manufacturers:").bold());
+                    for entry in &cmp.aml {
+                        println!(
+                            "  • {} {} ({}, {})",
+                            entry.manufacturer,
+                            entry.mpn,
+                            entry.approval,
+                            entry.lifecycle_status()
+                        );
+                    }
+                }
+            }
+
+            // Material compliance
+            if !cmp.compliance.is_empty() {
+                let today = chrono::Utc::now().date_naive();
+                println!();
+                println!("{}", style("Compliance:").bold());
+                for declaration in &cmp.compliance {
+                    let mut line = format!(
+                        "  • {}: {}",
+                        declaration.regulation.label(),
+                        declaration.status
+                    );
+                    if !declaration.exemptions.is_empty() {
+                        line.push_str(&format!(" ({})", declaration.exemptions.join(", ")));
+                    }
+                    if let Some(date) = declaration.expires {
+                        if declaration.is_expired(today) {
+                            line.push_str(&format!(" - expired {}", date));
+                        } else {
+                            line.push_str(&format!(" - expires {}", date));
+                        }
+                    }
+                    println!("{}", line);
+                }
+            }
+
+            // Selected quote and sourcing rationale
+            if let Some(ref quote_id) = cmp.selected_quote {
+                let short_ids = ShortIdIndex::load(&project);
+                let quote_display = short_ids
+                    .get_short_id(quote_id)
+                    .unwrap_or_else(|| quote_id.clone());
+                println!();
+                println!("{}", style("Selected Quote:").bold());
+                println!("  {}", style(&quote_display).cyan());
+                if let Some(ref sel) = cmp.quote_selection {
+                    println!(
+                        "  {}: {} on {}",
+                        style("Selected by").dim(),
+                        sel.selected_by,
+                        sel.date
+                    );
+                    if !sel.rationale.is_empty() {
+                        println!("  {}: {}", style("Rationale").dim(), sel.rationale);
+                    }
+                }
+            }
+
             // Documents
             if !cmp.documents.is_empty() && cmp.documents.iter().any(|d| !d.path.is_empty()) {
                 println!();
@@ -1329,6 +1559,233 @@ fn run_archive(args: ArchiveArgs) -> Result<()> {
 }
 
 /// Load all quotes from the project
+/// Supplier quote priced against a should-cost estimate
+#[derive(Debug, serde::Serialize)]
+struct QuoteVsShouldCost {
+    quote: String,
+    title: String,
+    supplier: String,
+    unit_price: f64,
+    /// Percent above (+) or below (-) the should-cost
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta_pct: Option<f64>,
+    expired: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ShouldCostReport {
+    currency: Currency,
+    #[serde(flatten)]
+    estimate: ShouldCost,
+    quotes: Vec<QuoteVsShouldCost>,
+}
+
+fn run_should_cost(args: ShouldCostArgs, global: &GlobalOpts) -> Result<()> {
+    use crate::entities::process::Process;
+    use crate::entities::quote::QuoteStatus;
+
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.component)
+        .unwrap_or_else(|| args.component.clone());
+
+    let components: Vec<Component> =
+        crate::core::loader::load_all(&project.root().join("bom/components"))?;
+    let cmp = components
+        .iter()
+        .find(|c| c.id.to_string() == cmp_id || c.id.to_string().starts_with(&cmp_id))
+        .ok_or_else(|| miette::miette!("Component '{}' not found", args.component))?;
+
+    let config = Config::load().costing.unwrap_or_default();
+    let processes: Vec<Process> =
+        crate::core::loader::load_all(&project.root().join("manufacturing/processes"))?;
+    let batch = args.batch.unwrap_or(config.batch_size);
+    let estimate = crate::core::should_cost::estimate(cmp, &processes, &config, batch);
+
+    // Supplier quotes for the same part, priced at the batch size
+    let mut converter = Converter::from_config(None);
+    let sym = converter.target.symbol();
+    let mut quotes: Vec<QuoteVsShouldCost> = load_all_quotes(&project)
+        .into_iter()
+        .filter(|q| q.component.as_deref() == Some(estimate.component.as_str()))
+        .filter(|q| q.quote_status != QuoteStatus::Rejected)
+        .filter_map(|q| {
+            let price = q.price_for_qty(estimate.batch_size)?;
+            let unit_price = converter.convert(price, q.currency);
+            Some(QuoteVsShouldCost {
+                quote: q.id.to_string(),
+                title: q.title.clone(),
+                supplier: q.supplier.clone(),
+                unit_price,
+                delta_pct: estimate
+                    .is_estimated()
+                    .then(|| (unit_price - estimate.total) / estimate.total * 100.0),
+                expired: q.is_expired(),
+            })
+        })
+        .collect();
+    quotes.sort_by(|a, b| a.unit_price.total_cmp(&b.unit_price));
+
+    let report = ShouldCostReport {
+        currency: converter.target,
+        estimate,
+        quotes,
+    };
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).into_diagnostic()?
+            );
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&report).into_diagnostic()?);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let estimate = &report.estimate;
+    let cmp_short = short_ids
+        .get_short_id(&estimate.component)
+        .unwrap_or_else(|| estimate.component.clone());
+    println!(
+        "{} {} ({})",
+        style("Should-cost:").bold(),
+        style(&estimate.title).cyan(),
+        cmp_short
+    );
+    println!(
+        "{} {}",
+        style("Batch Size:").bold(),
+        style(estimate.batch_size).yellow()
+    );
+    if cmp.make_buy != MakeBuy::Make {
+        println!(
+            "{} {} is a buy part; its should-cost is not used in cost rollups",
+            style("Note:").yellow().bold(),
+            cmp_short
+        );
+    }
+    println!();
+
+    if !estimate.steps.is_empty() {
+        println!(
+            "{:<10} {:<24} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9}",
+            style("OP").bold(),
+            style("PROCESS").bold(),
+            style("SETUP").bold(),
+            style("CYCLE").bold(),
+            style("RATE/H").bold(),
+            style("SETUP/U").bold(),
+            style("CYCLE/U").bold(),
+            style("TOTAL").bold()
+        );
+        println!("{}", "-".repeat(92));
+        for step in &estimate.steps {
+            let op = step.operation_number.clone().unwrap_or_else(|| {
+                short_ids
+                    .get_short_id(&step.process)
+                    .unwrap_or_else(|| truncate_str(&step.process, 8))
+            });
+            println!(
+                "{:<10} {:<24} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9}",
+                truncate_str(&op, 10),
+                truncate_str(&step.title, 22),
+                format!("{:.1}m", step.setup_minutes),
+                format!("{:.1}m", step.cycle_minutes),
+                format!("{}{:.2}", sym, step.hourly_rate),
+                format!("{}{:.2}", sym, step.setup_cost),
+                format!("{}{:.2}", sym, step.cycle_cost),
+                format!("{}{:.2}", sym, step.total)
+            );
+        }
+        println!("{}", "-".repeat(92));
+    }
+
+    print!(
+        "{} {}{:.2}",
+        style("Material:").bold(),
+        sym,
+        estimate.material_cost
+    );
+    if let (Some(material), Some(mass)) = (&estimate.material, estimate.mass_kg) {
+        print!(
+            " ({:.3} kg {}, {:.0}% scrap)",
+            mass,
+            material,
+            estimate.scrap_factor * 100.0
+        );
+    }
+    println!();
+    println!(
+        "{} {}{:.2}",
+        style("Process:").bold(),
+        sym,
+        estimate.process_cost
+    );
+    println!(
+        "{} {}{:.2}",
+        style("Should-Cost:").green().bold(),
+        sym,
+        estimate.total
+    );
+
+    if !estimate.issues.is_empty() {
+        println!();
+        println!(
+            "{} Not priced (left out of the estimate):",
+            style("Note:").yellow().bold()
+        );
+        for issue in &estimate.issues {
+            println!("   {} {}", style("•").dim(), issue);
+        }
+        println!(
+            "   {}",
+            style("Add the missing rates under 'costing' in .tdt/config.yaml").dim()
+        );
+    }
+
+    if !report.quotes.is_empty() {
+        println!();
+        println!(
+            "{}",
+            style(format!("Quotes at qty {}:", estimate.batch_size)).bold()
+        );
+        for q in &report.quotes {
+            let quote_short = short_ids
+                .get_short_id(&q.quote)
+                .unwrap_or_else(|| truncate_str(&q.quote, 10));
+            let supplier = short_ids
+                .get_short_id(&q.supplier)
+                .unwrap_or_else(|| truncate_str(&q.supplier, 10));
+            let delta = match q.delta_pct {
+                Some(pct) if pct > 0.0 => style(format!("{:+.1}%", pct)).red().to_string(),
+                Some(pct) => style(format!("{:+.1}%", pct)).green().to_string(),
+                None => "-".to_string(),
+            };
+            println!(
+                "   {:<10} {:<24} {:<10} {:>10} {:>8}{}",
+                quote_short,
+                truncate_str(&q.title, 22),
+                supplier,
+                format!("{}{:.2}", sym, q.unit_price),
+                delta,
+                if q.expired {
+                    style(" (expired)").red().to_string()
+                } else {
+                    String::new()
+                }
+            );
+        }
+    }
+    print_rate_warnings(&converter);
+
+    Ok(())
+}
+
 fn load_all_quotes(project: &Project) -> Vec<crate::entities::quote::Quote> {
     let mut quotes = Vec::new();
 
@@ -1389,6 +1846,10 @@ fn run_set_quote(args: SetQuoteArgs) -> Result<()> {
         ));
     }
 
+    if let Some(warning) = super::sup::approval_warning(&project, &quote.supplier) {
+        eprintln!("{} Warning: {}", style("!").yellow(), warning);
+    }
+
     // Find and load the component
     let cmp_dir = project.root().join("bom/components");
     let mut found_path = None;
@@ -1420,6 +1881,17 @@ fn run_set_quote(args: SetQuoteArgs) -> Result<()> {
     // Update the selected_quote field
     let old_quote = component.selected_quote.clone();
     component.selected_quote = Some(quote.id.to_string());
+    component.quote_selection = args.rationale.as_ref().map(|rationale| QuoteSelection {
+        quote: quote.id.to_string(),
+        selected_by: Config::load().author(),
+        date: chrono::Local::now().date_naive(),
+        rationale: rationale.clone(),
+        qty: None,
+        volume: None,
+        effective_unit_cost: None,
+        currency: None,
+        score: None,
+    });
 
     // Save the updated component
     let yaml = serde_yml::to_string(&component).into_diagnostic()?;
@@ -1520,6 +1992,7 @@ fn run_clear_quote(args: ClearQuoteArgs) -> Result<()> {
     }
 
     let old_quote = component.selected_quote.take();
+    component.quote_selection = None;
 
     // Save the updated component
     let yaml = serde_yml::to_string(&component).into_diagnostic()?;
@@ -1618,6 +2091,399 @@ fn run_routing(cmd: RoutingCommands) -> Result<()> {
     }
 }
 
+fn run_aml(cmd: AmlCommands) -> Result<()> {
+    match cmd {
+        AmlCommands::Add(args) => run_aml_add(args),
+        AmlCommands::Rm(args) => run_aml_rm(args),
+        AmlCommands::List(args) => run_aml_list(args),
+    }
+}
+
+fn run_aml_add(args: AmlAddArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (mut component, path) = find_component_file(&project, &cmp_id)?;
+
+    let mpn = args.mpn.trim().to_string();
+    let manufacturer = args.manufacturer.trim().to_string();
+    if mpn.is_empty() || manufacturer.is_empty() {
+        return Err(miette::miette!("Manufacturer and MPN must not be empty"));
+    }
+
+    let updated = match component
+        .aml
+        .iter_mut()
+        .find(|e| e.mpn.eq_ignore_ascii_case(&mpn))
+    {
+        Some(entry) => {
+            entry.manufacturer = manufacturer.clone();
+            entry.approval = args.approval;
+            true
+        }
+        None => {
+            component.aml.push(AmlEntry {
+                manufacturer: manufacturer.clone(),
+                mpn: mpn.clone(),
+                approval: args.approval,
+                lifecycle: None,
+            });
+            false
+        }
+    };
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    println!(
+        "{} {} {} {} ({}) on {}",
+        style("✓").green(),
+        if updated { "Updated" } else { "Added" },
+        style(&manufacturer).cyan(),
+        style(&mpn).yellow(),
+        args.approval,
+        args.cmp
+    );
+    let approved = component.approved_aml().count();
+    println!(
+        "   AML has {} approved MPN{}",
+        approved,
+        if approved == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+fn run_aml_rm(args: AmlRmArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (mut component, path) = find_component_file(&project, &cmp_id)?;
+
+    let before = component.aml.len();
+    component
+        .aml
+        .retain(|e| !e.mpn.eq_ignore_ascii_case(args.mpn.trim()));
+    if component.aml.len() == before {
+        return Err(miette::miette!(
+            "MPN {} is not on the AML for {}",
+            args.mpn,
+            args.cmp
+        ));
+    }
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    println!(
+        "{} Removed {} from the AML for {}",
+        style("✓").green(),
+        style(&args.mpn).yellow(),
+        args.cmp
+    );
+
+    Ok(())
+}
+
+fn run_aml_list(args: AmlListArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (component, _path) = find_component_file(&project, &cmp_id)?;
+
+    if component.aml.is_empty() {
+        println!("No AML entries for component {}", args.cmp);
+        return Ok(());
+    }
+
+    println!(
+        "Approved manufacturer list for {} (part lifecycle: {}):",
+        style(&args.cmp).cyan(),
+        component.lifecycle_status()
+    );
+    println!(
+        "{:<20} {:<22} {:<13} {:<14} {:<11} {:<11}",
+        style("MANUFACTURER").bold(),
+        style("MPN").bold(),
+        style("APPROVAL").bold(),
+        style("LIFECYCLE").bold(),
+        style("LTB").bold(),
+        style("EOL").bold()
+    );
+    for entry in &component.aml {
+        let date = |d: Option<NaiveDate>| d.map(|d| d.to_string()).unwrap_or("-".to_string());
+        let lifecycle = entry.lifecycle.as_ref();
+        let status = entry.lifecycle_status();
+        let status_str = if status.is_end_of_life() {
+            style(status.to_string()).red()
+        } else if status == LifecycleStatus::Nrnd {
+            style(status.to_string()).yellow()
+        } else {
+            style(status.to_string()).green()
+        };
+        println!(
+            "{:<20} {:<22} {:<13} {:<14} {:<11} {:<11}",
+            truncate_str(&entry.manufacturer, 20),
+            truncate_str(&entry.mpn, 22),
+            entry.approval,
+            status_str,
+            date(lifecycle.and_then(|l| l.last_time_buy)),
+            date(lifecycle.and_then(|l| l.end_of_life))
+        );
+    }
+
+    Ok(())
+}
+
+fn run_lifecycle(args: LifecycleArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (mut component, path) = find_component_file(&project, &cmp_id)?;
+
+    let lifecycle = Lifecycle {
+        status: args.status,
+        last_time_buy: args.last_time_buy,
+        end_of_life: args.end_of_life,
+        notice: args.notice.clone(),
+    };
+    let target = match args.mpn {
+        Some(ref mpn) => {
+            let entry = component
+                .aml
+                .iter_mut()
+                .find(|e| e.mpn.eq_ignore_ascii_case(mpn.trim()))
+                .ok_or_else(|| miette::miette!("MPN {} is not on the AML for {}", mpn, args.cmp))?;
+            entry.lifecycle = Some(lifecycle);
+            entry.mpn.clone()
+        }
+        None => {
+            component.lifecycle = Some(lifecycle);
+            args.cmp.clone()
+        }
+    };
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    println!(
+        "{} Set lifecycle of {} to {}",
+        style("✓").green(),
+        style(&target).cyan(),
+        style(args.status).yellow()
+    );
+    if args.mpn.is_some() {
+        println!("   Part lifecycle: {}", component.lifecycle_status());
+    }
+
+    Ok(())
+}
+
+fn run_compliance(cmd: ComplianceCommands) -> Result<()> {
+    match cmd {
+        ComplianceCommands::Set(args) => run_compliance_set(args),
+        ComplianceCommands::Rm(args) => run_compliance_rm(args),
+        ComplianceCommands::List(args) => run_compliance_list(args),
+    }
+}
+
+/// Parse a substance given as NAME[:CAS[:PERCENT]]
+fn parse_substance(s: &str) -> Result<Substance> {
+    let mut parts = s.split(':').map(str::trim);
+    let name = parts.next().unwrap_or_default().to_string();
+    if name.is_empty() {
+        return Err(miette::miette!(
+            "Invalid substance '{}'. Use NAME[:CAS[:PERCENT]]",
+            s
+        ));
+    }
+    let cas = parts.next().filter(|c| !c.is_empty()).map(String::from);
+    let percent = match parts.next().filter(|p| !p.is_empty()) {
+        Some(p) => Some(
+            p.trim_end_matches('%')
+                .parse::<f64>()
+                .map_err(|_| miette::miette!("Invalid concentration '{}' in '{}'", p, s))?,
+        ),
+        None => None,
+    };
+    Ok(Substance { name, cas, percent })
+}
+
+fn run_compliance_set(args: ComplianceSetArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (mut component, path) = find_component_file(&project, &cmp_id)?;
+
+    if args.status == ComplianceStatus::Exempt && args.exemptions.is_empty() {
+        return Err(miette::miette!(
+            "An exempt declaration needs at least one --exemption"
+        ));
+    }
+    let substances = args
+        .substances
+        .iter()
+        .map(|s| parse_substance(s))
+        .collect::<Result<Vec<_>>>()?;
+
+    let declaration = ComplianceDeclaration {
+        regulation: args.regulation,
+        status: args.status,
+        exemptions: args.exemptions.clone(),
+        substances,
+        documents: args.documents.clone(),
+        declared_by: args.declared_by.clone(),
+        date: Some(args.date.unwrap_or_else(|| chrono::Utc::now().date_naive())),
+        expires: args.expires,
+    };
+    let replaced = match component
+        .compliance
+        .iter_mut()
+        .find(|d| d.regulation == args.regulation)
+    {
+        Some(existing) => {
+            *existing = declaration;
+            true
+        }
+        None => {
+            component.compliance.push(declaration);
+            false
+        }
+    };
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    println!(
+        "{} {} {} declaration on {}: {}",
+        style("✓").green(),
+        if replaced { "Updated" } else { "Added" },
+        style(args.regulation.label()).cyan(),
+        args.cmp,
+        style(args.status).yellow()
+    );
+    if let Some(date) = args.expires {
+        println!("   Expires: {}", date);
+    }
+
+    Ok(())
+}
+
+fn run_compliance_rm(args: ComplianceRmArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (mut component, path) = find_component_file(&project, &cmp_id)?;
+
+    let before = component.compliance.len();
+    component
+        .compliance
+        .retain(|d| d.regulation != args.regulation);
+    if component.compliance.len() == before {
+        return Err(miette::miette!(
+            "No {} declaration on {}",
+            args.regulation.label(),
+            args.cmp
+        ));
+    }
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    println!(
+        "{} Removed {} declaration from {}",
+        style("✓").green(),
+        style(args.regulation.label()).cyan(),
+        args.cmp
+    );
+
+    Ok(())
+}
+
+fn run_compliance_list(args: ComplianceListArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let cmp_id = short_ids
+        .resolve(&args.cmp)
+        .unwrap_or_else(|| args.cmp.clone());
+    let (component, _path) = find_component_file(&project, &cmp_id)?;
+
+    if component.compliance.is_empty() {
+        println!("No compliance declarations for component {}", args.cmp);
+        return Ok(());
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    println!("Compliance declarations for {}:", style(&args.cmp).cyan());
+    println!(
+        "{:<18} {:<14} {:<11} {:<11} {:<20} {}",
+        style("REGULATION").bold(),
+        style("STATUS").bold(),
+        style("DATE").bold(),
+        style("EXPIRES").bold(),
+        style("DECLARED BY").bold(),
+        style("EXEMPTIONS").bold()
+    );
+    for declaration in &component.compliance {
+        let date = |d: Option<NaiveDate>| d.map(|d| d.to_string()).unwrap_or("-".to_string());
+        let status_str = if declaration.status == ComplianceStatus::NonCompliant {
+            style(declaration.status.to_string()).red()
+        } else if declaration.is_expired(today) {
+            style(format!("{} (expired)", declaration.status)).red()
+        } else if declaration.status == ComplianceStatus::Exempt {
+            style(declaration.status.to_string()).yellow()
+        } else {
+            style(declaration.status.to_string()).green()
+        };
+        println!(
+            "{:<18} {:<14} {:<11} {:<11} {:<20} {}",
+            declaration.regulation.label(),
+            status_str,
+            date(declaration.date),
+            date(declaration.expires),
+            truncate_str(declaration.declared_by.as_deref().unwrap_or("-"), 20),
+            if declaration.exemptions.is_empty() {
+                "-".to_string()
+            } else {
+                declaration.exemptions.join(", ")
+            }
+        );
+        for substance in &declaration.substances {
+            println!(
+                "    {} {}{}{}",
+                style("substance:").dim(),
+                substance.name,
+                substance
+                    .cas
+                    .as_ref()
+                    .map(|c| format!(" (CAS {})", c))
+                    .unwrap_or_default(),
+                substance
+                    .percent
+                    .map(|p| format!(" {}% w/w", p))
+                    .unwrap_or_default()
+            );
+        }
+        for document in &declaration.documents {
+            println!("    {} {}", style("document:").dim(), document);
+        }
+    }
+
+    Ok(())
+}
+
 /// Find a component file by ID and return the loaded component and path
 fn find_component_file(project: &Project, id: &str) -> Result<(Component, std::path::PathBuf)> {
     let cmp_dir = project.root().join("bom/components");
@@ -0,0 +1,1108 @@
+//! `tdt cmpl` command - Customer complaint / field return management
+
+use clap::{Subcommand, ValueEnum};
+use console::style;
+use miette::{IntoDiagnostic, Result};
+use std::fs;
+
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::field_feedback::{self, FieldVerdict};
+use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
+use crate::core::project::Project;
+use crate::core::shortid::ShortIdIndex;
+use crate::core::Config;
+use crate::entities::complaint::{Complaint, ComplaintStatus, ComplaintType};
+use crate::entities::lot::Lot;
+use crate::schema::template::{TemplateContext, TemplateGenerator};
+use crate::schema::wizard::SchemaWizard;
+
+/// CLI-friendly complaint type enum
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliComplaintType {
+    Complaint,
+    Return,
+    FieldFailure,
+}
+
+impl std::fmt::Display for CliComplaintType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliComplaintType::Complaint => write!(f, "complaint"),
+            CliComplaintType::Return => write!(f, "return"),
+            CliComplaintType::FieldFailure => write!(f, "field_failure"),
+        }
+    }
+}
+
+impl From<CliComplaintType> for ComplaintType {
+    fn from(cli: CliComplaintType) -> Self {
+        match cli {
+            CliComplaintType::Complaint => ComplaintType::Complaint,
+            CliComplaintType::Return => ComplaintType::Return,
+            CliComplaintType::FieldFailure => ComplaintType::FieldFailure,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CmplCommands {
+    /// List complaints with filtering
+    List(ListArgs),
+
+    /// Create a new complaint
+    New(NewArgs),
+
+    /// Show a complaint's details
+    Show(ShowArgs),
+
+    /// Edit a complaint in your editor
+    Edit(EditArgs),
+
+    /// Delete a complaint
+    Delete(DeleteArgs),
+
+    /// Archive a complaint (soft delete)
+    Archive(ArchiveArgs),
+
+    /// Record the regulatory reportability assessment
+    Assess(AssessArgs),
+
+    /// Compare field failure rates with FMEA occurrence ratings
+    RiskFeedback(RiskFeedbackArgs),
+}
+
+/// Complaint status filter
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CmplStatusFilter {
+    Received,
+    Investigating,
+    Resolved,
+    Closed,
+    /// Received or investigating
+    Open,
+    All,
+}
+
+/// List column for display and sorting
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListColumn {
+    #[value(name = "id")]
+    Id,
+    #[value(name = "title")]
+    Title,
+    #[value(name = "type")]
+    Type,
+    #[value(name = "customer")]
+    Customer,
+    #[value(name = "received")]
+    Received,
+    #[value(name = "quantity")]
+    Quantity,
+    #[value(name = "reportable")]
+    Reportable,
+    #[value(name = "cmpl-status")]
+    CmplStatus,
+    #[value(name = "author")]
+    Author,
+    #[value(name = "created")]
+    Created,
+}
+
+impl std::fmt::Display for ListColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListColumn::Id => write!(f, "id"),
+            ListColumn::Title => write!(f, "title"),
+            ListColumn::Type => write!(f, "type"),
+            ListColumn::Customer => write!(f, "customer"),
+            ListColumn::Received => write!(f, "received"),
+            ListColumn::Quantity => write!(f, "quantity"),
+            ListColumn::Reportable => write!(f, "reportable"),
+            ListColumn::CmplStatus => write!(f, "cmpl-status"),
+            ListColumn::Author => write!(f, "author"),
+            ListColumn::Created => write!(f, "created"),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ListArgs {
+    /// Filter by complaint status
+    #[arg(long, short = 's', default_value = "all")]
+    pub status: CmplStatusFilter,
+
+    /// Filter by complaint type
+    #[arg(long, short = 'T')]
+    pub r#type: Option<CliComplaintType>,
+
+    /// Filter by customer (substring match)
+    #[arg(long)]
+    pub customer: Option<String>,
+
+    /// Show only complaints assessed as reportable
+    #[arg(long)]
+    pub reportable: bool,
+
+    /// Show only complaints without a reportability assessment
+    #[arg(long)]
+    pub needs_assessment: bool,
+
+    /// Search in title, complaint number and RMA number
+    #[arg(long)]
+    pub search: Option<String>,
+
+    /// Columns to display
+    #[arg(long, value_delimiter = ',', default_values_t = vec![
+        ListColumn::Id,
+        ListColumn::Title,
+        ListColumn::Type,
+        ListColumn::Received,
+        ListColumn::Reportable,
+        ListColumn::CmplStatus
+    ])]
+    pub columns: Vec<ListColumn>,
+
+    /// Sort by column
+    #[arg(long, default_value = "received")]
+    pub sort: ListColumn,
+
+    /// Reverse sort order
+    #[arg(long, short = 'r')]
+    pub reverse: bool,
+
+    /// Limit number of results
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Show only count
+    #[arg(long)]
+    pub count: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NewArgs {
+    /// Complaint title (required)
+    #[arg(long, short = 't')]
+    pub title: Option<String>,
+
+    /// Complaint type
+    #[arg(long, short = 'T', default_value = "complaint")]
+    pub r#type: CliComplaintType,
+
+    /// Customer or reporter
+    #[arg(long, short = 'c')]
+    pub customer: Option<String>,
+
+    /// Return merchandise authorization number
+    #[arg(long)]
+    pub rma: Option<String>,
+
+    /// Open in editor after creation
+    #[arg(long, short = 'e')]
+    pub edit: bool,
+
+    /// Skip opening in editor
+    #[arg(long)]
+    pub no_edit: bool,
+
+    /// Interactive mode (prompt for fields)
+    #[arg(long, short = 'i')]
+    pub interactive: bool,
+
+    /// Link to another entity (auto-infers link type)
+    #[arg(long, short = 'L')]
+    pub link: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ShowArgs {
+    /// Complaint ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct EditArgs {
+    /// Complaint ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DeleteArgs {
+    /// Complaint ID (full or short)
+    pub id: String,
+
+    /// Force deletion even if entity is linked
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ArchiveArgs {
+    /// Complaint ID (full or short)
+    pub id: String,
+
+    /// Force archival even if entity is linked
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AssessArgs {
+    /// Complaint ID (full or short)
+    pub id: String,
+
+    /// The complaint is reportable to a regulator
+    #[arg(
+        long,
+        conflicts_with = "not_reportable",
+        required_unless_present = "not_reportable"
+    )]
+    pub reportable: bool,
+
+    /// The complaint is not reportable
+    #[arg(long)]
+    pub not_reportable: bool,
+
+    /// Regulation assessed against (e.g., "21 CFR 803")
+    #[arg(long)]
+    pub regulation: Option<String>,
+
+    /// Rationale for the decision
+    #[arg(long)]
+    pub rationale: Option<String>,
+
+    /// Regulatory report due date (YYYY-MM-DD)
+    #[arg(long)]
+    pub due: Option<chrono::NaiveDate>,
+
+    /// Assessed by (defaults to config author)
+    #[arg(long)]
+    pub assessed_by: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RiskFeedbackArgs {
+    /// Only check this risk
+    #[arg(long)]
+    pub risk: Option<String>,
+
+    /// Units fielded (overrides the population derived from completed lots)
+    #[arg(long)]
+    pub fielded: Option<u32>,
+
+    /// Show only risks whose occurrence rating is contradicted
+    #[arg(long)]
+    pub contradicted: bool,
+}
+
+/// Directories where complaints are stored
+const CMPL_DIRS: &[&str] = &["postmarket/complaints"];
+
+/// Entity configuration for complaint commands
+const ENTITY_CONFIG: crate::cli::EntityConfig = crate::cli::EntityConfig {
+    prefix: EntityPrefix::Cmpl,
+    dirs: CMPL_DIRS,
+    name: "complaint",
+    name_plural: "complaints",
+};
+
+/// Run a complaint command
+pub fn run(cmd: CmplCommands, global: &GlobalOpts) -> Result<()> {
+    match cmd {
+        CmplCommands::List(args) => run_list(args, global),
+        CmplCommands::New(args) => run_new(args, global),
+        CmplCommands::Show(args) => run_show(args, global),
+        CmplCommands::Edit(args) => run_edit(args),
+        CmplCommands::Delete(args) => run_delete(args),
+        CmplCommands::Archive(args) => run_archive(args),
+        CmplCommands::Assess(args) => run_assess(args, global),
+        CmplCommands::RiskFeedback(args) => run_risk_feedback(args, global),
+    }
+}
+
+/// Reportability as a short label
+fn reportable_label(cmpl: &Complaint) -> &'static str {
+    match cmpl.reportability.reportable {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "pending",
+    }
+}
+
+/// List complaints
+fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let cmpl_dir = project.root().join("postmarket/complaints");
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let complaints: Vec<Complaint> = loader::load_all(&cmpl_dir)?;
+
+    let mut complaints: Vec<Complaint> = complaints
+        .into_iter()
+        .filter(|c| match args.status {
+            CmplStatusFilter::Received => c.complaint_status == ComplaintStatus::Received,
+            CmplStatusFilter::Investigating => c.complaint_status == ComplaintStatus::Investigating,
+            CmplStatusFilter::Resolved => c.complaint_status == ComplaintStatus::Resolved,
+            CmplStatusFilter::Closed => c.complaint_status == ComplaintStatus::Closed,
+            CmplStatusFilter::Open => matches!(
+                c.complaint_status,
+                ComplaintStatus::Received | ComplaintStatus::Investigating
+            ),
+            CmplStatusFilter::All => true,
+        })
+        .filter(|c| {
+            args.r#type
+                .map(|t| c.complaint_type == ComplaintType::from(t))
+                .unwrap_or(true)
+        })
+        .filter(|c| {
+            args.customer
+                .as_ref()
+                .map(|cust| {
+                    c.customer
+                        .as_ref()
+                        .is_some_and(|n| n.to_lowercase().contains(&cust.to_lowercase()))
+                })
+                .unwrap_or(true)
+        })
+        .filter(|c| !args.reportable || c.reportability.reportable == Some(true))
+        .filter(|c| !args.needs_assessment || c.needs_assessment())
+        .filter(|c| {
+            args.search
+                .as_ref()
+                .map(|s| {
+                    let search = s.to_lowercase();
+                    c.title.to_lowercase().contains(&search)
+                        || [&c.complaint_number, &c.rma_number].iter().any(|n| {
+                            n.as_ref()
+                                .is_some_and(|n| n.to_lowercase().contains(&search))
+                        })
+                })
+                .unwrap_or(true)
+        })
+        .collect();
+
+    // Sort
+    complaints.sort_by(|a, b| match args.sort {
+        ListColumn::Id => a.id.to_string().cmp(&b.id.to_string()),
+        ListColumn::Title => a.title.cmp(&b.title),
+        ListColumn::Type => a
+            .complaint_type
+            .to_string()
+            .cmp(&b.complaint_type.to_string()),
+        ListColumn::Customer => a.customer.cmp(&b.customer),
+        ListColumn::Received => a.date().cmp(&b.date()),
+        ListColumn::Quantity => a.quantity.cmp(&b.quantity),
+        ListColumn::Reportable => reportable_label(a).cmp(reportable_label(b)),
+        ListColumn::CmplStatus => a
+            .complaint_status
+            .to_string()
+            .cmp(&b.complaint_status.to_string()),
+        ListColumn::Author => a.author.cmp(&b.author),
+        ListColumn::Created => a.created.cmp(&b.created),
+    });
+
+    if args.reverse {
+        complaints.reverse();
+    }
+
+    if let Some(limit) = args.limit {
+        complaints.truncate(limit);
+    }
+
+    if args.count {
+        println!("{}", complaints.len());
+        return Ok(());
+    }
+
+    if complaints.is_empty() {
+        println!("No complaints found.");
+        return Ok(());
+    }
+
+    // Update short ID index
+    let mut short_ids = ShortIdIndex::load(&project);
+    short_ids.ensure_all(complaints.iter().map(|c| c.id.to_string()));
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    let column_value = |cmpl: &Complaint, col: &ListColumn, short_id: &str| -> String {
+        match col {
+            ListColumn::Id => short_id.to_string(),
+            ListColumn::Title => cmpl.title.clone(),
+            ListColumn::Type => cmpl.complaint_type.to_string(),
+            ListColumn::Customer => cmpl.customer.clone().unwrap_or_default(),
+            ListColumn::Received => cmpl.date().to_string(),
+            ListColumn::Quantity => cmpl.quantity.to_string(),
+            ListColumn::Reportable => reportable_label(cmpl).to_string(),
+            ListColumn::CmplStatus => cmpl.complaint_status.to_string(),
+            ListColumn::Author => cmpl.author.clone(),
+            ListColumn::Created => cmpl.created.format("%Y-%m-%d").to_string(),
+        }
+    };
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&complaints).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yml::to_string(&complaints).into_diagnostic()?;
+            print!("{}", yaml);
+        }
+        OutputFormat::Csv => {
+            println!(
+                "short_id,id,title,type,customer,received,quantity,reportable,cmpl_status,author"
+            );
+            for cmpl in &complaints {
+                let short_id = short_ids
+                    .get_short_id(&cmpl.id.to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    short_id,
+                    cmpl.id,
+                    escape_csv(&cmpl.title),
+                    cmpl.complaint_type,
+                    escape_csv(cmpl.customer.as_deref().unwrap_or("")),
+                    cmpl.date(),
+                    cmpl.quantity,
+                    reportable_label(cmpl),
+                    cmpl.complaint_status,
+                    escape_csv(&cmpl.author)
+                );
+            }
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            let mut headers = vec![];
+            let mut widths = vec![];
+
+            for col in &args.columns {
+                let (header, width) = match col {
+                    ListColumn::Id => ("ID", 17),
+                    ListColumn::Title => ("TITLE", 30),
+                    ListColumn::Type => ("TYPE", 14),
+                    ListColumn::Customer => ("CUSTOMER", 20),
+                    ListColumn::Received => ("RECEIVED", 12),
+                    ListColumn::Quantity => ("QTY", 5),
+                    ListColumn::Reportable => ("REPORTABLE", 11),
+                    ListColumn::CmplStatus => ("STATUS", 14),
+                    ListColumn::Author => ("AUTHOR", 16),
+                    ListColumn::Created => ("CREATED", 12),
+                };
+                headers.push((header, *col));
+                widths.push(width);
+            }
+
+            print!("{:<8} ", style("SHORT").bold().dim());
+            for (i, (header, _)) in headers.iter().enumerate() {
+                print!("{:<width$} ", style(header).bold(), width = widths[i]);
+            }
+            println!();
+
+            for cmpl in &complaints {
+                let short_id = short_ids
+                    .get_short_id(&cmpl.id.to_string())
+                    .unwrap_or_default();
+
+                print!("{:<8} ", style(&short_id).cyan());
+
+                for (i, (_, col)) in headers.iter().enumerate() {
+                    let value = match col {
+                        ListColumn::Title => truncate_str(&cmpl.title, widths[i]),
+                        ListColumn::Id => cmpl.id.to_string(),
+                        ListColumn::Reportable => match cmpl.reportability.reportable {
+                            Some(true) => format!("{}", style("yes").red()),
+                            Some(false) => format!("{}", style("no").dim()),
+                            None => format!("{}", style("pending").yellow()),
+                        },
+                        _ => column_value(cmpl, col, &short_id),
+                    };
+                    print!("{:<width$} ", value, width = widths[i]);
+                }
+                println!();
+            }
+        }
+        OutputFormat::Md => {
+            let headers: Vec<&str> = args
+                .columns
+                .iter()
+                .map(|c| match c {
+                    ListColumn::Id => "ID",
+                    ListColumn::Title => "Title",
+                    ListColumn::Type => "Type",
+                    ListColumn::Customer => "Customer",
+                    ListColumn::Received => "Received",
+                    ListColumn::Quantity => "Qty",
+                    ListColumn::Reportable => "Reportable",
+                    ListColumn::CmplStatus => "Status",
+                    ListColumn::Author => "Author",
+                    ListColumn::Created => "Created",
+                })
+                .collect();
+            println!("| {} |", headers.join(" | "));
+            println!(
+                "| {} |",
+                headers
+                    .iter()
+                    .map(|_| "---")
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            );
+
+            for cmpl in &complaints {
+                let short_id = short_ids
+                    .get_short_id(&cmpl.id.to_string())
+                    .unwrap_or_default();
+                let values: Vec<String> = args
+                    .columns
+                    .iter()
+                    .map(|c| match c {
+                        ListColumn::Title => truncate_str(&cmpl.title, 40),
+                        _ => column_value(cmpl, c, &short_id),
+                    })
+                    .collect();
+                println!("| {} |", values.join(" | "));
+            }
+        }
+        OutputFormat::Id => {
+            for cmpl in &complaints {
+                println!("{}", cmpl.id);
+            }
+        }
+        OutputFormat::ShortId => {
+            for cmpl in &complaints {
+                let short_id = short_ids
+                    .get_short_id(&cmpl.id.to_string())
+                    .unwrap_or_default();
+                println!("{}", short_id);
+            }
+        }
+        OutputFormat::Path => {
+            for cmpl in &complaints {
+                let path = cmpl_dir.join(format!("{}.tdt.yaml", cmpl.id));
+                println!("{}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a new complaint
+fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+
+    let title: String;
+    let complaint_type: ComplaintType;
+    let customer: Option<String>;
+
+    if args.interactive {
+        let wizard = SchemaWizard::new();
+        let result = wizard.run(EntityPrefix::Cmpl)?;
+
+        title = result
+            .get_string("title")
+            .map(String::from)
+            .unwrap_or_else(|| "New Complaint".to_string());
+        complaint_type = result
+            .get_string("complaint_type")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default();
+        customer = result.get_string("customer").map(String::from);
+    } else {
+        title = args.title.unwrap_or_else(|| "New Complaint".to_string());
+        complaint_type = ComplaintType::from(args.r#type);
+        customer = args.customer;
+    }
+
+    let id = EntityId::new(EntityPrefix::Cmpl);
+
+    let generator = TemplateGenerator::new().map_err(|e| miette::miette!("{}", e))?;
+    let mut ctx = TemplateContext::new(id.clone(), config.author())
+        .with_title(&title)
+        .with_complaint_type(complaint_type.to_string());
+
+    if let Some(ref customer) = customer {
+        ctx = ctx.with_customer(customer);
+    }
+    if let Some(ref rma) = args.rma {
+        ctx = ctx.with_rma_number(rma);
+    }
+
+    let yaml_content = generator
+        .generate_complaint(&ctx)
+        .map_err(|e| miette::miette!("{}", e))?;
+
+    let output_dir = project.root().join("postmarket/complaints");
+    if !output_dir.exists() {
+        fs::create_dir_all(&output_dir).into_diagnostic()?;
+    }
+
+    let file_path = output_dir.join(format!("{}.tdt.yaml", id));
+    fs::write(&file_path, &yaml_content).into_diagnostic()?;
+
+    let mut short_ids = ShortIdIndex::load(&project);
+    let short_id = short_ids.add(id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    // Handle --link flags
+    let _added_links = crate::cli::entity_cmd::process_link_flags(
+        &file_path,
+        EntityPrefix::Cmpl,
+        &args.link,
+        &short_ids,
+    );
+
+    if !global.quiet {
+        let id_str = id.to_string();
+        let display_id = short_id.as_deref().unwrap_or(&id_str);
+        println!(
+            "{} Created complaint {}",
+            style("✓").green(),
+            style(display_id).cyan()
+        );
+        println!("  {}", file_path.display());
+    }
+
+    if args.edit && !args.no_edit {
+        println!(
+            "Opening {} in {}...",
+            style(file_path.display()).cyan(),
+            style(config.editor()).yellow()
+        );
+        config.run_editor(&file_path).into_diagnostic()?;
+    }
+
+    Ok(())
+}
+
+/// Show complaint details
+fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.id)
+        .unwrap_or_else(|| args.id.clone());
+
+    let cmpl_dir = project.root().join("postmarket/complaints");
+    let (path, cmpl) = loader::load_entity::<Complaint>(&cmpl_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No complaint found matching '{}'", args.id))?;
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let short_id = short_ids
+        .get_short_id(&cmpl.id.to_string())
+        .unwrap_or_default();
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&cmpl).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let content = fs::read_to_string(&path).into_diagnostic()?;
+            println!("{}", content);
+        }
+        OutputFormat::Csv => {
+            println!(
+                "id,title,type,customer,received,quantity,reportable,cmpl_status,author,created"
+            );
+            println!(
+                "{},{},{},{},{},{},{},{},{},{}",
+                escape_csv(&short_id),
+                escape_csv(&cmpl.title),
+                cmpl.complaint_type,
+                escape_csv(cmpl.customer.as_deref().unwrap_or("")),
+                cmpl.date(),
+                cmpl.quantity,
+                reportable_label(&cmpl),
+                cmpl.complaint_status,
+                escape_csv(&cmpl.author),
+                cmpl.created.format("%Y-%m-%d")
+            );
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Md
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            println!("{}", style("Complaint").bold());
+            println!("{}", style("─".repeat(60)).dim());
+            println!("  {} {}", style("ID:").dim(), style(&short_id).cyan());
+            println!("  {} {}", style("Title:").dim(), cmpl.title);
+            if let Some(ref num) = cmpl.complaint_number {
+                println!("  {} {}", style("Number:").dim(), num);
+            }
+            if let Some(ref rma) = cmpl.rma_number {
+                println!("  {} {}", style("RMA:").dim(), rma);
+            }
+            println!("  {} {}", style("Type:").dim(), cmpl.complaint_type);
+            if let Some(ref customer) = cmpl.customer {
+                println!("  {} {}", style("Customer:").dim(), customer);
+            }
+            println!("  {} {}", style("Received:").dim(), cmpl.date());
+            println!("  {} {}", style("Quantity:").dim(), cmpl.quantity);
+            if !cmpl.serials.is_empty() {
+                println!("  {} {}", style("Serials:").dim(), cmpl.serials.join(", "));
+            }
+            println!("  {} {}", style("Status:").dim(), cmpl.complaint_status);
+
+            if let Some(ref failure) = cmpl.failure {
+                println!();
+                println!("{}", style("Failure").bold());
+                println!("{}", style("─".repeat(60)).dim());
+                if let Some(ref mode) = failure.failure_mode {
+                    println!("  {} {}", style("Mode:").dim(), mode);
+                }
+                if let Some(date) = failure.event_date {
+                    println!("  {} {}", style("Event Date:").dim(), date);
+                }
+                if let Some(hours) = failure.operating_hours {
+                    println!("  {} {}", style("Operating Hours:").dim(), hours);
+                }
+                if let Some(ref env) = failure.environment {
+                    println!("  {} {}", style("Environment:").dim(), env);
+                }
+                if failure.injury {
+                    println!("  {} {}", style("Injury:").dim(), style("yes").red());
+                }
+                let confirmed = match failure.confirmed {
+                    Some(true) => style("confirmed").red(),
+                    Some(false) => style("no fault found").green(),
+                    None => style("not analyzed").yellow(),
+                };
+                println!("  {} {}", style("Analysis:").dim(), confirmed);
+                if let Some(ref analysis) = failure.analysis {
+                    for line in analysis.lines() {
+                        println!("    {}", line);
+                    }
+                }
+            }
+
+            println!();
+            println!("{}", style("Reportability").bold());
+            println!("{}", style("─".repeat(60)).dim());
+            let rep = &cmpl.reportability;
+            let reportable = match rep.reportable {
+                Some(true) => style("reportable").red(),
+                Some(false) => style("not reportable").green(),
+                None => style("not assessed").yellow(),
+            };
+            println!("  {} {}", style("Decision:").dim(), reportable);
+            if let Some(ref regulation) = rep.regulation {
+                println!("  {} {}", style("Regulation:").dim(), regulation);
+            }
+            if let Some(ref rationale) = rep.rationale {
+                println!("  {} {}", style("Rationale:").dim(), rationale);
+            }
+            if let Some(ref by) = rep.assessed_by {
+                println!("  {} {}", style("Assessed By:").dim(), by);
+            }
+            if let Some(due) = rep.report_due {
+                let today = chrono::Local::now().date_naive();
+                if cmpl.report_overdue(today) {
+                    println!(
+                        "  {} {}",
+                        style("Report Due:").dim(),
+                        style(format!("{} (overdue)", due)).red()
+                    );
+                } else {
+                    println!("  {} {}", style("Report Due:").dim(), due);
+                }
+            }
+            if let Some(date) = rep.submitted_date {
+                println!("  {} {}", style("Submitted:").dim(), date);
+            }
+
+            let links = &cmpl.links;
+            let linked: Vec<(&str, Vec<String>)> = vec![
+                (
+                    "Lots:",
+                    links.lots.iter().map(|id| id.to_string()).collect(),
+                ),
+                (
+                    "Components:",
+                    links.components.iter().map(|id| id.to_string()).collect(),
+                ),
+                (
+                    "Assemblies:",
+                    links.assemblies.iter().map(|id| id.to_string()).collect(),
+                ),
+                (
+                    "Risks:",
+                    links.risks.iter().map(|id| id.to_string()).collect(),
+                ),
+                (
+                    "NCRs:",
+                    links.ncrs.iter().map(|id| id.to_string()).collect(),
+                ),
+                (
+                    "CAPA:",
+                    links.capa.iter().map(|id| id.to_string()).collect(),
+                ),
+            ];
+            if linked.iter().any(|(_, ids)| !ids.is_empty()) {
+                println!();
+                println!("{}", style("Links").bold());
+                println!("{}", style("─".repeat(60)).dim());
+                for (label, ids) in linked.iter().filter(|(_, ids)| !ids.is_empty()) {
+                    let display: Vec<String> = ids
+                        .iter()
+                        .map(|id| short_ids.get_short_id(id).unwrap_or_else(|| id.clone()))
+                        .collect();
+                    println!("  {} {}", style(label).dim(), display.join(", "));
+                }
+            }
+
+            if let Some(ref desc) = cmpl.description {
+                println!();
+                println!("{}", style("Description").bold());
+                println!("{}", style("─".repeat(60)).dim());
+                for line in desc.lines() {
+                    println!("  {}", line);
+                }
+            }
+
+            println!();
+            println!("{}", style("─".repeat(60)).dim());
+            println!(
+                "{}: {} | {}: {} | {}: {}",
+                style("Author").dim(),
+                cmpl.author,
+                style("Created").dim(),
+                cmpl.created.format("%Y-%m-%d %H:%M"),
+                style("Revision").dim(),
+                cmpl.entity_revision
+            );
+        }
+        OutputFormat::Id => {
+            println!("{}", cmpl.id);
+        }
+        OutputFormat::ShortId => {
+            println!("{}", short_id);
+        }
+        OutputFormat::Path => {
+            println!("{}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Edit a complaint
+fn run_edit(args: EditArgs) -> Result<()> {
+    crate::cli::entity_cmd::run_edit_generic(&args.id, &ENTITY_CONFIG)
+}
+
+/// Delete a complaint
+fn run_delete(args: DeleteArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, CMPL_DIRS, args.force, false, args.quiet)
+}
+
+/// Archive a complaint
+fn run_archive(args: ArchiveArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, CMPL_DIRS, args.force, true, args.quiet)
+}
+
+/// Record the reportability assessment
+fn run_assess(args: AssessArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.id)
+        .unwrap_or_else(|| args.id.clone());
+
+    let cmpl_dir = project.root().join("postmarket/complaints");
+    let (path, mut cmpl) = loader::load_entity::<Complaint>(&cmpl_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No complaint found matching '{}'", args.id))?;
+
+    if !args.reportable && args.due.is_some() {
+        return Err(miette::miette!(
+            "--due only applies to reportable complaints"
+        ));
+    }
+
+    let rep = &mut cmpl.reportability;
+    rep.reportable = Some(args.reportable);
+    rep.assessed_by = Some(args.assessed_by.unwrap_or_else(|| config.author()));
+    rep.assessed_date = Some(chrono::Local::now().date_naive());
+    if args.regulation.is_some() {
+        rep.regulation = args.regulation;
+    }
+    if args.rationale.is_some() {
+        rep.rationale = args.rationale;
+    }
+    if args.due.is_some() {
+        rep.report_due = args.due;
+    }
+
+    let updated_content = serde_yml::to_string(&cmpl).into_diagnostic()?;
+    fs::write(&path, updated_content).into_diagnostic()?;
+
+    if !global.quiet {
+        let short_id = short_ids
+            .get_short_id(&cmpl.id.to_string())
+            .unwrap_or_default();
+        let decision = if args.reportable {
+            style("reportable").red()
+        } else {
+            style("not reportable").green()
+        };
+        println!(
+            "{} Assessed complaint {} as {}",
+            style("✓").green(),
+            style(&short_id).cyan(),
+            decision
+        );
+        if args.reportable && cmpl.reportability.report_due.is_none() {
+            println!(
+                "  {} No report due date set (use --due)",
+                style("!").yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare field failure rates with FMEA occurrence ratings
+fn run_risk_feedback(args: RiskFeedbackArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let root = project.root();
+
+    let complaints: Vec<Complaint> = loader::load_all(&root.join("postmarket/complaints"))?;
+    let lots: Vec<Lot> = loader::load_all(&root.join("manufacturing/lots"))?;
+    let mut risks = crate::cli::commands::report::load_all_risks(&project);
+
+    if let Some(ref risk) = args.risk {
+        let resolved = short_ids.resolve(risk).unwrap_or_else(|| risk.clone());
+        risks.retain(|r| r.id.to_string() == resolved);
+        if risks.is_empty() {
+            return Err(miette::miette!("No risk found matching '{}'", risk));
+        }
+    }
+
+    let mut checks = field_feedback::check_risks(&risks, &complaints, &lots, args.fielded);
+    if args.contradicted {
+        checks.retain(|c| c.contradicted());
+    }
+
+    match global.output {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&checks).into_diagnostic()?;
+            println!("{}", json);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yml::to_string(&checks).into_diagnostic()?;
+            print!("{}", yaml);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if checks.is_empty() {
+        println!("No risks with linked complaints found.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:<28} {:>4} {:>8} {:>8} {:>10} {:>4}  {}",
+        style("SHORT").bold().dim(),
+        style("RISK").bold(),
+        style("O").bold(),
+        style("FAILED").bold(),
+        style("FIELDED").bold(),
+        style("RATE").bold(),
+        style("OBS").bold(),
+        style("VERDICT").bold()
+    );
+    for check in &checks {
+        let short_id = short_ids
+            .get_short_id(&check.risk)
+            .unwrap_or_else(|| check.risk.clone());
+        let rate = check
+            .observed_rate
+            .map(|r| format!("{:.4}%", r * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        let verdict = match check.verdict {
+            FieldVerdict::Understated => style(check.verdict.to_string()).red().bold(),
+            FieldVerdict::Overstated => style(check.verdict.to_string()).yellow(),
+            FieldVerdict::Consistent => style(check.verdict.to_string()).green(),
+            FieldVerdict::NoPopulation | FieldVerdict::NotRated => {
+                style(check.verdict.to_string()).dim()
+            }
+        };
+        println!(
+            "{:<8} {:<28} {:>4} {:>8} {:>8} {:>10} {:>4}  {}",
+            style(&short_id).cyan(),
+            truncate_str(&check.title, 28),
+            check
+                .occurrence
+                .map(|o| o.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            check.failures,
+            check.units_fielded,
+            rate,
+            check
+                .observed_occurrence
+                .map(|o| o.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            verdict
+        );
+    }
+
+    let contradicted: Vec<_> = checks.iter().filter(|c| c.contradicted()).collect();
+    if !contradicted.is_empty() {
+        println!();
+        for check in contradicted {
+            let short_id = short_ids
+                .get_short_id(&check.risk)
+                .unwrap_or_else(|| check.risk.clone());
+            println!(
+                "{} {}: field data supports occurrence {} but FMEA rates {}",
+                style("!").red(),
+                style(&short_id).cyan(),
+                check.observed_occurrence.unwrap_or_default(),
+                check.occurrence.unwrap_or_default()
+            );
+        }
+    }
+    if checks
+        .iter()
+        .any(|c| c.verdict == FieldVerdict::NoPopulation)
+    {
+        println!();
+        println!(
+            "{} Some risks have no fielded population; link completed lots to the affected product or pass --fielded",
+            style("→").blue()
+        );
+    }
+
+    Ok(())
+}
@@ -11,10 +11,18 @@ use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, Table
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::EntityCache;
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
+use crate::core::manufacturing::lot_sampling_history;
 use crate::core::project::Project;
+use crate::core::receiving;
+use crate::core::sampling::{switching_state, PlanSpec};
 use crate::core::shortid::ShortIdIndex;
 use crate::core::Config;
-use crate::entities::control::{Control, ControlType};
+use crate::entities::control::{
+    Control, ControlType, InspectionLevel, InspectionSeverity, SamplingScheme,
+};
+use crate::entities::lot::Lot;
+use crate::entities::receipt::Receipt;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -37,6 +45,9 @@ pub enum CtrlCommands {
 
     /// Archive a control item (soft delete)
     Archive(ArchiveArgs),
+
+    /// Compute the acceptance sampling plan for a lot (ANSI Z1.4 / ISO 2859-1)
+    Sampling(SamplingArgs),
 }
 
 /// Control type filter
@@ -170,6 +181,18 @@ pub struct NewArgs {
     #[arg(long)]
     pub critical: bool,
 
+    /// AQL for lot acceptance sampling (ANSI Z1.4 / ISO 2859-1)
+    #[arg(long)]
+    pub aql: Option<f64>,
+
+    /// Inspection level for the AQL plan: s1, s2, s3, s4, i, ii, iii
+    #[arg(long, requires = "aql")]
+    pub level: Option<String>,
+
+    /// Sampling scheme for the AQL plan: single, c0, variables
+    #[arg(long, requires = "aql")]
+    pub scheme: Option<String>,
+
     /// Open in editor after creation
     #[arg(long, short = 'e')]
     pub edit: bool,
@@ -227,6 +250,45 @@ pub struct ArchiveArgs {
     pub quiet: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct SamplingArgs {
+    /// Control ID or short ID (CTRL@N); omit to compute from --aql alone
+    pub id: Option<String>,
+
+    /// Lot quantity
+    #[arg(long)]
+    pub lot_size: u32,
+
+    /// AQL (overrides the control's)
+    #[arg(long)]
+    pub aql: Option<f64>,
+
+    /// Inspection level: s1, s2, s3, s4, i, ii, iii (default: ii)
+    #[arg(long)]
+    pub level: Option<String>,
+
+    /// Sampling scheme: single, c0, variables (default: single)
+    #[arg(long)]
+    pub scheme: Option<String>,
+
+    /// Inspection severity: normal, tightened, reduced
+    /// (default: from the switching rules for a control, otherwise normal)
+    #[arg(long)]
+    pub severity: Option<String>,
+
+    /// Only count receipts from this supplier for the switching rules
+    #[arg(long)]
+    pub supplier: Option<String>,
+
+    /// Print the operating characteristic (OC) curve
+    #[arg(long)]
+    pub oc: bool,
+
+    /// Number of points on the OC curve
+    #[arg(long, default_value = "11")]
+    pub points: usize,
+}
+
 /// Directories where controls are stored
 const CONTROL_DIRS: &[&str] = &["manufacturing/controls"];
 
@@ -247,6 +309,7 @@ pub fn run(cmd: CtrlCommands, global: &GlobalOpts) -> Result<()> {
         CtrlCommands::Edit(args) => run_edit(args),
         CtrlCommands::Delete(args) => run_delete(args),
         CtrlCommands::Archive(args) => run_archive(args),
+        CtrlCommands::Sampling(args) => run_sampling(args, global),
     }
 }
 
@@ -611,6 +674,24 @@ fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
         .parse::<ControlType>()
         .map_err(|e| miette::miette!("{}", e))?;
 
+    // Validate the AQL plan
+    let level = args
+        .level
+        .as_deref()
+        .map(str::parse::<InspectionLevel>)
+        .transpose()
+        .map_err(|e| miette::miette!("{}", e))?;
+    let scheme = args
+        .scheme
+        .as_deref()
+        .map(str::parse::<SamplingScheme>)
+        .transpose()
+        .map_err(|e| miette::miette!("{}", e))?;
+    if let Some(aql) = args.aql {
+        PlanSpec::new(aql, level.unwrap_or_default(), scheme.unwrap_or_default())
+            .map_err(|e| miette::miette!("{}", e))?;
+    }
+
     // Generate ID
     let id = EntityId::new(EntityPrefix::Ctrl);
 
@@ -646,6 +727,31 @@ fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
         .generate_control(&ctx)
         .map_err(|e| miette::miette!("{}", e))?;
 
+    if let Some(aql) = args.aql {
+        let mut sampling = format!("sampling:\n  type: lot\n  aql: {}\n", aql);
+        if let Some(level) = level {
+            sampling.push_str(&format!(
+                "  inspection_level: {}\n",
+                serde_yml::to_string(&level).into_diagnostic()?.trim()
+            ));
+        }
+        if let Some(scheme) = scheme {
+            sampling.push_str(&format!(
+                "  scheme: {}\n",
+                serde_yml::to_string(&scheme).into_diagnostic()?.trim()
+            ));
+        }
+        yaml_content = yaml_content.replace(
+            "sampling:\n  type: continuous\n  frequency: \"5 parts\"\n  sample_size: 1\n  \
+             # AQL-based plan (ANSI Z1.4 / ISO 2859-1): sample size and accept/reject\n  \
+             # numbers then follow the lot quantity\n  \
+             # aql: 1.0\n  \
+             # inspection_level: ii      # s1, s2, s3, s4, i, ii, iii\n  \
+             # scheme: single            # single, zero_acceptance, variables\n",
+            &sampling,
+        );
+    }
+
     // Apply wizard values via string replacement (for interactive mode)
     if args.interactive {
         if let Some(ref desc) = description {
@@ -838,6 +944,17 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                 if let Some(size) = sampling.sample_size {
                     println!("  Sample Size: {}", size);
                 }
+                if let Some(aql) = sampling.aql {
+                    println!(
+                        "  AQL: {} (level {}, {})",
+                        aql,
+                        sampling.inspection_level.unwrap_or_default(),
+                        sampling.scheme.unwrap_or_default()
+                    );
+                }
+                if let Some(severity) = sampling.severity {
+                    println!("  Severity: {} (fixed)", severity);
+                }
             }
 
             // Measurement info
@@ -909,3 +1026,183 @@ fn run_delete(args: DeleteArgs) -> Result<()> {
 fn run_archive(args: ArchiveArgs) -> Result<()> {
     crate::cli::commands::utils::run_delete(&args.id, CONTROL_DIRS, args.force, true, args.quiet)
 }
+
+/// Compute a sampling plan for a lot from a control's sampling or from --aql
+fn run_sampling(args: SamplingArgs, global: &GlobalOpts) -> Result<()> {
+    let parse_err = |e: String| miette::miette!("{}", e);
+
+    let mut ctrl_label = None;
+    let mut base: Option<PlanSpec> = None;
+    let mut history = Vec::new();
+    if let Some(ref id) = args.id {
+        let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+        let short_ids = ShortIdIndex::load(&project);
+        let resolved = short_ids.resolve(id).unwrap_or_else(|| id.clone());
+        let (_, ctrl) = loader::load_entity::<Control>(
+            &project.root().join("manufacturing/controls"),
+            &resolved,
+        )?
+        .ok_or_else(|| miette::miette!("No control found matching '{}'", id))?;
+
+        base = ctrl
+            .sampling
+            .as_ref()
+            .map(PlanSpec::from_sampling)
+            .transpose()
+            .map_err(parse_err)?
+            .flatten();
+
+        // Process controls switch on earlier lots, incoming controls on receipts
+        history = if ctrl.links.process.is_some() {
+            let lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+            let lots: Vec<&Lot> = lots.iter().collect();
+            lot_sampling_history(&ctrl, &lots)
+        } else {
+            let receipts: Vec<Receipt> =
+                loader::load_all(&project.root().join("manufacturing/receipts"))?;
+            let supplier = args
+                .supplier
+                .as_ref()
+                .map(|s| short_ids.resolve(s).unwrap_or_else(|| s.clone()));
+            receiving::receipt_history(&receipts, &ctrl.id.to_string(), supplier.as_deref())
+        };
+
+        ctrl_label = Some(format!(
+            "{} \"{}\"",
+            short_ids
+                .get_short_id(&ctrl.id.to_string())
+                .unwrap_or_else(|| ctrl.id.to_string()),
+            ctrl.title
+        ));
+    }
+
+    let aql = args
+        .aql
+        .or(base.map(|b| b.aql))
+        .ok_or_else(|| miette::miette!("No AQL: set sampling.aql on the control or pass --aql"))?;
+    let level = match args.level {
+        Some(ref l) => l.parse::<InspectionLevel>().map_err(parse_err)?,
+        None => base.map(|b| b.level).unwrap_or_default(),
+    };
+    let scheme = match args.scheme {
+        Some(ref s) => s.parse::<SamplingScheme>().map_err(parse_err)?,
+        None => base.map(|b| b.scheme).unwrap_or_default(),
+    };
+    let mut spec = PlanSpec::new(aql, level, scheme).map_err(parse_err)?;
+    spec.severity = match args.severity {
+        Some(ref s) => Some(s.parse::<InspectionSeverity>().map_err(parse_err)?),
+        None => base.and_then(|b| b.severity),
+    };
+
+    let state = switching_state(&spec, &history);
+    let plan = spec.plan(args.lot_size, state.severity);
+    let summary = plan.oc_summary();
+    let curve = if args.oc {
+        plan.oc_curve(args.points)
+    } else {
+        Vec::new()
+    };
+
+    match global.output {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let mut value = serde_json::json!({
+                "plan": plan,
+                "switching": state,
+            });
+            if args.oc {
+                value["oc"] = serde_json::json!({ "summary": summary, "curve": curve });
+            }
+            if global.output == OutputFormat::Json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&value).into_diagnostic()?
+                );
+            } else {
+                print!("{}", serde_yml::to_string(&value).into_diagnostic()?);
+            }
+        }
+        _ => {
+            let unit = if aql > 10.0 { " per 100 units" } else { "%" };
+            match ctrl_label {
+                Some(ref label) => println!("{} {}", style("Sampling plan for").bold(), label),
+                None => println!("{}", style("Sampling plan").bold()),
+            }
+            println!("{}", style("─".repeat(50)).dim());
+            println!("  Lot size:     {}", plan.lot_size);
+            println!("  AQL:          {}{}", plan.aql, unit);
+            println!("  Level:        {}", plan.level);
+            println!("  Scheme:       {}", plan.scheme);
+            let severity_note = match (state.reason.as_deref(), state.lots) {
+                (Some(reason), _) => format!(" ({})", reason),
+                (None, 0) => String::new(),
+                (None, lots) => format!(
+                    " (switching score {} after {} lot{})",
+                    state.switching_score,
+                    lots,
+                    if lots == 1 { "" } else { "s" }
+                ),
+            };
+            println!("  Severity:     {}{}", plan.severity, severity_note);
+            println!("  Code letter:  {}", plan.code_letter);
+            println!(
+                "  Sample size:  {}{}",
+                style(plan.sample_size).cyan(),
+                if plan.hundred_percent {
+                    " (100% inspection)"
+                } else {
+                    ""
+                }
+            );
+            if let (Some(ac), Some(re)) = (plan.accept, plan.reject) {
+                println!("  Accept (Ac):  {}", style(ac).green());
+                println!("  Reject (Re):  {}", style(re).red());
+            }
+            if let Some(k) = plan.k {
+                println!(
+                    "  k:            {} (accept when (U - mean)/s and (mean - L)/s >= k)",
+                    style(k).cyan()
+                );
+            }
+            if state.discontinued {
+                eprintln!(
+                    "{} Acceptance inspection discontinued: {}",
+                    style("!").yellow(),
+                    state.reason.as_deref().unwrap_or_default()
+                );
+            }
+
+            if args.oc {
+                println!();
+                println!("{}", style("Operating characteristic").bold());
+                println!("{}", style("─".repeat(50)).dim());
+                println!("  Pa at AQL:    {:.1}%", summary.pa_at_aql * 100.0);
+                println!("  95% accepted: {:.3}{}", summary.p95, unit);
+                println!("  50% accepted: {:.3}{}", summary.p50, unit);
+                println!(
+                    "  10% accepted: {:.3}{} (limiting quality)",
+                    summary.p10, unit
+                );
+                if aql <= 10.0 {
+                    println!("  AOQL:         {:.3}%", summary.aoql);
+                }
+                println!();
+                println!(
+                    "  {:>10}  {:>8}  {:>8}",
+                    style("QUALITY").bold(),
+                    style("PA").bold(),
+                    style("AOQ").bold()
+                );
+                for point in &curve {
+                    println!(
+                        "  {:>10.3}  {:>7.1}%  {:>8.3}",
+                        point.quality,
+                        point.pa * 100.0,
+                        point.aoq
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
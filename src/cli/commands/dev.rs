@@ -8,12 +8,14 @@ use std::fs;
 use crate::cli::helpers::{escape_csv, truncate_str};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
 use crate::core::Config;
 use crate::entities::dev::{
     AuthorizationLevel, Dev, DevStatus, DeviationCategory, DeviationType, RiskLevel,
 };
+use crate::entities::lot::Lot;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -200,6 +202,9 @@ pub enum DevCommands {
 
     /// Expire/close a deviation
     Expire(ExpireArgs),
+
+    /// Report lots processed under deviations that have expired or been rejected
+    Check(CheckArgs),
 }
 
 /// Deviation status filter
@@ -430,6 +435,12 @@ pub struct ExpireArgs {
     pub yes: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    /// Only check lots processed under this deviation
+    pub id: Option<String>,
+}
+
 /// Directories where deviations are stored
 const DEV_DIRS: &[&str] = &["manufacturing/deviations"];
 
@@ -452,6 +463,7 @@ pub fn run(cmd: DevCommands, global: &GlobalOpts) -> Result<()> {
         DevCommands::Archive(args) => run_archive(args),
         DevCommands::Approve(args) => run_approve(args, global),
         DevCommands::Expire(args) => run_expire(args, global),
+        DevCommands::Check(args) => run_check(args, global),
     }
 }
 
@@ -1262,3 +1274,238 @@ fn run_expire(args: ExpireArgs, global: &GlobalOpts) -> Result<()> {
 
     Ok(())
 }
+
+/// A lot that was processed under a deviation that is no longer valid
+#[derive(Debug, serde::Serialize)]
+struct DeviationUse {
+    lot: String,
+    lot_number: Option<String>,
+    /// Step number (1-based), or None when the deviation links the lot directly
+    step: Option<usize>,
+    deviation: String,
+    dev_status: DevStatus,
+    processed: Option<chrono::NaiveDate>,
+    kind: UsageKind,
+    issues: Vec<String>,
+}
+
+/// Why a deviation usage is reported by `dev check`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum UsageKind {
+    /// Rejected or unapproved deviation, or processed outside its window
+    Violation,
+    /// Processed while the deviation was in effect; it has since expired or closed
+    SinceExpired,
+}
+
+impl std::fmt::Display for UsageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageKind::Violation => write!(f, "violation"),
+            UsageKind::SinceExpired => write!(f, "since_expired"),
+        }
+    }
+}
+
+/// Report lots processed under deviations that have expired or been rejected
+fn run_check(args: CheckArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+
+    let today = chrono::Local::now().date_naive();
+
+    let devs: Vec<Dev> = loader::load_all(&project.root().join("manufacturing/deviations"))?;
+    let lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+
+    let only = match args.id {
+        Some(ref query) => {
+            let resolved = short_ids.resolve(query).unwrap_or_else(|| query.clone());
+            let matches: Vec<&Dev> = devs
+                .iter()
+                .filter(|d| d.id.to_string().starts_with(&resolved))
+                .collect();
+            match matches.as_slice() {
+                [dev] => Some(dev.id.to_string()),
+                [] => return Err(miette::miette!("No deviation found matching '{}'", query)),
+                _ => {
+                    return Err(miette::miette!(
+                        "Ambiguous query '{}'. Please be more specific.",
+                        query
+                    ))
+                }
+            }
+        }
+        None => None,
+    };
+
+    // Every (lot, step, deviation) usage: steps citing a deviation, plus
+    // deviations that link a lot directly
+    let mut uses: Vec<(&Lot, Option<usize>, &Dev, Option<chrono::NaiveDate>)> = Vec::new();
+    for lot in &lots {
+        let lot_id = lot.id.to_string();
+        for (i, step) in lot.execution.iter().enumerate() {
+            if let Some(dev) = step
+                .deviation
+                .as_ref()
+                .and_then(|d| devs.iter().find(|dev| dev.id.to_string() == *d))
+            {
+                uses.push((
+                    lot,
+                    Some(i + 1),
+                    dev,
+                    step.completed_date.or(step.started_date),
+                ));
+            }
+        }
+        for dev in devs.iter().filter(|d| d.links.lots.contains(&lot_id)) {
+            if !uses
+                .iter()
+                .any(|(l, _, d, _)| l.id == lot.id && d.id == dev.id)
+            {
+                uses.push((lot, None, dev, lot.start_date));
+            }
+        }
+    }
+
+    let mut findings: Vec<DeviationUse> = Vec::new();
+    for (lot, step, dev, processed) in uses {
+        if only.as_ref().is_some_and(|o| dev.id.to_string() != *o) {
+            continue;
+        }
+        // Misuse is a violation; lots processed while the deviation was in
+        // effect are listed separately once it has expired or closed
+        let mut issues = Vec::new();
+        match dev.dev_status {
+            DevStatus::Rejected => issues.push("deviation was rejected".to_string()),
+            DevStatus::Pending => issues.push("deviation is pending approval".to_string()),
+            _ => {}
+        }
+        if let Some(date) = processed {
+            let outside = dev.effective_date.is_some_and(|d| date < d)
+                || dev.expiration_date.is_some_and(|d| date > d);
+            if outside {
+                issues.push(format!("processed {} outside effective window", date));
+            }
+        }
+        let kind = if !issues.is_empty() {
+            UsageKind::Violation
+        } else if matches!(dev.dev_status, DevStatus::Expired | DevStatus::Closed) {
+            issues.push(format!("processed in effect, since {}", dev.dev_status));
+            UsageKind::SinceExpired
+        } else if let Some(expired) = dev.expiration_date.filter(|d| *d < today) {
+            issues.push(format!("processed in effect, since expired on {}", expired));
+            UsageKind::SinceExpired
+        } else {
+            continue;
+        };
+        findings.push(DeviationUse {
+            lot: lot.id.to_string(),
+            lot_number: lot.lot_number.clone(),
+            step,
+            deviation: dev.id.to_string(),
+            dev_status: dev.dev_status,
+            processed,
+            kind,
+            issues,
+        });
+    }
+    // Violations first
+    findings.sort_by_key(|f| f.kind != UsageKind::Violation);
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&findings).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&findings).into_diagnostic()?);
+        }
+        OutputFormat::Csv => {
+            println!("lot,lot_number,step,deviation,dev_status,processed,kind,issues");
+            for f in &findings {
+                println!(
+                    "{},{},{},{},{},{},{},{}",
+                    f.lot,
+                    escape_csv(f.lot_number.as_deref().unwrap_or("")),
+                    f.step.map(|s| s.to_string()).unwrap_or_default(),
+                    f.deviation,
+                    f.dev_status,
+                    f.processed.map(|d| d.to_string()).unwrap_or_default(),
+                    f.kind,
+                    escape_csv(&f.issues.join("; "))
+                );
+            }
+        }
+        OutputFormat::Id | OutputFormat::ShortId => {
+            for f in &findings {
+                match global.output {
+                    OutputFormat::ShortId => println!(
+                        "{}",
+                        short_ids
+                            .get_short_id(&f.lot)
+                            .unwrap_or_else(|| f.lot.clone())
+                    ),
+                    _ => println!("{}", f.lot),
+                }
+            }
+        }
+        _ => {
+            if findings.is_empty() {
+                println!(
+                    "{} No lots processed under expired or rejected deviations",
+                    style("✓").green()
+                );
+                return Ok(());
+            }
+            println!(
+                "{:<10} {:<14} {:<5} {:<10} {:<10} {:<11} {}",
+                style("LOT").bold(),
+                style("LOT #").bold(),
+                style("STEP").bold(),
+                style("DEV").bold(),
+                style("STATUS").bold(),
+                style("PROCESSED").bold(),
+                style("ISSUE").bold()
+            );
+            for f in &findings {
+                let lot_short = short_ids
+                    .get_short_id(&f.lot)
+                    .unwrap_or_else(|| truncate_str(&f.lot, 10));
+                let dev_short = short_ids
+                    .get_short_id(&f.deviation)
+                    .unwrap_or_else(|| truncate_str(&f.deviation, 10));
+                println!(
+                    "{:<10} {:<14} {:<5} {:<10} {:<10} {:<11} {}",
+                    style(lot_short).cyan(),
+                    truncate_str(f.lot_number.as_deref().unwrap_or("-"), 14),
+                    f.step.map(|s| s.to_string()).unwrap_or_else(|| "-".into()),
+                    style(dev_short).cyan(),
+                    match f.kind {
+                        UsageKind::Violation => style(f.dev_status.to_string()).red(),
+                        UsageKind::SinceExpired => style(f.dev_status.to_string()).yellow(),
+                    },
+                    f.processed
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "-".into()),
+                    f.issues.join("; ")
+                );
+            }
+            let violations = findings
+                .iter()
+                .filter(|f| f.kind == UsageKind::Violation)
+                .count();
+            println!();
+            println!(
+                "{} {} violation(s), {} usage(s) under since-expired deviations",
+                style("!").yellow(),
+                violations,
+                findings.len() - violations
+            );
+        }
+    }
+
+    Ok(())
+}
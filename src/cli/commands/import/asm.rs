@@ -7,7 +7,11 @@ use std::fs::{self, File};
 use std::io::BufReader;
 use std::path::PathBuf;
 
+use crate::cli::commands::asm::{load_bom_snapshot, print_bom_diff};
+use crate::core::bom_diff::{compare, explode, BomView};
+use crate::core::bom_import::{parse_bom, plan_import, BomProfile, MatchedBy};
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader::find_entity_file;
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
 use crate::core::Config;
@@ -152,3 +156,162 @@ pub fn import(project: &Project, file_path: &PathBuf, args: &ImportArgs) -> Resu
 
     Ok(stats)
 }
+
+/// Import a CAD/EDA BOM export as the BOM of an existing assembly
+///
+/// Rows are reconciled against existing components before anything is
+/// written; the report shows how each row matched and the BOM diff the
+/// import would make.
+pub fn import_bom(
+    project: &Project,
+    file_path: &PathBuf,
+    args: &ImportArgs,
+    profile: BomProfile,
+) -> Result<ImportStats> {
+    let mut stats = ImportStats::default();
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(project);
+
+    let asm_ref = args.assembly.as_deref().ok_or_else(|| {
+        miette::miette!(
+            "--assembly is required with --profile (the assembly whose BOM is imported)"
+        )
+    })?;
+    let asm_id = short_ids
+        .resolve(asm_ref)
+        .unwrap_or_else(|| asm_ref.to_string());
+    let snapshot = load_bom_snapshot(project, None)?;
+    let target = snapshot
+        .assembly(&asm_id)
+        .cloned()
+        .ok_or_else(|| miette::miette!("Assembly not found: {}", asm_ref))?;
+
+    let file = File::open(file_path).into_diagnostic()?;
+    let parsed = parse_bom(BufReader::new(file), profile, &args.map)
+        .map_err(|e| miette::miette!("{}", e))?;
+    stats.rows_processed = parsed.rows.len() + parsed.skipped.len();
+    stats.skipped = parsed.skipped.len();
+
+    let plan = plan_import(&parsed.rows, &snapshot, &target, profile, &config.author());
+
+    // Reconciliation: how each row resolved
+    println!("{}", style("Reconciliation").bold());
+    println!(
+        "{:<6} {:<24} {:<30} {:>5} {:<12} {}",
+        style("LINE").bold(),
+        style("PART").bold(),
+        style("TITLE").bold(),
+        style("QTY").bold(),
+        style("MATCHED BY").bold(),
+        style("ENTITY").bold()
+    );
+    for m in &plan.matches {
+        let entity = match m.matched_by {
+            MatchedBy::New if m.is_assembly => style("new assembly".to_string()).green(),
+            MatchedBy::New => style("new component".to_string()).green(),
+            _ => style(
+                short_ids
+                    .get_short_id(&m.entity)
+                    .unwrap_or_else(|| m.entity.clone()),
+            )
+            .cyan(),
+        };
+        println!(
+            "{:<6} {:<24} {:<30} {:>5} {:<12} {}",
+            m.row.line,
+            truncate(
+                &format!("{}{}", "  ".repeat(m.row.depth), m.row.key().unwrap_or("")),
+                24
+            ),
+            truncate(&m.row.display_title(), 30),
+            m.row.quantity,
+            m.matched_by.to_string(),
+            entity
+        );
+        for note in &m.notes {
+            println!("{:<6} {} {}", "", style("!").yellow(), style(note).yellow());
+        }
+    }
+    for skipped in &parsed.skipped {
+        println!("{} Skipped {}", style("○").dim(), style(skipped).dim());
+    }
+
+    // BOM changes the import would make
+    let after = plan.apply_to(&snapshot);
+    let new_target = after
+        .assemblies
+        .get(&target.id.to_string())
+        .unwrap_or(&target);
+    let diff = compare(
+        &explode(&snapshot, &target, BomView::Indented),
+        &explode(&after, new_target, BomView::Indented),
+        BomView::Indented,
+    );
+    println!();
+    println!(
+        "{} {} {}",
+        style("BOM changes for").bold(),
+        style(&target.part_number).yellow(),
+        style(&target.title).dim()
+    );
+    print_bom_diff(&diff, &snapshot, &after);
+
+    if args.dry_run {
+        return Ok(stats);
+    }
+    stats.entities_created = plan.new_components.len() + plan.new_assemblies.len();
+    stats.entities_updated = plan.updated_assemblies.len();
+
+    let cmp_dir = project.root().join("bom/components");
+    let asm_dir = project.root().join("bom/assemblies");
+    fs::create_dir_all(&cmp_dir).into_diagnostic()?;
+    fs::create_dir_all(&asm_dir).into_diagnostic()?;
+
+    println!();
+    for cmp in &plan.new_components {
+        let yaml = serde_yml::to_string(cmp).into_diagnostic()?;
+        fs::write(cmp_dir.join(format!("{}.tdt.yaml", cmp.id)), yaml).into_diagnostic()?;
+        let short_id = short_ids.add(cmp.id.to_string());
+        println!(
+            "{} Created {} - {} ({})",
+            style("✓").green(),
+            style(short_id.unwrap_or_else(|| cmp.id.to_string())).cyan(),
+            truncate(&cmp.title, 30),
+            cmp.part_number
+        );
+    }
+    for asm in &plan.new_assemblies {
+        let yaml = serde_yml::to_string(asm).into_diagnostic()?;
+        fs::write(asm_dir.join(format!("{}.tdt.yaml", asm.id)), yaml).into_diagnostic()?;
+        let short_id = short_ids.add(asm.id.to_string());
+        println!(
+            "{} Created {} - {} ({})",
+            style("✓").green(),
+            style(short_id.unwrap_or_else(|| asm.id.to_string())).cyan(),
+            truncate(&asm.title, 30),
+            asm.part_number
+        );
+    }
+    for asm in &plan.updated_assemblies {
+        let path = find_entity_file(&asm_dir, &asm.id.to_string())
+            .ok_or_else(|| miette::miette!("Assembly file not found: {}", asm.id))?;
+        let yaml = serde_yml::to_string(asm).into_diagnostic()?;
+        fs::write(&path, yaml).into_diagnostic()?;
+        println!(
+            "{} Updated BOM of {} - {} ({} items)",
+            style("✓").green(),
+            style(
+                short_ids
+                    .get_short_id(&asm.id.to_string())
+                    .unwrap_or_else(|| asm.id.to_string())
+            )
+            .cyan(),
+            truncate(&asm.title, 30),
+            asm.bom.len()
+        );
+    }
+
+    crate::cli::commands::utils::save_short_ids(&mut short_ids, project);
+
+    Ok(stats)
+}
@@ -22,6 +22,8 @@ pub struct ImportArgs {
     pub process: Option<String>,
     /// Default assembly ID for component imports
     pub assembly: Option<String>,
+    /// Column overrides for BOM profile imports (field -> column)
+    pub map: HashMap<String, String>,
 }
 
 /// Truncate a string to max length with ellipsis
@@ -19,6 +19,7 @@ use console::style;
 use miette::Result;
 use std::path::PathBuf;
 
+use crate::core::bom_import::{BomProfile, FIELDS as BOM_FIELDS};
 use crate::core::identity::EntityPrefix;
 use crate::core::project::Project;
 
@@ -68,6 +69,29 @@ pub struct ImportArgs {
     /// Default assembly ID for component imports (used when CSV row lacks assembly column)
     #[arg(long)]
     pub assembly: Option<String>,
+
+    /// Import a CAD/EDA BOM export into --assembly (solidworks, onshape, kicad, altium, generic)
+    #[arg(long)]
+    pub profile: Option<BomProfile>,
+
+    /// Map a BOM field to a column, overriding the profile (e.g. --map mpn="Mfr PN")
+    #[arg(long = "map", value_name = "FIELD=COLUMN", value_parser = parse_column_map)]
+    pub map: Vec<(String, String)>,
+}
+
+fn parse_column_map(s: &str) -> Result<(String, String), String> {
+    let (field, column) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid mapping '{}'. Use FIELD=COLUMN", s))?;
+    let field = field.trim().to_lowercase().replace('-', "_");
+    if !BOM_FIELDS.contains(&field.as_str()) {
+        return Err(format!(
+            "Unknown BOM field '{}'. Use one of: {}",
+            field,
+            BOM_FIELDS.join(", ")
+        ));
+    }
+    Ok((field, column.trim().to_string()))
 }
 
 fn parse_entity_type(s: &str) -> Result<EntityPrefix, String> {
@@ -117,19 +141,39 @@ pub fn run(args: ImportArgs) -> Result<()> {
         return Err(miette::miette!("File not found: {}", file_path.display()));
     }
 
+    if args.profile.is_some() && entity_type != EntityPrefix::Asm {
+        return Err(miette::miette!(
+            "--profile imports a BOM and only applies to asm. Usage: tdt import asm bom.csv --profile kicad --assembly ASM@1"
+        ));
+    }
+
     let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
 
-    println!(
-        "{} Importing {} entities from {}{}",
-        style("→").blue(),
-        style(entity_type.as_str()).cyan(),
-        style(file_path.display()).yellow(),
-        if args.dry_run {
-            style(" (dry run)").dim().to_string()
-        } else {
-            String::new()
-        }
-    );
+    if let Some(profile) = args.profile {
+        println!(
+            "{} Importing {} BOM from {}{}",
+            style("→").blue(),
+            style(profile).cyan(),
+            style(file_path.display()).yellow(),
+            if args.dry_run {
+                style(" (dry run)").dim().to_string()
+            } else {
+                String::new()
+            }
+        );
+    } else {
+        println!(
+            "{} Importing {} entities from {}{}",
+            style("→").blue(),
+            style(entity_type.as_str()).cyan(),
+            style(file_path.display()).yellow(),
+            if args.dry_run {
+                style(" (dry run)").dim().to_string()
+            } else {
+                String::new()
+            }
+        );
+    }
     println!();
 
     // Convert clap args to internal args struct
@@ -141,13 +185,17 @@ pub fn run(args: ImportArgs) -> Result<()> {
         test: args.test.clone(),
         process: args.process.clone(),
         assembly: args.assembly.clone(),
+        map: args.map.iter().cloned().collect(),
     };
 
     let stats = match entity_type {
         EntityPrefix::Req => req::import(&project, &file_path, &internal_args)?,
         EntityPrefix::Risk => risk::import(&project, &file_path, &internal_args)?,
         EntityPrefix::Cmp => cmp::import(&project, &file_path, &internal_args)?,
-        EntityPrefix::Asm => asm::import(&project, &file_path, &internal_args)?,
+        EntityPrefix::Asm => match args.profile {
+            Some(profile) => asm::import_bom(&project, &file_path, &internal_args, profile)?,
+            None => asm::import(&project, &file_path, &internal_args)?,
+        },
         EntityPrefix::Sup => sup::import(&project, &file_path, &internal_args)?,
         EntityPrefix::Test => test::import(&project, &file_path, &internal_args)?,
         EntityPrefix::Rslt => rslt::import(&project, &file_path, &internal_args)?,
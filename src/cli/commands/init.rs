@@ -144,6 +144,9 @@ fn print_structure(root: &Path) {
         "manufacturing/controls/",
         "manufacturing/lots/",
         "manufacturing/deviations/",
+        "postmarket/complaints/",
+        "quality/audits/",
+        "manufacturing/receipts/",
     ];
 
     for dir in dirs {
@@ -1283,6 +1283,9 @@ fn find_entity_file(project: &Project, id: &EntityId) -> Result<PathBuf> {
         EntityPrefix::Act => vec![project.root().join("manufacturing/actions")],
         EntityPrefix::Lot => vec![project.root().join("manufacturing/lots")],
         EntityPrefix::Dev => vec![project.root().join("manufacturing/deviations")],
+        EntityPrefix::Cmpl => vec![project.root().join("postmarket/complaints")],
+        EntityPrefix::Aud => vec![project.root().join("quality/audits")],
+        EntityPrefix::Rcv => vec![project.root().join("manufacturing/receipts")],
     };
 
     for dir in search_dirs {
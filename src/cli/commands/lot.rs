@@ -5,26 +5,44 @@ use console::style;
 use miette::{IntoDiagnostic, Result};
 use std::fs;
 
-use crate::cli::helpers::{escape_csv, format_short_id, truncate_str};
+use crate::cli::helpers::{escape_csv, format_short_id, format_short_id_str, truncate_str};
 use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::dhr::{DhrBuilder, DhrOptions};
+use crate::core::genealogy::{GenealogyGraph, TraceNode, TraceNodeKind};
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
 use crate::core::manufacturing::{
-    create_execution_steps_from_routing, step_requires_signature, LotWorkflow, LotWorkflowConfig,
+    covering_deviations, create_execution_steps_from_routing, parse_step_value, step_data_specs,
+    step_requires_signature, step_sampling, LotWorkflow, LotWorkflowConfig, StepDataCheck,
+    StepDataSpec,
 };
 use crate::core::project::Project;
+use crate::core::receiving;
 use crate::core::shortid::ShortIdIndex;
 use crate::core::{Config, Git};
 use crate::entities::assembly::Assembly;
-use crate::entities::component::Component;
-use crate::entities::lot::{ExecutionStatus, Lot, LotStatus, WorkInstructionRef};
+use crate::entities::component::{Component, MakeBuy};
+use crate::entities::control::Control;
+use crate::entities::dev::Dev;
+use crate::entities::lot::{
+    ExecutionStatus, ExecutionStep, GenealogyOperation, Lot, LotOrigin, LotStatus, MaterialUsed,
+    SerialUnit, UnitStatus, WorkInstructionRef,
+};
+use crate::entities::ncr::{
+    AffectedItems, Defect, Detection, DetectionStage, Ncr, NcrCategory, NcrSeverity, NcrType,
+};
 use crate::entities::process::Process;
+use crate::entities::receipt::Receipt;
+use crate::entities::work_instruction::WorkInstruction;
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// CLI-friendly lot status enum
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CliLotStatus {
+    Planned,
     InProgress,
     OnHold,
     Completed,
@@ -34,6 +52,7 @@ pub enum CliLotStatus {
 impl std::fmt::Display for CliLotStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            CliLotStatus::Planned => write!(f, "planned"),
             CliLotStatus::InProgress => write!(f, "in_progress"),
             CliLotStatus::OnHold => write!(f, "on_hold"),
             CliLotStatus::Completed => write!(f, "completed"),
@@ -45,6 +64,7 @@ impl std::fmt::Display for CliLotStatus {
 impl From<CliLotStatus> for LotStatus {
     fn from(cli: CliLotStatus) -> Self {
         match cli {
+            CliLotStatus::Planned => LotStatus::Planned,
             CliLotStatus::InProgress => LotStatus::InProgress,
             CliLotStatus::OnHold => LotStatus::OnHold,
             CliLotStatus::Completed => LotStatus::Completed,
@@ -78,11 +98,30 @@ pub enum LotCommands {
 
     /// Complete a lot
     Complete(CompleteArgs),
+
+    /// Add serial numbers to a lot or update unit status
+    Serial(SerialArgs),
+
+    /// Record material consumed by a lot or a single serial
+    Material(MaterialArgs),
+
+    /// Split part of a lot into a new child lot
+    Split(SplitArgs),
+
+    /// Merge several lots into a new lot
+    Merge(MergeArgs),
+
+    /// Trace lot genealogy forward (where-used) or backward (where-from)
+    Trace(TraceArgs),
+
+    /// Export the Device History Record (DHR) for a lot
+    Dhr(DhrArgs),
 }
 
 /// Lot status filter
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum LotStatusFilter {
+    Planned,
     InProgress,
     OnHold,
     Completed,
@@ -187,6 +226,14 @@ pub struct NewArgs {
     #[arg(long, short = 'p')]
     pub product: Option<String>,
 
+    /// Create the lot as planned (not yet started) for `tdt plan`
+    #[arg(long)]
+    pub planned: bool,
+
+    /// Production start date, or planned start for a planned lot (YYYY-MM-DD)
+    #[arg(long)]
+    pub start_date: Option<chrono::NaiveDate>,
+
     /// Create a git branch for this lot (DHR workflow)
     #[arg(long, short = 'b')]
     pub branch: bool,
@@ -316,6 +363,18 @@ pub struct StepArgs {
     /// Interactive mode (prompt for step details)
     #[arg(long, short = 'i')]
     pub interactive: bool,
+
+    /// Record step data as NAME=VALUE (process parameters, WI quality checks)
+    #[arg(long = "data", short = 'd', value_name = "NAME=VALUE")]
+    pub data: Vec<String>,
+
+    /// Create an NCR linked to the lot and process for out-of-spec values
+    #[arg(long)]
+    pub ncr: bool,
+
+    /// Deviation (DEV ID) authorizing a departure in this step
+    #[arg(long = "deviation", short = 'D')]
+    pub deviation: Option<String>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -336,6 +395,191 @@ pub struct CompleteArgs {
     pub sign: bool,
 }
 
+/// CLI-friendly unit status enum
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliUnitStatus {
+    InProcess,
+    Completed,
+    Scrapped,
+}
+
+impl From<CliUnitStatus> for UnitStatus {
+    fn from(cli: CliUnitStatus) -> Self {
+        match cli {
+            CliUnitStatus::InProcess => UnitStatus::InProcess,
+            CliUnitStatus::Completed => UnitStatus::Completed,
+            CliUnitStatus::Scrapped => UnitStatus::Scrapped,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SerialArgs {
+    /// Lot ID or short ID (LOT@N)
+    pub lot: String,
+
+    /// Serial numbers to add (comma-separated)
+    #[arg(long, short = 'a', value_delimiter = ',')]
+    pub add: Vec<String>,
+
+    /// Generate N sequential serial numbers
+    #[arg(long, short = 'g')]
+    pub generate: Option<u32>,
+
+    /// Prefix for generated serials (default: "<lot number>-")
+    #[arg(long)]
+    pub prefix: Option<String>,
+
+    /// First number for generated serials
+    #[arg(long, default_value = "1")]
+    pub start: u32,
+
+    /// Zero-padded width of generated serial numbers
+    #[arg(long, default_value = "4")]
+    pub width: usize,
+
+    /// Set unit status for the serials given with --serials
+    #[arg(long, short = 's', requires = "serials")]
+    pub status: Option<CliUnitStatus>,
+
+    /// Existing serials to update (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub serials: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MaterialArgs {
+    /// Lot ID or short ID (LOT@N)
+    pub lot: String,
+
+    /// Component consumed (CMP ID or short ID)
+    #[arg(long, short = 'c')]
+    pub component: Option<String>,
+
+    /// Supplier lot number of the consumed material
+    #[arg(long)]
+    pub supplier_lot: Option<String>,
+
+    /// Accepted receipt the material is drawn from (RCV ID or short ID);
+    /// fills in the component and supplier lot
+    #[arg(long)]
+    pub receipt: Option<String>,
+
+    /// Record a purchased component that has no accepted receipt
+    /// (e.g. consigned or pre-existing stock)
+    #[arg(long, conflicts_with = "receipt")]
+    pub no_receipt: bool,
+
+    /// Quantity consumed
+    #[arg(long, short = 'Q')]
+    pub quantity: Option<u32>,
+
+    /// In-house lot the material came from (LOT ID or short ID)
+    #[arg(long)]
+    pub source_lot: Option<String>,
+
+    /// Serial numbers of the consumed items (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub source_serials: Vec<String>,
+
+    /// Record against a single serial in this lot instead of the whole lot
+    #[arg(long)]
+    pub serial: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SplitArgs {
+    /// Lot ID or short ID (LOT@N) to split
+    pub lot: String,
+
+    /// Quantity to move into the new lot
+    #[arg(long, short = 'Q')]
+    pub quantity: Option<u32>,
+
+    /// Serial numbers to move into the new lot (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub serials: Vec<String>,
+
+    /// Lot number for the new lot (default: "<parent>-<n>")
+    #[arg(long, short = 'l')]
+    pub lot_number: Option<String>,
+
+    /// Title for the new lot
+    #[arg(long, short = 't')]
+    pub title: Option<String>,
+
+    /// Reason for the split
+    #[arg(long, short = 'n')]
+    pub notes: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MergeArgs {
+    /// Lots to merge (LOT IDs or short IDs)
+    #[arg(required = true, num_args = 2..)]
+    pub lots: Vec<String>,
+
+    /// Lot number for the merged lot
+    #[arg(long, short = 'l')]
+    pub lot_number: Option<String>,
+
+    /// Title for the merged lot
+    #[arg(long, short = 't')]
+    pub title: Option<String>,
+
+    /// Reason for the merge
+    #[arg(long, short = 'n')]
+    pub notes: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct TraceArgs {
+    /// Supplier lot number, serial number, or lot ID (LOT@N)
+    pub target: String,
+
+    /// Forward trace: which lots and serials contain the target
+    #[arg(long, short = 'F', conflicts_with = "backward")]
+    pub forward: bool,
+
+    /// Backward trace: what went into the target
+    #[arg(long, short = 'B')]
+    pub backward: bool,
+
+    /// Only list finished (completed) serials in a forward trace
+    #[arg(long)]
+    pub finished: bool,
+}
+
+/// DHR document format
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum DhrFormat {
+    #[default]
+    Md,
+    Html,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DhrArgs {
+    /// Lot ID or short ID (LOT@N)
+    pub lot: String,
+
+    /// Document format
+    #[arg(long, default_value = "md")]
+    pub format: DhrFormat,
+
+    /// Directory for the DHR bundle (default: DHR-<lot number>)
+    #[arg(long, short = 'd')]
+    pub dir: Option<PathBuf>,
+
+    /// Print the document to stdout instead of writing a bundle
+    #[arg(long)]
+    pub stdout: bool,
+
+    /// Skip git commit signature verification
+    #[arg(long)]
+    pub no_verify: bool,
+}
+
 /// Directories where lots are stored
 const LOT_DIRS: &[&str] = &["manufacturing/lots"];
 
@@ -358,6 +602,12 @@ pub fn run(cmd: LotCommands, global: &GlobalOpts) -> Result<()> {
         LotCommands::Archive(args) => run_archive(args),
         LotCommands::Step(args) => run_step(args, global),
         LotCommands::Complete(args) => run_complete(args, global),
+        LotCommands::Serial(args) => run_serial(args, global),
+        LotCommands::Material(args) => run_material(args),
+        LotCommands::Split(args) => run_split(args, global),
+        LotCommands::Merge(args) => run_merge(args, global),
+        LotCommands::Trace(args) => run_trace(args, global),
+        LotCommands::Dhr(args) => run_dhr(args, global),
     }
 }
 
@@ -398,6 +648,7 @@ fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
     let lots: Vec<Lot> = lots
         .into_iter()
         .filter(|l| match args.status {
+            LotStatusFilter::Planned => l.lot_status == LotStatus::Planned,
             LotStatusFilter::InProgress => l.lot_status == LotStatus::InProgress,
             LotStatusFilter::OnHold => l.lot_status == LotStatus::OnHold,
             LotStatusFilter::Completed => l.lot_status == LotStatus::Completed,
@@ -442,7 +693,7 @@ fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
         })
         .filter(|l| {
             if args.active {
-                l.lot_status == LotStatus::InProgress || l.lot_status == LotStatus::OnHold
+                !matches!(l.lot_status, LotStatus::Completed | LotStatus::Scrapped)
             } else {
                 true
             }
@@ -571,6 +822,7 @@ fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
                         }
                         ListColumn::LotStatus => {
                             let status_styled = match lot.lot_status {
+                                LotStatus::Planned => style(lot.lot_status.to_string()).blue(),
                                 LotStatus::InProgress => style(lot.lot_status.to_string()).green(),
                                 LotStatus::OnHold => style(lot.lot_status.to_string()).yellow(),
                                 LotStatus::Completed => style(lot.lot_status.to_string()).cyan(),
@@ -696,6 +948,16 @@ fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
             yaml_content.replace("  product: null", &format!("  product: \"{}\"", resolved));
     }
 
+    if args.planned {
+        yaml_content = yaml_content.replace("lot_status: in_progress", "lot_status: planned");
+    }
+    if let Some(date) = args.start_date {
+        yaml_content = yaml_content.replace(
+            &format!("start_date: {}", ctx.created.format("%Y-%m-%d")),
+            &format!("start_date: {}", date),
+        );
+    }
+
     // Apply wizard-collected values via string replacement
     if args.interactive {
         if let Some(ref status) = lot_status {
@@ -1024,6 +1286,7 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                 println!("{}: {}", style("Quantity").bold(), q);
             }
             let status_styled = match lot.lot_status {
+                LotStatus::Planned => style(lot.lot_status.to_string()).blue(),
                 LotStatus::InProgress => style(lot.lot_status.to_string()).green(),
                 LotStatus::OnHold => style(lot.lot_status.to_string()).yellow(),
                 LotStatus::Completed => style(lot.lot_status.to_string()).cyan(),
@@ -1054,10 +1317,38 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                         .quantity
                         .map(|q| q.to_string())
                         .unwrap_or("-".to_string());
-                    println!("  • {} | Lot: {} | Qty: {}", comp, supplier_lot, qty);
+                    print!("  • {} | Lot: {} | Qty: {}", comp, supplier_lot, qty);
+                    if let Some(ref src) = mat.source_lot {
+                        let src_display =
+                            short_ids.get_short_id(src).unwrap_or_else(|| src.clone());
+                        print!(" | From: {}", style(src_display).cyan());
+                    }
+                    println!();
                 }
             }
 
+            // Serialized units
+            if !lot.serials.is_empty() {
+                let completed = lot
+                    .serials
+                    .iter()
+                    .filter(|u| u.unit_status == UnitStatus::Completed)
+                    .count();
+                let scrapped = lot
+                    .serials
+                    .iter()
+                    .filter(|u| u.unit_status == UnitStatus::Scrapped)
+                    .count();
+                println!();
+                println!(
+                    "{} ({}): {} completed, {} scrapped",
+                    style("Serials").bold(),
+                    lot.serials.len(),
+                    completed,
+                    scrapped
+                );
+            }
+
             // Execution steps
             if !lot.execution.is_empty() {
                 println!();
@@ -1087,6 +1378,11 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                             println!("     {}", style(notes).dim());
                         }
                     }
+                    let mut keys: Vec<_> = step.data.keys().collect();
+                    keys.sort();
+                    for key in keys {
+                        println!("     {}: {}", style(key).dim(), step.data[key]);
+                    }
                 }
             }
 
@@ -1097,6 +1393,50 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                 || !lot.links.ncrs.is_empty()
                 || !lot.links.results.is_empty();
 
+            // Genealogy
+            if lot.origin.is_some()
+                || !lot.links.parent_lots.is_empty()
+                || !lot.links.child_lots.is_empty()
+            {
+                println!();
+                println!("{}", style("Genealogy:").bold());
+                if let Some(ref origin) = lot.origin {
+                    println!(
+                        "  {}: {} by {} on {}",
+                        style("Origin").dim(),
+                        origin.operation,
+                        origin.performed_by,
+                        origin.date.format("%Y-%m-%d")
+                    );
+                }
+                if !lot.links.parent_lots.is_empty() {
+                    let list: Vec<_> = lot
+                        .links
+                        .parent_lots
+                        .iter()
+                        .map(|l| short_ids.get_short_id(l).unwrap_or_else(|| l.clone()))
+                        .collect();
+                    println!(
+                        "  {}: {}",
+                        style("Parent lots").dim(),
+                        style(list.join(", ")).cyan()
+                    );
+                }
+                if !lot.links.child_lots.is_empty() {
+                    let list: Vec<_> = lot
+                        .links
+                        .child_lots
+                        .iter()
+                        .map(|l| short_ids.get_short_id(l).unwrap_or_else(|| l.clone()))
+                        .collect();
+                    println!(
+                        "  {}: {}",
+                        style("Child lots").dim(),
+                        style(list.join(", ")).cyan()
+                    );
+                }
+            }
+
             if has_links {
                 println!();
                 println!("{}", style("Links:").bold());
@@ -1271,6 +1611,82 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
     // Get the current process for WI display
     let current_process = step.process.as_ref().and_then(|p| processes.get(p));
 
+    // Deviations in effect for this lot and process
+    let today = chrono::Local::now().date_naive();
+    let lot_id = lot.id.to_string();
+    let step_process = lot.execution[step_idx].process.clone();
+    let devs: Vec<Dev> =
+        loader::load_all(&project.root().join("manufacturing/deviations")).unwrap_or_default();
+    let dev_display = |dev: &Dev| {
+        short_ids
+            .get_short_id(&dev.id.to_string())
+            .unwrap_or_else(|| format_short_id(&dev.id))
+    };
+    let devs_in_effect: Vec<&Dev> = devs
+        .iter()
+        .filter(|d| d.is_in_effect_on(today) && d.applies_to(&lot_id, step_process.as_deref()))
+        .collect();
+
+    // Sampling plans of the process's controls for this lot
+    let sampling = match step_process.as_deref() {
+        Some(proc_id) => {
+            let controls: Vec<Control> =
+                loader::load_all(&project.root().join("manufacturing/controls"))
+                    .unwrap_or_default();
+            let lots: Vec<Lot> = loader::load_all(&lot_dir).unwrap_or_default();
+            step_sampling(proc_id, &controls, &lots, &lot)
+        }
+        None => Vec::new(),
+    };
+    for plan in sampling.iter().filter(|p| p.state.discontinued) {
+        eprintln!(
+            "{} {}: acceptance inspection discontinued ({})",
+            style("!").yellow(),
+            plan.characteristic,
+            plan.state.reason.as_deref().unwrap_or_default()
+        );
+    }
+
+    // An explicitly cited deviation must be approved, in effect and applicable
+    let authorizing: Option<&Dev> = match args.deviation {
+        Some(ref dev_ref) => {
+            let resolved = short_ids
+                .resolve(dev_ref)
+                .unwrap_or_else(|| dev_ref.clone());
+            let matches: Vec<&Dev> = devs
+                .iter()
+                .filter(|d| d.id.to_string().starts_with(&resolved))
+                .collect();
+            let dev = match matches.as_slice() {
+                [dev] => *dev,
+                [] => return Err(miette::miette!("No deviation found matching '{}'", dev_ref)),
+                _ => {
+                    return Err(miette::miette!(
+                        "Ambiguous query '{}'. Please be more specific.",
+                        dev_ref
+                    ))
+                }
+            };
+            if let Some(issue) = dev.usability_issue(today) {
+                return Err(miette::miette!(
+                    "Deviation {} {} and cannot authorize this step",
+                    dev_display(dev),
+                    issue
+                ));
+            }
+            if !dev.applies_to(&lot_id, step_process.as_deref()) {
+                return Err(miette::miette!(
+                    "Deviation {} does not apply to lot {} or process {}",
+                    dev_display(dev),
+                    display_id,
+                    step_process.as_deref().unwrap_or("(unlinked)")
+                ));
+            }
+            Some(dev)
+        }
+        None => None,
+    };
+
     // Show work instructions if requested
     if args.show_wi {
         if let Some(proc) = current_process {
@@ -1291,6 +1707,37 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
         } else {
             println!("{} No process linked to this step", style("!").yellow());
         }
+        if !devs_in_effect.is_empty() {
+            println!("{}", style("Deviations in Effect").bold().cyan());
+            println!("{}", style("─".repeat(50)).dim());
+            for dev in &devs_in_effect {
+                println!("   • {} {}", style(dev_display(dev)).cyan(), dev.title);
+            }
+            println!();
+        }
+        if !sampling.is_empty() {
+            println!("{}", style("Sampling").bold().cyan());
+            println!("{}", style("─".repeat(50)).dim());
+            for plan in &sampling {
+                let ctrl_short = short_ids
+                    .get_short_id(&plan.control)
+                    .unwrap_or_else(|| plan.control.clone());
+                println!(
+                    "   • {} ({}): {}, {} inspection",
+                    plan.characteristic,
+                    style(&ctrl_short).cyan(),
+                    plan.plan.describe(),
+                    plan.state.severity
+                );
+                if plan.plan.accept.is_some() {
+                    println!(
+                        "     {}",
+                        style(format!("record with --data \"{}=N\"", plan.defects_key())).dim()
+                    );
+                }
+            }
+            println!();
+        }
     }
 
     // Interactive mode
@@ -1370,6 +1817,258 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
         ));
     }
 
+    // Capture step data and validate it against process parameters and
+    // work instruction quality checks
+    let wi_dir = project.root().join("manufacturing/work_instructions");
+    let wi_ids: Vec<String> = if !args.wi_used.is_empty() {
+        args.wi_used
+            .iter()
+            .map(|wi| short_ids.resolve(wi).unwrap_or_else(|| wi.clone()))
+            .collect()
+    } else if !lot.execution[step_idx].work_instructions_used.is_empty() {
+        lot.execution[step_idx]
+            .work_instructions_used
+            .iter()
+            .map(|w| w.id.clone())
+            .collect()
+    } else {
+        current_process
+            .map(|p| {
+                p.links
+                    .work_instructions
+                    .iter()
+                    .map(|w| w.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let step_wis: Vec<WorkInstruction> = wi_ids
+        .iter()
+        .filter_map(|id| {
+            loader::load_entity::<WorkInstruction>(&wi_dir, id)
+                .ok()
+                .flatten()
+        })
+        .map(|(_, wi)| wi)
+        .collect();
+    let mut specs = step_data_specs(current_process, &step_wis);
+    specs.extend(sampling.iter().filter_map(|p| p.data_spec()));
+
+    let mut data = lot.execution[step_idx].data.clone();
+    for entry in &args.data {
+        let (name, value) = entry
+            .split_once('=')
+            .ok_or_else(|| miette::miette!("Invalid --data '{}', expected NAME=VALUE", entry))?;
+        let name = name.trim();
+        let key = specs
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| name.to_string());
+        data.retain(|k, _| !k.eq_ignore_ascii_case(&key));
+        data.insert(key, parse_step_value(value));
+    }
+
+    if args.interactive && !specs.is_empty() {
+        println!();
+        println!("{}", style("Step Data").bold().cyan());
+        for spec in &specs {
+            let current = data
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(&spec.name))
+                .map(|(_, v)| v.to_string());
+            let limits = spec.limits_display();
+            print!(
+                "{}{}{}: ",
+                spec.name,
+                if limits.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", limits)
+                },
+                current
+                    .as_ref()
+                    .map(|c| format!(" [{}]", c))
+                    .unwrap_or_default()
+            );
+            std::io::Write::flush(&mut std::io::stdout()).into_diagnostic()?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input).into_diagnostic()?;
+            if !input.trim().is_empty() {
+                data.retain(|k, _| !k.eq_ignore_ascii_case(&spec.name));
+                data.insert(spec.name.clone(), parse_step_value(&input));
+            }
+        }
+    }
+
+    let checks: Vec<(&StepDataSpec, StepDataCheck, Option<serde_json::Value>)> = specs
+        .iter()
+        .map(|spec| {
+            let value = data
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(&spec.name))
+                .map(|(_, v)| v.clone());
+            (spec, spec.check(value.as_ref()), value)
+        })
+        .collect();
+
+    if let Some((spec, _, value)) = checks
+        .iter()
+        .find(|(_, check, _)| *check == StepDataCheck::NotNumeric)
+    {
+        return Err(miette::miette!(
+            "Value '{}' for {} is not a number (limits: {})",
+            value.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+            spec.name,
+            spec.limits_display()
+        ));
+    }
+
+    let out_of_spec: Vec<(&StepDataSpec, String)> = checks
+        .iter()
+        .filter(|(_, check, _)| *check == StepDataCheck::OutOfSpec)
+        .map(|(spec, _, value)| {
+            (
+                *spec,
+                value.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+            )
+        })
+        .collect();
+    lot.execution[step_idx].data = data;
+
+    let mut created_ncr: Option<String> = None;
+    let mut held = false;
+    let mut applied_deviation: Option<&Dev> = authorizing;
+    if !out_of_spec.is_empty() {
+        // Without --deviation, apply a deviation only when exactly one covers
+        // the step; otherwise the operator has to choose
+        let covering = match authorizing {
+            Some(dev) => Some(dev),
+            None => {
+                let candidates =
+                    covering_deviations(&devs, &lot_id, step_process.as_deref(), today);
+                match candidates.as_slice() {
+                    [dev] => Some(*dev),
+                    [] => None,
+                    _ => {
+                        let listed = candidates
+                            .iter()
+                            .map(|d| dev_display(d))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if new_status == ExecutionStatus::Completed {
+                            return Err(miette::miette!(
+                                "Step {} has out-of-spec values and {} deviations cover it: {}.\n\
+                                 Choose one with --deviation.",
+                                step_idx + 1,
+                                candidates.len(),
+                                listed
+                            ));
+                        }
+                        eprintln!(
+                            "{} Several deviations cover this step ({}); none applied. Choose one with --deviation",
+                            style("!").yellow(),
+                            listed
+                        );
+                        None
+                    }
+                }
+            }
+        };
+
+        for (spec, value) in &out_of_spec {
+            eprintln!(
+                "{} {} = {} is outside {}",
+                style("✗").red(),
+                spec.name,
+                value,
+                spec.limits_display()
+            );
+        }
+
+        if args.ncr {
+            let mut short_ids = ShortIdIndex::load(&project);
+            let ncr_id = create_step_ncr(
+                &project,
+                &lot,
+                step_idx,
+                current_process,
+                &out_of_spec,
+                &operator,
+            )?;
+            let ncr_short = short_ids
+                .add(ncr_id.clone())
+                .unwrap_or_else(|| ncr_id.clone());
+            super::utils::save_short_ids(&mut short_ids, &project);
+            lot.links.ncrs.push(ncr_id);
+            eprintln!(
+                "{} Created NCR {} for out-of-spec values",
+                style("!").yellow(),
+                style(&ncr_short).cyan()
+            );
+            created_ncr = Some(ncr_short);
+        }
+
+        match covering {
+            Some(dev) => {
+                eprintln!(
+                    "{} Out-of-spec values accepted under deviation {}",
+                    style("!").yellow(),
+                    style(dev_display(dev)).cyan()
+                );
+                applied_deviation = Some(dev);
+            }
+            None if new_status == ExecutionStatus::Completed => {
+                if created_ncr.is_none() {
+                    return Err(miette::miette!(
+                        "Step {} has out-of-spec values and cannot be completed. \
+                         Record an NCR with --ncr or obtain an active deviation for this lot or process.",
+                        step_idx + 1
+                    ));
+                }
+                held = true;
+            }
+            None => {}
+        }
+    }
+
+    if new_status == ExecutionStatus::Completed {
+        for (spec, check, _) in &checks {
+            if *check == StepDataCheck::Missing {
+                eprintln!(
+                    "{} Warning: no value recorded for {}",
+                    style("!").yellow(),
+                    spec.name
+                );
+            }
+        }
+    }
+
+    if let Some(dev) = applied_deviation {
+        lot.execution[step_idx].deviation = Some(dev.id.to_string());
+    }
+
+    if held {
+        // Keep the data and NCR link, but leave the step open
+        let step = &mut lot.execution[step_idx];
+        step.status = ExecutionStatus::InProgress;
+        step.operator = Some(operator.clone());
+        if step.started_date.is_none() {
+            step.started_date = Some(chrono::Local::now().date_naive());
+        }
+        if let Some(ref n) = notes {
+            step.notes = Some(n.clone());
+        }
+        lot.entity_revision += 1;
+        save_lot(&path, &lot)?;
+        return Err(miette::miette!(
+            "Step {} held at in_progress: out-of-spec values recorded in {}. \
+             Disposition the NCR or obtain an active deviation before completing.",
+            step_idx + 1,
+            created_ncr.unwrap_or_default()
+        ));
+    }
+
     // Update the step
     lot.execution[step_idx].status = new_status.clone();
     lot.execution[step_idx].operator = Some(operator.clone());
@@ -1416,12 +2115,29 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
         }
     }
 
+    // Record the revision of each work instruction as used
+    for wi_ref in lot.execution[step_idx].work_instructions_used.iter_mut() {
+        if wi_ref.revision.is_none() {
+            if let Ok(Some((_, wi))) = loader::load_entity::<WorkInstruction>(&wi_dir, &wi_ref.id) {
+                wi_ref.revision = Some(wi.entity_revision);
+            }
+        }
+    }
+
     // Handle signing
     if args.sign {
         lot.execution[step_idx].signature_verified = Some(true);
         // Note: signing_key would be populated by git commit signing
     }
 
+    // Work on a planned lot means production has started
+    if lot.lot_status == LotStatus::Planned {
+        lot.lot_status = LotStatus::InProgress;
+        if lot.start_date.is_none() {
+            lot.start_date = Some(today);
+        }
+    }
+
     // Increment revision
     lot.entity_revision += 1;
 
@@ -1477,8 +2193,23 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
             if args.sign {
                 result["signed"] = serde_json::json!(true);
             }
-            println!(
-                "{}",
+            if !lot.execution[step_idx].data.is_empty() {
+                result["data"] = serde_json::json!(lot.execution[step_idx].data);
+            }
+            if !out_of_spec.is_empty() {
+                result["out_of_spec"] = serde_json::json!(out_of_spec
+                    .iter()
+                    .map(|(spec, _)| spec.name.clone())
+                    .collect::<Vec<_>>());
+            }
+            if let Some(ref ncr) = created_ncr {
+                result["ncr"] = serde_json::json!(ncr);
+            }
+            if let Some(dev) = applied_deviation {
+                result["deviation"] = serde_json::json!(dev.id.to_string());
+            }
+            println!(
+                "{}",
                 serde_json::to_string_pretty(&result).unwrap_or_default()
             );
         }
@@ -1509,6 +2240,51 @@ fn run_step(args: StepArgs, global: &GlobalOpts) -> Result<()> {
             if args.sign {
                 println!("   {} Signed", style("✓").green());
             }
+            for (spec, check, value) in &checks {
+                let value = value
+                    .as_ref()
+                    .map(|v| match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                let marker = match check {
+                    StepDataCheck::InSpec | StepDataCheck::Recorded => style("✓").green(),
+                    StepDataCheck::OutOfSpec => style("✗").red(),
+                    _ => style("?").yellow(),
+                };
+                println!(
+                    "   {} {} = {} {}",
+                    marker,
+                    spec.name,
+                    value,
+                    style(format!("({})", spec.limits_display())).dim()
+                );
+            }
+            if let Some(ref ncr) = created_ncr {
+                println!("   {} NCR: {}", style("→").dim(), style(ncr).cyan());
+            }
+            if let Some(dev) = applied_deviation {
+                println!(
+                    "   {} Deviation: {}",
+                    style("→").dim(),
+                    style(dev_display(dev)).cyan()
+                );
+            }
+            for dev in devs_in_effect
+                .iter()
+                .filter(|d| applied_deviation.is_none_or(|a| a.id != d.id))
+            {
+                println!(
+                    "   {} Deviation {} in effect: {}{}",
+                    style("!").yellow(),
+                    style(dev_display(dev)).cyan(),
+                    dev.title,
+                    dev.expiration_date
+                        .map(|d| format!(" (expires {})", d))
+                        .unwrap_or_default()
+                );
+            }
             if let Some(ref sha) = commit_sha {
                 println!(
                     "   {} Commit: {}",
@@ -1743,3 +2519,1046 @@ fn run_complete(args: CompleteArgs, global: &GlobalOpts) -> Result<()> {
 
     Ok(())
 }
+
+/// Create an NCR for out-of-spec step data, returning its ID
+fn create_step_ncr(
+    project: &Project,
+    lot: &Lot,
+    step_idx: usize,
+    process: Option<&Process>,
+    out_of_spec: &[(&StepDataSpec, String)],
+    operator: &str,
+) -> Result<String> {
+    let names: Vec<&str> = out_of_spec.iter().map(|(s, _)| s.name.as_str()).collect();
+    let operation = process
+        .map(|p| match p.operation_number {
+            Some(ref op) => format!("{} {}", op, p.title),
+            None => p.title.clone(),
+        })
+        .unwrap_or_else(|| format!("Step {}", step_idx + 1));
+
+    let mut ncr = Ncr::new(
+        format!("Out-of-spec {} on lot {}", names.join(", "), lot.label()),
+        NcrType::Internal,
+        NcrSeverity::Minor,
+        Config::load().author(),
+    );
+    ncr.category = NcrCategory::Process;
+    ncr.description = Some(
+        out_of_spec
+            .iter()
+            .map(|(spec, value)| {
+                format!(
+                    "{} recorded {} (limits {})",
+                    spec.name,
+                    value,
+                    spec.limits_display()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    ncr.detection = Some(Detection {
+        found_at: DetectionStage::InProcess,
+        found_by: Some(operator.to_string()),
+        found_date: Some(chrono::Local::now().date_naive()),
+        operation: Some(operation),
+    });
+    ncr.affected_items = Some(AffectedItems {
+        lot_number: lot.lot_number.clone(),
+        quantity_affected: lot.quantity,
+        ..Default::default()
+    });
+    if let Some((spec, value)) = out_of_spec.first() {
+        ncr.defect = Some(Defect {
+            characteristic: Some(spec.name.clone()),
+            specification: Some(spec.limits_display()),
+            actual: Some(value.clone()),
+            ..Default::default()
+        });
+    }
+    ncr.links.process = process.map(|p| p.id.clone());
+
+    let ncr_dir = project.root().join("manufacturing/ncrs");
+    fs::create_dir_all(&ncr_dir).into_diagnostic()?;
+    let ncr_id = ncr.id.to_string();
+    let yaml_content = serde_yml::to_string(&ncr).into_diagnostic()?;
+    fs::write(ncr_dir.join(format!("{}.tdt.yaml", ncr_id)), yaml_content).into_diagnostic()?;
+    Ok(ncr_id)
+}
+
+/// Load a lot by full or short ID, returning its file path
+fn load_lot(project: &Project, short_ids: &ShortIdIndex, id: &str) -> Result<(PathBuf, Lot)> {
+    let resolved = short_ids.resolve(id).unwrap_or_else(|| id.to_string());
+    loader::load_entity(&project.root().join("manufacturing/lots"), &resolved)?
+        .ok_or_else(|| miette::miette!("No lot found matching '{}'", id))
+}
+
+/// Write a lot back to disk
+fn save_lot(path: &Path, lot: &Lot) -> Result<()> {
+    let yaml_content = serde_yml::to_string(lot).into_diagnostic()?;
+    fs::write(path, yaml_content).into_diagnostic()
+}
+
+/// Display a lot reference as short ID plus lot number
+fn lot_display(short_ids: &ShortIdIndex, lot: &Lot) -> String {
+    let short = short_ids
+        .get_short_id(&lot.id.to_string())
+        .unwrap_or_else(|| format_short_id(&lot.id));
+    match lot.lot_number {
+        Some(ref ln) => format!("{} ({})", short, ln),
+        None => short,
+    }
+}
+
+fn run_serial(args: SerialArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, mut lot) = load_lot(&project, &short_ids, &args.lot)?;
+    let display_id = lot_display(&short_ids, &lot);
+
+    // Collect new serials from --add and --generate
+    let mut new_serials: Vec<String> = args
+        .add
+        .iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if let Some(count) = args.generate {
+        let prefix = args
+            .prefix
+            .clone()
+            .unwrap_or_else(|| format!("{}-", lot.label()));
+        for n in args.start..args.start.saturating_add(count) {
+            new_serials.push(format!("{}{:0width$}", prefix, n, width = args.width));
+        }
+    }
+
+    let is_update = !new_serials.is_empty() || args.status.is_some();
+
+    if !new_serials.is_empty() {
+        // Serials must be unique across every lot in the project
+        let all_lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+        let mut existing: HashMap<String, String> = HashMap::new();
+        for other in &all_lots {
+            for unit in &other.serials {
+                existing.insert(unit.serial.clone(), other.label());
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for serial in &new_serials {
+            if let Some(owner) = existing.get(serial) {
+                return Err(miette::miette!(
+                    "Serial {} already exists in lot {}",
+                    serial,
+                    owner
+                ));
+            }
+            if !seen.insert(serial.clone()) {
+                return Err(miette::miette!("Serial {} given more than once", serial));
+            }
+        }
+
+        lot.serials
+            .extend(new_serials.iter().map(|serial| SerialUnit {
+                serial: serial.clone(),
+                ..Default::default()
+            }));
+
+        if let Some(qty) = lot.quantity {
+            if lot.serials.len() > qty as usize {
+                eprintln!(
+                    "{} Warning: lot {} now has {} serials but quantity is {}",
+                    style("!").yellow(),
+                    display_id,
+                    lot.serials.len(),
+                    qty
+                );
+            }
+        }
+    }
+
+    if let Some(status) = args.status {
+        let status = UnitStatus::from(status);
+        for serial in &args.serials {
+            let unit = lot
+                .serials
+                .iter_mut()
+                .find(|u| &u.serial == serial)
+                .ok_or_else(|| {
+                    miette::miette!("Serial {} not found in lot {}", serial, display_id)
+                })?;
+            unit.unit_status = status;
+        }
+    }
+
+    if is_update {
+        lot.entity_revision += 1;
+        save_lot(&path, &lot)?;
+    }
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&lot.serials).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&lot.serials).into_diagnostic()?);
+        }
+        OutputFormat::Csv => {
+            println!("serial,unit_status,materials");
+            for unit in &lot.serials {
+                println!(
+                    "{},{},{}",
+                    escape_csv(&unit.serial),
+                    unit.unit_status,
+                    unit.materials.len()
+                );
+            }
+        }
+        OutputFormat::Id | OutputFormat::ShortId => {
+            for unit in &lot.serials {
+                println!("{}", unit.serial);
+            }
+        }
+        _ => {
+            if !new_serials.is_empty() {
+                println!(
+                    "{} Added {} serial{} to lot {}",
+                    style("✓").green(),
+                    style(new_serials.len()).cyan(),
+                    if new_serials.len() == 1 { "" } else { "s" },
+                    style(&display_id).cyan()
+                );
+            }
+            if let Some(status) = args.status {
+                println!(
+                    "{} Set {} serial{} to {}",
+                    style("✓").green(),
+                    style(args.serials.len()).cyan(),
+                    if args.serials.len() == 1 { "" } else { "s" },
+                    style(UnitStatus::from(status).to_string()).yellow()
+                );
+            }
+            if !is_update {
+                if lot.serials.is_empty() {
+                    println!("No serials in lot {}.", display_id);
+                    return Ok(());
+                }
+                println!(
+                    "{:<20} {:<12} {}",
+                    style("SERIAL").bold(),
+                    style("STATUS").bold(),
+                    style("MATERIALS").bold()
+                );
+                println!("{}", "-".repeat(44));
+                for unit in &lot.serials {
+                    let status_styled = match unit.unit_status {
+                        UnitStatus::InProcess => style(unit.unit_status.to_string()).yellow(),
+                        UnitStatus::Completed => style(unit.unit_status.to_string()).green(),
+                        UnitStatus::Scrapped => style(unit.unit_status.to_string()).red(),
+                    };
+                    println!(
+                        "{:<20} {:<12} {}",
+                        unit.serial,
+                        status_styled,
+                        unit.materials.len()
+                    );
+                }
+                println!();
+                println!(
+                    "{} serial(s) in lot {}",
+                    style(lot.serials.len()).cyan(),
+                    display_id
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_material(args: MaterialArgs) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, mut lot) = load_lot(&project, &short_ids, &args.lot)?;
+    let display_id = lot_display(&short_ids, &lot);
+
+    if args.component.is_none() && args.source_lot.is_none() && args.receipt.is_none() {
+        return Err(miette::miette!(
+            "Specify the consumed material with --component, --receipt and/or --source-lot"
+        ));
+    }
+
+    let mut component = args
+        .component
+        .as_ref()
+        .map(|c| short_ids.resolve(c).unwrap_or_else(|| c.clone()));
+    let mut supplier_lot = args.supplier_lot.clone();
+    let quantity = args
+        .quantity
+        .or_else(|| (!args.source_serials.is_empty()).then_some(args.source_serials.len() as u32));
+
+    // Purchased material must come from an accepted receipt
+    let receipt = resolve_receipt(
+        &project,
+        &short_ids,
+        args.receipt.as_deref(),
+        component.as_deref(),
+        supplier_lot.as_deref(),
+    )?;
+    if let Some(ref rcv) = receipt {
+        let rcv_display = short_ids
+            .get_short_id(&rcv.id.to_string())
+            .unwrap_or_else(|| rcv.id.to_string());
+        if !rcv.is_usable() {
+            return Err(miette::miette!(
+                "Receipt {} is {}; only accepted receipts can be consumed",
+                rcv_display,
+                rcv.receipt_status
+            ));
+        }
+        let rcv_component = rcv.links.component.as_ref().map(|c| c.to_string());
+        if component.is_some() && rcv_component.is_some() && component != rcv_component {
+            return Err(miette::miette!(
+                "Receipt {} is for a different component",
+                rcv_display
+            ));
+        }
+        if let (Some(received), Some(qty)) = (rcv.quantity, quantity) {
+            let lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+            let used = receiving::consumed(&lots, &rcv.id.to_string());
+            if used + qty > received {
+                return Err(miette::miette!(
+                    "Receipt {} has {} of {} remaining; cannot consume {}",
+                    rcv_display,
+                    received.saturating_sub(used),
+                    received,
+                    qty
+                ));
+            }
+        }
+        component = component.or(rcv_component);
+        supplier_lot = supplier_lot.or_else(|| rcv.supplier_lot.clone());
+    } else if args.source_lot.is_none() && !args.no_receipt {
+        if let Some(ref cmp_id) = component {
+            let components: Vec<Component> =
+                loader::load_all(&project.root().join("bom/components"))?;
+            let purchased = components
+                .iter()
+                .find(|c| &c.id.to_string() == cmp_id)
+                .filter(|c| c.make_buy == MakeBuy::Buy);
+            if let Some(cmp) = purchased {
+                return Err(miette::miette!(
+                    "{} is a purchased part with no accepted receipt{}; receive it with 'tdt rcv new', give --receipt, or pass --no-receipt",
+                    cmp.part_number,
+                    supplier_lot
+                        .as_ref()
+                        .map(|l| format!(" for supplier lot {}", l))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+    }
+
+    let source_lot = match args.source_lot {
+        Some(ref src) => {
+            let (_, src_lot) = load_lot(&project, &short_ids, src)?;
+            if src_lot.id == lot.id {
+                return Err(miette::miette!("A lot cannot consume itself"));
+            }
+            // Warn about serials that are not in the source lot
+            for sn in &args.source_serials {
+                if src_lot.find_serial(sn).is_none() {
+                    eprintln!(
+                        "{} Warning: serial {} is not in source lot {}",
+                        style("!").yellow(),
+                        sn,
+                        lot_display(&short_ids, &src_lot)
+                    );
+                }
+            }
+            Some(src_lot.id.to_string())
+        }
+        None => None,
+    };
+
+    let material = MaterialUsed {
+        component,
+        supplier_lot,
+        quantity,
+        source_lot,
+        serials: args.source_serials.clone(),
+        receipt: receipt.map(|r| r.id.to_string()),
+    };
+
+    let target = match args.serial {
+        Some(ref serial) => {
+            let unit = lot
+                .serials
+                .iter_mut()
+                .find(|u| &u.serial == serial)
+                .ok_or_else(|| {
+                    miette::miette!("Serial {} not found in lot {}", serial, display_id)
+                })?;
+            unit.materials.push(material);
+            format!("serial {}", serial)
+        }
+        None => {
+            lot.materials_used.push(material);
+            format!("lot {}", display_id)
+        }
+    };
+
+    lot.entity_revision += 1;
+    save_lot(&path, &lot)?;
+
+    println!(
+        "{} Recorded material for {}",
+        style("✓").green(),
+        style(&target).cyan()
+    );
+    Ok(())
+}
+
+/// Find the receipt purchased material is drawn from
+///
+/// An explicit `--receipt` is loaded directly. Otherwise a component and
+/// supplier lot with receiving records resolve to the accepted receipt (or,
+/// when none is accepted, the first one so the caller can refuse it).
+/// Returns `None` when there are no receiving records; the caller decides
+/// whether the material may be recorded without one.
+fn resolve_receipt(
+    project: &Project,
+    short_ids: &ShortIdIndex,
+    receipt: Option<&str>,
+    component: Option<&str>,
+    supplier_lot: Option<&str>,
+) -> Result<Option<Receipt>> {
+    if let Some(id) = receipt {
+        let (_, rcv) = super::rcv::load_receipt(project, short_ids, id)?;
+        return Ok(Some(rcv));
+    }
+    let (Some(component), Some(supplier_lot)) = (component, supplier_lot) else {
+        return Ok(None);
+    };
+    let receipts: Vec<Receipt> = loader::load_all(&project.root().join("manufacturing/receipts"))?;
+    let matching = receiving::receipts_for_supplier_lot(&receipts, component, supplier_lot);
+    Ok(matching
+        .iter()
+        .find(|r| r.is_usable())
+        .or(matching.first())
+        .map(|r| (*r).clone()))
+}
+
+fn run_split(args: SplitArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+    let (parent_path, mut parent) = load_lot(&project, &short_ids, &args.lot)?;
+    let parent_display = lot_display(&short_ids, &parent);
+
+    if matches!(
+        parent.lot_status,
+        LotStatus::Completed | LotStatus::Scrapped
+    ) {
+        return Err(miette::miette!(
+            "Lot {} is {} and cannot be split",
+            parent_display,
+            parent.lot_status
+        ));
+    }
+
+    for serial in &args.serials {
+        if parent.find_serial(serial).is_none() {
+            return Err(miette::miette!(
+                "Serial {} not found in lot {}",
+                serial,
+                parent_display
+            ));
+        }
+    }
+
+    let quantity = match (args.quantity, args.serials.len()) {
+        (Some(q), _) => q,
+        (None, n) if n > 0 => n as u32,
+        _ => {
+            return Err(miette::miette!(
+                "Specify --quantity and/or --serials for the new lot"
+            ))
+        }
+    };
+    if quantity == 0 {
+        return Err(miette::miette!("Split quantity must be greater than zero"));
+    }
+    if (args.serials.len() as u32) > quantity {
+        return Err(miette::miette!(
+            "{} serials given but split quantity is {}",
+            args.serials.len(),
+            quantity
+        ));
+    }
+    if let Some(parent_qty) = parent.quantity {
+        if quantity >= parent_qty {
+            return Err(miette::miette!(
+                "Split quantity {} must be less than lot quantity {}",
+                quantity,
+                parent_qty
+            ));
+        }
+    }
+
+    let lot_number = args
+        .lot_number
+        .clone()
+        .unwrap_or_else(|| format!("{}-{}", parent.label(), parent.links.child_lots.len() + 1));
+    let title = args
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} (split)", parent.title));
+
+    // The child inherits the parent's materials and as-run history
+    let mut child = Lot::with_lot_number(title, lot_number, config.author());
+    child.quantity = Some(quantity);
+    child.lot_status = parent.lot_status;
+    child.start_date = parent.start_date;
+    child.materials_used = parent.materials_used.clone();
+    child.execution = parent.execution.clone();
+    child.links.product = parent.links.product.clone();
+    child.links.processes = parent.links.processes.clone();
+    child.links.work_instructions = parent.links.work_instructions.clone();
+    child.links.parent_lots.push(parent.id.to_string());
+    child.origin = Some(LotOrigin {
+        operation: GenealogyOperation::Split,
+        date: chrono::Utc::now(),
+        performed_by: config.author(),
+        notes: args.notes.clone(),
+    });
+
+    // Move serials from parent to child
+    let (moved, kept): (Vec<SerialUnit>, Vec<SerialUnit>) = parent
+        .serials
+        .drain(..)
+        .partition(|u| args.serials.contains(&u.serial));
+    parent.serials = kept;
+    child.serials = moved;
+
+    parent.quantity = parent.quantity.map(|q| q - quantity);
+    parent.links.child_lots.push(child.id.to_string());
+    parent.entity_revision += 1;
+
+    let child_path = project
+        .root()
+        .join("manufacturing/lots")
+        .join(format!("{}.tdt.yaml", child.id));
+    save_lot(&child_path, &child)?;
+    save_lot(&parent_path, &parent)?;
+
+    let child_short = short_ids.add(child.id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    match global.output {
+        OutputFormat::Id => println!("{}", child.id),
+        OutputFormat::ShortId => println!(
+            "{}",
+            child_short.unwrap_or_else(|| format_short_id(&child.id))
+        ),
+        OutputFormat::Path => println!("{}", child_path.display()),
+        _ => {
+            println!(
+                "{} Split {} unit{} from {} into {}",
+                style("✓").green(),
+                style(quantity).cyan(),
+                if quantity == 1 { "" } else { "s" },
+                style(&parent_display).cyan(),
+                style(lot_display(&short_ids, &child)).cyan()
+            );
+            println!("   {}", style(child_path.display()).dim());
+            if let Some(q) = parent.quantity {
+                println!("   {} {} remaining in parent", style("→").dim(), q);
+            }
+            if !child.serials.is_empty() {
+                println!(
+                    "   {} {} serial{} moved",
+                    style("→").dim(),
+                    child.serials.len(),
+                    if child.serials.len() == 1 { "" } else { "s" }
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_merge(args: MergeArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+
+    let mut sources: Vec<(PathBuf, Lot)> = Vec::new();
+    for id in &args.lots {
+        let (path, lot) = load_lot(&project, &short_ids, id)?;
+        if sources.iter().any(|(_, l)| l.id == lot.id) {
+            return Err(miette::miette!("Lot {} given more than once", id));
+        }
+        if matches!(lot.lot_status, LotStatus::Completed | LotStatus::Scrapped) {
+            return Err(miette::miette!(
+                "Lot {} is {} and cannot be merged",
+                lot_display(&short_ids, &lot),
+                lot.lot_status
+            ));
+        }
+        sources.push((path, lot));
+    }
+
+    // All sources must make the same product
+    let product = sources[0].1.links.product.clone();
+    if let Some((_, other)) = sources.iter().find(|(_, l)| l.links.product != product) {
+        return Err(miette::miette!(
+            "Cannot merge lots for different products ({} makes {})",
+            lot_display(&short_ids, other),
+            other.links.product.as_deref().unwrap_or("nothing")
+        ));
+    }
+
+    let first = &sources[0].1;
+    let lot_number = args.lot_number.clone().unwrap_or_else(|| {
+        let labels: Vec<String> = sources.iter().map(|(_, l)| l.label()).collect();
+        format!("M-{}", labels.join("+"))
+    });
+    let title = args
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} (merged)", first.title));
+
+    let mut merged = Lot::with_lot_number(title, lot_number, config.author());
+    merged.quantity = sources.iter().map(|(_, l)| l.quantity).sum::<Option<u32>>();
+    merged.start_date = sources.iter().filter_map(|(_, l)| l.start_date).min();
+    merged.links.product = product;
+    merged.links.processes = first.links.processes.clone();
+    merged.links.work_instructions = first.links.work_instructions.clone();
+    merged.origin = Some(LotOrigin {
+        operation: GenealogyOperation::Merge,
+        date: chrono::Utc::now(),
+        performed_by: config.author(),
+        notes: args.notes.clone(),
+    });
+
+    // A step keeps its record only if every source has finished it;
+    // otherwise it restarts as pending in the merged lot. The per-source
+    // records stay in the parent lots.
+    let step_done = |lot: &Lot, proc: &Option<String>| {
+        lot.execution.iter().any(|s| {
+            &s.process == proc
+                && matches!(
+                    s.status,
+                    ExecutionStatus::Completed | ExecutionStatus::Skipped
+                )
+        })
+    };
+    merged.execution = first
+        .execution
+        .iter()
+        .map(|step| {
+            if sources.iter().all(|(_, l)| step_done(l, &step.process)) {
+                step.clone()
+            } else {
+                ExecutionStep {
+                    process: step.process.clone(),
+                    process_revision: step.process_revision,
+                    work_instructions_used: step.work_instructions_used.clone(),
+                    ..Default::default()
+                }
+            }
+        })
+        .collect();
+
+    for (_, source) in sources.iter_mut() {
+        merged
+            .materials_used
+            .extend(source.materials_used.iter().cloned());
+        merged.serials.append(&mut source.serials);
+        merged.links.parent_lots.push(source.id.to_string());
+        for ncr in &source.links.ncrs {
+            if !merged.links.ncrs.contains(ncr) {
+                merged.links.ncrs.push(ncr.clone());
+            }
+        }
+    }
+
+    for (path, source) in sources.iter_mut() {
+        source.quantity = source.quantity.map(|_| 0);
+        source.links.child_lots.push(merged.id.to_string());
+        source.entity_revision += 1;
+        save_lot(path, source)?;
+    }
+
+    let merged_path = project
+        .root()
+        .join("manufacturing/lots")
+        .join(format!("{}.tdt.yaml", merged.id));
+    save_lot(&merged_path, &merged)?;
+
+    let merged_short = short_ids.add(merged.id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    match global.output {
+        OutputFormat::Id => println!("{}", merged.id),
+        OutputFormat::ShortId => println!(
+            "{}",
+            merged_short.unwrap_or_else(|| format_short_id(&merged.id))
+        ),
+        OutputFormat::Path => println!("{}", merged_path.display()),
+        _ => {
+            println!(
+                "{} Merged {} lots into {}",
+                style("✓").green(),
+                style(sources.len()).cyan(),
+                style(lot_display(&short_ids, &merged)).cyan()
+            );
+            println!("   {}", style(merged_path.display()).dim());
+            for (_, source) in &sources {
+                println!(
+                    "   {} {}",
+                    style("←").dim(),
+                    lot_display(&short_ids, source)
+                );
+            }
+            if let Some(q) = merged.quantity {
+                println!("   {} Quantity: {}", style("→").dim(), q);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_trace(args: TraceArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+    let graph = GenealogyGraph::new(&lots);
+
+    // LOT@N and partial IDs resolve to lots; anything else is a serial or supplier lot
+    let query = match short_ids.resolve(&args.target) {
+        Some(id) if id.starts_with("LOT-") => id,
+        _ => args.target.clone(),
+    };
+    let query = lots
+        .iter()
+        .map(|l| l.id.to_string())
+        .find(|id| query.starts_with("LOT-") && id.starts_with(&query))
+        .unwrap_or(query);
+    let target = graph.classify(&query);
+
+    let display_lot = |id: &str| -> String {
+        match graph.lot(id) {
+            Some(lot) => lot_display(&short_ids, lot),
+            None => format_short_id_str(id),
+        }
+    };
+
+    if args.backward {
+        let tree = graph.trace_backward(&target).ok_or_else(|| {
+            miette::miette!(
+                "No lot or serial found matching '{}' (supplier lots have no backward trace)",
+                args.target
+            )
+        })?;
+
+        match global.output {
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&tree).into_diagnostic()?);
+            }
+            OutputFormat::Yaml => {
+                print!("{}", serde_yml::to_string(&tree).into_diagnostic()?);
+            }
+            OutputFormat::Csv => {
+                println!("component,supplier_lot");
+                for (cmp, supplier_lots) in GenealogyGraph::supplier_lots(&tree) {
+                    for sl in supplier_lots {
+                        println!("{},{}", cmp, escape_csv(&sl));
+                    }
+                }
+            }
+            _ => {
+                println!(
+                    "{} {}",
+                    style("Backward trace:").bold(),
+                    style(target.to_string()).cyan()
+                );
+                println!("{}", style("─".repeat(60)).dim());
+
+                fn print_node(
+                    node: &TraceNode,
+                    prefix: &str,
+                    is_last: bool,
+                    root: bool,
+                    short_ids: &ShortIdIndex,
+                ) {
+                    let label = match node.kind {
+                        TraceNodeKind::Lot => style(
+                            node.label.replace(
+                                &node.id,
+                                &short_ids
+                                    .get_short_id(&node.id)
+                                    .unwrap_or_else(|| node.id.clone()),
+                            ),
+                        )
+                        .cyan(),
+                        TraceNodeKind::Serial => style(node.label.clone()).yellow(),
+                        TraceNodeKind::Material => style(
+                            node.label.replace(
+                                &node.id,
+                                &short_ids
+                                    .get_short_id(&node.id)
+                                    .unwrap_or_else(|| node.id.clone()),
+                            ),
+                        )
+                        .white(),
+                    };
+                    if root {
+                        println!("{}", label);
+                    } else {
+                        let branch = if is_last { "└─ " } else { "├─ " };
+                        println!("{}{}{}", prefix, branch, label);
+                    }
+                    let child_prefix = if root {
+                        String::new()
+                    } else if is_last {
+                        format!("{}   ", prefix)
+                    } else {
+                        format!("{}│  ", prefix)
+                    };
+                    for (i, child) in node.children.iter().enumerate() {
+                        print_node(
+                            child,
+                            &child_prefix,
+                            i == node.children.len() - 1,
+                            false,
+                            short_ids,
+                        );
+                    }
+                }
+                print_node(&tree, "", true, true, &short_ids);
+
+                let supplier_lots = GenealogyGraph::supplier_lots(&tree);
+                if !supplier_lots.is_empty() {
+                    println!();
+                    println!("{}", style("Supplier lots:").bold());
+                    for (cmp, lots) in &supplier_lots {
+                        let cmp_display =
+                            short_ids.get_short_id(cmp).unwrap_or_else(|| cmp.clone());
+                        let lots: Vec<&str> = lots.iter().map(|s| s.as_str()).collect();
+                        println!("  {}: {}", style(cmp_display).cyan(), lots.join(", "));
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    if !args.forward {
+        return Err(miette::miette!(
+            "Specify a trace direction: --forward or --backward"
+        ));
+    }
+
+    let mut trace = graph.trace_forward(&target);
+    if args.finished {
+        trace
+            .serials
+            .retain(|s| s.unit_status == UnitStatus::Completed);
+    }
+
+    match global.output {
+        OutputFormat::Json => {
+            let result = serde_json::json!({
+                "target": target.to_string(),
+                "lots": trace.lots,
+                "serials": trace.serials,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&trace).into_diagnostic()?);
+        }
+        OutputFormat::Csv => {
+            println!("serial,lot,lot_number,unit_status");
+            for s in &trace.serials {
+                let lot_number = graph
+                    .lot(&s.lot)
+                    .and_then(|l| l.lot_number.clone())
+                    .unwrap_or_default();
+                println!(
+                    "{},{},{},{}",
+                    escape_csv(&s.serial),
+                    s.lot,
+                    escape_csv(&lot_number),
+                    s.unit_status
+                );
+            }
+        }
+        OutputFormat::Id | OutputFormat::ShortId => {
+            for s in &trace.serials {
+                println!("{}", s.serial);
+            }
+        }
+        _ => {
+            println!(
+                "{} {}",
+                style("Forward trace:").bold(),
+                style(target.to_string()).cyan()
+            );
+            println!("{}", style("─".repeat(60)).dim());
+
+            if trace.lots.is_empty() {
+                println!("No lots contain {}.", target);
+                return Ok(());
+            }
+
+            println!("{} ({}):", style("Lots").bold(), trace.lots.len());
+            for lot in &trace.lots {
+                let scope = if lot.whole_lot {
+                    style("whole lot".to_string()).red()
+                } else {
+                    style("some serials".to_string()).yellow()
+                };
+                let qty = lot
+                    .quantity
+                    .map(|q| format!(" | qty {}", q))
+                    .unwrap_or_default();
+                println!("  • {} [{}]{}", display_lot(&lot.lot), scope, qty);
+            }
+
+            if !trace.serials.is_empty() {
+                println!();
+                println!("{} ({}):", style("Serials").bold(), trace.serials.len());
+                for s in &trace.serials {
+                    let status_styled = match s.unit_status {
+                        UnitStatus::InProcess => style(s.unit_status.to_string()).yellow(),
+                        UnitStatus::Completed => style(s.unit_status.to_string()).green(),
+                        UnitStatus::Scrapped => style(s.unit_status.to_string()).dim(),
+                    };
+                    println!(
+                        "  {:<20} {:<12} {}",
+                        s.serial,
+                        status_styled,
+                        style(display_lot(&s.lot)).dim()
+                    );
+                }
+            }
+
+            println!();
+            println!(
+                "{} lot(s), {} finished serial(s) affected",
+                style(trace.lots.len()).cyan(),
+                style(trace.finished_serials().count()).cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn run_dhr(args: DhrArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (lot_path, lot) = load_lot(&project, &short_ids, &args.lot)?;
+
+    let options = DhrOptions {
+        verify_signatures: !args.no_verify,
+        generated_by: Config::load().author(),
+    };
+    let dhr = DhrBuilder::new(&project, options).build(&lot_path, &lot);
+
+    let document = match args.format {
+        DhrFormat::Md => dhr.to_markdown(),
+        DhrFormat::Html => dhr.to_html(),
+    };
+
+    if args.stdout {
+        print!("{}", document);
+        return Ok(());
+    }
+    match global.output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&dhr).into_diagnostic()?);
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&dhr).into_diagnostic()?);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Bundle: document, manifest, and copies of every source record
+    let out_dir = args.dir.unwrap_or_else(|| {
+        let label = lot.label().replace(['/', '\\', ' '], "_");
+        PathBuf::from(format!("DHR-{}", label))
+    });
+    let records_dir = out_dir.join("records");
+    fs::create_dir_all(&records_dir).into_diagnostic()?;
+
+    let doc_name = match args.format {
+        DhrFormat::Md => "DHR.md",
+        DhrFormat::Html => "DHR.html",
+    };
+    let doc_path = out_dir.join(doc_name);
+    fs::write(&doc_path, &document).into_diagnostic()?;
+
+    for record in &dhr.records {
+        let src = project.root().join(&record.path);
+        let dest = records_dir.join(&record.path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).into_diagnostic()?;
+        }
+        fs::copy(&src, &dest).into_diagnostic()?;
+    }
+
+    let manifest = serde_json::json!({
+        "lot": dhr.lot,
+        "lot_number": dhr.lot_number,
+        "generated": dhr.generated,
+        "generated_by": dhr.generated_by,
+        "git_commit": dhr.git_commit,
+        "document": {
+            "path": doc_name,
+            "sha256": crate::core::dhr::sha256_file(&doc_path).into_diagnostic()?,
+        },
+        "records": dhr.records,
+    });
+    fs::write(
+        out_dir.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest).into_diagnostic()?,
+    )
+    .into_diagnostic()?;
+
+    println!(
+        "{} Exported DHR for {} to {}",
+        style("✓").green(),
+        style(lot_display(&short_ids, &lot)).cyan(),
+        style(out_dir.display()).yellow()
+    );
+    println!(
+        "   {} step(s), {} deviation(s), {} NCR(s), {} result(s), {} record(s)",
+        dhr.steps.len(),
+        dhr.deviations.len(),
+        dhr.ncrs.len(),
+        dhr.results.len(),
+        dhr.records.len()
+    );
+    for finding in dhr.findings() {
+        eprintln!("{} {}", style("!").yellow(), finding);
+    }
+
+    Ok(())
+}
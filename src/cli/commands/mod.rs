@@ -3,12 +3,14 @@
 pub mod utils;
 
 pub mod asm;
+pub mod audit;
 pub mod baseline;
 pub mod blame;
 pub mod bulk;
 pub mod cache;
 pub mod capa;
 pub mod cmp;
+pub mod cmpl;
 pub mod completions;
 pub mod config;
 pub mod ctrl;
@@ -26,8 +28,10 @@ pub mod log;
 pub mod lot;
 pub mod mate;
 pub mod ncr;
+pub mod plan;
 pub mod proc;
 pub mod quote;
+pub mod rcv;
 pub mod recent;
 pub mod report;
 pub mod req;
@@ -4,16 +4,26 @@ use clap::{Subcommand, ValueEnum};
 use console::style;
 use miette::{IntoDiagnostic, Result};
 use std::fs;
+use std::path::PathBuf;
 
 use crate::cli::commands::utils::format_link_with_title;
-use crate::cli::helpers::format_short_id;
+use crate::cli::helpers::{escape_csv, format_short_id};
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::{CachedNcr, EntityCache};
+use crate::core::git::Git;
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
+use crate::core::mrb::{self, MrbConfig, CUSTOMER_ROLE};
+use crate::core::ncr_trends::{self, Escalation, TrendKey};
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
+use crate::core::team::{Role, TeamRoster};
+use crate::core::workflow::ApprovalRecord;
 use crate::core::Config;
+use crate::entities::capa::{Capa, Source};
+use crate::entities::component::Component;
+use crate::entities::dev::Dev;
 use crate::entities::ncr::{
     Disposition, DispositionDecision, Ncr, NcrCategory, NcrSeverity, NcrStatus, NcrType,
 };
@@ -136,8 +146,17 @@ pub enum NcrCommands {
     /// Archive an NCR (soft delete)
     Archive(ArchiveArgs),
 
+    /// Propose a disposition for Material Review Board sign-off
+    Disposition(DispositionArgs),
+
+    /// Record an MRB sign-off on an NCR's proposed disposition
+    MrbApprove(MrbApproveArgs),
+
     /// Close an NCR with disposition
     Close(CloseArgs),
+
+    /// Detect recurring NCRs and propose (or create) CAPAs per escalation rules
+    Trends(TrendsArgs),
 }
 
 /// NCR type filter
@@ -342,6 +361,21 @@ pub struct ArchiveArgs {
     pub quiet: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct TrendsArgs {
+    /// Only evaluate the escalation rule with this name
+    #[arg(long)]
+    pub rule: Option<String>,
+
+    /// Evaluate rule windows as of this date (YYYY-MM-DD, default today)
+    #[arg(long)]
+    pub as_of: Option<chrono::NaiveDate>,
+
+    /// Create a CAPA for each escalation and link the matching NCRs
+    #[arg(long)]
+    pub create: bool,
+}
+
 /// Directories where NCRs are stored
 const NCR_DIRS: &[&str] = &["manufacturing/ncrs"];
 
@@ -360,12 +394,70 @@ pub enum CliDisposition {
     UseAsIs,
     /// Rework the part to spec
     Rework,
+    /// Repair the part (functional, but not to original spec)
+    Repair,
     /// Scrap the part
     Scrap,
     /// Return to supplier
     Return,
 }
 
+impl CliDisposition {
+    fn decision(self) -> DispositionDecision {
+        match self {
+            CliDisposition::UseAsIs => DispositionDecision::UseAsIs,
+            CliDisposition::Rework => DispositionDecision::Rework,
+            CliDisposition::Repair => DispositionDecision::Repair,
+            CliDisposition::Scrap => DispositionDecision::Scrap,
+            CliDisposition::Return => DispositionDecision::ReturnToSupplier,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DispositionArgs {
+    /// NCR ID or short ID (NCR@N)
+    pub ncr: String,
+
+    /// Proposed disposition decision
+    #[arg(long, short = 'd')]
+    pub disposition: CliDisposition,
+
+    /// Disposition justification/rationale (engineering justification for use-as-is)
+    #[arg(long, short = 'r')]
+    pub rationale: Option<String>,
+
+    /// Deviation (DEV) authorizing a use-as-is disposition
+    #[arg(long)]
+    pub deviation: Option<String>,
+
+    /// Require MRB sign-off even if the decision does not need it by default
+    #[arg(long)]
+    pub mrb: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MrbApproveArgs {
+    /// NCR ID or short ID (NCR@N)
+    pub ncr: String,
+
+    /// MRB role to sign for (default: first unsigned required role you hold)
+    #[arg(long, value_enum)]
+    pub role: Option<Role>,
+
+    /// Record customer concurrence from this customer contact
+    #[arg(long, conflicts_with = "role")]
+    pub customer: Option<String>,
+
+    /// Sign-off comment
+    #[arg(long, short = 'm')]
+    pub message: Option<String>,
+
+    /// Record the sign-off in a signed git commit
+    #[arg(long, short = 'S')]
+    pub sign: bool,
+}
+
 #[derive(clap::Args, Debug)]
 pub struct CloseArgs {
     /// NCR ID or short ID (NCR@N)
@@ -379,6 +471,10 @@ pub struct CloseArgs {
     #[arg(long, short = 'r')]
     pub rationale: Option<String>,
 
+    /// Deviation (DEV) authorizing a use-as-is disposition
+    #[arg(long)]
+    pub deviation: Option<String>,
+
     /// Link to CAPA (create if needed)
     #[arg(long)]
     pub capa: Option<String>,
@@ -397,7 +493,10 @@ pub fn run(cmd: NcrCommands, global: &GlobalOpts) -> Result<()> {
         NcrCommands::Edit(args) => run_edit(args),
         NcrCommands::Delete(args) => run_delete(args),
         NcrCommands::Archive(args) => run_archive(args),
+        NcrCommands::Disposition(args) => run_disposition(args, global),
+        NcrCommands::MrbApprove(args) => run_mrb_approve(args, global),
         NcrCommands::Close(args) => run_close(args, global),
+        NcrCommands::Trends(args) => run_trends(args, global),
     }
 }
 
@@ -953,6 +1052,52 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                 }
             }
 
+            // Disposition and MRB sign-off
+            if let Some(ref disp) = ncr.disposition {
+                let mrb_config = Config::load().mrb;
+                let required = mrb_config
+                    .as_ref()
+                    .map(|m| m.required_signoffs(disp))
+                    .unwrap_or_default();
+                if let Some(ref justification) = disp.justification {
+                    println!();
+                    println!("{}: {}", style("Justification").bold(), justification);
+                }
+                if let Some(ref dev) = disp.deviation {
+                    let display = short_ids
+                        .get_short_id(&dev.to_string())
+                        .unwrap_or_else(|| dev.to_string());
+                    println!("{}: {}", style("Deviation").bold(), style(display).cyan());
+                }
+                if !required.is_empty() || !disp.approvals.is_empty() {
+                    println!();
+                    println!("{}", style("MRB Sign-off:").bold());
+                    for approval in &disp.approvals {
+                        let signed = match approval.signature_verified {
+                            Some(true) => " (signature verified)",
+                            Some(false) => " (signature not verified)",
+                            None if approval.signing_key.is_some() => " (signed)",
+                            None => "",
+                        };
+                        println!(
+                            "  {} {}: {} on {}{}",
+                            style("✓").green(),
+                            approval.role.as_deref().unwrap_or("-"),
+                            approval.approver,
+                            approval.timestamp.format("%Y-%m-%d"),
+                            signed
+                        );
+                    }
+                    let missing = mrb_config
+                        .as_ref()
+                        .map(|m| m.missing_signoffs(disp))
+                        .unwrap_or_default();
+                    for role in missing {
+                        println!("  {} {}: pending", style("○").yellow(), role);
+                    }
+                }
+            }
+
             // Tags
             if !ncr.tags.is_empty() {
                 println!();
@@ -962,8 +1107,10 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
             // Links
             let cache = EntityCache::open(&project).ok();
             let has_links = ncr.links.component.is_some()
+                || ncr.links.supplier.is_some()
                 || ncr.links.process.is_some()
                 || ncr.links.control.is_some()
+                || ncr.links.receipt.is_some()
                 || ncr.links.capa.is_some();
 
             if has_links {
@@ -975,6 +1122,11 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                     println!("  {}: {}", style("Component").dim(), style(&display).cyan());
                 }
 
+                if let Some(ref id) = ncr.links.supplier {
+                    let display = format_link_with_title(&id.to_string(), &short_ids, &cache);
+                    println!("  {}: {}", style("Supplier").dim(), style(&display).cyan());
+                }
+
                 if let Some(ref id) = ncr.links.process {
                     let display = format_link_with_title(&id.to_string(), &short_ids, &cache);
                     println!("  {}: {}", style("Process").dim(), style(&display).cyan());
@@ -985,6 +1137,11 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
                     println!("  {}: {}", style("Control").dim(), style(&display).cyan());
                 }
 
+                if let Some(ref id) = ncr.links.receipt {
+                    let display = format_link_with_title(&id.to_string(), &short_ids, &cache);
+                    println!("  {}: {}", style("Receipt").dim(), style(&display).cyan());
+                }
+
                 if let Some(ref id) = ncr.links.capa {
                     let display = format_link_with_title(&id.to_string(), &short_ids, &cache);
                     println!("  {}: {}", style("CAPA").dim(), style(&display).cyan());
@@ -1128,26 +1285,8 @@ fn run_close(args: CloseArgs, global: &GlobalOpts) -> Result<()> {
         .resolve(&args.ncr)
         .unwrap_or_else(|| args.ncr.clone());
 
-    // Find the NCR file
-    let ncr_dir = project.root().join("manufacturing/ncrs");
-    let mut found_path = None;
-
-    if ncr_dir.exists() {
-        for entry in fs::read_dir(&ncr_dir).into_diagnostic()? {
-            let entry = entry.into_diagnostic()?;
-            let path = entry.path();
-
-            if path.extension().is_some_and(|e| e == "yaml") {
-                let filename = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-                if filename.contains(&resolved_id) || filename.starts_with(&resolved_id) {
-                    found_path = Some(path);
-                    break;
-                }
-            }
-        }
-    }
-
-    let path = found_path.ok_or_else(|| miette::miette!("No NCR found matching '{}'", args.ncr))?;
+    let path = find_ncr_file(&project, &resolved_id)
+        .ok_or_else(|| miette::miette!("No NCR found matching '{}'", args.ncr))?;
 
     // Read and parse NCR
     let content = fs::read_to_string(&path).into_diagnostic()?;
@@ -1163,13 +1302,48 @@ fn run_close(args: CloseArgs, global: &GlobalOpts) -> Result<()> {
         return Err(miette::miette!("NCR {} is already closed", display_id));
     }
 
-    // Convert CLI disposition to entity enum
-    let disposition_decision = match args.disposition {
-        CliDisposition::UseAsIs => DispositionDecision::UseAsIs,
-        CliDisposition::Rework => DispositionDecision::Rework,
-        CliDisposition::Scrap => DispositionDecision::Scrap,
-        CliDisposition::Return => DispositionDecision::ReturnToSupplier,
-    };
+    // Convert CLI disposition to entity enum, keeping MRB sign-offs already
+    // recorded against the same decision
+    let disposition_decision = args.disposition.decision();
+    let deviation = resolve_deviation(
+        &project,
+        &short_ids,
+        &ncr,
+        disposition_decision,
+        args.deviation.as_deref(),
+    )?;
+    let today = chrono::Local::now().date_naive();
+    let mut disposition = propose_disposition(
+        ncr.disposition.take(),
+        disposition_decision,
+        args.rationale.clone(),
+        deviation,
+    );
+    disposition.decision_date = Some(today);
+    disposition.decision_by = Some(config.author().to_string());
+
+    if mrb::missing_justification(&disposition) {
+        return Err(miette::miette!(
+            "Use-as-is requires a deviation or engineering justification.\n\
+             Link a deviation with --deviation DEV@N or give the justification with --rationale."
+        ));
+    }
+
+    // Enforce MRB sign-off only when MRB rules are configured
+    if let Some(mrb_config) = config.mrb.as_ref().filter(|m| m.is_required(&disposition)) {
+        disposition.mrb_required = true;
+        let missing = mrb_config.missing_signoffs(&disposition);
+        if !missing.is_empty() {
+            return Err(miette::miette!(
+                "Disposition '{}' requires MRB sign-off before closing.\n\
+                 Missing sign-off: {}\n\n\
+                 Record sign-offs with: tdt ncr mrb-approve {}",
+                disposition_decision,
+                missing.join(", "),
+                display_id
+            ));
+        }
+    }
 
     // Resolve CAPA link if provided
     let capa_ref = args
@@ -1211,14 +1385,7 @@ fn run_close(args: CloseArgs, global: &GlobalOpts) -> Result<()> {
     }
 
     // Update disposition
-    let today = chrono::Local::now().date_naive();
-    ncr.disposition = Some(Disposition {
-        decision: Some(disposition_decision),
-        decision_date: Some(today),
-        decision_by: Some(config.author().to_string()),
-        justification: args.rationale.clone(),
-        mrb_required: false,
-    });
+    ncr.disposition = Some(disposition);
 
     // Update status
     ncr.ncr_status = NcrStatus::Closed;
@@ -1282,3 +1449,694 @@ fn run_close(args: CloseArgs, global: &GlobalOpts) -> Result<()> {
 
     Ok(())
 }
+
+/// Find an NCR file by full or partial ID
+fn find_ncr_file(project: &Project, id: &str) -> Option<PathBuf> {
+    let ncr_dir = project.root().join("manufacturing/ncrs");
+    fs::read_dir(&ncr_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|path| {
+            path.extension().is_some_and(|e| e == "yaml")
+                && path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|name| name.contains(id))
+        })
+}
+
+/// Resolve the deviation authorizing a disposition
+///
+/// An explicit `--deviation` must name an existing DEV. Without one, a
+/// use-as-is disposition picks up a deviation that links back to the NCR.
+fn resolve_deviation(
+    project: &Project,
+    short_ids: &ShortIdIndex,
+    ncr: &Ncr,
+    decision: DispositionDecision,
+    deviation: Option<&str>,
+) -> Result<Option<EntityId>> {
+    let dev_dir = project.root().join("manufacturing/deviations");
+
+    if let Some(dev_ref) = deviation {
+        let resolved = short_ids
+            .resolve(dev_ref)
+            .unwrap_or_else(|| dev_ref.to_string());
+        let dev_id: EntityId = resolved
+            .parse()
+            .map_err(|_| miette::miette!("Invalid deviation ID: {}", dev_ref))?;
+        if dev_id.prefix() != EntityPrefix::Dev {
+            return Err(miette::miette!("{} is not a deviation (DEV)", dev_ref));
+        }
+        if loader::load_entity::<Dev>(&dev_dir, &resolved)?.is_none() {
+            return Err(miette::miette!("No deviation found matching '{}'", dev_ref));
+        }
+        return Ok(Some(dev_id));
+    }
+
+    if decision != DispositionDecision::UseAsIs {
+        return Ok(None);
+    }
+    let ncr_id = ncr.id.to_string();
+    let devs: Vec<Dev> = loader::load_all(&dev_dir)?;
+    Ok(devs
+        .into_iter()
+        .find(|d| d.links.ncrs.contains(&ncr_id))
+        .map(|d| d.id))
+}
+
+/// Build the proposed disposition, keeping the existing deviation, rationale
+/// and MRB sign-offs when the decision is unchanged
+fn propose_disposition(
+    existing: Option<Disposition>,
+    decision: DispositionDecision,
+    rationale: Option<String>,
+    deviation: Option<EntityId>,
+) -> Disposition {
+    let mut disposition = match existing {
+        Some(d) if d.decision == Some(decision) => d,
+        _ => Disposition {
+            decision: Some(decision),
+            ..Default::default()
+        },
+    };
+    if rationale.is_some() {
+        disposition.justification = rationale;
+    }
+    if deviation.is_some() {
+        disposition.deviation = deviation;
+    }
+    disposition
+}
+
+/// Propose a disposition for MRB review
+fn run_disposition(args: DispositionArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.ncr)
+        .unwrap_or_else(|| args.ncr.clone());
+
+    let path = find_ncr_file(&project, &resolved_id)
+        .ok_or_else(|| miette::miette!("No NCR found matching '{}'", args.ncr))?;
+    let content = fs::read_to_string(&path).into_diagnostic()?;
+    let mut ncr: Ncr = serde_yml::from_str(&content).into_diagnostic()?;
+    let display_id = short_ids
+        .get_short_id(&ncr.id.to_string())
+        .unwrap_or_else(|| format_short_id(&ncr.id));
+
+    if ncr.ncr_status == NcrStatus::Closed {
+        return Err(miette::miette!("NCR {} is already closed", display_id));
+    }
+
+    let decision = args.disposition.decision();
+    let deviation = resolve_deviation(
+        &project,
+        &short_ids,
+        &ncr,
+        decision,
+        args.deviation.as_deref(),
+    )?;
+    let previous = ncr.disposition.as_ref().and_then(|d| d.decision);
+    let mut disposition = propose_disposition(
+        ncr.disposition.take(),
+        decision,
+        args.rationale.clone(),
+        deviation,
+    );
+    disposition.decision_date = Some(chrono::Local::now().date_naive());
+    disposition.decision_by = Some(config.author().to_string());
+
+    if mrb::missing_justification(&disposition) {
+        return Err(miette::miette!(
+            "Use-as-is requires a deviation or engineering justification.\n\
+             Link a deviation with --deviation DEV@N or give the justification with --rationale."
+        ));
+    }
+
+    // MRB rules apply only when configured under `mrb`
+    let (required, missing) = match config.mrb {
+        Some(ref mrb_config) => {
+            disposition.mrb_required = args.mrb || mrb_config.is_required(&disposition);
+            (
+                mrb_config.required_signoffs(&disposition),
+                mrb_config.missing_signoffs(&disposition),
+            )
+        }
+        None => {
+            if args.mrb {
+                eprintln!(
+                    "{} Warning: MRB sign-off is not enforced until an 'mrb' section is configured",
+                    style("!").yellow()
+                );
+            }
+            disposition.mrb_required = args.mrb;
+            (Vec::new(), Vec::new())
+        }
+    };
+    let cleared = previous.is_some_and(|p| p != decision);
+
+    ncr.disposition = Some(disposition);
+    ncr.ncr_status = NcrStatus::Disposition;
+    ncr.entity_revision += 1;
+    let yaml_content = serde_yml::to_string(&ncr).into_diagnostic()?;
+    fs::write(&path, &yaml_content).into_diagnostic()?;
+
+    match global.output {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let result = serde_json::json!({
+                "id": ncr.id.to_string(),
+                "short_id": display_id,
+                "disposition": decision.to_string(),
+                "mrb_required": !required.is_empty(),
+                "required_signoffs": required,
+                "missing_signoffs": missing,
+            });
+            if global.output == OutputFormat::Json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+            } else {
+                print!("{}", serde_yml::to_string(&result).unwrap_or_default());
+            }
+        }
+        _ => {
+            println!(
+                "{} Proposed disposition {} for NCR {}",
+                style("✓").green(),
+                style(decision).yellow(),
+                style(&display_id).cyan()
+            );
+            if cleared {
+                println!("  Decision changed - previous MRB sign-offs cleared");
+            }
+            if missing.is_empty() {
+                println!("  Ready to close: tdt ncr close {} -d ...", display_id);
+            } else {
+                println!("  MRB sign-off required: {}", missing.join(", "));
+                println!("  Sign off with: tdt ncr mrb-approve {}", display_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Record an MRB sign-off on an NCR's proposed disposition
+fn run_mrb_approve(args: MrbApproveArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mrb_config: MrbConfig = config.mrb.clone().ok_or_else(|| {
+        miette::miette!(
+            "MRB sign-off is not configured.\n\
+             Add an 'mrb' section to .tdt/config.yaml to enable it."
+        )
+    })?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.ncr)
+        .unwrap_or_else(|| args.ncr.clone());
+
+    let path = find_ncr_file(&project, &resolved_id)
+        .ok_or_else(|| miette::miette!("No NCR found matching '{}'", args.ncr))?;
+    let content = fs::read_to_string(&path).into_diagnostic()?;
+    let mut ncr: Ncr = serde_yml::from_str(&content).into_diagnostic()?;
+    let display_id = short_ids
+        .get_short_id(&ncr.id.to_string())
+        .unwrap_or_else(|| format_short_id(&ncr.id));
+
+    if ncr.ncr_status == NcrStatus::Closed {
+        return Err(miette::miette!("NCR {} is already closed", display_id));
+    }
+    let disposition = ncr
+        .disposition
+        .as_mut()
+        .filter(|d| d.decision.is_some())
+        .ok_or_else(|| {
+            miette::miette!(
+                "NCR {} has no proposed disposition.\n\
+                 Propose one with: tdt ncr disposition {} -d <decision>",
+                display_id,
+                display_id
+            )
+        })?;
+
+    let required = mrb_config.required_signoffs(disposition);
+    if required.is_empty() {
+        return Err(miette::miette!(
+            "Disposition '{}' of NCR {} does not require MRB sign-off",
+            disposition.decision.unwrap_or_default(),
+            display_id
+        ));
+    }
+    let missing = mrb_config.missing_signoffs(disposition);
+
+    // Work out who is signing, and for which role
+    let (approver, email, role) = if let Some(ref customer) = args.customer {
+        if !required.iter().any(|r| r == CUSTOMER_ROLE) {
+            return Err(miette::miette!(
+                "Disposition '{}' does not require customer concurrence",
+                disposition.decision.unwrap_or_default()
+            ));
+        }
+        (customer.clone(), None, CUSTOMER_ROLE.to_string())
+    } else {
+        let roster = TeamRoster::load(&project).ok_or_else(|| {
+            miette::miette!(
+                "MRB sign-off requires a team roster.\n\
+                 Create one with: tdt team init"
+            )
+        })?;
+        let member = roster.current_user().ok_or_else(|| {
+            miette::miette!("You are not a member of the team roster (.tdt/team.yaml)")
+        })?;
+        let holds =
+            |role: &str| member.is_admin() || member.roles.iter().any(|r| r.to_string() == role);
+
+        let role = match args.role {
+            Some(role) => {
+                let role = role.to_string();
+                if !required.contains(&role) {
+                    return Err(miette::miette!(
+                        "Role '{}' is not an MRB role for this disposition (required: {})",
+                        role,
+                        required.join(", ")
+                    ));
+                }
+                if !holds(&role) {
+                    return Err(miette::miette!(
+                        "{} does not hold the '{}' role",
+                        member.name,
+                        role
+                    ));
+                }
+                role
+            }
+            None => missing
+                .iter()
+                .find(|r| r.as_str() != CUSTOMER_ROLE && holds(r))
+                .cloned()
+                .ok_or_else(|| {
+                    miette::miette!(
+                        "No pending MRB sign-off for your roles (pending: {})",
+                        if missing.is_empty() {
+                            "none".to_string()
+                        } else {
+                            missing.join(", ")
+                        }
+                    )
+                })?,
+        };
+
+        // One person cannot sign for several MRB roles
+        if disposition
+            .approvals
+            .iter()
+            .any(|a| a.approver == member.name && a.role.as_deref() != Some(CUSTOMER_ROLE))
+        {
+            return Err(miette::miette!(
+                "{} has already signed off on this disposition",
+                member.name
+            ));
+        }
+        (member.name.clone(), Some(member.email.clone()), role)
+    };
+
+    if !missing.contains(&role) {
+        return Err(miette::miette!(
+            "The '{}' sign-off has already been recorded",
+            role
+        ));
+    }
+    if mrb_config.require_signature && !args.sign {
+        return Err(miette::miette!(
+            "MRB sign-offs must be signed.\n\
+             Use --sign (-S) to sign the sign-off commit."
+        ));
+    }
+
+    let git = Git::new(project.root());
+    let signing_key = if args.sign {
+        if !git.is_repo() {
+            return Err(miette::miette!("--sign requires a git repository"));
+        }
+        if !git.signing_configured() {
+            return Err(miette::miette!(
+                "Signing requested but not configured.\n\
+                 Configure with: git config --global user.signingkey <KEY_ID>"
+            ));
+        }
+        git.signing_key()
+    } else {
+        None
+    };
+
+    disposition.mrb_required = true;
+    disposition.approvals.push(ApprovalRecord {
+        approver: approver.clone(),
+        email,
+        role: Some(role.clone()),
+        timestamp: chrono::Utc::now(),
+        comment: args.message.clone(),
+        signature_verified: None,
+        signing_key,
+    });
+
+    ncr.entity_revision += 1;
+    let yaml_content = serde_yml::to_string(&ncr).into_diagnostic()?;
+    fs::write(&path, &yaml_content).into_diagnostic()?;
+
+    // Commit the sign-off signed and verify it before recording the result;
+    // a failed or (when required) unverified signature withdraws the sign-off
+    if args.sign {
+        let restore = |reason: &str| -> Result<()> {
+            fs::write(&path, &content).into_diagnostic()?;
+            git.stage_file(&path).into_diagnostic()?;
+            if !reason.is_empty() {
+                git.commit(reason).into_diagnostic()?;
+            }
+            Ok(())
+        };
+        let message = format!("MRB sign-off ({}) for {}", role, display_id);
+        git.stage_file(&path).into_diagnostic()?;
+        let commit = match git.commit_signed(&message) {
+            Ok(commit) => commit,
+            Err(e) => {
+                restore("")?;
+                return Err(miette::miette!("Sign-off not recorded: {}", e));
+            }
+        };
+        let verified = match git.verify_commit_signature(&commit) {
+            Ok(Some(signer)) => {
+                eprintln!("  Signature verified: {}", signer);
+                true
+            }
+            Ok(None) => {
+                eprintln!("  Warning: Commit was not signed");
+                false
+            }
+            Err(e) => {
+                eprintln!("  Warning: Signature verification failed: {}", e);
+                false
+            }
+        };
+        if !verified && mrb_config.require_signature {
+            restore(&format!(
+                "Withdraw unverified MRB sign-off ({}) for {}",
+                role, display_id
+            ))?;
+            return Err(miette::miette!(
+                "MRB sign-offs must carry a verified signature; the '{}' sign-off was withdrawn",
+                role
+            ));
+        }
+        if let Some(record) = ncr
+            .disposition
+            .as_mut()
+            .and_then(|d| d.approvals.last_mut())
+        {
+            record.signature_verified = Some(verified);
+        }
+        let yaml_content = serde_yml::to_string(&ncr).into_diagnostic()?;
+        fs::write(&path, &yaml_content).into_diagnostic()?;
+        git.stage_file(&path).into_diagnostic()?;
+        git.amend_signed().into_diagnostic()?;
+    }
+    let remaining = ncr
+        .disposition
+        .as_ref()
+        .map(|d| mrb_config.missing_signoffs(d))
+        .unwrap_or_default();
+
+    match global.output {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let result = serde_json::json!({
+                "id": ncr.id.to_string(),
+                "short_id": display_id,
+                "role": role,
+                "approver": approver,
+                "signed": args.sign,
+                "missing_signoffs": remaining,
+            });
+            if global.output == OutputFormat::Json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+            } else {
+                print!("{}", serde_yml::to_string(&result).unwrap_or_default());
+            }
+        }
+        _ => {
+            println!(
+                "{} MRB sign-off recorded for NCR {}: {} by {}",
+                style("✓").green(),
+                style(&display_id).cyan(),
+                role,
+                approver
+            );
+            if remaining.is_empty() {
+                println!(
+                    "  MRB sign-off complete - close with: tdt ncr close {} -d ...",
+                    display_id
+                );
+            } else {
+                println!("  Still required: {}", remaining.join(", "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A proposed or created escalation, with display details resolved
+#[derive(serde::Serialize)]
+struct TrendEscalation {
+    #[serde(flatten)]
+    escalation: Escalation,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capa: Option<String>,
+}
+
+/// Detect recurring NCRs and propose or create CAPAs
+fn run_trends(args: TrendsArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let as_of = args
+        .as_of
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+
+    let mut rules = config
+        .ncr_trends
+        .clone()
+        .unwrap_or_default()
+        .effective_rules();
+    if let Some(ref name) = args.rule {
+        rules.retain(|r| &r.name == name);
+        if rules.is_empty() {
+            return Err(miette::miette!("No escalation rule named '{}'", name));
+        }
+    }
+
+    let ncr_dir = project.root().join("manufacturing/ncrs");
+    let capa_dir = project.root().join("manufacturing/capas");
+    let ncrs: Vec<Ncr> = loader::load_all(&ncr_dir)?;
+    let capas: Vec<Capa> = loader::load_all(&capa_dir)?;
+    let components: Vec<Component> = loader::load_all(&project.root().join("bom/components"))?;
+    let processes: Vec<crate::entities::process::Process> =
+        loader::load_all(&project.root().join("manufacturing/processes"))?;
+
+    let mut short_ids = ShortIdIndex::load(&project);
+    let mut results: Vec<TrendEscalation> = ncr_trends::evaluate(&rules, &ncrs, &capas, as_of)
+        .into_iter()
+        .map(|escalation| {
+            let parts: Vec<String> = escalation
+                .key
+                .iter()
+                .map(|kv| match kv.key {
+                    TrendKey::Component => components
+                        .iter()
+                        .find(|c| c.id.to_string() == kv.value)
+                        .map(|c| format!("{} {}", c.part_number, c.title))
+                        .unwrap_or_else(|| kv.value.clone()),
+                    TrendKey::Process => processes
+                        .iter()
+                        .find(|p| p.id.to_string() == kv.value)
+                        .map(|p| p.title.clone())
+                        .unwrap_or_else(|| kv.value.clone()),
+                    _ => kv.value.clone(),
+                })
+                .collect();
+            let subject = if !parts.is_empty() {
+                parts.join(" / ")
+            } else if escalation.ncrs.len() == 1 {
+                ncrs.iter()
+                    .find(|n| n.id.to_string() == escalation.ncrs[0])
+                    .map(|n| n.title.clone())
+                    .unwrap_or_default()
+            } else {
+                format!("{} NCRs", escalation.ncrs.len())
+            };
+            TrendEscalation {
+                title: format!("{}: {}", escalation.rule, subject),
+                escalation,
+                capa: None,
+            }
+        })
+        .collect();
+
+    if args.create && !results.is_empty() {
+        let author = config.author();
+        fs::create_dir_all(&capa_dir).into_diagnostic()?;
+
+        for result in &mut results {
+            let esc = &result.escalation;
+            let mut capa = Capa::new(result.title.clone(), esc.capa_type, author.clone());
+            capa.source = Some(Source {
+                source_type: esc.source_type,
+                reference: Some(esc.source_reference.clone()),
+            });
+
+            let mut statement = format!(
+                "{} NCR(s) matched escalation rule '{}' between {} and {}:",
+                esc.ncrs.len(),
+                esc.rule,
+                esc.first_date,
+                esc.last_date
+            );
+            for id in &esc.ncrs {
+                let title = ncrs
+                    .iter()
+                    .find(|n| n.id.to_string() == *id)
+                    .map(|n| n.title.as_str())
+                    .unwrap_or("");
+                statement.push_str(&format!("\n- {}: {}", id, title));
+            }
+            capa.problem_statement = Some(statement);
+            capa.links.ncrs = esc
+                .ncrs
+                .iter()
+                .filter_map(|id| id.parse::<EntityId>().ok())
+                .collect();
+
+            let capa_path = capa_dir.join(format!("{}.tdt.yaml", capa.id));
+            fs::write(&capa_path, serde_yml::to_string(&capa).into_diagnostic()?)
+                .into_diagnostic()?;
+            short_ids.add(capa.id.to_string());
+
+            // Back-link each NCR to the new CAPA
+            for id in &esc.ncrs {
+                if let Some((path, mut ncr)) = loader::load_entity::<Ncr>(&ncr_dir, id)? {
+                    ncr.links.capa = Some(capa.id.clone());
+                    ncr.entity_revision += 1;
+                    fs::write(&path, serde_yml::to_string(&ncr).into_diagnostic()?)
+                        .into_diagnostic()?;
+                }
+            }
+
+            result.capa = Some(capa.id.to_string());
+        }
+        super::utils::save_short_ids(&mut short_ids, &project);
+    }
+
+    let short = |id: &str| short_ids.get_short_id(id).unwrap_or_else(|| id.to_string());
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&results).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&results).into_diagnostic()?);
+        }
+        OutputFormat::Csv => {
+            println!("rule,title,ncr_count,first_date,last_date,highest_severity,ncrs,capa");
+            for r in &results {
+                let e = &r.escalation;
+                println!(
+                    "{},{},{},{},{},{},{},{}",
+                    escape_csv(&e.rule),
+                    escape_csv(&r.title),
+                    e.ncrs.len(),
+                    e.first_date,
+                    e.last_date,
+                    e.highest_severity,
+                    e.ncrs.join(";"),
+                    r.capa.as_deref().unwrap_or("")
+                );
+            }
+        }
+        OutputFormat::Id | OutputFormat::ShortId => {
+            for r in &results {
+                let id = r.capa.as_ref().unwrap_or(&r.escalation.ncrs[0]);
+                match global.output {
+                    OutputFormat::ShortId => println!("{}", short(id)),
+                    _ => println!("{}", id),
+                }
+            }
+        }
+        _ => {
+            if results.is_empty() {
+                println!(
+                    "{} No NCR trends require escalation ({} rule(s) evaluated as of {})",
+                    style("✓").green(),
+                    rules.len(),
+                    as_of
+                );
+                return Ok(());
+            }
+
+            for r in &results {
+                let e = &r.escalation;
+                println!();
+                println!("{} {}", style("▲").yellow(), style(&r.title).bold());
+                println!(
+                    "  {} {} NCR(s), {} to {}, highest severity {}",
+                    style("Rule:").dim(),
+                    e.ncrs.len(),
+                    e.first_date,
+                    e.last_date,
+                    e.highest_severity
+                );
+                for id in &e.ncrs {
+                    let title = ncrs
+                        .iter()
+                        .find(|n| n.id.to_string() == *id)
+                        .map(|n| n.title.as_str())
+                        .unwrap_or("");
+                    println!("    {} {}", style(short(id)).cyan(), title);
+                }
+                match r.capa {
+                    Some(ref capa) => println!(
+                        "  {} Created {} CAPA {}",
+                        style("✓").green(),
+                        e.capa_type,
+                        style(short(capa)).cyan()
+                    ),
+                    None => println!(
+                        "  {} Proposed {} CAPA (source: {} {})",
+                        style("→").dim(),
+                        e.capa_type,
+                        e.source_type,
+                        e.source_reference
+                    ),
+                }
+            }
+            println!();
+            if !args.create {
+                println!(
+                    "Run {} to create these CAPAs",
+                    style("tdt ncr trends --create").yellow()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,418 @@
+//! `tdt plan` command - Material requirements planning from planned lots
+//!
+//! Explodes open lot demand through the multi-level BOM, nets it against an
+//! on-hand inventory file and prints a time-phased purchase plan grouped by
+//! supplier.
+
+use chrono::NaiveDate;
+use console::style;
+use miette::{IntoDiagnostic, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::cli::commands::report::{
+    load_all_assemblies, load_all_components, load_all_quotes, write_output,
+};
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::currency::Converter;
+use crate::core::loader;
+use crate::core::mrp::{self, MrpPlan, PlanWarning, PlannedOrder, Stock};
+use crate::core::project::Project;
+use crate::core::shortid::ShortIdIndex;
+use crate::core::Config;
+use crate::entities::assembly::Assembly;
+use crate::entities::component::Component;
+use crate::entities::lot::Lot;
+use crate::entities::quote::{Currency, Quote};
+use crate::entities::supplier::Supplier;
+
+#[derive(clap::Args, Debug)]
+pub struct PlanArgs {
+    /// On-hand inventory CSV (component or part_number, on_hand, optional safety_stock)
+    #[arg(long, short = 'i')]
+    pub inventory: Option<PathBuf>,
+
+    /// Plan only these lots (default: all planned, in-progress and on-hold lots)
+    #[arg(long = "lot", short = 'l')]
+    pub lots: Vec<String>,
+
+    /// Report in this currency instead of the configured reporting currency
+    #[arg(long)]
+    pub currency: Option<Currency>,
+
+    /// Planning date (default: today); orders due before it are late
+    #[arg(long)]
+    pub as_of: Option<NaiveDate>,
+
+    /// Output to file instead of stdout
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
+pub fn run(args: PlanArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+
+    let mut lots: Vec<Lot> = loader::load_all(&project.root().join("manufacturing/lots"))?;
+    if !args.lots.is_empty() {
+        let wanted: Vec<String> = args
+            .lots
+            .iter()
+            .map(|l| short_ids.resolve(l).unwrap_or_else(|| l.clone()))
+            .collect();
+        for id in &wanted {
+            if !lots.iter().any(|l| l.id.to_string() == *id) {
+                return Err(miette::miette!("No lot found matching '{}'", id));
+            }
+        }
+        lots.retain(|l| wanted.contains(&l.id.to_string()));
+    }
+
+    let components = load_all_components(&project);
+    let assemblies = load_all_assemblies(&project);
+    let quotes = load_all_quotes(&project);
+    let suppliers: Vec<Supplier> = loader::load_all(&project.root().join("bom/suppliers"))?;
+
+    let component_map: HashMap<String, &Component> =
+        components.iter().map(|c| (c.id.to_string(), c)).collect();
+    let assembly_map: HashMap<String, &Assembly> =
+        assemblies.iter().map(|a| (a.id.to_string(), a)).collect();
+    let quote_map: HashMap<String, &Quote> = quotes.iter().map(|q| (q.id.to_string(), q)).collect();
+
+    let stock: HashMap<String, Stock> = match args.inventory {
+        Some(ref path) => {
+            let file = File::open(path)
+                .map_err(|e| miette::miette!("Cannot open {}: {}", path.display(), e))?;
+            let resolve = |part: &str| {
+                let id = short_ids.resolve(part).unwrap_or_else(|| part.to_string());
+                if component_map.contains_key(&id) {
+                    return Some(id);
+                }
+                components
+                    .iter()
+                    .find(|c| c.part_number.eq_ignore_ascii_case(part))
+                    .map(|c| c.id.to_string())
+            };
+            let (stock, unknown) =
+                mrp::parse_inventory(file, resolve).map_err(|e| miette::miette!("{}", e))?;
+            for part in unknown {
+                eprintln!(
+                    "{} Inventory part '{}' matches no component, ignored",
+                    style("!").yellow(),
+                    part
+                );
+            }
+            stock
+        }
+        None => HashMap::new(),
+    };
+
+    let currency_config = Config::load().currency.unwrap_or_default();
+    let as_of = args
+        .as_of
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+    let mut converter = Converter::new(currency_config, args.currency, as_of);
+
+    let plan = mrp::plan(
+        &lots,
+        &assembly_map,
+        &component_map,
+        &quote_map,
+        &stock,
+        &mut converter,
+    );
+
+    let supplier_names: HashMap<String, String> = suppliers
+        .iter()
+        .map(|s| {
+            (
+                s.id.to_string(),
+                s.short_name.clone().unwrap_or_else(|| s.name.clone()),
+            )
+        })
+        .collect();
+    let names = Names {
+        short_ids: &short_ids,
+        suppliers: &supplier_names,
+    };
+
+    let output = match global.output {
+        OutputFormat::Json => serde_json::to_string_pretty(&plan).into_diagnostic()? + "\n",
+        OutputFormat::Yaml => serde_yml::to_string(&plan).into_diagnostic()?,
+        OutputFormat::Csv => render_csv(&plan, &names),
+        _ => render_markdown(&plan, &converter, args.inventory.is_some(), &names),
+    };
+
+    write_output(&output, args.file)?;
+    Ok(())
+}
+
+/// Display names for IDs in the plan
+struct Names<'a> {
+    short_ids: &'a ShortIdIndex,
+    suppliers: &'a HashMap<String, String>,
+}
+
+impl Names<'_> {
+    fn short(&self, id: &str) -> String {
+        self.short_ids
+            .get_short_id(id)
+            .unwrap_or_else(|| truncate_str(id, 12))
+    }
+
+    fn supplier(&self, order: &PlannedOrder) -> String {
+        match order.supplier {
+            Some(ref id) => match self.suppliers.get(id) {
+                Some(name) => format!("{} ({})", name, self.short(id)),
+                None => id.clone(),
+            },
+            None => "Unassigned".to_string(),
+        }
+    }
+
+    fn warning(&self, warning: &PlanWarning) -> String {
+        match warning {
+            PlanWarning::NoProduct { lot } => {
+                format!("{}: no product, lot skipped", self.short(lot))
+            }
+            PlanWarning::NoQuantity { lot } => {
+                format!("{}: no quantity, lot skipped", self.short(lot))
+            }
+            PlanWarning::UnknownPart { lot, part } => format!(
+                "{}: unknown part {}, not planned",
+                self.short(lot),
+                self.short(part)
+            ),
+            PlanWarning::NoLeadTime { component } => format!(
+                "{}: no lead time, ordered on the need date",
+                self.short(component)
+            ),
+            PlanWarning::NoPrice { component } => format!("{}: no price", self.short(component)),
+            PlanWarning::NoSupplier { component } => {
+                format!("{}: no supplier", self.short(component))
+            }
+            PlanWarning::QuoteExpired {
+                component,
+                quote,
+                valid_until,
+            } => format!(
+                "{}: selected quote {} expired {}",
+                self.short(component),
+                self.short(quote),
+                valid_until
+            ),
+        }
+    }
+}
+
+fn money(amount: Option<f64>, sym: &str) -> String {
+    amount
+        .map(|a| format!("{}{:.2}", sym, a))
+        .unwrap_or("-".to_string())
+}
+
+fn render_markdown(
+    plan: &MrpPlan,
+    converter: &Converter,
+    has_inventory: bool,
+    names: &Names,
+) -> String {
+    let sym = converter.target.symbol();
+    let mut output = String::from("# Material Plan\n\n");
+    output.push_str(&format!("As of: {}\n", plan.as_of));
+    output.push_str(&format!("Currency: {}\n", converter.target));
+    if !has_inventory {
+        output.push_str("Inventory: none given, all demand treated as short\n");
+    }
+    output.push('\n');
+
+    if plan.lots.is_empty() {
+        output.push_str("*No open lots with a product and quantity to plan*\n");
+        return output;
+    }
+
+    output.push_str("## Demand\n\n");
+    let mut table = Builder::default();
+    table.push_record(["Lot", "Title", "Status", "Product", "Qty", "Need Date"]);
+    for lot in &plan.lots {
+        table.push_record([
+            names.short(&lot.lot),
+            truncate_str(&lot.title, 30).to_string(),
+            lot.status.to_string(),
+            names.short(&lot.product),
+            lot.quantity.to_string(),
+            lot.need_date.to_string(),
+        ]);
+    }
+    output.push_str(&table.build().with(Style::markdown()).to_string());
+    output.push_str("\n\n## Purchase Orders\n\n");
+
+    if plan.orders.is_empty() {
+        output.push_str("*Nothing to buy - stock covers all demand*\n");
+    }
+    let mut by_supplier: BTreeMap<(bool, String), Vec<&PlannedOrder>> = BTreeMap::new();
+    for order in &plan.orders {
+        by_supplier
+            .entry((order.supplier.is_none(), names.supplier(order)))
+            .or_default()
+            .push(order);
+    }
+    for ((_, supplier), orders) in &by_supplier {
+        output.push_str(&format!("### {}\n\n", supplier));
+        let mut table = Builder::default();
+        table.push_record([
+            "Order By",
+            "Need By",
+            "Component",
+            "Part Number",
+            "Lead (d)",
+            "Net Qty",
+            "Order Qty",
+            "Unit Price",
+            "Extended",
+            "Lot",
+        ]);
+        for order in orders {
+            let order_by = if order.late {
+                format!("{} (LATE)", order.order_date)
+            } else {
+                order.order_date.to_string()
+            };
+            table.push_record([
+                order_by,
+                order.need_date.to_string(),
+                names.short(&order.component),
+                order.part_number.clone(),
+                order
+                    .lead_time_days
+                    .map(|d| d.to_string())
+                    .unwrap_or("-".to_string()),
+                order.net_quantity.to_string(),
+                order.quantity.to_string(),
+                money(order.unit_price, sym),
+                money(order.extended_price, sym),
+                names.short(&order.lot),
+            ]);
+        }
+        output.push_str(&table.build().with(Style::markdown()).to_string());
+        let subtotal = orders
+            .iter()
+            .filter_map(|o| o.extended_price)
+            .fold(0.0, |sum, p| sum + p);
+        output.push_str(&format!("\n\nSubtotal: {}{:.2}\n\n", sym, subtotal));
+    }
+
+    let builds: Vec<_> = plan.builds().collect();
+    if !builds.is_empty() {
+        output.push_str("## Builds (make parts)\n\n");
+        let mut table = Builder::default();
+        table.push_record([
+            "Component",
+            "Part Number",
+            "Title",
+            "Need By",
+            "Gross",
+            "On Hand",
+            "Build Qty",
+        ]);
+        for part in builds {
+            table.push_record([
+                names.short(&part.component),
+                part.part_number.clone(),
+                truncate_str(&part.title, 30).to_string(),
+                part.first_need.to_string(),
+                part.gross.to_string(),
+                part.on_hand.to_string(),
+                part.planned.to_string(),
+            ]);
+        }
+        output.push_str(&table.build().with(Style::markdown()).to_string());
+        output.push_str("\n\n");
+    }
+
+    output.push_str("## Net Requirements\n\n");
+    let mut table = Builder::default();
+    table.push_record([
+        "Component",
+        "Part Number",
+        "Make/Buy",
+        "Gross",
+        "On Hand",
+        "Safety",
+        "Net",
+        "Planned",
+        "Projected",
+    ]);
+    for part in &plan.parts {
+        table.push_record([
+            names.short(&part.component),
+            part.part_number.clone(),
+            part.make_buy.to_string(),
+            part.gross.to_string(),
+            part.on_hand.to_string(),
+            part.safety_stock.to_string(),
+            part.net.to_string(),
+            part.planned.to_string(),
+            part.projected_on_hand.to_string(),
+        ]);
+    }
+    output.push_str(&table.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    if !plan.warnings.is_empty() || !converter.issues().is_empty() {
+        output.push_str("\n## Warnings\n\n");
+        for warning in &plan.warnings {
+            output.push_str(&format!("- {}\n", names.warning(warning)));
+        }
+        for issue in converter.issues() {
+            output.push_str(&format!("- exchange rate: {}\n", issue));
+        }
+    }
+
+    output.push_str(&format!(
+        "\n*{} lot(s), {} purchase order(s) totalling {}{:.2}, {} late*\n",
+        plan.lots.len(),
+        plan.orders.len(),
+        sym,
+        plan.total_value(),
+        plan.late_orders()
+    ));
+    output
+}
+
+fn render_csv(plan: &MrpPlan, names: &Names) -> String {
+    let mut output = String::from(
+        "supplier,order_date,need_date,component,part_number,title,lead_time_days,net_quantity,quantity,unit_price,extended_price,lot,late\n",
+    );
+    for order in &plan.orders {
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            escape_csv(&names.supplier(order)),
+            order.order_date,
+            order.need_date,
+            names.short(&order.component),
+            escape_csv(&order.part_number),
+            escape_csv(&order.title),
+            order
+                .lead_time_days
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            order.net_quantity,
+            order.quantity,
+            order
+                .unit_price
+                .map(|p| format!("{:.4}", p))
+                .unwrap_or_default(),
+            order
+                .extended_price
+                .map(|p| format!("{:.2}", p))
+                .unwrap_or_default(),
+            names.short(&order.lot),
+            order.late,
+        ));
+    }
+    output
+}
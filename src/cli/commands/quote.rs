@@ -3,7 +3,9 @@
 use clap::{Subcommand, ValueEnum};
 use console::style;
 use miette::{IntoDiagnostic, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::cli::commands::utils::format_link_with_title;
 use crate::cli::filters::StatusFilter;
@@ -11,12 +13,18 @@ use crate::cli::helpers::{format_short_id, truncate_str};
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::EntityCache;
+use crate::core::currency::Converter;
 use crate::core::identity::{EntityId, EntityPrefix};
 use crate::core::project::Project;
+use crate::core::rfq::{self, RfqDocument, RfqItem, RfqManifest};
 use crate::core::shortid::ShortIdIndex;
+use crate::core::sourcing::{self, SourcingMatrix, SourcingOption, SourcingParams};
 use crate::core::CachedQuote;
 use crate::core::Config;
-use crate::entities::quote::{Quote, QuoteStatus};
+use crate::entities::assembly::Assembly;
+use crate::entities::component::{Component, MakeBuy, QuoteSelection};
+use crate::entities::quote::{Currency, Quote, QuoteStatus};
+use crate::entities::supplier::Supplier;
 use crate::schema::wizard::SchemaWizard;
 
 #[derive(Subcommand, Debug)]
@@ -44,6 +52,12 @@ pub enum QuoteCommands {
 
     /// Get price for a specific quantity (shows price break and total cost)
     Price(PriceArgs),
+
+    /// Create request-for-quote packages (spreadsheet, manifest, documents) per supplier
+    Rfq(RfqArgs),
+
+    /// Import a supplier-filled RFQ spreadsheet as quotes
+    RfqImport(RfqImportArgs),
 }
 
 /// Quote status filter
@@ -289,6 +303,18 @@ pub struct CompareArgs {
     /// Exclude NRE/tooling costs from comparison
     #[arg(long)]
     pub no_nre: bool,
+
+    /// Compare in this currency instead of the configured reporting currency
+    #[arg(long)]
+    pub currency: Option<Currency>,
+
+    /// Set the component's selected quote: the best-ranked one, or the quote given
+    #[arg(long, value_name = "QUOTE", num_args = 0..=1)]
+    pub select: Option<Option<String>>,
+
+    /// Note recorded with the selection, ahead of the generated rationale
+    #[arg(long, requires = "select")]
+    pub rationale: Option<String>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -310,6 +336,46 @@ pub struct PriceArgs {
     pub all: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct RfqArgs {
+    /// Components to request quotes for (CMP@N or full ID)
+    pub components: Vec<String>,
+
+    /// Also request every buy part in this assembly's BOM
+    #[arg(long, short = 'a')]
+    pub assembly: Option<String>,
+
+    /// Supplier to send the request to (repeatable; default: each component's listed suppliers)
+    #[arg(long, short = 's')]
+    pub supplier: Vec<String>,
+
+    /// Quantities to quote (e.g., --qty 100,500,1000); per assembly with --assembly
+    #[arg(long, short = 'Q', value_delimiter = ',', default_value = "1")]
+    pub qty: Vec<u32>,
+
+    /// Date responses are due (YYYY-MM-DD)
+    #[arg(long)]
+    pub due: Option<chrono::NaiveDate>,
+
+    /// Currency to request prices in (default: reporting currency)
+    #[arg(long)]
+    pub currency: Option<Currency>,
+
+    /// Directory to create the packages in (default: current directory)
+    #[arg(long, short = 'd')]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RfqImportArgs {
+    /// RFQ package folder, or the filled-in spreadsheet (manifest.json must sit beside it)
+    pub path: PathBuf,
+
+    /// Show what would be imported without creating quotes
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 /// Parse a price break triplet (QTY:PRICE:LEAD_TIME)
 /// Returns (min_qty, unit_price, lead_time_days)
 fn parse_price_break(input: &str) -> Result<(u32, f64, Option<u32>)> {
@@ -354,6 +420,8 @@ pub fn run(cmd: QuoteCommands, global: &GlobalOpts) -> Result<()> {
         QuoteCommands::Archive(args) => run_archive(args),
         QuoteCommands::Compare(args) => run_compare(args, global),
         QuoteCommands::Price(args) => run_price(args, global),
+        QuoteCommands::Rfq(args) => run_rfq(args, global),
+        QuoteCommands::RfqImport(args) => run_rfq_import(args, global),
     }
 }
 
@@ -899,6 +967,10 @@ fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
         }
     }
 
+    if let Some(warning) = super::sup::approval_warning(&project, &supplier) {
+        eprintln!("{} Warning: {}", style("!").yellow(), warning);
+    }
+
     // Generate ID
     let id = EntityId::new(EntityPrefix::Quot);
 
@@ -1210,25 +1282,6 @@ fn run_archive(args: ArchiveArgs) -> Result<()> {
     crate::cli::commands::utils::run_delete(&args.id, QUOTE_DIRS, args.force, true, args.quiet)
 }
 
-/// Calculate effective unit price including amortized NRE/tooling costs
-fn effective_unit_price(quote: &Quote, qty: u32, amortize: Option<u32>, include_nre: bool) -> f64 {
-    let base_price = quote.price_for_qty(qty).unwrap_or(0.0);
-
-    if !include_nre {
-        return base_price;
-    }
-
-    // Calculate NRE per unit
-    let nre_per_unit = if let Some(amort_qty) = amortize {
-        let total_nre = quote.total_nre();
-        total_nre / amort_qty as f64
-    } else {
-        0.0
-    };
-
-    base_price + nre_per_unit
-}
-
 fn run_compare(args: CompareArgs, global: &GlobalOpts) -> Result<()> {
     let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
     let quote_dir = project.root().join("bom/quotes");
@@ -1272,19 +1325,47 @@ fn run_compare(args: CompareArgs, global: &GlobalOpts) -> Result<()> {
         return Ok(());
     }
 
-    // Sort by effective unit price at the specified quantity (lowest first)
-    quotes.sort_by(|a, b| {
-        let price_a = effective_unit_price(a, qty, args.amortize, include_nre);
-        let price_b = effective_unit_price(b, qty, args.amortize, include_nre);
-        price_a
-            .partial_cmp(&price_b)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Decision matrix in the reporting currency
+    let mut converter = Converter::from_config(args.currency);
+    let config = Config::load();
+    let author = config.author();
+    let sourcing_config = config.sourcing.unwrap_or_default();
+    let weights = &sourcing_config.weights;
+    let supplier_ids: HashSet<&str> = quotes.iter().map(|q| q.supplier.as_str()).collect();
+    let suppliers: Vec<crate::entities::supplier::Supplier> =
+        crate::core::loader::load_all(&project.root().join("bom/suppliers"))?
+            .into_iter()
+            .filter(|s: &crate::entities::supplier::Supplier| {
+                supplier_ids.contains(s.id.to_string().as_str())
+            })
+            .collect();
+    let supplier_scores: HashMap<String, f64> = super::sup::score_suppliers(
+        &project,
+        &suppliers,
+        &config.supplier_scorecard.unwrap_or_default(),
+    )?
+    .into_iter()
+    .map(|card| (card.supplier, card.score))
+    .collect();
+    let params = SourcingParams {
+        qty,
+        volume: args.amortize,
+        include_nre,
+        as_of: chrono::Local::now().date_naive(),
+    };
+    let matrix = sourcing::evaluate(
+        &quotes,
+        &supplier_scores,
+        &mut converter,
+        &params,
+        &sourcing_config,
+    );
 
     // Update short ID index
     let mut short_ids = ShortIdIndex::load(&project);
     short_ids.ensure_all(quotes.iter().map(|q| q.id.to_string()));
     super::utils::save_short_ids(&mut short_ids, &project);
+    let short = |id: &str| short_ids.get_short_id(id).unwrap_or_else(|| id.to_string());
 
     // Output comparison
     let format = match global.output {
@@ -1294,148 +1375,245 @@ fn run_compare(args: CompareArgs, global: &GlobalOpts) -> Result<()> {
 
     match format {
         OutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&quotes).into_diagnostic()?;
+            let json = serde_json::to_string_pretty(&matrix).into_diagnostic()?;
             println!("{}", json);
         }
         OutputFormat::Yaml => {
-            let yaml = serde_yml::to_string(&quotes).into_diagnostic()?;
+            let yaml = serde_yml::to_string(&matrix).into_diagnostic()?;
             print!("{}", yaml);
         }
-        OutputFormat::Tsv => {
+        _ => {
             println!(
-                "Comparing {} quotes for {} at qty {}",
+                "Comparing {} quotes for {} at qty {} ({})",
                 style(quotes.len()).cyan(),
                 style(&args.item).yellow(),
-                style(qty).white()
+                style(qty).white(),
+                converter.target
             );
-            if let Some(amort) = args.amortize {
+            if let Some(amort) = args.amortize.filter(|_| include_nre) {
                 println!(
                     "   {} NRE/tooling amortized over {} units",
                     style("→").dim(),
                     style(amort).cyan()
                 );
             }
+            println!(
+                "   {} weights: cost {:.0}%, lead time {:.0}%, supplier {:.0}%",
+                style("→").dim(),
+                weights.cost * 100.0,
+                weights.lead_time * 100.0,
+                weights.supplier * 100.0
+            );
             println!();
 
-            // Adjust header based on whether we're showing NRE
-            let show_nre_col = include_nre && args.amortize.is_some();
-            if show_nre_col {
-                println!(
-                    "{:<8} {:<18} {:<12} {:<10} {:<10} {:<8} {:<8} {:<10} {:<10}",
-                    style("SHORT").bold().dim(),
-                    style("TITLE").bold(),
-                    style("SUPPLIER").bold(),
-                    style("UNIT").bold(),
-                    style("EFF.UNIT").bold(),
-                    style("MOQ").bold(),
-                    style("LEAD").bold(),
-                    style("TOOLING").bold(),
-                    style("STATUS").bold()
-                );
-            } else {
-                println!(
-                    "{:<8} {:<20} {:<15} {:<10} {:<8} {:<10} {:<10} {:<10}",
-                    style("SHORT").bold().dim(),
-                    style("TITLE").bold(),
-                    style("SUPPLIER").bold(),
-                    style("PRICE").bold(),
-                    style("MOQ").bold(),
-                    style("LEAD").bold(),
-                    style("TOOLING").bold(),
-                    style("STATUS").bold()
-                );
-            }
-            println!("{}", "-".repeat(if show_nre_col { 110 } else { 100 }));
+            println!(
+                "{:<4} {:<8} {:<18} {:<10} {:<8} {:<10} {:<9} {:<9} {:<10} {:<6} {:<5} {:<5}",
+                style("#").bold(),
+                style("SHORT").bold().dim(),
+                style("TITLE").bold(),
+                style("SUPPLIER").bold(),
+                style("BUY").bold(),
+                style("UNIT").bold(),
+                style("MOQ+").bold(),
+                style("NRE/U").bold(),
+                style("EFF.UNIT").bold(),
+                style("LEAD").bold(),
+                style("SUP").bold(),
+                style("SCORE").bold()
+            );
+            println!("{}", "-".repeat(108));
 
-            for (i, quote) in quotes.iter().enumerate() {
-                let short_id = short_ids
-                    .get_short_id(&quote.id.to_string())
-                    .unwrap_or_default();
-                let title_truncated =
-                    truncate_str(&quote.title, if show_nre_col { 16 } else { 18 });
-                let supplier_short = short_ids.get_short_id(&quote.supplier).unwrap_or_else(|| {
-                    truncate_str(&quote.supplier, if show_nre_col { 10 } else { 13 }).to_string()
-                });
-                let base_price = quote.price_for_qty(qty);
-                let unit_price_str = base_price.map_or("-".to_string(), |p| format!("{:.2}", p));
-                let eff_price = effective_unit_price(quote, qty, args.amortize, include_nre);
-                let eff_price_str = format!("{:.2}", eff_price);
-                let moq = quote.moq.map_or("-".to_string(), |m| m.to_string());
-                let lead_time = quote
-                    .lead_time_for_qty(qty)
-                    .map_or("-".to_string(), |d| format!("{}d", d));
-                let tooling = quote
-                    .tooling_cost
-                    .map_or("-".to_string(), |t| format!("{:.0}", t));
-
-                let price_style = if i == 0 {
-                    style(if show_nre_col {
-                        eff_price_str.clone()
-                    } else {
-                        unit_price_str.clone()
-                    })
-                    .green()
-                } else {
-                    style(if show_nre_col {
-                        eff_price_str.clone()
+            for option in &matrix.options {
+                let mut unit = format!("{:.2}", option.unit_price);
+                if option.currency != converter.target {
+                    // Flag converted prices
+                    unit.push('*');
+                }
+                let dash_if_zero = |v: f64| {
+                    if v > 0.0 {
+                        format!("{:.4}", v)
                     } else {
-                        unit_price_str.clone()
-                    })
-                    .white()
+                        "-".to_string()
+                    }
                 };
-
-                if show_nre_col {
-                    println!(
-                        "{:<8} {:<18} {:<12} {:<10} {:<10} {:<8} {:<8} {:<10} {:<10}",
-                        style(&short_id).cyan(),
-                        title_truncated,
-                        supplier_short,
-                        unit_price_str,
-                        price_style,
-                        moq,
-                        lead_time,
-                        tooling,
-                        quote.quote_status
-                    );
-                } else {
-                    println!(
-                        "{:<8} {:<20} {:<15} {:<10} {:<8} {:<10} {:<10} {:<10}",
-                        style(&short_id).cyan(),
-                        title_truncated,
-                        supplier_short,
-                        price_style,
-                        moq,
-                        lead_time,
-                        tooling,
-                        quote.quote_status
-                    );
+                let eff = format!("{:.4}", option.effective_unit_cost);
+                let mut score = format!("{:.0}", option.score);
+                if !option.missing.is_empty() {
+                    // Flag scores with factors counted as missing
+                    score.push('?');
                 }
+                println!(
+                    "{:<4} {:<8} {:<18} {:<10} {:<8} {:<10} {:<9} {:<9} {:<10} {:<6} {:<5} {:<5}",
+                    option.rank,
+                    style(short(&option.quote)).cyan(),
+                    truncate_str(&option.title, 16),
+                    truncate_str(&short(&option.supplier), 10),
+                    option.buy_qty,
+                    unit,
+                    dash_if_zero(option.moq_excess_per_unit),
+                    dash_if_zero(option.nre_per_unit),
+                    if option.rank == 1 {
+                        style(eff).green()
+                    } else {
+                        style(eff).white()
+                    },
+                    option
+                        .lead_time_days
+                        .map_or("-".to_string(), |d| format!("{}d", d)),
+                    option
+                        .supplier_score
+                        .map_or("-".to_string(), |s| format!("{:.0}", s)),
+                    if option.rank == 1 {
+                        style(score).green().bold()
+                    } else {
+                        style(score).white()
+                    }
+                );
             }
 
-            if let Some(lowest) = quotes.first() {
-                let supplier_display = short_ids
-                    .get_short_id(&lowest.supplier)
-                    .unwrap_or_else(|| lowest.supplier.clone());
-                let best_price = effective_unit_price(lowest, qty, args.amortize, include_nre);
+            for option in matrix.options.iter().filter(|o| !o.missing.is_empty()) {
+                println!(
+                    "{} {}: no {} data, scored {:.0}",
+                    style("?").yellow(),
+                    short(&option.quote),
+                    option.missing.join(" or "),
+                    sourcing_config.missing_score
+                );
+            }
+
+            if let Some(best) = matrix.best() {
                 println!();
                 println!(
-                    "{} Lowest price at qty {}: ${:.2} from {}",
+                    "{} Best at qty {}: {} from {} - {}{:.4} effective, score {:.0}",
                     style("★").yellow(),
                     qty,
-                    style(format!("{:.2}", best_price)).green(),
-                    style(&supplier_display).cyan()
+                    style(short(&best.quote)).cyan(),
+                    style(short(&best.supplier)).cyan(),
+                    converter.target.symbol(),
+                    best.effective_unit_cost,
+                    best.score
                 );
             }
+            if matrix
+                .options
+                .iter()
+                .any(|o| o.currency != converter.target)
+            {
+                println!(
+                    "{}",
+                    style(format!("* converted to {}", converter.target)).dim()
+                );
+            }
+            for excluded in &matrix.excluded {
+                println!(
+                    "{} {} ({}) excluded: {}",
+                    style("○").dim(),
+                    style(short(&excluded.quote)).dim(),
+                    truncate_str(&excluded.title, 24),
+                    excluded.reason
+                );
+            }
+            for issue in converter.issues() {
+                println!("{} {}", style("⚠ Exchange rate:").yellow(), issue);
+            }
+
+            let mut warned = HashSet::new();
+            for option in &matrix.options {
+                if !warned.insert(option.supplier.as_str()) {
+                    continue;
+                }
+                if let Some(warning) = super::sup::approval_warning(&project, &option.supplier) {
+                    println!("{} {}", style("!").yellow(), warning);
+                }
+            }
         }
-        _ => {
-            let yaml = serde_yml::to_string(&quotes).into_diagnostic()?;
-            print!("{}", yaml);
-        }
+    }
+
+    if let Some(ref selection) = args.select {
+        let chosen = match selection {
+            Some(quote) => {
+                let quote_id = short_ids.resolve(quote).unwrap_or_else(|| quote.clone());
+                if let Some(excluded) = matrix
+                    .excluded
+                    .iter()
+                    .find(|e| e.quote.starts_with(&quote_id))
+                {
+                    return Err(miette::miette!(
+                        "Quote {} is not a valid option: {}",
+                        quote,
+                        excluded.reason
+                    ));
+                }
+                matrix.option(&quote_id).ok_or_else(|| {
+                    miette::miette!("Quote {} is not a quote for {}", quote, args.item)
+                })?
+            }
+            None => matrix
+                .best()
+                .ok_or_else(|| miette::miette!("No valid quotes to select from"))?,
+        };
+        select_quote(
+            &project,
+            &item,
+            &matrix,
+            chosen,
+            args.rationale.as_deref(),
+            &author,
+        )?;
+        println!();
+        println!(
+            "{} Selected {} for {}",
+            style("✓").green(),
+            style(short(&chosen.quote)).cyan(),
+            style(&args.item).yellow()
+        );
     }
 
     Ok(())
 }
 
+/// Record a quote selection and its rationale on the component
+fn select_quote(
+    project: &Project,
+    component_id: &str,
+    matrix: &SourcingMatrix,
+    chosen: &SourcingOption,
+    note: Option<&str>,
+    author: &str,
+) -> Result<()> {
+    let cmp_dir = project.root().join("bom/components");
+    let (path, mut component) =
+        crate::core::loader::load_entity::<Component>(&cmp_dir, component_id)?.ok_or_else(
+            || {
+                miette::miette!(
+                    "Only component quotes can be selected; '{}' is not a component",
+                    component_id
+                )
+            },
+        )?;
+
+    let mut rationale = matrix.rationale(chosen);
+    if let Some(note) = note {
+        rationale = format!("{} {}", note.trim(), rationale);
+    }
+    component.selected_quote = Some(chosen.quote.clone());
+    component.quote_selection = Some(QuoteSelection {
+        quote: chosen.quote.clone(),
+        selected_by: author.to_string(),
+        date: chrono::Local::now().date_naive(),
+        rationale,
+        qty: Some(matrix.qty),
+        volume: matrix.volume,
+        effective_unit_cost: Some(chosen.effective_unit_cost),
+        currency: Some(matrix.currency),
+        score: Some((chosen.score * 10.0).round() / 10.0),
+    });
+
+    let yaml = serde_yml::to_string(&component).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+    Ok(())
+}
+
 fn run_price(args: PriceArgs, global: &GlobalOpts) -> Result<()> {
     let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
 
@@ -1618,3 +1796,346 @@ fn run_price(args: PriceArgs, global: &GlobalOpts) -> Result<()> {
 
     Ok(())
 }
+
+fn run_rfq(args: RfqArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let display = |id: &str| {
+        short_ids
+            .get_short_id(id)
+            .unwrap_or_else(|| truncate_str(id, 12))
+    };
+
+    if args.components.is_empty() && args.assembly.is_none() {
+        return Err(miette::miette!(
+            "Give components to quote and/or --assembly"
+        ));
+    }
+    let mut tiers = args.qty.clone();
+    tiers.retain(|q| *q > 0);
+    tiers.sort_unstable();
+    tiers.dedup();
+    if tiers.is_empty() {
+        return Err(miette::miette!("--qty needs at least one quantity above 0"));
+    }
+
+    let components = super::report::load_all_components(&project);
+    let by_id: HashMap<String, &Component> =
+        components.iter().map(|c| (c.id.to_string(), c)).collect();
+
+    // Quantities to quote for each requested component
+    let mut requested: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    if let Some(ref reference) = args.assembly {
+        let resolved = short_ids
+            .resolve(reference)
+            .unwrap_or_else(|| reference.clone());
+        let assembly = super::report::load_assembly(&project, &resolved)?;
+        let assemblies = super::report::load_all_assemblies(&project);
+        let assembly_map: HashMap<String, &Assembly> =
+            assemblies.iter().map(|a| (a.id.to_string(), a)).collect();
+        for (id, per_unit) in crate::core::obsolescence::bom_quantities(&assembly, &assembly_map) {
+            if by_id.get(&id).is_some_and(|c| c.make_buy == MakeBuy::Buy) {
+                requested.insert(id, tiers.iter().map(|q| q * per_unit).collect());
+            }
+        }
+    }
+    for reference in &args.components {
+        let id = short_ids
+            .resolve(reference)
+            .unwrap_or_else(|| reference.clone());
+        if !by_id.contains_key(&id) {
+            return Err(miette::miette!(
+                "No component found matching '{}'",
+                reference
+            ));
+        }
+        requested.entry(id).or_insert_with(|| tiers.clone());
+    }
+    if requested.is_empty() {
+        return Err(miette::miette!("No buy parts to request quotes for"));
+    }
+
+    // Who gets asked for what
+    let mut by_supplier: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if args.supplier.is_empty() {
+        let mut unsourced = Vec::new();
+        for id in requested.keys() {
+            let listed: Vec<&String> = by_id[id]
+                .suppliers
+                .iter()
+                .filter_map(|s| s.supplier_id.as_ref())
+                .collect();
+            if listed.is_empty() {
+                unsourced.push(display(id));
+            }
+            for sup in listed {
+                by_supplier.entry(sup.clone()).or_default().push(id.clone());
+            }
+        }
+        if !unsourced.is_empty() {
+            return Err(miette::miette!(
+                "No supplier listed for {}; name one with --supplier",
+                unsourced.join(", ")
+            ));
+        }
+    } else {
+        for reference in &args.supplier {
+            let sup = short_ids
+                .resolve(reference)
+                .unwrap_or_else(|| reference.clone());
+            by_supplier.insert(sup, requested.keys().cloned().collect());
+        }
+    }
+
+    let suppliers: Vec<Supplier> =
+        crate::core::loader::load_all(&project.root().join("bom/suppliers"))?;
+    let currency = args
+        .currency
+        .unwrap_or_else(|| Config::load().currency.unwrap_or_default().reporting);
+    let issued = chrono::Local::now().date_naive();
+    let base_dir = args.dir.unwrap_or_else(|| PathBuf::from("."));
+
+    let mut manifests = Vec::new();
+    for (supplier_id, items) in by_supplier {
+        let supplier = suppliers.iter().find(|s| s.id.to_string() == supplier_id);
+        let Some(supplier) = supplier else {
+            return Err(miette::miette!(
+                "No supplier found matching '{}'",
+                supplier_id
+            ));
+        };
+        if let Some(warning) = supplier.approval_warning() {
+            eprintln!("{} Warning: {}", style("!").yellow(), warning);
+        }
+        let label = supplier.short_name.as_ref().unwrap_or(&supplier.name);
+        let rfq_ref = format!("RFQ-{}-{}", issued, label.replace(['/', '\\', ' '], "_"));
+        let out_dir = base_dir.join(&rfq_ref);
+        fs::create_dir_all(&out_dir).into_diagnostic()?;
+
+        let mut rfq_items = Vec::new();
+        for id in items {
+            let cmp = by_id[&id];
+            let documents = cmp
+                .documents
+                .iter()
+                .map(|doc| copy_rfq_document(&project, &out_dir, &cmp.part_number, doc))
+                .collect::<Result<Vec<_>>>()?;
+            rfq_items.push(RfqItem {
+                component: id.clone(),
+                part_number: cmp.part_number.clone(),
+                revision: cmp.revision.clone().filter(|r| !r.is_empty()),
+                title: cmp.title.clone(),
+                quantities: requested[&id].clone(),
+                documents,
+            });
+        }
+
+        let manifest = RfqManifest {
+            rfq: rfq_ref,
+            supplier: supplier_id,
+            supplier_name: supplier.name.clone(),
+            issued,
+            respond_by: args.due,
+            currency,
+            items: rfq_items,
+        };
+        fs::write(out_dir.join(rfq::SPREADSHEET_FILE), manifest.spreadsheet()).into_diagnostic()?;
+        fs::write(
+            out_dir.join(rfq::MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest).into_diagnostic()?,
+        )
+        .into_diagnostic()?;
+        manifests.push((out_dir, manifest));
+    }
+
+    match global.output {
+        OutputFormat::Json => {
+            let list: Vec<&RfqManifest> = manifests.iter().map(|(_, m)| m).collect();
+            println!("{}", serde_json::to_string_pretty(&list).into_diagnostic()?);
+        }
+        OutputFormat::Yaml => {
+            let list: Vec<&RfqManifest> = manifests.iter().map(|(_, m)| m).collect();
+            print!("{}", serde_yml::to_string(&list).into_diagnostic()?);
+        }
+        _ => {
+            for (out_dir, manifest) in &manifests {
+                let lines: usize = manifest.items.iter().map(|i| i.quantities.len()).sum();
+                let documents = manifest.items.iter().flat_map(|i| &i.documents);
+                let (sent, missing): (Vec<&RfqDocument>, Vec<&RfqDocument>) =
+                    documents.partition(|d| d.path.is_some());
+                println!(
+                    "{} Created {} for {} ({})",
+                    style("✓").green(),
+                    style(&manifest.rfq).cyan(),
+                    style(&manifest.supplier_name).yellow(),
+                    display(&manifest.supplier)
+                );
+                println!("   {}", style(out_dir.display()).dim());
+                println!(
+                    "   {} item(s), {} line(s), {} document(s)",
+                    manifest.items.len(),
+                    lines,
+                    sent.len()
+                );
+                for doc in missing {
+                    eprintln!(
+                        "{} Document not found, not included: {}",
+                        style("!").yellow(),
+                        doc.source
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy one of a component's documents into an RFQ package
+fn copy_rfq_document(
+    project: &Project,
+    out_dir: &Path,
+    part_number: &str,
+    doc: &crate::entities::component::Document,
+) -> Result<RfqDocument> {
+    let source = project.root().join(&doc.path);
+    let path = match source.file_name() {
+        Some(name) if source.is_file() => {
+            let rel = Path::new("documents")
+                .join(part_number.replace(['/', '\\', ' '], "_"))
+                .join(name);
+            let dest = out_dir.join(&rel);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent).into_diagnostic()?;
+            }
+            fs::copy(&source, &dest).into_diagnostic()?;
+            Some(rel.to_string_lossy().replace('\\', "/"))
+        }
+        _ => None,
+    };
+    Ok(RfqDocument {
+        doc_type: doc.doc_type.clone(),
+        source: doc.path.clone(),
+        path,
+        revision: doc.revision.clone(),
+    })
+}
+
+fn run_rfq_import(args: RfqImportArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+
+    let (package_dir, sheet) = if args.path.is_dir() {
+        (args.path.clone(), args.path.join(rfq::SPREADSHEET_FILE))
+    } else {
+        let dir = args
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        (dir, args.path.clone())
+    };
+    let manifest_path = package_dir.join(rfq::MANIFEST_FILE);
+    let manifest_content = fs::read_to_string(&manifest_path).map_err(|e| {
+        miette::miette!(
+            "Cannot read RFQ manifest {}: {}",
+            manifest_path.display(),
+            e
+        )
+    })?;
+    let manifest: RfqManifest = serde_json::from_str(&manifest_content)
+        .map_err(|e| miette::miette!("Invalid RFQ manifest {}: {}", manifest_path.display(), e))?;
+    let file = fs::File::open(&sheet)
+        .map_err(|e| miette::miette!("Cannot open {}: {}", sheet.display(), e))?;
+    let response = rfq::parse_response(file, &manifest).map_err(|e| miette::miette!("{}", e))?;
+
+    let quotes: Vec<Quote> = response
+        .quoted
+        .iter()
+        .map(|item| item.to_quote(&manifest, &config.author()))
+        .collect();
+
+    let mut short_ids = ShortIdIndex::load(&project);
+    let mut created = Vec::new();
+    if !args.dry_run {
+        let output_dir = project.root().join("bom/quotes");
+        fs::create_dir_all(&output_dir).into_diagnostic()?;
+        for quote in &quotes {
+            let file_path = output_dir.join(format!("{}.tdt.yaml", quote.id));
+            fs::write(&file_path, serde_yml::to_string(quote).into_diagnostic()?)
+                .into_diagnostic()?;
+            let short_id = short_ids
+                .add(quote.id.to_string())
+                .unwrap_or_else(|| format_short_id(&quote.id));
+            created.push(short_id);
+        }
+        super::utils::save_short_ids(&mut short_ids, &project);
+    }
+
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&quotes).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&quotes).into_diagnostic()?);
+        }
+        _ => {
+            let display = |id: &str| {
+                short_ids
+                    .get_short_id(id)
+                    .unwrap_or_else(|| truncate_str(id, 12))
+            };
+            let verb = if args.dry_run {
+                "Would create"
+            } else {
+                "Created"
+            };
+            println!(
+                "{} {} {} quote(s) from {} ({})",
+                style("✓").green(),
+                verb,
+                quotes.len(),
+                style(&manifest.rfq).cyan(),
+                style(&manifest.supplier_name).yellow()
+            );
+            for (i, quote) in quotes.iter().enumerate() {
+                let breaks = quote
+                    .price_breaks
+                    .iter()
+                    .map(|pb| format!("{}+ @ {:.2}", pb.min_qty, pb.unit_price))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let nre = quote.total_nre();
+                println!(
+                    "   {} {} {} | {} {}{}{}",
+                    created.get(i).map(String::as_str).unwrap_or("-"),
+                    style("→").dim(),
+                    display(quote.component.as_deref().unwrap_or_default()),
+                    quote.currency,
+                    breaks,
+                    if nre > 0.0 {
+                        format!(" | NRE {:.2}", nre)
+                    } else {
+                        String::new()
+                    },
+                    quote
+                        .valid_until
+                        .map(|d| format!(" | valid until {}", d))
+                        .unwrap_or_default()
+                );
+            }
+            if !response.no_bid.is_empty() {
+                println!(
+                    "   {} No price for: {}",
+                    style("!").yellow(),
+                    response.no_bid.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1343 @@
+//! `tdt rcv` command - Receiving inspection of supplier lots
+
+use clap::{Subcommand, ValueEnum};
+use console::style;
+use miette::{IntoDiagnostic, Result};
+use std::fs;
+
+use crate::cli::commands::ncr::{CliNcrCategory, CliNcrSeverity};
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
+use crate::core::project::Project;
+use crate::core::receiving;
+use crate::core::sampling;
+use crate::core::shortid::ShortIdIndex;
+use crate::core::Config;
+use crate::entities::component::Component;
+use crate::entities::control::Control;
+use crate::entities::feature::Feature;
+use crate::entities::ncr::{
+    AffectedItems, Defect, Detection, DetectionStage, Ncr, NcrCategory, NcrType,
+};
+use crate::entities::receipt::{CheckResult, Receipt, ReceiptCheck, ReceiptStatus};
+use crate::schema::template::{TemplateContext, TemplateGenerator};
+use crate::schema::wizard::SchemaWizard;
+
+#[derive(Subcommand, Debug)]
+pub enum RcvCommands {
+    /// List receipts with filtering
+    List(ListArgs),
+
+    /// Record a new receipt (inspection checks come from the component's controls)
+    New(NewArgs),
+
+    /// Show a receipt's details
+    Show(ShowArgs),
+
+    /// Edit a receipt in your editor
+    Edit(EditArgs),
+
+    /// Delete a receipt
+    Delete(DeleteArgs),
+
+    /// Archive a receipt (soft delete)
+    Archive(ArchiveArgs),
+
+    /// Record an inspection check result
+    Inspect(InspectArgs),
+
+    /// Accept a receipt for use in production
+    Accept(AcceptArgs),
+
+    /// Reject a receipt and open a supplier NCR
+    Reject(RejectArgs),
+}
+
+/// Receipt status filter
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReceiptStatusFilter {
+    Pending,
+    Accepted,
+    Rejected,
+    All,
+}
+
+/// List column for display and sorting
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListColumn {
+    #[value(name = "id")]
+    Id,
+    #[value(name = "title")]
+    Title,
+    #[value(name = "supplier-lot")]
+    SupplierLot,
+    #[value(name = "quantity")]
+    Quantity,
+    #[value(name = "received")]
+    Received,
+    #[value(name = "checks")]
+    Checks,
+    #[value(name = "receipt-status")]
+    ReceiptStatus,
+    #[value(name = "author")]
+    Author,
+    #[value(name = "created")]
+    Created,
+}
+
+impl std::fmt::Display for ListColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListColumn::Id => write!(f, "id"),
+            ListColumn::Title => write!(f, "title"),
+            ListColumn::SupplierLot => write!(f, "supplier-lot"),
+            ListColumn::Quantity => write!(f, "quantity"),
+            ListColumn::Received => write!(f, "received"),
+            ListColumn::Checks => write!(f, "checks"),
+            ListColumn::ReceiptStatus => write!(f, "receipt-status"),
+            ListColumn::Author => write!(f, "author"),
+            ListColumn::Created => write!(f, "created"),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ListArgs {
+    /// Filter by receipt status
+    #[arg(long, short = 's', default_value = "all")]
+    pub status: ReceiptStatusFilter,
+
+    /// Filter by supplier (SUP ID or short ID)
+    #[arg(long)]
+    pub supplier: Option<String>,
+
+    /// Filter by component (CMP ID or short ID)
+    #[arg(long, short = 'c')]
+    pub component: Option<String>,
+
+    /// Search in title, supplier lot and PO number
+    #[arg(long)]
+    pub search: Option<String>,
+
+    /// Columns to display
+    #[arg(long, value_delimiter = ',', default_values_t = vec![
+        ListColumn::Id,
+        ListColumn::Title,
+        ListColumn::SupplierLot,
+        ListColumn::Quantity,
+        ListColumn::Received,
+        ListColumn::Checks,
+        ListColumn::ReceiptStatus
+    ])]
+    pub columns: Vec<ListColumn>,
+
+    /// Sort by column
+    #[arg(long, default_value = "received")]
+    pub sort: ListColumn,
+
+    /// Reverse sort order
+    #[arg(long, short = 'r')]
+    pub reverse: bool,
+
+    /// Limit number of results
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Show only count
+    #[arg(long)]
+    pub count: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NewArgs {
+    /// Receipt title (default: part number and supplier lot)
+    #[arg(long, short = 't')]
+    pub title: Option<String>,
+
+    /// Supplier the material came from (SUP ID or short ID)
+    #[arg(long)]
+    pub supplier: Option<String>,
+
+    /// Component received (CMP ID or short ID)
+    #[arg(long, short = 'c')]
+    pub component: Option<String>,
+
+    /// Supplier lot / batch number
+    #[arg(long)]
+    pub supplier_lot: Option<String>,
+
+    /// Quantity received
+    #[arg(long, short = 'Q')]
+    pub quantity: Option<u32>,
+
+    /// Purchase order number
+    #[arg(long)]
+    pub po: Option<String>,
+
+    /// Date received (YYYY-MM-DD, default: today)
+    #[arg(long, short = 'd')]
+    pub date: Option<chrono::NaiveDate>,
+
+    /// Open in editor after creation
+    #[arg(long, short = 'e')]
+    pub edit: bool,
+
+    /// Skip opening in editor
+    #[arg(long)]
+    pub no_edit: bool,
+
+    /// Interactive mode (prompt for fields)
+    #[arg(long, short = 'i')]
+    pub interactive: bool,
+
+    /// Link to another entity (auto-infers link type)
+    #[arg(long, short = 'L')]
+    pub link: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ShowArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct EditArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DeleteArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+
+    /// Force deletion even if other entities reference this one
+    #[arg(long)]
+    pub force: bool,
+
+    /// Suppress output
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ArchiveArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+
+    /// Force archiving even if other entities reference this one
+    #[arg(long)]
+    pub force: bool,
+
+    /// Suppress output
+    #[arg(long, short = 'y')]
+    pub quiet: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct InspectArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+
+    /// Check to record: control ID, characteristic name or check number
+    /// (a control or name not yet on the receipt adds a new check)
+    pub check: String,
+
+    /// Check passed
+    #[arg(long, conflicts_with = "fail")]
+    pub pass: bool,
+
+    /// Check failed
+    #[arg(long)]
+    pub fail: bool,
+
+    /// Worst measured value (judged against the control's limits when
+    /// neither --pass nor --fail is given)
+    #[arg(long)]
+    pub value: Option<f64>,
+
+    /// Number of defective samples found (judged against the check's
+    /// acceptance number when it has a sampling plan)
+    #[arg(long)]
+    pub defects: Option<u32>,
+
+    /// Sample mean, for checks with a variables sampling plan
+    #[arg(long, requires = "std_dev")]
+    pub mean: Option<f64>,
+
+    /// Sample standard deviation, for checks with a variables sampling plan
+    #[arg(long, requires = "mean")]
+    pub std_dev: Option<f64>,
+
+    /// Number of units inspected
+    #[arg(long)]
+    pub sample_size: Option<u32>,
+
+    /// Notes
+    #[arg(long, short = 'm')]
+    pub notes: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AcceptArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+
+    /// Accept even though some checks have not been recorded
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RejectArgs {
+    /// Receipt ID (full or short)
+    pub id: String,
+
+    /// Reason for rejection
+    #[arg(long, short = 'r')]
+    pub reason: String,
+
+    /// Severity of the supplier NCR
+    #[arg(long, short = 'S', default_value = "minor")]
+    pub severity: CliNcrSeverity,
+
+    /// Category of the supplier NCR (default: dimensional when a measured
+    /// check failed, otherwise material)
+    #[arg(long)]
+    pub category: Option<CliNcrCategory>,
+}
+
+/// Directories where receipts are stored
+const RECEIPT_DIRS: &[&str] = &["manufacturing/receipts"];
+
+/// Entity configuration for receipt commands
+const ENTITY_CONFIG: crate::cli::EntityConfig = crate::cli::EntityConfig {
+    prefix: EntityPrefix::Rcv,
+    dirs: RECEIPT_DIRS,
+    name: "receipt",
+    name_plural: "receipts",
+};
+
+/// Run a receipt command
+pub fn run(cmd: RcvCommands, global: &GlobalOpts) -> Result<()> {
+    match cmd {
+        RcvCommands::List(args) => run_list(args, global),
+        RcvCommands::New(args) => run_new(args, global),
+        RcvCommands::Show(args) => run_show(args, global),
+        RcvCommands::Edit(args) => run_edit(args),
+        RcvCommands::Delete(args) => run_delete(args),
+        RcvCommands::Archive(args) => run_archive(args),
+        RcvCommands::Inspect(args) => run_inspect(args, global),
+        RcvCommands::Accept(args) => run_accept(args, global),
+        RcvCommands::Reject(args) => run_reject(args, global),
+    }
+}
+
+/// Load a receipt by full or short ID
+pub(crate) fn load_receipt(
+    project: &Project,
+    short_ids: &ShortIdIndex,
+    id: &str,
+) -> Result<(std::path::PathBuf, Receipt)> {
+    let resolved_id = short_ids.resolve(id).unwrap_or_else(|| id.to_string());
+    let rcv_dir = project.root().join("manufacturing/receipts");
+    loader::load_entity::<Receipt>(&rcv_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No receipt found matching '{}'", id))
+}
+
+fn save_receipt(path: &std::path::Path, receipt: &Receipt) -> Result<()> {
+    fs::write(path, serde_yml::to_string(receipt).into_diagnostic()?).into_diagnostic()
+}
+
+/// Check results as "passed/total"
+fn checks_label(receipt: &Receipt) -> String {
+    if receipt.checks.is_empty() {
+        return "-".to_string();
+    }
+    let passed = receipt
+        .checks
+        .iter()
+        .filter(|c| c.result == CheckResult::Pass)
+        .count();
+    format!("{}/{}", passed, receipt.checks.len())
+}
+
+/// List receipts
+fn run_list(args: ListArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let rcv_dir = project.root().join("manufacturing/receipts");
+    let mut short_ids = ShortIdIndex::load(&project);
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let resolve = |id: &Option<String>| {
+        id.as_ref()
+            .map(|i| short_ids.resolve(i).unwrap_or_else(|| i.clone()))
+    };
+    let supplier = resolve(&args.supplier);
+    let component = resolve(&args.component);
+
+    let receipts: Vec<Receipt> = loader::load_all(&rcv_dir)?;
+
+    let mut receipts: Vec<Receipt> = receipts
+        .into_iter()
+        .filter(|r| match args.status {
+            ReceiptStatusFilter::Pending => r.receipt_status == ReceiptStatus::Pending,
+            ReceiptStatusFilter::Accepted => r.receipt_status == ReceiptStatus::Accepted,
+            ReceiptStatusFilter::Rejected => r.receipt_status == ReceiptStatus::Rejected,
+            ReceiptStatusFilter::All => true,
+        })
+        .filter(|r| {
+            supplier.as_ref().is_none_or(|s| {
+                r.links
+                    .supplier
+                    .as_ref()
+                    .is_some_and(|id| &id.to_string() == s)
+            })
+        })
+        .filter(|r| {
+            component.as_ref().is_none_or(|c| {
+                r.links
+                    .component
+                    .as_ref()
+                    .is_some_and(|id| &id.to_string() == c)
+            })
+        })
+        .filter(|r| {
+            args.search
+                .as_ref()
+                .map(|s| {
+                    let search = s.to_lowercase();
+                    r.title.to_lowercase().contains(&search)
+                        || [&r.supplier_lot, &r.po_number].iter().any(|n| {
+                            n.as_ref()
+                                .is_some_and(|n| n.to_lowercase().contains(&search))
+                        })
+                })
+                .unwrap_or(true)
+        })
+        .collect();
+
+    // Sort
+    receipts.sort_by(|a, b| match args.sort {
+        ListColumn::Id => a.id.to_string().cmp(&b.id.to_string()),
+        ListColumn::Title => a.title.cmp(&b.title),
+        ListColumn::SupplierLot => a.supplier_lot.cmp(&b.supplier_lot),
+        ListColumn::Quantity => a.quantity.cmp(&b.quantity),
+        ListColumn::Received => a.received_date.cmp(&b.received_date),
+        ListColumn::Checks => a.checks.len().cmp(&b.checks.len()),
+        ListColumn::ReceiptStatus => a
+            .receipt_status
+            .to_string()
+            .cmp(&b.receipt_status.to_string()),
+        ListColumn::Author => a.author.cmp(&b.author),
+        ListColumn::Created => a.created.cmp(&b.created),
+    });
+
+    if args.reverse {
+        receipts.reverse();
+    }
+
+    if let Some(limit) = args.limit {
+        receipts.truncate(limit);
+    }
+
+    if args.count {
+        println!("{}", receipts.len());
+        return Ok(());
+    }
+
+    if receipts.is_empty() {
+        println!("No receipts found.");
+        return Ok(());
+    }
+
+    // Update short ID index
+    short_ids.ensure_all(receipts.iter().map(|r| r.id.to_string()));
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    let column_value = |receipt: &Receipt, col: &ListColumn, short_id: &str| -> String {
+        match col {
+            ListColumn::Id => short_id.to_string(),
+            ListColumn::Title => receipt.title.clone(),
+            ListColumn::SupplierLot => receipt.supplier_lot.clone().unwrap_or_default(),
+            ListColumn::Quantity => receipt.quantity.map(|q| q.to_string()).unwrap_or_default(),
+            ListColumn::Received => receipt
+                .received_date
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            ListColumn::Checks => checks_label(receipt),
+            ListColumn::ReceiptStatus => receipt.receipt_status.to_string(),
+            ListColumn::Author => receipt.author.clone(),
+            ListColumn::Created => receipt.created.format("%Y-%m-%d").to_string(),
+        }
+    };
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&receipts).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yml::to_string(&receipts).into_diagnostic()?;
+            print!("{}", yaml);
+        }
+        OutputFormat::Csv => {
+            println!(
+                "short_id,id,title,supplier,component,supplier_lot,quantity,received,receipt_status,author"
+            );
+            for receipt in &receipts {
+                let short_id = short_ids
+                    .get_short_id(&receipt.id.to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    short_id,
+                    receipt.id,
+                    escape_csv(&receipt.title),
+                    receipt
+                        .links
+                        .supplier
+                        .as_ref()
+                        .map(|s| s.to_string())
+                        .unwrap_or_default(),
+                    receipt
+                        .links
+                        .component
+                        .as_ref()
+                        .map(|c| c.to_string())
+                        .unwrap_or_default(),
+                    escape_csv(receipt.supplier_lot.as_deref().unwrap_or("")),
+                    column_value(receipt, &ListColumn::Quantity, &short_id),
+                    column_value(receipt, &ListColumn::Received, &short_id),
+                    receipt.receipt_status,
+                    escape_csv(&receipt.author)
+                );
+            }
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            let mut headers = vec![];
+            let mut widths = vec![];
+
+            for col in &args.columns {
+                let (header, width) = match col {
+                    ListColumn::Id => ("ID", 17),
+                    ListColumn::Title => ("TITLE", 30),
+                    ListColumn::SupplierLot => ("SUPPLIER LOT", 14),
+                    ListColumn::Quantity => ("QTY", 6),
+                    ListColumn::Received => ("RECEIVED", 12),
+                    ListColumn::Checks => ("CHECKS", 7),
+                    ListColumn::ReceiptStatus => ("STATUS", 10),
+                    ListColumn::Author => ("AUTHOR", 16),
+                    ListColumn::Created => ("CREATED", 12),
+                };
+                headers.push((header, *col));
+                widths.push(width);
+            }
+
+            print!("{:<8} ", style("SHORT").bold().dim());
+            for (i, (header, _)) in headers.iter().enumerate() {
+                print!("{:<width$} ", style(header).bold(), width = widths[i]);
+            }
+            println!();
+
+            for receipt in &receipts {
+                let short_id = short_ids
+                    .get_short_id(&receipt.id.to_string())
+                    .unwrap_or_default();
+
+                print!("{:<8} ", style(&short_id).cyan());
+
+                for (i, (_, col)) in headers.iter().enumerate() {
+                    let value = match col {
+                        ListColumn::Title => truncate_str(&receipt.title, widths[i]),
+                        ListColumn::Id => receipt.id.to_string(),
+                        _ => column_value(receipt, col, &short_id),
+                    };
+                    print!("{:<width$} ", value, width = widths[i]);
+                }
+                println!();
+            }
+        }
+        OutputFormat::Md => {
+            let headers: Vec<&str> = args
+                .columns
+                .iter()
+                .map(|c| match c {
+                    ListColumn::Id => "ID",
+                    ListColumn::Title => "Title",
+                    ListColumn::SupplierLot => "Supplier Lot",
+                    ListColumn::Quantity => "Qty",
+                    ListColumn::Received => "Received",
+                    ListColumn::Checks => "Checks",
+                    ListColumn::ReceiptStatus => "Status",
+                    ListColumn::Author => "Author",
+                    ListColumn::Created => "Created",
+                })
+                .collect();
+            println!("| {} |", headers.join(" | "));
+            println!(
+                "| {} |",
+                headers
+                    .iter()
+                    .map(|_| "---")
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            );
+
+            for receipt in &receipts {
+                let short_id = short_ids
+                    .get_short_id(&receipt.id.to_string())
+                    .unwrap_or_default();
+                let values: Vec<String> = args
+                    .columns
+                    .iter()
+                    .map(|c| match c {
+                        ListColumn::Title => truncate_str(&receipt.title, 40),
+                        _ => column_value(receipt, c, &short_id),
+                    })
+                    .collect();
+                println!("| {} |", values.join(" | "));
+            }
+        }
+        OutputFormat::Id => {
+            for receipt in &receipts {
+                println!("{}", receipt.id);
+            }
+        }
+        OutputFormat::ShortId => {
+            for receipt in &receipts {
+                let short_id = short_ids
+                    .get_short_id(&receipt.id.to_string())
+                    .unwrap_or_default();
+                println!("{}", short_id);
+            }
+        }
+        OutputFormat::Path => {
+            for receipt in &receipts {
+                let path = rcv_dir.join(format!("{}.tdt.yaml", receipt.id));
+                println!("{}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a new receipt
+fn run_new(args: NewArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+
+    let mut title = args.title.clone();
+    if args.interactive {
+        let wizard = SchemaWizard::new();
+        let result = wizard.run(EntityPrefix::Rcv)?;
+        title = result.get_string("title").map(String::from);
+    }
+
+    let supplier = args
+        .supplier
+        .as_ref()
+        .map(|s| short_ids.resolve(s).unwrap_or_else(|| s.clone()));
+    let component_id = args
+        .component
+        .as_ref()
+        .map(|c| short_ids.resolve(c).unwrap_or_else(|| c.clone()));
+
+    let component = match component_id {
+        Some(ref id) => {
+            let cmp_dir = project.root().join("bom/components");
+            let (_, cmp) = loader::load_entity::<Component>(&cmp_dir, id)?
+                .ok_or_else(|| miette::miette!("No component found matching '{}'", id))?;
+            Some(cmp)
+        }
+        None => None,
+    };
+
+    let title = title.unwrap_or_else(|| match (&component, &args.supplier_lot) {
+        (Some(cmp), Some(lot)) => format!("{} lot {}", cmp.part_number, lot),
+        (Some(cmp), None) => format!("{} receipt", cmp.part_number),
+        _ => "New Receipt".to_string(),
+    });
+
+    let id = EntityId::new(EntityPrefix::Rcv);
+
+    let generator = TemplateGenerator::new().map_err(|e| miette::miette!("{}", e))?;
+    let mut ctx = TemplateContext::new(id.clone(), config.author()).with_title(&title);
+    if let Some(ref supplier) = supplier {
+        ctx = ctx.with_supplier(supplier);
+    }
+    if let Some(ref cmp) = component {
+        ctx = ctx.with_component_id(cmp.id.to_string());
+    }
+    if let Some(ref lot) = args.supplier_lot {
+        ctx = ctx.with_supplier_lot(lot);
+    }
+    if let Some(ref po) = args.po {
+        ctx = ctx.with_po_number(po);
+    }
+    if let Some(qty) = args.quantity {
+        ctx = ctx.with_quantity(qty);
+    }
+
+    let mut yaml_content = generator
+        .generate_receipt(&ctx)
+        .map_err(|e| miette::miette!("{}", e))?;
+
+    // Populate the inspection plan from the component's controls
+    let mut checks_added = 0;
+    let mut plan_notes = Vec::new();
+    if component.is_some() || args.date.is_some() {
+        let mut receipt: Receipt = serde_yml::from_str(&yaml_content).into_diagnostic()?;
+        if let Some(date) = args.date {
+            receipt.received_date = Some(date);
+        }
+        if let Some(ref cmp) = component {
+            let features: Vec<Feature> =
+                loader::load_all(&project.root().join("tolerances/features"))?;
+            let controls: Vec<Control> =
+                loader::load_all(&project.root().join("manufacturing/controls"))?;
+            let earlier: Vec<Receipt> =
+                loader::load_all(&project.root().join("manufacturing/receipts"))?;
+            let plan = receiving::inspection_plan(
+                &cmp.id.to_string(),
+                &features,
+                &controls,
+                args.quantity,
+                &earlier,
+                supplier.as_deref(),
+            );
+            checks_added = plan.checks.len();
+            plan_notes = plan.notes;
+            receipt.checks = plan.checks;
+            receipt.sample_size = plan.sample_size;
+        }
+        yaml_content = serde_yml::to_string(&receipt).into_diagnostic()?;
+    }
+
+    let output_dir = project.root().join("manufacturing/receipts");
+    if !output_dir.exists() {
+        fs::create_dir_all(&output_dir).into_diagnostic()?;
+    }
+
+    let file_path = output_dir.join(format!("{}.tdt.yaml", id));
+    fs::write(&file_path, &yaml_content).into_diagnostic()?;
+
+    let short_id = short_ids.add(id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    // Handle --link flags
+    let _added_links = crate::cli::entity_cmd::process_link_flags(
+        &file_path,
+        EntityPrefix::Rcv,
+        &args.link,
+        &short_ids,
+    );
+
+    if !global.quiet {
+        let id_str = id.to_string();
+        let display_id = short_id.as_deref().unwrap_or(&id_str);
+        println!(
+            "{} Created receipt {}",
+            style("✓").green(),
+            style(display_id).cyan()
+        );
+        println!("  {}", file_path.display());
+        if checks_added > 0 {
+            println!(
+                "  {} inspection check(s) from the component's controls",
+                style(checks_added).cyan()
+            );
+        }
+    }
+
+    for note in &plan_notes {
+        eprintln!("{} {}", style("!").yellow(), note);
+    }
+
+    if let Some(warning) = supplier
+        .as_deref()
+        .and_then(|s| super::sup::approval_warning(&project, s))
+    {
+        eprintln!("{} {}", style("!").yellow(), warning);
+    }
+
+    if args.edit && !args.no_edit {
+        println!(
+            "Opening {} in {}...",
+            style(file_path.display()).cyan(),
+            style(config.editor()).yellow()
+        );
+        config.run_editor(&file_path).into_diagnostic()?;
+    }
+
+    Ok(())
+}
+
+/// Show receipt details
+fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, receipt) = load_receipt(&project, &short_ids, &args.id)?;
+
+    let format = match global.output {
+        OutputFormat::Auto => OutputFormat::Tsv,
+        f => f,
+    };
+
+    let short_id = short_ids
+        .get_short_id(&receipt.id.to_string())
+        .unwrap_or_default();
+    let display = |id: &EntityId| {
+        short_ids
+            .get_short_id(&id.to_string())
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&receipt).into_diagnostic()?;
+            println!("{}", json);
+        }
+        OutputFormat::Yaml => {
+            let content = fs::read_to_string(&path).into_diagnostic()?;
+            println!("{}", content);
+        }
+        OutputFormat::Csv => {
+            println!(
+                "id,title,supplier_lot,quantity,received,checks,receipt_status,author,created"
+            );
+            println!(
+                "{},{},{},{},{},{},{},{},{}",
+                escape_csv(&short_id),
+                escape_csv(&receipt.title),
+                escape_csv(receipt.supplier_lot.as_deref().unwrap_or("")),
+                receipt.quantity.map(|q| q.to_string()).unwrap_or_default(),
+                receipt
+                    .received_date
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                checks_label(&receipt),
+                receipt.receipt_status,
+                escape_csv(&receipt.author),
+                receipt.created.format("%Y-%m-%d")
+            );
+        }
+        OutputFormat::Tsv
+        | OutputFormat::Auto
+        | OutputFormat::Md
+        | OutputFormat::Table
+        | OutputFormat::Dot
+        | OutputFormat::Tree => {
+            println!("{}", style("Receipt").bold());
+            println!("{}", style("─".repeat(60)).dim());
+            println!("  {} {}", style("ID:").dim(), style(&short_id).cyan());
+            println!("  {} {}", style("Title:").dim(), receipt.title);
+            if let Some(ref sup) = receipt.links.supplier {
+                println!("  {} {}", style("Supplier:").dim(), display(sup));
+            }
+            if let Some(ref cmp) = receipt.links.component {
+                println!("  {} {}", style("Component:").dim(), display(cmp));
+            }
+            if let Some(ref lot) = receipt.supplier_lot {
+                println!("  {} {}", style("Supplier Lot:").dim(), lot);
+            }
+            if let Some(ref po) = receipt.po_number {
+                println!("  {} {}", style("PO:").dim(), po);
+            }
+            if let Some(qty) = receipt.quantity {
+                println!("  {} {}", style("Quantity:").dim(), qty);
+            }
+            if let Some(date) = receipt.received_date {
+                println!("  {} {}", style("Received:").dim(), date);
+            }
+            if let Some(n) = receipt.sample_size {
+                println!("  {} {}", style("Sample Size:").dim(), n);
+            }
+            let status = match receipt.receipt_status {
+                ReceiptStatus::Accepted => style(receipt.receipt_status.to_string()).green(),
+                ReceiptStatus::Rejected => style(receipt.receipt_status.to_string()).red(),
+                ReceiptStatus::Pending => style(receipt.receipt_status.to_string()).yellow(),
+            };
+            println!("  {} {}", style("Status:").dim(), status);
+            if let Some(ref inspector) = receipt.inspector {
+                let date = receipt
+                    .inspected_date
+                    .map(|d| format!(" on {}", d))
+                    .unwrap_or_default();
+                println!("  {} {}{}", style("Inspector:").dim(), inspector, date);
+            }
+            if let Some(ref reason) = receipt.rejection_reason {
+                println!("  {} {}", style("Rejected:").dim(), reason);
+            }
+            if let Some(ref ncr) = receipt.links.ncr {
+                println!("  {} {}", style("NCR:").dim(), display(ncr));
+            }
+
+            if !receipt.checks.is_empty() {
+                println!();
+                println!("{} ({})", style("Inspection").bold(), receipt.checks.len());
+                println!("{}", style("─".repeat(60)).dim());
+                for (i, check) in receipt.checks.iter().enumerate() {
+                    let result = match check.result {
+                        CheckResult::Pass => style(check.result.to_string()).green(),
+                        CheckResult::Fail => style(check.result.to_string()).red(),
+                        CheckResult::Pending => style(check.result.to_string()).dim(),
+                    };
+                    let control = check
+                        .control
+                        .as_ref()
+                        .map(|c| format!(" ({})", display(c)))
+                        .unwrap_or_default();
+                    println!(
+                        "  {}. {}{} {}",
+                        i + 1,
+                        check.characteristic,
+                        control,
+                        result
+                    );
+                    let mut detail = Vec::new();
+                    if let Some(ref spec) = check.specification {
+                        detail.push(format!("spec {}", spec));
+                    }
+                    if let Some(plan) = receiving::plan_label(check) {
+                        detail.push(format!("plan {}", plan));
+                    }
+                    if let Some(v) = check.measured {
+                        detail.push(format!("measured {}", v));
+                    }
+                    if let (Some(mean), Some(sd)) = (check.mean, check.std_dev) {
+                        detail.push(format!("mean {} s {}", mean, sd));
+                    }
+                    if let Some(d) = check.defects {
+                        detail.push(format!("{} defective", d));
+                    }
+                    if !detail.is_empty() {
+                        println!("     {}", style(detail.join(", ")).dim());
+                    }
+                    if let Some(ref notes) = check.notes {
+                        println!("     {}", notes);
+                    }
+                }
+            }
+
+            println!();
+            println!("{}", style("─".repeat(60)).dim());
+            println!(
+                "{}: {} | {}: {} | {}: {}",
+                style("Author").dim(),
+                receipt.author,
+                style("Created").dim(),
+                receipt.created.format("%Y-%m-%d %H:%M"),
+                style("Revision").dim(),
+                receipt.entity_revision
+            );
+        }
+        OutputFormat::Id => {
+            println!("{}", receipt.id);
+        }
+        OutputFormat::ShortId => {
+            println!("{}", short_id);
+        }
+        OutputFormat::Path => {
+            println!("{}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Edit a receipt
+fn run_edit(args: EditArgs) -> Result<()> {
+    crate::cli::entity_cmd::run_edit_generic(&args.id, &ENTITY_CONFIG)
+}
+
+/// Delete a receipt
+fn run_delete(args: DeleteArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, RECEIPT_DIRS, args.force, false, args.quiet)
+}
+
+/// Archive a receipt
+fn run_archive(args: ArchiveArgs) -> Result<()> {
+    crate::cli::commands::utils::run_delete(&args.id, RECEIPT_DIRS, args.force, true, args.quiet)
+}
+
+/// Record an inspection check result
+fn run_inspect(args: InspectArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, mut receipt) = load_receipt(&project, &short_ids, &args.id)?;
+    let short_id = short_ids
+        .get_short_id(&receipt.id.to_string())
+        .unwrap_or_else(|| receipt.id.to_string());
+
+    if receipt.receipt_status != ReceiptStatus::Pending {
+        return Err(miette::miette!(
+            "Receipt {} is already {}",
+            short_id,
+            receipt.receipt_status
+        ));
+    }
+
+    // A control ID (full or short) selects or adds a check for that control
+    let control_id = short_ids
+        .resolve(&args.check)
+        .unwrap_or_else(|| args.check.clone());
+    let control = if control_id.starts_with("CTRL-") {
+        let ctrl_dir = project.root().join("manufacturing/controls");
+        let (_, ctrl) = loader::load_entity::<Control>(&ctrl_dir, &control_id)?
+            .ok_or_else(|| miette::miette!("No control found matching '{}'", args.check))?;
+        Some(ctrl)
+    } else {
+        None
+    };
+
+    let index = match control {
+        Some(ref ctrl) => receipt
+            .checks
+            .iter()
+            .position(|c| c.control.as_ref() == Some(&ctrl.id)),
+        None => args
+            .check
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=receipt.checks.len()).contains(n))
+            .map(|n| n - 1)
+            .or_else(|| {
+                receipt
+                    .checks
+                    .iter()
+                    .position(|c| c.characteristic.eq_ignore_ascii_case(&args.check))
+            }),
+    };
+    let index = match index {
+        Some(i) => i,
+        None => {
+            let check = match control {
+                Some(ref ctrl) => ReceiptCheck {
+                    control: Some(ctrl.id.clone()),
+                    characteristic: if ctrl.characteristic.name.is_empty() {
+                        ctrl.title.clone()
+                    } else {
+                        ctrl.characteristic.name.clone()
+                    },
+                    specification: receiving::specification(&ctrl.characteristic),
+                    ..Default::default()
+                },
+                None => ReceiptCheck {
+                    characteristic: args.check.clone(),
+                    ..Default::default()
+                },
+            };
+            receipt.checks.push(check);
+            receipt.checks.len() - 1
+        }
+    };
+
+    // Limits come from the check's control, when it has one
+    let limits = match control {
+        Some(ctrl) => Some(ctrl.characteristic),
+        None => match receipt.checks[index].control {
+            Some(ref id) => {
+                let ctrl_dir = project.root().join("manufacturing/controls");
+                loader::load_entity::<Control>(&ctrl_dir, &id.to_string())?
+                    .map(|(_, c)| c.characteristic)
+            }
+            None => None,
+        },
+    };
+
+    let result = if args.pass {
+        CheckResult::Pass
+    } else if args.fail {
+        CheckResult::Fail
+    } else if let Some(value) = args.value {
+        limits
+            .as_ref()
+            .and_then(|ch| receiving::evaluate(ch, value))
+            .ok_or_else(|| {
+                miette::miette!(
+                    "Check '{}' has no limits to judge {} against; use --pass or --fail",
+                    receipt.checks[index].characteristic,
+                    value
+                )
+            })?
+    } else if let (Some(mean), Some(sd)) = (args.mean, args.std_dev) {
+        let check = &receipt.checks[index];
+        let k = check.k.ok_or_else(|| {
+            miette::miette!(
+                "Check '{}' has no variables sampling plan; use --value, --defects, --pass or --fail",
+                check.characteristic
+            )
+        })?;
+        let ch = limits
+            .as_ref()
+            .filter(|ch| ch.lower_limit.is_some() || ch.upper_limit.is_some())
+            .ok_or_else(|| {
+                miette::miette!(
+                    "Check '{}' has no limits to judge the sample against",
+                    check.characteristic
+                )
+            })?;
+        if sampling::variables_accepts(ch.lower_limit, ch.upper_limit, mean, sd, k) {
+            CheckResult::Pass
+        } else {
+            CheckResult::Fail
+        }
+    } else if let Some(defects) = args.defects {
+        receiving::judge_defects(&receipt.checks[index], defects)
+    } else {
+        return Err(miette::miette!(
+            "Specify the result with --pass, --fail, --value, --mean/--std-dev or --defects"
+        ));
+    };
+
+    let check = &mut receipt.checks[index];
+    check.result = result;
+    if args.value.is_some() {
+        check.measured = args.value;
+    }
+    if args.defects.is_some() {
+        check.defects = args.defects;
+    }
+    if args.mean.is_some() {
+        check.mean = args.mean;
+        check.std_dev = args.std_dev;
+    }
+    if args.notes.is_some() {
+        check.notes = args.notes;
+    }
+    let characteristic = check.characteristic.clone();
+    if args.sample_size.is_some() {
+        receipt.sample_size = args.sample_size;
+    }
+    receipt.entity_revision += 1;
+    save_receipt(&path, &receipt)?;
+
+    if !global.quiet {
+        let result_styled = match result {
+            CheckResult::Pass => style(result.to_string()).green(),
+            _ => style(result.to_string()).red(),
+        };
+        println!(
+            "{} {} on receipt {}: {}",
+            style("✓").green(),
+            characteristic,
+            style(&short_id).cyan(),
+            result_styled
+        );
+        let pending = receipt.pending_checks();
+        if result == CheckResult::Fail {
+            println!(
+                "  Reject with: tdt rcv reject {} --reason \"...\"",
+                short_id
+            );
+        } else if pending > 0 {
+            println!("  {} check(s) still pending", pending);
+        }
+    }
+
+    Ok(())
+}
+
+/// Accept a receipt
+fn run_accept(args: AcceptArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let short_ids = ShortIdIndex::load(&project);
+    let (path, mut receipt) = load_receipt(&project, &short_ids, &args.id)?;
+    let short_id = short_ids
+        .get_short_id(&receipt.id.to_string())
+        .unwrap_or_else(|| receipt.id.to_string());
+
+    if receipt.receipt_status != ReceiptStatus::Pending {
+        return Err(miette::miette!(
+            "Receipt {} is already {}",
+            short_id,
+            receipt.receipt_status
+        ));
+    }
+    let failed: Vec<&str> = receipt
+        .failed_checks()
+        .map(|c| c.characteristic.as_str())
+        .collect();
+    if !failed.is_empty() {
+        return Err(miette::miette!(
+            "Receipt {} failed inspection ({}); reject it with 'tdt rcv reject {} --reason ...'",
+            short_id,
+            failed.join(", "),
+            short_id
+        ));
+    }
+    let pending = receipt.pending_checks();
+    if pending > 0 && !args.force {
+        return Err(miette::miette!(
+            "Receipt {} has {} check(s) not yet inspected; record them with 'tdt rcv inspect' or use --force",
+            short_id,
+            pending
+        ));
+    }
+
+    receipt.receipt_status = ReceiptStatus::Accepted;
+    receipt.inspector = Some(config.author());
+    receipt.inspected_date = Some(chrono::Local::now().date_naive());
+    receipt.entity_revision += 1;
+    save_receipt(&path, &receipt)?;
+
+    if !global.quiet {
+        println!(
+            "{} Accepted receipt {}",
+            style("✓").green(),
+            style(&short_id).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Reject a receipt and open a supplier NCR
+fn run_reject(args: RejectArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let config = Config::load();
+    let mut short_ids = ShortIdIndex::load(&project);
+    let (path, mut receipt) = load_receipt(&project, &short_ids, &args.id)?;
+    let short_id = short_ids
+        .get_short_id(&receipt.id.to_string())
+        .unwrap_or_else(|| receipt.id.to_string());
+
+    if receipt.receipt_status == ReceiptStatus::Rejected {
+        return Err(miette::miette!("Receipt {} is already rejected", short_id));
+    }
+
+    let component = match receipt.links.component {
+        Some(ref id) => {
+            let cmp_dir = project.root().join("bom/components");
+            loader::load_entity::<Component>(&cmp_dir, &id.to_string())?.map(|(_, c)| c)
+        }
+        None => None,
+    };
+    let failed: Vec<&ReceiptCheck> = receipt.failed_checks().collect();
+
+    let category = args.category.map(NcrCategory::from).unwrap_or_else(|| {
+        if failed.iter().any(|c| c.measured.is_some()) {
+            NcrCategory::Dimensional
+        } else {
+            NcrCategory::Material
+        }
+    });
+    let part = component
+        .as_ref()
+        .map(|c| c.part_number.clone())
+        .unwrap_or_else(|| receipt.title.clone());
+    let ncr_title = match receipt.supplier_lot {
+        Some(ref lot) => format!("Rejected receipt: {} lot {}", part, lot),
+        None => format!("Rejected receipt: {}", part),
+    };
+
+    let mut ncr = Ncr::new(
+        ncr_title,
+        NcrType::Supplier,
+        args.severity.into(),
+        config.author(),
+    );
+    ncr.category = category;
+    let mut description = format!(
+        "Receiving inspection {} rejected: {}",
+        receipt.id, args.reason
+    );
+    for check in &failed {
+        description.push_str(&format!("\n- {} failed", check.characteristic));
+        if let Some(v) = check.measured {
+            description.push_str(&format!(" (measured {})", v));
+        }
+        if let Some(d) = check.defects {
+            description.push_str(&format!(" ({} defective)", d));
+        }
+    }
+    ncr.description = Some(description);
+    ncr.detection = Some(Detection {
+        found_at: DetectionStage::Incoming,
+        found_by: Some(config.author()),
+        found_date: Some(chrono::Local::now().date_naive()),
+        operation: Some("Receiving inspection".to_string()),
+    });
+    ncr.affected_items = Some(AffectedItems {
+        part_number: component.as_ref().map(|c| c.part_number.clone()),
+        lot_number: receipt.supplier_lot.clone(),
+        quantity_affected: receipt.quantity,
+        ..Default::default()
+    });
+    if let Some(check) = failed.first() {
+        ncr.defect = Some(Defect {
+            characteristic: Some(check.characteristic.clone()),
+            specification: check.specification.clone(),
+            actual: check.measured.map(|v| v.to_string()),
+            ..Default::default()
+        });
+        ncr.links.control = check.control.clone();
+    }
+    ncr.links.component = receipt.links.component.clone();
+    ncr.links.supplier = receipt.links.supplier.clone();
+    ncr.links.receipt = Some(receipt.id.clone());
+
+    let ncr_dir = project.root().join("manufacturing/ncrs");
+    fs::create_dir_all(&ncr_dir).into_diagnostic()?;
+    fs::write(
+        ncr_dir.join(format!("{}.tdt.yaml", ncr.id)),
+        serde_yml::to_string(&ncr).into_diagnostic()?,
+    )
+    .into_diagnostic()?;
+
+    receipt.receipt_status = ReceiptStatus::Rejected;
+    receipt.rejection_reason = Some(args.reason);
+    receipt.inspector = Some(config.author());
+    receipt.inspected_date = Some(chrono::Local::now().date_naive());
+    receipt.links.ncr = Some(ncr.id.clone());
+    receipt.entity_revision += 1;
+    save_receipt(&path, &receipt)?;
+
+    let ncr_short = short_ids
+        .add(ncr.id.to_string())
+        .unwrap_or_else(|| ncr.id.to_string());
+    super::utils::save_short_ids(&mut short_ids, &project);
+
+    match global.output {
+        OutputFormat::Json => {
+            let result = serde_json::json!({
+                "receipt": receipt.id.to_string(),
+                "ncr": ncr.id.to_string(),
+                "ncr_short_id": ncr_short,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result).unwrap_or_default()
+            );
+        }
+        OutputFormat::Id => println!("{}", ncr.id),
+        OutputFormat::ShortId => println!("{}", ncr_short),
+        _ => {
+            if !global.quiet {
+                println!(
+                    "{} Rejected receipt {}",
+                    style("✓").green(),
+                    style(&short_id).cyan()
+                );
+                println!("  Opened supplier NCR {}", style(&ncr_short).cyan());
+            }
+        }
+    }
+
+    Ok(())
+}
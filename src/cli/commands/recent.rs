@@ -45,6 +45,9 @@ pub enum EntityTypeFilter {
     Dev,
     Ncr,
     Capa,
+    Cmpl,
+    Aud,
+    Rcv,
     Quote,
     Sup,
 }
@@ -68,6 +71,9 @@ impl EntityTypeFilter {
             EntityTypeFilter::Dev => "DEV",
             EntityTypeFilter::Ncr => "NCR",
             EntityTypeFilter::Capa => "CAPA",
+            EntityTypeFilter::Cmpl => "CMPL",
+            EntityTypeFilter::Aud => "AUD",
+            EntityTypeFilter::Rcv => "RCV",
             EntityTypeFilter::Quote => "QUOT",
             EntityTypeFilter::Sup => "SUP",
         }
@@ -173,8 +179,8 @@ pub fn run(args: RecentArgs, global: &GlobalOpts) -> Result<()> {
                     "RISK" => style(&result.prefix).red(),
                     "TEST" | "RSLT" => style(&result.prefix).green(),
                     "CMP" | "ASM" => style(&result.prefix).yellow(),
-                    "NCR" | "CAPA" => style(&result.prefix).magenta(),
-                    "LOT" | "DEV" => style(&result.prefix).cyan(),
+                    "NCR" | "CAPA" | "CMPL" | "AUD" => style(&result.prefix).magenta(),
+                    "LOT" | "DEV" | "RCV" => style(&result.prefix).cyan(),
                     _ => style(&result.prefix).white(),
                 };
 
@@ -6,10 +6,12 @@ use std::path::PathBuf;
 use tabled::{builder::Builder, settings::Style};
 
 use crate::cli::GlobalOpts;
+use crate::core::currency::Converter;
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
+use crate::core::should_cost::{estimate_make_parts, ShouldCost};
 use crate::entities::component::Component;
-use crate::entities::quote::Quote;
+use crate::entities::quote::{Currency, Quote};
 
 use super::{
     load_all_assemblies, load_all_components, load_all_quotes, load_assembly, write_output,
@@ -31,6 +33,10 @@ pub struct BomArgs {
     /// Include mass rollup
     #[arg(long)]
     pub with_mass: bool,
+
+    /// Report costs in this currency instead of the configured reporting currency
+    #[arg(long)]
+    pub currency: Option<Currency>,
 }
 
 pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
@@ -59,6 +65,13 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
     let quotes = load_all_quotes(&project);
     let quote_map: HashMap<String, &Quote> = quotes.iter().map(|q| (q.id.to_string(), q)).collect();
 
+    // Should-cost of make parts (used when --with-cost)
+    let should_costs = if args.with_cost {
+        estimate_make_parts(&project, &components, None)
+    } else {
+        HashMap::new()
+    };
+
     // Generate indented BOM
     let mut output = String::new();
     output.push_str(&format!("# Bill of Materials: {}\n\n", assembly.title));
@@ -67,6 +80,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
 
     let mut total_cost = 0.0;
     let mut total_mass = 0.0;
+    let mut converter = Converter::from_config(args.currency);
 
     output.push_str("```\n");
 
@@ -76,6 +90,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
         component_map: &HashMap<String, &Component>,
         assembly_map: &HashMap<String, &crate::entities::assembly::Assembly>,
         quote_map: &HashMap<String, &Quote>,
+        should_costs: &HashMap<String, ShouldCost>,
         short_ids: &ShortIdIndex,
         bom: &[crate::entities::assembly::BomItem],
         indent: usize,
@@ -84,6 +99,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
         with_cost: bool,
         with_mass: bool,
         visited: &mut std::collections::HashSet<String>,
+        converter: &mut Converter,
     ) {
         let prefix = "│  ".repeat(indent);
         for (i, item) in bom.iter().enumerate() {
@@ -99,21 +115,24 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
             if let Some(cmp) = component_map.get(&item_id) {
                 let cost_str = if with_cost {
                     // Priority 1: Use selected quote if set
-                    let unit_price = if let Some(ref quote_id) = cmp.selected_quote {
-                        if let Some(quote) = quote_map.get(quote_id) {
-                            quote.price_for_qty(item.quantity).unwrap_or(0.0)
-                        } else {
-                            cmp.unit_cost.unwrap_or(0.0)
-                        }
-                    } else {
-                        // Priority 2: Fall back to unit_cost
-                        cmp.unit_cost.unwrap_or(0.0)
-                    };
+                    let unit_price =
+                        match cmp.selected_quote.as_ref().and_then(|id| quote_map.get(id)) {
+                            Some(quote) => converter.convert(
+                                quote.price_for_qty(item.quantity).unwrap_or(0.0),
+                                quote.currency,
+                            ),
+                            None => match should_costs.get(&item_id).filter(|e| e.is_estimated()) {
+                                // Priority 2: Should-cost of a make part
+                                Some(estimate) => converter.convert_unit_cost(estimate.total),
+                                // Priority 3: Fall back to unit_cost
+                                None => converter.convert_unit_cost(cmp.unit_cost.unwrap_or(0.0)),
+                            },
+                        };
 
                     if unit_price > 0.0 {
                         let line_cost = unit_price * item.quantity as f64;
                         *total_cost += line_cost;
-                        format!(" ${:.2}", line_cost)
+                        format!(" {}{:.2}", converter.target.symbol(), line_cost)
                     } else {
                         "".to_string()
                     }
@@ -154,6 +173,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
                         component_map,
                         assembly_map,
                         quote_map,
+                        should_costs,
                         short_ids,
                         &asm.bom,
                         indent + 1,
@@ -162,6 +182,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
                         with_cost,
                         with_mass,
                         visited,
+                        converter,
                     );
                     visited.remove(&item_id);
                 }
@@ -181,6 +202,7 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
         &component_map,
         &assembly_map,
         &quote_map,
+        &should_costs,
         &short_ids,
         &assembly.bom,
         0,
@@ -189,13 +211,22 @@ pub fn run(args: BomArgs, _global: &GlobalOpts) -> Result<()> {
         args.with_cost,
         args.with_mass,
         &mut visited,
+        &mut converter,
     );
 
     output.push_str("```\n");
 
     // Totals
     if args.with_cost {
-        output.push_str(&format!("\n**Total Cost:** ${:.2}\n", total_cost));
+        output.push_str(&format!(
+            "\n**Total Cost:** {}{:.2} ({})\n",
+            converter.target.symbol(),
+            total_cost,
+            converter.target
+        ));
+        for issue in converter.issues() {
+            output.push_str(&format!("\n> **Exchange rate:** {}\n", issue));
+        }
     }
     if args.with_mass {
         output.push_str(&format!("**Total Mass:** {:.3} kg\n", total_mass));
@@ -0,0 +1,286 @@
+//! Compliance report: material declarations rolled up through an assembly's BOM
+
+use chrono::Utc;
+use miette::{IntoDiagnostic, Result};
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::compliance::{
+    rollup, ComplianceLine, ComplianceReport, ComponentState, SVHC_THRESHOLD_PERCENT,
+};
+use crate::core::project::Project;
+use crate::core::shortid::ShortIdIndex;
+use crate::entities::component::Regulation;
+
+use super::{load_all_assemblies, load_all_components, load_assembly, write_output};
+
+#[derive(clap::Args, Debug)]
+pub struct ComplianceArgs {
+    /// Assembly to roll up (ASM-xxx or short ID like ASM@1)
+    pub assembly: String,
+
+    /// Regulation to check (repeatable; default: all)
+    #[arg(long = "regulation", short = 'r')]
+    pub regulations: Vec<Regulation>,
+
+    /// Warn about declarations expiring within this many days
+    #[arg(long, default_value = "90")]
+    pub expiring_days: i64,
+
+    /// Output to file instead of stdout
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
+pub fn run(args: ComplianceArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+
+    let resolved = short_ids
+        .resolve(&args.assembly)
+        .unwrap_or_else(|| args.assembly.clone());
+    let assembly = load_assembly(&project, &resolved)?;
+    let assemblies = load_all_assemblies(&project);
+    let components = load_all_components(&project);
+
+    let mut regulations = if args.regulations.is_empty() {
+        Regulation::ALL.to_vec()
+    } else {
+        args.regulations.clone()
+    };
+    regulations.sort();
+    regulations.dedup();
+
+    let report = rollup(
+        &assembly,
+        &assemblies,
+        &components,
+        &regulations,
+        Utc::now().date_naive(),
+    );
+
+    let output = match global.output {
+        OutputFormat::Json => serde_json::to_string_pretty(&report).into_diagnostic()? + "\n",
+        OutputFormat::Yaml => serde_yml::to_string(&report).into_diagnostic()?,
+        OutputFormat::Csv => render_csv(&report, &short_ids),
+        _ => render_markdown(&report, &assembly.title, args.expiring_days, &short_ids),
+    };
+
+    write_output(&output, args.file)?;
+    Ok(())
+}
+
+fn short(short_ids: &ShortIdIndex, id: &str) -> String {
+    short_ids
+        .get_short_id(id)
+        .unwrap_or_else(|| truncate_str(id, 12))
+}
+
+fn render_markdown(
+    report: &ComplianceReport,
+    assembly_title: &str,
+    expiring_days: i64,
+    short_ids: &ShortIdIndex,
+) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("# Compliance Report: {}\n\n", assembly_title));
+    output.push_str(&format!("As of: {}\n", report.as_of));
+    output.push_str(&format!(
+        "Components checked: {}\n\n",
+        report.components_checked
+    ));
+
+    // Summary by regulation
+    let mut table = Builder::default();
+    table.push_record([
+        "Regulation",
+        "Status",
+        "Compliant",
+        "Exempt",
+        "Non-Compliant",
+        "Expired",
+        "Undeclared",
+    ]);
+    for summary in &report.summaries {
+        table.push_record([
+            summary.regulation.label().to_string(),
+            summary.status.to_string(),
+            summary.compliant.to_string(),
+            summary.exempt.to_string(),
+            summary.non_compliant.to_string(),
+            summary.expired.to_string(),
+            summary.undeclared.to_string(),
+        ]);
+    }
+    output.push_str(&table.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    // Non-compliant, expired and undeclared components
+    let issues: Vec<&ComplianceLine> = report.issues().collect();
+    output.push_str("\n## Issues\n\n");
+    if issues.is_empty() {
+        output.push_str("*No non-compliant or undeclared components*\n");
+    } else {
+        let mut table = Builder::default();
+        table.push_record([
+            "Component",
+            "Part Number",
+            "Title",
+            "Qty",
+            "Used In",
+            "Regulation",
+            "State",
+        ]);
+        for line in &issues {
+            let mut state = line.state.to_string();
+            if let (ComponentState::Expired, Some(date)) = (line.state, line.expires) {
+                state.push_str(&format!(" ({})", date));
+            }
+            table.push_record([
+                short(short_ids, &line.component),
+                line.part_number.clone(),
+                truncate_str(&line.title, 25).to_string(),
+                line.quantity.to_string(),
+                line.used_in.join(", "),
+                line.regulation.label().to_string(),
+                state,
+            ]);
+        }
+        output.push_str(&table.build().with(Style::markdown()).to_string());
+        output.push('\n');
+    }
+
+    // Exemptions relied on
+    let exempt: Vec<&ComplianceLine> = report
+        .lines
+        .iter()
+        .filter(|l| l.state == ComponentState::Exempt)
+        .collect();
+    if !exempt.is_empty() {
+        output.push_str("\n## Exemptions\n\n");
+        for line in exempt {
+            output.push_str(&format!(
+                "- {} {} ({}): {}\n",
+                short(short_ids, &line.component),
+                line.part_number,
+                line.regulation.label(),
+                line.exemptions.join(", ")
+            ));
+        }
+    }
+
+    // Declared substances
+    let with_substances: Vec<&ComplianceLine> = report
+        .lines
+        .iter()
+        .filter(|l| !l.substances.is_empty())
+        .collect();
+    if !with_substances.is_empty() {
+        output.push_str("\n## Declared Substances\n\n");
+        let mut table = Builder::default();
+        table.push_record([
+            "Component",
+            "Part Number",
+            "Regulation",
+            "Substance",
+            "CAS",
+            "% w/w",
+        ]);
+        for line in with_substances {
+            for substance in &line.substances {
+                let mut percent = substance
+                    .percent
+                    .map(|p| p.to_string())
+                    .unwrap_or("-".to_string());
+                if line.regulation == Regulation::Reach
+                    && substance
+                        .percent
+                        .is_some_and(|p| p > SVHC_THRESHOLD_PERCENT)
+                {
+                    percent.push_str(" (> 0.1%)");
+                }
+                table.push_record([
+                    short(short_ids, &line.component),
+                    line.part_number.clone(),
+                    line.regulation.label().to_string(),
+                    substance.name.clone(),
+                    substance.cas.clone().unwrap_or("-".to_string()),
+                    percent,
+                ]);
+            }
+        }
+        output.push_str(&table.build().with(Style::markdown()).to_string());
+        output.push('\n');
+    }
+
+    // Declarations due for renewal
+    let expiring: Vec<&ComplianceLine> = report
+        .lines
+        .iter()
+        .filter(|l| !l.state.is_issue())
+        .filter(|l| l.days_to_expiry.is_some_and(|d| d <= expiring_days))
+        .collect();
+    if !expiring.is_empty() {
+        output.push_str(&format!(
+            "\n## Declarations Expiring Within {} Days\n\n",
+            expiring_days
+        ));
+        for line in expiring {
+            output.push_str(&format!(
+                "- {} {} ({}): expires {} ({} days)\n",
+                short(short_ids, &line.component),
+                line.part_number,
+                line.regulation.label(),
+                line.expires.map(|d| d.to_string()).unwrap_or_default(),
+                line.days_to_expiry.unwrap_or_default()
+            ));
+        }
+    }
+
+    output.push_str(&format!(
+        "\n*{}: {} issue(s) across {} regulation(s)*\n",
+        if report.is_compliant() {
+            "Compliant"
+        } else {
+            "Not compliant"
+        },
+        issues.len(),
+        report.summaries.len()
+    ));
+    output
+}
+
+fn render_csv(report: &ComplianceReport, short_ids: &ShortIdIndex) -> String {
+    let mut output = String::from(
+        "component,part_number,title,quantity,used_in,regulation,state,exemptions,substances,declared_by,expires,documents\n",
+    );
+    for line in &report.lines {
+        let substances = line
+            .substances
+            .iter()
+            .map(|s| match s.percent {
+                Some(p) => format!("{} {}%", s.name, p),
+                None => s.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            short(short_ids, &line.component),
+            escape_csv(&line.part_number),
+            escape_csv(&line.title),
+            line.quantity,
+            escape_csv(&line.used_in.join("; ")),
+            line.regulation,
+            line.state,
+            escape_csv(&line.exemptions.join("; ")),
+            escape_csv(&substances),
+            escape_csv(line.declared_by.as_deref().unwrap_or_default()),
+            line.expires.map(|d| d.to_string()).unwrap_or_default(),
+            escape_csv(&line.documents.join("; ")),
+        ));
+    }
+    output
+}
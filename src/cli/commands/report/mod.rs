@@ -1,8 +1,11 @@
 //! `tdt report` command - Generate engineering reports
 
 mod bom;
+mod compliance;
 mod fmea;
+mod obsolescence;
 mod open_issues;
+mod quality;
 mod rvm;
 mod test_status;
 mod tolerance;
@@ -27,8 +30,11 @@ use crate::entities::stackup::Stackup;
 use crate::entities::test::Test;
 
 pub use bom::BomArgs;
+pub use compliance::ComplianceArgs;
 pub use fmea::FmeaArgs;
+pub use obsolescence::ObsolescenceArgs;
 pub use open_issues::OpenIssuesArgs;
+pub use quality::QualityArgs;
 pub use rvm::RvmArgs;
 pub use test_status::TestStatusArgs;
 pub use tolerance::ToleranceArgs;
@@ -44,12 +50,21 @@ pub enum ReportCommands {
     /// BOM (Bill of Materials) with costs
     Bom(BomArgs),
 
+    /// End-of-life and single-source parts with approved alternates
+    Obsolescence(ObsolescenceArgs),
+
+    /// Material compliance (RoHS, REACH, Prop 65, conflict minerals) rolled up through a BOM
+    Compliance(ComplianceArgs),
+
     /// Test execution status summary
     TestStatus(TestStatusArgs),
 
     /// All open issues (NCRs, CAPAs, failed tests)
     OpenIssues(OpenIssuesArgs),
 
+    /// NCR quality analytics: Pareto, cost of poor quality, escapes, trend
+    Quality(QualityArgs),
+
     /// Tolerance analysis report (features, mates, stackups by component)
     #[clap(alias = "tol")]
     Tolerance(ToleranceArgs),
@@ -60,8 +75,11 @@ pub fn run(cmd: ReportCommands, global: &GlobalOpts) -> Result<()> {
         ReportCommands::Rvm(args) => rvm::run(args, global),
         ReportCommands::Fmea(args) => fmea::run(args, global),
         ReportCommands::Bom(args) => bom::run(args, global),
+        ReportCommands::Obsolescence(args) => obsolescence::run(args, global),
+        ReportCommands::Compliance(args) => compliance::run(args, global),
         ReportCommands::TestStatus(args) => test_status::run(args, global),
         ReportCommands::OpenIssues(args) => open_issues::run(args, global),
+        ReportCommands::Quality(args) => quality::run(args, global),
         ReportCommands::Tolerance(args) => tolerance::run(args, global),
     }
 }
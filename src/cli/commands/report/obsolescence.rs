@@ -0,0 +1,219 @@
+//! Obsolescence report: end-of-life and single-source parts with approved alternates
+
+use chrono::Utc;
+use miette::{IntoDiagnostic, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::obsolescence::{
+    analyze, bom_quantities, Alternate, ObsolescenceReport, PartRisk, RiskFlag,
+};
+use crate::core::project::Project;
+use crate::core::shortid::ShortIdIndex;
+use crate::entities::assembly::Assembly;
+
+use super::{load_all_assemblies, load_all_components, load_assembly, write_output};
+
+#[derive(clap::Args, Debug)]
+pub struct ObsolescenceArgs {
+    /// Assembly to walk (default: all components)
+    pub assembly: Option<String>,
+
+    /// Output to file instead of stdout
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
+pub fn run(args: ObsolescenceArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let components = load_all_components(&project);
+    let as_of = Utc::now().date_naive();
+
+    let (report, assembly_title) = match args.assembly {
+        Some(ref reference) => {
+            let resolved = short_ids
+                .resolve(reference)
+                .unwrap_or_else(|| reference.clone());
+            let assembly = load_assembly(&project, &resolved)?;
+            let assemblies = load_all_assemblies(&project);
+            let assembly_map: HashMap<String, &Assembly> =
+                assemblies.iter().map(|a| (a.id.to_string(), a)).collect();
+            let parts: BTreeMap<String, Option<u32>> = bom_quantities(&assembly, &assembly_map)
+                .into_iter()
+                .map(|(id, qty)| (id, Some(qty)))
+                .collect();
+            let report = analyze(&parts, &components, Some(assembly.id.to_string()), as_of);
+            (report, Some(assembly.title))
+        }
+        None => {
+            let parts: BTreeMap<String, Option<u32>> = components
+                .iter()
+                .map(|c| (c.id.to_string(), None))
+                .collect();
+            (analyze(&parts, &components, None, as_of), None)
+        }
+    };
+
+    let output = match global.output {
+        OutputFormat::Json => serde_json::to_string_pretty(&report).into_diagnostic()? + "\n",
+        OutputFormat::Yaml => serde_yml::to_string(&report).into_diagnostic()?,
+        OutputFormat::Csv => render_csv(&report, &short_ids),
+        _ => render_markdown(&report, assembly_title.as_deref(), &short_ids),
+    };
+
+    write_output(&output, args.file)?;
+    Ok(())
+}
+
+fn short(short_ids: &ShortIdIndex, id: &str) -> String {
+    short_ids
+        .get_short_id(id)
+        .unwrap_or_else(|| truncate_str(id, 12))
+}
+
+fn flags_str(part: &PartRisk) -> String {
+    part.flags
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn alternates_str(part: &PartRisk, short_ids: &ShortIdIndex) -> String {
+    if part.alternates.is_empty() {
+        return "-".to_string();
+    }
+    part.alternates
+        .iter()
+        .map(|a| match a {
+            Alternate::Mpn {
+                manufacturer, mpn, ..
+            } => format!("{} {}", manufacturer, mpn),
+            Alternate::Component {
+                id, part_number, ..
+            } => format!("{} {}", short(short_ids, id), part_number),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn lifecycle_str(part: &PartRisk) -> String {
+    let mut s = part.lifecycle.to_string();
+    if let Some(ref date) = part.last_time_buy {
+        s.push_str(&format!(" (LTB {})", date));
+    } else if let Some(ref date) = part.end_of_life {
+        s.push_str(&format!(" (EOL {})", date));
+    }
+    s
+}
+
+fn render_markdown(
+    report: &ObsolescenceReport,
+    assembly_title: Option<&str>,
+    short_ids: &ShortIdIndex,
+) -> String {
+    let mut output = String::new();
+    match assembly_title {
+        Some(title) => output.push_str(&format!("# Obsolescence Report: {}\n\n", title)),
+        None => output.push_str("# Obsolescence Report\n\n"),
+    }
+    output.push_str(&format!("As of: {}\n", report.as_of));
+    output.push_str(&format!("Parts checked: {}\n\n", report.parts_checked));
+
+    if report.parts.is_empty() {
+        output.push_str("*No end-of-life or single-source parts*\n");
+        return output;
+    }
+
+    let mut table = Builder::default();
+    table.push_record([
+        "Component",
+        "Part Number",
+        "Title",
+        "Qty",
+        "Lifecycle",
+        "Sources",
+        "Flags",
+        "Approved Alternates",
+    ]);
+    for part in &report.parts {
+        table.push_record([
+            short(short_ids, &part.component),
+            part.part_number.clone(),
+            truncate_str(&part.title, 25).to_string(),
+            part.quantity
+                .map(|q| q.to_string())
+                .unwrap_or("-".to_string()),
+            lifecycle_str(part),
+            part.approved_sources.to_string(),
+            flags_str(part),
+            alternates_str(part, short_ids),
+        ]);
+    }
+    output.push_str(&table.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    // Parts going away with nothing approved to move to
+    let stranded: Vec<&PartRisk> = report
+        .parts
+        .iter()
+        .filter(|p| p.lifecycle.is_end_of_life() && p.alternates.is_empty())
+        .collect();
+    if !stranded.is_empty() {
+        output.push_str("\n## End-of-Life Without Approved Alternates\n\n");
+        for part in stranded {
+            let due = match part.days_to_last_time_buy {
+                Some(days) if days < 0 => format!(" - last-time-buy passed {} days ago", -days),
+                Some(days) => format!(" - last-time-buy in {} days", days),
+                None => String::new(),
+            };
+            output.push_str(&format!(
+                "- {} {} ({}){}\n",
+                short(short_ids, &part.component),
+                part.part_number,
+                part.lifecycle,
+                due
+            ));
+        }
+    }
+
+    output.push_str(&format!(
+        "\n*{} flagged: {} obsolete, {} last-time-buy, {} NRND, {} single-source, {} without an approved source, {} with an end-of-life MPN*\n",
+        report.parts.len(),
+        report.count(RiskFlag::Obsolete),
+        report.count(RiskFlag::LastTimeBuy),
+        report.count(RiskFlag::Nrnd),
+        report.count(RiskFlag::SingleSource),
+        report.count(RiskFlag::NoApprovedSource),
+        report.count(RiskFlag::EndOfLifeMpn),
+    ));
+    output
+}
+
+fn render_csv(report: &ObsolescenceReport, short_ids: &ShortIdIndex) -> String {
+    let mut output = String::from(
+        "component,part_number,title,quantity,lifecycle,last_time_buy,end_of_life,approved_sources,flags,alternates\n",
+    );
+    for part in &report.parts {
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            short(short_ids, &part.component),
+            escape_csv(&part.part_number),
+            escape_csv(&part.title),
+            part.quantity.map(|q| q.to_string()).unwrap_or_default(),
+            part.lifecycle,
+            part.last_time_buy
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            part.end_of_life.map(|d| d.to_string()).unwrap_or_default(),
+            part.approved_sources,
+            escape_csv(&flags_str(part)),
+            escape_csv(&alternates_str(part, short_ids)),
+        ));
+    }
+    output
+}
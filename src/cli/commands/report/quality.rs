@@ -0,0 +1,363 @@
+//! Quality report: NCR Pareto charts, cost of poor quality, escape rate and trend
+
+use chrono::{NaiveDate, Utc};
+use clap::ValueEnum;
+use miette::{IntoDiagnostic, Result};
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::cli::helpers::{escape_csv, truncate_str};
+use crate::cli::{GlobalOpts, OutputFormat};
+use crate::core::loader;
+use crate::core::project::Project;
+use crate::core::quality_metrics::{
+    build_report, Pareto, ParetoDimension, ParetoMeasure, QualityOptions, QualityReport,
+};
+use crate::core::shortid::ShortIdIndex;
+use crate::entities::process::Process;
+use crate::entities::supplier::Supplier;
+
+use super::{load_all_components, load_all_ncrs, write_output};
+
+/// Width of the text bar charts
+const CHART_WIDTH: usize = 30;
+
+/// Pareto dimension selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QualityBy {
+    Category,
+    Component,
+    Supplier,
+    Process,
+    Detection,
+}
+
+impl From<QualityBy> for ParetoDimension {
+    fn from(by: QualityBy) -> Self {
+        match by {
+            QualityBy::Category => ParetoDimension::Category,
+            QualityBy::Component => ParetoDimension::Component,
+            QualityBy::Supplier => ParetoDimension::Supplier,
+            QualityBy::Process => ParetoDimension::Process,
+            QualityBy::Detection => ParetoDimension::DetectionStage,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct QualityArgs {
+    /// Output to file instead of stdout
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// Pareto dimensions to include (default: all)
+    #[arg(long, value_delimiter = ',')]
+    pub by: Vec<QualityBy>,
+
+    /// Rank Pareto bars by cost instead of NCR count
+    #[arg(long)]
+    pub by_cost: bool,
+
+    /// Only include NCRs reported on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub since: Option<NaiveDate>,
+
+    /// Number of months in the trend (default: 12)
+    #[arg(long, default_value = "12")]
+    pub months: u32,
+}
+
+pub fn run(args: QualityArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+
+    let ncrs = load_all_ncrs(&project);
+    let components = load_all_components(&project);
+    let suppliers: Vec<Supplier> = loader::load_all(&project.root().join("bom/suppliers"))?;
+    let processes: Vec<Process> =
+        loader::load_all(&project.root().join("manufacturing/processes"))?;
+
+    let dimensions: Vec<ParetoDimension> = if args.by.is_empty() {
+        ParetoDimension::ALL.to_vec()
+    } else {
+        args.by.iter().map(|b| (*b).into()).collect()
+    };
+    let options = QualityOptions {
+        as_of: Utc::now().date_naive(),
+        since: args.since,
+        dimensions,
+        measure: if args.by_cost {
+            ParetoMeasure::Cost
+        } else {
+            ParetoMeasure::Count
+        },
+        months: args.months.max(1),
+    };
+    let mut report = build_report(&ncrs, &options);
+
+    // Label entity-valued bars with short ID and title
+    for pareto in &mut report.paretos {
+        for bar in &mut pareto.bars {
+            let title = match pareto.dimension {
+                ParetoDimension::Component => components
+                    .iter()
+                    .find(|c| c.id.to_string() == bar.key)
+                    .map(|c| format!("{} {}", c.part_number, c.title)),
+                ParetoDimension::Supplier => suppliers
+                    .iter()
+                    .find(|s| s.id.to_string() == bar.key)
+                    .map(|s| s.name.clone()),
+                ParetoDimension::Process => processes
+                    .iter()
+                    .find(|p| p.id.to_string() == bar.key)
+                    .map(|p| p.title.clone()),
+                _ => None,
+            };
+            if let Some(title) = title {
+                let short = short_ids
+                    .get_short_id(&bar.key)
+                    .unwrap_or_else(|| truncate_str(&bar.key, 12));
+                bar.label = format!("{} {}", short, title);
+            }
+        }
+    }
+
+    let output = match global.output {
+        OutputFormat::Json => serde_json::to_string_pretty(&report).into_diagnostic()? + "\n",
+        OutputFormat::Yaml => serde_yml::to_string(&report).into_diagnostic()?,
+        OutputFormat::Csv => render_csv(&report),
+        _ => render_markdown(&report),
+    };
+
+    write_output(&output, args.file)?;
+    Ok(())
+}
+
+fn money(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+fn bar(value: f64, max: f64) -> String {
+    let len = if max > 0.0 {
+        ((value / max) * CHART_WIDTH as f64).round() as usize
+    } else {
+        0
+    };
+    "█".repeat(len)
+}
+
+fn dimension_title(dimension: ParetoDimension) -> &'static str {
+    match dimension {
+        ParetoDimension::Category => "Category",
+        ParetoDimension::Component => "Component",
+        ParetoDimension::Supplier => "Supplier",
+        ParetoDimension::Process => "Process",
+        ParetoDimension::DetectionStage => "Detection Stage",
+    }
+}
+
+fn render_pareto(output: &mut String, pareto: &Pareto) {
+    output.push_str(&format!(
+        "\n## Pareto by {}\n\n",
+        dimension_title(pareto.dimension)
+    ));
+    if pareto.bars.is_empty() {
+        output.push_str(&format!(
+            "*No NCRs with a {} ({} unassigned)*\n",
+            pareto.dimension, pareto.unassigned
+        ));
+        return;
+    }
+
+    let mut table = Builder::default();
+    table.push_record(["Item", "NCRs", "Cost", "%", "Cum. %"]);
+    for b in &pareto.bars {
+        table.push_record([
+            truncate_str(&b.label, 40).to_string(),
+            b.count.to_string(),
+            money(b.cost),
+            format!("{:.1}", b.percent),
+            format!("{:.1}", b.cumulative_percent),
+        ]);
+    }
+    output.push_str(&table.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    let value = |b: &crate::core::quality_metrics::ParetoBar| match pareto.measure {
+        ParetoMeasure::Count => b.count as f64,
+        ParetoMeasure::Cost => b.cost,
+    };
+    let max = pareto.bars.iter().map(value).fold(0.0, f64::max);
+    output.push_str("\n```\n");
+    for b in &pareto.bars {
+        output.push_str(&format!(
+            "{:<24} {:<width$} {:>5.1}%\n",
+            truncate_str(&b.label, 24),
+            bar(value(b), max),
+            b.cumulative_percent,
+            width = CHART_WIDTH
+        ));
+    }
+    output.push_str("```\n");
+    if pareto.unassigned > 0 {
+        output.push_str(&format!(
+            "\n*{} NCR(s) without a {}*\n",
+            pareto.unassigned, pareto.dimension
+        ));
+    }
+}
+
+fn render_markdown(report: &QualityReport) -> String {
+    let mut output = String::new();
+    output.push_str("# Quality Report\n\n");
+    output.push_str(&format!("*As of {}", report.as_of));
+    if let Some(since) = report.since {
+        output.push_str(&format!(", NCRs since {}", since));
+    }
+    output.push_str("*\n\n");
+
+    if report.currencies.len() > 1 {
+        output.push_str(&format!(
+            "> **Warning:** costs are recorded in multiple currencies ({}) and are summed without conversion.\n\n",
+            report.currencies.join(", ")
+        ));
+    }
+    let currency = report
+        .currencies
+        .first()
+        .map(|c| format!(" ({})", c))
+        .unwrap_or_default();
+
+    // Summary
+    output.push_str("## Summary\n\n");
+    let all_time = report.copq.iter().find(|w| w.days.is_none());
+    let mut summary = Builder::default();
+    summary.push_record(["Metric", "Value"]);
+    summary.push_record(["NCRs", &report.ncr_count.to_string()]);
+    summary.push_record([
+        "Escapes (customer/field)",
+        &report.escape_rate.escapes.to_string(),
+    ]);
+    summary.push_record([
+        "Escape Rate",
+        &format!("{:.1}%", report.escape_rate.rate * 100.0),
+    ]);
+    summary.push_record([
+        format!("Cost of Poor Quality{}", currency),
+        all_time.map(|w| money(w.total_cost)).unwrap_or_default(),
+    ]);
+    output.push_str(&summary.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    // COPQ windows
+    output.push_str(&format!("\n## Cost of Poor Quality{}\n\n", currency));
+    let mut copq = Builder::default();
+    copq.push_record(["Window", "NCRs", "Rework", "Scrap", "Total"]);
+    for w in &report.copq {
+        copq.push_record([
+            w.days
+                .map(|d| format!("Last {} days", d))
+                .unwrap_or_else(|| "All".to_string()),
+            w.ncr_count.to_string(),
+            money(w.rework_cost),
+            money(w.scrap_cost),
+            money(w.total_cost),
+        ]);
+    }
+    output.push_str(&copq.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    // Pareto charts
+    for pareto in &report.paretos {
+        render_pareto(&mut output, pareto);
+    }
+
+    // Monthly trend
+    output.push_str("\n## Monthly Trend\n\n");
+    let mut trend = Builder::default();
+    trend.push_record(["Month", "NCRs", "Escapes", "Rework", "Scrap", "Total"]);
+    for m in &report.monthly {
+        trend.push_record([
+            m.month.clone(),
+            m.ncr_count.to_string(),
+            m.escapes.to_string(),
+            money(m.rework_cost),
+            money(m.scrap_cost),
+            money(m.total_cost),
+        ]);
+    }
+    output.push_str(&trend.build().with(Style::markdown()).to_string());
+    output.push('\n');
+
+    let max = report
+        .monthly
+        .iter()
+        .map(|m| m.ncr_count)
+        .max()
+        .unwrap_or(0) as f64;
+    output.push_str("\n```\n");
+    for m in &report.monthly {
+        output.push_str(&format!(
+            "{} {:<width$} {}\n",
+            m.month,
+            bar(m.ncr_count as f64, max),
+            m.ncr_count,
+            width = CHART_WIDTH
+        ));
+    }
+    output.push_str("```\n");
+
+    output
+}
+
+fn render_csv(report: &QualityReport) -> String {
+    let mut output = String::from(
+        "section,key,label,ncrs,escapes,percent,cumulative_percent,rework_cost,scrap_cost,total_cost\n",
+    );
+    for w in &report.copq {
+        let key = w
+            .days
+            .map(|d| format!("{}d", d))
+            .unwrap_or_else(|| "all".to_string());
+        output.push_str(&format!(
+            "copq,{},,{},,,,{},{},{}\n",
+            key,
+            w.ncr_count,
+            money(w.rework_cost),
+            money(w.scrap_cost),
+            money(w.total_cost)
+        ));
+    }
+    output.push_str(&format!(
+        "escape_rate,,,{},{},{:.1},,,,\n",
+        report.escape_rate.total,
+        report.escape_rate.escapes,
+        report.escape_rate.rate * 100.0
+    ));
+    for pareto in &report.paretos {
+        for b in &pareto.bars {
+            output.push_str(&format!(
+                "pareto_{},{},{},{},,{:.1},{:.1},,,{}\n",
+                pareto.dimension,
+                escape_csv(&b.key),
+                escape_csv(&b.label),
+                b.count,
+                b.percent,
+                b.cumulative_percent,
+                money(b.cost)
+            ));
+        }
+    }
+    for m in &report.monthly {
+        output.push_str(&format!(
+            "month,{},,{},{},,,{},{},{}\n",
+            m.month,
+            m.ncr_count,
+            m.escapes,
+            money(m.rework_cost),
+            money(m.scrap_cost),
+            money(m.total_cost)
+        ));
+    }
+    output
+}
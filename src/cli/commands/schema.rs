@@ -26,6 +26,9 @@ const SCHEMAS: &[(&str, &str)] = &[
     ("dev", include_str!("../../../schemas/dev.schema.json")),
     ("ncr", include_str!("../../../schemas/ncr.schema.json")),
     ("capa", include_str!("../../../schemas/capa.schema.json")),
+    ("cmpl", include_str!("../../../schemas/cmpl.schema.json")),
+    ("aud", include_str!("../../../schemas/aud.schema.json")),
+    ("rcv", include_str!("../../../schemas/rcv.schema.json")),
     ("feat", include_str!("../../../schemas/feat.schema.json")),
     ("mate", include_str!("../../../schemas/mate.schema.json")),
     ("tol", include_str!("../../../schemas/tol.schema.json")),
@@ -6,15 +6,18 @@ use miette::{IntoDiagnostic, Result};
 use std::fs;
 
 use crate::cli::filters::StatusFilter;
+use crate::cli::helpers::{format_short_id, truncate_str};
 use crate::cli::table::{CellValue, ColumnDef, TableConfig, TableFormatter, TableRow};
 use crate::cli::{GlobalOpts, OutputFormat};
 use crate::core::cache::EntityCache;
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::core::loader;
 use crate::core::project::Project;
 use crate::core::shortid::ShortIdIndex;
+use crate::core::supplier_scorecard::{self, ScorecardConfig, SupplierScorecard};
 use crate::core::CachedSupplier;
 use crate::core::Config;
-use crate::entities::supplier::Supplier;
+use crate::entities::supplier::{ApprovalStatus, Supplier};
 use crate::schema::template::{TemplateContext, TemplateGenerator};
 use crate::schema::wizard::SchemaWizard;
 
@@ -37,6 +40,12 @@ pub enum SupCommands {
 
     /// Archive a supplier (soft delete)
     Archive(ArchiveArgs),
+
+    /// Score suppliers on quality, quote responsiveness, lead time and certifications
+    Scorecard(ScorecardArgs),
+
+    /// Set a supplier's approval status (approved, conditional, disqualified)
+    Approval(ApprovalArgs),
 }
 
 /// Capability filter
@@ -232,6 +241,51 @@ pub struct ArchiveArgs {
     pub quiet: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct ScorecardArgs {
+    /// Supplier ID or short ID (SUP@N); scores all suppliers if omitted
+    pub id: Option<String>,
+
+    /// Only count NCRs, quotes and lots from the last N days (overrides config)
+    #[arg(long)]
+    pub days: Option<u32>,
+
+    /// Only show suppliers whose suggested status differs from their current status
+    #[arg(long)]
+    pub changes: bool,
+}
+
+/// Approval status for CLI
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliApprovalStatus {
+    Approved,
+    Conditional,
+    Disqualified,
+}
+
+impl From<CliApprovalStatus> for ApprovalStatus {
+    fn from(status: CliApprovalStatus) -> Self {
+        match status {
+            CliApprovalStatus::Approved => ApprovalStatus::Approved,
+            CliApprovalStatus::Conditional => ApprovalStatus::Conditional,
+            CliApprovalStatus::Disqualified => ApprovalStatus::Disqualified,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ApprovalArgs {
+    /// Supplier ID or short ID (SUP@N)
+    pub id: String,
+
+    /// New approval status
+    pub status: CliApprovalStatus,
+
+    /// Conditions or reason (e.g., "100% incoming inspection until Q3")
+    #[arg(long, short = 'n')]
+    pub notes: Option<String>,
+}
+
 /// Directories where suppliers are stored
 const SUPPLIER_DIRS: &[&str] = &["bom/suppliers"];
 
@@ -252,6 +306,8 @@ pub fn run(cmd: SupCommands, global: &GlobalOpts) -> Result<()> {
         SupCommands::Edit(args) => run_edit(args),
         SupCommands::Delete(args) => run_delete(args),
         SupCommands::Archive(args) => run_archive(args),
+        SupCommands::Scorecard(args) => run_scorecard(args, global),
+        SupCommands::Approval(args) => run_approval(args, global),
     }
 }
 
@@ -619,6 +675,21 @@ fn run_show(args: ShowArgs, global: &GlobalOpts) -> Result<()> {
             );
             println!("{}: {}", style("Name").bold(), style(&sup.name).yellow());
             println!("{}: {}", style("Status").bold(), sup.status);
+            if let Some(approval) = sup.approval_status {
+                let styled = match approval {
+                    ApprovalStatus::Approved => style(approval.to_string()).green(),
+                    ApprovalStatus::Conditional => style(approval.to_string()).yellow(),
+                    ApprovalStatus::Disqualified => style(approval.to_string()).red(),
+                };
+                print!("{}: {}", style("Approval").bold(), styled);
+                if let Some(date) = sup.approval_date {
+                    print!(" ({})", date);
+                }
+                println!();
+                if let Some(ref notes) = sup.approval_notes {
+                    println!("  {}", style(notes).dim());
+                }
+            }
             println!("{}", style("─".repeat(60)).dim());
 
             // Contact Info
@@ -725,3 +796,271 @@ fn run_delete(args: DeleteArgs) -> Result<()> {
 fn run_archive(args: ArchiveArgs) -> Result<()> {
     crate::cli::commands::utils::run_delete(&args.id, SUPPLIER_DIRS, args.force, true, args.quiet)
 }
+
+/// Warning for selecting a supplier that is not approved, if any
+pub(crate) fn approval_warning(project: &Project, supplier_id: &str) -> Option<String> {
+    let sup_dir = project.root().join("bom/suppliers");
+    let (_, sup) = loader::load_entity::<Supplier>(&sup_dir, supplier_id).ok()??;
+    sup.approval_warning()
+}
+
+fn run_approval(args: ApprovalArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let resolved_id = short_ids
+        .resolve(&args.id)
+        .unwrap_or_else(|| args.id.clone());
+
+    let sup_dir = project.root().join("bom/suppliers");
+    let (path, mut sup) = loader::load_entity::<Supplier>(&sup_dir, &resolved_id)?
+        .ok_or_else(|| miette::miette!("No supplier found matching '{}'", args.id))?;
+
+    let status: ApprovalStatus = args.status.into();
+    sup.approval_status = Some(status);
+    sup.approval_date = Some(chrono::Utc::now().date_naive());
+    if args.notes.is_some() {
+        sup.approval_notes = args.notes;
+    }
+
+    let yaml = serde_yml::to_string(&sup).into_diagnostic()?;
+    fs::write(&path, yaml).into_diagnostic()?;
+
+    match global.output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&sup).into_diagnostic()?);
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&sup).into_diagnostic()?);
+        }
+        _ => {
+            let display_id = short_ids
+                .get_short_id(&sup.id.to_string())
+                .unwrap_or_else(|| format_short_id(&sup.id));
+            println!(
+                "{} {} ({}) is now {}",
+                style("✓").green(),
+                style(&display_id).cyan(),
+                sup.name,
+                style(status).yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Scorecards for the given suppliers, from project NCRs, quotes and lots
+pub(crate) fn score_suppliers(
+    project: &Project,
+    suppliers: &[Supplier],
+    config: &ScorecardConfig,
+) -> Result<Vec<SupplierScorecard>> {
+    let ncrs = crate::cli::commands::report::load_all_ncrs(project);
+    let quotes = crate::cli::commands::report::load_all_quotes(project);
+    let components = crate::cli::commands::report::load_all_components(project);
+    let lots: Vec<crate::entities::lot::Lot> =
+        loader::load_all(&project.root().join("manufacturing/lots"))?;
+
+    let as_of = chrono::Utc::now().date_naive();
+    let since = config
+        .window_days
+        .map(|d| as_of - chrono::Duration::days(d as i64));
+    let single_source = supplier_scorecard::single_source_map(&components);
+    let received = supplier_scorecard::units_received(&lots, &single_source, since);
+
+    Ok(suppliers
+        .iter()
+        .map(|s| {
+            supplier_scorecard::score_supplier(
+                s,
+                &ncrs,
+                &quotes,
+                received.get(&s.id.to_string()).copied().unwrap_or(0),
+                &single_source,
+                config,
+                as_of,
+            )
+        })
+        .collect())
+}
+
+fn run_scorecard(args: ScorecardArgs, global: &GlobalOpts) -> Result<()> {
+    let project = Project::discover().map_err(|e| miette::miette!("{}", e))?;
+    let short_ids = ShortIdIndex::load(&project);
+    let root = project.root();
+
+    let mut config: ScorecardConfig = Config::load().supplier_scorecard.unwrap_or_default();
+    if let Some(days) = args.days {
+        config.window_days = Some(days);
+    }
+
+    let mut suppliers: Vec<Supplier> = loader::load_all(&root.join("bom/suppliers"))?;
+    if let Some(ref id) = args.id {
+        let resolved = short_ids.resolve(id).unwrap_or_else(|| id.clone());
+        suppliers.retain(|s| s.id.to_string().starts_with(&resolved));
+        if suppliers.is_empty() {
+            return Err(miette::miette!("No supplier found matching '{}'", id));
+        }
+    }
+
+    let mut cards = score_suppliers(&project, &suppliers, &config)?;
+    if args.changes {
+        cards.retain(|c| c.approval_status != Some(c.suggested_status));
+    }
+    cards.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let pct = |v: Option<f64>| v.map(|v| format!("{:.0}", v)).unwrap_or_else(|| "-".into());
+    match global.output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&cards).into_diagnostic()?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yml::to_string(&cards).into_diagnostic()?);
+        }
+        OutputFormat::Csv => {
+            println!("supplier,name,approval_status,suggested_status,ncrs,ppm,quality_score,response_rate,avg_response_days,responsiveness_score,avg_lead_time_days,lead_time_score,certs_valid,certs_total,certification_score,score");
+            for c in &cards {
+                println!(
+                    "{},{},{},{},{},{},{:.1},{},{},{},{},{},{},{},{:.1},{:.1}",
+                    c.supplier,
+                    crate::cli::helpers::escape_csv(&c.name),
+                    c.approval_status.map(|s| s.to_string()).unwrap_or_default(),
+                    c.suggested_status,
+                    c.quality.ncrs,
+                    c.quality
+                        .ppm
+                        .map(|p| format!("{:.0}", p))
+                        .unwrap_or_default(),
+                    c.quality.score,
+                    c.responsiveness
+                        .response_rate
+                        .map(|r| format!("{:.2}", r))
+                        .unwrap_or_default(),
+                    c.responsiveness
+                        .avg_response_days
+                        .map(|d| format!("{:.1}", d))
+                        .unwrap_or_default(),
+                    c.responsiveness
+                        .score
+                        .map(|s| format!("{:.1}", s))
+                        .unwrap_or_default(),
+                    c.lead_time
+                        .avg_lead_time_days
+                        .map(|d| format!("{:.1}", d))
+                        .unwrap_or_default(),
+                    c.lead_time
+                        .score
+                        .map(|s| format!("{:.1}", s))
+                        .unwrap_or_default(),
+                    c.certification.valid,
+                    c.certification.total,
+                    c.certification.score,
+                    c.score
+                );
+            }
+        }
+        OutputFormat::Id | OutputFormat::ShortId => {
+            for c in &cards {
+                if global.output == OutputFormat::ShortId {
+                    println!(
+                        "{}",
+                        short_ids.get_short_id(&c.supplier).unwrap_or_default()
+                    );
+                } else {
+                    println!("{}", c.supplier);
+                }
+            }
+        }
+        _ => {
+            if cards.is_empty() {
+                println!("No suppliers to score.");
+                return Ok(());
+            }
+            println!(
+                "{:<8} {:<20} {:<13} {:<13} {:>5} {:>7} {:>5} {:>5} {:>5} {:>5} {:>6}",
+                style("SHORT").bold().dim(),
+                style("NAME").bold(),
+                style("APPROVAL").bold(),
+                style("SUGGESTED").bold(),
+                style("NCRS").bold(),
+                style("PPM").bold(),
+                style("QUAL").bold(),
+                style("RESP").bold(),
+                style("LEAD").bold(),
+                style("CERT").bold(),
+                style("SCORE").bold()
+            );
+            println!("{}", "-".repeat(100));
+            for c in &cards {
+                let short = short_ids
+                    .get_short_id(&c.supplier)
+                    .unwrap_or_else(|| truncate_str(&c.supplier, 8));
+                let approval = c
+                    .approval_status
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "-".into());
+                let suggested = if c.approval_status == Some(c.suggested_status) {
+                    style(c.suggested_status.to_string()).dim()
+                } else {
+                    match c.suggested_status {
+                        ApprovalStatus::Approved => style(c.suggested_status.to_string()).green(),
+                        ApprovalStatus::Conditional => {
+                            style(c.suggested_status.to_string()).yellow()
+                        }
+                        ApprovalStatus::Disqualified => style(c.suggested_status.to_string()).red(),
+                    }
+                };
+                println!(
+                    "{:<8} {:<20} {:<13} {:<13} {:>5} {:>7} {:>5} {:>5} {:>5} {:>5} {:>6}",
+                    style(short).cyan(),
+                    truncate_str(&c.name, 20),
+                    approval,
+                    suggested,
+                    c.quality.ncrs,
+                    c.quality
+                        .ppm
+                        .map(|p| format!("{:.0}", p))
+                        .unwrap_or_else(|| "-".into()),
+                    pct(Some(c.quality.score)),
+                    pct(c.responsiveness.score),
+                    pct(c.lead_time.score),
+                    pct(Some(c.certification.score)),
+                    style(format!("{:.1}", c.score)).bold()
+                );
+            }
+
+            let flagged: Vec<&SupplierScorecard> =
+                cards.iter().filter(|c| !c.flags.is_empty()).collect();
+            if !flagged.is_empty() {
+                println!();
+                for c in flagged {
+                    let short = short_ids
+                        .get_short_id(&c.supplier)
+                        .unwrap_or_else(|| truncate_str(&c.supplier, 8));
+                    println!(
+                        "{} {}: {}",
+                        style("!").yellow(),
+                        style(short).cyan(),
+                        c.flags.join(", ")
+                    );
+                }
+            }
+
+            println!();
+            println!(
+                "{} supplier(s) scored{}. Set status with: {}",
+                style(cards.len()).cyan(),
+                config
+                    .window_days
+                    .map(|d| format!(" over the last {} days", d))
+                    .unwrap_or_default(),
+                style("tdt sup approval SUP@N <status>").yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -216,7 +216,7 @@ impl RejectArgs {
             let prefix = parts[0].to_uppercase();
             let valid_prefixes = [
                 "REQ", "RISK", "TEST", "RSLT", "CMP", "ASM", "FEAT", "MATE", "TOL", "PROC", "CTRL",
-                "WORK", "LOT", "DEV", "NCR", "CAPA", "QUOT", "SUP",
+                "WORK", "LOT", "DEV", "NCR", "CAPA", "CMPL", "AUD", "RCV", "QUOT", "SUP",
             ];
             if valid_prefixes.contains(&prefix.as_str()) {
                 // Return as PREFIX@shortid format for resolution
@@ -0,0 +1,157 @@
+//! Audit schedule: upcoming and overdue audits
+//!
+//! Planned and in-progress audits are scheduled on their `scheduled_date`.
+//! A completed audit with an `interval_months` also schedules the next audit
+//! of its scope, until an open audit with the same title is planned.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::entities::audit::{Audit, AuditStatus, AuditType};
+
+/// Where an audit stands relative to its due date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleState {
+    /// Past the due date and not started
+    Overdue,
+    /// Under way
+    InProgress,
+    /// Due within the horizon
+    Upcoming,
+}
+
+impl std::fmt::Display for ScheduleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleState::Overdue => write!(f, "overdue"),
+            ScheduleState::InProgress => write!(f, "in progress"),
+            ScheduleState::Upcoming => write!(f, "upcoming"),
+        }
+    }
+}
+
+/// One row of the audit schedule
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleEntry {
+    pub audit: String,
+    pub title: String,
+    pub audit_type: AuditType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard: Option<String>,
+    pub due: NaiveDate,
+    /// Days from the as-of date to the due date (negative when overdue)
+    pub days: i64,
+    pub state: ScheduleState,
+    /// The next recurrence of a completed audit, not yet planned
+    pub recurrence: bool,
+}
+
+/// Build the audit schedule as of a date
+///
+/// Audits due more than `horizon_days` ahead are left out (`None` keeps all).
+/// Entries are ordered overdue first, then by due date.
+pub fn schedule(
+    audits: &[Audit],
+    as_of: NaiveDate,
+    horizon_days: Option<i64>,
+) -> Vec<ScheduleEntry> {
+    let open_titles: BTreeSet<String> = audits
+        .iter()
+        .filter(|a| a.is_open())
+        .map(|a| a.title.to_lowercase())
+        .collect();
+
+    let mut entries: Vec<ScheduleEntry> = audits
+        .iter()
+        .filter_map(|audit| {
+            let (due, recurrence) = match audit.audit_status {
+                AuditStatus::Planned | AuditStatus::InProgress => (audit.scheduled_date?, false),
+                AuditStatus::Completed if !open_titles.contains(&audit.title.to_lowercase()) => {
+                    (audit.next_due()?, true)
+                }
+                _ => return None,
+            };
+            let days = (due - as_of).num_days();
+            let state = if audit.audit_status == AuditStatus::InProgress {
+                ScheduleState::InProgress
+            } else if days < 0 {
+                ScheduleState::Overdue
+            } else {
+                ScheduleState::Upcoming
+            };
+            if state == ScheduleState::Upcoming && horizon_days.is_some_and(|h| days > h) {
+                return None;
+            }
+            Some(ScheduleEntry {
+                audit: audit.id.to_string(),
+                title: audit.title.clone(),
+                audit_type: audit.audit_type,
+                standard: audit.standard.clone(),
+                due,
+                days,
+                state,
+                recurrence,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.state.cmp(&b.state).then(a.due.cmp(&b.due)));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit(title: &str, status: AuditStatus, scheduled: &str) -> Audit {
+        let mut audit = Audit::new(title.to_string(), AuditType::Internal, "test".to_string());
+        audit.audit_status = status;
+        audit.scheduled_date = Some(scheduled.parse().unwrap());
+        audit
+    }
+
+    #[test]
+    fn test_schedule_states_and_horizon() {
+        let as_of: NaiveDate = "2024-06-01".parse().unwrap();
+        let audits = vec![
+            audit("Purchasing", AuditStatus::Planned, "2024-05-20"),
+            audit("Design controls", AuditStatus::Planned, "2024-06-15"),
+            audit("Production", AuditStatus::Planned, "2024-12-01"),
+            audit("CAPA process", AuditStatus::InProgress, "2024-05-30"),
+            audit("Old", AuditStatus::Cancelled, "2024-01-01"),
+        ];
+
+        let entries = schedule(&audits, as_of, Some(90));
+        let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Purchasing", "CAPA process", "Design controls"]
+        );
+        assert_eq!(entries[0].state, ScheduleState::Overdue);
+        assert_eq!(entries[0].days, -12);
+
+        assert_eq!(schedule(&audits, as_of, None).len(), 4);
+    }
+
+    #[test]
+    fn test_recurrence_until_next_audit_planned() {
+        let as_of: NaiveDate = "2024-06-01".parse().unwrap();
+        let mut done = audit("Purchasing", AuditStatus::Completed, "2023-05-01");
+        done.completed_date = Some("2023-05-10".parse().unwrap());
+        done.interval_months = Some(12);
+
+        let entries = schedule(&[done.clone()], as_of, Some(90));
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].recurrence);
+        assert_eq!(entries[0].due, "2024-05-10".parse::<NaiveDate>().unwrap());
+        assert_eq!(entries[0].state, ScheduleState::Overdue);
+
+        // Once the next audit is planned, it replaces the recurrence
+        let next = audit("Purchasing", AuditStatus::Planned, "2024-06-20");
+        let entries = schedule(&[done, next], as_of, Some(90));
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].recurrence);
+    }
+}
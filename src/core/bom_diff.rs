@@ -0,0 +1,617 @@
+//! BOM comparison between assemblies, revisions and baselines
+//!
+//! An assembly's BOM is exploded against a snapshot of assemblies, components
+//! and quotes, either multi-level (one line per BOM occurrence, keyed by its
+//! parent path) or flattened (one line per component, quantities rolled up
+//! per top-level unit). Two exploded BOMs are then compared line by line.
+//! Cost and mass come from component lines only, so sub-assembly lines never
+//! count twice.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::entities::assembly::Assembly;
+use crate::entities::component::Component;
+use crate::entities::quote::Quote;
+
+/// Assemblies, components and quotes as of one point in time
+#[derive(Debug, Default)]
+pub struct BomSnapshot {
+    pub assemblies: HashMap<String, Assembly>,
+    pub components: HashMap<String, Component>,
+    pub quotes: HashMap<String, Quote>,
+}
+
+impl BomSnapshot {
+    pub fn new(assemblies: Vec<Assembly>, components: Vec<Component>, quotes: Vec<Quote>) -> Self {
+        Self {
+            assemblies: assemblies
+                .into_iter()
+                .map(|a| (a.id.to_string(), a))
+                .collect(),
+            components: components
+                .into_iter()
+                .map(|c| (c.id.to_string(), c))
+                .collect(),
+            quotes: quotes.into_iter().map(|q| (q.id.to_string(), q)).collect(),
+        }
+    }
+
+    /// Find an assembly by full ID or unique ID prefix
+    pub fn assembly(&self, id: &str) -> Option<&Assembly> {
+        if let Some(asm) = self.assemblies.get(id) {
+            return Some(asm);
+        }
+        let mut matches = self
+            .assemblies
+            .values()
+            .filter(|a| a.id.to_string().starts_with(id));
+        match (matches.next(), matches.next()) {
+            (Some(asm), None) => Some(asm),
+            _ => None,
+        }
+    }
+
+    /// Unit price of a component: selected quote at the quantity, else unit cost
+    fn unit_price(&self, cmp: &Component, qty: u32) -> Option<f64> {
+        cmp.selected_quote
+            .as_ref()
+            .and_then(|q| self.quotes.get(q))
+            .and_then(|q| q.price_for_qty(qty))
+            .or(cmp.unit_cost)
+    }
+}
+
+/// BOM view to compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BomView {
+    /// One line per occurrence, nested under its sub-assemblies
+    Indented,
+    /// One line per component, quantities summed across sub-assemblies
+    Flat,
+}
+
+/// One line of an exploded BOM
+#[derive(Debug, Clone, Serialize)]
+pub struct BomLine {
+    /// Sub-assembly IDs between the top-level assembly and this line
+    pub path: Vec<String>,
+    pub id: String,
+    pub part_number: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    pub title: String,
+    pub is_assembly: bool,
+    /// Quantity per parent (per top-level unit in the flat view)
+    pub quantity: u32,
+    /// Quantity per top-level unit
+    pub extended_quantity: u32,
+    pub reference_designators: Vec<String>,
+    /// Component unit price (None for sub-assemblies and unpriced parts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_cost: Option<f64>,
+    /// Component unit mass in kg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_mass: Option<f64>,
+    /// Components this one replaces
+    #[serde(skip)]
+    replaces: Vec<String>,
+}
+
+impl BomLine {
+    /// Cost per top-level unit
+    pub fn extended_cost(&self) -> f64 {
+        self.unit_cost.unwrap_or(0.0) * self.extended_quantity as f64
+    }
+
+    /// Mass per top-level unit in kg
+    pub fn extended_mass(&self) -> f64 {
+        self.unit_mass.unwrap_or(0.0) * self.extended_quantity as f64
+    }
+
+    fn key(&self) -> (Vec<String>, String) {
+        (self.path.clone(), self.id.clone())
+    }
+}
+
+/// Explode an assembly's BOM
+///
+/// Sub-assemblies that would repeat one of their parents are listed but not
+/// expanded. Items found in neither assemblies nor components are kept with
+/// an empty part number so that they still show up in a comparison.
+pub fn explode(snapshot: &BomSnapshot, assembly: &Assembly, view: BomView) -> Vec<BomLine> {
+    let mut lines = Vec::new();
+    let mut visiting = HashSet::from([assembly.id.to_string()]);
+    explode_into(
+        snapshot,
+        assembly,
+        &mut Vec::new(),
+        1,
+        &mut visiting,
+        &mut lines,
+    );
+
+    match view {
+        BomView::Indented => lines,
+        BomView::Flat => {
+            let mut flat: Vec<BomLine> = Vec::new();
+            for line in lines.into_iter().filter(|l| !l.is_assembly) {
+                match flat.iter_mut().find(|f| f.id == line.id) {
+                    Some(existing) => {
+                        existing.extended_quantity += line.extended_quantity;
+                        existing.quantity = existing.extended_quantity;
+                        for r in line.reference_designators {
+                            if !existing.reference_designators.contains(&r) {
+                                existing.reference_designators.push(r);
+                            }
+                        }
+                    }
+                    None => flat.push(BomLine {
+                        path: Vec::new(),
+                        quantity: line.extended_quantity,
+                        ..line
+                    }),
+                }
+            }
+            // Price breaks apply to the rolled-up quantity
+            for line in &mut flat {
+                if let Some(cmp) = snapshot.components.get(&line.id) {
+                    line.unit_cost = snapshot.unit_price(cmp, line.extended_quantity);
+                }
+            }
+            flat
+        }
+    }
+}
+
+fn explode_into(
+    snapshot: &BomSnapshot,
+    assembly: &Assembly,
+    path: &mut Vec<String>,
+    multiplier: u32,
+    visiting: &mut HashSet<String>,
+    lines: &mut Vec<BomLine>,
+) {
+    for item in &assembly.bom {
+        let extended = item.quantity * multiplier;
+        let mut line = BomLine {
+            path: path.clone(),
+            id: item.component_id.clone(),
+            part_number: String::new(),
+            revision: None,
+            title: "(not found)".to_string(),
+            is_assembly: false,
+            quantity: item.quantity,
+            extended_quantity: extended,
+            reference_designators: item.reference_designators.clone(),
+            unit_cost: None,
+            unit_mass: None,
+            replaces: Vec::new(),
+        };
+
+        if let Some(cmp) = snapshot.components.get(&item.component_id) {
+            line.part_number = cmp.part_number.clone();
+            line.revision = cmp.revision.clone().filter(|r| !r.is_empty());
+            line.title = cmp.title.clone();
+            line.unit_cost = snapshot.unit_price(cmp, extended);
+            line.unit_mass = cmp.mass_kg;
+            line.replaces = cmp.links.replaces.iter().map(|r| r.to_string()).collect();
+            lines.push(line);
+        } else if let Some(sub) = snapshot.assemblies.get(&item.component_id) {
+            line.part_number = sub.part_number.clone();
+            line.revision = sub.revision.clone().filter(|r| !r.is_empty());
+            line.title = sub.title.clone();
+            line.is_assembly = true;
+            lines.push(line);
+            if visiting.insert(item.component_id.clone()) {
+                path.push(item.component_id.clone());
+                explode_into(snapshot, sub, path, extended, visiting, lines);
+                path.pop();
+                visiting.remove(&item.component_id);
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+}
+
+/// Kind of difference on a BOM line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// A new component that lists the removed one in `links.replaces`
+    Replaced,
+    Changed,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Replaced => write!(f, "replaced"),
+            ChangeKind::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+/// One difference between two BOMs
+#[derive(Debug, Clone, Serialize)]
+pub struct BomChange {
+    pub kind: ChangeKind,
+    pub path: Vec<String>,
+    /// Item in the new BOM (the old one for removals)
+    pub id: String,
+    /// Item it replaces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
+    pub part_number: String,
+    pub title: String,
+    pub is_assembly: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_quantity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_quantity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_revision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_revision: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub refs_added: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub refs_removed: Vec<String>,
+    /// Change in cost per top-level unit
+    pub cost_delta: f64,
+    /// Change in mass per top-level unit, kg
+    pub mass_delta: f64,
+}
+
+impl BomChange {
+    fn new(kind: ChangeKind, old: Option<&BomLine>, new: Option<&BomLine>) -> Self {
+        let line = new.or(old).expect("a change has at least one side");
+        let old_refs: BTreeSet<&String> = old
+            .map(|l| l.reference_designators.iter().collect())
+            .unwrap_or_default();
+        let new_refs: BTreeSet<&String> = new
+            .map(|l| l.reference_designators.iter().collect())
+            .unwrap_or_default();
+        Self {
+            kind,
+            path: line.path.clone(),
+            id: line.id.clone(),
+            replaces: None,
+            part_number: line.part_number.clone(),
+            title: line.title.clone(),
+            is_assembly: line.is_assembly,
+            old_quantity: old.map(|l| l.quantity),
+            new_quantity: new.map(|l| l.quantity),
+            old_revision: old.and_then(|l| l.revision.clone()),
+            new_revision: new.and_then(|l| l.revision.clone()),
+            refs_added: new_refs
+                .difference(&old_refs)
+                .map(|r| r.to_string())
+                .collect(),
+            refs_removed: old_refs
+                .difference(&new_refs)
+                .map(|r| r.to_string())
+                .collect(),
+            cost_delta: new.map_or(0.0, BomLine::extended_cost)
+                - old.map_or(0.0, BomLine::extended_cost),
+            mass_delta: new.map_or(0.0, BomLine::extended_mass)
+                - old.map_or(0.0, BomLine::extended_mass),
+        }
+    }
+
+    /// Whether the revision differs between the two BOMs
+    pub fn revision_changed(&self) -> bool {
+        self.kind != ChangeKind::Added
+            && self.kind != ChangeKind::Removed
+            && self.old_revision != self.new_revision
+    }
+
+    /// Whether the quantity differs between the two BOMs
+    pub fn quantity_changed(&self) -> bool {
+        self.old_quantity.is_some()
+            && self.new_quantity.is_some()
+            && self.old_quantity != self.new_quantity
+    }
+}
+
+/// Result of comparing two BOMs
+#[derive(Debug, Clone, Serialize)]
+pub struct BomDiff {
+    pub view: BomView,
+    pub changes: Vec<BomChange>,
+    /// Lines present and identical in both BOMs
+    pub unchanged: usize,
+    pub old_cost: f64,
+    pub new_cost: f64,
+    pub old_mass: f64,
+    pub new_mass: f64,
+}
+
+impl BomDiff {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+const EPSILON: f64 = 1e-9;
+
+/// Compare two exploded BOMs of the same view
+///
+/// Changes are listed in the order of the new BOM, with removed lines after
+/// the last surviving line that preceded them in the old BOM.
+pub fn compare(old: &[BomLine], new: &[BomLine], view: BomView) -> BomDiff {
+    let old_index: HashMap<(Vec<String>, String), &BomLine> =
+        old.iter().map(|l| (l.key(), l)).collect();
+    let new_keys: HashSet<(Vec<String>, String)> = new.iter().map(BomLine::key).collect();
+
+    // Removed lines that a new line at the same level replaces
+    let mut replaced: HashMap<(Vec<String>, String), &BomLine> = HashMap::new();
+    for line in new.iter().filter(|l| !old_index.contains_key(&l.key())) {
+        for target in &line.replaces {
+            let key = (line.path.clone(), target.clone());
+            if let Some(old_line) = old_index.get(&key) {
+                if !new_keys.contains(&key) && !replaced.values().any(|l| l.id == *target) {
+                    replaced.insert(line.key(), old_line);
+                    break;
+                }
+            }
+        }
+    }
+    let replaced_by: HashMap<(Vec<String>, String), (Vec<String>, String)> = replaced
+        .iter()
+        .map(|(new_key, old_line)| (old_line.key(), new_key.clone()))
+        .collect();
+
+    // Each change sorts by (index of its line in the new BOM, 0); a removal
+    // follows the nearest line that preceded it in the old BOM and survives
+    let new_position: HashMap<(Vec<String>, String), i64> = new
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (l.key(), i as i64))
+        .collect();
+    let mut changes: Vec<((i64, usize), BomChange)> = Vec::new();
+    let mut unchanged = 0;
+    for (i, line) in new.iter().enumerate() {
+        let key = line.key();
+        let position = (i as i64, 0);
+        match (old_index.get(&key), replaced.get(&key)) {
+            (Some(old_line), _) => {
+                let change = BomChange::new(ChangeKind::Changed, Some(old_line), Some(line));
+                let price_changed = differs(old_line.unit_cost, line.unit_cost);
+                let mass_changed = differs(old_line.unit_mass, line.unit_mass);
+                if change.quantity_changed()
+                    || change.revision_changed()
+                    || !change.refs_added.is_empty()
+                    || !change.refs_removed.is_empty()
+                    || price_changed
+                    || mass_changed
+                {
+                    changes.push((position, change));
+                } else {
+                    unchanged += 1;
+                }
+            }
+            (None, Some(old_line)) => {
+                let mut change = BomChange::new(ChangeKind::Replaced, Some(old_line), Some(line));
+                change.replaces = Some(old_line.id.clone());
+                changes.push((position, change));
+            }
+            (None, None) => changes.push((
+                position,
+                BomChange::new(ChangeKind::Added, None, Some(line)),
+            )),
+        }
+    }
+
+    let mut anchor = -1;
+    for (seq, line) in old.iter().enumerate() {
+        let key = line.key();
+        if let Some(&i) = new_position.get(replaced_by.get(&key).unwrap_or(&key)) {
+            anchor = i;
+        } else {
+            changes.push((
+                (anchor, seq + 1),
+                BomChange::new(ChangeKind::Removed, Some(line), None),
+            ));
+        }
+    }
+    changes.sort_by_key(|(position, _)| *position);
+    let changes = changes.into_iter().map(|(_, c)| c).collect();
+
+    let total = |lines: &[BomLine], f: fn(&BomLine) -> f64| {
+        // Fold from +0.0: an empty f64 sum is -0.0, which prints as "-0.00"
+        lines
+            .iter()
+            .filter(|l| !l.is_assembly)
+            .map(f)
+            .fold(0.0, |sum, v| sum + v)
+    };
+    BomDiff {
+        view,
+        changes,
+        unchanged,
+        old_cost: total(old, BomLine::extended_cost),
+        new_cost: total(new, BomLine::extended_cost),
+        old_mass: total(old, BomLine::extended_mass),
+        new_mass: total(new, BomLine::extended_mass),
+    }
+}
+
+/// Whether two optional amounts differ
+fn differs(a: Option<f64>, b: Option<f64>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => (a - b).abs() > EPSILON,
+        (a, b) => a.is_some() != b.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::EntityId;
+    use crate::entities::assembly::BomItem;
+    use crate::entities::component::{ComponentCategory, MakeBuy};
+
+    fn component(pn: &str, rev: &str, cost: f64, mass: f64) -> Component {
+        let mut cmp = Component::new(
+            pn.to_string(),
+            format!("Part {}", pn),
+            MakeBuy::Buy,
+            ComponentCategory::Mechanical,
+            "test".to_string(),
+        );
+        cmp.revision = Some(rev.to_string());
+        cmp.unit_cost = Some(cost);
+        cmp.mass_kg = Some(mass);
+        cmp
+    }
+
+    fn item(id: &EntityId, qty: u32, refs: &[&str]) -> BomItem {
+        BomItem {
+            component_id: id.to_string(),
+            quantity: qty,
+            reference_designators: refs.iter().map(|r| r.to_string()).collect(),
+            notes: None,
+            placements: Vec::new(),
+        }
+    }
+
+    fn assembly(pn: &str, bom: Vec<BomItem>) -> Assembly {
+        let mut asm = Assembly::new(pn.to_string(), pn.to_string(), "test".to_string());
+        asm.bom = bom;
+        asm
+    }
+
+    #[test]
+    fn test_compare_quantity_refs_revision_and_replacement() {
+        let screw = component("SCR-1", "A", 0.10, 0.002);
+        let mut screw_b = screw.clone();
+        screw_b.revision = Some("B".to_string());
+        let cap = component("CAP-1", "A", 0.05, 0.001);
+        let old_led = component("LED-1", "A", 0.20, 0.001);
+        let mut new_led = component("LED-2", "A", 0.15, 0.001);
+        new_led.links.replaces.push(old_led.id.clone());
+        let label = component("LBL-1", "A", 0.01, 0.0);
+
+        let old_asm = assembly(
+            "ASM-1",
+            vec![
+                item(&screw.id, 4, &[]),
+                item(&cap.id, 2, &["C1", "C2"]),
+                item(&old_led.id, 1, &["D1"]),
+                item(&label.id, 1, &[]),
+            ],
+        );
+        let mut new_asm = old_asm.clone();
+        new_asm.bom = vec![
+            item(&screw.id, 6, &[]),
+            item(&cap.id, 2, &["C1", "C3"]),
+            item(&new_led.id, 1, &["D1"]),
+        ];
+
+        let old = BomSnapshot::new(
+            vec![old_asm.clone()],
+            vec![screw.clone(), cap.clone(), old_led.clone(), label.clone()],
+            vec![],
+        );
+        let new = BomSnapshot::new(
+            vec![new_asm.clone()],
+            vec![screw_b, cap, old_led, new_led.clone(), label],
+            vec![],
+        );
+
+        let diff = compare(
+            &explode(&old, &old_asm, BomView::Indented),
+            &explode(&new, &new_asm, BomView::Indented),
+            BomView::Indented,
+        );
+        let kinds: Vec<ChangeKind> = diff.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::Changed,
+                ChangeKind::Changed,
+                ChangeKind::Replaced,
+                ChangeKind::Removed
+            ]
+        );
+        let screw_change = &diff.changes[0];
+        assert!(screw_change.quantity_changed() && screw_change.revision_changed());
+        assert!((screw_change.cost_delta - 0.20).abs() < 1e-9);
+        assert_eq!(diff.changes[1].refs_added, vec!["C3".to_string()]);
+        assert_eq!(diff.changes[1].refs_removed, vec!["C2".to_string()]);
+        assert_eq!(diff.changes[2].id, new_led.id.to_string());
+        assert!((diff.old_cost - 0.71).abs() < 1e-9);
+        assert!((diff.new_cost - 0.85).abs() < 1e-9);
+        assert_eq!(diff.unchanged, 0);
+    }
+
+    #[test]
+    fn test_flat_view_rolls_up_sub_assemblies() {
+        let screw = component("SCR-1", "A", 0.10, 0.002);
+        let bracket = component("BRK-1", "A", 1.00, 0.050);
+        let sub = assembly("SUB-1", vec![item(&screw.id, 2, &[])]);
+        let mut sub_b = sub.clone();
+        sub_b.bom = vec![item(&screw.id, 3, &[])];
+        let top = assembly(
+            "TOP-1",
+            vec![item(&sub.id, 2, &[]), item(&screw.id, 1, &[])],
+        );
+        let top_b = {
+            let mut t = top.clone();
+            t.bom.push(item(&bracket.id, 1, &[]));
+            t
+        };
+
+        let old = BomSnapshot::new(vec![top.clone(), sub], vec![screw.clone()], vec![]);
+        let new = BomSnapshot::new(
+            vec![top_b.clone(), sub_b],
+            vec![screw.clone(), bracket.clone()],
+            vec![],
+        );
+
+        let old_flat = explode(&old, &top, BomView::Flat);
+        assert_eq!(old_flat.len(), 1);
+        assert_eq!(old_flat[0].extended_quantity, 5);
+
+        let diff = compare(
+            &old_flat,
+            &explode(&new, &top_b, BomView::Flat),
+            BomView::Flat,
+        );
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0].old_quantity, Some(5));
+        assert_eq!(diff.changes[0].new_quantity, Some(7));
+        assert_eq!(diff.changes[1].kind, ChangeKind::Added);
+        assert!((diff.new_mass - 0.064).abs() < 1e-9);
+
+        // The indented view keys the screw under the sub-assembly separately
+        let indented = compare(
+            &explode(&old, &top, BomView::Indented),
+            &explode(&new, &top_b, BomView::Indented),
+            BomView::Indented,
+        );
+        let sub_screw = indented
+            .changes
+            .iter()
+            .find(|c| !c.path.is_empty())
+            .unwrap();
+        assert_eq!(sub_screw.old_quantity, Some(2));
+        assert_eq!(sub_screw.new_quantity, Some(3));
+        assert_eq!(indented.unchanged, 2);
+    }
+
+    #[test]
+    fn test_empty_bom_totals_are_positive_zero() {
+        let diff = compare(&[], &[], BomView::Flat);
+        assert_eq!(format!("{:.2}", diff.old_cost), "0.00");
+        assert_eq!(format!("{:.3}", diff.new_mass), "0.000");
+    }
+}
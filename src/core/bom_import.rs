@@ -0,0 +1,757 @@
+//! BOM import from CAD and EDA exports
+//!
+//! A profile maps the column layout of a BOM export (SolidWorks, Onshape,
+//! KiCad, Altium) onto BOM rows. Indented item numbers ("1", "1.1", "1.2")
+//! give the sub-assembly structure. Rows are matched to existing components
+//! by part number, then by supplier/manufacturer part number; anything that
+//! doesn't match becomes a new component (or a new assembly for rows with
+//! children). The resulting plan is applied by the caller after review.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use serde::Serialize;
+
+use crate::core::bom_diff::BomSnapshot;
+use crate::entities::assembly::{Assembly, BomItem};
+use crate::entities::component::{Component, ComponentCategory, ComponentSupplier, MakeBuy};
+
+/// Column layout of a BOM export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BomProfile {
+    /// Any of the column names below
+    Generic,
+    Solidworks,
+    Onshape,
+    Kicad,
+    Altium,
+}
+
+impl std::fmt::Display for BomProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BomProfile::Generic => write!(f, "generic"),
+            BomProfile::Solidworks => write!(f, "solidworks"),
+            BomProfile::Onshape => write!(f, "onshape"),
+            BomProfile::Kicad => write!(f, "kicad"),
+            BomProfile::Altium => write!(f, "altium"),
+        }
+    }
+}
+
+impl std::str::FromStr for BomProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "generic" | "csv" => Ok(BomProfile::Generic),
+            "solidworks" | "sw" => Ok(BomProfile::Solidworks),
+            "onshape" => Ok(BomProfile::Onshape),
+            "kicad" => Ok(BomProfile::Kicad),
+            "altium" => Ok(BomProfile::Altium),
+            _ => Err(format!(
+                "Invalid BOM profile: {}. Use generic, solidworks, onshape, kicad, or altium",
+                s
+            )),
+        }
+    }
+}
+
+/// BOM row fields a column can map to
+pub const FIELDS: &[&str] = &[
+    "item",
+    "part_number",
+    "quantity",
+    "refs",
+    "title",
+    "description",
+    "revision",
+    "mpn",
+    "manufacturer",
+    "dnp",
+];
+
+impl BomProfile {
+    /// Column names for a field, most specific first
+    fn aliases(&self, field: &str) -> &'static [&'static str] {
+        use BomProfile::*;
+        match (self, field) {
+            (Solidworks, "item") => &["ITEM NO.", "Item"],
+            (Solidworks, "part_number") => &["PART NUMBER", "Part No."],
+            (Solidworks, "quantity") => &["QTY.", "QTY", "Quantity"],
+            (Solidworks, "title") => &["DESCRIPTION"],
+            (Solidworks, "revision") => &["REVISION", "Rev"],
+            (Solidworks, "mpn") => &["VENDOR NO.", "Vendor Part Number"],
+            (Solidworks, "manufacturer") => &["VENDOR"],
+
+            (Onshape, "item") => &["Item"],
+            (Onshape, "part_number") => &["Part number"],
+            (Onshape, "quantity") => &["Quantity"],
+            (Onshape, "title") => &["Name"],
+            (Onshape, "description") => &["Description"],
+            (Onshape, "revision") => &["Revision"],
+            (Onshape, "mpn") => &["Vendor part number"],
+            (Onshape, "manufacturer") => &["Vendor"],
+
+            (Kicad, "refs") => &["Reference", "References", "Ref", "Refs"],
+            (Kicad, "quantity") => &["Qty", "Quantity", "Qnty"],
+            (Kicad, "part_number") => &["Part Number", "PN"],
+            (Kicad, "title") => &["Value"],
+            (Kicad, "description") => &["Description"],
+            (Kicad, "mpn") => &[
+                "MPN",
+                "Manufacturer Part Number",
+                "MFR PN",
+                "MFR Part Number",
+            ],
+            (Kicad, "manufacturer") => &["Manufacturer", "MFR"],
+            (Kicad, "dnp") => &["DNP", "Do not populate", "Exclude from BOM"],
+
+            (Altium, "refs") => &["Designator"],
+            (Altium, "quantity") => &["Quantity", "Qty"],
+            (Altium, "part_number") => &["Part Number", "Company Part Number"],
+            (Altium, "title") => &["Comment"],
+            (Altium, "description") => &["Description"],
+            (Altium, "mpn") => &[
+                "Manufacturer Part Number",
+                "Manufacturer Part Number 1",
+                "MPN",
+            ],
+            (Altium, "manufacturer") => &["Manufacturer", "Manufacturer 1"],
+            (Altium, "dnp") => &["DNP"],
+
+            (Generic, "item") => &["Item", "Item No.", "Find Number"],
+            (Generic, "part_number") => &["Part Number", "PN"],
+            (Generic, "quantity") => &["Quantity", "Qty"],
+            (Generic, "refs") => &["Reference Designators", "References", "Designator"],
+            (Generic, "title") => &["Title", "Name", "Value"],
+            (Generic, "description") => &["Description"],
+            (Generic, "revision") => &["Revision", "Rev"],
+            (Generic, "mpn") => &["MPN", "Manufacturer Part Number", "Supplier PN"],
+            (Generic, "manufacturer") => &["Manufacturer", "Supplier"],
+            (Generic, "dnp") => &["DNP"],
+            _ => &[],
+        }
+    }
+
+    /// Whether the profile is an electronics (EDA) export
+    pub fn is_eda(&self) -> bool {
+        matches!(self, BomProfile::Kicad | BomProfile::Altium)
+    }
+}
+
+/// Normalize a column name for matching ("QTY." and "qty" are the same)
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// One row of a BOM export, after mapping
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BomRow {
+    /// Line in the file (header is line 1)
+    pub line: usize,
+    /// Indented item number, e.g. "1.2"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+    /// Nesting depth from the item number (0 = top level)
+    pub depth: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_number: Option<String>,
+    pub quantity: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub refs: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+}
+
+impl BomRow {
+    /// Part number, or the MPN when the export has none
+    pub fn key(&self) -> Option<&str> {
+        self.part_number.as_deref().or(self.mpn.as_deref())
+    }
+
+    /// Best title for a new entity
+    pub fn display_title(&self) -> String {
+        self.title
+            .clone()
+            .or_else(|| self.description.clone())
+            .or_else(|| self.key().map(String::from))
+            .unwrap_or_default()
+    }
+}
+
+/// Rows read from a BOM export
+#[derive(Debug, Default)]
+pub struct ParsedBom {
+    pub rows: Vec<BomRow>,
+    /// Rows left out (do-not-populate, missing part numbers)
+    pub skipped: Vec<String>,
+}
+
+/// Read a BOM export with a profile
+///
+/// `overrides` maps BOM fields to column names and takes precedence over the
+/// profile. Rows for the same part under the same parent are merged, so
+/// per-designator EDA exports collapse into one line.
+pub fn parse_bom<R: Read>(
+    reader: R,
+    profile: BomProfile,
+    overrides: &HashMap<String, String>,
+) -> Result<ParsedBom, String> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers: Vec<String> = rdr
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(normalize)
+        .collect();
+
+    let mut columns: HashMap<&str, usize> = HashMap::new();
+    for field in FIELDS {
+        let names: Vec<String> = match overrides.get(*field) {
+            Some(column) => vec![column.clone()],
+            None => profile
+                .aliases(field)
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        };
+        if let Some(idx) = names
+            .iter()
+            .find_map(|n| headers.iter().position(|h| *h == normalize(n)))
+        {
+            columns.insert(field, idx);
+        } else if overrides.contains_key(*field) {
+            return Err(format!(
+                "Column '{}' mapped to {} not found",
+                overrides[*field], field
+            ));
+        }
+    }
+    if !columns.contains_key("part_number") && !columns.contains_key("mpn") {
+        return Err(format!(
+            "No part number or MPN column found for the {} profile (use --map part_number=<column>)",
+            profile
+        ));
+    }
+
+    let mut parsed = ParsedBom::default();
+    // (parent item, key) -> index into rows, for merging repeated parts
+    let mut seen: HashMap<(Option<String>, String), usize> = HashMap::new();
+    for (idx, record) in rdr.records().enumerate() {
+        let line = idx + 2;
+        let record = record.map_err(|e| format!("Line {}: {}", line, e))?;
+        let get = |field: &str| {
+            columns
+                .get(field)
+                .and_then(|&i| record.get(i))
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        if record.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+
+        let refs = get("refs").map(|r| expand_refs(&r)).unwrap_or_default();
+        if get("dnp").is_some_and(|v| is_truthy(&v)) {
+            parsed.skipped.push(format!(
+                "line {}: {} not populated",
+                line,
+                if refs.is_empty() {
+                    get("part_number").or(get("mpn")).unwrap_or_default()
+                } else {
+                    refs.join(",")
+                }
+            ));
+            continue;
+        }
+
+        let quantity = match get("quantity") {
+            Some(q) => {
+                let value: f64 = q
+                    .parse()
+                    .map_err(|_| format!("Line {}: invalid quantity '{}'", line, q))?;
+                if value < 0.0 || value.fract() != 0.0 {
+                    return Err(format!(
+                        "Line {}: quantity '{}' is not a whole number",
+                        line, q
+                    ));
+                }
+                value as u32
+            }
+            None if !refs.is_empty() => refs.len() as u32,
+            None => 1,
+        };
+
+        let item = get("item");
+        let row = BomRow {
+            line,
+            depth: item.as_deref().map_or(0, |i| i.matches('.').count()),
+            item,
+            part_number: get("part_number"),
+            quantity,
+            refs,
+            title: get("title"),
+            description: get("description"),
+            revision: get("revision"),
+            mpn: get("mpn"),
+            manufacturer: get("manufacturer"),
+        };
+        let Some(key) = row.key().map(String::from) else {
+            parsed.skipped.push(format!(
+                "line {}: no part number or MPN ({})",
+                line,
+                row.display_title()
+            ));
+            continue;
+        };
+
+        let parent = parent_item(row.item.as_deref());
+        match seen.get(&(parent.clone(), key.clone())) {
+            Some(&i) if row.item.is_none() || parsed.rows[i].item.is_none() => {
+                let existing = &mut parsed.rows[i];
+                existing.quantity += row.quantity;
+                for r in row.refs {
+                    if !existing.refs.contains(&r) {
+                        existing.refs.push(r);
+                    }
+                }
+            }
+            _ => {
+                seen.insert((parent, key), parsed.rows.len());
+                parsed.rows.push(row);
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parent item number of an indented item ("1.2.3" -> "1.2")
+fn parent_item(item: Option<&str>) -> Option<String> {
+    item.and_then(|i| i.rsplit_once('.'))
+        .map(|(p, _)| p.to_string())
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.to_lowercase().as_str(),
+        "1" | "y" | "yes" | "true" | "x" | "dnp" | "dnf"
+    )
+}
+
+/// Split a designator list ("R1, R2 R5-R7") into single designators
+pub fn expand_refs(text: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    for token in text
+        .split([',', ';', ' '])
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        let range = token.split_once('-').and_then(|(a, b)| {
+            let split = |s: &str| {
+                let digits = s.trim_start_matches(|c: char| !c.is_ascii_digit());
+                let prefix = &s[..s.len() - digits.len()];
+                digits.parse::<u32>().ok().map(|n| (prefix.to_string(), n))
+            };
+            let (pa, na) = split(a)?;
+            let (pb, nb) = split(b)?;
+            (!pa.is_empty() && (pa == pb || pb.is_empty()) && na <= nb && nb - na < 1000).then(
+                || {
+                    (na..=nb)
+                        .map(|n| format!("{}{}", pa, n))
+                        .collect::<Vec<_>>()
+                },
+            )
+        });
+        match range {
+            Some(expanded) => refs.extend(expanded),
+            None => refs.push(token.to_string()),
+        }
+    }
+    refs
+}
+
+/// How a row was matched to a TDT entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchedBy {
+    PartNumber,
+    SupplierPn,
+    /// No match: a new entity will be created
+    New,
+}
+
+impl std::fmt::Display for MatchedBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchedBy::PartNumber => write!(f, "part number"),
+            MatchedBy::SupplierPn => write!(f, "supplier PN"),
+            MatchedBy::New => write!(f, "new"),
+        }
+    }
+}
+
+/// Reconciliation of one BOM row
+#[derive(Debug, Clone, Serialize)]
+pub struct RowMatch {
+    pub row: BomRow,
+    /// Component or assembly the row resolves to
+    pub entity: String,
+    pub is_assembly: bool,
+    pub matched_by: MatchedBy,
+    /// Differences between the row and the matched entity worth reviewing
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Everything an import would change
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub matches: Vec<RowMatch>,
+    pub new_components: Vec<Component>,
+    pub new_assemblies: Vec<Assembly>,
+    /// Existing assemblies with their BOM replaced (the target first)
+    pub updated_assemblies: Vec<Assembly>,
+}
+
+impl ImportPlan {
+    /// The snapshot after applying the plan
+    pub fn apply_to(&self, snapshot: &BomSnapshot) -> BomSnapshot {
+        let mut after = BomSnapshot::new(
+            snapshot.assemblies.values().cloned().collect(),
+            snapshot.components.values().cloned().collect(),
+            snapshot.quotes.values().cloned().collect(),
+        );
+        for cmp in &self.new_components {
+            after.components.insert(cmp.id.to_string(), cmp.clone());
+        }
+        for asm in self.new_assemblies.iter().chain(&self.updated_assemblies) {
+            after.assemblies.insert(asm.id.to_string(), asm.clone());
+        }
+        after
+    }
+}
+
+/// Match rows to existing entities and build the assemblies they describe
+///
+/// The rows replace the BOM of `target` and of every sub-assembly that has
+/// child rows. Sub-assemblies are added to `subassemblies` as well as `bom`.
+pub fn plan_import(
+    rows: &[BomRow],
+    snapshot: &BomSnapshot,
+    target: &Assembly,
+    profile: BomProfile,
+    author: &str,
+) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+    let by_pn = |pn: &str| -> Option<&Component> {
+        snapshot
+            .components
+            .values()
+            .find(|c| c.part_number.eq_ignore_ascii_case(pn))
+    };
+    let by_supplier_pn = |mpn: &str| -> Option<&Component> {
+        snapshot.components.values().find(|c| {
+            c.suppliers.iter().any(|s| {
+                s.supplier_pn
+                    .as_deref()
+                    .is_some_and(|p| p.eq_ignore_ascii_case(mpn))
+            })
+        })
+    };
+    let asm_by_pn = |pn: &str| -> Option<&Assembly> {
+        snapshot
+            .assemblies
+            .values()
+            .find(|a| a.part_number.eq_ignore_ascii_case(pn) && a.id != target.id)
+    };
+
+    let mut assemblies: Vec<Assembly> = vec![Assembly {
+        bom: Vec::new(),
+        ..target.clone()
+    }];
+    let mut is_new_assembly = vec![false];
+    // Assembly index for each indented item number
+    let mut item_assembly: HashMap<String, usize> = HashMap::new();
+    // Created components by part number, so repeats share one entity
+    let mut created: HashMap<String, String> = HashMap::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let has_children = row.item.as_ref().is_some_and(|item| {
+            rows.get(i + 1)
+                .and_then(|next| next.item.as_deref())
+                .is_some_and(|next| parent_item(Some(next)).as_deref() == Some(item))
+        });
+        let key = row.key().unwrap_or_default();
+        let mut notes = Vec::new();
+
+        let (entity, is_assembly, matched_by) = if has_children {
+            match row.part_number.as_deref().and_then(asm_by_pn) {
+                Some(asm) => {
+                    assemblies.push(Assembly {
+                        bom: Vec::new(),
+                        ..asm.clone()
+                    });
+                    is_new_assembly.push(false);
+                    (asm.id.to_string(), true, MatchedBy::PartNumber)
+                }
+                None => {
+                    let mut asm = Assembly::new(key, row.display_title(), author);
+                    asm.revision = row.revision.clone();
+                    asm.description = row.description.clone().filter(|_| row.title.is_some());
+                    let id = asm.id.to_string();
+                    assemblies.push(asm);
+                    is_new_assembly.push(true);
+                    (id, true, MatchedBy::New)
+                }
+            }
+        } else {
+            let existing = row
+                .part_number
+                .as_deref()
+                .and_then(by_pn)
+                .map(|c| (c, MatchedBy::PartNumber))
+                .or_else(|| {
+                    row.mpn
+                        .as_deref()
+                        .and_then(|m| by_pn(m).or_else(|| by_supplier_pn(m)))
+                        .map(|c| (c, MatchedBy::SupplierPn))
+                });
+            match existing {
+                Some((cmp, by)) => {
+                    let rev = cmp.revision.as_deref().filter(|r| !r.is_empty());
+                    if let Some(file_rev) = row.revision.as_deref() {
+                        if rev != Some(file_rev) {
+                            notes.push(format!(
+                                "revision {} in file, {} in TDT",
+                                file_rev,
+                                rev.unwrap_or("none")
+                            ));
+                        }
+                    }
+                    (cmp.id.to_string(), false, by)
+                }
+                None => match row
+                    .part_number
+                    .as_deref()
+                    .and_then(asm_by_pn)
+                    .map(|a| a.id.to_string())
+                {
+                    Some(asm_id) => (asm_id, true, MatchedBy::PartNumber),
+                    None => match created.get(&key.to_lowercase()) {
+                        Some(id) => (id.clone(), false, MatchedBy::New),
+                        None => {
+                            let cmp = new_component(row, profile, author);
+                            let id = cmp.id.to_string();
+                            created.insert(key.to_lowercase(), id.clone());
+                            plan.new_components.push(cmp);
+                            (id, false, MatchedBy::New)
+                        }
+                    },
+                },
+            }
+        };
+
+        if has_children {
+            if let Some(item) = &row.item {
+                item_assembly.insert(item.clone(), assemblies.len() - 1);
+            }
+        }
+        let parent = parent_item(row.item.as_deref())
+            .and_then(|p| item_assembly.get(&p).copied())
+            .unwrap_or(0);
+        let parent_asm = &mut assemblies[parent];
+        match parent_asm.bom.iter_mut().find(|b| b.component_id == entity) {
+            Some(existing) => {
+                existing.quantity += row.quantity;
+                existing.reference_designators.extend(row.refs.clone());
+            }
+            None => parent_asm.bom.push(BomItem {
+                component_id: entity.clone(),
+                quantity: row.quantity,
+                reference_designators: row.refs.clone(),
+                notes: None,
+                placements: Vec::new(),
+            }),
+        }
+        if is_assembly && !parent_asm.subassemblies.contains(&entity) {
+            parent_asm.subassemblies.push(entity.clone());
+        }
+
+        plan.matches.push(RowMatch {
+            row: row.clone(),
+            entity,
+            is_assembly,
+            matched_by,
+            notes,
+        });
+    }
+
+    for (asm, is_new) in assemblies.into_iter().zip(is_new_assembly) {
+        if is_new {
+            plan.new_assemblies.push(asm);
+        } else {
+            plan.updated_assemblies.push(asm);
+        }
+    }
+    plan
+}
+
+fn new_component(row: &BomRow, profile: BomProfile, author: &str) -> Component {
+    let (make_buy, category) = if profile.is_eda() {
+        (MakeBuy::Buy, ComponentCategory::Electrical)
+    } else {
+        (MakeBuy::default(), ComponentCategory::Mechanical)
+    };
+    let mut cmp = Component::new(
+        row.key().unwrap_or_default().to_string(),
+        row.display_title(),
+        make_buy,
+        category,
+        author.to_string(),
+    );
+    cmp.revision = row.revision.clone();
+    cmp.description = row.description.clone().filter(|_| row.title.is_some());
+    if let Some(mpn) = &row.mpn {
+        cmp.suppliers.push(ComponentSupplier {
+            name: row.manufacturer.clone().unwrap_or_default(),
+            supplier_pn: Some(mpn.clone()),
+            ..Default::default()
+        });
+    }
+    cmp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(profile: BomProfile, csv: &str) -> ParsedBom {
+        parse_bom(csv.as_bytes(), profile, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_kicad_rows_merge_and_expand_refs() {
+        let bom = parse(
+            BomProfile::Kicad,
+            "Reference,Value,Footprint,MPN,Manufacturer,DNP\n\
+             R1,10k,0603,RC0603FR-0710KL,Yageo,\n\
+             R2 R4-R5,10k,0603,RC0603FR-0710KL,Yageo,\n\
+             C1,100n,0603,GRM188R71H104KA93D,Murata,\n\
+             C2,100n,0603,GRM188R71H104KA93D,Murata,DNP\n\
+             J1,Conn,,,,\n",
+        );
+        assert_eq!(bom.rows.len(), 2);
+        assert_eq!(bom.rows[0].quantity, 4);
+        assert_eq!(bom.rows[0].refs, vec!["R1", "R2", "R4", "R5"]);
+        assert_eq!(bom.rows[1].quantity, 1);
+        assert_eq!(bom.skipped.len(), 2);
+    }
+
+    #[test]
+    fn test_solidworks_indented_bom_plan() {
+        let bom = parse(
+            BomProfile::Solidworks,
+            "ITEM NO.,PART NUMBER,DESCRIPTION,QTY.\n\
+             1,BRK-100,Bracket,2\n\
+             2,SUB-200,Motor module,1\n\
+             2.1,MTR-10,Motor,1\n\
+             2.2,SCR-M3,M3 screw,4\n\
+             3,SCR-M3,M3 screw,8\n",
+        );
+        assert_eq!(bom.rows.len(), 5);
+        assert_eq!(bom.rows[2].depth, 1);
+
+        let mut bracket = Component::new(
+            "brk-100".to_string(),
+            "Bracket".to_string(),
+            MakeBuy::Make,
+            ComponentCategory::Mechanical,
+            "test".to_string(),
+        );
+        bracket.revision = Some("A".to_string());
+        let mut top = Assembly::new("TOP-1", "Widget", "test");
+        top.bom.push(BomItem {
+            component_id: bracket.id.to_string(),
+            quantity: 1,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        });
+        let snapshot = BomSnapshot::new(vec![top.clone()], vec![bracket.clone()], vec![]);
+
+        let plan = plan_import(&bom.rows, &snapshot, &top, BomProfile::Solidworks, "test");
+        assert_eq!(plan.matches[0].matched_by, MatchedBy::PartNumber);
+        assert_eq!(plan.matches[0].entity, bracket.id.to_string());
+        assert!(plan.matches[1].is_assembly);
+        // Screw appears twice but is created once
+        assert_eq!(plan.new_components.len(), 2);
+        assert_eq!(plan.new_assemblies.len(), 1);
+        assert_eq!(plan.new_assemblies[0].bom.len(), 2);
+
+        let updated = &plan.updated_assemblies[0];
+        assert_eq!(updated.id, top.id);
+        assert_eq!(updated.bom.len(), 3);
+        assert_eq!(updated.bom[0].quantity, 2);
+        assert_eq!(
+            updated.subassemblies,
+            vec![plan.new_assemblies[0].id.to_string()]
+        );
+
+        let after = plan.apply_to(&snapshot);
+        assert_eq!(after.components.len(), 3);
+    }
+
+    #[test]
+    fn test_match_by_supplier_pn_and_overrides() {
+        let mut cap = Component::new(
+            "CAP-0001".to_string(),
+            "100nF".to_string(),
+            MakeBuy::Buy,
+            ComponentCategory::Electrical,
+            "test".to_string(),
+        );
+        cap.suppliers.push(ComponentSupplier {
+            name: "Murata".to_string(),
+            supplier_pn: Some("GRM188R71H104KA93D".to_string()),
+            ..Default::default()
+        });
+        let top = Assembly::new("PCBA-1", "Board", "test");
+        let snapshot = BomSnapshot::new(vec![top.clone()], vec![cap.clone()], vec![]);
+
+        let overrides = HashMap::from([("mpn".to_string(), "Mfr PN".to_string())]);
+        let bom = parse_bom(
+            "Designator,Comment,Mfr PN\n\"C1, C2\",100n,grm188r71h104ka93d\n".as_bytes(),
+            BomProfile::Altium,
+            &overrides,
+        )
+        .unwrap();
+        let plan = plan_import(&bom.rows, &snapshot, &top, BomProfile::Altium, "test");
+        assert_eq!(plan.matches[0].matched_by, MatchedBy::SupplierPn);
+        assert_eq!(plan.updated_assemblies[0].bom[0].quantity, 2);
+        assert!(plan.new_components.is_empty());
+
+        let missing = parse_bom(
+            "Designator,Comment\nC1,100n\n".as_bytes(),
+            BomProfile::Altium,
+            &HashMap::new(),
+        );
+        assert!(missing.is_err());
+    }
+}
@@ -236,6 +236,9 @@ impl EntityCache {
             "manufacturing/capas",
             "manufacturing/lots",
             "manufacturing/deviations",
+            "postmarket/complaints",
+            "quality/audits",
+            "manufacturing/receipts",
         ]
     }
 
@@ -0,0 +1,416 @@
+//! Material compliance rollup through an assembly's BOM
+//!
+//! Each component in the recursive BOM is checked against every regulation
+//! requested. A component without a declaration is undeclared, and one whose
+//! declaration has passed its expiry date is expired. An assembly is
+//! non-compliant for a regulation when any component is, incomplete when any
+//! component is undeclared or expired, and otherwise compliant (with
+//! exemptions when any component relies on one).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::entities::assembly::Assembly;
+use crate::entities::component::{ComplianceStatus, Component, Regulation, Substance};
+
+/// REACH Article 33 SVHC concentration threshold, percent by weight
+pub const SVHC_THRESHOLD_PERCENT: f64 = 0.1;
+
+/// Compliance state of one component for one regulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    NonCompliant,
+    Expired,
+    Undeclared,
+    Exempt,
+    Compliant,
+}
+
+impl ComponentState {
+    /// Non-compliant, expired or undeclared
+    pub fn is_issue(&self) -> bool {
+        matches!(
+            self,
+            ComponentState::NonCompliant | ComponentState::Expired | ComponentState::Undeclared
+        )
+    }
+}
+
+impl std::fmt::Display for ComponentState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentState::NonCompliant => write!(f, "non-compliant"),
+            ComponentState::Expired => write!(f, "expired"),
+            ComponentState::Undeclared => write!(f, "undeclared"),
+            ComponentState::Exempt => write!(f, "exempt"),
+            ComponentState::Compliant => write!(f, "compliant"),
+        }
+    }
+}
+
+/// Rolled-up compliance of the assembly for one regulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupStatus {
+    Compliant,
+    CompliantWithExemptions,
+    /// Some components are undeclared or their declarations expired
+    Incomplete,
+    NonCompliant,
+}
+
+impl std::fmt::Display for RollupStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollupStatus::Compliant => write!(f, "compliant"),
+            RollupStatus::CompliantWithExemptions => write!(f, "compliant with exemptions"),
+            RollupStatus::Incomplete => write!(f, "incomplete"),
+            RollupStatus::NonCompliant => write!(f, "non-compliant"),
+        }
+    }
+}
+
+/// One component checked against one regulation
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplianceLine {
+    pub component: String,
+    pub part_number: String,
+    pub title: String,
+    /// Quantity per top-level unit
+    pub quantity: u32,
+    /// Part numbers of the assemblies the component is placed in
+    pub used_in: Vec<String>,
+    pub regulation: Regulation,
+    pub state: ComponentState,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exemptions: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub substances: Vec<Substance>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+    /// Days until the declaration expires (negative once passed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_to_expiry: Option<i64>,
+}
+
+/// Component counts and rolled-up status for one regulation
+#[derive(Debug, Clone, Serialize)]
+pub struct RegulationSummary {
+    pub regulation: Regulation,
+    pub status: RollupStatus,
+    pub compliant: usize,
+    pub exempt: usize,
+    pub non_compliant: usize,
+    pub expired: usize,
+    pub undeclared: usize,
+}
+
+/// Compliance rollup for an assembly
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplianceReport {
+    pub as_of: NaiveDate,
+    pub assembly: String,
+    pub components_checked: usize,
+    pub summaries: Vec<RegulationSummary>,
+    /// Every component checked against every regulation, issues first
+    pub lines: Vec<ComplianceLine>,
+}
+
+impl ComplianceReport {
+    /// Lines that are non-compliant, expired or undeclared
+    pub fn issues(&self) -> impl Iterator<Item = &ComplianceLine> {
+        self.lines.iter().filter(|l| l.state.is_issue())
+    }
+
+    /// Whether every regulation rolled up compliant
+    pub fn is_compliant(&self) -> bool {
+        self.summaries.iter().all(|s| {
+            matches!(
+                s.status,
+                RollupStatus::Compliant | RollupStatus::CompliantWithExemptions
+            )
+        })
+    }
+}
+
+/// Component quantities per top-level unit and the assemblies each is placed in
+fn explode(
+    assembly: &Assembly,
+    assemblies: &HashMap<String, &Assembly>,
+) -> BTreeMap<String, (u32, BTreeSet<String>)> {
+    fn walk(
+        asm: &Assembly,
+        multiplier: u32,
+        assemblies: &HashMap<String, &Assembly>,
+        out: &mut BTreeMap<String, (u32, BTreeSet<String>)>,
+        visited: &mut HashSet<String>,
+    ) {
+        for item in &asm.bom {
+            let qty = item.quantity * multiplier;
+            if let Some(sub) = assemblies.get(&item.component_id) {
+                if visited.insert(item.component_id.clone()) {
+                    walk(sub, qty, assemblies, out, visited);
+                    visited.remove(&item.component_id);
+                }
+            } else {
+                let entry = out.entry(item.component_id.clone()).or_default();
+                entry.0 += qty;
+                entry.1.insert(asm.part_number.clone());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    let mut visited = HashSet::from([assembly.id.to_string()]);
+    walk(assembly, 1, assemblies, &mut out, &mut visited);
+    out
+}
+
+/// Roll up material compliance for an assembly
+///
+/// BOM entries that are neither a known component nor an assembly are skipped.
+pub fn rollup(
+    assembly: &Assembly,
+    assemblies: &[Assembly],
+    components: &[Component],
+    regulations: &[Regulation],
+    as_of: NaiveDate,
+) -> ComplianceReport {
+    let assembly_map: HashMap<String, &Assembly> =
+        assemblies.iter().map(|a| (a.id.to_string(), a)).collect();
+    let by_id: HashMap<String, &Component> =
+        components.iter().map(|c| (c.id.to_string(), c)).collect();
+
+    let mut checked = 0;
+    let mut lines = Vec::new();
+    for (id, (quantity, used_in)) in explode(assembly, &assembly_map) {
+        let Some(cmp) = by_id.get(&id) else {
+            continue;
+        };
+        checked += 1;
+        for &regulation in regulations {
+            lines.push(check(cmp, quantity, &used_in, regulation, as_of));
+        }
+    }
+    lines.sort_by(|a, b| {
+        a.state
+            .cmp(&b.state)
+            .then(a.regulation.cmp(&b.regulation))
+            .then(a.part_number.cmp(&b.part_number))
+    });
+
+    let summaries = regulations
+        .iter()
+        .map(|&regulation| summarize(regulation, &lines))
+        .collect();
+
+    ComplianceReport {
+        as_of,
+        assembly: assembly.id.to_string(),
+        components_checked: checked,
+        summaries,
+        lines,
+    }
+}
+
+fn check(
+    cmp: &Component,
+    quantity: u32,
+    used_in: &BTreeSet<String>,
+    regulation: Regulation,
+    as_of: NaiveDate,
+) -> ComplianceLine {
+    let declaration = cmp.declaration(regulation);
+    let state = match declaration {
+        None => ComponentState::Undeclared,
+        Some(d) if d.status == ComplianceStatus::NonCompliant => ComponentState::NonCompliant,
+        Some(d) if d.is_expired(as_of) => ComponentState::Expired,
+        Some(d) if d.status == ComplianceStatus::Exempt => ComponentState::Exempt,
+        Some(_) => ComponentState::Compliant,
+    };
+    let expires = declaration.and_then(|d| d.expires);
+
+    ComplianceLine {
+        component: cmp.id.to_string(),
+        part_number: cmp.part_number.clone(),
+        title: cmp.title.clone(),
+        quantity,
+        used_in: used_in.iter().cloned().collect(),
+        regulation,
+        state,
+        exemptions: declaration
+            .map(|d| d.exemptions.clone())
+            .unwrap_or_default(),
+        substances: declaration
+            .map(|d| d.substances.clone())
+            .unwrap_or_default(),
+        documents: declaration.map(|d| d.documents.clone()).unwrap_or_default(),
+        declared_by: declaration.and_then(|d| d.declared_by.clone()),
+        expires,
+        days_to_expiry: expires.map(|d| (d - as_of).num_days()),
+    }
+}
+
+fn summarize(regulation: Regulation, lines: &[ComplianceLine]) -> RegulationSummary {
+    let count = |state: ComponentState| {
+        lines
+            .iter()
+            .filter(|l| l.regulation == regulation && l.state == state)
+            .count()
+    };
+    let compliant = count(ComponentState::Compliant);
+    let exempt = count(ComponentState::Exempt);
+    let non_compliant = count(ComponentState::NonCompliant);
+    let expired = count(ComponentState::Expired);
+    let undeclared = count(ComponentState::Undeclared);
+
+    let status = if non_compliant > 0 {
+        RollupStatus::NonCompliant
+    } else if expired + undeclared > 0 {
+        RollupStatus::Incomplete
+    } else if exempt > 0 {
+        RollupStatus::CompliantWithExemptions
+    } else {
+        RollupStatus::Compliant
+    };
+
+    RegulationSummary {
+        regulation,
+        status,
+        compliant,
+        exempt,
+        non_compliant,
+        expired,
+        undeclared,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assembly::BomItem;
+    use crate::entities::component::{ComplianceDeclaration, ComponentCategory, MakeBuy};
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn part(pn: &str) -> Component {
+        Component::new(
+            pn.to_string(),
+            format!("Part {}", pn),
+            MakeBuy::Buy,
+            ComponentCategory::Electrical,
+            "test".to_string(),
+        )
+    }
+
+    fn declare(
+        regulation: Regulation,
+        status: ComplianceStatus,
+        expires: Option<&str>,
+    ) -> ComplianceDeclaration {
+        ComplianceDeclaration {
+            regulation,
+            status,
+            exemptions: Vec::new(),
+            substances: Vec::new(),
+            documents: Vec::new(),
+            declared_by: None,
+            date: None,
+            expires: expires.map(date),
+        }
+    }
+
+    fn item(id: &str, quantity: u32) -> BomItem {
+        BomItem {
+            component_id: id.to_string(),
+            quantity,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rollup_through_subassemblies() {
+        let mut a = part("A");
+        a.compliance = vec![
+            declare(Regulation::Rohs, ComplianceStatus::Exempt, None),
+            declare(Regulation::Reach, ComplianceStatus::Compliant, None),
+        ];
+        let mut b = part("B");
+        b.compliance = vec![
+            declare(Regulation::Rohs, ComplianceStatus::Compliant, None),
+            declare(
+                Regulation::Reach,
+                ComplianceStatus::Compliant,
+                Some("2026-01-01"),
+            ),
+        ];
+
+        let mut sub = Assembly::new("SUB".to_string(), "Sub".to_string(), "test".to_string());
+        sub.bom.push(item(&a.id.to_string(), 2));
+        let mut top = Assembly::new("TOP".to_string(), "Top".to_string(), "test".to_string());
+        top.bom.push(item(&sub.id.to_string(), 3));
+        top.bom.push(item(&a.id.to_string(), 1));
+        top.bom.push(item(&b.id.to_string(), 1));
+
+        let assemblies = vec![sub];
+        let report = rollup(
+            &top,
+            &assemblies,
+            &[a, b],
+            &[Regulation::Rohs, Regulation::Reach],
+            date("2026-10-01"),
+        );
+
+        assert_eq!(report.components_checked, 2);
+        assert_eq!(
+            report.summaries[0].status,
+            RollupStatus::CompliantWithExemptions
+        );
+        assert_eq!(report.summaries[1].status, RollupStatus::Incomplete);
+        assert!(!report.is_compliant());
+
+        let issues: Vec<_> = report.issues().collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].part_number, "B");
+        assert_eq!(issues[0].state, ComponentState::Expired);
+        assert_eq!(issues[0].days_to_expiry, Some(-273));
+
+        let a_line = report
+            .lines
+            .iter()
+            .find(|l| l.part_number == "A" && l.regulation == Regulation::Rohs)
+            .unwrap();
+        assert_eq!(a_line.quantity, 7);
+        assert_eq!(a_line.used_in, vec!["SUB", "TOP"]);
+    }
+
+    #[test]
+    fn test_non_compliant_outranks_undeclared() {
+        let mut a = part("A");
+        a.compliance = vec![declare(
+            Regulation::Rohs,
+            ComplianceStatus::NonCompliant,
+            None,
+        )];
+        let b = part("B");
+        let mut top = Assembly::new("TOP".to_string(), "Top".to_string(), "test".to_string());
+        top.bom.push(item(&a.id.to_string(), 1));
+        top.bom.push(item(&b.id.to_string(), 1));
+
+        let report = rollup(&top, &[], &[a, b], &[Regulation::Rohs], date("2026-10-01"));
+        assert_eq!(report.summaries[0].status, RollupStatus::NonCompliant);
+        assert_eq!(report.summaries[0].undeclared, 1);
+        assert_eq!(report.lines[0].state, ComponentState::NonCompliant);
+    }
+}
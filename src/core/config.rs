@@ -3,6 +3,12 @@
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::core::currency::CurrencyConfig;
+use crate::core::mrb::MrbConfig;
+use crate::core::ncr_trends::NcrTrendConfig;
+use crate::core::should_cost::CostingConfig;
+use crate::core::sourcing::SourcingConfig;
+use crate::core::supplier_scorecard::ScorecardConfig;
 use crate::core::workflow::WorkflowConfig;
 use crate::core::Project;
 
@@ -54,6 +60,24 @@ pub struct Config {
 
     /// Manufacturing workflow configuration
     pub manufacturing: Option<ManufacturingConfigSection>,
+
+    /// NCR trend and CAPA escalation rules
+    pub ncr_trends: Option<NcrTrendConfig>,
+
+    /// Supplier scorecard weights and thresholds
+    pub supplier_scorecard: Option<ScorecardConfig>,
+
+    /// Material Review Board sign-off rules for NCR dispositions
+    pub mrb: Option<MrbConfig>,
+
+    /// Reporting currency and exchange-rate table for cost rollups
+    pub currency: Option<CurrencyConfig>,
+
+    /// Factor weights for the quote sourcing decision matrix
+    pub sourcing: Option<SourcingConfig>,
+
+    /// Labor, machine and material rates for make-part should-cost
+    pub costing: Option<CostingConfig>,
 }
 
 impl Config {
@@ -119,6 +143,26 @@ impl Config {
         if other.manufacturing.is_some() {
             self.manufacturing = other.manufacturing;
         }
+        // NCR trend rules: merge if present
+        if other.ncr_trends.is_some() {
+            self.ncr_trends = other.ncr_trends;
+        }
+        // Supplier scorecard: merge if present
+        if other.supplier_scorecard.is_some() {
+            self.supplier_scorecard = other.supplier_scorecard;
+        }
+        if other.mrb.is_some() {
+            self.mrb = other.mrb;
+        }
+        if other.currency.is_some() {
+            self.currency = other.currency;
+        }
+        if other.sourcing.is_some() {
+            self.sourcing = other.sourcing;
+        }
+        if other.costing.is_some() {
+            self.costing = other.costing;
+        }
     }
 
     /// Get the path to the global config file (public for config command)
@@ -207,6 +251,12 @@ mod tests {
             default_format: Some("yaml".to_string()),
             workflow: WorkflowConfig::default(),
             manufacturing: None,
+            ncr_trends: None,
+            supplier_scorecard: None,
+            mrb: None,
+            currency: None,
+            sourcing: None,
+            costing: None,
         };
 
         let other = Config {
@@ -216,6 +266,12 @@ mod tests {
             default_format: None, // Should NOT override
             workflow: WorkflowConfig::default(),
             manufacturing: None,
+            ncr_trends: None,
+            supplier_scorecard: None,
+            mrb: None,
+            currency: None,
+            sourcing: None,
+            costing: None,
         };
 
         base.merge(other);
@@ -237,6 +293,12 @@ mod tests {
             default_format: Some("json".to_string()),
             workflow: WorkflowConfig::default(),
             manufacturing: None,
+            ncr_trends: None,
+            supplier_scorecard: None,
+            mrb: None,
+            currency: None,
+            sourcing: None,
+            costing: None,
         };
 
         base.merge(other);
@@ -0,0 +1,253 @@
+//! Exchange rates for multi-currency cost rollups
+//!
+//! Quotes carry their own currency; component `unit_cost` values are in the
+//! reporting currency. Rollups convert every price into the reporting
+//! currency using the most recent rate effective on the costing date. Direct
+//! rates are preferred, then the inverse of the opposite pair. Missing rates
+//! leave the amount unconverted and are reported, as are rates older than
+//! `stale_after_days`.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::quote::Currency;
+
+/// Exchange-rate configuration (`currency` in config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CurrencyConfig {
+    /// Currency costs are reported in (and component `unit_cost` is entered in)
+    pub reporting: Currency,
+
+    /// Rates older than this many days are flagged as stale
+    pub stale_after_days: u32,
+
+    /// Exchange-rate table
+    pub rates: Vec<ExchangeRate>,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            reporting: Currency::Usd,
+            stale_after_days: 90,
+            rates: Vec::new(),
+        }
+    }
+}
+
+/// One exchange rate: 1 `from` = `rate` `to`, from `effective` on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub from: Currency,
+    pub to: Currency,
+    pub rate: f64,
+    pub effective: NaiveDate,
+}
+
+/// Problem with the rate used for a conversion
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum RateIssue {
+    /// No rate effective on the costing date; amounts used unconverted
+    Missing { from: Currency, to: Currency },
+    /// Rate older than `stale_after_days`
+    Stale {
+        from: Currency,
+        to: Currency,
+        effective: NaiveDate,
+        age_days: i64,
+    },
+}
+
+impl std::fmt::Display for RateIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateIssue::Missing { from, to } => write!(
+                f,
+                "no {}→{} exchange rate; {} amounts used unconverted",
+                from, to, from
+            ),
+            RateIssue::Stale {
+                from,
+                to,
+                effective,
+                age_days,
+            } => write!(
+                f,
+                "{}→{} rate effective {} is {} days old",
+                from, to, effective, age_days
+            ),
+        }
+    }
+}
+
+impl CurrencyConfig {
+    /// Rate from one currency to another effective on `as_of`
+    ///
+    /// Returns the rate and the date it took effect, or `None` when neither
+    /// the pair nor its inverse has a rate on or before `as_of`.
+    pub fn rate(&self, from: Currency, to: Currency, as_of: NaiveDate) -> Option<(f64, NaiveDate)> {
+        let latest = |a: Currency, b: Currency| {
+            self.rates
+                .iter()
+                .filter(|r| r.from == a && r.to == b && r.effective <= as_of && r.rate > 0.0)
+                .max_by_key(|r| r.effective)
+        };
+        if let Some(r) = latest(from, to) {
+            return Some((r.rate, r.effective));
+        }
+        latest(to, from).map(|r| (1.0 / r.rate, r.effective))
+    }
+}
+
+/// Converts amounts into a target currency, collecting rate issues
+#[derive(Debug, Clone)]
+pub struct Converter {
+    config: CurrencyConfig,
+    /// Currency amounts are converted into
+    pub target: Currency,
+    /// Costing date rates are looked up at
+    pub as_of: NaiveDate,
+    issues: Vec<RateIssue>,
+}
+
+impl Converter {
+    /// Converter into `target`, or the configured reporting currency
+    pub fn new(config: CurrencyConfig, target: Option<Currency>, as_of: NaiveDate) -> Self {
+        Self {
+            target: target.unwrap_or(config.reporting),
+            config,
+            as_of,
+            issues: Vec::new(),
+        }
+    }
+
+    /// Converter for today's rates using the loaded configuration
+    pub fn from_config(target: Option<Currency>) -> Self {
+        let config = crate::core::Config::load().currency.unwrap_or_default();
+        Self::new(config, target, chrono::Local::now().date_naive())
+    }
+
+    /// Convert an amount into the target currency
+    pub fn convert(&mut self, amount: f64, from: Currency) -> f64 {
+        if from == self.target {
+            return amount;
+        }
+        match self.config.rate(from, self.target, self.as_of) {
+            Some((rate, effective)) => {
+                let age_days = (self.as_of - effective).num_days();
+                if age_days > self.config.stale_after_days as i64 {
+                    self.record(RateIssue::Stale {
+                        from,
+                        to: self.target,
+                        effective,
+                        age_days,
+                    });
+                }
+                amount * rate
+            }
+            None => {
+                self.record(RateIssue::Missing {
+                    from,
+                    to: self.target,
+                });
+                amount
+            }
+        }
+    }
+
+    /// Convert a `unit_cost` (entered in the reporting currency)
+    pub fn convert_unit_cost(&mut self, amount: f64) -> f64 {
+        let reporting = self.config.reporting;
+        self.convert(amount, reporting)
+    }
+
+    /// Rate problems met so far, one per currency pair
+    pub fn issues(&self) -> &[RateIssue] {
+        &self.issues
+    }
+
+    fn record(&mut self, issue: RateIssue) {
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn config() -> CurrencyConfig {
+        CurrencyConfig {
+            reporting: Currency::Usd,
+            stale_after_days: 30,
+            rates: vec![
+                ExchangeRate {
+                    from: Currency::Eur,
+                    to: Currency::Usd,
+                    rate: 1.05,
+                    effective: date("2026-01-01"),
+                },
+                ExchangeRate {
+                    from: Currency::Eur,
+                    to: Currency::Usd,
+                    rate: 1.10,
+                    effective: date("2026-06-01"),
+                },
+                ExchangeRate {
+                    from: Currency::Usd,
+                    to: Currency::Jpy,
+                    rate: 150.0,
+                    effective: date("2026-06-01"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_rate_uses_latest_effective_and_inverse() {
+        let cfg = config();
+        assert_eq!(
+            cfg.rate(Currency::Eur, Currency::Usd, date("2026-03-01")),
+            Some((1.05, date("2026-01-01")))
+        );
+        assert_eq!(
+            cfg.rate(Currency::Eur, Currency::Usd, date("2026-06-15")),
+            Some((1.10, date("2026-06-01")))
+        );
+        let (rate, _) = cfg
+            .rate(Currency::Jpy, Currency::Usd, date("2026-06-15"))
+            .unwrap();
+        assert!((rate - 1.0 / 150.0).abs() < 1e-12);
+        assert_eq!(
+            cfg.rate(Currency::Eur, Currency::Usd, date("2025-12-31")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_converter_reports_missing_and_stale_once() {
+        let mut conv = Converter::new(config(), None, date("2026-08-01"));
+        assert!((conv.convert(10.0, Currency::Eur) - 11.0).abs() < 1e-9);
+        assert_eq!(conv.convert(5.0, Currency::Gbp), 5.0);
+        conv.convert(1.0, Currency::Gbp);
+        conv.convert(1.0, Currency::Eur);
+        assert_eq!(conv.issues().len(), 2);
+        assert!(matches!(
+            conv.issues()[0],
+            RateIssue::Stale { age_days: 61, .. }
+        ));
+        assert!(matches!(conv.issues()[1], RateIssue::Missing { .. }));
+
+        // Reporting in EUR converts unit_cost out of USD
+        let mut eur = Converter::new(config(), Some(Currency::Eur), date("2026-06-15"));
+        assert!((eur.convert_unit_cost(11.0) - 10.0).abs() < 1e-9);
+        assert!(eur.issues().is_empty());
+    }
+}
@@ -0,0 +1,1004 @@
+//! Device History Record (DHR) assembly for production lots
+//!
+//! Collects everything needed to reconstruct how a lot was built - the
+//! as-run process and work instruction revisions, deviations in effect,
+//! materials, NCRs, test results and signature status - into a single
+//! serializable record, and renders it as markdown or HTML.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::git::Git;
+use crate::core::project::Project;
+use crate::entities::assembly::Assembly;
+use crate::entities::component::Component;
+use crate::entities::dev::{Dev, DevStatus};
+use crate::entities::lot::{ExecutionStatus, Lot, MaterialUsed};
+use crate::entities::ncr::Ncr;
+use crate::entities::process::Process;
+use crate::entities::result::Result as TestResult;
+use crate::entities::work_instruction::WorkInstruction;
+
+/// Outcome of verifying a step's git commit signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "state", content = "detail")]
+pub enum SignatureCheck {
+    /// Commit carries a valid signature (signer)
+    Verified(String),
+    /// Commit exists but is not signed
+    Unsigned,
+    /// Signature present but failed verification
+    Invalid(String),
+    /// Step has no commit SHA, or verification was skipped
+    NotChecked,
+}
+
+impl std::fmt::Display for SignatureCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureCheck::Verified(signer) => write!(f, "verified ({})", signer),
+            SignatureCheck::Unsigned => write!(f, "unsigned"),
+            SignatureCheck::Invalid(_) => write!(f, "INVALID"),
+            SignatureCheck::NotChecked => write!(f, "not checked"),
+        }
+    }
+}
+
+/// Work instruction as used in a step
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrWorkInstruction {
+    pub id: String,
+    pub title: Option<String>,
+    pub document_number: Option<String>,
+    /// Entity revision recorded at execution
+    pub revision_used: Option<u32>,
+    /// Entity revision today
+    pub current_revision: Option<u32>,
+}
+
+/// Approval recorded against a step
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrApproval {
+    pub approver: String,
+    pub role: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub signature_verified: Option<bool>,
+}
+
+/// One executed (or pending) step of the lot
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrStep {
+    pub sequence: usize,
+    pub process: Option<String>,
+    pub process_title: Option<String>,
+    pub operation_number: Option<String>,
+    /// Process revision at execution (as-run)
+    pub process_revision: Option<u32>,
+    /// Process revision today
+    pub current_process_revision: Option<u32>,
+    pub status: ExecutionStatus,
+    pub started_date: Option<NaiveDate>,
+    pub completed_date: Option<NaiveDate>,
+    pub operator: Option<String>,
+    pub work_instructions: Vec<DhrWorkInstruction>,
+    pub signature_required: bool,
+    pub signature_verified: Option<bool>,
+    pub signing_key: Option<String>,
+    pub commit_sha: Option<String>,
+    pub commit_signature: SignatureCheck,
+    pub approvals: Vec<DhrApproval>,
+    pub data: HashMap<String, serde_json::Value>,
+    /// Deviation that authorized a departure in this step
+    pub deviation: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Deviation that applied to the lot
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrDeviation {
+    pub id: String,
+    pub deviation_number: Option<String>,
+    pub title: String,
+    pub dev_status: DevStatus,
+    pub effective_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub approved_by: Option<String>,
+    /// How the deviation applies ("lot" or a process ID)
+    pub applies_via: String,
+}
+
+/// NCR raised against the lot
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrNcr {
+    pub id: String,
+    pub ncr_number: Option<String>,
+    pub title: String,
+    pub severity: String,
+    pub ncr_status: String,
+    pub disposition: Option<String>,
+}
+
+/// Test result linked to the lot
+#[derive(Debug, Clone, Serialize)]
+pub struct DhrResult {
+    pub id: String,
+    pub test: String,
+    pub title: Option<String>,
+    pub verdict: String,
+    pub executed_by: String,
+    pub executed_date: DateTime<Utc>,
+}
+
+/// Signature summary across all steps
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SignatureSummary {
+    pub steps_requiring_signature: usize,
+    pub steps_signed: usize,
+    pub commits_verified: usize,
+    pub commits_unsigned: usize,
+    pub commits_invalid: usize,
+    pub approvals: usize,
+}
+
+/// Hash of a record file included in the DHR
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    /// Entity ID
+    pub id: String,
+    /// Path relative to the project root
+    pub path: String,
+    /// SHA-256 of the file contents
+    pub sha256: String,
+}
+
+/// Complete device history record for a lot
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceHistoryRecord {
+    pub lot: String,
+    pub lot_number: Option<String>,
+    pub title: String,
+    pub lot_status: String,
+    pub quantity: Option<u32>,
+    pub product: Option<String>,
+    pub product_part_number: Option<String>,
+    pub product_revision: Option<String>,
+    pub product_title: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub completion_date: Option<NaiveDate>,
+    pub serial_count: usize,
+    pub parent_lots: Vec<String>,
+    pub child_lots: Vec<String>,
+    pub materials: Vec<MaterialUsed>,
+    pub steps: Vec<DhrStep>,
+    pub deviations: Vec<DhrDeviation>,
+    pub ncrs: Vec<DhrNcr>,
+    pub results: Vec<DhrResult>,
+    pub signatures: SignatureSummary,
+    pub generated: DateTime<Utc>,
+    pub generated_by: String,
+    /// Git HEAD when the record was generated
+    pub git_commit: Option<String>,
+    /// Source records included in the DHR
+    pub records: Vec<ManifestEntry>,
+}
+
+/// Compute the SHA-256 of a file's bytes as lowercase hex
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Load all entities of a type under a project directory, keeping file paths
+pub(crate) fn load_with_paths<T: DeserializeOwned>(
+    project: &Project,
+    dirs: &[&str],
+) -> Vec<(PathBuf, T)> {
+    let mut out = Vec::new();
+    for dir in dirs {
+        let path = project.root().join(dir);
+        if !path.exists() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.path().to_string_lossy().ends_with(".tdt.yaml"))
+        {
+            if let Ok(content) = fs::read_to_string(entry.path()) {
+                if let Ok(entity) = serde_yml::from_str::<T>(&content) {
+                    out.push((entry.path().to_path_buf(), entity));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Options for DHR assembly
+#[derive(Debug, Clone, Default)]
+pub struct DhrOptions {
+    /// Verify step commit signatures with git
+    pub verify_signatures: bool,
+    /// Name recorded as the generator of the DHR
+    pub generated_by: String,
+}
+
+/// Builds a [`DeviceHistoryRecord`] from project files
+pub struct DhrBuilder<'a> {
+    project: &'a Project,
+    options: DhrOptions,
+}
+
+impl<'a> DhrBuilder<'a> {
+    pub fn new(project: &'a Project, options: DhrOptions) -> Self {
+        Self { project, options }
+    }
+
+    /// Assemble the DHR for a lot stored at `lot_path`
+    pub fn build(&self, lot_path: &Path, lot: &Lot) -> DeviceHistoryRecord {
+        let root = self.project.root();
+        let lot_id = lot.id.to_string();
+        let mut records: Vec<(String, PathBuf)> = vec![(lot_id.clone(), lot_path.to_path_buf())];
+        let mut include = |id: String, path: &Path| {
+            if !records.iter().any(|(i, _)| *i == id) {
+                records.push((id, path.to_path_buf()));
+            }
+        };
+
+        // Product
+        let mut product_part_number = None;
+        let mut product_revision = None;
+        let mut product_title = None;
+        if let Some(ref product) = lot.links.product {
+            if product.starts_with("ASM-") {
+                for (path, asm) in load_with_paths::<Assembly>(self.project, &["bom/assemblies"]) {
+                    if asm.id.to_string() == *product {
+                        product_part_number = Some(asm.part_number.clone());
+                        product_revision = asm.revision.clone();
+                        product_title = Some(asm.title.clone());
+                        include(product.clone(), &path);
+                    }
+                }
+            } else {
+                for (path, cmp) in load_with_paths::<Component>(self.project, &["bom/components"]) {
+                    if cmp.id.to_string() == *product {
+                        product_part_number = Some(cmp.part_number.clone());
+                        product_revision = cmp.revision.clone();
+                        product_title = Some(cmp.title.clone());
+                        include(product.clone(), &path);
+                    }
+                }
+            }
+        }
+
+        // Processes and work instructions
+        let processes: HashMap<String, (PathBuf, Process)> =
+            load_with_paths::<Process>(self.project, &["manufacturing/processes"])
+                .into_iter()
+                .map(|(p, e)| (e.id.to_string(), (p, e)))
+                .collect();
+        let work_instructions: HashMap<String, (PathBuf, WorkInstruction)> =
+            load_with_paths::<WorkInstruction>(self.project, &["manufacturing/work_instructions"])
+                .into_iter()
+                .map(|(p, e)| (e.id.to_string(), (p, e)))
+                .collect();
+
+        let git = Git::new(root);
+        let can_verify = self.options.verify_signatures && git.is_repo();
+        let mut signatures = SignatureSummary::default();
+
+        let mut steps = Vec::new();
+        for (i, step) in lot.execution.iter().enumerate() {
+            let proc = step.process.as_ref().and_then(|p| processes.get(p));
+            if let (Some(id), Some((path, _))) = (step.process.as_ref(), proc) {
+                include(id.clone(), path);
+            }
+
+            let wis = step
+                .work_instructions_used
+                .iter()
+                .map(|wi_ref| {
+                    let wi = work_instructions.get(&wi_ref.id);
+                    if let Some((path, _)) = wi {
+                        include(wi_ref.id.clone(), path);
+                    }
+                    DhrWorkInstruction {
+                        id: wi_ref.id.clone(),
+                        title: wi.map(|(_, w)| w.title.clone()),
+                        document_number: wi.and_then(|(_, w)| w.document_number.clone()),
+                        revision_used: wi_ref.revision,
+                        current_revision: wi.map(|(_, w)| w.entity_revision),
+                    }
+                })
+                .collect();
+
+            let signature_required = proc.map(|(_, p)| p.require_signature).unwrap_or(false);
+            if signature_required {
+                signatures.steps_requiring_signature += 1;
+            }
+            if step.signature_verified == Some(true) {
+                signatures.steps_signed += 1;
+            }
+
+            let commit_signature = match step.commit_sha {
+                Some(ref sha) if can_verify => match git.verify_commit_signature(sha) {
+                    Ok(Some(signer)) => SignatureCheck::Verified(signer),
+                    Ok(None) => SignatureCheck::Unsigned,
+                    Err(e) => SignatureCheck::Invalid(e.to_string()),
+                },
+                _ => SignatureCheck::NotChecked,
+            };
+            match commit_signature {
+                SignatureCheck::Verified(_) => signatures.commits_verified += 1,
+                SignatureCheck::Unsigned => signatures.commits_unsigned += 1,
+                SignatureCheck::Invalid(_) => signatures.commits_invalid += 1,
+                SignatureCheck::NotChecked => {}
+            }
+            signatures.approvals += step.approvals.len();
+
+            steps.push(DhrStep {
+                sequence: i + 1,
+                process: step.process.clone(),
+                process_title: proc.map(|(_, p)| p.title.clone()),
+                operation_number: proc.and_then(|(_, p)| p.operation_number.clone()),
+                process_revision: step.process_revision,
+                current_process_revision: proc.map(|(_, p)| p.entity_revision),
+                status: step.status,
+                started_date: step.started_date,
+                completed_date: step.completed_date,
+                operator: step.operator.clone(),
+                work_instructions: wis,
+                signature_required,
+                signature_verified: step.signature_verified,
+                signing_key: step.signing_key.clone(),
+                commit_sha: step.commit_sha.clone(),
+                commit_signature,
+                approvals: step
+                    .approvals
+                    .iter()
+                    .map(|a| DhrApproval {
+                        approver: a.approver.clone(),
+                        role: a.role.clone(),
+                        timestamp: a.timestamp,
+                        signature_verified: a.signature_verified,
+                    })
+                    .collect(),
+                data: step.data.clone(),
+                deviation: step.deviation.clone(),
+                notes: step.notes.clone(),
+            });
+        }
+
+        // Deviations: linked to the lot directly, or to one of its processes
+        // with an effective window overlapping production
+        let lot_processes: Vec<&String> = lot
+            .execution
+            .iter()
+            .filter_map(|s| s.process.as_ref())
+            .chain(lot.links.processes.iter())
+            .collect();
+        let window_start = lot.start_date;
+        let window_end = lot
+            .completion_date
+            .unwrap_or_else(|| chrono::Local::now().date_naive());
+        let mut deviations = Vec::new();
+        for (path, dev) in load_with_paths::<Dev>(self.project, &["manufacturing/deviations"]) {
+            let dev_id = dev.id.to_string();
+            let cited_by = lot
+                .execution
+                .iter()
+                .position(|s| s.deviation.as_deref() == Some(dev_id.as_str()));
+            let applies_via = if dev.links.lots.contains(&lot_id) {
+                Some("lot".to_string())
+            } else if let Some(i) = cited_by {
+                Some(format!("step {}", i + 1))
+            } else {
+                let was_authorized =
+                    !matches!(dev.dev_status, DevStatus::Pending | DevStatus::Rejected);
+                let overlaps = dev.effective_date.is_none_or(|d| d <= window_end)
+                    && match (dev.expiration_date, window_start) {
+                        (Some(exp), Some(start)) => exp >= start,
+                        _ => true,
+                    };
+                dev.links
+                    .processes
+                    .iter()
+                    .find(|p| lot_processes.contains(p))
+                    .filter(|_| was_authorized && overlaps)
+                    .cloned()
+            };
+            if let Some(applies_via) = applies_via {
+                include(dev.id.to_string(), &path);
+                deviations.push(DhrDeviation {
+                    id: dev.id.to_string(),
+                    deviation_number: dev.deviation_number.clone(),
+                    title: dev.title.clone(),
+                    dev_status: dev.dev_status,
+                    effective_date: dev.effective_date,
+                    expiration_date: dev.expiration_date,
+                    approved_by: dev.approval.approved_by.clone(),
+                    applies_via,
+                });
+            }
+        }
+
+        // NCRs: linked from the lot, or naming the lot number
+        let mut ncrs = Vec::new();
+        for (path, ncr) in load_with_paths::<Ncr>(self.project, &["manufacturing/ncrs"]) {
+            let ncr_id = ncr.id.to_string();
+            let by_number = lot.lot_number.is_some()
+                && ncr
+                    .affected_items
+                    .as_ref()
+                    .and_then(|a| a.lot_number.as_ref())
+                    == lot.lot_number.as_ref();
+            if lot.links.ncrs.contains(&ncr_id) || by_number {
+                include(ncr_id.clone(), &path);
+                ncrs.push(DhrNcr {
+                    id: ncr_id,
+                    ncr_number: ncr.ncr_number.clone(),
+                    title: ncr.title.clone(),
+                    severity: ncr.severity.to_string(),
+                    ncr_status: ncr.ncr_status.to_string(),
+                    disposition: ncr
+                        .disposition
+                        .as_ref()
+                        .and_then(|d| d.decision)
+                        .map(|d| d.to_string()),
+                });
+            }
+        }
+
+        // Test results linked from the lot
+        let mut results = Vec::new();
+        for (path, rslt) in load_with_paths::<TestResult>(
+            self.project,
+            &["verification/results", "validation/results"],
+        ) {
+            let rslt_id = rslt.id.to_string();
+            if lot.links.results.contains(&rslt_id) {
+                include(rslt_id.clone(), &path);
+                results.push(DhrResult {
+                    id: rslt_id,
+                    test: rslt.test_id.to_string(),
+                    title: rslt.title.clone(),
+                    verdict: rslt.verdict.to_string(),
+                    executed_by: rslt.executed_by.clone(),
+                    executed_date: rslt.executed_date,
+                });
+            }
+        }
+
+        let records = records
+            .into_iter()
+            .map(|(id, path)| ManifestEntry {
+                id,
+                path: path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                sha256: sha256_file(&path).unwrap_or_default(),
+            })
+            .collect();
+
+        DeviceHistoryRecord {
+            lot: lot_id,
+            lot_number: lot.lot_number.clone(),
+            title: lot.title.clone(),
+            lot_status: lot.lot_status.to_string(),
+            quantity: lot.quantity,
+            product: lot.links.product.clone(),
+            product_part_number,
+            product_revision,
+            product_title,
+            start_date: lot.start_date,
+            completion_date: lot.completion_date,
+            serial_count: lot.serials.len(),
+            parent_lots: lot.links.parent_lots.clone(),
+            child_lots: lot.links.child_lots.clone(),
+            materials: lot.materials_used.clone(),
+            steps,
+            deviations,
+            ncrs,
+            results,
+            signatures,
+            generated: Utc::now(),
+            generated_by: self.options.generated_by.clone(),
+            git_commit: if git.is_repo() {
+                git.head_sha().ok()
+            } else {
+                None
+            },
+            records,
+        }
+    }
+}
+
+impl DeviceHistoryRecord {
+    /// Steps that are neither completed nor skipped
+    pub fn open_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|s| {
+                !matches!(
+                    s.status,
+                    ExecutionStatus::Completed | ExecutionStatus::Skipped
+                )
+            })
+            .count()
+    }
+
+    /// Steps whose process or work instructions changed since execution
+    pub fn revision_changes(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|s| {
+                s.process_revision.is_some() && s.process_revision != s.current_process_revision
+                    || s.work_instructions
+                        .iter()
+                        .any(|w| w.revision_used.is_some() && w.revision_used != w.current_revision)
+            })
+            .count()
+    }
+
+    /// Findings a reviewer must resolve before release
+    pub fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        let open = self.open_steps();
+        if open > 0 {
+            findings.push(format!("{} step(s) not completed", open));
+        }
+        for step in &self.steps {
+            if step.status == ExecutionStatus::Completed
+                && step.signature_required
+                && step.signature_verified != Some(true)
+            {
+                findings.push(format!(
+                    "Step {} requires a signature but is unsigned",
+                    step.sequence
+                ));
+            }
+            if let SignatureCheck::Invalid(_) = step.commit_signature {
+                findings.push(format!(
+                    "Step {} commit signature is invalid",
+                    step.sequence
+                ));
+            }
+        }
+        for dev in &self.deviations {
+            if matches!(dev.dev_status, DevStatus::Pending | DevStatus::Rejected) {
+                findings.push(format!(
+                    "Deviation {} is {}",
+                    dev.deviation_number.as_deref().unwrap_or(&dev.id),
+                    dev.dev_status
+                ));
+            }
+        }
+        for ncr in &self.ncrs {
+            if ncr.ncr_status != "closed" {
+                findings.push(format!(
+                    "NCR {} is {}",
+                    ncr.ncr_number.as_deref().unwrap_or(&ncr.id),
+                    ncr.ncr_status
+                ));
+            }
+        }
+        for rslt in &self.results {
+            if rslt.verdict != "pass" {
+                findings.push(format!("Result {} verdict is {}", rslt.id, rslt.verdict));
+            }
+        }
+        findings
+    }
+
+    /// Render the DHR as a markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        let opt_date =
+            |d: &Option<NaiveDate>| d.map(|d| d.to_string()).unwrap_or_else(|| "-".into());
+        let opt_rev = |r: &Option<u32>| r.map(|r| r.to_string()).unwrap_or_else(|| "-".into());
+
+        md.push_str(&format!(
+            "# Device History Record: {}\n\n",
+            self.lot_number.as_deref().unwrap_or(&self.lot)
+        ));
+        md.push_str("| Field | Value |\n|---|---|\n");
+        md.push_str(&format!("| Lot | {} |\n", self.lot));
+        md.push_str(&format!("| Title | {} |\n", self.title));
+        md.push_str(&format!("| Lot Status | {} |\n", self.lot_status));
+        md.push_str(&format!(
+            "| Quantity | {} |\n",
+            self.quantity.map(|q| q.to_string()).unwrap_or("-".into())
+        ));
+        md.push_str(&format!(
+            "| Product | {} {} {} |\n",
+            opt(&self.product_part_number),
+            self.product_revision
+                .as_ref()
+                .map(|r| format!("rev {}", r))
+                .unwrap_or_default(),
+            opt(&self.product_title)
+        ));
+        md.push_str(&format!(
+            "| Start Date | {} |\n",
+            opt_date(&self.start_date)
+        ));
+        md.push_str(&format!(
+            "| Completion Date | {} |\n",
+            opt_date(&self.completion_date)
+        ));
+        md.push_str(&format!("| Serialized Units | {} |\n", self.serial_count));
+        if !self.parent_lots.is_empty() {
+            md.push_str(&format!(
+                "| Parent Lots | {} |\n",
+                self.parent_lots.join(", ")
+            ));
+        }
+        if !self.child_lots.is_empty() {
+            md.push_str(&format!(
+                "| Child Lots | {} |\n",
+                self.child_lots.join(", ")
+            ));
+        }
+
+        md.push_str("\n## Review Findings\n\n");
+        let findings = self.findings();
+        if findings.is_empty() {
+            md.push_str("No open findings.\n");
+        } else {
+            for f in &findings {
+                md.push_str(&format!("- {}\n", f));
+            }
+        }
+
+        md.push_str("\n## Process Execution (As-Run)\n\n");
+        if self.steps.is_empty() {
+            md.push_str("No execution steps recorded.\n");
+        } else {
+            md.push_str("| # | Op | Process | Rev (run/now) | Status | Operator | Completed | Signed | Commit |\n");
+            md.push_str("|---|---|---|---|---|---|---|---|---|\n");
+            for s in &self.steps {
+                let rev_flag = if s.process_revision.is_some()
+                    && s.process_revision != s.current_process_revision
+                {
+                    " ⚠"
+                } else {
+                    ""
+                };
+                md.push_str(&format!(
+                    "| {} | {} | {} | {}/{}{} | {} | {} | {} | {} | {} |\n",
+                    s.sequence,
+                    opt(&s.operation_number),
+                    s.process_title
+                        .clone()
+                        .or_else(|| s.process.clone())
+                        .unwrap_or_else(|| "(unlinked)".into()),
+                    opt_rev(&s.process_revision),
+                    opt_rev(&s.current_process_revision),
+                    rev_flag,
+                    s.status,
+                    opt(&s.operator),
+                    opt_date(&s.completed_date),
+                    match s.signature_verified {
+                        Some(true) => "yes",
+                        _ if s.signature_required => "REQUIRED",
+                        _ => "no",
+                    },
+                    s.commit_sha
+                        .as_ref()
+                        .map(|c| format!("{} ({})", &c[..c.len().min(8)], s.commit_signature))
+                        .unwrap_or_else(|| "-".into()),
+                ));
+            }
+
+            for s in &self.steps {
+                let has_detail = !s.work_instructions.is_empty()
+                    || !s.approvals.is_empty()
+                    || !s.data.is_empty()
+                    || s.deviation.is_some()
+                    || s.notes.is_some();
+                if !has_detail {
+                    continue;
+                }
+                md.push_str(&format!(
+                    "\n### Step {}: {}\n\n",
+                    s.sequence,
+                    s.process_title
+                        .clone()
+                        .or_else(|| s.process.clone())
+                        .unwrap_or_default()
+                ));
+                for wi in &s.work_instructions {
+                    md.push_str(&format!(
+                        "- Work instruction {} {} (rev used: {}, current: {})\n",
+                        wi.document_number.as_deref().unwrap_or(&wi.id),
+                        wi.title.as_deref().unwrap_or(""),
+                        opt_rev(&wi.revision_used),
+                        opt_rev(&wi.current_revision)
+                    ));
+                }
+                for a in &s.approvals {
+                    md.push_str(&format!(
+                        "- Approved by {}{} on {}{}\n",
+                        a.approver,
+                        a.role
+                            .as_ref()
+                            .map(|r| format!(" ({})", r))
+                            .unwrap_or_default(),
+                        a.timestamp.format("%Y-%m-%d %H:%M UTC"),
+                        if a.signature_verified == Some(true) {
+                            " [signed]"
+                        } else {
+                            ""
+                        }
+                    ));
+                }
+                if !s.data.is_empty() {
+                    let mut keys: Vec<_> = s.data.keys().collect();
+                    keys.sort();
+                    for k in keys {
+                        md.push_str(&format!("- {}: {}\n", k, s.data[k]));
+                    }
+                }
+                if let Some(ref dev) = s.deviation {
+                    md.push_str(&format!("- Performed under deviation {}\n", dev));
+                }
+                if let Some(ref n) = s.notes {
+                    md.push_str(&format!("- Notes: {}\n", n));
+                }
+            }
+        }
+
+        md.push_str("\n## Materials\n\n");
+        if self.materials.is_empty() {
+            md.push_str("No materials recorded.\n");
+        } else {
+            md.push_str("| Component | Supplier Lot | Source Lot | Qty |\n|---|---|---|---|\n");
+            for m in &self.materials {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    opt(&m.component),
+                    opt(&m.supplier_lot),
+                    opt(&m.source_lot),
+                    m.quantity.map(|q| q.to_string()).unwrap_or("-".into())
+                ));
+            }
+        }
+
+        md.push_str("\n## Deviations in Effect\n\n");
+        if self.deviations.is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str("| Deviation | Title | Status | Effective | Expires | Approved By | Applies Via |\n");
+            md.push_str("|---|---|---|---|---|---|---|\n");
+            for d in &self.deviations {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |\n",
+                    d.deviation_number.as_deref().unwrap_or(&d.id),
+                    d.title,
+                    d.dev_status,
+                    opt_date(&d.effective_date),
+                    opt_date(&d.expiration_date),
+                    opt(&d.approved_by),
+                    d.applies_via
+                ));
+            }
+        }
+
+        md.push_str("\n## Nonconformances\n\n");
+        if self.ncrs.is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str(
+                "| NCR | Title | Severity | Status | Disposition |\n|---|---|---|---|---|\n",
+            );
+            for n in &self.ncrs {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    n.ncr_number.as_deref().unwrap_or(&n.id),
+                    n.title,
+                    n.severity,
+                    n.ncr_status,
+                    opt(&n.disposition)
+                ));
+            }
+        }
+
+        md.push_str("\n## Test Results\n\n");
+        if self.results.is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str(
+                "| Result | Test | Verdict | Executed By | Date |\n|---|---|---|---|---|\n",
+            );
+            for r in &self.results {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    r.id,
+                    r.test,
+                    r.verdict,
+                    r.executed_by,
+                    r.executed_date.format("%Y-%m-%d")
+                ));
+            }
+        }
+
+        md.push_str("\n## Signatures\n\n");
+        let sig = &self.signatures;
+        md.push_str(&format!(
+            "- Steps requiring signature: {} ({} signed)\n",
+            sig.steps_requiring_signature, sig.steps_signed
+        ));
+        md.push_str(&format!(
+            "- Commit signatures: {} verified, {} unsigned, {} invalid\n",
+            sig.commits_verified, sig.commits_unsigned, sig.commits_invalid
+        ));
+        md.push_str(&format!("- Step approvals: {}\n", sig.approvals));
+
+        md.push_str("\n## Records\n\n| Entity | File | SHA-256 |\n|---|---|---|\n");
+        for r in &self.records {
+            md.push_str(&format!("| {} | {} | `{}` |\n", r.id, r.path, r.sha256));
+        }
+
+        md.push_str(&format!(
+            "\n*Generated {} by {}{}*\n",
+            self.generated.format("%Y-%m-%d %H:%M UTC"),
+            self.generated_by,
+            self.git_commit
+                .as_ref()
+                .map(|c| format!(" at commit {}", c))
+                .unwrap_or_default()
+        ));
+        md
+    }
+
+    /// Render the DHR as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let body = markdown_tables_to_html(&self.to_markdown());
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>DHR {}</title>\n\
+             <style>body{{font-family:sans-serif;max-width:1100px;margin:2em auto}}\
+             table{{border-collapse:collapse;margin:1em 0}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}\
+             th{{background:#f0f0f0}}code{{font-size:0.85em}}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            html_escape(self.lot_number.as_deref().unwrap_or(&self.lot)),
+            body
+        )
+    }
+}
+
+/// Escape text for HTML
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Convert the small markdown subset used by TDT reports (headings, pipe
+/// tables, bullet lists, emphasis lines, inline code) to HTML
+pub fn markdown_tables_to_html(md: &str) -> String {
+    fn inline(s: &str) -> String {
+        let escaped = html_escape(s);
+        let mut out = String::new();
+        for (i, part) in escaped.split('`').enumerate() {
+            if i % 2 == 1 {
+                out.push_str(&format!("<code>{}</code>", part));
+            } else {
+                out.push_str(part);
+            }
+        }
+        out
+    }
+    fn cells(line: &str) -> Vec<&str> {
+        line.trim()
+            .trim_start_matches('|')
+            .trim_end_matches('|')
+            .split('|')
+            .map(|c| c.trim())
+            .collect()
+    }
+
+    let mut html = String::new();
+    let lines: Vec<&str> = md.lines().collect();
+    let mut i = 0;
+    let mut in_list = false;
+    while i < lines.len() {
+        let line = lines[i];
+        if in_list && !line.starts_with("- ") {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+        if let Some(h) = line.strip_prefix("### ") {
+            html.push_str(&format!("<h3>{}</h3>\n", inline(h)));
+        } else if let Some(h) = line.strip_prefix("## ") {
+            html.push_str(&format!("<h2>{}</h2>\n", inline(h)));
+        } else if let Some(h) = line.strip_prefix("# ") {
+            html.push_str(&format!("<h1>{}</h1>\n", inline(h)));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline(item)));
+        } else if line.starts_with('|') {
+            html.push_str("<table>\n<tr>");
+            for c in cells(line) {
+                html.push_str(&format!("<th>{}</th>", inline(c)));
+            }
+            html.push_str("</tr>\n");
+            i += 1;
+            // skip separator row
+            if i < lines.len() && lines[i].starts_with("|---") {
+                i += 1;
+            }
+            while i < lines.len() && lines[i].starts_with('|') {
+                html.push_str("<tr>");
+                for c in cells(lines[i]) {
+                    html.push_str(&format!("<td>{}</td>", inline(c)));
+                }
+                html.push_str("</tr>\n");
+                i += 1;
+            }
+            html.push_str("</table>\n");
+            continue;
+        } else if line.starts_with('*') && line.ends_with('*') && line.len() > 1 {
+            html.push_str(&format!(
+                "<p><em>{}</em></p>\n",
+                inline(line.trim_matches('*'))
+            ));
+        } else if !line.trim().is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", inline(line)));
+        }
+        i += 1;
+    }
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_table_to_html() {
+        let md = "# Title\n\n| A | B |\n|---|---|\n| 1 | <x> |\n\n- item `code`\n";
+        let html = markdown_tables_to_html(md);
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<th>A</th><th>B</th>"));
+        assert!(html.contains("<td>1</td><td>&lt;x&gt;</td>"));
+        assert!(html.contains("<li>item <code>code</code></li>"));
+    }
+
+    #[test]
+    fn test_signature_check_display() {
+        assert_eq!(SignatureCheck::Unsigned.to_string(), "unsigned");
+        assert_eq!(
+            SignatureCheck::Verified("Jane".into()).to_string(),
+            "verified (Jane)"
+        );
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "abc").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,747 @@
+//! 8D problem-solving report assembled from a CAPA and its linked entities
+//!
+//! Each discipline (D0-D8) is filled from existing data: linked NCRs supply
+//! the problem detail and containment, the CAPA supplies the root cause,
+//! actions, effectiveness and closure. Disciplines without enough data are
+//! reported as gaps rather than left silently empty.
+
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::entities::capa::{ActionItem, ActionStatus, ActionType, Capa, CapaStatus};
+use crate::entities::control::Control;
+use crate::entities::ncr::{ContainmentStatus, DetectionStage, Ncr};
+use crate::entities::process::Process;
+use crate::entities::risk::Risk;
+
+/// Entities an 8D report is built from
+pub struct EightDInputs<'a> {
+    pub capa: &'a Capa,
+    /// All NCRs in the project; linked ones are selected by the builder
+    pub ncrs: &'a [Ncr],
+    pub risks: &'a [Risk],
+    pub processes: &'a [Process],
+    pub controls: &'a [Control],
+}
+
+/// One discipline of the 8D report
+#[derive(Debug, Clone, Serialize)]
+pub struct Discipline {
+    /// "D0" .. "D8"
+    pub code: String,
+    pub title: String,
+    /// Markdown body
+    pub content: String,
+    /// Missing or incomplete data
+    pub gaps: Vec<String>,
+}
+
+impl Discipline {
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// A complete D0-D8 report
+#[derive(Debug, Clone, Serialize)]
+pub struct EightDReport {
+    pub capa: String,
+    pub capa_number: Option<String>,
+    pub title: String,
+    pub capa_status: CapaStatus,
+    /// NCR IDs linked to the CAPA
+    pub ncrs: Vec<String>,
+    pub generated: NaiveDate,
+    pub disciplines: Vec<Discipline>,
+}
+
+/// Placeholder text left by templates ("# Describe ...") counts as missing
+fn has_text(s: &Option<String>) -> bool {
+    s.as_ref()
+        .map(|t| t.trim())
+        .is_some_and(|t| !t.is_empty() && !t.starts_with('#'))
+}
+
+/// Make text safe for a single markdown table cell
+fn cell(s: &str) -> String {
+    let flat = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.is_empty() {
+        "-".to_string()
+    } else {
+        flat.replace('|', "/")
+    }
+}
+
+fn opt_date(d: Option<NaiveDate>) -> String {
+    d.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn detection_stage_name(stage: DetectionStage) -> &'static str {
+    match stage {
+        DetectionStage::Incoming => "incoming",
+        DetectionStage::InProcess => "in process",
+        DetectionStage::Final => "final",
+        DetectionStage::Customer => "customer",
+        DetectionStage::Field => "field",
+    }
+}
+
+fn action_done(action: &ActionItem) -> bool {
+    matches!(
+        action.status,
+        ActionStatus::Completed | ActionStatus::Verified
+    )
+}
+
+fn action_table(actions: &[&ActionItem]) -> String {
+    let mut md = String::from("| # | Action | Owner | Due | Completed | Status | Evidence |\n");
+    md.push_str("|---|---|---|---|---|---|---|\n");
+    for a in actions {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            a.action_number,
+            cell(&a.description),
+            cell(a.owner.as_deref().unwrap_or("")),
+            opt_date(a.due_date),
+            opt_date(a.completed_date),
+            a.status,
+            cell(a.evidence.as_deref().unwrap_or(""))
+        ));
+    }
+    md
+}
+
+/// Build the 8D report for a CAPA
+pub fn build_report(inputs: &EightDInputs) -> EightDReport {
+    let capa = inputs.capa;
+    let capa_id = capa.id.to_string();
+    let ncrs: Vec<&Ncr> = inputs
+        .ncrs
+        .iter()
+        .filter(|n| capa.links.ncrs.contains(&n.id) || n.links.capa.as_ref() == Some(&capa.id))
+        .collect();
+    let rca = capa.root_cause_analysis.as_ref();
+
+    let mut disciplines = Vec::new();
+
+    // D0 - Preparation and emergency response
+    {
+        let mut md = String::from("| Field | Value |\n|---|---|\n");
+        md.push_str(&format!("| CAPA | {} |\n", capa_id));
+        if let Some(ref num) = capa.capa_number {
+            md.push_str(&format!("| CAPA Number | {} |\n", cell(num)));
+        }
+        md.push_str(&format!("| Type | {} |\n", capa.capa_type));
+        if let Some(ref source) = capa.source {
+            md.push_str(&format!(
+                "| Source | {} {} |\n",
+                source.source_type,
+                source.reference.as_deref().unwrap_or("")
+            ));
+        }
+        let timeline = capa.timeline.as_ref();
+        md.push_str(&format!(
+            "| Initiated | {} |\n",
+            opt_date(timeline.and_then(|t| t.initiated_date))
+        ));
+        md.push_str(&format!(
+            "| Target Date | {} |\n",
+            opt_date(timeline.and_then(|t| t.target_date))
+        ));
+        let mut gaps = Vec::new();
+        if capa.source.is_none() && ncrs.is_empty() {
+            gaps.push("no source or linked NCR recorded".to_string());
+        }
+        if !ncrs.is_empty() {
+            md.push_str("\n| NCR | Title | Type | Severity | Reported | Status |\n");
+            md.push_str("|---|---|---|---|---|---|\n");
+            for n in &ncrs {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} |\n",
+                    n.id,
+                    cell(&n.title),
+                    n.ncr_type,
+                    n.severity,
+                    opt_date(n.report_date),
+                    n.ncr_status
+                ));
+            }
+        }
+        disciplines.push(Discipline {
+            code: "D0".into(),
+            title: "Preparation and Emergency Response".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D1 - Team
+    {
+        let mut team: Vec<(String, String)> = vec![(capa.author.clone(), "CAPA owner".into())];
+        let mut add = |name: &str, role: String| {
+            let name = name.trim();
+            if name.is_empty() {
+                return;
+            }
+            match team.iter_mut().find(|(n, _)| n == name) {
+                Some((_, roles)) => {
+                    if !roles.split(", ").any(|r| r == role) {
+                        roles.push_str(&format!(", {}", role));
+                    }
+                }
+                None => team.push((name.to_string(), role)),
+            }
+        };
+        for a in &capa.actions {
+            if let Some(ref owner) = a.owner {
+                add(owner, "action owner".into());
+            }
+        }
+        for n in &ncrs {
+            if let Some(ref by) = n.detection.as_ref().and_then(|d| d.found_by.clone()) {
+                add(by, "detection".into());
+            }
+            for c in &n.containment {
+                if let Some(ref by) = c.completed_by {
+                    add(by, "containment".into());
+                }
+            }
+            if let Some(ref by) = n.disposition.as_ref().and_then(|d| d.decision_by.clone()) {
+                add(by, "disposition".into());
+            }
+        }
+        if let Some(ref by) = capa.closure.as_ref().and_then(|c| c.closed_by.clone()) {
+            add(by, "closure".into());
+        }
+
+        let mut md = String::from("| Member | Role |\n|---|---|\n");
+        for (name, role) in &team {
+            md.push_str(&format!("| {} | {} |\n", cell(name), role));
+        }
+        let mut gaps = Vec::new();
+        if !capa.actions.iter().any(|a| has_text(&a.owner)) {
+            gaps.push("no action owners assigned".to_string());
+        }
+        disciplines.push(Discipline {
+            code: "D1".into(),
+            title: "Team".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D2 - Problem description
+    {
+        let mut md = String::new();
+        let mut gaps = Vec::new();
+        if has_text(&capa.problem_statement) {
+            md.push_str(capa.problem_statement.as_deref().unwrap_or("").trim());
+            md.push_str("\n\n");
+        } else {
+            gaps.push("problem statement is empty".to_string());
+        }
+        let detailed: Vec<&&Ncr> = ncrs
+            .iter()
+            .filter(|n| n.defect.is_some() || n.affected_items.is_some() || n.detection.is_some())
+            .collect();
+        if !detailed.is_empty() {
+            md.push_str("| NCR | Characteristic | Specification | Actual | Part | Lot | Qty | Found At | Found |\n");
+            md.push_str("|---|---|---|---|---|---|---|---|---|\n");
+            for n in detailed {
+                let defect = n.defect.clone().unwrap_or_default();
+                let items = n.affected_items.clone().unwrap_or_default();
+                let detection = n.detection.as_ref();
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                    n.id,
+                    cell(defect.characteristic.as_deref().unwrap_or("")),
+                    cell(defect.specification.as_deref().unwrap_or("")),
+                    cell(defect.actual.as_deref().unwrap_or("")),
+                    cell(items.part_number.as_deref().unwrap_or("")),
+                    cell(items.lot_number.as_deref().unwrap_or("")),
+                    items
+                        .quantity_affected
+                        .map(|q| q.to_string())
+                        .unwrap_or_else(|| "-".into()),
+                    detection
+                        .map(|d| detection_stage_name(d.found_at))
+                        .unwrap_or("-"),
+                    opt_date(detection.and_then(|d| d.found_date))
+                ));
+            }
+        } else if ncrs.is_empty() {
+            gaps.push("no linked NCR with defect details".to_string());
+        }
+        disciplines.push(Discipline {
+            code: "D2".into(),
+            title: "Problem Description".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D3 - Containment
+    {
+        let mut md = String::new();
+        let mut gaps = Vec::new();
+        let actions: Vec<_> = ncrs
+            .iter()
+            .flat_map(|n| n.containment.iter().map(move |c| (n, c)))
+            .collect();
+        if actions.is_empty() {
+            gaps.push("no containment actions recorded on linked NCRs".to_string());
+        } else {
+            md.push_str("| NCR | Action | Date | By | Status |\n|---|---|---|---|---|\n");
+            for (n, c) in &actions {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    n.id,
+                    cell(&c.action),
+                    opt_date(c.date),
+                    cell(c.completed_by.as_deref().unwrap_or("")),
+                    match c.status {
+                        ContainmentStatus::Open => "open",
+                        ContainmentStatus::Completed => "completed",
+                    }
+                ));
+            }
+            let open = actions
+                .iter()
+                .filter(|(_, c)| c.status != ContainmentStatus::Completed)
+                .count();
+            if open > 0 {
+                gaps.push(format!("{} containment action(s) still open", open));
+            }
+        }
+        disciplines.push(Discipline {
+            code: "D3".into(),
+            title: "Containment Actions".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D4 - Root cause
+    {
+        let mut md = String::new();
+        let mut gaps = Vec::new();
+        match rca {
+            Some(rca) => {
+                md.push_str(&format!("**Method:** {}\n\n", rca.method));
+                if has_text(&rca.root_cause) {
+                    md.push_str(&format!(
+                        "**Root Cause:** {}\n\n",
+                        rca.root_cause.as_deref().unwrap_or("").trim()
+                    ));
+                } else {
+                    gaps.push("root cause not identified".to_string());
+                }
+                if !rca.five_whys.is_empty() {
+                    md.push_str("| Why | Because | Evidence |\n|---|---|---|\n");
+                    for (i, step) in rca.five_whys.iter().enumerate() {
+                        md.push_str(&format!(
+                            "| {}. {} | {} | {} |\n",
+                            i + 1,
+                            cell(step.why.as_deref().unwrap_or("Why?")),
+                            cell(&step.because),
+                            cell(step.evidence.as_deref().unwrap_or(""))
+                        ));
+                    }
+                    md.push('\n');
+                }
+                if !rca.fishbone.is_empty() {
+                    md.push_str(
+                        "| Category | Cause | Sub-causes | Confirmed |\n|---|---|---|---|\n",
+                    );
+                    for c in &rca.fishbone {
+                        md.push_str(&format!(
+                            "| {} | {} | {} | {} |\n",
+                            c.category,
+                            cell(&c.cause),
+                            cell(&c.sub_causes.join("; ")),
+                            if c.confirmed { "yes" } else { "no" }
+                        ));
+                    }
+                    md.push('\n');
+                }
+                for factor in &rca.contributing_factors {
+                    md.push_str(&format!("- Contributing factor: {}\n", factor));
+                }
+            }
+            None => gaps.push("no root cause analysis recorded".to_string()),
+        }
+        disciplines.push(Discipline {
+            code: "D4".into(),
+            title: "Root Cause Analysis".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    let corrective: Vec<&ActionItem> = capa
+        .actions
+        .iter()
+        .filter(|a| a.action_type == ActionType::Corrective)
+        .collect();
+    let preventive: Vec<&ActionItem> = capa
+        .actions
+        .iter()
+        .filter(|a| a.action_type == ActionType::Preventive)
+        .collect();
+
+    // D5 - Permanent corrective actions
+    {
+        let mut gaps = Vec::new();
+        let md = if corrective.is_empty() {
+            gaps.push("no corrective actions defined".to_string());
+            String::new()
+        } else {
+            for a in &corrective {
+                if !has_text(&a.owner) {
+                    gaps.push(format!("action {} has no owner", a.action_number));
+                }
+                if a.due_date.is_none() {
+                    gaps.push(format!("action {} has no due date", a.action_number));
+                }
+            }
+            action_table(&corrective)
+        };
+        disciplines.push(Discipline {
+            code: "D5".into(),
+            title: "Permanent Corrective Actions".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D6 - Implementation and validation
+    {
+        let mut md = String::new();
+        let mut gaps = Vec::new();
+        let pending = corrective.iter().filter(|a| !action_done(a)).count();
+        if !corrective.is_empty() {
+            md.push_str(&format!(
+                "{} of {} corrective action(s) implemented.\n\n",
+                corrective.len() - pending,
+                corrective.len()
+            ));
+        }
+        if pending > 0 {
+            gaps.push(format!("{} corrective action(s) not completed", pending));
+        }
+        match capa.effectiveness.as_ref().filter(|e| e.verified) {
+            Some(e) => {
+                md.push_str("| Field | Value |\n|---|---|\n");
+                md.push_str(&format!(
+                    "| Result | {} |\n",
+                    e.result
+                        .map(|r| r.to_string())
+                        .unwrap_or_else(|| "-".into())
+                ));
+                md.push_str(&format!("| Verified | {} |\n", opt_date(e.verified_date)));
+                md.push_str(&format!(
+                    "| Evidence | {} |\n",
+                    cell(e.evidence.as_deref().unwrap_or(""))
+                ));
+            }
+            None => gaps.push("effectiveness not verified".to_string()),
+        }
+        disciplines.push(Discipline {
+            code: "D6".into(),
+            title: "Implementation and Validation".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D7 - Prevent recurrence
+    {
+        let mut md = String::new();
+        if !preventive.is_empty() {
+            md.push_str(&action_table(&preventive));
+            md.push('\n');
+        }
+
+        let mut risk_ids: Vec<&crate::core::identity::EntityId> = capa.links.risks.iter().collect();
+        if let Some(rca) = rca {
+            for (id, _) in rca.implicated_risks() {
+                if !risk_ids.contains(&id) {
+                    risk_ids.push(id);
+                }
+            }
+        }
+        let mut updates = String::new();
+        for id in &risk_ids {
+            let risk = inputs.risks.iter().find(|r| &&r.id == id);
+            updates.push_str(&format!(
+                "| Risk (FMEA) | {} | {} | {} |\n",
+                id,
+                cell(risk.map(|r| r.title.as_str()).unwrap_or("")),
+                risk.and_then(|r| r.rpn)
+                    .map(|r| format!("RPN {}", r))
+                    .unwrap_or_else(|| "-".into())
+            ));
+        }
+        for id in &capa.links.processes_modified {
+            let title = inputs
+                .processes
+                .iter()
+                .find(|p| &p.id == id)
+                .map(|p| p.title.as_str())
+                .unwrap_or("");
+            updates.push_str(&format!(
+                "| Process modified | {} | {} | - |\n",
+                id,
+                cell(title)
+            ));
+        }
+        for id in &capa.links.controls_added {
+            let title = inputs
+                .controls
+                .iter()
+                .find(|c| &c.id == id)
+                .map(|c| c.title.as_str())
+                .unwrap_or("");
+            updates.push_str(&format!(
+                "| Control added | {} | {} | - |\n",
+                id,
+                cell(title)
+            ));
+        }
+        if !updates.is_empty() {
+            md.push_str("| Change | ID | Title | Note |\n|---|---|---|---|\n");
+            md.push_str(&updates);
+        }
+
+        let mut gaps = Vec::new();
+        if preventive.is_empty()
+            && capa.links.processes_modified.is_empty()
+            && capa.links.controls_added.is_empty()
+        {
+            gaps.push(
+                "no preventive actions, modified processes or added controls recorded".to_string(),
+            );
+        }
+        let pending = preventive.iter().filter(|a| !action_done(a)).count();
+        if pending > 0 {
+            gaps.push(format!("{} preventive action(s) not completed", pending));
+        }
+        disciplines.push(Discipline {
+            code: "D7".into(),
+            title: "Prevent Recurrence".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    // D8 - Closure and recognition
+    {
+        let mut md = String::new();
+        let mut gaps = Vec::new();
+        match capa.closure.as_ref().filter(|c| c.closed) {
+            Some(c) => {
+                md.push_str("| Field | Value |\n|---|---|\n");
+                md.push_str(&format!("| Closed | {} |\n", opt_date(c.closed_date)));
+                md.push_str(&format!(
+                    "| Closed By | {} |\n",
+                    cell(c.closed_by.as_deref().unwrap_or(""))
+                ));
+            }
+            None if capa.capa_status == CapaStatus::Closed => {
+                md.push_str("CAPA status is closed.\n");
+            }
+            None => gaps.push(format!("CAPA is not closed (status: {})", capa.capa_status)),
+        }
+        disciplines.push(Discipline {
+            code: "D8".into(),
+            title: "Closure and Team Recognition".into(),
+            content: md,
+            gaps,
+        });
+    }
+
+    EightDReport {
+        capa: capa_id,
+        capa_number: capa.capa_number.clone(),
+        title: capa.title.clone(),
+        capa_status: capa.capa_status,
+        ncrs: ncrs.iter().map(|n| n.id.to_string()).collect(),
+        generated: Utc::now().date_naive(),
+        disciplines,
+    }
+}
+
+impl EightDReport {
+    /// Disciplines with gaps, as "D3 Containment Actions: ..." lines
+    pub fn gaps(&self) -> Vec<String> {
+        self.disciplines
+            .iter()
+            .flat_map(|d| {
+                d.gaps
+                    .iter()
+                    .map(move |g| format!("{} {}: {}", d.code, d.title, g))
+            })
+            .collect()
+    }
+
+    /// Render as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# 8D Report: {}\n\n",
+            self.capa_number.as_deref().unwrap_or(&self.title)
+        );
+        md.push_str("| Field | Value |\n|---|---|\n");
+        md.push_str(&format!("| CAPA | {} |\n", self.capa));
+        md.push_str(&format!("| Title | {} |\n", cell(&self.title)));
+        md.push_str(&format!("| Status | {} |\n", self.capa_status));
+        md.push_str(&format!("| Generated | {} |\n", self.generated));
+
+        md.push_str("\n## Summary\n\n| Discipline | Status |\n|---|---|\n");
+        for d in &self.disciplines {
+            md.push_str(&format!(
+                "| {} {} | {} |\n",
+                d.code,
+                d.title,
+                if d.is_complete() {
+                    "complete".to_string()
+                } else {
+                    format!("INCOMPLETE ({} gap(s))", d.gaps.len())
+                }
+            ));
+        }
+
+        for d in &self.disciplines {
+            md.push_str(&format!("\n## {} {}\n\n", d.code, d.title));
+            for gap in &d.gaps {
+                md.push_str(&format!("- **Missing:** {}\n", gap));
+            }
+            if !d.gaps.is_empty() {
+                md.push('\n');
+            }
+            if !d.content.is_empty() {
+                md.push_str(&d.content);
+                if !d.content.ends_with('\n') {
+                    md.push('\n');
+                }
+            }
+        }
+        md
+    }
+
+    /// Render as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let body = crate::core::dhr::markdown_tables_to_html(&self.to_markdown());
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>8D {}</title>\n\
+             <style>body{{font-family:sans-serif;max-width:1100px;margin:2em auto}}\
+             table{{border-collapse:collapse;margin:1em 0}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}\
+             th{{background:#f0f0f0}}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            crate::core::dhr::html_escape(&self.title),
+            body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+    use crate::entities::capa::{
+        CapaType, Closure, Effectiveness, EffectivenessResult, RootCauseAnalysis,
+    };
+    use crate::entities::ncr::ContainmentAction;
+
+    fn ncr_for(capa: &Capa) -> Ncr {
+        let mut n: Ncr = serde_yml::from_str(&format!(
+            "id: {}\ntitle: Bore oversize\ncreated: 2024-01-01T00:00:00Z\nauthor: qa\n",
+            EntityId::new(EntityPrefix::Ncr)
+        ))
+        .unwrap();
+        n.links.capa = Some(capa.id.clone());
+        n
+    }
+
+    fn action(number: u32, action_type: ActionType, status: ActionStatus) -> ActionItem {
+        ActionItem {
+            action_number: number,
+            description: format!("Action {}", number),
+            action_type,
+            owner: Some("Sam".to_string()),
+            due_date: Some("2024-06-01".parse().unwrap()),
+            completed_date: None,
+            status,
+            evidence: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_capa_flags_gaps() {
+        let capa = Capa::new("Bore oversize".into(), CapaType::Corrective, "qa".into());
+        let report = build_report(&EightDInputs {
+            capa: &capa,
+            ncrs: &[],
+            risks: &[],
+            processes: &[],
+            controls: &[],
+        });
+        assert_eq!(report.disciplines.len(), 9);
+        assert!(report.disciplines.iter().all(|d| !d.is_complete()));
+        let md = report.to_markdown();
+        assert!(md.contains("## D3 Containment Actions"));
+        assert!(md.contains("**Missing:** no containment actions recorded on linked NCRs"));
+    }
+
+    #[test]
+    fn test_complete_capa() {
+        let mut capa = Capa::new("Bore oversize".into(), CapaType::Corrective, "qa".into());
+        capa.problem_statement = Some("Bore diameter above tolerance".into());
+        capa.root_cause_analysis = Some(RootCauseAnalysis {
+            root_cause: Some("No tool life limit".into()),
+            ..Default::default()
+        });
+        capa.actions = vec![
+            action(1, ActionType::Corrective, ActionStatus::Verified),
+            action(2, ActionType::Preventive, ActionStatus::Completed),
+        ];
+        capa.effectiveness = Some(Effectiveness {
+            verified: true,
+            verified_date: Some("2024-07-01".parse().unwrap()),
+            result: Some(EffectivenessResult::Effective),
+            evidence: Some("No recurrence".into()),
+        });
+        capa.closure = Some(Closure {
+            closed: true,
+            closed_date: Some("2024-07-02".parse().unwrap()),
+            closed_by: Some("Lee".into()),
+        });
+        capa.capa_status = CapaStatus::Closed;
+
+        let mut ncr = ncr_for(&capa);
+        ncr.containment = vec![ContainmentAction {
+            action: "Quarantine lot".into(),
+            date: None,
+            completed_by: Some("Kim".into()),
+            status: ContainmentStatus::Completed,
+        }];
+        let ncrs = [ncr];
+
+        let report = build_report(&EightDInputs {
+            capa: &capa,
+            ncrs: &ncrs,
+            risks: &[],
+            processes: &[],
+            controls: &[],
+        });
+        assert!(report.gaps().is_empty(), "{:?}", report.gaps());
+        assert_eq!(report.ncrs.len(), 1);
+
+        let team = &report.disciplines[1].content;
+        assert!(team.contains("| Sam | action owner |"));
+        assert!(team.contains("| Kim | containment |"));
+        assert!(team.contains("| Lee | closure |"));
+
+        let html = report.to_html();
+        assert!(html.contains("<h2>D8 Closure and Team Recognition</h2>"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Field feedback: compare observed field failure rates with FMEA occurrence
+//!
+//! Complaints linked to a risk count as field failures of that risk's failure
+//! mode. The fielded population is the completed lots of the products and
+//! processes the risk affects, falling back to the lots the complaints
+//! themselves link to. The observed rate maps onto the occurrence scale from
+//! the risk documentation, so a risk whose rating is lower than the field
+//! data supports is flagged as understated.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::entities::complaint::Complaint;
+use crate::entities::lot::{Lot, LotStatus};
+use crate::entities::risk::Risk;
+
+/// Lower bound of the failure rate for each occurrence rating, highest first
+const OCCURRENCE_SCALE: [(u8, f64); 9] = [
+    (10, 1.0 / 2.0),
+    (9, 1.0 / 3.0),
+    (8, 1.0 / 8.0),
+    (7, 1.0 / 20.0),
+    (6, 1.0 / 80.0),
+    (5, 1.0 / 400.0),
+    (4, 1.0 / 2_000.0),
+    (3, 1.0 / 15_000.0),
+    (2, 1.0 / 150_000.0),
+];
+
+/// Occurrence rating (1-10) for an observed failure rate (failures per unit)
+pub fn occurrence_for_rate(rate: f64) -> u8 {
+    OCCURRENCE_SCALE
+        .iter()
+        .find(|(_, min)| rate >= *min)
+        .map(|(rating, _)| *rating)
+        .unwrap_or(1)
+}
+
+/// How the field data compares with the FMEA occurrence rating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldVerdict {
+    /// Field data shows a higher occurrence than rated
+    Understated,
+    /// Field data is well below the rating (3+ steps)
+    Overstated,
+    /// Field data is consistent with the rating
+    Consistent,
+    /// No fielded population known, so no rate can be computed
+    NoPopulation,
+    /// The risk has no occurrence rating
+    NotRated,
+}
+
+impl std::fmt::Display for FieldVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldVerdict::Understated => write!(f, "understated"),
+            FieldVerdict::Overstated => write!(f, "overstated"),
+            FieldVerdict::Consistent => write!(f, "consistent"),
+            FieldVerdict::NoPopulation => write!(f, "no population"),
+            FieldVerdict::NotRated => write!(f, "not rated"),
+        }
+    }
+}
+
+/// Field data for one FMEA line
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskFieldCheck {
+    pub risk: String,
+    pub title: String,
+    pub failure_mode: Option<String>,
+    /// Occurrence rating from the FMEA
+    pub occurrence: Option<u8>,
+    /// Linked complaints
+    pub complaints: usize,
+    /// Failed units from linked complaints (excluding no-fault-found)
+    pub failures: u32,
+    pub units_fielded: u32,
+    /// Failures per unit fielded
+    pub observed_rate: Option<f64>,
+    /// Occurrence rating supported by the field data
+    pub observed_occurrence: Option<u8>,
+    pub verdict: FieldVerdict,
+}
+
+impl RiskFieldCheck {
+    /// Whether the field data contradicts the FMEA rating
+    pub fn contradicted(&self) -> bool {
+        self.verdict == FieldVerdict::Understated
+    }
+}
+
+/// Units in a lot (quantity, or the number of serialized units)
+fn lot_units(lot: &Lot) -> u32 {
+    lot.quantity.unwrap_or(lot.serials.len() as u32)
+}
+
+/// Units fielded for a risk: completed lots of the products/processes it affects
+pub fn units_fielded(risk: &Risk, lots: &[Lot]) -> u32 {
+    let affects: BTreeSet<String> = risk.links.affects.iter().map(|id| id.to_string()).collect();
+    lots.iter()
+        .filter(|lot| lot.lot_status == LotStatus::Completed)
+        .filter(|lot| {
+            lot.links
+                .product
+                .as_ref()
+                .is_some_and(|p| affects.contains(p))
+                || lot.links.processes.iter().any(|p| affects.contains(p))
+        })
+        .map(lot_units)
+        .sum()
+}
+
+/// Compare field failures against the occurrence rating of every risk that
+/// has linked complaints
+///
+/// `fielded_override` replaces the lot-derived population for all risks.
+pub fn check_risks(
+    risks: &[Risk],
+    complaints: &[Complaint],
+    lots: &[Lot],
+    fielded_override: Option<u32>,
+) -> Vec<RiskFieldCheck> {
+    let mut checks = Vec::new();
+
+    for risk in risks {
+        let risk_id = risk.id.to_string();
+        let linked: Vec<&Complaint> = complaints
+            .iter()
+            .filter(|c| c.links.risks.iter().any(|r| r.to_string() == risk_id))
+            .collect();
+        if linked.is_empty() {
+            continue;
+        }
+
+        let failures: u32 = linked
+            .iter()
+            .filter(|c| c.counts_as_failure())
+            .map(|c| c.quantity)
+            .sum();
+
+        let units = fielded_override.unwrap_or_else(|| {
+            let from_affects = units_fielded(risk, lots);
+            if from_affects > 0 {
+                return from_affects;
+            }
+            // Fall back to the lots the failed units came from
+            let lot_ids: BTreeSet<String> = linked
+                .iter()
+                .flat_map(|c| c.links.lots.iter().map(|l| l.to_string()))
+                .collect();
+            lots.iter()
+                .filter(|lot| lot_ids.contains(&lot.id.to_string()))
+                .map(lot_units)
+                .sum()
+        });
+
+        let observed_rate = (units > 0).then(|| failures as f64 / units as f64);
+        let observed_occurrence = observed_rate.map(occurrence_for_rate);
+
+        let verdict = match (risk.occurrence, observed_occurrence) {
+            (None, _) => FieldVerdict::NotRated,
+            (Some(_), None) => FieldVerdict::NoPopulation,
+            (Some(rated), Some(observed)) if observed > rated => FieldVerdict::Understated,
+            (Some(rated), Some(observed)) if failures > 0 && observed + 2 < rated => {
+                FieldVerdict::Overstated
+            }
+            _ => FieldVerdict::Consistent,
+        };
+
+        checks.push(RiskFieldCheck {
+            risk: risk_id,
+            title: risk.title.clone(),
+            failure_mode: risk.failure_mode.clone(),
+            occurrence: risk.occurrence,
+            complaints: linked.len(),
+            failures,
+            units_fielded: units,
+            observed_rate,
+            observed_occurrence,
+            verdict,
+        });
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+    use crate::entities::complaint::{ComplaintType, FailureReport};
+    use crate::entities::risk::RiskType;
+
+    #[test]
+    fn test_occurrence_for_rate() {
+        assert_eq!(occurrence_for_rate(0.6), 10);
+        assert_eq!(occurrence_for_rate(0.01), 5);
+        assert_eq!(occurrence_for_rate(1.0 / 2_000.0), 4);
+        assert_eq!(occurrence_for_rate(0.0), 1);
+    }
+
+    #[test]
+    fn test_check_risks_flags_understated_occurrence() {
+        let product = EntityId::new(EntityPrefix::Asm);
+        let mut risk = Risk::new(
+            RiskType::Design,
+            "Seal leak".to_string(),
+            "Seal leaks under pressure".to_string(),
+            "test".to_string(),
+        );
+        risk.occurrence = Some(3);
+        risk.links.affects.push(product.clone());
+
+        let mut lot = Lot::new("Build 1".to_string(), "test".to_string());
+        lot.quantity = Some(500);
+        lot.lot_status = LotStatus::Completed;
+        lot.links.product = Some(product.to_string());
+
+        let mut cmpl = Complaint::new(
+            "Leaking unit".to_string(),
+            ComplaintType::Return,
+            "test".to_string(),
+        );
+        cmpl.quantity = 2;
+        cmpl.failure = Some(FailureReport {
+            failure_mode: Some("Leak".to_string()),
+            ..Default::default()
+        });
+        cmpl.links.risks.push(risk.id.clone());
+
+        let checks = check_risks(&[risk.clone()], &[cmpl.clone()], &[lot.clone()], None);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].units_fielded, 500);
+        assert_eq!(checks[0].failures, 2);
+        // 2 / 500 = 1 in 250 -> occurrence 5
+        assert_eq!(checks[0].observed_occurrence, Some(5));
+        assert!(checks[0].contradicted());
+
+        // No fault found on analysis: nothing counts against the rating
+        cmpl.failure.as_mut().unwrap().confirmed = Some(false);
+        let checks = check_risks(&[risk], &[cmpl], &[lot], None);
+        assert_eq!(checks[0].failures, 0);
+        assert_eq!(checks[0].verdict, FieldVerdict::Consistent);
+    }
+}
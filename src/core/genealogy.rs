@@ -0,0 +1,553 @@
+//! Lot genealogy and serial-number traceability
+//!
+//! Builds a genealogy graph over production lots and answers recall
+//! questions in both directions:
+//!
+//! - **Forward**: which lots and serials contain a supplier lot, lot or serial?
+//! - **Backward**: what went into a serial or lot?
+//!
+//! Edges come from lot splits/merges (`links.parent_lots` / `links.child_lots`)
+//! and from material consumption that references an in-house lot
+//! (`MaterialUsed.source_lot`) or specific serials (`MaterialUsed.serials`).
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::entities::lot::{Lot, MaterialUsed, UnitStatus};
+
+/// Starting point for a trace query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceTarget {
+    /// A production lot (LOT-xxx)
+    Lot(String),
+    /// A serialized unit
+    Serial(String),
+    /// A supplier lot number recorded in `materials_used`
+    SupplierLot(String),
+}
+
+impl std::fmt::Display for TraceTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceTarget::Lot(id) => write!(f, "lot {}", id),
+            TraceTarget::Serial(sn) => write!(f, "serial {}", sn),
+            TraceTarget::SupplierLot(sl) => write!(f, "supplier lot {}", sl),
+        }
+    }
+}
+
+/// A lot reached by a forward trace
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedLot {
+    /// Lot ID
+    pub lot: String,
+    /// Lot number (if set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_number: Option<String>,
+    /// True if the whole lot is affected, false if only some serials are
+    pub whole_lot: bool,
+    /// Lot quantity (for non-serialized lots)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+}
+
+/// A serial reached by a forward trace
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedSerial {
+    /// Serial number
+    pub serial: String,
+    /// Lot currently holding the serial
+    pub lot: String,
+    /// Unit production status
+    pub unit_status: UnitStatus,
+}
+
+/// Result of a forward trace
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ForwardTrace {
+    /// Affected lots, in discovery order
+    pub lots: Vec<AffectedLot>,
+    /// Affected serials, sorted by serial number
+    pub serials: Vec<AffectedSerial>,
+}
+
+impl ForwardTrace {
+    /// Serials that have completed production (finished goods)
+    pub fn finished_serials(&self) -> impl Iterator<Item = &AffectedSerial> {
+        self.serials
+            .iter()
+            .filter(|s| s.unit_status == UnitStatus::Completed)
+    }
+}
+
+/// Kind of node in a backward trace tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceNodeKind {
+    Lot,
+    Serial,
+    Material,
+}
+
+/// Node in a backward (where-from) trace tree
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceNode {
+    /// Node kind
+    pub kind: TraceNodeKind,
+    /// Lot ID, serial number or component ID
+    pub id: String,
+    /// Human-readable description
+    pub label: String,
+    /// Supplier lot number (material nodes only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier_lot: Option<String>,
+    /// Inputs to this node
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TraceNode>,
+}
+
+/// Genealogy graph over all lots in a project
+pub struct GenealogyGraph<'a> {
+    lots: HashMap<String, &'a Lot>,
+    order: Vec<&'a Lot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Reach {
+    WholeLot(String),
+    Unit(String, String),
+}
+
+impl<'a> GenealogyGraph<'a> {
+    /// Build a graph from a set of lots
+    pub fn new(lots: &'a [Lot]) -> Self {
+        let mut order: Vec<&Lot> = lots.iter().collect();
+        order.sort_by_key(|l| l.created);
+        Self {
+            lots: lots.iter().map(|l| (l.id.to_string(), l)).collect(),
+            order,
+        }
+    }
+
+    /// Look up a lot by ID
+    pub fn lot(&self, id: &str) -> Option<&'a Lot> {
+        self.lots.get(id).copied()
+    }
+
+    /// Find the lot currently holding a serial number
+    pub fn lot_for_serial(&self, serial: &str) -> Option<&'a Lot> {
+        self.order
+            .iter()
+            .copied()
+            .find(|l| l.find_serial(serial).is_some())
+    }
+
+    /// Classify a user-supplied query string
+    ///
+    /// Lot IDs win over serials, which win over supplier lot numbers.
+    pub fn classify(&self, query: &str) -> TraceTarget {
+        if self.lots.contains_key(query) {
+            TraceTarget::Lot(query.to_string())
+        } else if self.lot_for_serial(query).is_some() {
+            TraceTarget::Serial(query.to_string())
+        } else {
+            TraceTarget::SupplierLot(query.to_string())
+        }
+    }
+
+    /// Forward trace: everything built from the target
+    pub fn trace_forward(&self, target: &TraceTarget) -> ForwardTrace {
+        let mut queue: VecDeque<Reach> = VecDeque::new();
+
+        match target {
+            TraceTarget::Lot(id) => queue.push_back(Reach::WholeLot(id.clone())),
+            TraceTarget::Serial(sn) => {
+                if let Some(lot) = self.lot_for_serial(sn) {
+                    queue.push_back(Reach::Unit(lot.id.to_string(), sn.clone()));
+                }
+            }
+            TraceTarget::SupplierLot(sl) => {
+                let uses = |m: &MaterialUsed| m.supplier_lot.as_deref() == Some(sl.as_str());
+                for lot in &self.order {
+                    let lot_id = lot.id.to_string();
+                    if lot.materials_used.iter().any(uses) {
+                        queue.push_back(Reach::WholeLot(lot_id));
+                        continue;
+                    }
+                    for unit in &lot.serials {
+                        if unit.materials.iter().any(uses) {
+                            queue.push_back(Reach::Unit(lot_id.clone(), unit.serial.clone()));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut visited: HashSet<Reach> = HashSet::new();
+        let mut lot_order: Vec<String> = Vec::new();
+        let mut whole: HashSet<String> = HashSet::new();
+        let mut units: BTreeSet<(String, String)> = BTreeSet::new();
+
+        while let Some(reach) = queue.pop_front() {
+            if !visited.insert(reach.clone()) {
+                continue;
+            }
+            match reach {
+                Reach::WholeLot(lot_id) => {
+                    let Some(lot) = self.lot(&lot_id) else {
+                        continue;
+                    };
+                    if !lot_order.contains(&lot_id) {
+                        lot_order.push(lot_id.clone());
+                    }
+                    whole.insert(lot_id.clone());
+
+                    for child in &lot.links.child_lots {
+                        queue.push_back(Reach::WholeLot(child.clone()));
+                    }
+                    for unit in &lot.serials {
+                        queue.push_back(Reach::Unit(lot_id.clone(), unit.serial.clone()));
+                    }
+                    self.push_consumers(&mut queue, |m| {
+                        m.source_lot.as_deref() == Some(lot_id.as_str())
+                    });
+                }
+                Reach::Unit(lot_id, serial) => {
+                    if !lot_order.contains(&lot_id) {
+                        lot_order.push(lot_id.clone());
+                    }
+                    units.insert((lot_id, serial.clone()));
+                    self.push_consumers(&mut queue, |m| m.serials.contains(&serial));
+                }
+            }
+        }
+
+        let lots = lot_order
+            .iter()
+            .filter_map(|id| self.lot(id))
+            .map(|lot| AffectedLot {
+                lot: lot.id.to_string(),
+                lot_number: lot.lot_number.clone(),
+                whole_lot: whole.contains(&lot.id.to_string()),
+                quantity: lot.quantity,
+            })
+            .collect();
+
+        let mut serials: Vec<AffectedSerial> = units
+            .into_iter()
+            .filter_map(|(lot_id, serial)| {
+                let lot = self.lot(&lot_id)?;
+                let unit = lot.find_serial(&serial)?;
+                Some(AffectedSerial {
+                    serial,
+                    lot: lot_id,
+                    unit_status: unit.unit_status,
+                })
+            })
+            .collect();
+        serials.sort_by(|a, b| a.serial.cmp(&b.serial));
+
+        ForwardTrace { lots, serials }
+    }
+
+    /// Queue every lot or unit whose materials match the predicate
+    fn push_consumers<F>(&self, queue: &mut VecDeque<Reach>, matches: F)
+    where
+        F: Fn(&MaterialUsed) -> bool,
+    {
+        for lot in &self.order {
+            let lot_id = lot.id.to_string();
+            if lot.materials_used.iter().any(&matches) {
+                queue.push_back(Reach::WholeLot(lot_id.clone()));
+            }
+            for unit in &lot.serials {
+                if unit.materials.iter().any(&matches) {
+                    queue.push_back(Reach::Unit(lot_id.clone(), unit.serial.clone()));
+                }
+            }
+        }
+    }
+
+    /// Backward trace: everything that went into the target
+    ///
+    /// Returns `None` if the target lot or serial does not exist.
+    /// Supplier lots are leaves and have no backward trace.
+    pub fn trace_backward(&self, target: &TraceTarget) -> Option<TraceNode> {
+        let mut visited = HashSet::new();
+        match target {
+            TraceTarget::Lot(id) => self.lot(id).map(|lot| self.lot_node(lot, &mut visited)),
+            TraceTarget::Serial(sn) => self
+                .lot_for_serial(sn)
+                .map(|lot| self.serial_node(lot, sn, &mut visited)),
+            TraceTarget::SupplierLot(_) => None,
+        }
+    }
+
+    fn serial_node(&self, lot: &Lot, serial: &str, visited: &mut HashSet<String>) -> TraceNode {
+        let mut children = Vec::new();
+        if let Some(unit) = lot.find_serial(serial) {
+            for mat in &unit.materials {
+                children.push(self.material_node(mat, visited));
+            }
+        }
+        children.push(self.lot_node(lot, visited));
+
+        TraceNode {
+            kind: TraceNodeKind::Serial,
+            id: serial.to_string(),
+            label: format!("Serial {}", serial),
+            supplier_lot: None,
+            children,
+        }
+    }
+
+    fn lot_node(&self, lot: &Lot, visited: &mut HashSet<String>) -> TraceNode {
+        let lot_id = lot.id.to_string();
+        let mut label = format!("Lot {}", lot.label());
+        if let Some(ref origin) = lot.origin {
+            label.push_str(&format!(" ({})", origin.operation));
+        }
+
+        // Guard against cycles in hand-edited genealogy
+        if !visited.insert(lot_id.clone()) {
+            return TraceNode {
+                kind: TraceNodeKind::Lot,
+                id: lot_id,
+                label: format!("{} (see above)", label),
+                supplier_lot: None,
+                children: Vec::new(),
+            };
+        }
+
+        let mut children: Vec<TraceNode> = lot
+            .materials_used
+            .iter()
+            .map(|m| self.material_node(m, visited))
+            .collect();
+
+        for parent_id in &lot.links.parent_lots {
+            match self.lot(parent_id) {
+                Some(parent) => children.push(self.lot_node(parent, visited)),
+                None => children.push(TraceNode {
+                    kind: TraceNodeKind::Lot,
+                    id: parent_id.clone(),
+                    label: format!("Lot {} (not found)", parent_id),
+                    supplier_lot: None,
+                    children: Vec::new(),
+                }),
+            }
+        }
+
+        TraceNode {
+            kind: TraceNodeKind::Lot,
+            id: lot_id,
+            label,
+            supplier_lot: None,
+            children,
+        }
+    }
+
+    fn material_node(&self, mat: &MaterialUsed, visited: &mut HashSet<String>) -> TraceNode {
+        let component = mat.component.clone().unwrap_or_else(|| "(no CMP)".into());
+        let mut label = component.clone();
+        if let Some(ref sl) = mat.supplier_lot {
+            label.push_str(&format!(" | supplier lot {}", sl));
+        }
+        if let Some(q) = mat.quantity {
+            label.push_str(&format!(" | qty {}", q));
+        }
+
+        let mut children = Vec::new();
+        if !mat.serials.is_empty() {
+            // Specific serialized inputs: follow each serial to its own lot
+            for sn in &mat.serials {
+                match self.lot_for_serial(sn) {
+                    Some(src) => children.push(self.serial_node(src, sn, visited)),
+                    None => children.push(TraceNode {
+                        kind: TraceNodeKind::Serial,
+                        id: sn.clone(),
+                        label: format!("Serial {} (not found)", sn),
+                        supplier_lot: None,
+                        children: Vec::new(),
+                    }),
+                }
+            }
+        } else if let Some(src) = mat.source_lot.as_deref().and_then(|id| self.lot(id)) {
+            children.push(self.lot_node(src, visited));
+        }
+
+        TraceNode {
+            kind: TraceNodeKind::Material,
+            id: component,
+            label,
+            supplier_lot: mat.supplier_lot.clone(),
+            children,
+        }
+    }
+
+    /// Supplier lots reachable from a backward trace, grouped by component
+    pub fn supplier_lots(node: &TraceNode) -> BTreeMap<String, BTreeSet<String>> {
+        fn walk(node: &TraceNode, out: &mut BTreeMap<String, BTreeSet<String>>) {
+            if let Some(ref sl) = node.supplier_lot {
+                out.entry(node.id.clone()).or_default().insert(sl.clone());
+            }
+            for child in &node.children {
+                walk(child, out);
+            }
+        }
+        let mut out = BTreeMap::new();
+        walk(node, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::lot::SerialUnit;
+
+    fn material(component: &str, supplier_lot: &str) -> MaterialUsed {
+        MaterialUsed {
+            component: Some(component.to_string()),
+            supplier_lot: Some(supplier_lot.to_string()),
+            quantity: Some(1),
+            ..Default::default()
+        }
+    }
+
+    fn unit(serial: &str, status: UnitStatus) -> SerialUnit {
+        SerialUnit {
+            serial: serial.to_string(),
+            unit_status: status,
+            ..Default::default()
+        }
+    }
+
+    /// Subassembly lot (with supplier lot RES-1) feeds a top-level lot,
+    /// which is then split into a child lot.
+    fn sample_lots() -> Vec<Lot> {
+        let mut sub = Lot::new("Sub".into(), "test".into());
+        sub.lot_number = Some("SUB-1".into());
+        sub.materials_used.push(material("CMP-RES", "RES-1"));
+        sub.serials.push(unit("SUB-001", UnitStatus::Completed));
+        sub.serials.push(unit("SUB-002", UnitStatus::Completed));
+
+        let mut top = Lot::new("Top".into(), "test".into());
+        top.lot_number = Some("TOP-1".into());
+        top.materials_used.push(material("CMP-CASE", "CASE-9"));
+        let mut sn1 = unit("TOP-001", UnitStatus::Completed);
+        sn1.materials.push(MaterialUsed {
+            component: Some("CMP-SUB".into()),
+            source_lot: Some(sub.id.to_string()),
+            serials: vec!["SUB-001".into()],
+            ..Default::default()
+        });
+        top.serials.push(sn1);
+
+        let mut child = Lot::new("Top split".into(), "test".into());
+        child.lot_number = Some("TOP-1-1".into());
+        child.materials_used = top.materials_used.clone();
+        child.links.parent_lots.push(top.id.to_string());
+        child.serials.push(unit("TOP-002", UnitStatus::InProcess));
+        top.links.child_lots.push(child.id.to_string());
+
+        vec![sub, top, child]
+    }
+
+    #[test]
+    fn test_classify_target() {
+        let lots = sample_lots();
+        let graph = GenealogyGraph::new(&lots);
+        assert_eq!(
+            graph.classify(&lots[0].id.to_string()),
+            TraceTarget::Lot(lots[0].id.to_string())
+        );
+        assert_eq!(
+            graph.classify("TOP-002"),
+            TraceTarget::Serial("TOP-002".into())
+        );
+        assert_eq!(
+            graph.classify("RES-1"),
+            TraceTarget::SupplierLot("RES-1".into())
+        );
+    }
+
+    #[test]
+    fn test_forward_trace_supplier_lot_through_serial() {
+        let lots = sample_lots();
+        let graph = GenealogyGraph::new(&lots);
+        let trace = graph.trace_forward(&TraceTarget::SupplierLot("RES-1".into()));
+
+        let serials: Vec<_> = trace.serials.iter().map(|s| s.serial.as_str()).collect();
+        // SUB-002 is in the affected sub lot but was never installed
+        assert_eq!(serials, vec!["SUB-001", "SUB-002", "TOP-001"]);
+        let finished: Vec<_> = trace
+            .finished_serials()
+            .map(|s| s.serial.as_str())
+            .collect();
+        assert!(finished.contains(&"TOP-001"));
+        // Only the consuming unit is affected, not the whole top lot or its split
+        let top = trace
+            .lots
+            .iter()
+            .find(|l| l.lot_number.as_deref() == Some("TOP-1"))
+            .unwrap();
+        assert!(!top.whole_lot);
+        assert!(!trace
+            .lots
+            .iter()
+            .any(|l| l.lot_number.as_deref() == Some("TOP-1-1")));
+    }
+
+    #[test]
+    fn test_forward_trace_follows_splits() {
+        let lots = sample_lots();
+        let graph = GenealogyGraph::new(&lots);
+        let trace = graph.trace_forward(&TraceTarget::SupplierLot("CASE-9".into()));
+
+        assert_eq!(trace.lots.len(), 2);
+        assert!(trace.lots.iter().all(|l| l.whole_lot));
+        let serials: Vec<_> = trace.serials.iter().map(|s| s.serial.as_str()).collect();
+        assert_eq!(serials, vec!["TOP-001", "TOP-002"]);
+    }
+
+    #[test]
+    fn test_backward_trace_serial() {
+        let lots = sample_lots();
+        let graph = GenealogyGraph::new(&lots);
+        let tree = graph
+            .trace_backward(&TraceTarget::Serial("TOP-001".into()))
+            .unwrap();
+
+        assert_eq!(tree.kind, TraceNodeKind::Serial);
+        let supplier_lots = GenealogyGraph::supplier_lots(&tree);
+        assert!(supplier_lots["CMP-RES"].contains("RES-1"));
+        assert!(supplier_lots["CMP-CASE"].contains("CASE-9"));
+    }
+
+    #[test]
+    fn test_backward_trace_split_lot_reaches_parent() {
+        let lots = sample_lots();
+        let graph = GenealogyGraph::new(&lots);
+        let tree = graph
+            .trace_backward(&TraceTarget::Lot(lots[2].id.to_string()))
+            .unwrap();
+
+        assert!(tree
+            .children
+            .iter()
+            .any(|c| c.kind == TraceNodeKind::Lot && c.id == lots[1].id.to_string()));
+    }
+
+    #[test]
+    fn test_backward_trace_cycle_guard() {
+        let mut lots = sample_lots();
+        let top_id = lots[1].id.to_string();
+        let child_id = lots[2].id.to_string();
+        lots[1].links.parent_lots.push(child_id);
+        let graph = GenealogyGraph::new(&lots);
+        // Must terminate
+        let tree = graph.trace_backward(&TraceTarget::Lot(top_id)).unwrap();
+        assert_eq!(tree.kind, TraceNodeKind::Lot);
+    }
+}
@@ -471,6 +471,19 @@ impl Git {
         }
     }
 
+    /// Amend the last commit with the staged changes, signing it again
+    pub fn amend_signed(&self) -> Result<String, GitError> {
+        let output = self.run(&["commit", "--amend", "--no-edit", "-S"])?;
+        if output.success {
+            let hash_output = self.run(&["rev-parse", "HEAD"])?;
+            Ok(hash_output.stdout)
+        } else {
+            Err(GitError::CommandFailed {
+                message: output.stderr,
+            })
+        }
+    }
+
     /// Verify the signature of a commit
     /// Returns Ok(Some(signer)) if valid, Ok(None) if no signature, Err if invalid
     pub fn verify_commit_signature(&self, commit: &str) -> Result<Option<String>, GitError> {
@@ -743,6 +756,86 @@ impl Git {
         }
     }
 
+    /// Contents of the files under a directory as of a reference
+    ///
+    /// `dir` is relative to the repository path this instance was created
+    /// with. Returns an empty list when the directory did not exist then.
+    pub fn files_at(&self, reference: &str, dir: &str) -> Result<Vec<String>, GitError> {
+        use std::io::Write;
+
+        let listing = self.run_checked(&["ls-tree", "-r", reference, "--", dir])?;
+        let oids: Vec<&str> = listing
+            .stdout
+            .lines()
+            .filter_map(|line| {
+                let (meta, _path) = line.split_once('\t')?;
+                let mut parts = meta.split_whitespace();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(_mode), Some("blob"), Some(oid)) => Some(oid),
+                    _ => None,
+                }
+            })
+            .collect();
+        if oids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut child = Command::new("git")
+            .args(["cat-file", "--batch"])
+            .current_dir(&self.repo_root)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    GitError::GitNotFound
+                } else {
+                    GitError::IoError(e)
+                }
+            })?;
+        // Feed stdin from its own thread: git writes objects while it reads
+        // requests, so writing everything up front deadlocks once both pipe
+        // buffers fill.
+        let input = format!("{}\n", oids.join("\n"));
+        let writer = child
+            .stdin
+            .take()
+            .map(|mut stdin| std::thread::spawn(move || stdin.write_all(input.as_bytes())));
+        let output = child.wait_with_output()?;
+        if let Some(writer) = writer {
+            writer.join().map_err(|_| GitError::CommandFailed {
+                message: "cat-file writer thread panicked".to_string(),
+            })??;
+        }
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        // Each object is "<oid> <type> <size>\n<content>\n"
+        let mut files = Vec::new();
+        let mut rest = output.stdout.as_slice();
+        while let Some(eol) = rest.iter().position(|&b| b == b'\n') {
+            let header = String::from_utf8_lossy(&rest[..eol]).to_string();
+            let size: usize = header
+                .rsplit(' ')
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| GitError::CommandFailed {
+                    message: format!("unexpected cat-file output: {}", header),
+                })?;
+            let body = &rest[eol + 1..];
+            if body.len() < size {
+                break;
+            }
+            files.push(String::from_utf8_lossy(&body[..size]).to_string());
+            rest = body.get(size + 1..).unwrap_or_default();
+        }
+        Ok(files)
+    }
+
     /// Get the short commit SHA (first 7 characters)
     pub fn head_sha_short(&self) -> Result<String, GitError> {
         let output = self.run(&["rev-parse", "--short", "HEAD"])?;
@@ -885,6 +978,42 @@ mod tests {
         assert!(git.is_clean());
     }
 
+    #[test]
+    fn test_files_at() {
+        let (tmp, git) = init_test_repo();
+        std::fs::create_dir(tmp.path().join("docs")).unwrap();
+        std::fs::write(tmp.path().join("docs/a.txt"), "first").unwrap();
+        std::fs::write(tmp.path().join("docs/b.txt"), "line\n").unwrap();
+        git.stage_file(&tmp.path().join("docs/a.txt")).unwrap();
+        git.stage_file(&tmp.path().join("docs/b.txt")).unwrap();
+        git.commit("Add docs").unwrap();
+        std::fs::write(tmp.path().join("docs/a.txt"), "changed").unwrap();
+
+        let mut files = git.files_at("HEAD", "docs").unwrap();
+        files.sort();
+        assert_eq!(files, vec!["first".to_string(), "line\n".to_string()]);
+        assert!(git.files_at("HEAD", "missing").unwrap().is_empty());
+        assert!(git.files_at("no-such-ref", "docs").is_err());
+    }
+
+    #[test]
+    fn test_files_at_many_files() {
+        // Enough objects to fill both cat-file pipes
+        let (tmp, git) = init_test_repo();
+        std::fs::create_dir(tmp.path().join("big")).unwrap();
+        for i in 0..3000 {
+            std::fs::write(
+                tmp.path().join(format!("big/{}.txt", i)),
+                format!("file {}\n", i),
+            )
+            .unwrap();
+        }
+        git.run_checked(&["add", "big"]).unwrap();
+        git.commit("Add many files").unwrap();
+
+        assert_eq!(git.files_at("HEAD", "big").unwrap().len(), 3000);
+    }
+
     #[test]
     fn test_format_command() {
         let formatted = Git::format_command(&["checkout", "-b", "feature/test"]);
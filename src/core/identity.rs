@@ -50,6 +50,12 @@ pub enum EntityPrefix {
     Lot,
     /// Process deviation
     Dev,
+    /// Customer complaint / field return
+    Cmpl,
+    /// Internal / supplier audit
+    Aud,
+    /// Receiving inspection
+    Rcv,
 }
 
 impl EntityPrefix {
@@ -76,6 +82,9 @@ impl EntityPrefix {
             EntityPrefix::Capa => "CAPA",
             EntityPrefix::Lot => "LOT",
             EntityPrefix::Dev => "DEV",
+            EntityPrefix::Cmpl => "CMPL",
+            EntityPrefix::Aud => "AUD",
+            EntityPrefix::Rcv => "RCV",
         }
     }
 
@@ -102,6 +111,9 @@ impl EntityPrefix {
             EntityPrefix::Capa,
             EntityPrefix::Lot,
             EntityPrefix::Dev,
+            EntityPrefix::Cmpl,
+            EntityPrefix::Aud,
+            EntityPrefix::Rcv,
         ]
     }
 
@@ -156,6 +168,9 @@ impl EntityPrefix {
                     "capas" => return Some(EntityPrefix::Capa),
                     "lots" => return Some(EntityPrefix::Lot),
                     "deviations" => return Some(EntityPrefix::Dev),
+                    "complaints" => return Some(EntityPrefix::Cmpl),
+                    "audits" => return Some(EntityPrefix::Aud),
+                    "receipts" => return Some(EntityPrefix::Rcv),
                     _ => {}
                 }
             }
@@ -195,6 +210,9 @@ impl FromStr for EntityPrefix {
             "CAPA" => Ok(EntityPrefix::Capa),
             "LOT" => Ok(EntityPrefix::Lot),
             "DEV" => Ok(EntityPrefix::Dev),
+            "CMPL" => Ok(EntityPrefix::Cmpl),
+            "AUD" => Ok(EntityPrefix::Aud),
+            "RCV" => Ok(EntityPrefix::Rcv),
             _ => Err(IdParseError::InvalidPrefix(s.to_string())),
         }
     }
@@ -0,0 +1,306 @@
+//! Line balancing and takt-time analysis for manufacturing routings
+//!
+//! Each process in a routing is treated as one station. Station load is the
+//! process cycle time plus its setup time amortized over the batch size.
+
+use serde::Serialize;
+
+use crate::entities::process::Process;
+
+/// Working time available for production
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ShiftPattern {
+    pub shifts_per_day: u32,
+    pub hours_per_shift: f64,
+    /// Unproductive minutes per shift (breaks, meetings)
+    pub break_minutes: f64,
+    pub days_per_week: u32,
+}
+
+impl Default for ShiftPattern {
+    fn default() -> Self {
+        Self {
+            shifts_per_day: 1,
+            hours_per_shift: 8.0,
+            break_minutes: 30.0,
+            days_per_week: 5,
+        }
+    }
+}
+
+impl ShiftPattern {
+    /// Available production minutes per day
+    pub fn minutes_per_day(&self) -> f64 {
+        (self.hours_per_shift * 60.0 - self.break_minutes).max(0.0) * self.shifts_per_day as f64
+    }
+
+    /// Available production minutes per week
+    pub fn minutes_per_week(&self) -> f64 {
+        self.minutes_per_day() * self.days_per_week as f64
+    }
+}
+
+/// Period that customer demand is expressed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DemandPeriod {
+    Day,
+    Week,
+}
+
+impl std::fmt::Display for DemandPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DemandPeriod::Day => write!(f, "day"),
+            DemandPeriod::Week => write!(f, "week"),
+        }
+    }
+}
+
+/// Customer demand used to derive takt time
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Demand {
+    pub units: f64,
+    pub period: DemandPeriod,
+}
+
+/// Load on one routing station
+#[derive(Debug, Clone, Serialize)]
+pub struct StationLoad {
+    pub process: String,
+    pub title: String,
+    pub operation_number: Option<String>,
+    pub cycle_minutes: f64,
+    pub setup_minutes: f64,
+    /// Cycle time plus setup amortized over the batch
+    pub unit_minutes: f64,
+    /// Station time as a fraction of takt (None without demand)
+    pub takt_utilization: Option<f64>,
+    /// Parallel stations needed to meet takt (None without demand)
+    pub stations_needed: Option<u32>,
+    /// Process has no cycle time defined
+    pub missing_cycle_time: bool,
+}
+
+/// Per-unit time for one batch size
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupAmortization {
+    pub batch_size: u32,
+    /// Labor content per unit including amortized setup
+    pub unit_minutes: f64,
+    /// Bottleneck station time per unit
+    pub bottleneck_minutes: f64,
+}
+
+/// Result of a routing analysis
+#[derive(Debug, Clone, Serialize)]
+pub struct LineBalance {
+    pub stations: Vec<StationLoad>,
+    pub batch_size: u32,
+    pub shift: ShiftPattern,
+    pub demand: Option<Demand>,
+    /// Sum of cycle times (minutes per unit)
+    pub labor_content_minutes: f64,
+    /// Sum of setup times (minutes per batch)
+    pub setup_minutes: f64,
+    /// Labor content per unit including amortized setup
+    pub unit_minutes: f64,
+    /// Index into `stations` of the slowest station
+    pub bottleneck: Option<usize>,
+    pub bottleneck_minutes: f64,
+    /// Available minutes per demand period divided by demand
+    pub takt_minutes: Option<f64>,
+    /// Theoretical minimum stations to meet takt: ceil(content / takt)
+    pub min_stations: Option<u32>,
+    /// Sum of station times / (stations x bottleneck time)
+    pub line_efficiency: f64,
+    /// 1 - line efficiency
+    pub balance_delay: f64,
+    /// Line output limited by the bottleneck, in units per day
+    pub capacity_per_day: f64,
+    /// Whether the bottleneck is within takt
+    pub meets_takt: Option<bool>,
+    pub amortization: Vec<SetupAmortization>,
+}
+
+/// Batch sizes always shown in the setup amortization table
+const AMORTIZATION_BATCHES: &[u32] = &[1, 5, 10, 25, 50, 100];
+
+/// Analyze a routing (processes in routing order)
+pub fn analyze_routing(
+    processes: &[&Process],
+    batch_size: u32,
+    shift: ShiftPattern,
+    demand: Option<Demand>,
+) -> LineBalance {
+    let batch = batch_size.max(1);
+    let takt_minutes = demand.filter(|d| d.units > 0.0).map(|d| {
+        let available = match d.period {
+            DemandPeriod::Day => shift.minutes_per_day(),
+            DemandPeriod::Week => shift.minutes_per_week(),
+        };
+        available / d.units
+    });
+
+    let stations: Vec<StationLoad> = processes
+        .iter()
+        .map(|p| {
+            let cycle = p.cycle_time_minutes.unwrap_or(0.0);
+            let setup = p.setup_time_minutes.unwrap_or(0.0);
+            let unit = cycle + setup / batch as f64;
+            StationLoad {
+                process: p.id.to_string(),
+                title: p.title.clone(),
+                operation_number: p.operation_number.clone(),
+                cycle_minutes: cycle,
+                setup_minutes: setup,
+                unit_minutes: unit,
+                takt_utilization: takt_minutes.map(|t| unit / t),
+                stations_needed: takt_minutes.map(|t| (unit / t).ceil().max(1.0) as u32),
+                missing_cycle_time: p.cycle_time_minutes.is_none(),
+            }
+        })
+        .collect();
+
+    let labor_content_minutes: f64 = stations.iter().map(|s| s.cycle_minutes).sum();
+    let setup_minutes: f64 = stations.iter().map(|s| s.setup_minutes).sum();
+    let unit_minutes: f64 = stations.iter().map(|s| s.unit_minutes).sum();
+
+    let bottleneck = stations
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.unit_minutes > 0.0)
+        .max_by(|(_, a), (_, b)| a.unit_minutes.total_cmp(&b.unit_minutes))
+        .map(|(i, _)| i);
+    let bottleneck_minutes = bottleneck.map(|i| stations[i].unit_minutes).unwrap_or(0.0);
+
+    let line_efficiency = if bottleneck_minutes > 0.0 {
+        unit_minutes / (stations.len() as f64 * bottleneck_minutes)
+    } else {
+        0.0
+    };
+    let capacity_per_day = if bottleneck_minutes > 0.0 {
+        shift.minutes_per_day() / bottleneck_minutes
+    } else {
+        0.0
+    };
+
+    let mut batches: Vec<u32> = AMORTIZATION_BATCHES.to_vec();
+    if !batches.contains(&batch) {
+        batches.push(batch);
+        batches.sort_unstable();
+    }
+    let amortization = batches
+        .into_iter()
+        .map(|b| {
+            let per_station = stations
+                .iter()
+                .map(|s| s.cycle_minutes + s.setup_minutes / b as f64);
+            SetupAmortization {
+                batch_size: b,
+                unit_minutes: per_station.clone().sum(),
+                bottleneck_minutes: per_station.fold(0.0, f64::max),
+            }
+        })
+        .collect();
+
+    LineBalance {
+        batch_size: batch,
+        shift,
+        demand,
+        labor_content_minutes,
+        setup_minutes,
+        unit_minutes,
+        bottleneck,
+        bottleneck_minutes,
+        takt_minutes,
+        min_stations: takt_minutes.map(|t| (unit_minutes / t).ceil() as u32),
+        line_efficiency,
+        balance_delay: if bottleneck.is_some() {
+            1.0 - line_efficiency
+        } else {
+            0.0
+        },
+        capacity_per_day,
+        meets_takt: takt_minutes.map(|t| bottleneck_minutes <= t),
+        amortization,
+        stations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::process::ProcessType;
+
+    fn process(title: &str, cycle: Option<f64>, setup: Option<f64>) -> Process {
+        let mut p = Process::new(
+            title.to_string(),
+            ProcessType::default(),
+            "test".to_string(),
+        );
+        p.cycle_time_minutes = cycle;
+        p.setup_time_minutes = setup;
+        p
+    }
+
+    #[test]
+    fn test_shift_pattern_minutes() {
+        let shift = ShiftPattern {
+            shifts_per_day: 2,
+            hours_per_shift: 8.0,
+            break_minutes: 30.0,
+            days_per_week: 5,
+        };
+        assert_eq!(shift.minutes_per_day(), 900.0);
+        assert_eq!(shift.minutes_per_week(), 4500.0);
+    }
+
+    #[test]
+    fn test_analyze_routing_bottleneck_and_takt() {
+        let a = process("Cut", Some(4.0), Some(20.0));
+        let b = process("Weld", Some(8.0), None);
+        let c = process("Paint", Some(6.0), Some(40.0));
+        let demand = Demand {
+            units: 45.0,
+            period: DemandPeriod::Day,
+        };
+
+        let lb = analyze_routing(&[&a, &b, &c], 10, ShiftPattern::default(), Some(demand));
+
+        // Unit times: 4 + 2, 8, 6 + 4
+        assert_eq!(lb.labor_content_minutes, 18.0);
+        assert_eq!(lb.unit_minutes, 24.0);
+        assert_eq!(lb.bottleneck, Some(2));
+        assert_eq!(lb.bottleneck_minutes, 10.0);
+
+        // 450 available minutes / 45 units
+        assert_eq!(lb.takt_minutes, Some(10.0));
+        assert_eq!(lb.meets_takt, Some(true));
+        assert_eq!(lb.min_stations, Some(3));
+        assert!((lb.line_efficiency - 0.8).abs() < 1e-9);
+        assert_eq!(lb.capacity_per_day, 45.0);
+        assert_eq!(lb.stations[1].stations_needed, Some(1));
+    }
+
+    #[test]
+    fn test_setup_amortization_table() {
+        let a = process("Mold", Some(1.0), Some(60.0));
+        let lb = analyze_routing(&[&a], 30, ShiftPattern::default(), None);
+
+        let batch_1 = lb.amortization.iter().find(|r| r.batch_size == 1).unwrap();
+        assert_eq!(batch_1.unit_minutes, 61.0);
+        let batch_30 = lb.amortization.iter().find(|r| r.batch_size == 30).unwrap();
+        assert_eq!(batch_30.unit_minutes, 3.0);
+        assert!(lb.takt_minutes.is_none());
+    }
+
+    #[test]
+    fn test_missing_cycle_time_flagged() {
+        let a = process("Inspect", None, None);
+        let lb = analyze_routing(&[&a], 1, ShiftPattern::default(), None);
+        assert!(lb.stations[0].missing_cycle_time);
+        assert_eq!(lb.bottleneck, None);
+        assert_eq!(lb.line_efficiency, 0.0);
+    }
+}
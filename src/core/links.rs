@@ -115,6 +115,7 @@ pub fn infer_link_type(source_prefix: EntityPrefix, target_prefix: EntityPrefix)
         (EntityPrefix::Ncr, EntityPrefix::Cmp) => Some("component".to_string()),
         (EntityPrefix::Ncr, EntityPrefix::Sup) => Some("supplier".to_string()),
         (EntityPrefix::Ncr, EntityPrefix::Proc) => Some("process".to_string()),
+        (EntityPrefix::Ncr, EntityPrefix::Rcv) => Some("receipt".to_string()),
 
         // CAPAs
         (EntityPrefix::Capa, EntityPrefix::Ncr) => Some("ncrs".to_string()),
@@ -124,6 +125,25 @@ pub fn infer_link_type(source_prefix: EntityPrefix, target_prefix: EntityPrefix)
         (EntityPrefix::Capa, EntityPrefix::Sup) => Some("supplier".to_string()),
         (EntityPrefix::Capa, EntityPrefix::Risk) => Some("risks".to_string()),
 
+        // Complaints / field returns
+        (EntityPrefix::Cmpl, EntityPrefix::Lot) => Some("lots".to_string()),
+        (EntityPrefix::Cmpl, EntityPrefix::Cmp) => Some("components".to_string()),
+        (EntityPrefix::Cmpl, EntityPrefix::Asm) => Some("assemblies".to_string()),
+        (EntityPrefix::Cmpl, EntityPrefix::Risk) => Some("risks".to_string()),
+        (EntityPrefix::Cmpl, EntityPrefix::Ncr) => Some("ncrs".to_string()),
+        (EntityPrefix::Cmpl, EntityPrefix::Capa) => Some("capa".to_string()),
+
+        // Audits
+        (EntityPrefix::Aud, EntityPrefix::Sup) => Some("supplier".to_string()),
+        (EntityPrefix::Aud, EntityPrefix::Proc) => Some("processes".to_string()),
+        (EntityPrefix::Aud, EntityPrefix::Ncr) => Some("ncrs".to_string()),
+        (EntityPrefix::Aud, EntityPrefix::Capa) => Some("capas".to_string()),
+
+        // Receiving inspection
+        (EntityPrefix::Rcv, EntityPrefix::Sup) => Some("supplier".to_string()),
+        (EntityPrefix::Rcv, EntityPrefix::Cmp) => Some("component".to_string()),
+        (EntityPrefix::Rcv, EntityPrefix::Ncr) => Some("ncr".to_string()),
+
         // Hazard links
         (EntityPrefix::Haz, EntityPrefix::Cmp) => Some("originates_from".to_string()),
         (EntityPrefix::Haz, EntityPrefix::Asm) => Some("originates_from".to_string()),
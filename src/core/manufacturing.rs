@@ -6,9 +6,15 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use crate::core::git::{Git, GitError};
+use crate::core::sampling::{
+    switching_state, LotInspection, PlanSpec, SamplingPlan, SwitchingState,
+};
 use crate::core::Config;
+use crate::entities::control::Control;
+use crate::entities::dev::Dev;
 use crate::entities::lot::{ExecutionStatus, ExecutionStep, Lot, WorkInstructionRef};
 use crate::entities::process::Process;
+use crate::entities::work_instruction::WorkInstruction;
 
 /// Configuration for lot-based manufacturing workflow
 #[derive(Debug, Clone)]
@@ -297,6 +303,275 @@ pub fn step_required_roles(
         .unwrap_or_default()
 }
 
+/// Where a step data specification is defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepDataSource {
+    /// Process parameter
+    Parameter,
+    /// Quality check in a work instruction (WI ID)
+    QualityCheck(String),
+    /// Sampling plan of a control on the step's process (CTRL ID)
+    Sampling(String),
+}
+
+/// A value the operator records for a step, with optional limits
+#[derive(Debug, Clone)]
+pub struct StepDataSpec {
+    /// Data key (parameter name or checked characteristic)
+    pub name: String,
+    pub source: StepDataSource,
+    pub nominal: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub units: Option<String>,
+    /// Free-text specification from the work instruction
+    pub specification: Option<String>,
+}
+
+/// Result of checking a recorded value against its specification
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepDataCheck {
+    /// Numeric value within limits
+    InSpec,
+    /// Numeric value outside limits
+    OutOfSpec,
+    /// Value recorded, but the specification has no numeric limits
+    Recorded,
+    /// Limits defined but the value is not numeric
+    NotNumeric,
+    /// No value recorded
+    Missing,
+}
+
+impl StepDataSpec {
+    /// Whether the spec defines numeric limits
+    pub fn has_limits(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+
+    /// Human-readable limits, e.g. "100 - 200 RPM"
+    pub fn limits_display(&self) -> String {
+        let units = self
+            .units
+            .as_ref()
+            .map(|u| format!(" {}", u))
+            .unwrap_or_default();
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{} - {}{}", min, max, units),
+            (Some(min), None) => format!(">= {}{}", min, units),
+            (None, Some(max)) => format!("<= {}{}", max, units),
+            (None, None) => self.specification.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Check a recorded value against the limits
+    pub fn check(&self, value: Option<&serde_json::Value>) -> StepDataCheck {
+        let Some(value) = value else {
+            return StepDataCheck::Missing;
+        };
+        if !self.has_limits() {
+            return StepDataCheck::Recorded;
+        }
+        let number = match value {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        };
+        match number {
+            Some(v) => {
+                let above_min = self.min.is_none_or(|min| v >= min);
+                let below_max = self.max.is_none_or(|max| v <= max);
+                if above_min && below_max {
+                    StepDataCheck::InSpec
+                } else {
+                    StepDataCheck::OutOfSpec
+                }
+            }
+            None => StepDataCheck::NotNumeric,
+        }
+    }
+}
+
+/// Collect the data to record for a step from its process parameters
+/// and the quality checks of its work instructions
+pub fn step_data_specs(
+    process: Option<&Process>,
+    work_instructions: &[WorkInstruction],
+) -> Vec<StepDataSpec> {
+    let mut specs: Vec<StepDataSpec> = Vec::new();
+    if let Some(proc) = process {
+        for param in &proc.parameters {
+            specs.push(StepDataSpec {
+                name: param.name.clone(),
+                source: StepDataSource::Parameter,
+                nominal: Some(param.value),
+                min: param.min,
+                max: param.max,
+                units: param.units.clone(),
+                specification: None,
+            });
+        }
+    }
+    for wi in work_instructions {
+        for qc in &wi.quality_checks {
+            if specs
+                .iter()
+                .any(|s| s.name.eq_ignore_ascii_case(&qc.characteristic))
+            {
+                continue;
+            }
+            specs.push(StepDataSpec {
+                name: qc.characteristic.clone(),
+                source: StepDataSource::QualityCheck(wi.id.to_string()),
+                nominal: None,
+                min: qc.min,
+                max: qc.max,
+                units: qc.units.clone(),
+                specification: qc.specification.clone(),
+            });
+        }
+    }
+    specs
+}
+
+/// Sampling plan of a control on a lot step's process
+#[derive(Debug, Clone)]
+pub struct StepSampling {
+    /// Control ID
+    pub control: String,
+    pub characteristic: String,
+    pub plan: SamplingPlan,
+    pub state: SwitchingState,
+}
+
+impl StepSampling {
+    /// Step data key holding the defects found in the sample
+    pub fn defects_key(&self) -> String {
+        format!("{} defects", self.characteristic)
+    }
+
+    /// Defect count to record, limited to the acceptance number
+    /// (attribute plans only)
+    pub fn data_spec(&self) -> Option<StepDataSpec> {
+        let accept = self.plan.accept?;
+        Some(StepDataSpec {
+            name: self.defects_key(),
+            source: StepDataSource::Sampling(self.control.clone()),
+            nominal: None,
+            min: None,
+            max: Some(accept as f64),
+            units: None,
+            specification: Some(self.plan.describe()),
+        })
+    }
+}
+
+/// Sampling plans for a lot step from the controls on its process
+///
+/// Controls with an AQL get a plan for the lot quantity. The switching rules
+/// run over the defect counts recorded for the same control on earlier lots.
+pub fn step_sampling(
+    process_id: &str,
+    controls: &[Control],
+    lots: &[Lot],
+    lot: &Lot,
+) -> Vec<StepSampling> {
+    let Some(quantity) = lot.quantity else {
+        return Vec::new();
+    };
+    let earlier: Vec<&Lot> = lots
+        .iter()
+        .filter(|l| l.id != lot.id && l.created < lot.created)
+        .collect();
+
+    controls
+        .iter()
+        .filter(|c| {
+            c.links
+                .process
+                .as_ref()
+                .is_some_and(|p| p.to_string() == process_id)
+        })
+        .filter_map(|ctrl| {
+            let spec = ctrl
+                .sampling
+                .as_ref()
+                .and_then(|s| PlanSpec::from_sampling(s).ok().flatten())?;
+            let state = switching_state(&spec, &lot_sampling_history(ctrl, &earlier));
+            Some(StepSampling {
+                control: ctrl.id.to_string(),
+                characteristic: sampled_characteristic(ctrl),
+                plan: spec.plan(quantity, state.severity),
+                state,
+            })
+        })
+        .collect()
+}
+
+fn sampled_characteristic(ctrl: &Control) -> String {
+    if ctrl.characteristic.name.is_empty() {
+        ctrl.title.clone()
+    } else {
+        ctrl.characteristic.name.clone()
+    }
+}
+
+/// Defect counts recorded for a process control's sampling plan, oldest lot first
+pub fn lot_sampling_history(ctrl: &Control, lots: &[&Lot]) -> Vec<LotInspection> {
+    let Some(process_id) = ctrl.links.process.as_ref().map(|p| p.to_string()) else {
+        return Vec::new();
+    };
+    let key = format!("{} defects", sampled_characteristic(ctrl));
+    let mut lots = lots.to_vec();
+    lots.sort_by_key(|l| l.created);
+    lots.iter()
+        .filter_map(|l| {
+            let defects = l
+                .execution
+                .iter()
+                .filter(|step| step.process.as_deref() == Some(process_id.as_str()))
+                .find_map(|step| find_step_value(&step.data, &key))
+                .and_then(|(_, v)| v.as_f64())?;
+            Some(LotInspection {
+                lot_size: l.quantity?,
+                defects: Some(defects as u32),
+                accepted: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse a recorded value: numbers are stored as JSON numbers, anything else as text
+pub fn parse_step_value(raw: &str) -> serde_json::Value {
+    let raw = raw.trim();
+    match raw.parse::<f64>() {
+        Ok(v) => serde_json::Number::from_f64(v)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(raw.to_string())),
+        Err(_) => serde_json::Value::String(raw.to_string()),
+    }
+}
+
+/// Find the data key matching a spec name (case-insensitive)
+pub fn find_step_value<'a>(
+    data: &'a HashMap<String, serde_json::Value>,
+    name: &str,
+) -> Option<(&'a String, &'a serde_json::Value)> {
+    data.iter().find(|(k, _)| k.eq_ignore_ascii_case(name))
+}
+
+/// Deviations in effect for a lot step on `date`
+pub fn covering_deviations<'a>(
+    devs: &'a [Dev],
+    lot_id: &str,
+    process_id: Option<&str>,
+    date: chrono::NaiveDate,
+) -> Vec<&'a Dev> {
+    devs.iter()
+        .filter(|d| d.is_in_effect_on(date) && d.applies_to(lot_id, process_id))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,4 +603,123 @@ mod tests {
         let branch_name = config.branch_name(&lot);
         assert!(branch_name.starts_with("lot/"));
     }
+
+    fn spec(min: Option<f64>, max: Option<f64>) -> StepDataSpec {
+        StepDataSpec {
+            name: "Torque".to_string(),
+            source: StepDataSource::Parameter,
+            nominal: Some(5.0),
+            min,
+            max,
+            units: Some("Nm".to_string()),
+            specification: None,
+        }
+    }
+
+    #[test]
+    fn test_step_data_check_limits() {
+        let s = spec(Some(4.5), Some(5.5));
+        assert_eq!(
+            s.check(Some(&parse_step_value("5.0"))),
+            StepDataCheck::InSpec
+        );
+        assert_eq!(
+            s.check(Some(&parse_step_value("5.6"))),
+            StepDataCheck::OutOfSpec
+        );
+        assert_eq!(
+            s.check(Some(&parse_step_value("4.5"))),
+            StepDataCheck::InSpec
+        );
+        assert_eq!(
+            s.check(Some(&parse_step_value("ok"))),
+            StepDataCheck::NotNumeric
+        );
+        assert_eq!(s.check(None), StepDataCheck::Missing);
+        assert_eq!(s.limits_display(), "4.5 - 5.5 Nm");
+
+        let free = spec(None, None);
+        assert_eq!(
+            free.check(Some(&parse_step_value("ok"))),
+            StepDataCheck::Recorded
+        );
+    }
+
+    #[test]
+    fn test_step_data_specs_merges_sources() {
+        use crate::entities::process::{Process, ProcessParameter};
+        use crate::entities::work_instruction::{QualityCheck, WorkInstruction};
+
+        let mut proc = Process::new(
+            "Press".to_string(),
+            crate::entities::process::ProcessType::default(),
+            "test".to_string(),
+        );
+        proc.parameters.push(ProcessParameter {
+            name: "Force".to_string(),
+            value: 10.0,
+            units: Some("kN".to_string()),
+            min: Some(9.0),
+            max: Some(11.0),
+        });
+        let mut wi = WorkInstruction::new("Press WI".to_string(), "test".to_string());
+        wi.quality_checks.push(QualityCheck {
+            at_step: 1,
+            characteristic: "Height".to_string(),
+            specification: Some("12.0 ±0.1 mm".to_string()),
+            units: Some("mm".to_string()),
+            min: Some(11.9),
+            max: Some(12.1),
+        });
+        wi.quality_checks.push(QualityCheck {
+            at_step: 2,
+            characteristic: "force".to_string(),
+            specification: None,
+            units: None,
+            min: None,
+            max: None,
+        });
+
+        let specs = step_data_specs(Some(&proc), &[wi]);
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].name, "Force");
+        assert!(matches!(specs[1].source, StepDataSource::QualityCheck(_)));
+    }
+
+    #[test]
+    fn test_covering_deviations() {
+        use crate::entities::dev::DevStatus;
+        use chrono::NaiveDate;
+
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let mut dev = Dev::new("Alt torque".to_string(), "test".to_string());
+        dev.links.processes.push("PROC-1".to_string());
+        dev.effective_date = NaiveDate::from_ymd_opt(2024, 5, 1);
+        dev.expiration_date = NaiveDate::from_ymd_opt(2024, 7, 1);
+
+        // Pending deviations never cover
+        let devs = vec![dev.clone()];
+        assert!(covering_deviations(&devs, "LOT-1", Some("PROC-1"), today).is_empty());
+
+        dev.dev_status = DevStatus::Active;
+        let devs = vec![dev.clone()];
+        assert_eq!(
+            covering_deviations(&devs, "LOT-1", Some("PROC-1"), today).len(),
+            1
+        );
+        assert!(covering_deviations(&devs, "LOT-1", Some("PROC-2"), today).is_empty());
+
+        let expired = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        assert!(covering_deviations(&devs, "LOT-1", Some("PROC-1"), expired).is_empty());
+
+        // Every applicable deviation is returned, not just the first loaded
+        let mut other = Dev::new("Alt fixture".to_string(), "test".to_string());
+        other.dev_status = DevStatus::Active;
+        other.links.lots.push("LOT-1".to_string());
+        let devs = vec![dev, other];
+        assert_eq!(
+            covering_deviations(&devs, "LOT-1", Some("PROC-1"), today).len(),
+            2
+        );
+    }
 }
@@ -0,0 +1,524 @@
+//! Mass properties rollup through an assembly's BOM
+//!
+//! Every component instance is placed in the top-level assembly frame by
+//! composing BOM placements down the tree. A BOM line without placements uses
+//! the component's `coordinate_system` (or the identity for sub-assemblies).
+//! Total mass, center of gravity and the inertia tensor are then combined with
+//! the parallel axis theorem. Lengths are in mm, masses in kg and inertia in
+//! kg·mm².
+//!
+//! Components without `mass_kg` are left out. Components without a CG count
+//! as a point mass at their frame origin, and components without an inertia
+//! tensor as a point mass at their CG. Both are reported as warnings.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use nalgebra::{Matrix3, Vector3};
+use serde::Serialize;
+
+use crate::entities::assembly::{Assembly, BomItem, MassBudget};
+use crate::entities::component::{Component, CoordinateSystem, InertiaTensor};
+
+/// Rigid transform into the top-level assembly frame
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    rotation: Matrix3<f64>,
+    origin: Vector3<f64>,
+}
+
+impl Frame {
+    fn identity() -> Self {
+        Self {
+            rotation: Matrix3::identity(),
+            origin: Vector3::zeros(),
+        }
+    }
+
+    fn from_coordinate_system(cs: &CoordinateSystem) -> Self {
+        let x = Vector3::from(cs.x_axis).normalize();
+        let z = Vector3::from(cs.z_axis).normalize();
+        let y = z.cross(&x);
+        Self {
+            rotation: Matrix3::from_columns(&[x, y, z]),
+            origin: Vector3::from(cs.origin),
+        }
+    }
+
+    /// This frame followed by a child placement expressed in it
+    fn then(&self, child: &Frame) -> Frame {
+        Frame {
+            rotation: self.rotation * child.rotation,
+            origin: self.rotation * child.origin + self.origin,
+        }
+    }
+}
+
+/// A point mass with its own inertia, in the top-level frame
+#[derive(Debug, Clone, Copy)]
+struct Body {
+    mass: f64,
+    cg: Vector3<f64>,
+    /// Inertia about the body's own CG
+    inertia: Matrix3<f64>,
+}
+
+/// Mass, CG and inertia about the CG of a set of bodies
+fn combine(bodies: &[Body]) -> Option<Body> {
+    let mass: f64 = bodies.iter().map(|b| b.mass).sum();
+    if mass <= 0.0 {
+        return None;
+    }
+    let cg = bodies
+        .iter()
+        .fold(Vector3::zeros(), |acc, b| acc + b.cg * b.mass)
+        / mass;
+    let inertia = bodies.iter().fold(Matrix3::zeros(), |acc, b| {
+        acc + b.inertia + parallel_axis(b.mass, &(b.cg - cg))
+    });
+    Some(Body { mass, cg, inertia })
+}
+
+/// Inertia of a point mass at offset `d`: m (|d|² E - d dᵀ)
+fn parallel_axis(mass: f64, d: &Vector3<f64>) -> Matrix3<f64> {
+    (Matrix3::identity() * d.norm_squared() - d * d.transpose()) * mass
+}
+
+fn to_matrix(t: &InertiaTensor) -> Matrix3<f64> {
+    Matrix3::new(
+        t.ixx, t.ixy, t.ixz, //
+        t.ixy, t.iyy, t.iyz, //
+        t.ixz, t.iyz, t.izz,
+    )
+}
+
+fn from_matrix(m: &Matrix3<f64>) -> InertiaTensor {
+    InertiaTensor {
+        ixx: m[(0, 0)],
+        iyy: m[(1, 1)],
+        izz: m[(2, 2)],
+        ixy: m[(0, 1)],
+        ixz: m[(0, 2)],
+        iyz: m[(1, 2)],
+    }
+}
+
+/// Missing or inconsistent data on a component or BOM line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MassIssue {
+    /// No `mass_kg` - left out of the rollup
+    NoMass,
+    /// No CG - treated as a point mass at its frame origin
+    NoCg,
+    /// No inertia tensor - treated as a point mass at its CG
+    NoInertia,
+    /// Placement count matches neither 1 nor the quantity - placements ignored
+    PlacementCount,
+}
+
+impl std::fmt::Display for MassIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MassIssue::NoMass => write!(f, "no mass, left out"),
+            MassIssue::NoCg => write!(f, "no CG, treated as a point mass at its origin"),
+            MassIssue::NoInertia => write!(f, "no inertia, treated as a point mass at its CG"),
+            MassIssue::PlacementCount => {
+                write!(
+                    f,
+                    "placement count matches neither 1 nor the quantity, ignored"
+                )
+            }
+        }
+    }
+}
+
+/// A warning about data the rollup had to work around
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct MassWarning {
+    pub id: String,
+    pub part_number: String,
+    pub issue: MassIssue,
+}
+
+/// One BOM occurrence in the rollup
+#[derive(Debug, Clone, Serialize)]
+pub struct MassLine {
+    pub id: String,
+    pub title: String,
+    /// Nesting depth (0 = top-level BOM)
+    pub depth: usize,
+    /// Quantity per top-level unit
+    pub quantity: u32,
+    /// Extended mass of all instances
+    pub mass_kg: f64,
+    /// Combined CG of all instances, in the top-level frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cg_mm: Option<[f64; 3]>,
+}
+
+/// Mass budget comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetCheck {
+    pub max_kg: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    /// Budget minus total mass (negative when over budget)
+    pub margin_kg: f64,
+    /// Margin as a percentage of the budget
+    pub margin_percent: f64,
+    pub within_budget: bool,
+}
+
+impl BudgetCheck {
+    pub fn new(budget: &MassBudget, mass_kg: f64) -> Self {
+        let margin_kg = budget.max_kg - mass_kg;
+        Self {
+            max_kg: budget.max_kg,
+            requirement: budget.requirement.clone(),
+            margin_kg,
+            margin_percent: if budget.max_kg > 0.0 {
+                margin_kg / budget.max_kg * 100.0
+            } else {
+                0.0
+            },
+            within_budget: margin_kg >= 0.0,
+        }
+    }
+}
+
+/// Mass properties of an assembly in its own frame
+#[derive(Debug, Clone, Serialize)]
+pub struct MassRollup {
+    pub assembly: String,
+    pub mass_kg: f64,
+    /// Center of gravity in mm
+    pub cg_mm: [f64; 3],
+    /// Inertia about the CG, aligned with the assembly axes
+    pub inertia_cg: InertiaTensor,
+    /// Inertia about the assembly origin
+    pub inertia_origin: InertiaTensor,
+    /// Whether any component declares a CG or inertia
+    pub has_mass_properties: bool,
+    pub lines: Vec<MassLine>,
+    pub warnings: Vec<MassWarning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetCheck>,
+}
+
+struct Walker<'a> {
+    components: HashMap<String, &'a Component>,
+    assemblies: HashMap<String, &'a Assembly>,
+    lines: Vec<MassLine>,
+    warnings: BTreeSet<MassWarning>,
+    has_mass_properties: bool,
+}
+
+impl Walker<'_> {
+    fn warn(&mut self, id: &str, part_number: &str, issue: MassIssue) {
+        self.warnings.insert(MassWarning {
+            id: id.to_string(),
+            part_number: part_number.to_string(),
+            issue,
+        });
+    }
+
+    /// Instance frames for a BOM line with how many instances share each
+    fn instances(
+        &mut self,
+        item: &BomItem,
+        part_number: &str,
+        default: Option<&CoordinateSystem>,
+    ) -> Vec<(Frame, u32)> {
+        let default_frame = || {
+            default
+                .map(Frame::from_coordinate_system)
+                .unwrap_or_else(Frame::identity)
+        };
+        match item.placements.len() {
+            0 => vec![(default_frame(), item.quantity)],
+            1 => vec![(
+                Frame::from_coordinate_system(&item.placements[0]),
+                item.quantity,
+            )],
+            n if n == item.quantity as usize => item
+                .placements
+                .iter()
+                .map(|p| (Frame::from_coordinate_system(p), 1))
+                .collect(),
+            _ => {
+                self.warn(&item.component_id, part_number, MassIssue::PlacementCount);
+                vec![(default_frame(), item.quantity)]
+            }
+        }
+    }
+
+    fn walk(
+        &mut self,
+        asm: &Assembly,
+        frame: &Frame,
+        multiplier: u32,
+        depth: usize,
+        visited: &mut HashSet<String>,
+    ) -> Vec<Body> {
+        let mut bodies = Vec::new();
+        for item in &asm.bom {
+            let id = item.component_id.clone();
+            let quantity = item.quantity * multiplier;
+
+            if let Some(cmp) = self.components.get(&id).copied() {
+                let Some(mass) = cmp.mass_kg else {
+                    self.warn(&id, &cmp.part_number, MassIssue::NoMass);
+                    self.lines.push(MassLine {
+                        id,
+                        title: cmp.title.clone(),
+                        depth,
+                        quantity,
+                        mass_kg: 0.0,
+                        cg_mm: None,
+                    });
+                    continue;
+                };
+                let (cg, inertia) = match cmp.mass_properties {
+                    Some(ref mp) => {
+                        self.has_mass_properties = true;
+                        if mp.inertia.is_none() {
+                            self.warn(&id, &cmp.part_number, MassIssue::NoInertia);
+                        }
+                        (
+                            Vector3::from(mp.cg_mm),
+                            mp.inertia.as_ref().map(to_matrix).unwrap_or_default(),
+                        )
+                    }
+                    None => {
+                        self.warn(&id, &cmp.part_number, MassIssue::NoCg);
+                        (Vector3::zeros(), Matrix3::zeros())
+                    }
+                };
+
+                let mut line_bodies = Vec::new();
+                for (placement, count) in
+                    self.instances(item, &cmp.part_number, cmp.coordinate_system.as_ref())
+                {
+                    let placed = frame.then(&placement);
+                    let n = (count * multiplier) as f64;
+                    line_bodies.push(Body {
+                        mass: mass * n,
+                        cg: placed.rotation * cg + placed.origin,
+                        inertia: placed.rotation * inertia * placed.rotation.transpose() * n,
+                    });
+                }
+                self.lines
+                    .push(line(&id, &cmp.title, depth, quantity, &line_bodies));
+                bodies.extend(line_bodies);
+            } else if let Some(sub) = self.assemblies.get(&id).copied() {
+                if !visited.insert(id.clone()) {
+                    continue;
+                }
+                let index = self.lines.len();
+                self.lines.push(MassLine {
+                    id: id.clone(),
+                    title: sub.title.clone(),
+                    depth,
+                    quantity,
+                    mass_kg: 0.0,
+                    cg_mm: None,
+                });
+                let mut line_bodies = Vec::new();
+                for (placement, count) in self.instances(item, &sub.part_number, None) {
+                    let placed = frame.then(&placement);
+                    line_bodies.extend(self.walk(
+                        sub,
+                        &placed,
+                        count * multiplier,
+                        depth + 1,
+                        visited,
+                    ));
+                }
+                self.lines[index] = line(&id, &sub.title, depth, quantity, &line_bodies);
+                bodies.extend(line_bodies);
+                visited.remove(&id);
+            }
+        }
+        bodies
+    }
+}
+
+fn line(id: &str, title: &str, depth: usize, quantity: u32, bodies: &[Body]) -> MassLine {
+    let combined = combine(bodies);
+    MassLine {
+        id: id.to_string(),
+        title: title.to_string(),
+        depth,
+        quantity,
+        mass_kg: combined.map(|b| b.mass).unwrap_or(0.0),
+        cg_mm: combined.map(|b| b.cg.into()),
+    }
+}
+
+/// Roll up mass, CG and inertia for an assembly
+///
+/// `budget` overrides the assembly's own `mass_budget` when given.
+pub fn rollup(
+    assembly: &Assembly,
+    assemblies: &[Assembly],
+    components: &[Component],
+    budget: Option<&MassBudget>,
+) -> MassRollup {
+    let mut walker = Walker {
+        components: components.iter().map(|c| (c.id.to_string(), c)).collect(),
+        assemblies: assemblies.iter().map(|a| (a.id.to_string(), a)).collect(),
+        lines: Vec::new(),
+        warnings: BTreeSet::new(),
+        has_mass_properties: false,
+    };
+    let mut visited = HashSet::from([assembly.id.to_string()]);
+    let bodies = walker.walk(assembly, &Frame::identity(), 1, 0, &mut visited);
+
+    let total = combine(&bodies).unwrap_or(Body {
+        mass: 0.0,
+        cg: Vector3::zeros(),
+        inertia: Matrix3::zeros(),
+    });
+    let inertia_origin = total.inertia + parallel_axis(total.mass, &total.cg);
+    let budget = budget
+        .or(assembly.mass_budget.as_ref())
+        .map(|b| BudgetCheck::new(b, total.mass));
+
+    MassRollup {
+        assembly: assembly.id.to_string(),
+        mass_kg: total.mass,
+        cg_mm: total.cg.into(),
+        inertia_cg: from_matrix(&total.inertia),
+        inertia_origin: from_matrix(&inertia_origin),
+        has_mass_properties: walker.has_mass_properties,
+        lines: walker.lines,
+        warnings: walker.warnings.into_iter().collect(),
+        budget,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::component::{ComponentCategory, MakeBuy, MassProperties};
+
+    fn part(pn: &str, mass: f64, cg: [f64; 3], inertia: Option<InertiaTensor>) -> Component {
+        let mut cmp = Component::new(
+            pn.to_string(),
+            format!("Part {}", pn),
+            MakeBuy::Buy,
+            ComponentCategory::Mechanical,
+            "test".to_string(),
+        );
+        cmp.mass_kg = Some(mass);
+        cmp.mass_properties = Some(MassProperties { cg_mm: cg, inertia });
+        cmp
+    }
+
+    fn at(origin: [f64; 3]) -> CoordinateSystem {
+        CoordinateSystem {
+            origin,
+            ..Default::default()
+        }
+    }
+
+    fn item(id: &str, quantity: u32, placements: Vec<CoordinateSystem>) -> BomItem {
+        BomItem {
+            component_id: id.to_string(),
+            quantity,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements,
+        }
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_two_point_masses() {
+        // 1 kg at x = -100 and 3 kg at x = +100: CG at x = 50
+        let a = part("A", 1.0, [0.0; 3], None);
+        let b = part("B", 3.0, [0.0; 3], None);
+        let mut top = Assembly::new("TOP".to_string(), "Top".to_string(), "test".to_string());
+        top.bom
+            .push(item(&a.id.to_string(), 1, vec![at([-100.0, 0.0, 0.0])]));
+        top.bom
+            .push(item(&b.id.to_string(), 1, vec![at([100.0, 0.0, 0.0])]));
+
+        let rollup = rollup(&top, &[], &[a, b], None);
+        assert!(approx(rollup.mass_kg, 4.0));
+        assert!(approx(rollup.cg_mm[0], 50.0));
+        // About the CG: 1*150² + 3*50² = 30000 about y and z, nothing about x
+        assert!(approx(rollup.inertia_cg.ixx, 0.0));
+        assert!(approx(rollup.inertia_cg.iyy, 30000.0));
+        assert!(approx(rollup.inertia_cg.izz, 30000.0));
+        // About the origin: 4 * 100² = 40000
+        assert!(approx(rollup.inertia_origin.iyy, 40000.0));
+        assert_eq!(rollup.warnings.len(), 2);
+        assert!(rollup
+            .warnings
+            .iter()
+            .all(|w| w.issue == MassIssue::NoInertia));
+    }
+
+    #[test]
+    fn test_rotated_subassembly_placements() {
+        // Slender part along its own x axis
+        let rod = part(
+            "ROD",
+            2.0,
+            [10.0, 0.0, 0.0],
+            Some(InertiaTensor {
+                ixx: 1.0,
+                iyy: 50.0,
+                izz: 50.0,
+                ..Default::default()
+            }),
+        );
+        let mut sub = Assembly::new("SUB".to_string(), "Sub".to_string(), "test".to_string());
+        sub.bom.push(item(
+            &rod.id.to_string(),
+            2,
+            vec![at([0.0, 0.0, 0.0]), at([0.0, 0.0, 20.0])],
+        ));
+
+        // Sub-assembly rotated 90° about z and offset along y
+        let mut top = Assembly::new("TOP".to_string(), "Top".to_string(), "test".to_string());
+        top.bom.push(item(
+            &sub.id.to_string(),
+            1,
+            vec![CoordinateSystem {
+                origin: [0.0, 100.0, 0.0],
+                x_axis: [0.0, 1.0, 0.0],
+                z_axis: [0.0, 0.0, 1.0],
+            }],
+        ));
+        top.mass_budget = Some(MassBudget {
+            max_kg: 5.0,
+            requirement: None,
+        });
+
+        let assemblies = vec![sub];
+        let rollup = rollup(&top, &assemblies, &[rod], None);
+        assert!(approx(rollup.mass_kg, 4.0));
+        // Rod CG (10, 0, z) maps to (0, 110, z); instances at z = 0 and 20
+        assert!(approx(rollup.cg_mm[0], 0.0));
+        assert!(approx(rollup.cg_mm[1], 110.0));
+        assert!(approx(rollup.cg_mm[2], 10.0));
+        // Rod axis now along y; the z offsets of ±10 add 2 * 2 * 10² about x and y
+        assert!(approx(rollup.inertia_cg.ixx, 500.0));
+        assert!(approx(rollup.inertia_cg.iyy, 402.0));
+        assert!(approx(rollup.inertia_cg.izz, 100.0));
+        assert!(rollup.warnings.is_empty());
+        assert_eq!(rollup.lines.len(), 2);
+        assert_eq!(rollup.lines[1].depth, 1);
+        assert_eq!(rollup.lines[1].quantity, 2);
+
+        let budget = rollup.budget.unwrap();
+        assert!(budget.within_budget);
+        assert!(approx(budget.margin_kg, 1.0));
+        assert!(approx(budget.margin_percent, 20.0));
+    }
+}
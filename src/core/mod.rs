@@ -1,18 +1,40 @@
 //! Core module - fundamental types and utilities
 
+pub mod audit_schedule;
+pub mod bom_diff;
+pub mod bom_import;
 pub mod cache;
+pub mod compliance;
 pub mod config;
+pub mod currency;
+pub mod dhr;
+pub mod eight_d;
 pub mod entity;
+pub mod field_feedback;
 pub mod gdt_torsor;
+pub mod genealogy;
 pub mod git;
 pub mod identity;
+pub mod line_balance;
 pub mod links;
 pub mod loader;
 pub mod manufacturing;
+pub mod mass_properties;
+pub mod mrb;
+pub mod mrp;
+pub mod ncr_trends;
+pub mod obsolescence;
 pub mod project;
 pub mod provider;
+pub mod quality_metrics;
+pub mod receiving;
+pub mod rfq;
+pub mod sampling;
 pub mod sdt;
 pub mod shortid;
+pub mod should_cost;
+pub mod sourcing;
+pub mod supplier_scorecard;
 pub mod suspect;
 pub mod team;
 pub mod workflow;
@@ -36,8 +58,10 @@ pub use team::{Role, TeamMember, TeamRoster};
 pub use workflow::{WorkflowConfig, WorkflowEngine, WorkflowError};
 
 pub use manufacturing::{
-    create_execution_steps_from_routing, step_min_approvals, step_required_roles,
-    step_requires_approval, step_requires_signature, LotWorkflow, LotWorkflowConfig,
+    covering_deviations, create_execution_steps_from_routing, parse_step_value, step_data_specs,
+    step_min_approvals, step_required_roles, step_requires_approval, step_requires_signature,
+    step_sampling, LotWorkflow, LotWorkflowConfig, StepDataCheck, StepDataSource, StepDataSpec,
+    StepSampling,
 };
 
 pub use gdt_torsor::{
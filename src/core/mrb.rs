@@ -0,0 +1,164 @@
+//! Material Review Board (MRB) sign-off rules for NCR dispositions
+//!
+//! Dispositions that accept nonconforming material (use-as-is, repair) need
+//! sign-off from the configured MRB roles before the NCR can be closed. Each
+//! sign-off is an `ApprovalRecord` on the disposition, with the MRB role in
+//! `role`. Customer concurrence is recorded under the role `customer`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::team::Role;
+use crate::entities::ncr::{Disposition, DispositionDecision};
+
+/// Role name recorded for customer concurrence
+pub const CUSTOMER_ROLE: &str = "customer";
+
+/// MRB configuration (`mrb` in config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MrbConfig {
+    /// Dispositions that require MRB sign-off
+    pub dispositions: Vec<DispositionDecision>,
+
+    /// Team roles that must each sign off
+    pub required_roles: Vec<Role>,
+
+    /// Dispositions that also require customer concurrence
+    pub customer_dispositions: Vec<DispositionDecision>,
+
+    /// Sign-offs must be made with a signed commit (`--sign`) whose
+    /// signature verified
+    pub require_signature: bool,
+}
+
+impl Default for MrbConfig {
+    fn default() -> Self {
+        Self {
+            dispositions: vec![DispositionDecision::UseAsIs, DispositionDecision::Repair],
+            required_roles: vec![Role::Quality, Role::Engineering],
+            customer_dispositions: Vec::new(),
+            require_signature: false,
+        }
+    }
+}
+
+impl MrbConfig {
+    /// Whether a disposition needs MRB sign-off
+    pub fn is_required(&self, disposition: &Disposition) -> bool {
+        disposition.mrb_required
+            || disposition
+                .decision
+                .is_some_and(|d| self.dispositions.contains(&d))
+    }
+
+    /// Role names that must sign off on a disposition, in signing order
+    pub fn required_signoffs(&self, disposition: &Disposition) -> Vec<String> {
+        if !self.is_required(disposition) {
+            return Vec::new();
+        }
+        let mut roles: Vec<String> = self.required_roles.iter().map(|r| r.to_string()).collect();
+        if disposition
+            .decision
+            .is_some_and(|d| self.customer_dispositions.contains(&d))
+        {
+            roles.push(CUSTOMER_ROLE.to_string());
+        }
+        roles
+    }
+
+    /// Required sign-offs not yet recorded
+    ///
+    /// With `require_signature`, only sign-offs with a verified signature count.
+    pub fn missing_signoffs(&self, disposition: &Disposition) -> Vec<String> {
+        self.required_signoffs(disposition)
+            .into_iter()
+            .filter(|role| {
+                !disposition.approvals.iter().any(|a| {
+                    a.role.as_deref() == Some(role.as_str())
+                        && (!self.require_signature || a.signature_verified == Some(true))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Whether a use-as-is disposition lacks its deviation or engineering justification
+pub fn missing_justification(disposition: &Disposition) -> bool {
+    disposition.decision == Some(DispositionDecision::UseAsIs)
+        && disposition.deviation.is_none()
+        && disposition
+            .justification
+            .as_deref()
+            .is_none_or(|j| j.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::workflow::ApprovalRecord;
+
+    fn signoff(role: &str, verified: Option<bool>) -> ApprovalRecord {
+        ApprovalRecord {
+            approver: "Jane Doe".to_string(),
+            email: None,
+            role: Some(role.to_string()),
+            timestamp: chrono::Utc::now(),
+            comment: None,
+            signature_verified: verified,
+            signing_key: verified.map(|_| "ABCD1234".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_missing_signoffs() {
+        let mut config = MrbConfig::default();
+        let mut disp = Disposition {
+            decision: Some(DispositionDecision::Rework),
+            ..Default::default()
+        };
+        assert!(config.missing_signoffs(&disp).is_empty());
+
+        disp.decision = Some(DispositionDecision::Repair);
+        assert_eq!(
+            config.missing_signoffs(&disp),
+            vec!["quality", "engineering"]
+        );
+
+        disp.approvals.push(signoff("quality", None));
+        assert_eq!(config.missing_signoffs(&disp), vec!["engineering"]);
+
+        config.customer_dispositions = vec![DispositionDecision::Repair];
+        assert_eq!(
+            config.missing_signoffs(&disp),
+            vec!["engineering", "customer"]
+        );
+
+        // A signing key alone is not enough; the signature must have verified
+        config.require_signature = true;
+        disp.approvals.push(signoff("engineering", Some(false)));
+        assert_eq!(
+            config.missing_signoffs(&disp),
+            vec!["quality", "engineering", "customer"]
+        );
+        disp.approvals[1].signature_verified = Some(true);
+        assert_eq!(config.missing_signoffs(&disp), vec!["quality", "customer"]);
+    }
+
+    #[test]
+    fn test_use_as_is_needs_justification() {
+        let mut disp = Disposition {
+            decision: Some(DispositionDecision::UseAsIs),
+            ..Default::default()
+        };
+        assert!(missing_justification(&disp));
+
+        disp.justification = Some("Fit and function unaffected per analysis".to_string());
+        assert!(!missing_justification(&disp));
+
+        disp.justification = None;
+        disp.deviation = Some(crate::core::identity::EntityId::new(
+            crate::core::identity::EntityPrefix::Dev,
+        ));
+        assert!(!missing_justification(&disp));
+    }
+}
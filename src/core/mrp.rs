@@ -0,0 +1,691 @@
+//! Material requirements planning from planned production lots
+//!
+//! Open lots (planned, in progress or on hold) are the demand: each lot's
+//! quantity is exploded through its product's multi-level BOM and reduced by
+//! the material the lot has already recorded as used. A lot needs its
+//! material on its start date, or today when that has passed. Demand for each
+//! buy part is netted in need-date order against on-hand stock above safety
+//! stock, and every shortage becomes a planned order. Order quantities are
+//! raised to the MOQ and to the lowest price break, then to a higher break
+//! when that costs no more in total; the excess is carried forward to later
+//! demand. Orders are placed the lead time before they are needed. Make parts
+//! are netted the same way but listed as builds rather than purchases.
+//!
+//! Terms come from the component's selected quote, else its first supplier
+//! entry, else its `unit_cost` with no supplier.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::core::currency::Converter;
+use crate::core::obsolescence::bom_quantities;
+use crate::entities::assembly::Assembly;
+use crate::entities::component::{Component, MakeBuy};
+use crate::entities::lot::{Lot, LotStatus};
+use crate::entities::quote::{PriceBreak, Quote};
+
+/// On-hand stock of one component
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stock {
+    pub on_hand: u32,
+    /// Quantity to keep in stock; only stock above it is available
+    #[serde(default)]
+    pub safety_stock: u32,
+}
+
+/// Parse an inventory CSV into stock by component ID
+///
+/// The part column may be `component`, `id`, `part_number` or `part`; the
+/// quantity column `on_hand`, `quantity`, `qty` or `stock`; `safety_stock` is
+/// optional. Rows for the same component (e.g. several bins) are added up.
+/// `resolve` maps the part column to a component ID; parts it does not know
+/// are returned separately.
+pub fn parse_inventory<R: Read>(
+    reader: R,
+    resolve: impl Fn(&str) -> Option<String>,
+) -> Result<(HashMap<String, Stock>, Vec<String>), String> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers: Vec<String> = rdr
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| headers.iter().position(|h| h == n))
+    };
+    let part_col = column(&["component", "id", "part_number", "part"])
+        .ok_or("Inventory needs a component, id, part_number or part column".to_string())?;
+    let qty_col = column(&["on_hand", "quantity", "qty", "stock"])
+        .ok_or("Inventory needs an on_hand, quantity, qty or stock column".to_string())?;
+    let safety_col = column(&["safety_stock", "safety"]);
+
+    let mut stock: HashMap<String, Stock> = HashMap::new();
+    let mut unknown = Vec::new();
+    for (idx, record) in rdr.records().enumerate() {
+        let row = idx + 2;
+        let record = record.map_err(|e| format!("Row {}: {}", row, e))?;
+        let part = record.get(part_col).unwrap_or_default();
+        if part.is_empty() {
+            continue;
+        }
+        let number = |col: Option<usize>| -> Result<u32, String> {
+            match col.and_then(|c| record.get(c)).filter(|s| !s.is_empty()) {
+                Some(s) => s
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|n| *n >= 0.0)
+                    .map(|n| n.floor() as u32)
+                    .ok_or(format!("Row {}: invalid quantity '{}'", row, s)),
+                None => Ok(0),
+            }
+        };
+        let on_hand = number(Some(qty_col))?;
+        let safety_stock = number(safety_col)?;
+        match resolve(part) {
+            Some(id) => {
+                let entry = stock.entry(id).or_default();
+                entry.on_hand += on_hand;
+                entry.safety_stock = entry.safety_stock.max(safety_stock);
+            }
+            None => unknown.push(part.to_string()),
+        }
+    }
+    Ok((stock, unknown))
+}
+
+/// Where and on what terms a component is bought
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Source {
+    /// Supplier ID (SUP-...) or name; none when nothing is on file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
+    /// Quote the terms come from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+    /// Price breaks in the reporting currency, lowest quantity first
+    pub price_breaks: Vec<PriceBreak>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moq: Option<u32>,
+    /// Standard lead time (price breaks may override it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_time_days: Option<u32>,
+}
+
+impl Source {
+    /// Purchasing terms for a component
+    pub fn for_component(
+        component: &Component,
+        quotes: &HashMap<String, &Quote>,
+        converter: &mut Converter,
+    ) -> Self {
+        if let Some(quote) = component
+            .selected_quote
+            .as_ref()
+            .and_then(|id| quotes.get(id))
+        {
+            let mut price_breaks: Vec<PriceBreak> = quote
+                .price_breaks
+                .iter()
+                .map(|pb| PriceBreak {
+                    min_qty: pb.min_qty,
+                    unit_price: converter.convert(pb.unit_price, quote.currency),
+                    lead_time_days: pb.lead_time_days,
+                })
+                .collect();
+            price_breaks.sort_by_key(|pb| pb.min_qty);
+            return Self {
+                supplier: Some(quote.supplier.clone()),
+                quote: Some(quote.id.to_string()),
+                price_breaks,
+                moq: quote.moq,
+                lead_time_days: quote.lead_time_days,
+            };
+        }
+
+        let single_price = |cost: Option<f64>, converter: &mut Converter| {
+            cost.map(|c| PriceBreak {
+                min_qty: 1,
+                unit_price: converter.convert_unit_cost(c),
+                lead_time_days: None,
+            })
+            .into_iter()
+            .collect()
+        };
+        match component.suppliers.first() {
+            Some(sup) => Self {
+                supplier: sup
+                    .supplier_id
+                    .clone()
+                    .or_else(|| Some(sup.name.clone()).filter(|n| !n.is_empty())),
+                quote: None,
+                price_breaks: single_price(sup.unit_cost.or(component.unit_cost), converter),
+                moq: sup.moq,
+                lead_time_days: sup.lead_time_days,
+            },
+            None => Self {
+                price_breaks: single_price(component.unit_cost, converter),
+                ..Self::default()
+            },
+        }
+    }
+
+    fn price_break(&self, qty: u32) -> Option<&PriceBreak> {
+        self.price_breaks
+            .iter()
+            .filter(|pb| pb.min_qty <= qty)
+            .max_by_key(|pb| pb.min_qty)
+    }
+
+    /// Unit price when buying `qty`
+    pub fn unit_price(&self, qty: u32) -> Option<f64> {
+        self.price_break(qty).map(|pb| pb.unit_price)
+    }
+
+    /// Lead time when buying `qty`
+    pub fn lead_time(&self, qty: u32) -> Option<u32> {
+        self.price_break(qty)
+            .and_then(|pb| pb.lead_time_days)
+            .or(self.lead_time_days)
+    }
+
+    /// Quantity to order to cover a shortage of `net`
+    ///
+    /// Raised to the MOQ and the lowest price break, then to the higher price
+    /// break with the lowest total cost if that is no more than the cost of
+    /// the smaller order.
+    pub fn order_qty(&self, net: u32) -> u32 {
+        let mut qty = net.max(self.moq.unwrap_or(0)).max(1);
+        if self.unit_price(qty).is_none() {
+            if let Some(lowest) = self.price_breaks.first() {
+                qty = qty.max(lowest.min_qty);
+            }
+        }
+        let Some(price) = self.unit_price(qty) else {
+            return qty;
+        };
+        let mut best = (qty, qty as f64 * price);
+        for pb in self.price_breaks.iter().filter(|pb| pb.min_qty > qty) {
+            let total = pb.min_qty as f64 * pb.unit_price;
+            if total < best.1 - 1e-9 || (total <= best.1 + 1e-9 && pb.min_qty > best.0) {
+                best = (pb.min_qty, total);
+            }
+        }
+        best.0
+    }
+}
+
+/// A lot counted as demand
+#[derive(Debug, Clone, Serialize)]
+pub struct LotDemand {
+    pub lot: String,
+    pub title: String,
+    pub status: LotStatus,
+    pub product: String,
+    pub quantity: u32,
+    pub need_date: NaiveDate,
+}
+
+/// A purchase to place
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedOrder {
+    pub component: String,
+    pub part_number: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+    /// Lot whose demand triggered the order
+    pub lot: String,
+    pub need_date: NaiveDate,
+    /// Need date less the lead time
+    pub order_date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_time_days: Option<u32>,
+    /// Shortage being covered
+    pub net_quantity: u32,
+    /// Quantity to order after MOQ and price breaks
+    pub quantity: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_price: Option<f64>,
+    /// Order date has already passed
+    pub late: bool,
+}
+
+/// Netting summary for one component
+#[derive(Debug, Clone, Serialize)]
+pub struct PartPlan {
+    pub component: String,
+    pub part_number: String,
+    pub title: String,
+    pub make_buy: MakeBuy,
+    /// Total remaining demand across lots
+    pub gross: u32,
+    pub on_hand: u32,
+    pub safety_stock: u32,
+    /// Demand not covered by available stock
+    pub net: u32,
+    /// Quantity planned to buy (buy parts) or build (make parts)
+    pub planned: u32,
+    /// Earliest need date
+    pub first_need: NaiveDate,
+    /// Stock left after all demand and planned orders
+    pub projected_on_hand: u32,
+}
+
+/// Something that makes the plan incomplete
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum PlanWarning {
+    /// Lot has no product to explode
+    NoProduct { lot: String },
+    /// Lot has no quantity
+    NoQuantity { lot: String },
+    /// BOM or lot product references an unknown part
+    UnknownPart { lot: String, part: String },
+    /// Buy part with no lead time; ordered on the need date
+    NoLeadTime { component: String },
+    /// Buy part with no price
+    NoPrice { component: String },
+    /// Buy part with no supplier
+    NoSupplier { component: String },
+    /// Selected quote has expired
+    QuoteExpired {
+        component: String,
+        quote: String,
+        valid_until: NaiveDate,
+    },
+}
+
+/// Time-phased material plan
+#[derive(Debug, Clone, Serialize)]
+pub struct MrpPlan {
+    pub as_of: NaiveDate,
+    pub lots: Vec<LotDemand>,
+    pub parts: Vec<PartPlan>,
+    /// Purchases by supplier, then order date
+    pub orders: Vec<PlannedOrder>,
+    pub warnings: Vec<PlanWarning>,
+}
+
+impl MrpPlan {
+    /// Total value of planned orders with a price
+    pub fn total_value(&self) -> f64 {
+        self.orders
+            .iter()
+            .filter_map(|o| o.extended_price)
+            .fold(0.0, |sum, p| sum + p)
+    }
+
+    /// Orders whose order date has passed
+    pub fn late_orders(&self) -> usize {
+        self.orders.iter().filter(|o| o.late).count()
+    }
+
+    /// Make parts that need building
+    pub fn builds(&self) -> impl Iterator<Item = &PartPlan> {
+        self.parts
+            .iter()
+            .filter(|p| p.make_buy == MakeBuy::Make && p.planned > 0)
+    }
+}
+
+/// Whether a lot still needs material
+pub fn is_open(lot: &Lot) -> bool {
+    matches!(
+        lot.lot_status,
+        LotStatus::Planned | LotStatus::InProgress | LotStatus::OnHold
+    )
+}
+
+struct Requirement {
+    lot: String,
+    need_date: NaiveDate,
+    quantity: u32,
+}
+
+/// Build the material plan for the open lots in `lots`
+///
+/// `quotes` and `stock` are keyed by full ID. Prices are in the converter's
+/// target currency and the converter's date is the planning date.
+pub fn plan(
+    lots: &[Lot],
+    assemblies: &HashMap<String, &Assembly>,
+    components: &HashMap<String, &Component>,
+    quotes: &HashMap<String, &Quote>,
+    stock: &HashMap<String, Stock>,
+    converter: &mut Converter,
+) -> MrpPlan {
+    let as_of = converter.as_of;
+    let mut warnings = Vec::new();
+    let mut demands = Vec::new();
+    let mut requirements: BTreeMap<String, Vec<Requirement>> = BTreeMap::new();
+
+    for lot in lots.iter().filter(|l| is_open(l)) {
+        let lot_id = lot.id.to_string();
+        let Some(product) = lot.links.product.clone() else {
+            warnings.push(PlanWarning::NoProduct { lot: lot_id });
+            continue;
+        };
+        let Some(quantity) = lot.quantity.filter(|q| *q > 0) else {
+            warnings.push(PlanWarning::NoQuantity { lot: lot_id });
+            continue;
+        };
+        let need_date = lot.start_date.unwrap_or(as_of).max(as_of);
+
+        let per_unit: BTreeMap<String, u32> = match assemblies.get(&product) {
+            Some(asm) => bom_quantities(asm, assemblies),
+            None => BTreeMap::from([(product.clone(), 1)]),
+        };
+        for (part, qty) in per_unit {
+            if !components.contains_key(&part) {
+                warnings.push(PlanWarning::UnknownPart {
+                    lot: lot_id.clone(),
+                    part,
+                });
+                continue;
+            }
+            let used: u32 = lot
+                .materials_used
+                .iter()
+                .filter(|m| m.component.as_deref() == Some(part.as_str()))
+                .filter_map(|m| m.quantity)
+                .sum();
+            let remaining = (qty * quantity).saturating_sub(used);
+            if remaining > 0 {
+                requirements.entry(part).or_default().push(Requirement {
+                    lot: lot_id.clone(),
+                    need_date,
+                    quantity: remaining,
+                });
+            }
+        }
+
+        demands.push(LotDemand {
+            lot: lot_id,
+            title: lot.title.clone(),
+            status: lot.lot_status,
+            product,
+            quantity,
+            need_date,
+        });
+    }
+    demands.sort_by(|a, b| a.need_date.cmp(&b.need_date).then(a.lot.cmp(&b.lot)));
+
+    let mut parts = Vec::new();
+    let mut orders = Vec::new();
+    for (id, mut reqs) in requirements {
+        let cmp = components[&id];
+        reqs.sort_by(|a, b| a.need_date.cmp(&b.need_date).then(a.lot.cmp(&b.lot)));
+        let stock = stock.get(&id).copied().unwrap_or_default();
+        let source =
+            (cmp.make_buy == MakeBuy::Buy).then(|| Source::for_component(cmp, quotes, converter));
+
+        if let Some(ref source) = source {
+            if let Some(quote) = source.quote.as_ref().and_then(|q| quotes.get(q)) {
+                if let Some(valid_until) = quote.valid_until.filter(|d| *d < as_of) {
+                    warnings.push(PlanWarning::QuoteExpired {
+                        component: id.clone(),
+                        quote: quote.id.to_string(),
+                        valid_until,
+                    });
+                }
+            }
+            if source.supplier.is_none() {
+                warnings.push(PlanWarning::NoSupplier {
+                    component: id.clone(),
+                });
+            }
+            if source.price_breaks.is_empty() {
+                warnings.push(PlanWarning::NoPrice {
+                    component: id.clone(),
+                });
+            }
+        }
+
+        // Net in need-date order; negative means safety stock is already short
+        let mut available = stock.on_hand as i64 - stock.safety_stock as i64;
+        let mut gross = 0;
+        let mut net = 0;
+        let mut planned = 0;
+        let mut missing_lead_time = false;
+        for req in &reqs {
+            gross += req.quantity;
+            let covered = available.clamp(0, req.quantity as i64);
+            available -= req.quantity as i64;
+            if available >= 0 {
+                continue;
+            }
+            let shortage = (-available) as u32;
+            net += req.quantity - covered as u32;
+            let Some(ref source) = source else {
+                // Make part: build exactly the shortage
+                planned += shortage;
+                available = 0;
+                continue;
+            };
+            let quantity = source.order_qty(shortage);
+            let lead_time_days = source.lead_time(quantity);
+            missing_lead_time |= lead_time_days.is_none();
+            let order_date = req.need_date - Duration::days(lead_time_days.unwrap_or(0) as i64);
+            let unit_price = source.unit_price(quantity);
+            orders.push(PlannedOrder {
+                component: id.clone(),
+                part_number: cmp.part_number.clone(),
+                title: cmp.title.clone(),
+                supplier: source.supplier.clone(),
+                quote: source.quote.clone(),
+                lot: req.lot.clone(),
+                need_date: req.need_date,
+                order_date,
+                lead_time_days,
+                net_quantity: shortage,
+                quantity,
+                unit_price,
+                extended_price: unit_price.map(|p| p * quantity as f64),
+                late: order_date < as_of,
+            });
+            planned += quantity;
+            available += quantity as i64;
+        }
+        if missing_lead_time {
+            warnings.push(PlanWarning::NoLeadTime {
+                component: id.clone(),
+            });
+        }
+
+        parts.push(PartPlan {
+            component: id.clone(),
+            part_number: cmp.part_number.clone(),
+            title: cmp.title.clone(),
+            make_buy: cmp.make_buy,
+            gross,
+            on_hand: stock.on_hand,
+            safety_stock: stock.safety_stock,
+            net,
+            planned,
+            first_need: reqs[0].need_date,
+            projected_on_hand: (available + stock.safety_stock as i64).max(0) as u32,
+        });
+    }
+
+    orders.sort_by(|a, b| {
+        a.supplier
+            .is_none()
+            .cmp(&b.supplier.is_none())
+            .then(a.supplier.cmp(&b.supplier))
+            .then(a.order_date.cmp(&b.order_date))
+            .then(a.part_number.cmp(&b.part_number))
+    });
+
+    MrpPlan {
+        as_of,
+        lots: demands,
+        parts,
+        orders,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::currency::CurrencyConfig;
+    use crate::entities::assembly::BomItem;
+    use crate::entities::component::{ComponentCategory, ComponentSupplier};
+    use crate::entities::lot::MaterialUsed;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn item(id: &str, quantity: u32) -> BomItem {
+        BomItem {
+            component_id: id.to_string(),
+            quantity,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        }
+    }
+
+    fn lot(product: &str, quantity: u32, start: &str) -> Lot {
+        let mut lot = Lot::new("Build".to_string(), "test".to_string());
+        lot.lot_status = LotStatus::Planned;
+        lot.quantity = Some(quantity);
+        lot.start_date = Some(date(start));
+        lot.links.product = Some(product.to_string());
+        lot
+    }
+
+    #[test]
+    fn test_order_qty_moq_and_price_breaks() {
+        let source = Source {
+            price_breaks: vec![
+                PriceBreak {
+                    min_qty: 10,
+                    unit_price: 1.0,
+                    lead_time_days: None,
+                },
+                PriceBreak {
+                    min_qty: 100,
+                    unit_price: 0.5,
+                    lead_time_days: Some(30),
+                },
+            ],
+            moq: Some(25),
+            lead_time_days: Some(10),
+            ..Source::default()
+        };
+        // MOQ: 25 x 1.00 = 25 < 100 x 0.50 = 50
+        assert_eq!(source.order_qty(5), 25);
+        // 60 x 1.00 = 60 > 100 x 0.50 = 50, so buy 100
+        assert_eq!(source.order_qty(60), 100);
+        assert_eq!(source.lead_time(25), Some(10));
+        assert_eq!(source.lead_time(100), Some(30));
+    }
+
+    #[test]
+    fn test_plan_explodes_nets_and_phases_orders() {
+        let mut sub = Assembly::new("SUB", "Sub", "test");
+        sub.bom = vec![item("CMP-BOLT", 4)];
+        let mut top = Assembly::new("TOP", "Top", "test");
+        top.bom = vec![item(&sub.id.to_string(), 2), item("CMP-PCB", 1)];
+
+        let mut bolt = Component::new(
+            "BOLT".to_string(),
+            "Bolt".to_string(),
+            MakeBuy::Buy,
+            ComponentCategory::Mechanical,
+            "test".to_string(),
+        );
+        bolt.suppliers = vec![ComponentSupplier {
+            supplier_id: Some("SUP-A".to_string()),
+            name: String::new(),
+            supplier_pn: None,
+            lead_time_days: Some(14),
+            moq: Some(100),
+            unit_cost: Some(0.10),
+        }];
+        let pcb = Component::new(
+            "PCB".to_string(),
+            "Board".to_string(),
+            MakeBuy::Make,
+            ComponentCategory::Electrical,
+            "test".to_string(),
+        );
+
+        let assemblies: HashMap<String, &Assembly> = [&sub, &top]
+            .into_iter()
+            .map(|a| (a.id.to_string(), a))
+            .collect();
+        let components: HashMap<String, &Component> = HashMap::from([
+            ("CMP-BOLT".to_string(), &bolt),
+            ("CMP-PCB".to_string(), &pcb),
+        ]);
+        let stock = HashMap::from([(
+            "CMP-BOLT".to_string(),
+            Stock {
+                on_hand: 50,
+                safety_stock: 10,
+            },
+        )]);
+
+        let top_id = top.id.to_string();
+        let mut first = lot(&top_id, 5, "2026-07-01");
+        first.materials_used.push(MaterialUsed {
+            component: Some("CMP-BOLT".to_string()),
+            quantity: Some(8),
+            ..MaterialUsed::default()
+        });
+        let second = lot(&top_id, 10, "2026-08-01");
+        let mut done = lot(&top_id, 100, "2026-05-01");
+        done.lot_status = LotStatus::Completed;
+
+        let mut conv = Converter::new(CurrencyConfig::default(), None, date("2026-06-20"));
+        let plan = plan(
+            &[first, second, done],
+            &assemblies,
+            &components,
+            &HashMap::new(),
+            &stock,
+            &mut conv,
+        );
+
+        assert_eq!(plan.lots.len(), 2);
+        // Bolts: 5 x 8 - 8 used = 32, then 10 x 8 = 80; 40 available
+        let bolts = plan
+            .parts
+            .iter()
+            .find(|p| p.component == "CMP-BOLT")
+            .unwrap();
+        assert_eq!(bolts.gross, 112);
+        assert_eq!(bolts.net, 72);
+        // Only the second lot runs short (by 72), raised to the MOQ of 100
+        assert_eq!(plan.orders.len(), 1);
+        let order = &plan.orders[0];
+        assert_eq!(order.net_quantity, 72);
+        assert_eq!(order.quantity, 100);
+        assert_eq!(order.order_date, date("2026-07-18"));
+        assert!(!order.late);
+        assert_eq!(bolts.projected_on_hand, 38);
+
+        // Boards are built, not bought
+        let boards: Vec<&PartPlan> = plan.builds().collect();
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].planned, 15);
+        assert!(plan.warnings.is_empty());
+    }
+}
@@ -0,0 +1,382 @@
+//! Recurring-nonconformance detection and CAPA escalation rules
+//!
+//! Rules group NCRs by shared attributes (component, category, ...) and fire
+//! when a group reaches a threshold count within a rolling window. Rules are
+//! evaluated in order; an NCR claimed by an earlier escalation is not counted
+//! again by later rules. NCRs already linked to a CAPA are ignored.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::entities::capa::{Capa, CapaType, SourceType};
+use crate::entities::ncr::{Ncr, NcrCategory, NcrSeverity, NcrType};
+
+/// NCR attribute used to group NCRs within a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendKey {
+    /// Linked component (`links.component`)
+    Component,
+    /// Linked process (`links.process`)
+    Process,
+    /// Affected part number (`affected_items.part_number`)
+    PartNumber,
+    Category,
+    NcrType,
+    Severity,
+}
+
+impl std::fmt::Display for TrendKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrendKey::Component => write!(f, "component"),
+            TrendKey::Process => write!(f, "process"),
+            TrendKey::PartNumber => write!(f, "part_number"),
+            TrendKey::Category => write!(f, "category"),
+            TrendKey::NcrType => write!(f, "ncr_type"),
+            TrendKey::Severity => write!(f, "severity"),
+        }
+    }
+}
+
+impl TrendKey {
+    /// Value of this key on an NCR, if set
+    fn value_of(&self, ncr: &Ncr) -> Option<String> {
+        match self {
+            TrendKey::Component => ncr.links.component.as_ref().map(|id| id.to_string()),
+            TrendKey::Process => ncr.links.process.as_ref().map(|id| id.to_string()),
+            TrendKey::PartNumber => ncr
+                .affected_items
+                .as_ref()
+                .and_then(|a| a.part_number.clone())
+                .filter(|p| !p.is_empty()),
+            TrendKey::Category => Some(ncr.category.to_string()),
+            TrendKey::NcrType => Some(ncr.ncr_type.to_string()),
+            TrendKey::Severity => Some(ncr.severity.to_string()),
+        }
+    }
+}
+
+/// A configurable escalation rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationRule {
+    /// Rule name, used as the CAPA source reference for trends
+    pub name: String,
+
+    /// Attributes NCRs must share to be counted together (empty = all NCRs)
+    #[serde(default)]
+    pub group_by: Vec<TrendKey>,
+
+    /// Number of matching NCRs that triggers escalation
+    #[serde(default = "default_threshold")]
+    pub threshold: u32,
+
+    /// Only count NCRs reported within this many days (None = all time)
+    #[serde(default)]
+    pub window_days: Option<u32>,
+
+    /// Only count NCRs of these types
+    #[serde(default)]
+    pub ncr_types: Vec<NcrType>,
+
+    /// Only count NCRs at or above this severity
+    #[serde(default)]
+    pub min_severity: Option<NcrSeverity>,
+
+    /// Only count NCRs in these categories
+    #[serde(default)]
+    pub categories: Vec<NcrCategory>,
+
+    /// Type of CAPA to propose
+    #[serde(default)]
+    pub capa_type: CapaType,
+}
+
+fn default_threshold() -> u32 {
+    1
+}
+
+/// NCR trend configuration (`ncr_trends` in config.yaml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NcrTrendConfig {
+    /// Escalation rules; the built-in defaults apply when empty
+    pub rules: Vec<EscalationRule>,
+}
+
+impl NcrTrendConfig {
+    /// Configured rules, or the defaults when none are configured
+    pub fn effective_rules(&self) -> Vec<EscalationRule> {
+        if self.rules.is_empty() {
+            default_rules()
+        } else {
+            self.rules.clone()
+        }
+    }
+}
+
+/// Built-in rules: recurring component/category issues and critical customer NCRs
+pub fn default_rules() -> Vec<EscalationRule> {
+    vec![
+        EscalationRule {
+            name: "critical-customer".to_string(),
+            group_by: Vec::new(),
+            threshold: 1,
+            window_days: None,
+            ncr_types: vec![NcrType::Customer],
+            min_severity: Some(NcrSeverity::Critical),
+            categories: Vec::new(),
+            capa_type: CapaType::Corrective,
+        },
+        EscalationRule {
+            name: "recurring-component-category".to_string(),
+            group_by: vec![TrendKey::Component, TrendKey::Category],
+            threshold: 3,
+            window_days: Some(90),
+            ncr_types: Vec::new(),
+            min_severity: None,
+            categories: Vec::new(),
+            capa_type: CapaType::Corrective,
+        },
+    ]
+}
+
+fn severity_rank(severity: NcrSeverity) -> u8 {
+    match severity {
+        NcrSeverity::Minor => 0,
+        NcrSeverity::Major => 1,
+        NcrSeverity::Critical => 2,
+    }
+}
+
+/// Date an NCR counts from: report date, falling back to creation date
+pub fn ncr_date(ncr: &Ncr) -> NaiveDate {
+    ncr.report_date.unwrap_or_else(|| ncr.created.date_naive())
+}
+
+impl EscalationRule {
+    /// Whether an NCR passes this rule's filters and window
+    pub fn matches(&self, ncr: &Ncr, as_of: NaiveDate) -> bool {
+        if !self.ncr_types.is_empty() && !self.ncr_types.contains(&ncr.ncr_type) {
+            return false;
+        }
+        if !self.categories.is_empty() && !self.categories.contains(&ncr.category) {
+            return false;
+        }
+        if let Some(min) = self.min_severity {
+            if severity_rank(ncr.severity) < severity_rank(min) {
+                return false;
+            }
+        }
+        if let Some(days) = self.window_days {
+            let date = ncr_date(ncr);
+            if date > as_of || date < as_of - Duration::days(days as i64) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Source to record on a CAPA raised by this rule
+    pub fn capa_source(&self, ncrs: &[String]) -> (SourceType, String) {
+        if self.threshold <= 1 && ncrs.len() == 1 {
+            (SourceType::Ncr, ncrs[0].clone())
+        } else {
+            (SourceType::TrendAnalysis, self.name.clone())
+        }
+    }
+}
+
+/// One attribute value shared by an escalation's NCRs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrendKeyValue {
+    pub key: TrendKey,
+    pub value: String,
+}
+
+/// A group of NCRs that triggered a rule
+#[derive(Debug, Clone, Serialize)]
+pub struct Escalation {
+    pub rule: String,
+    pub key: Vec<TrendKeyValue>,
+    /// Matching NCR IDs, oldest first
+    pub ncrs: Vec<String>,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    pub highest_severity: NcrSeverity,
+    pub capa_type: CapaType,
+    pub source_type: SourceType,
+    pub source_reference: String,
+}
+
+/// Evaluate escalation rules against NCRs
+///
+/// NCRs linked to a CAPA (either `links.capa` on the NCR or listed in a
+/// CAPA's `links.ncrs`) are skipped.
+pub fn evaluate(
+    rules: &[EscalationRule],
+    ncrs: &[Ncr],
+    capas: &[Capa],
+    as_of: NaiveDate,
+) -> Vec<Escalation> {
+    let linked: HashSet<String> = capas
+        .iter()
+        .flat_map(|c| c.links.ncrs.iter().map(|id| id.to_string()))
+        .collect();
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut escalations = Vec::new();
+
+    for rule in rules {
+        let mut groups: BTreeMap<Vec<String>, Vec<&Ncr>> = BTreeMap::new();
+        for ncr in ncrs {
+            let id = ncr.id.to_string();
+            if ncr.links.capa.is_some() || linked.contains(&id) || claimed.contains(&id) {
+                continue;
+            }
+            if !rule.matches(ncr, as_of) {
+                continue;
+            }
+            let values: Option<Vec<String>> =
+                rule.group_by.iter().map(|k| k.value_of(ncr)).collect();
+            if let Some(values) = values {
+                groups.entry(values).or_default().push(ncr);
+            }
+        }
+
+        for (values, mut members) in groups {
+            if (members.len() as u32) < rule.threshold.max(1) {
+                continue;
+            }
+            members.sort_by_key(|n| (ncr_date(n), n.created));
+            let ids: Vec<String> = members.iter().map(|n| n.id.to_string()).collect();
+            let (source_type, source_reference) = rule.capa_source(&ids);
+            let highest_severity = members
+                .iter()
+                .map(|n| n.severity)
+                .max_by_key(|s| severity_rank(*s))
+                .unwrap_or_default();
+
+            claimed.extend(ids.iter().cloned());
+            escalations.push(Escalation {
+                rule: rule.name.clone(),
+                key: rule
+                    .group_by
+                    .iter()
+                    .zip(values)
+                    .map(|(key, value)| TrendKeyValue { key: *key, value })
+                    .collect(),
+                first_date: ncr_date(members[0]),
+                last_date: ncr_date(members[members.len() - 1]),
+                ncrs: ids,
+                highest_severity,
+                capa_type: rule.capa_type,
+                source_type,
+                source_reference,
+            });
+        }
+    }
+
+    escalations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+
+    fn ncr(category: NcrCategory, component: Option<&EntityId>, date: NaiveDate) -> Ncr {
+        let mut n: Ncr = serde_yml::from_str(&format!(
+            "id: {}\ntitle: Test\ncreated: 2024-01-01T00:00:00Z\nauthor: test\n",
+            EntityId::new(EntityPrefix::Ncr)
+        ))
+        .unwrap();
+        n.category = category;
+        n.links.component = component.cloned();
+        n.report_date = Some(date);
+        n
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_recurring_component_category() {
+        let cmp = EntityId::new(EntityPrefix::Cmp);
+        let ncrs = vec![
+            ncr(NcrCategory::Dimensional, Some(&cmp), date("2024-03-01")),
+            ncr(NcrCategory::Dimensional, Some(&cmp), date("2024-04-01")),
+            ncr(NcrCategory::Dimensional, Some(&cmp), date("2024-05-01")),
+            ncr(NcrCategory::Cosmetic, Some(&cmp), date("2024-05-01")),
+            ncr(NcrCategory::Dimensional, None, date("2024-05-01")),
+        ];
+
+        let esc = evaluate(&default_rules(), &ncrs, &[], date("2024-05-15"));
+        assert_eq!(esc.len(), 1);
+        assert_eq!(esc[0].rule, "recurring-component-category");
+        assert_eq!(esc[0].ncrs.len(), 3);
+        assert_eq!(esc[0].source_type, SourceType::TrendAnalysis);
+        assert_eq!(esc[0].first_date, date("2024-03-01"));
+        assert_eq!(esc[0].key[1].value, "dimensional");
+
+        // The oldest NCR falls outside the 90-day window
+        let esc = evaluate(&default_rules(), &ncrs, &[], date("2024-06-15"));
+        assert!(esc.is_empty());
+    }
+
+    #[test]
+    fn test_critical_customer_and_claiming() {
+        let cmp = EntityId::new(EntityPrefix::Cmp);
+        let mut ncrs = vec![
+            ncr(NcrCategory::Functional, Some(&cmp), date("2024-05-01")),
+            ncr(NcrCategory::Functional, Some(&cmp), date("2024-05-02")),
+            ncr(NcrCategory::Functional, Some(&cmp), date("2024-05-03")),
+        ];
+        ncrs[0].ncr_type = NcrType::Customer;
+        ncrs[0].severity = NcrSeverity::Critical;
+
+        let esc = evaluate(&default_rules(), &ncrs, &[], date("2024-05-10"));
+        // The critical customer NCR is claimed first, leaving only two for the trend rule
+        assert_eq!(esc.len(), 1);
+        assert_eq!(esc[0].rule, "critical-customer");
+        assert_eq!(esc[0].source_type, SourceType::Ncr);
+        assert_eq!(esc[0].source_reference, ncrs[0].id.to_string());
+    }
+
+    #[test]
+    fn test_linked_ncrs_skipped() {
+        let cmp = EntityId::new(EntityPrefix::Cmp);
+        let mut ncrs: Vec<Ncr> = (1..=3)
+            .map(|d| {
+                ncr(
+                    NcrCategory::Material,
+                    Some(&cmp),
+                    date(&format!("2024-05-0{}", d)),
+                )
+            })
+            .collect();
+        ncrs[0].links.capa = Some(EntityId::new(EntityPrefix::Capa));
+        let esc = evaluate(&default_rules(), &ncrs, &[], date("2024-05-10"));
+        assert!(esc.is_empty());
+
+        ncrs[0].links.capa = None;
+        let mut capa = Capa::new("Existing".to_string(), CapaType::Corrective, "t".into());
+        capa.links.ncrs.push(ncrs[1].id.clone());
+        let esc = evaluate(&default_rules(), &ncrs, &[capa], date("2024-05-10"));
+        assert!(esc.is_empty());
+    }
+
+    #[test]
+    fn test_rule_config_parses() {
+        let cfg: NcrTrendConfig = serde_yml::from_str(
+            "rules:\n  - name: supplier-major\n    group_by: [part_number]\n    threshold: 2\n    window_days: 30\n    ncr_types: [supplier]\n    min_severity: major\n",
+        )
+        .unwrap();
+        let rule = &cfg.effective_rules()[0];
+        assert_eq!(rule.group_by, vec![TrendKey::PartNumber]);
+        assert_eq!(rule.min_severity, Some(NcrSeverity::Major));
+        assert_eq!(rule.capa_type, CapaType::Corrective);
+        assert_eq!(NcrTrendConfig::default().effective_rules().len(), 2);
+    }
+}
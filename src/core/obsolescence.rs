@@ -0,0 +1,430 @@
+//! Obsolescence and single-source analysis for BOM parts
+//!
+//! A part's lifecycle is its own `lifecycle` when recorded, else the best
+//! status among its approved manufacturer part numbers. Buy parts are counted
+//! by approved sources: distinct manufacturers of approved, not end-of-life
+//! AML entries, or named suppliers when the part has no AML. Approved
+//! alternates are other approved AML entries that are not end-of-life, and
+//! released or approved components linked as interchangeable or replacing the
+//! part.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::core::entity::Status;
+use crate::entities::assembly::Assembly;
+use crate::entities::component::{Component, LifecycleStatus, MakeBuy};
+
+/// Why a part is flagged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFlag {
+    Obsolete,
+    LastTimeBuy,
+    NoApprovedSource,
+    SingleSource,
+    /// An approved manufacturer part number is end-of-life
+    EndOfLifeMpn,
+    Nrnd,
+}
+
+impl std::fmt::Display for RiskFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskFlag::Obsolete => write!(f, "obsolete"),
+            RiskFlag::LastTimeBuy => write!(f, "last-time-buy"),
+            RiskFlag::NoApprovedSource => write!(f, "no approved source"),
+            RiskFlag::SingleSource => write!(f, "single source"),
+            RiskFlag::EndOfLifeMpn => write!(f, "end-of-life MPN"),
+            RiskFlag::Nrnd => write!(f, "NRND"),
+        }
+    }
+}
+
+/// An approved alternate for a flagged part
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Alternate {
+    /// Another approved manufacturer part number on the same part
+    Mpn {
+        manufacturer: String,
+        mpn: String,
+        lifecycle: LifecycleStatus,
+    },
+    /// An interchangeable or replacement component
+    Component {
+        id: String,
+        part_number: String,
+        title: String,
+        lifecycle: LifecycleStatus,
+    },
+}
+
+/// A flagged part
+#[derive(Debug, Clone, Serialize)]
+pub struct PartRisk {
+    pub component: String,
+    pub part_number: String,
+    pub title: String,
+    pub make_buy: MakeBuy,
+    /// Quantity per top-level unit (when analyzing an assembly)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    pub lifecycle: LifecycleStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_time_buy: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_of_life: Option<NaiveDate>,
+    /// Days until the last-time-buy date (negative once passed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_to_last_time_buy: Option<i64>,
+    /// Approved sources (not counted for make parts)
+    pub approved_sources: usize,
+    /// End-of-life approved MPNs
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub end_of_life_mpns: Vec<String>,
+    pub flags: Vec<RiskFlag>,
+    pub alternates: Vec<Alternate>,
+}
+
+/// Obsolescence report over a BOM or the whole component library
+#[derive(Debug, Clone, Serialize)]
+pub struct ObsolescenceReport {
+    pub as_of: NaiveDate,
+    /// Assembly analyzed (all components when absent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assembly: Option<String>,
+    pub parts_checked: usize,
+    /// Flagged parts, most severe first
+    pub parts: Vec<PartRisk>,
+}
+
+impl ObsolescenceReport {
+    /// Number of flagged parts carrying a flag
+    pub fn count(&self, flag: RiskFlag) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| p.flags.contains(&flag))
+            .count()
+    }
+}
+
+/// Components in an assembly's BOM with their quantity per top-level unit
+pub fn bom_quantities(
+    assembly: &Assembly,
+    assemblies: &HashMap<String, &Assembly>,
+) -> BTreeMap<String, u32> {
+    fn walk(
+        asm: &Assembly,
+        multiplier: u32,
+        assemblies: &HashMap<String, &Assembly>,
+        out: &mut BTreeMap<String, u32>,
+        visited: &mut HashSet<String>,
+    ) {
+        for item in &asm.bom {
+            let qty = item.quantity * multiplier;
+            if let Some(sub) = assemblies.get(&item.component_id) {
+                if visited.insert(item.component_id.clone()) {
+                    walk(sub, qty, assemblies, out, visited);
+                    visited.remove(&item.component_id);
+                }
+            } else {
+                *out.entry(item.component_id.clone()).or_default() += qty;
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    let mut visited = HashSet::from([assembly.id.to_string()]);
+    walk(assembly, 1, assemblies, &mut out, &mut visited);
+    out
+}
+
+/// Analyze parts for lifecycle and sourcing risk
+///
+/// `parts` pairs each component ID to check with its quantity per unit.
+/// Unknown IDs are skipped.
+pub fn analyze(
+    parts: &BTreeMap<String, Option<u32>>,
+    components: &[Component],
+    assembly: Option<String>,
+    as_of: NaiveDate,
+) -> ObsolescenceReport {
+    let by_id: HashMap<String, &Component> =
+        components.iter().map(|c| (c.id.to_string(), c)).collect();
+
+    let mut checked = 0;
+    let mut flagged = Vec::new();
+    for (id, quantity) in parts {
+        let Some(cmp) = by_id.get(id) else {
+            continue;
+        };
+        checked += 1;
+        if let Some(risk) = assess(cmp, *quantity, components, as_of) {
+            flagged.push(risk);
+        }
+    }
+    flagged.sort_by(|a, b| {
+        a.flags
+            .first()
+            .cmp(&b.flags.first())
+            .then(a.part_number.cmp(&b.part_number))
+    });
+
+    ObsolescenceReport {
+        as_of,
+        assembly,
+        parts_checked: checked,
+        parts: flagged,
+    }
+}
+
+fn assess(
+    cmp: &Component,
+    quantity: Option<u32>,
+    components: &[Component],
+    as_of: NaiveDate,
+) -> Option<PartRisk> {
+    let lifecycle = cmp.lifecycle_status();
+    let mut flags = Vec::new();
+    match lifecycle {
+        LifecycleStatus::Obsolete => flags.push(RiskFlag::Obsolete),
+        LifecycleStatus::LastTimeBuy => flags.push(RiskFlag::LastTimeBuy),
+        LifecycleStatus::Nrnd => flags.push(RiskFlag::Nrnd),
+        LifecycleStatus::Active => {}
+    }
+
+    let end_of_life_mpns: Vec<String> = cmp
+        .approved_aml()
+        .filter(|e| e.lifecycle_status().is_end_of_life())
+        .map(|e| e.mpn.clone())
+        .collect();
+    if !end_of_life_mpns.is_empty() && !lifecycle.is_end_of_life() {
+        flags.push(RiskFlag::EndOfLifeMpn);
+    }
+
+    let approved_sources = if cmp.aml.is_empty() {
+        cmp.suppliers
+            .iter()
+            .filter(|s| !s.name.is_empty() || s.supplier_id.is_some())
+            .count()
+    } else {
+        cmp.approved_aml()
+            .filter(|e| !e.lifecycle_status().is_end_of_life())
+            .map(|e| e.manufacturer.to_lowercase())
+            .collect::<HashSet<_>>()
+            .len()
+    };
+    if cmp.make_buy == MakeBuy::Buy && !lifecycle.is_end_of_life() {
+        match approved_sources {
+            0 => flags.push(RiskFlag::NoApprovedSource),
+            1 => flags.push(RiskFlag::SingleSource),
+            _ => {}
+        }
+    }
+
+    if flags.is_empty() {
+        return None;
+    }
+    flags.sort();
+
+    // Approved alternates
+    let mut alternates: Vec<Alternate> = cmp
+        .approved_aml()
+        .filter(|e| !e.lifecycle_status().is_end_of_life())
+        .filter(|_| !end_of_life_mpns.is_empty())
+        .map(|e| Alternate::Mpn {
+            manufacturer: e.manufacturer.clone(),
+            mpn: e.mpn.clone(),
+            lifecycle: e.lifecycle_status(),
+        })
+        .collect();
+    let id = cmp.id.to_string();
+    let linked: HashSet<String> = cmp
+        .links
+        .interchangeable_with
+        .iter()
+        .chain(&cmp.links.replaced_by)
+        .map(|l| l.to_string())
+        .collect();
+    for other in components {
+        if other.id == cmp.id {
+            continue;
+        }
+        let links_back = other
+            .links
+            .interchangeable_with
+            .iter()
+            .chain(&other.links.replaces)
+            .any(|l| l.to_string() == id);
+        if !linked.contains(&other.id.to_string()) && !links_back {
+            continue;
+        }
+        let other_lifecycle = other.lifecycle_status();
+        if matches!(other.status, Status::Approved | Status::Released)
+            && !other_lifecycle.is_end_of_life()
+        {
+            alternates.push(Alternate::Component {
+                id: other.id.to_string(),
+                part_number: other.part_number.clone(),
+                title: other.title.clone(),
+                lifecycle: other_lifecycle,
+            });
+        }
+    }
+
+    let last_time_buy = cmp.lifecycle.as_ref().and_then(|l| l.last_time_buy);
+    Some(PartRisk {
+        component: id,
+        part_number: cmp.part_number.clone(),
+        title: cmp.title.clone(),
+        make_buy: cmp.make_buy,
+        quantity,
+        lifecycle,
+        last_time_buy,
+        end_of_life: cmp.lifecycle.as_ref().and_then(|l| l.end_of_life),
+        days_to_last_time_buy: last_time_buy.map(|d| (d - as_of).num_days()),
+        approved_sources,
+        end_of_life_mpns,
+        flags,
+        alternates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assembly::BomItem;
+    use crate::entities::component::{AmlApproval, AmlEntry, ComponentCategory, Lifecycle};
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn part(pn: &str) -> Component {
+        Component::new(
+            pn.to_string(),
+            format!("Part {}", pn),
+            MakeBuy::Buy,
+            ComponentCategory::Electrical,
+            "test".to_string(),
+        )
+    }
+
+    fn aml(manufacturer: &str, mpn: &str, status: LifecycleStatus) -> AmlEntry {
+        AmlEntry {
+            manufacturer: manufacturer.to_string(),
+            mpn: mpn.to_string(),
+            approval: AmlApproval::Approved,
+            lifecycle: Some(Lifecycle {
+                status,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_analyze_flags_and_alternates() {
+        // Two approved MPNs, one end-of-life: single source with an alternate MPN
+        let mut regulator = part("U1");
+        regulator.aml = vec![
+            aml("TI", "LM317T", LifecycleStatus::Obsolete),
+            aml("ST", "LM317-ST", LifecycleStatus::Active),
+        ];
+
+        // Part marked last-time-buy, replaced by a released successor
+        let mut old = part("R1");
+        old.lifecycle = Some(Lifecycle {
+            status: LifecycleStatus::LastTimeBuy,
+            last_time_buy: Some(date("2026-12-31")),
+            ..Default::default()
+        });
+        let mut successor = part("R2");
+        successor.status = Status::Released;
+        successor.links.replaces.push(old.id.clone());
+        successor.aml = vec![
+            aml("Vishay", "CRCW0603", LifecycleStatus::Active),
+            aml("Yageo", "RC0603", LifecycleStatus::Active),
+        ];
+
+        // Dual-sourced part with no issues
+        let mut cap = part("C1");
+        cap.aml = vec![
+            aml("Murata", "GRM188", LifecycleStatus::Active),
+            aml("TDK", "C1608", LifecycleStatus::Nrnd),
+        ];
+
+        let components = vec![regulator, old, successor, cap];
+        let parts: BTreeMap<String, Option<u32>> = components
+            .iter()
+            .map(|c| (c.id.to_string(), Some(1)))
+            .collect();
+        let report = analyze(&parts, &components, None, date("2026-10-01"));
+
+        assert_eq!(report.parts_checked, 4);
+        assert_eq!(report.parts.len(), 2);
+
+        let r1 = &report.parts[0];
+        assert_eq!(r1.part_number, "R1");
+        assert_eq!(r1.flags, vec![RiskFlag::LastTimeBuy]);
+        assert_eq!(r1.days_to_last_time_buy, Some(91));
+        assert!(matches!(
+            &r1.alternates[..],
+            [Alternate::Component { part_number, .. }] if part_number == "R2"
+        ));
+
+        let u1 = &report.parts[1];
+        assert_eq!(
+            u1.flags,
+            vec![RiskFlag::SingleSource, RiskFlag::EndOfLifeMpn]
+        );
+        assert_eq!(u1.approved_sources, 1);
+        assert_eq!(u1.end_of_life_mpns, vec!["LM317T"]);
+        assert!(matches!(
+            &u1.alternates[..],
+            [Alternate::Mpn { mpn, .. }] if mpn == "LM317-ST"
+        ));
+    }
+
+    #[test]
+    fn test_bom_quantities_multiply_through_subassemblies() {
+        let a = part("A");
+        let b = part("B");
+        let mut sub = Assembly::new("SUB".to_string(), "Sub".to_string(), "test".to_string());
+        sub.bom.push(BomItem {
+            component_id: a.id.to_string(),
+            quantity: 2,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        });
+        let mut top = Assembly::new("TOP".to_string(), "Top".to_string(), "test".to_string());
+        top.bom.push(BomItem {
+            component_id: sub.id.to_string(),
+            quantity: 3,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        });
+        top.bom.push(BomItem {
+            component_id: a.id.to_string(),
+            quantity: 1,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        });
+        top.bom.push(BomItem {
+            component_id: b.id.to_string(),
+            quantity: 4,
+            reference_designators: Vec::new(),
+            notes: None,
+            placements: Vec::new(),
+        });
+        let assemblies = HashMap::from([(sub.id.to_string(), &sub)]);
+        let qty = bom_quantities(&top, &assemblies);
+        assert_eq!(qty[&a.id.to_string()], 7);
+        assert_eq!(qty[&b.id.to_string()], 4);
+    }
+}
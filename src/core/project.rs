@@ -169,6 +169,9 @@ impl Project {
             "manufacturing/capas",
             "manufacturing/lots",
             "manufacturing/deviations",
+            "postmarket/complaints",
+            "quality/audits",
+            "manufacturing/receipts",
         ];
 
         for dir in dirs {
@@ -218,6 +221,9 @@ impl Project {
             EntityPrefix::Capa => "manufacturing/capas",
             EntityPrefix::Lot => "manufacturing/lots",
             EntityPrefix::Dev => "manufacturing/deviations",
+            EntityPrefix::Cmpl => "postmarket/complaints",
+            EntityPrefix::Aud => "quality/audits",
+            EntityPrefix::Rcv => "manufacturing/receipts",
         }
     }
 
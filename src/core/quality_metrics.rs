@@ -0,0 +1,458 @@
+//! NCR quality analytics: Pareto breakdowns, cost of poor quality,
+//! escape rate and monthly trending
+//!
+//! Cost of poor quality (COPQ) is the sum of `cost_impact.rework_cost` and
+//! `cost_impact.scrap_cost`. An escape is an NCR detected by the customer or
+//! in the field, or raised as a customer NCR.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::entities::ncr::{DetectionStage, Ncr, NcrType};
+
+/// Attribute an NCR Pareto is broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParetoDimension {
+    Category,
+    Component,
+    Supplier,
+    Process,
+    DetectionStage,
+}
+
+impl ParetoDimension {
+    pub const ALL: [ParetoDimension; 5] = [
+        ParetoDimension::Category,
+        ParetoDimension::Component,
+        ParetoDimension::Supplier,
+        ParetoDimension::Process,
+        ParetoDimension::DetectionStage,
+    ];
+
+    /// Value of this dimension on an NCR, if set
+    fn value_of(&self, ncr: &Ncr) -> Option<String> {
+        match self {
+            ParetoDimension::Category => Some(ncr.category.to_string()),
+            ParetoDimension::Component => ncr.links.component.as_ref().map(|id| id.to_string()),
+            ParetoDimension::Supplier => ncr.links.supplier.as_ref().map(|id| id.to_string()),
+            ParetoDimension::Process => ncr.links.process.as_ref().map(|id| id.to_string()),
+            ParetoDimension::DetectionStage => ncr
+                .detection
+                .as_ref()
+                .map(|d| detection_stage_name(d.found_at).to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ParetoDimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParetoDimension::Category => write!(f, "category"),
+            ParetoDimension::Component => write!(f, "component"),
+            ParetoDimension::Supplier => write!(f, "supplier"),
+            ParetoDimension::Process => write!(f, "process"),
+            ParetoDimension::DetectionStage => write!(f, "detection_stage"),
+        }
+    }
+}
+
+fn detection_stage_name(stage: DetectionStage) -> &'static str {
+    match stage {
+        DetectionStage::Incoming => "incoming",
+        DetectionStage::InProcess => "in_process",
+        DetectionStage::Final => "final",
+        DetectionStage::Customer => "customer",
+        DetectionStage::Field => "field",
+    }
+}
+
+/// What a Pareto is ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParetoMeasure {
+    Count,
+    Cost,
+}
+
+/// One bar of a Pareto chart
+#[derive(Debug, Clone, Serialize)]
+pub struct ParetoBar {
+    /// Raw value (entity ID or enum value)
+    pub key: String,
+    /// Display label (defaults to the key)
+    pub label: String,
+    pub count: usize,
+    pub cost: f64,
+    /// Share of the ranked measure
+    pub percent: f64,
+    pub cumulative_percent: f64,
+}
+
+/// Pareto breakdown along one dimension
+#[derive(Debug, Clone, Serialize)]
+pub struct Pareto {
+    pub dimension: ParetoDimension,
+    pub measure: ParetoMeasure,
+    pub bars: Vec<ParetoBar>,
+    /// NCRs with no value for this dimension
+    pub unassigned: usize,
+}
+
+/// Cost of poor quality over a trailing window
+#[derive(Debug, Clone, Serialize)]
+pub struct CopqWindow {
+    /// Window length in days (None = all NCRs in the report)
+    pub days: Option<u32>,
+    pub ncr_count: usize,
+    pub rework_cost: f64,
+    pub scrap_cost: f64,
+    pub total_cost: f64,
+}
+
+/// Escapes versus internally detected NCRs
+#[derive(Debug, Clone, Serialize)]
+pub struct EscapeRate {
+    pub total: usize,
+    pub escapes: usize,
+    pub internal: usize,
+    /// Escapes / total (0 when there are no NCRs)
+    pub rate: f64,
+}
+
+/// NCR volume and cost for one calendar month
+#[derive(Debug, Clone, Serialize)]
+pub struct MonthlyQuality {
+    /// Month as YYYY-MM
+    pub month: String,
+    pub ncr_count: usize,
+    pub escapes: usize,
+    pub rework_cost: f64,
+    pub scrap_cost: f64,
+    pub total_cost: f64,
+}
+
+/// Complete quality analytics for a set of NCRs
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityReport {
+    pub as_of: NaiveDate,
+    pub since: Option<NaiveDate>,
+    pub ncr_count: usize,
+    /// Currencies seen in cost impacts; costs are summed as-is
+    pub currencies: Vec<String>,
+    pub paretos: Vec<Pareto>,
+    pub copq: Vec<CopqWindow>,
+    pub escape_rate: EscapeRate,
+    pub monthly: Vec<MonthlyQuality>,
+}
+
+/// Options for building a quality report
+#[derive(Debug, Clone)]
+pub struct QualityOptions {
+    pub as_of: NaiveDate,
+    /// Ignore NCRs reported before this date
+    pub since: Option<NaiveDate>,
+    pub dimensions: Vec<ParetoDimension>,
+    pub measure: ParetoMeasure,
+    /// Number of calendar months in the trend, ending with `as_of`
+    pub months: u32,
+}
+
+/// COPQ windows reported in addition to the all-time total
+pub const COPQ_WINDOWS: &[u32] = &[30, 90, 365];
+
+/// Date an NCR counts from: report date, falling back to creation date
+pub fn ncr_date(ncr: &Ncr) -> NaiveDate {
+    ncr.report_date.unwrap_or_else(|| ncr.created.date_naive())
+}
+
+/// Rework and scrap cost of an NCR
+pub fn ncr_cost(ncr: &Ncr) -> (f64, f64) {
+    ncr.cost_impact
+        .as_ref()
+        .map(|c| (c.rework_cost.unwrap_or(0.0), c.scrap_cost.unwrap_or(0.0)))
+        .unwrap_or((0.0, 0.0))
+}
+
+/// Whether an NCR escaped to the customer or field
+pub fn is_escape(ncr: &Ncr) -> bool {
+    ncr.ncr_type == NcrType::Customer
+        || ncr
+            .detection
+            .as_ref()
+            .is_some_and(|d| matches!(d.found_at, DetectionStage::Customer | DetectionStage::Field))
+}
+
+/// Pareto breakdown of NCRs along one dimension
+pub fn pareto(ncrs: &[&Ncr], dimension: ParetoDimension, measure: ParetoMeasure) -> Pareto {
+    let mut groups: HashMap<String, (usize, f64)> = HashMap::new();
+    let mut unassigned = 0;
+    for ncr in ncrs {
+        match dimension.value_of(ncr) {
+            Some(key) => {
+                let (rework, scrap) = ncr_cost(ncr);
+                let entry = groups.entry(key).or_default();
+                entry.0 += 1;
+                entry.1 += rework + scrap;
+            }
+            None => unassigned += 1,
+        }
+    }
+
+    let mut bars: Vec<ParetoBar> = groups
+        .into_iter()
+        .map(|(key, (count, cost))| ParetoBar {
+            label: key.clone(),
+            key,
+            count,
+            cost,
+            percent: 0.0,
+            cumulative_percent: 0.0,
+        })
+        .collect();
+    let value = |b: &ParetoBar| match measure {
+        ParetoMeasure::Count => b.count as f64,
+        ParetoMeasure::Cost => b.cost,
+    };
+    bars.sort_by(|a, b| {
+        value(b)
+            .total_cmp(&value(a))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    let total: f64 = bars.iter().map(value).sum();
+    let mut cumulative = 0.0;
+    for bar in &mut bars {
+        let share = if total > 0.0 {
+            value(bar) / total * 100.0
+        } else {
+            0.0
+        };
+        cumulative += share;
+        bar.percent = share;
+        bar.cumulative_percent = cumulative;
+    }
+
+    Pareto {
+        dimension,
+        measure,
+        bars,
+        unassigned,
+    }
+}
+
+fn copq_window(ncrs: &[&Ncr], days: Option<u32>, as_of: NaiveDate) -> CopqWindow {
+    let start = days.map(|d| as_of - Duration::days(d as i64));
+    let mut window = CopqWindow {
+        days,
+        ncr_count: 0,
+        rework_cost: 0.0,
+        scrap_cost: 0.0,
+        total_cost: 0.0,
+    };
+    for ncr in ncrs {
+        if start.is_some_and(|s| ncr_date(ncr) <= s) {
+            continue;
+        }
+        let (rework, scrap) = ncr_cost(ncr);
+        window.ncr_count += 1;
+        window.rework_cost += rework;
+        window.scrap_cost += scrap;
+    }
+    window.total_cost = window.rework_cost + window.scrap_cost;
+    window
+}
+
+fn month_key(date: NaiveDate) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+/// Monthly NCR counts and costs for the `months` calendar months ending at `as_of`
+pub fn monthly_trend(ncrs: &[&Ncr], as_of: NaiveDate, months: u32) -> Vec<MonthlyQuality> {
+    let mut trend: Vec<MonthlyQuality> = Vec::new();
+    let (mut year, mut month) = (as_of.year(), as_of.month());
+    for _ in 0..months {
+        trend.push(MonthlyQuality {
+            month: format!("{:04}-{:02}", year, month),
+            ncr_count: 0,
+            escapes: 0,
+            rework_cost: 0.0,
+            scrap_cost: 0.0,
+            total_cost: 0.0,
+        });
+        if month == 1 {
+            year -= 1;
+            month = 12;
+        } else {
+            month -= 1;
+        }
+    }
+    trend.reverse();
+
+    for ncr in ncrs {
+        let key = month_key(ncr_date(ncr));
+        if let Some(m) = trend.iter_mut().find(|m| m.month == key) {
+            let (rework, scrap) = ncr_cost(ncr);
+            m.ncr_count += 1;
+            if is_escape(ncr) {
+                m.escapes += 1;
+            }
+            m.rework_cost += rework;
+            m.scrap_cost += scrap;
+            m.total_cost += rework + scrap;
+        }
+    }
+    trend
+}
+
+/// Build the full quality report
+pub fn build_report(ncrs: &[Ncr], options: &QualityOptions) -> QualityReport {
+    let selected: Vec<&Ncr> = ncrs
+        .iter()
+        .filter(|n| {
+            let date = ncr_date(n);
+            date <= options.as_of && options.since.is_none_or(|s| date >= s)
+        })
+        .collect();
+
+    let currencies: BTreeSet<String> = selected
+        .iter()
+        .filter_map(|n| n.cost_impact.as_ref())
+        .filter(|c| c.rework_cost.unwrap_or(0.0) != 0.0 || c.scrap_cost.unwrap_or(0.0) != 0.0)
+        .filter_map(|c| c.currency.clone())
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    let escapes = selected.iter().filter(|n| is_escape(n)).count();
+    let total = selected.len();
+
+    let mut copq: Vec<CopqWindow> = COPQ_WINDOWS
+        .iter()
+        .map(|d| copq_window(&selected, Some(*d), options.as_of))
+        .collect();
+    copq.push(copq_window(&selected, None, options.as_of));
+
+    QualityReport {
+        as_of: options.as_of,
+        since: options.since,
+        ncr_count: total,
+        currencies: currencies.into_iter().collect(),
+        paretos: options
+            .dimensions
+            .iter()
+            .map(|d| pareto(&selected, *d, options.measure))
+            .collect(),
+        copq,
+        escape_rate: EscapeRate {
+            total,
+            escapes,
+            internal: total - escapes,
+            rate: if total > 0 {
+                escapes as f64 / total as f64
+            } else {
+                0.0
+            },
+        },
+        monthly: monthly_trend(&selected, options.as_of, options.months),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+    use crate::entities::ncr::{CostImpact, Detection, NcrCategory};
+
+    fn ncr(category: NcrCategory, date: &str, rework: f64, scrap: f64) -> Ncr {
+        let mut n: Ncr = serde_yml::from_str(&format!(
+            "id: {}\ntitle: Test\ncreated: 2024-01-01T00:00:00Z\nauthor: test\n",
+            EntityId::new(EntityPrefix::Ncr)
+        ))
+        .unwrap();
+        n.category = category;
+        n.report_date = Some(date.parse().unwrap());
+        n.cost_impact = Some(CostImpact {
+            rework_cost: Some(rework),
+            scrap_cost: Some(scrap),
+            currency: Some("USD".to_string()),
+        });
+        n
+    }
+
+    fn options(as_of: &str) -> QualityOptions {
+        QualityOptions {
+            as_of: as_of.parse().unwrap(),
+            since: None,
+            dimensions: ParetoDimension::ALL.to_vec(),
+            measure: ParetoMeasure::Count,
+            months: 3,
+        }
+    }
+
+    #[test]
+    fn test_pareto_by_count_and_cost() {
+        let ncrs = [
+            ncr(NcrCategory::Dimensional, "2024-05-01", 10.0, 0.0),
+            ncr(NcrCategory::Dimensional, "2024-05-02", 10.0, 0.0),
+            ncr(NcrCategory::Dimensional, "2024-05-03", 0.0, 0.0),
+            ncr(NcrCategory::Material, "2024-05-04", 0.0, 500.0),
+        ];
+        let refs: Vec<&Ncr> = ncrs.iter().collect();
+
+        let by_count = pareto(&refs, ParetoDimension::Category, ParetoMeasure::Count);
+        assert_eq!(by_count.bars[0].key, "dimensional");
+        assert_eq!(by_count.bars[0].count, 3);
+        assert_eq!(by_count.bars[0].percent, 75.0);
+        assert_eq!(by_count.bars[1].cumulative_percent, 100.0);
+
+        let by_cost = pareto(&refs, ParetoDimension::Category, ParetoMeasure::Cost);
+        assert_eq!(by_cost.bars[0].key, "material");
+        assert_eq!(by_cost.bars[0].cost, 500.0);
+
+        // No NCRs are linked to a component
+        let by_cmp = pareto(&refs, ParetoDimension::Component, ParetoMeasure::Count);
+        assert!(by_cmp.bars.is_empty());
+        assert_eq!(by_cmp.unassigned, 4);
+    }
+
+    #[test]
+    fn test_copq_windows_and_escapes() {
+        let mut ncrs = vec![
+            ncr(NcrCategory::Functional, "2024-06-20", 100.0, 0.0),
+            ncr(NcrCategory::Functional, "2024-04-15", 0.0, 50.0),
+            ncr(NcrCategory::Functional, "2023-01-10", 25.0, 25.0),
+        ];
+        ncrs[0].detection = Some(Detection {
+            found_at: DetectionStage::Field,
+            ..Default::default()
+        });
+
+        let report = build_report(&ncrs, &options("2024-06-30"));
+        let window = |d: Option<u32>| report.copq.iter().find(|w| w.days == d).unwrap();
+        assert_eq!(window(Some(30)).total_cost, 100.0);
+        assert_eq!(window(Some(90)).total_cost, 150.0);
+        assert_eq!(window(Some(90)).scrap_cost, 50.0);
+        assert_eq!(window(None).total_cost, 200.0);
+        assert_eq!(window(None).ncr_count, 3);
+
+        assert_eq!(report.escape_rate.escapes, 1);
+        assert_eq!(report.escape_rate.internal, 2);
+        assert_eq!(report.currencies, vec!["USD".to_string()]);
+    }
+
+    #[test]
+    fn test_monthly_trend_spans_year_boundary() {
+        let ncrs = [
+            ncr(NcrCategory::Cosmetic, "2023-12-05", 10.0, 0.0),
+            ncr(NcrCategory::Cosmetic, "2024-02-10", 0.0, 5.0),
+            ncr(NcrCategory::Cosmetic, "2024-02-11", 0.0, 5.0),
+        ];
+        let report = build_report(&ncrs, &options("2024-02-28"));
+        let months: Vec<&str> = report.monthly.iter().map(|m| m.month.as_str()).collect();
+        assert_eq!(months, vec!["2023-12", "2024-01", "2024-02"]);
+        assert_eq!(report.monthly[0].ncr_count, 1);
+        assert_eq!(report.monthly[1].ncr_count, 0);
+        assert_eq!(report.monthly[2].total_cost, 10.0);
+    }
+}
@@ -0,0 +1,379 @@
+//! Receiving inspection: inspection plans and receipt consumption
+//!
+//! A receipt is inspected against the controls on its component's features,
+//! with the largest sample size any of those controls calls for. Controls with
+//! an AQL get a sampling plan for the quantity received, at the severity the
+//! switching rules give for earlier receipts from the same supplier. Lots
+//! consume receipts through `MaterialUsed.receipt`; the quantity consumed
+//! across all lots may not exceed the quantity received.
+
+use crate::core::sampling::{switching_state, LotInspection, PlanSpec};
+use crate::entities::control::{Characteristic, Control};
+use crate::entities::feature::Feature;
+use crate::entities::lot::Lot;
+use crate::entities::receipt::{CheckResult, Receipt, ReceiptCheck, ReceiptStatus};
+
+/// Checks and sample size for receiving a component
+#[derive(Debug, Clone, Default)]
+pub struct InspectionPlan {
+    pub checks: Vec<ReceiptCheck>,
+    pub sample_size: Option<u32>,
+    /// Switching rule changes and sampling problems worth reporting
+    pub notes: Vec<String>,
+}
+
+/// Build the inspection plan for a component from the controls on its features
+///
+/// `history` holds earlier receipts; those from `supplier` drive the switching
+/// rules. Sample sizes are capped at the quantity received.
+pub fn inspection_plan(
+    component: &str,
+    features: &[Feature],
+    controls: &[Control],
+    quantity: Option<u32>,
+    history: &[Receipt],
+    supplier: Option<&str>,
+) -> InspectionPlan {
+    let feature_ids: Vec<String> = features
+        .iter()
+        .filter(|f| f.component == component)
+        .map(|f| f.id.to_string())
+        .collect();
+
+    let mut plan = InspectionPlan::default();
+    for ctrl in controls {
+        let on_component = ctrl
+            .links
+            .feature
+            .as_ref()
+            .is_some_and(|f| feature_ids.contains(&f.to_string()));
+        if !on_component {
+            continue;
+        }
+        let name = if ctrl.characteristic.name.is_empty() {
+            ctrl.title.clone()
+        } else {
+            ctrl.characteristic.name.clone()
+        };
+        let mut check = ReceiptCheck {
+            control: Some(ctrl.id.clone()),
+            characteristic: name,
+            specification: specification(&ctrl.characteristic),
+            ..Default::default()
+        };
+
+        let spec = match ctrl.sampling.as_ref().map(PlanSpec::from_sampling) {
+            Some(Ok(spec)) => spec,
+            Some(Err(e)) => {
+                plan.notes.push(format!("{}: {}", check.characteristic, e));
+                None
+            }
+            None => None,
+        };
+        match (spec, quantity) {
+            (Some(spec), Some(qty)) => {
+                let earlier = receipt_history(history, &ctrl.id.to_string(), supplier);
+                let state = switching_state(&spec, &earlier);
+                let sampling = spec.plan(qty, state.severity);
+                if state.discontinued {
+                    plan.notes.push(format!(
+                        "{}: acceptance inspection discontinued ({})",
+                        check.characteristic,
+                        state.reason.as_deref().unwrap_or_default()
+                    ));
+                } else if let Some(reason) =
+                    state.reason.as_deref().filter(|_| spec.severity.is_none())
+                {
+                    plan.notes.push(format!(
+                        "{}: {} inspection ({})",
+                        check.characteristic, state.severity, reason
+                    ));
+                }
+                check.sample_size = Some(sampling.sample_size);
+                check.accept = sampling.accept;
+                check.reject = sampling.reject;
+                check.k = sampling.k;
+                check.severity = Some(state.severity);
+            }
+            _ => {
+                check.sample_size = ctrl
+                    .sampling
+                    .as_ref()
+                    .and_then(|s| s.sample_size)
+                    .map(|n| quantity.map_or(n, |q| n.min(q)));
+            }
+        }
+        if let Some(n) = check.sample_size {
+            plan.sample_size = Some(plan.sample_size.map_or(n, |m| m.max(n)));
+        }
+        plan.checks.push(check);
+    }
+    plan
+}
+
+/// Dispositioned inspections of a control on earlier receipts, oldest first
+///
+/// Only receipts from `supplier` count when it is given.
+pub fn receipt_history(
+    receipts: &[Receipt],
+    control: &str,
+    supplier: Option<&str>,
+) -> Vec<LotInspection> {
+    let mut earlier: Vec<&Receipt> = receipts
+        .iter()
+        .filter(|r| r.receipt_status != ReceiptStatus::Pending)
+        .filter(|r| {
+            supplier.is_none_or(|s| {
+                r.links
+                    .supplier
+                    .as_ref()
+                    .is_some_and(|x| x.to_string() == s)
+            })
+        })
+        .collect();
+    earlier.sort_by_key(|r| (r.received_date, r.created));
+    earlier
+        .into_iter()
+        .filter_map(|r| {
+            let check = r.checks.iter().find(|c| {
+                c.control
+                    .as_ref()
+                    .is_some_and(|id| id.to_string() == control)
+            })?;
+            if check.result == CheckResult::Pending {
+                return None;
+            }
+            Some(LotInspection {
+                lot_size: r.quantity?,
+                defects: check.defects,
+                accepted: Some(check.result == CheckResult::Pass),
+            })
+        })
+        .collect()
+}
+
+/// Sampling plan of a check, e.g. "n=80 Ac 2 Re 3, tightened"
+pub fn plan_label(check: &ReceiptCheck) -> Option<String> {
+    let mut text = format!("n={}", check.sample_size?);
+    if let (Some(ac), Some(re)) = (check.accept, check.reject) {
+        text.push_str(&format!(" Ac {} Re {}", ac, re));
+    }
+    if let Some(k) = check.k {
+        text.push_str(&format!(" k={}", k));
+    }
+    if let Some(severity) = check.severity {
+        text.push_str(&format!(", {}", severity));
+    }
+    Some(text)
+}
+
+/// Judge a defect count against a check's accept number
+///
+/// Without a sampling plan, any defect fails the check.
+pub fn judge_defects(check: &ReceiptCheck, defects: u32) -> CheckResult {
+    let passes = match check.accept {
+        Some(ac) => defects <= ac,
+        None => defects == 0,
+    };
+    if passes {
+        CheckResult::Pass
+    } else {
+        CheckResult::Fail
+    }
+}
+
+/// Specification text for a characteristic (e.g., "10 [9.95, 10.05] mm")
+pub fn specification(ch: &Characteristic) -> Option<String> {
+    let limits = match (ch.lower_limit, ch.upper_limit) {
+        (Some(lo), Some(hi)) => Some(format!("[{}, {}]", lo, hi)),
+        (Some(lo), None) => Some(format!(">= {}", lo)),
+        (None, Some(hi)) => Some(format!("<= {}", hi)),
+        (None, None) => None,
+    };
+    let mut parts: Vec<String> = ch.nominal.map(|n| n.to_string()).into_iter().collect();
+    parts.extend(limits);
+    if parts.is_empty() {
+        return None;
+    }
+    parts.extend(ch.units.clone());
+    Some(parts.join(" "))
+}
+
+/// Pass/fail for a measured value against a characteristic's limits
+///
+/// Returns `None` when the characteristic has no limits.
+pub fn evaluate(ch: &Characteristic, measured: f64) -> Option<CheckResult> {
+    if ch.lower_limit.is_none() && ch.upper_limit.is_none() {
+        return None;
+    }
+    let within = ch.lower_limit.is_none_or(|lo| measured >= lo)
+        && ch.upper_limit.is_none_or(|hi| measured <= hi);
+    Some(if within {
+        CheckResult::Pass
+    } else {
+        CheckResult::Fail
+    })
+}
+
+/// Quantity of a receipt already consumed by lots (including per-serial material)
+pub fn consumed(lots: &[Lot], receipt_id: &str) -> u32 {
+    lots.iter()
+        .flat_map(|lot| {
+            lot.materials_used
+                .iter()
+                .chain(lot.serials.iter().flat_map(|u| u.materials.iter()))
+        })
+        .filter(|m| m.receipt.as_deref() == Some(receipt_id))
+        .map(|m| m.quantity.unwrap_or(1))
+        .sum()
+}
+
+/// Receipts of a component's supplier lot
+pub fn receipts_for_supplier_lot<'a>(
+    receipts: &'a [Receipt],
+    component: &str,
+    supplier_lot: &str,
+) -> Vec<&'a Receipt> {
+    receipts
+        .iter()
+        .filter(|r| {
+            r.links
+                .component
+                .as_ref()
+                .is_some_and(|c| c.to_string() == component)
+                && r.supplier_lot.as_deref() == Some(supplier_lot)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+    use crate::entities::control::{ControlType, InspectionSeverity, Sampling};
+    use crate::entities::feature::FeatureType;
+    use crate::entities::lot::MaterialUsed;
+
+    fn bore() -> Characteristic {
+        Characteristic {
+            name: "Bore".to_string(),
+            nominal: Some(10.0),
+            lower_limit: Some(9.95),
+            upper_limit: Some(10.05),
+            units: Some("mm".to_string()),
+            critical: false,
+        }
+    }
+
+    #[test]
+    fn test_inspection_plan_from_feature_controls() {
+        let cmp = EntityId::new(EntityPrefix::Cmp).to_string();
+        let feat = Feature::new(&cmp, FeatureType::Internal, "Bore", "test");
+
+        let mut ctrl = Control::new(
+            "Bore check".to_string(),
+            ControlType::Inspection,
+            "test".to_string(),
+        );
+        ctrl.characteristic = bore();
+        ctrl.links.feature = Some(feat.id.clone());
+        ctrl.sampling = Some(Sampling {
+            sample_size: Some(32),
+            ..Default::default()
+        });
+        let unrelated = Control::new("Other".to_string(), ControlType::Visual, "test".to_string());
+
+        let plan = inspection_plan(&cmp, &[feat], &[ctrl, unrelated], Some(20), &[], None);
+        assert_eq!(plan.checks.len(), 1);
+        assert_eq!(plan.checks[0].characteristic, "Bore");
+        assert_eq!(
+            plan.checks[0].specification.as_deref(),
+            Some("10 [9.95, 10.05] mm")
+        );
+        assert_eq!(plan.sample_size, Some(20));
+    }
+
+    #[test]
+    fn test_inspection_plan_tightened_after_rejections() {
+        let cmp = EntityId::new(EntityPrefix::Cmp).to_string();
+        let feat = Feature::new(&cmp, FeatureType::Internal, "Bore", "test");
+        let sup = EntityId::new(EntityPrefix::Sup);
+
+        let mut ctrl = Control::new(
+            "Bore check".to_string(),
+            ControlType::Inspection,
+            "test".to_string(),
+        );
+        ctrl.characteristic = bore();
+        ctrl.links.feature = Some(feat.id.clone());
+        ctrl.sampling = Some(Sampling {
+            aql: Some(1.0),
+            ..Default::default()
+        });
+        let features = [feat];
+
+        let normal = inspection_plan(&cmp, &features, &[ctrl.clone()], Some(1000), &[], None);
+        assert_eq!(normal.checks[0].sample_size, Some(80));
+        assert_eq!(normal.checks[0].accept, Some(2));
+        assert!(normal.notes.is_empty());
+
+        let rejected: Vec<Receipt> = (0..2)
+            .map(|_| {
+                let mut rcv = Receipt::new("Bores".to_string(), "test".to_string());
+                rcv.quantity = Some(1000);
+                rcv.receipt_status = ReceiptStatus::Rejected;
+                rcv.links.supplier = Some(sup.clone());
+                rcv.checks.push(ReceiptCheck {
+                    control: Some(ctrl.id.clone()),
+                    result: CheckResult::Fail,
+                    defects: Some(3),
+                    ..Default::default()
+                });
+                rcv
+            })
+            .collect();
+
+        let supplier = sup.to_string();
+        let plan = inspection_plan(
+            &cmp,
+            &features,
+            &[ctrl.clone()],
+            Some(1000),
+            &rejected,
+            Some(&supplier),
+        );
+        let check = &plan.checks[0];
+        assert_eq!(check.severity, Some(InspectionSeverity::Tightened));
+        assert_eq!((check.accept, check.reject), (Some(1), Some(2)));
+        assert_eq!(plan.notes.len(), 1);
+        assert_eq!(judge_defects(check, 2), CheckResult::Fail);
+
+        // Rejections from another supplier don't count
+        let other = inspection_plan(
+            &cmp,
+            &features,
+            &[ctrl],
+            Some(1000),
+            &rejected,
+            Some("SUP-X"),
+        );
+        assert_eq!(other.checks[0].severity, Some(InspectionSeverity::Normal));
+    }
+
+    #[test]
+    fn test_evaluate_and_consumed() {
+        assert_eq!(evaluate(&bore(), 10.01), Some(CheckResult::Pass));
+        assert_eq!(evaluate(&bore(), 10.06), Some(CheckResult::Fail));
+        assert_eq!(evaluate(&Characteristic::default(), 1.0), None);
+
+        let mut lot = Lot::new("Build".to_string(), "test".to_string());
+        for qty in [100, 50] {
+            lot.materials_used.push(MaterialUsed {
+                receipt: Some("RCV-1".to_string()),
+                quantity: Some(qty),
+                ..Default::default()
+            });
+        }
+        assert_eq!(consumed(&[lot], "RCV-1"), 150);
+    }
+}
@@ -0,0 +1,457 @@
+//! Request-for-quote packages and supplier responses
+//!
+//! An RFQ package is a folder per supplier holding a manifest of the
+//! requested items, a spreadsheet with one line per item and requested
+//! quantity, and copies of each item's documents. The supplier fills in the
+//! price and terms columns and sends the spreadsheet back; each line with a
+//! price becomes a price break on a quote for that item. Item-level terms
+//! (MOQ, currency, tooling, validity, quote reference) are taken from the
+//! first line of the item that gives them; NRE lines are collected from all
+//! of the item's lines. Suppliers may add lines for other quantities.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::quote::{Currency, NreCost, PriceBreak, Quote, QuoteStatus};
+
+/// Manifest file in an RFQ package
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Spreadsheet file in an RFQ package
+pub const SPREADSHEET_FILE: &str = "rfq.csv";
+
+/// Spreadsheet columns, the part the supplier fills in starting at `unit_price`
+pub const COLUMNS: &[&str] = &[
+    "line",
+    "component",
+    "part_number",
+    "revision",
+    "title",
+    "quantity",
+    "unit_price",
+    "lead_time_days",
+    "moq",
+    "currency",
+    "tooling_cost",
+    "nre_description",
+    "nre_cost",
+    "valid_until",
+    "quote_ref",
+    "notes",
+];
+
+/// A document sent with an item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RfqDocument {
+    #[serde(rename = "type")]
+    pub doc_type: String,
+    /// Path in the project
+    pub source: String,
+    /// Path in the package; none when the file was not found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+}
+
+/// An item requested for quotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RfqItem {
+    pub component: String,
+    pub part_number: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    pub title: String,
+    /// Quantities to quote
+    pub quantities: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<RfqDocument>,
+}
+
+/// Contents of an RFQ package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RfqManifest {
+    /// Package reference (also the folder name)
+    pub rfq: String,
+    /// Supplier ID (SUP-...)
+    pub supplier: String,
+    pub supplier_name: String,
+    pub issued: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respond_by: Option<NaiveDate>,
+    /// Currency prices are requested in
+    pub currency: Currency,
+    pub items: Vec<RfqItem>,
+}
+
+impl RfqManifest {
+    /// The spreadsheet the supplier fills in
+    pub fn spreadsheet(&self) -> String {
+        let mut output = COLUMNS.join(",") + "\n";
+        let mut line = 0;
+        for item in &self.items {
+            for qty in &item.quantities {
+                line += 1;
+                let mut row = vec![
+                    line.to_string(),
+                    item.component.clone(),
+                    csv_field(&item.part_number),
+                    csv_field(item.revision.as_deref().unwrap_or_default()),
+                    csv_field(&item.title),
+                    qty.to_string(),
+                ];
+                // Price and terms are left for the supplier, currency prefilled
+                row.extend(COLUMNS[6..].iter().map(|c| match *c {
+                    "currency" => self.currency.to_string(),
+                    _ => String::new(),
+                }));
+                output.push_str(&row.join(","));
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    fn item(&self, component: &str, part_number: &str) -> Option<&RfqItem> {
+        self.items
+            .iter()
+            .find(|i| !component.is_empty() && i.component == component)
+            .or_else(|| {
+                self.items
+                    .iter()
+                    .find(|i| !part_number.is_empty() && i.part_number == part_number)
+            })
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// A supplier's quotation for one item
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemResponse {
+    pub component: String,
+    pub part_number: String,
+    pub price_breaks: Vec<PriceBreak>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moq: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tooling_cost: Option<f64>,
+    pub nre_costs: Vec<NreCost>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl ItemResponse {
+    /// Quote entity for this response
+    pub fn to_quote(&self, manifest: &RfqManifest, author: &str) -> Quote {
+        let mut quote = Quote::new_for_component(
+            &manifest.supplier,
+            &self.component,
+            format!("{} - {}", manifest.rfq, self.part_number),
+            author,
+        );
+        quote.quote_ref = Some(self.quote_ref.clone().unwrap_or(manifest.rfq.clone()));
+        quote.description = self.notes.clone();
+        quote.currency = self.currency.unwrap_or(manifest.currency);
+        quote.price_breaks = self.price_breaks.clone();
+        quote.moq = self.moq;
+        quote.tooling_cost = self.tooling_cost;
+        quote.nre_costs = self.nre_costs.clone();
+        quote.lead_time_days = self.price_breaks.first().and_then(|pb| pb.lead_time_days);
+        quote.quote_date = Some(Utc::now().date_naive());
+        quote.valid_until = self.valid_until;
+        quote.quote_status = QuoteStatus::Received;
+        quote
+    }
+}
+
+/// Items the supplier quoted, and the ones they did not
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RfqResponse {
+    pub quoted: Vec<ItemResponse>,
+    /// Requested items (part numbers) with no price on any line
+    pub no_bid: Vec<String>,
+}
+
+/// Read a filled-in RFQ spreadsheet
+///
+/// Lines are matched to manifest items by component ID, then part number.
+/// Lines for items not in the manifest are an error, as are unreadable
+/// numbers, dates and currencies.
+pub fn parse_response<R: Read>(reader: R, manifest: &RfqManifest) -> Result<RfqResponse, String> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers: Vec<String> = rdr
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    for required in ["quantity", "unit_price"] {
+        if column(required).is_none() {
+            return Err(format!("Spreadsheet has no '{}' column", required));
+        }
+    }
+    if column("component").is_none() && column("part_number").is_none() {
+        return Err("Spreadsheet needs a component or part_number column".to_string());
+    }
+
+    let mut responses: BTreeMap<String, ItemResponse> = BTreeMap::new();
+    for (idx, record) in rdr.records().enumerate() {
+        let row = idx + 2;
+        let record = record.map_err(|e| format!("Row {}: {}", row, e))?;
+        let field = |name: &str| {
+            column(name)
+                .and_then(|c| record.get(c))
+                .filter(|s| !s.is_empty())
+        };
+        let number = |name: &str| -> Result<Option<f64>, String> {
+            field(name)
+                .map(|s| {
+                    s.trim_start_matches(['$', '€', '£', '¥'])
+                        .replace(',', "")
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|n| *n >= 0.0)
+                        .ok_or(format!("Row {}: invalid {} '{}'", row, name, s))
+                })
+                .transpose()
+        };
+        let whole = |name: &str| -> Result<Option<u32>, String> {
+            number(name).map(|n| n.map(|n| n.round() as u32))
+        };
+
+        let component = field("component").unwrap_or_default();
+        let part_number = field("part_number").unwrap_or_default();
+        if component.is_empty() && part_number.is_empty() {
+            continue;
+        }
+        let item = manifest.item(component, part_number).ok_or(format!(
+            "Row {}: {} was not part of {}",
+            row,
+            if component.is_empty() {
+                part_number
+            } else {
+                component
+            },
+            manifest.rfq
+        ))?;
+
+        let response = responses
+            .entry(item.component.clone())
+            .or_insert_with(|| ItemResponse {
+                component: item.component.clone(),
+                part_number: item.part_number.clone(),
+                price_breaks: Vec::new(),
+                moq: None,
+                currency: None,
+                tooling_cost: None,
+                nre_costs: Vec::new(),
+                valid_until: None,
+                quote_ref: None,
+                notes: None,
+            });
+
+        if let (Some(qty), Some(price)) = (whole("quantity")?, number("unit_price")?) {
+            let price_break = PriceBreak {
+                min_qty: qty,
+                unit_price: price,
+                lead_time_days: whole("lead_time_days")?,
+            };
+            match response.price_breaks.iter().find(|pb| pb.min_qty == qty) {
+                Some(existing)
+                    if existing.unit_price == price_break.unit_price
+                        && existing.lead_time_days == price_break.lead_time_days => {}
+                Some(existing) => {
+                    return Err(format!(
+                        "Row {}: {} at quantity {} conflicts with an earlier row (unit price {})",
+                        row, item.part_number, qty, existing.unit_price
+                    ))
+                }
+                None => response.price_breaks.push(price_break),
+            }
+        }
+        if response.moq.is_none() {
+            response.moq = whole("moq")?;
+        }
+        if response.currency.is_none() {
+            response.currency = field("currency")
+                .map(|s| {
+                    s.parse::<Currency>()
+                        .map_err(|e| format!("Row {}: {}", row, e))
+                })
+                .transpose()?;
+        }
+        if response.tooling_cost.is_none() {
+            response.tooling_cost = number("tooling_cost")?;
+        }
+        if let Some(cost) = number("nre_cost")? {
+            let description = field("nre_description").unwrap_or("NRE").to_string();
+            if !response
+                .nre_costs
+                .iter()
+                .any(|n| n.description == description && n.cost == cost)
+            {
+                response.nre_costs.push(NreCost {
+                    description,
+                    cost,
+                    one_time: true,
+                });
+            }
+        }
+        if response.valid_until.is_none() {
+            response.valid_until = field("valid_until")
+                .map(|s| {
+                    s.parse::<NaiveDate>()
+                        .map_err(|_| format!("Row {}: invalid valid_until '{}'", row, s))
+                })
+                .transpose()?;
+        }
+        if response.quote_ref.is_none() {
+            response.quote_ref = field("quote_ref").map(String::from);
+        }
+        if let Some(note) = field("notes") {
+            match response.notes {
+                Some(ref mut notes) => {
+                    notes.push('\n');
+                    notes.push_str(note);
+                }
+                None => response.notes = Some(note.to_string()),
+            }
+        }
+    }
+
+    let mut result = RfqResponse::default();
+    for mut response in responses.into_values() {
+        if response.price_breaks.is_empty() {
+            result.no_bid.push(response.part_number);
+            continue;
+        }
+        response.price_breaks.sort_by_key(|pb| pb.min_qty);
+        result.quoted.push(response);
+    }
+    for item in &manifest.items {
+        let answered = result.quoted.iter().any(|r| r.component == item.component)
+            || result.no_bid.contains(&item.part_number);
+        if !answered {
+            result.no_bid.push(item.part_number.clone());
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> RfqManifest {
+        RfqManifest {
+            rfq: "RFQ-2026-06-01-ACME".to_string(),
+            supplier: "SUP-ACME".to_string(),
+            supplier_name: "Acme".to_string(),
+            issued: "2026-06-01".parse().unwrap(),
+            respond_by: None,
+            currency: Currency::Usd,
+            items: vec![
+                RfqItem {
+                    component: "CMP-BRACKET".to_string(),
+                    part_number: "BRK-1".to_string(),
+                    revision: Some("B".to_string()),
+                    title: "Bracket, steel".to_string(),
+                    quantities: vec![100, 500],
+                    documents: Vec::new(),
+                },
+                RfqItem {
+                    component: "CMP-SHAFT".to_string(),
+                    part_number: "SHF-1".to_string(),
+                    revision: None,
+                    title: "Shaft".to_string(),
+                    quantities: vec![100],
+                    documents: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_spreadsheet_lines_per_quantity() {
+        let sheet = manifest().spreadsheet();
+        let lines: Vec<&str> = sheet.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "1,CMP-BRACKET,BRK-1,B,\"Bracket, steel\",100,,,,USD,,,,,,"
+        );
+        assert_eq!(lines[3], "3,CMP-SHAFT,SHF-1,,Shaft,100,,,,USD,,,,,,");
+    }
+
+    #[test]
+    fn test_parse_filled_spreadsheet() {
+        let manifest = manifest();
+        let filled = "\
+line,component,part_number,revision,title,quantity,unit_price,lead_time_days,moq,currency,tooling_cost,nre_description,nre_cost,valid_until,quote_ref,notes
+1,CMP-BRACKET,BRK-1,B,Bracket,100,2.50,21,50,EUR,\"1,200\",First article,300,2026-09-30,Q-881,
+2,CMP-BRACKET,BRK-1,B,Bracket,500,1.95,14,,EUR,,First article,300,,,Zinc plated
+3,CMP-SHAFT,SHF-1,,Shaft,100,,,,USD,,,,,,Cannot make
+,,BRK-1,,,1000,$1.80,14,,,,,,,,
+";
+        let response = parse_response(filled.as_bytes(), &manifest).unwrap();
+        assert_eq!(response.no_bid, vec!["SHF-1".to_string()]);
+        assert_eq!(response.quoted.len(), 1);
+
+        let bracket = &response.quoted[0];
+        let breaks: Vec<(u32, f64)> = bracket
+            .price_breaks
+            .iter()
+            .map(|pb| (pb.min_qty, pb.unit_price))
+            .collect();
+        assert_eq!(breaks, vec![(100, 2.5), (500, 1.95), (1000, 1.8)]);
+        assert_eq!(bracket.moq, Some(50));
+        assert_eq!(bracket.currency, Some(Currency::Eur));
+        assert_eq!(bracket.nre_costs.len(), 1);
+
+        let quote = bracket.to_quote(&manifest, "buyer");
+        assert_eq!(quote.component.as_deref(), Some("CMP-BRACKET"));
+        assert_eq!(quote.supplier, "SUP-ACME");
+        assert_eq!(quote.quote_ref.as_deref(), Some("Q-881"));
+        assert_eq!(quote.total_nre(), 1500.0);
+        assert_eq!(quote.lead_time_days, Some(21));
+        assert_eq!(quote.valid_until, Some("2026-09-30".parse().unwrap()));
+
+        let stray = "component,quantity,unit_price\nCMP-OTHER,1,1.0\n";
+        assert!(parse_response(stray.as_bytes(), &manifest).is_err());
+
+        // A repeated row is fine; two prices for one quantity are not
+        let repeated = "part_number,quantity,unit_price\nBRK-1,100,2.00\nBRK-1,100,2.00\n";
+        assert_eq!(
+            parse_response(repeated.as_bytes(), &manifest)
+                .unwrap()
+                .quoted[0]
+                .price_breaks
+                .len(),
+            1
+        );
+        let conflicting = "part_number,quantity,unit_price\nBRK-1,100,2.00\nBRK-1,100,1.80\n";
+        let err = parse_response(conflicting.as_bytes(), &manifest).unwrap_err();
+        assert!(err.starts_with("Row 3:"), "{}", err);
+    }
+}
@@ -0,0 +1,939 @@
+//! Acceptance sampling plans (ANSI Z1.4 / ISO 2859-1)
+//!
+//! Single sampling plans by attributes follow the standard's master tables:
+//! the lot size and inspection level give a sample size code letter (Table I),
+//! and the code letter and AQL give the sample size and accept/reject numbers
+//! (Tables II-A, II-B and II-C), following the table arrows to the nearest
+//! plan. The switching rules move a control between normal, tightened and
+//! reduced inspection based on the outcome of earlier lots.
+//!
+//! Two schemes are derived from the single sampling plan:
+//! - zero acceptance (c=0): the smallest sample with no defects allowed that
+//!   gives at least the single plan's consumer protection (the quality
+//!   accepted 10% of the time)
+//! - variables: the k-method with unknown standard deviation, designed to
+//!   match the single plan's OC curve at the AQL and at 10% acceptance
+
+use serde::Serialize;
+
+use crate::entities::control::{InspectionLevel, InspectionSeverity, Sampling, SamplingScheme};
+
+/// Preferred AQL values
+pub const AQL_VALUES: [f64; 26] = [
+    0.010, 0.015, 0.025, 0.040, 0.065, 0.10, 0.15, 0.25, 0.40, 0.65, 1.0, 1.5, 2.5, 4.0, 6.5, 10.0,
+    15.0, 25.0, 40.0, 65.0, 100.0, 150.0, 250.0, 400.0, 650.0, 1000.0,
+];
+
+/// Sample size code letters (S is used by tightened inspection only)
+const CODE_LETTERS: [char; 17] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'Q', 'R', 'S',
+];
+
+/// Sample size for each code letter
+const SAMPLE_SIZES: [u32; 17] = [
+    2, 3, 5, 8, 13, 20, 32, 50, 80, 125, 200, 315, 500, 800, 1250, 2000, 3150,
+];
+
+/// Upper lot size of each row of Table I
+const LOT_SIZE_BOUNDS: [u32; 15] = [
+    8,
+    15,
+    25,
+    50,
+    90,
+    150,
+    280,
+    500,
+    1200,
+    3200,
+    10000,
+    35000,
+    150000,
+    500000,
+    u32::MAX,
+];
+
+/// Table I code letters for levels S-1, S-2, S-3, S-4, I, II and III
+const TABLE_I: [&str; 15] = [
+    "AAAAAAB", "AAAAABC", "AABBBCD", "ABBCCDE", "BBCCCEF", "BBCDDFG", "BCDEEGH", "BCDEFHJ",
+    "CCEFGJK", "CDEGHKL", "CDFGJLM", "CDFHKMN", "DEGJLNP", "DEGJMPQ", "DEHKNQR",
+];
+
+/// Accept/reject numbers along a diagonal of the normal table, after (0, 1)
+const NORMAL_PLANS: [(u32, u32); 10] = [
+    (1, 2),
+    (2, 3),
+    (3, 4),
+    (5, 6),
+    (7, 8),
+    (10, 11),
+    (14, 15),
+    (21, 22),
+    (30, 31),
+    (44, 45),
+];
+
+/// Accept/reject numbers along a diagonal of the tightened table, after (0, 1)
+const TIGHTENED_PLANS: [(u32, u32); 9] = [
+    (1, 2),
+    (2, 3),
+    (3, 4),
+    (5, 6),
+    (8, 9),
+    (12, 13),
+    (18, 19),
+    (27, 28),
+    (41, 42),
+];
+
+/// Switching score at which normal inspection moves to reduced
+const REDUCED_SWITCHING_SCORE: u32 = 30;
+
+/// A cell of a master table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Plan(u32, u32),
+    /// Use the first plan above the arrow
+    Up,
+    /// Use the first plan below the arrow
+    Down,
+}
+
+/// Index of a preferred AQL value
+pub fn aql_index(aql: f64) -> Option<usize> {
+    AQL_VALUES.iter().position(|v| (v - aql).abs() <= v * 1e-6)
+}
+
+/// Sample size code letter index (Table I) for a lot size and inspection level
+fn code_letter_index(lot_size: u32, level: InspectionLevel) -> usize {
+    let row = LOT_SIZE_BOUNDS
+        .iter()
+        .position(|&bound| lot_size <= bound)
+        .unwrap_or(LOT_SIZE_BOUNDS.len() - 1);
+    let column = match level {
+        InspectionLevel::S1 => 0,
+        InspectionLevel::S2 => 1,
+        InspectionLevel::S3 => 2,
+        InspectionLevel::S4 => 3,
+        InspectionLevel::I => 4,
+        InspectionLevel::Ii => 5,
+        InspectionLevel::Iii => 6,
+    };
+    let letter = TABLE_I[row].as_bytes()[column] as char;
+    CODE_LETTERS
+        .iter()
+        .position(|&c| c == letter)
+        .unwrap_or_default()
+}
+
+/// Sample size code letter (Table I) for a lot size and inspection level
+pub fn code_letter(lot_size: u32, level: InspectionLevel) -> char {
+    CODE_LETTERS[code_letter_index(lot_size, level)]
+}
+
+/// Master table cell for a code letter row and AQL column
+///
+/// Plans run along the table diagonals: moving one code letter down and one
+/// AQL step left gives the same accept/reject numbers. Acceptance numbers
+/// above 21 only appear for the smallest samples (letters A-E).
+fn table_cell(tightened: bool, row: usize, col: usize) -> Cell {
+    let (offset, plans): (i32, &[(u32, u32)]) = if tightened {
+        (16, &TIGHTENED_PLANS)
+    } else {
+        (14, &NORMAL_PLANS)
+    };
+    // Position along the diagonal; the normal table has an extra up arrow
+    // between (0, 1) and the down arrow before (1, 2)
+    let pos = row as i32 + col as i32 - offset;
+    let first_plan = if tightened { 2 } else { 3 };
+    match pos {
+        p if p < 0 => Cell::Down,
+        0 => Cell::Plan(0, 1),
+        1 if !tightened => Cell::Up,
+        p if p < first_plan => Cell::Down,
+        p => match plans.get((p - first_plan) as usize) {
+            Some(&(ac, re)) if ac <= 21 || row <= 4 => Cell::Plan(ac, re),
+            _ => Cell::Up,
+        },
+    }
+}
+
+/// Resolve a master table cell to a plan, following its arrow
+///
+/// Returns the code letter row the plan was found on. An arrow that would
+/// leave the table is followed in the other direction instead.
+fn resolve(tightened: bool, row: usize, col: usize) -> (usize, u32, u32) {
+    let last_row: i32 = if tightened { 16 } else { 15 };
+    let step: i32 = match table_cell(tightened, row, col) {
+        Cell::Plan(ac, re) => return (row, ac, re),
+        Cell::Up => -1,
+        Cell::Down => 1,
+    };
+    for dir in [step, -step] {
+        let mut r = row as i32 + dir;
+        while (0..=last_row).contains(&r) {
+            if let Cell::Plan(ac, re) = table_cell(tightened, r as usize, col) {
+                return (r as usize, ac, re);
+            }
+            r += dir;
+        }
+    }
+    (row, 0, 1)
+}
+
+/// How to choose sampling plans for a control
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanSpec {
+    pub aql: f64,
+    pub level: InspectionLevel,
+    pub scheme: SamplingScheme,
+    /// Fixed severity; when `None`, the switching rules decide
+    pub severity: Option<InspectionSeverity>,
+}
+
+impl PlanSpec {
+    /// Create a plan specification, checking the AQL is a preferred value
+    pub fn new(aql: f64, level: InspectionLevel, scheme: SamplingScheme) -> Result<Self, String> {
+        if aql_index(aql).is_none() {
+            return Err(format!(
+                "AQL {} is not a preferred value ({})",
+                aql,
+                AQL_VALUES
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if scheme == SamplingScheme::Variables && aql > 10.0 {
+            return Err(format!(
+                "Variables plans need an AQL of 10 percent nonconforming or less (got {})",
+                aql
+            ));
+        }
+        Ok(Self {
+            aql,
+            level,
+            scheme,
+            severity: None,
+        })
+    }
+
+    /// Plan specification of a control's sampling, if it has an AQL
+    pub fn from_sampling(sampling: &Sampling) -> Result<Option<Self>, String> {
+        let Some(aql) = sampling.aql else {
+            return Ok(None);
+        };
+        let mut spec = Self::new(
+            aql,
+            sampling.inspection_level.unwrap_or_default(),
+            sampling.scheme.unwrap_or_default(),
+        )?;
+        spec.severity = sampling.severity;
+        Ok(Some(spec))
+    }
+
+    fn aql_col(&self) -> usize {
+        aql_index(self.aql).unwrap_or_default()
+    }
+
+    /// AQL above 10 is expressed in nonconformities per hundred units
+    fn per_hundred(&self) -> bool {
+        self.aql > 10.0
+    }
+
+    /// Single sampling plan by attributes: (code letter row, n, Ac, Re)
+    fn single(&self, lot_size: u32, severity: InspectionSeverity) -> (usize, u32, u32, u32) {
+        let letter = code_letter_index(lot_size.max(1), self.level);
+        let (row, ac, re) = match severity {
+            InspectionSeverity::Normal => resolve(false, letter, self.aql_col()),
+            InspectionSeverity::Tightened => resolve(true, letter, self.aql_col()),
+            // Reduced sample sizes are those of the code letter two rows up,
+            // with that letter's normal accept/reject numbers
+            InspectionSeverity::Reduced => resolve(false, letter.saturating_sub(2), self.aql_col()),
+        };
+        (row, SAMPLE_SIZES[row], ac, re)
+    }
+
+    /// Sampling plan for a lot at the given severity
+    pub fn plan(&self, lot_size: u32, severity: InspectionSeverity) -> SamplingPlan {
+        let lot_size = lot_size.max(1);
+        let (_, n, ac, re) = self.single(lot_size, severity);
+        let single = Attributes {
+            n,
+            ac,
+            per_hundred: self.per_hundred(),
+        };
+
+        let mut plan = SamplingPlan {
+            scheme: self.scheme,
+            severity,
+            aql: self.aql,
+            level: self.level,
+            lot_size,
+            code_letter: code_letter(lot_size, self.level),
+            sample_size: n,
+            accept: Some(ac),
+            reject: Some(re),
+            k: None,
+            hundred_percent: false,
+        };
+
+        match self.scheme {
+            SamplingScheme::Single => {}
+            SamplingScheme::ZeroAcceptance => {
+                if ac > 0 {
+                    let lq = single.quality_at(0.10);
+                    let n0 = if single.per_hundred {
+                        10f64.ln() / lq
+                    } else {
+                        0.1f64.ln() / (1.0 - lq).ln()
+                    };
+                    plan.sample_size = n0.ceil() as u32;
+                }
+                plan.accept = Some(0);
+                plan.reject = Some(1);
+            }
+            SamplingScheme::Variables => {
+                let (nv, k) = variables_design(&single, self.aql / 100.0);
+                plan.sample_size = nv;
+                plan.k = Some((k * 1000.0).round() / 1000.0);
+                plan.accept = None;
+                plan.reject = None;
+            }
+        }
+
+        if plan.sample_size >= lot_size {
+            plan.sample_size = lot_size;
+            plan.hundred_percent = true;
+        }
+        plan
+    }
+
+    /// Acceptance number one AQL step tighter, on the same sample
+    fn tighter_accept(&self, lot_size: u32) -> Option<u32> {
+        let col = self.aql_col().checked_sub(1)?;
+        let (row, _, _, _) = self.single(lot_size, InspectionSeverity::Normal);
+        match table_cell(false, row, col) {
+            Cell::Plan(ac, _) => Some(ac),
+            _ => None,
+        }
+    }
+}
+
+/// Single sampling by attributes, for OC calculations
+#[derive(Debug, Clone, Copy)]
+struct Attributes {
+    n: u32,
+    ac: u32,
+    per_hundred: bool,
+}
+
+impl Attributes {
+    fn probability_of_acceptance(&self, p: f64) -> f64 {
+        if self.per_hundred {
+            poisson_cdf(self.n as f64 * p, self.ac)
+        } else {
+            binomial_cdf(self.n, self.ac, p)
+        }
+    }
+
+    fn quality_at(&self, pa: f64) -> f64 {
+        quality_at(|p| self.probability_of_acceptance(p), pa, self.per_hundred)
+    }
+}
+
+/// Design a k-method variables plan (unknown sigma) matching an attribute
+/// plan's OC curve at the AQL and at 10% acceptance
+fn variables_design(single: &Attributes, p1: f64) -> (u32, f64) {
+    let alpha = (1.0 - single.probability_of_acceptance(p1)).clamp(0.001, 0.5);
+    let beta: f64 = 0.10;
+    let p2 = single.quality_at(beta).min(0.999);
+
+    let z1 = inverse_normal_cdf(1.0 - p1);
+    let z2 = inverse_normal_cdf(1.0 - p2);
+    let za = inverse_normal_cdf(1.0 - alpha);
+    let zb = inverse_normal_cdf(1.0 - beta);
+
+    let k = (z1 * zb + z2 * za) / (za + zb);
+    if z1 - z2 <= 1e-9 {
+        return (single.n, k);
+    }
+    let n = (1.0 + k * k / 2.0) * ((za + zb) / (z1 - z2)).powi(2);
+    ((n.ceil() as u32).max(3), k)
+}
+
+/// A sampling plan for one lot
+#[derive(Debug, Clone, Serialize)]
+pub struct SamplingPlan {
+    pub scheme: SamplingScheme,
+    pub severity: InspectionSeverity,
+    pub aql: f64,
+    pub level: InspectionLevel,
+    pub lot_size: u32,
+    /// Sample size code letter from Table I
+    pub code_letter: char,
+    pub sample_size: u32,
+    /// Acceptance number (attribute plans)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept: Option<u32>,
+    /// Rejection number (attribute plans)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject: Option<u32>,
+    /// Acceptability constant (variables plans)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<f64>,
+    /// Sample size reached the lot size: inspect every unit
+    pub hundred_percent: bool,
+}
+
+/// One point of an operating characteristic curve
+#[derive(Debug, Clone, Serialize)]
+pub struct OcPoint {
+    /// Lot quality (percent nonconforming, or nonconformities per hundred units)
+    pub quality: f64,
+    /// Probability of acceptance
+    pub pa: f64,
+    /// Average outgoing quality, assuming rejected lots are screened
+    pub aoq: f64,
+}
+
+/// Key points of an operating characteristic curve (qualities in percent)
+#[derive(Debug, Clone, Serialize)]
+pub struct OcSummary {
+    /// Probability of accepting a lot at the AQL
+    pub pa_at_aql: f64,
+    /// Quality accepted 95% of the time
+    pub p95: f64,
+    /// Quality accepted 50% of the time (indifference quality)
+    pub p50: f64,
+    /// Quality accepted 10% of the time (limiting quality)
+    pub p10: f64,
+    /// Average outgoing quality limit
+    pub aoql: f64,
+}
+
+impl SamplingPlan {
+    /// Short description, e.g. "n=80 Ac 2 Re 3" or "n=37 k=1.868"
+    pub fn describe(&self) -> String {
+        let mut text = format!("n={}", self.sample_size);
+        if let (Some(ac), Some(re)) = (self.accept, self.reject) {
+            text.push_str(&format!(" Ac {} Re {}", ac, re));
+        }
+        if let Some(k) = self.k {
+            text.push_str(&format!(" k={}", k));
+        }
+        if self.hundred_percent {
+            text.push_str(" (100%)");
+        }
+        text
+    }
+
+    fn per_hundred(&self) -> bool {
+        self.aql > 10.0
+    }
+
+    /// Lot disposition for a defect count (attribute plans)
+    pub fn accepts(&self, defects: u32) -> Option<bool> {
+        self.accept.map(|ac| defects <= ac)
+    }
+
+    /// Probability of accepting a lot of quality `p` (fraction nonconforming,
+    /// or nonconformities per unit when the AQL is above 10)
+    pub fn probability_of_acceptance(&self, p: f64) -> f64 {
+        match self.k {
+            Some(k) => {
+                if p <= 0.0 {
+                    return 1.0;
+                }
+                if p >= 1.0 {
+                    return 0.0;
+                }
+                let n = self.sample_size as f64;
+                let zp = inverse_normal_cdf(1.0 - p);
+                normal_cdf((zp - k) * (n / (1.0 + k * k / 2.0)).sqrt())
+            }
+            None => Attributes {
+                n: self.sample_size,
+                ac: self.accept.unwrap_or_default(),
+                per_hundred: self.per_hundred(),
+            }
+            .probability_of_acceptance(p),
+        }
+    }
+
+    /// Lot quality (fraction) accepted with probability `pa`
+    pub fn quality_at(&self, pa: f64) -> f64 {
+        quality_at(
+            |p| self.probability_of_acceptance(p),
+            pa,
+            self.per_hundred(),
+        )
+    }
+
+    fn aoq(&self, p: f64) -> f64 {
+        let n = self.lot_size as f64;
+        let screened = (n - self.sample_size as f64).max(0.0) / n;
+        self.probability_of_acceptance(p) * p * screened
+    }
+
+    /// OC curve from perfect quality to the quality accepted 1% of the time
+    pub fn oc_curve(&self, points: usize) -> Vec<OcPoint> {
+        let end = self.quality_at(0.01);
+        let steps = points.max(2) - 1;
+        (0..=steps)
+            .map(|i| {
+                let p = end * i as f64 / steps as f64;
+                OcPoint {
+                    quality: p * 100.0,
+                    pa: self.probability_of_acceptance(p),
+                    aoq: self.aoq(p) * 100.0,
+                }
+            })
+            .collect()
+    }
+
+    /// Key points of the OC curve
+    pub fn oc_summary(&self) -> OcSummary {
+        let end = self.quality_at(0.01);
+        let aoql = (0..=400)
+            .map(|i| self.aoq(end * i as f64 / 400.0))
+            .fold(0.0, f64::max);
+        OcSummary {
+            pa_at_aql: self.probability_of_acceptance(self.aql / 100.0),
+            p95: self.quality_at(0.95) * 100.0,
+            p50: self.quality_at(0.50) * 100.0,
+            p10: self.quality_at(0.10) * 100.0,
+            aoql: aoql * 100.0,
+        }
+    }
+}
+
+/// Outcome of inspecting one earlier lot, for the switching rules
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LotInspection {
+    pub lot_size: u32,
+    /// Defects (or nonconformities) found in the sample
+    pub defects: Option<u32>,
+    /// Recorded disposition; judged from `defects` against the plan when unset
+    pub accepted: Option<bool>,
+}
+
+/// Inspection severity after applying the switching rules to a lot history
+#[derive(Debug, Clone, Serialize)]
+pub struct SwitchingState {
+    pub severity: InspectionSeverity,
+    /// Switching score toward reduced inspection (normal inspection only)
+    pub switching_score: u32,
+    /// Lots in the history
+    pub lots: usize,
+    /// Five lots rejected on tightened inspection: acceptance inspection
+    /// should stop until the supplier has corrected the process
+    pub discontinued: bool,
+    /// Why the current severity applies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Apply the switching rules to earlier lots, oldest first
+///
+/// - normal → tightened: 2 of 5 or fewer consecutive lots rejected
+/// - tightened → normal: 5 consecutive lots accepted
+/// - tightened: 5 lots rejected discontinues acceptance inspection
+/// - normal → reduced: switching score of 30 or more
+/// - reduced → normal: a lot rejected
+///
+/// The switching score adds 3 for each lot that would also have been accepted
+/// one AQL step tighter (plans with Ac of 2 or more) or 2 for each accepted
+/// lot (Ac of 0 or 1, c=0 and variables plans), and resets to 0 otherwise.
+pub fn switching_state(spec: &PlanSpec, history: &[LotInspection]) -> SwitchingState {
+    let mut state = SwitchingState {
+        severity: InspectionSeverity::Normal,
+        switching_score: 0,
+        lots: history.len(),
+        discontinued: false,
+        reason: None,
+    };
+    if let Some(fixed) = spec.severity {
+        state.severity = fixed;
+        state.reason = Some("fixed by the control's sampling plan".to_string());
+        return state;
+    }
+
+    let mut recent: Vec<bool> = Vec::new();
+    let mut tightened_accepted = 0;
+    let mut tightened_rejected = 0;
+
+    for lot in history {
+        let plan = spec.plan(lot.lot_size, state.severity);
+        let accepted = lot
+            .accepted
+            .or_else(|| plan.accepts(lot.defects.unwrap_or_default()))
+            .unwrap_or(true);
+
+        match state.severity {
+            InspectionSeverity::Normal => {
+                recent.push(accepted);
+                if recent.len() > 5 {
+                    recent.remove(0);
+                }
+                if recent.iter().filter(|a| !**a).count() >= 2 {
+                    state.severity = InspectionSeverity::Tightened;
+                    state.reason =
+                        Some("2 of the last 5 lots rejected on normal inspection".to_string());
+                    state.switching_score = 0;
+                    tightened_accepted = 0;
+                    tightened_rejected = 0;
+                    continue;
+                }
+
+                let ac = plan.accept.unwrap_or_default();
+                let single = spec.scheme == SamplingScheme::Single;
+                state.switching_score = if !accepted {
+                    0
+                } else if single && ac >= 2 {
+                    let defects = lot.defects.unwrap_or_default();
+                    match spec.tighter_accept(lot.lot_size) {
+                        Some(tighter) if defects <= tighter => state.switching_score + 3,
+                        _ => 0,
+                    }
+                } else {
+                    state.switching_score + 2
+                };
+                if state.switching_score >= REDUCED_SWITCHING_SCORE {
+                    state.severity = InspectionSeverity::Reduced;
+                    state.reason = Some(format!(
+                        "switching score reached {} on normal inspection",
+                        state.switching_score
+                    ));
+                    state.switching_score = 0;
+                }
+            }
+            InspectionSeverity::Tightened => {
+                if accepted {
+                    tightened_accepted += 1;
+                    if tightened_accepted >= 5 {
+                        state.severity = InspectionSeverity::Normal;
+                        state.reason =
+                            Some("5 consecutive lots accepted on tightened inspection".to_string());
+                        state.discontinued = false;
+                        recent.clear();
+                    }
+                } else {
+                    tightened_accepted = 0;
+                    tightened_rejected += 1;
+                    if tightened_rejected >= 5 {
+                        state.discontinued = true;
+                        state.reason = Some("5 lots rejected on tightened inspection".to_string());
+                    }
+                }
+            }
+            InspectionSeverity::Reduced => {
+                if !accepted {
+                    state.severity = InspectionSeverity::Normal;
+                    state.reason = Some("lot rejected on reduced inspection".to_string());
+                    recent.clear();
+                }
+            }
+        }
+    }
+    state
+}
+
+/// Variables acceptance: the quality indices (U - mean)/s and (mean - L)/s
+/// must both be at least k
+pub fn variables_accepts(
+    lower: Option<f64>,
+    upper: Option<f64>,
+    mean: f64,
+    std_dev: f64,
+    k: f64,
+) -> bool {
+    if std_dev <= 0.0 {
+        return lower.is_none_or(|lo| mean >= lo) && upper.is_none_or(|hi| mean <= hi);
+    }
+    let q_upper = upper.map(|hi| (hi - mean) / std_dev);
+    let q_lower = lower.map(|lo| (mean - lo) / std_dev);
+    q_upper.is_none_or(|q| q >= k) && q_lower.is_none_or(|q| q >= k)
+}
+
+/// Lot quality accepted with probability `pa` (bisection on a decreasing OC curve)
+fn quality_at(pa_of: impl Fn(f64) -> f64, pa: f64, unbounded: bool) -> f64 {
+    let mut hi = 1.0;
+    if unbounded {
+        while pa_of(hi) > pa && hi < 1e6 {
+            hi *= 2.0;
+        }
+    }
+    let mut lo = 0.0;
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if pa_of(mid) > pa {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+/// P(X <= c) for X ~ Binomial(n, p)
+fn binomial_cdf(n: u32, c: u32, p: f64) -> f64 {
+    if c >= n || p <= 0.0 {
+        return 1.0;
+    }
+    if p >= 1.0 {
+        return 0.0;
+    }
+    let ratio = p / (1.0 - p);
+    let mut term = (1.0 - p).powi(n as i32);
+    let mut sum = term;
+    for d in 0..c {
+        term *= (n - d) as f64 / (d + 1) as f64 * ratio;
+        sum += term;
+    }
+    sum.min(1.0)
+}
+
+/// P(X <= c) for X ~ Poisson(lambda)
+fn poisson_cdf(lambda: f64, c: u32) -> f64 {
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+    let mut term = (-lambda).exp();
+    let mut sum = term;
+    for d in 0..c {
+        term *= lambda / (d + 1) as f64;
+        sum += term;
+    }
+    sum.min(1.0)
+}
+
+/// Standard normal CDF (Hastings approximation, A&S 26.2.17)
+fn normal_cdf(z: f64) -> f64 {
+    if z >= 8.0 {
+        return 1.0;
+    }
+    if z <= -8.0 {
+        return 0.0;
+    }
+    let (z_abs, negate) = if z < 0.0 { (-z, true) } else { (z, false) };
+    let t = 1.0 / (1.0 + 0.2316419 * z_abs);
+    let poly = t
+        * (0.319381530
+            + t * (-0.356563782 + t * (1.781477937 + t * (-1.821255978 + t * 1.330274429))));
+    let pdf = (-0.5 * z_abs * z_abs).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    let cdf = 1.0 - pdf * poly;
+    if negate {
+        1.0 - cdf
+    } else {
+        cdf
+    }
+}
+
+/// Inverse standard normal CDF (Acklam's rational approximation)
+fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.383_577_518_672_69e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(aql: f64, scheme: SamplingScheme) -> PlanSpec {
+        PlanSpec::new(aql, InspectionLevel::Ii, scheme).unwrap()
+    }
+
+    #[test]
+    fn test_code_letters() {
+        assert_eq!(code_letter(1000, InspectionLevel::Ii), 'J');
+        assert_eq!(code_letter(50, InspectionLevel::S1), 'A');
+        assert_eq!(code_letter(2000, InspectionLevel::Iii), 'L');
+        assert_eq!(code_letter(600_000, InspectionLevel::Iii), 'R');
+        assert_eq!(code_letter(1, InspectionLevel::Ii), 'A');
+    }
+
+    #[test]
+    fn test_single_sampling_plans() {
+        let s = spec(1.0, SamplingScheme::Single);
+        let normal = s.plan(1000, InspectionSeverity::Normal);
+        assert_eq!(normal.code_letter, 'J');
+        assert_eq!(
+            (normal.sample_size, normal.accept, normal.reject),
+            (80, Some(2), Some(3))
+        );
+        let tightened = s.plan(1000, InspectionSeverity::Tightened);
+        assert_eq!(
+            (tightened.sample_size, tightened.accept, tightened.reject),
+            (80, Some(1), Some(2))
+        );
+        let reduced = s.plan(2000, InspectionSeverity::Reduced);
+        assert_eq!(
+            (reduced.sample_size, reduced.accept, reduced.reject),
+            (50, Some(1), Some(2))
+        );
+
+        // K at 0.65: 125, Ac 2 Re 3
+        let k = spec(0.65, SamplingScheme::Single).plan(2000, InspectionSeverity::Normal);
+        assert_eq!((k.sample_size, k.accept), (125, Some(2)));
+
+        // Down arrow: J at 0.10 uses K's 0/1 plan
+        let arrow = spec(0.10, SamplingScheme::Single).plan(1000, InspectionSeverity::Normal);
+        assert_eq!(
+            (arrow.sample_size, arrow.accept, arrow.reject),
+            (125, Some(0), Some(1))
+        );
+
+        // Sample larger than the lot: inspect every unit
+        let small = s.plan(5, InspectionSeverity::Normal);
+        assert_eq!(small.sample_size, 5);
+        assert!(small.hundred_percent);
+    }
+
+    #[test]
+    fn test_aql_must_be_preferred() {
+        assert!(PlanSpec::new(1.2, InspectionLevel::Ii, SamplingScheme::Single).is_err());
+        assert!(PlanSpec::new(25.0, InspectionLevel::Ii, SamplingScheme::Variables).is_err());
+        assert!(PlanSpec::new(0.065, InspectionLevel::Ii, SamplingScheme::Single).is_ok());
+    }
+
+    #[test]
+    fn test_zero_acceptance_and_variables_plans() {
+        let single = spec(1.0, SamplingScheme::Single).plan(1000, InspectionSeverity::Normal);
+        let c0 = spec(1.0, SamplingScheme::ZeroAcceptance).plan(1000, InspectionSeverity::Normal);
+        assert_eq!((c0.accept, c0.reject), (Some(0), Some(1)));
+        assert_eq!(c0.sample_size, 35);
+        // At least the single plan's protection at its limiting quality
+        let lq = single.quality_at(0.10);
+        assert!(c0.probability_of_acceptance(lq) <= 0.10 + 1e-9);
+
+        let var = spec(1.0, SamplingScheme::Variables).plan(1000, InspectionSeverity::Normal);
+        assert!(var.accept.is_none());
+        assert_eq!(var.sample_size, 37);
+        let k = var.k.unwrap();
+        assert!(k > 1.5 && k < 2.2, "k = {}", k);
+        // OC curve matches the single plan near the limiting quality
+        assert!((var.probability_of_acceptance(lq) - 0.10).abs() < 0.03);
+
+        assert!(variables_accepts(Some(9.95), Some(10.05), 10.0, 0.01, k));
+        assert!(!variables_accepts(Some(9.95), Some(10.05), 10.035, 0.01, k));
+    }
+
+    #[test]
+    fn test_oc_curve() {
+        let plan = spec(1.0, SamplingScheme::Single).plan(1000, InspectionSeverity::Normal);
+        let summary = plan.oc_summary();
+        assert!((summary.pa_at_aql - 0.953).abs() < 0.005);
+        assert!(summary.p95 < summary.p50 && summary.p50 < summary.p10);
+        assert!(summary.aoql > 0.0 && summary.aoql < summary.p10);
+
+        let curve = plan.oc_curve(11);
+        assert_eq!(curve.len(), 11);
+        assert!((curve[0].pa - 1.0).abs() < 1e-9);
+        assert!(curve.windows(2).all(|w| w[1].pa <= w[0].pa));
+        assert!((curve[10].pa - 0.01).abs() < 1e-6);
+
+        // Nonconformities per hundred units use the Poisson model
+        let per_hundred = spec(25.0, SamplingScheme::Single).plan(100, InspectionSeverity::Normal);
+        assert!(per_hundred.oc_summary().p10 > 25.0);
+    }
+
+    #[test]
+    fn test_switching_rules() {
+        let s = spec(1.0, SamplingScheme::Single);
+        let lot = |defects: u32| LotInspection {
+            lot_size: 1000,
+            defects: Some(defects),
+            accepted: None,
+        };
+
+        // Two rejections within five lots: tightened
+        let history = vec![lot(0), lot(3), lot(1), lot(4)];
+        let state = switching_state(&s, &history);
+        assert_eq!(state.severity, InspectionSeverity::Tightened);
+
+        // Five accepted on tightened: back to normal
+        let mut back = history.clone();
+        back.extend(std::iter::repeat_n(lot(0), 5));
+        assert_eq!(
+            switching_state(&s, &back).severity,
+            InspectionSeverity::Normal
+        );
+
+        // Five rejected on tightened: discontinue
+        let mut stop = history.clone();
+        stop.extend(std::iter::repeat_n(lot(5), 5));
+        assert!(switching_state(&s, &stop).discontinued);
+
+        // Ten clean lots (Ac 2, +3 each): reduced; a rejection returns to normal
+        let mut clean: Vec<LotInspection> = std::iter::repeat_n(lot(0), 9).collect();
+        assert_eq!(switching_state(&s, &clean).switching_score, 27);
+        clean.push(lot(0));
+        assert_eq!(
+            switching_state(&s, &clean).severity,
+            InspectionSeverity::Reduced
+        );
+        clean.push(lot(2));
+        assert_eq!(
+            switching_state(&s, &clean).severity,
+            InspectionSeverity::Normal
+        );
+
+        // Accepted but not at one step tighter (Ac 1 at 0.65): score resets
+        let reset = vec![lot(0), lot(0), lot(2)];
+        assert_eq!(switching_state(&s, &reset).switching_score, 0);
+
+        // A fixed severity ignores the history
+        let mut fixed = s;
+        fixed.severity = Some(InspectionSeverity::Tightened);
+        assert_eq!(
+            switching_state(&fixed, &clean).severity,
+            InspectionSeverity::Tightened
+        );
+    }
+}
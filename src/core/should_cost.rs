@@ -0,0 +1,408 @@
+//! Should-cost estimates for make parts
+//!
+//! A make component's should-cost is built from its manufacturing routing and
+//! material. Each routing step costs its cycle time, plus its setup time
+//! amortized over the batch size, at the operator's labor rate plus the hourly
+//! rate of the step's equipment. Material cost is `mass_kg` × cost per kg,
+//! grossed up by the scrap factor. Rates are entered in the reporting
+//! currency. Anything that cannot be priced is left out of the total and
+//! reported as an issue.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::project::Project;
+use crate::entities::component::{Component, MakeBuy};
+use crate::entities::process::{Process, SkillLevel};
+
+/// Rate tables for should-cost estimates (`costing` in config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostingConfig {
+    /// Units per production batch that setup time is amortized over
+    pub batch_size: u32,
+
+    /// Scrap allowance applied to materials without their own
+    pub scrap_factor: f64,
+
+    /// Labor rates per hour by operator skill level
+    pub labor_rates: LaborRates,
+
+    /// Machine rates per hour, matched to process equipment by name or ID
+    pub machine_rates: Vec<MachineRate>,
+
+    /// Material costs per kg, matched to component `material`
+    pub materials: Vec<MaterialCost>,
+}
+
+impl Default for CostingConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            scrap_factor: 0.05,
+            labor_rates: LaborRates::default(),
+            machine_rates: Vec::new(),
+            materials: Vec::new(),
+        }
+    }
+}
+
+/// Hourly labor rates; `default` applies to skill levels not listed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaborRates {
+    pub default: Option<f64>,
+    pub entry: Option<f64>,
+    pub intermediate: Option<f64>,
+    pub advanced: Option<f64>,
+    pub expert: Option<f64>,
+}
+
+impl LaborRates {
+    /// Hourly rate for an operator skill level
+    pub fn rate(&self, skill: SkillLevel) -> Option<f64> {
+        let rate = match skill {
+            SkillLevel::Entry => self.entry,
+            SkillLevel::Intermediate => self.intermediate,
+            SkillLevel::Advanced => self.advanced,
+            SkillLevel::Expert => self.expert,
+        };
+        rate.or(self.default)
+    }
+}
+
+/// Hourly rate for a piece of equipment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MachineRate {
+    /// Equipment name or equipment ID
+    pub equipment: String,
+    pub rate: f64,
+}
+
+/// Cost per kg of a material
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialCost {
+    pub material: String,
+    pub cost_per_kg: f64,
+    /// Scrap allowance for this material (overrides `scrap_factor`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scrap_factor: Option<f64>,
+}
+
+impl CostingConfig {
+    fn machine_rate(&self, name: &str, equipment_id: Option<&str>) -> Option<f64> {
+        self.machine_rates
+            .iter()
+            .find(|m| {
+                m.equipment.eq_ignore_ascii_case(name.trim())
+                    || equipment_id.is_some_and(|id| m.equipment.eq_ignore_ascii_case(id.trim()))
+            })
+            .map(|m| m.rate)
+    }
+
+    fn material(&self, material: &str) -> Option<&MaterialCost> {
+        self.materials
+            .iter()
+            .find(|m| m.material.eq_ignore_ascii_case(material.trim()))
+    }
+}
+
+/// Cost of one routing step, per unit
+#[derive(Debug, Clone, Serialize)]
+pub struct StepCost {
+    pub process: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_number: Option<String>,
+    pub setup_minutes: f64,
+    pub cycle_minutes: f64,
+    /// Labor plus machine rate per hour
+    pub hourly_rate: f64,
+    /// Setup cost amortized over the batch
+    pub setup_cost: f64,
+    pub cycle_cost: f64,
+    pub total: f64,
+}
+
+/// Should-cost estimate for one make part, per unit
+#[derive(Debug, Clone, Serialize)]
+pub struct ShouldCost {
+    pub component: String,
+    pub title: String,
+    pub batch_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mass_kg: Option<f64>,
+    pub scrap_factor: f64,
+    pub material_cost: f64,
+    pub steps: Vec<StepCost>,
+    pub process_cost: f64,
+    pub total: f64,
+    /// Inputs that could not be priced
+    pub issues: Vec<String>,
+}
+
+impl ShouldCost {
+    /// Whether anything could be priced
+    pub fn is_estimated(&self) -> bool {
+        self.total > 0.0
+    }
+}
+
+/// Estimate a component's should-cost from its routing and material
+pub fn estimate(
+    component: &Component,
+    processes: &[Process],
+    config: &CostingConfig,
+    batch_size: u32,
+) -> ShouldCost {
+    let batch_size = batch_size.max(1);
+    let mut issues = Vec::new();
+
+    // Material
+    let material = component
+        .material
+        .as_ref()
+        .filter(|m| !m.trim().is_empty())
+        .cloned();
+    let mut scrap_factor = config.scrap_factor;
+    let mut material_cost = 0.0;
+    match (&material, component.mass_kg) {
+        (Some(name), Some(mass)) => match config.material(name) {
+            Some(cost) => {
+                scrap_factor = cost.scrap_factor.unwrap_or(config.scrap_factor);
+                material_cost = mass * cost.cost_per_kg * (1.0 + scrap_factor);
+            }
+            None => issues.push(format!("no cost per kg for material '{}'", name)),
+        },
+        (Some(_), None) => issues.push("no mass_kg for material cost".to_string()),
+        (None, _) => issues.push("no material".to_string()),
+    }
+
+    // Routing
+    let routing = component
+        .manufacturing
+        .as_ref()
+        .map(|m| m.routing.as_slice())
+        .unwrap_or_default();
+    if routing.is_empty() {
+        issues.push("no routing".to_string());
+    }
+    let mut steps = Vec::new();
+    for proc_id in routing {
+        let Some(process) = processes.iter().find(|p| p.id.to_string() == *proc_id) else {
+            issues.push(format!("routing process {} not found", proc_id));
+            continue;
+        };
+        let label = process
+            .operation_number
+            .clone()
+            .unwrap_or_else(|| process.title.clone());
+
+        let labor = match config.labor_rates.rate(process.operator_skill) {
+            Some(rate) => rate,
+            None => {
+                issues.push(format!(
+                    "{}: no labor rate for {} operators",
+                    label, process.operator_skill
+                ));
+                0.0
+            }
+        };
+        let mut machine = 0.0;
+        for eq in process.equipment.iter().filter(|e| !e.name.is_empty()) {
+            match config.machine_rate(&eq.name, eq.equipment_id.as_deref()) {
+                Some(rate) => machine += rate,
+                None => issues.push(format!("{}: no machine rate for '{}'", label, eq.name)),
+            }
+        }
+        let cycle_minutes = match process.cycle_time_minutes {
+            Some(minutes) => minutes,
+            None => {
+                issues.push(format!("{}: no cycle time", label));
+                0.0
+            }
+        };
+        let setup_minutes = process.setup_time_minutes.unwrap_or(0.0);
+
+        let hourly_rate = labor + machine;
+        let setup_cost = setup_minutes / 60.0 * hourly_rate / batch_size as f64;
+        let cycle_cost = cycle_minutes / 60.0 * hourly_rate;
+        steps.push(StepCost {
+            process: process.id.to_string(),
+            title: process.title.clone(),
+            operation_number: process.operation_number.clone(),
+            setup_minutes,
+            cycle_minutes,
+            hourly_rate,
+            setup_cost,
+            cycle_cost,
+            total: setup_cost + cycle_cost,
+        });
+    }
+
+    let process_cost: f64 = steps.iter().map(|s| s.total).sum();
+    ShouldCost {
+        component: component.id.to_string(),
+        title: component.title.clone(),
+        batch_size,
+        material,
+        mass_kg: component.mass_kg,
+        scrap_factor,
+        material_cost,
+        steps,
+        process_cost,
+        total: material_cost + process_cost,
+        issues,
+    }
+}
+
+/// Should-cost of every make component, keyed by component ID
+///
+/// Uses the `costing` rate tables from config and the project's processes.
+/// `batch_size` overrides the configured batch size.
+pub fn estimate_make_parts(
+    project: &Project,
+    components: &[Component],
+    batch_size: Option<u32>,
+) -> HashMap<String, ShouldCost> {
+    let config = crate::core::Config::load().costing.unwrap_or_default();
+    let batch_size = batch_size.unwrap_or(config.batch_size);
+    let make_parts: Vec<&Component> = components
+        .iter()
+        .filter(|c| c.make_buy == MakeBuy::Make)
+        .collect();
+    if make_parts.is_empty() {
+        return HashMap::new();
+    }
+    let processes: Vec<Process> =
+        crate::core::loader::load_all(&project.root().join("manufacturing/processes"))
+            .unwrap_or_default();
+    make_parts
+        .into_iter()
+        .map(|c| {
+            (
+                c.id.to_string(),
+                estimate(c, &processes, &config, batch_size),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assembly::ManufacturingConfig;
+    use crate::entities::process::{Equipment, ProcessType};
+
+    fn config() -> CostingConfig {
+        CostingConfig {
+            batch_size: 50,
+            scrap_factor: 0.05,
+            labor_rates: LaborRates {
+                default: Some(30.0),
+                advanced: Some(48.0),
+                ..Default::default()
+            },
+            machine_rates: vec![MachineRate {
+                equipment: "CNC-01".to_string(),
+                rate: 72.0,
+            }],
+            materials: vec![MaterialCost {
+                material: "6061-T6 Aluminum".to_string(),
+                cost_per_kg: 8.0,
+                scrap_factor: Some(0.25),
+            }],
+        }
+    }
+
+    fn process(title: &str, cycle: Option<f64>, setup: f64) -> Process {
+        let mut p = Process::new(
+            title.to_string(),
+            ProcessType::Machining,
+            "test".to_string(),
+        );
+        p.cycle_time_minutes = cycle;
+        p.setup_time_minutes = Some(setup);
+        p
+    }
+
+    #[test]
+    fn test_estimate_routing_and_material() {
+        let mut mill = process("Mill", Some(10.0), 60.0);
+        mill.operator_skill = SkillLevel::Advanced;
+        mill.equipment.push(Equipment {
+            name: "Haas VF-2".to_string(),
+            equipment_id: Some("cnc-01".to_string()),
+            capability: None,
+        });
+        let deburr = process("Deburr", Some(3.0), 0.0);
+
+        let mut cmp = Component::new(
+            "PN-1".to_string(),
+            "Bracket".to_string(),
+            MakeBuy::Make,
+            Default::default(),
+            "test".to_string(),
+        );
+        cmp.material = Some("6061-t6 aluminum".to_string());
+        cmp.mass_kg = Some(0.5);
+        cmp.manufacturing = Some(ManufacturingConfig {
+            routing: vec![mill.id.to_string(), deburr.id.to_string()],
+            work_cell: None,
+        });
+
+        let est = estimate(&cmp, &[mill, deburr], &config(), 50);
+        // Material: 0.5 kg × $8 × 1.25 scrap
+        assert!((est.material_cost - 5.0).abs() < 1e-9);
+        // Mill: $120/h; 10 min cycle = $20, 60 min setup / 50 = $2.40
+        assert!((est.steps[0].cycle_cost - 20.0).abs() < 1e-9);
+        assert!((est.steps[0].setup_cost - 2.4).abs() < 1e-9);
+        // Deburr: default labor $30/h, 3 min = $1.50
+        assert!((est.steps[1].total - 1.5).abs() < 1e-9);
+        assert!((est.total - 28.9).abs() < 1e-9);
+        assert!(est.issues.is_empty());
+    }
+
+    #[test]
+    fn test_estimate_reports_unpriced_inputs() {
+        let mut mill = process("Mill", None, 30.0);
+        mill.operation_number = Some("OP-010".to_string());
+        mill.equipment.push(Equipment {
+            name: "Lathe".to_string(),
+            equipment_id: None,
+            capability: None,
+        });
+
+        let mut cmp = Component::new(
+            "PN-2".to_string(),
+            "Shaft".to_string(),
+            MakeBuy::Make,
+            Default::default(),
+            "test".to_string(),
+        );
+        cmp.material = Some("Titanium".to_string());
+        cmp.mass_kg = Some(1.0);
+        cmp.manufacturing = Some(ManufacturingConfig {
+            routing: vec![mill.id.to_string(), "PROC-MISSING".to_string()],
+            work_cell: None,
+        });
+
+        let est = estimate(&cmp, &[mill], &config(), 0);
+        assert_eq!(est.batch_size, 1);
+        assert_eq!(est.material_cost, 0.0);
+        // Setup still priced at the default labor rate: 30 min at $30/h
+        assert!((est.total - 15.0).abs() < 1e-9);
+        assert_eq!(
+            est.issues,
+            vec![
+                "no cost per kg for material 'Titanium'",
+                "OP-010: no machine rate for 'Lathe'",
+                "OP-010: no cycle time",
+                "routing process PROC-MISSING not found",
+            ]
+        );
+    }
+}
@@ -0,0 +1,445 @@
+//! Sourcing decision matrix for comparing quotes
+//!
+//! Every valid quote for an item is normalized to the same target quantity:
+//! the purchase quantity is raised to the MOQ or the lowest price break when
+//! needed, and the cost of the excess units is spread over the units actually
+//! required. Tooling and NRE are amortized over the program volume. Each
+//! quote then gets 0-100 scores for cost, lead time and supplier scorecard,
+//! combined with configurable weights. A factor no quote has data for is left
+//! out of the weighting; a quote missing a factor that others have scores the
+//! configured `missing_score` for it, so missing data is never rewarded.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::currency::Converter;
+use crate::entities::quote::{Currency, Quote, QuoteStatus};
+
+/// Relative weight of each sourcing factor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourcingWeights {
+    pub cost: f64,
+    pub lead_time: f64,
+    pub supplier: f64,
+}
+
+impl Default for SourcingWeights {
+    fn default() -> Self {
+        Self {
+            cost: 0.6,
+            lead_time: 0.2,
+            supplier: 0.2,
+        }
+    }
+}
+
+/// Sourcing configuration (`sourcing` in config)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourcingConfig {
+    pub weights: SourcingWeights,
+    /// Score (0-100) given to a factor a quote has no data for
+    pub missing_score: f64,
+}
+
+/// What the quotes are compared at
+#[derive(Debug, Clone, Copy)]
+pub struct SourcingParams {
+    /// Units required
+    pub qty: u32,
+    /// Program volume NRE/tooling is amortized over (None = not amortized)
+    pub volume: Option<u32>,
+    /// Whether NRE/tooling counts toward the effective unit cost
+    pub include_nre: bool,
+    /// Quotes that expired before this date are excluded
+    pub as_of: NaiveDate,
+}
+
+/// One quote in the decision matrix, amounts in the reporting currency
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcingOption {
+    pub quote: String,
+    pub title: String,
+    pub supplier: String,
+    /// Currency the quote was given in
+    pub currency: Currency,
+    /// Units purchased (target quantity raised to MOQ / lowest price break)
+    pub buy_qty: u32,
+    /// Unit price at the purchase quantity
+    pub unit_price: f64,
+    /// Cost of units bought beyond the target, per required unit
+    pub moq_excess_per_unit: f64,
+    /// Total tooling and NRE
+    pub nre_total: f64,
+    /// Tooling and NRE per unit over the program volume
+    pub nre_per_unit: f64,
+    /// Unit price + MOQ excess + amortized NRE
+    pub effective_unit_cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_time_days: Option<u32>,
+    /// Supplier scorecard score (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier_score: Option<f64>,
+    pub cost_score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_time_score: Option<f64>,
+    /// Weighted factors this quote has no data for, scored as `missing_score`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+    /// Weighted overall score (0-100)
+    pub score: f64,
+    /// 1 = best
+    pub rank: usize,
+}
+
+/// A quote left out of the comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct ExcludedQuote {
+    pub quote: String,
+    pub title: String,
+    pub supplier: String,
+    pub reason: String,
+}
+
+/// Ranked quotes for one item
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcingMatrix {
+    pub qty: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<u32>,
+    pub currency: Currency,
+    pub options: Vec<SourcingOption>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded: Vec<ExcludedQuote>,
+}
+
+impl SourcingMatrix {
+    /// Highest-ranked option
+    pub fn best(&self) -> Option<&SourcingOption> {
+        self.options.first()
+    }
+
+    /// Option for a quote ID (full ID or prefix)
+    pub fn option(&self, quote: &str) -> Option<&SourcingOption> {
+        self.options.iter().find(|o| o.quote.starts_with(quote))
+    }
+
+    /// One-paragraph justification for choosing an option
+    pub fn rationale(&self, chosen: &SourcingOption) -> String {
+        let sym = self.currency.symbol();
+        let mut text = format!(
+            "Ranked {} of {} valid quote{} at qty {}",
+            chosen.rank,
+            self.options.len(),
+            if self.options.len() == 1 { "" } else { "s" },
+            self.qty
+        );
+        if let Some(volume) = self.volume {
+            text.push_str(&format!(" (NRE over {} units)", volume));
+        }
+        text.push_str(&format!(
+            ": score {:.0}, effective unit cost {}{:.4}",
+            chosen.score, sym, chosen.effective_unit_cost
+        ));
+        if let Some(lead) = chosen.lead_time_days {
+            text.push_str(&format!(", lead time {}d", lead));
+        }
+        if let Some(score) = chosen.supplier_score {
+            text.push_str(&format!(", supplier score {:.0}", score));
+        }
+        if !chosen.missing.is_empty() {
+            text.push_str(&format!(", no data for {}", chosen.missing.join(", ")));
+        }
+        if chosen.buy_qty > self.qty {
+            text.push_str(&format!(", buys {} for MOQ", chosen.buy_qty));
+        }
+        let runner_up = self
+            .options
+            .iter()
+            .find(|o| o.quote != chosen.quote && (chosen.rank == 1 || o.rank == 1));
+        if let Some(other) = runner_up {
+            text.push_str(&format!(
+                ". {} {} scored {:.0} at {}{:.4}",
+                if chosen.rank == 1 { "Next" } else { "Best" },
+                other.quote,
+                other.score,
+                sym,
+                other.effective_unit_cost
+            ));
+        }
+        text.push('.');
+        text
+    }
+}
+
+/// Build the decision matrix for a set of quotes
+///
+/// `supplier_scores` maps supplier IDs to their scorecard score.
+pub fn evaluate(
+    quotes: &[Quote],
+    supplier_scores: &HashMap<String, f64>,
+    converter: &mut Converter,
+    params: &SourcingParams,
+    config: &SourcingConfig,
+) -> SourcingMatrix {
+    let weights = &config.weights;
+    let mut options = Vec::new();
+    let mut excluded = Vec::new();
+    let qty = params.qty.max(1);
+
+    for quote in quotes {
+        let exclude = |reason: String| ExcludedQuote {
+            quote: quote.id.to_string(),
+            title: quote.title.clone(),
+            supplier: quote.supplier.clone(),
+            reason,
+        };
+        let expired = quote
+            .valid_until
+            .filter(|d| *d < params.as_of)
+            .map(|d| format!("expired {}", d));
+        if quote.quote_status == QuoteStatus::Rejected {
+            excluded.push(exclude("rejected".to_string()));
+            continue;
+        }
+        if let Some(reason) = expired
+            .or_else(|| (quote.quote_status == QuoteStatus::Expired).then(|| "expired".to_string()))
+        {
+            excluded.push(exclude(reason));
+            continue;
+        }
+
+        // Raise the purchase quantity to the MOQ and the lowest price break
+        let lowest_break = quote.price_breaks.iter().map(|pb| pb.min_qty).min();
+        let mut buy_qty = qty.max(quote.moq.unwrap_or(0));
+        if quote.price_for_qty(buy_qty).is_none() {
+            buy_qty = buy_qty.max(lowest_break.unwrap_or(0));
+        }
+        let Some(price) = quote.price_for_qty(buy_qty) else {
+            excluded.push(exclude("no price breaks".to_string()));
+            continue;
+        };
+
+        let unit_price = converter.convert(price, quote.currency);
+        let moq_excess_per_unit = unit_price * (buy_qty - qty) as f64 / qty as f64;
+        let nre_total = converter.convert(quote.total_nre(), quote.currency);
+        let nre_per_unit = match params.volume {
+            Some(volume) if params.include_nre && volume > 0 => nre_total / volume as f64,
+            _ => 0.0,
+        };
+        options.push(SourcingOption {
+            quote: quote.id.to_string(),
+            title: quote.title.clone(),
+            supplier: quote.supplier.clone(),
+            currency: quote.currency,
+            buy_qty,
+            unit_price,
+            moq_excess_per_unit,
+            nre_total,
+            nre_per_unit,
+            effective_unit_cost: unit_price + moq_excess_per_unit + nre_per_unit,
+            lead_time_days: quote.lead_time_for_qty(buy_qty),
+            supplier_score: supplier_scores.get(&quote.supplier).copied(),
+            cost_score: 0.0,
+            lead_time_score: None,
+            missing: Vec::new(),
+            score: 0.0,
+            rank: 0,
+        });
+    }
+
+    // Relative scores: the best option in each factor scores 100
+    let min_cost = options
+        .iter()
+        .map(|o| o.effective_unit_cost)
+        .fold(f64::INFINITY, f64::min);
+    let min_lead = options.iter().filter_map(|o| o.lead_time_days).min();
+    let any_supplier = options.iter().any(|o| o.supplier_score.is_some());
+    for o in &mut options {
+        o.cost_score = if o.effective_unit_cost > 0.0 {
+            100.0 * min_cost / o.effective_unit_cost
+        } else {
+            100.0
+        };
+        o.lead_time_score = o
+            .lead_time_days
+            .zip(min_lead)
+            .map(|(lead, min)| 100.0 * min.max(1) as f64 / lead.max(1) as f64);
+
+        // (name, score, weight, whether any quote has data for the factor)
+        let factors = [
+            ("cost", Some(o.cost_score), weights.cost, true),
+            (
+                "lead time",
+                o.lead_time_score,
+                weights.lead_time,
+                min_lead.is_some(),
+            ),
+            (
+                "supplier score",
+                o.supplier_score,
+                weights.supplier,
+                any_supplier,
+            ),
+        ];
+        let mut sum = 0.0;
+        let mut weight = 0.0;
+        for (name, score, w, any) in factors {
+            if !any {
+                continue;
+            }
+            let score = score.unwrap_or_else(|| {
+                if w > 0.0 {
+                    o.missing.push(name.to_string());
+                }
+                config.missing_score
+            });
+            sum += score * w;
+            weight += w;
+        }
+        o.score = if weight > 0.0 { sum / weight } else { 0.0 };
+    }
+
+    options.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.effective_unit_cost.total_cmp(&b.effective_unit_cost))
+    });
+    for (i, o) in options.iter_mut().enumerate() {
+        o.rank = i + 1;
+    }
+
+    SourcingMatrix {
+        qty,
+        volume: params.volume.filter(|_| params.include_nre),
+        currency: converter.target,
+        options,
+        excluded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::currency::CurrencyConfig;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn quote(supplier: &str, breaks: &[(u32, f64)], lead: u32) -> Quote {
+        let mut q = Quote::new_for_component(supplier, "CMP-1", supplier, "test");
+        for (min_qty, price) in breaks {
+            q.add_price_break(*min_qty, *price, None);
+        }
+        q.lead_time_days = Some(lead);
+        q
+    }
+
+    fn params(qty: u32, volume: Option<u32>) -> SourcingParams {
+        SourcingParams {
+            qty,
+            volume,
+            include_nre: true,
+            as_of: date("2026-06-01"),
+        }
+    }
+
+    #[test]
+    fn test_moq_excess_and_nre_amortization() {
+        let mut cheap_moq = quote("SUP-A", &[(1, 1.00)], 10);
+        cheap_moq.moq = Some(500);
+        let mut tooled = quote("SUP-B", &[(1, 1.50), (100, 1.20)], 10);
+        tooled.tooling_cost = Some(1000.0);
+        let mut conv = Converter::new(CurrencyConfig::default(), None, date("2026-06-01"));
+
+        let matrix = evaluate(
+            &[cheap_moq, tooled],
+            &HashMap::new(),
+            &mut conv,
+            &params(100, Some(10_000)),
+            &SourcingConfig::default(),
+        );
+        let a = matrix
+            .options
+            .iter()
+            .find(|o| o.supplier == "SUP-A")
+            .unwrap();
+        assert_eq!(a.buy_qty, 500);
+        assert!((a.moq_excess_per_unit - 4.0).abs() < 1e-9);
+        assert!((a.effective_unit_cost - 5.0).abs() < 1e-9);
+        let b = matrix
+            .options
+            .iter()
+            .find(|o| o.supplier == "SUP-B")
+            .unwrap();
+        assert!((b.effective_unit_cost - 1.3).abs() < 1e-9);
+        assert_eq!(b.rank, 1);
+        assert!(matrix
+            .rationale(b)
+            .starts_with("Ranked 1 of 2 valid quotes at qty 100"));
+    }
+
+    #[test]
+    fn test_excludes_expired_and_weights_supplier_score() {
+        let mut expired = quote("SUP-X", &[(1, 0.50)], 5);
+        expired.valid_until = Some(date("2026-01-01"));
+        let fast = quote("SUP-A", &[(1, 1.00)], 5);
+        let slow_trusted = quote("SUP-B", &[(1, 1.00)], 20);
+        let scores = HashMap::from([("SUP-A".to_string(), 40.0), ("SUP-B".to_string(), 100.0)]);
+        let mut conv = Converter::new(CurrencyConfig::default(), None, date("2026-06-01"));
+
+        let matrix = evaluate(
+            &[expired, fast, slow_trusted],
+            &scores,
+            &mut conv,
+            &params(1, None),
+            &SourcingConfig {
+                weights: SourcingWeights {
+                    cost: 0.2,
+                    lead_time: 0.2,
+                    supplier: 0.6,
+                },
+                ..Default::default()
+            },
+        );
+        assert_eq!(matrix.excluded.len(), 1);
+        assert!(matrix.excluded[0].reason.starts_with("expired"));
+        // Same price: A = 0.2*100 + 0.2*100 + 0.6*40, B = 0.2*100 + 0.2*25 + 0.6*100
+        assert_eq!(matrix.best().unwrap().supplier, "SUP-B");
+        assert!((matrix.options[0].score - 85.0).abs() < 1e-9);
+        assert!((matrix.options[1].score - 64.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_supplier_score_is_not_rewarded() {
+        let scored = quote("SUP-A", &[(1, 1.00)], 10);
+        let unscored = quote("SUP-B", &[(1, 0.95)], 10);
+        let scores = HashMap::from([("SUP-A".to_string(), 70.0)]);
+        let mut conv = Converter::new(CurrencyConfig::default(), None, date("2026-06-01"));
+        let config = SourcingConfig {
+            weights: SourcingWeights {
+                cost: 0.5,
+                lead_time: 0.25,
+                supplier: 0.25,
+            },
+            ..Default::default()
+        };
+
+        let matrix = evaluate(
+            &[scored, unscored],
+            &scores,
+            &mut conv,
+            &params(1, None),
+            &config,
+        );
+        // A = 0.5*95 + 0.25*100 + 0.25*70, B = 0.5*100 + 0.25*100 + 0.25*0
+        assert_eq!(matrix.best().unwrap().supplier, "SUP-A");
+        assert!((matrix.options[0].score - 90.0).abs() < 1e-9);
+        assert!((matrix.options[1].score - 75.0).abs() < 1e-9);
+        assert_eq!(matrix.options[1].missing, vec!["supplier score"]);
+        assert!(matrix.options[0].missing.is_empty());
+    }
+}
@@ -0,0 +1,515 @@
+//! Supplier scorecards: quality, quote responsiveness, lead time and certifications
+//!
+//! Each supplier gets a 0-100 score per category and a weighted overall score.
+//! Categories without data (no quotes, no quoted lead times) are left out of
+//! the weighting rather than scored as zero. The overall score maps to a
+//! suggested approval status using configurable thresholds.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::core::ncr_trends::ncr_date;
+use crate::entities::component::Component;
+use crate::entities::lot::Lot;
+use crate::entities::ncr::{Ncr, NcrType};
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::entities::supplier::{ApprovalStatus, Supplier};
+
+/// Relative weight of each scorecard category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScorecardWeights {
+    pub quality: f64,
+    pub responsiveness: f64,
+    pub lead_time: f64,
+    pub certification: f64,
+}
+
+impl Default for ScorecardWeights {
+    fn default() -> Self {
+        Self {
+            quality: 0.4,
+            responsiveness: 0.2,
+            lead_time: 0.2,
+            certification: 0.2,
+        }
+    }
+}
+
+/// Scorecard configuration (`supplier_scorecard` in config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScorecardConfig {
+    pub weights: ScorecardWeights,
+
+    /// Only NCRs, quotes and lots from the last N days count (None = all time)
+    pub window_days: Option<u32>,
+
+    /// PPM at which the quality score reaches zero
+    pub ppm_limit: f64,
+
+    /// Quality points lost per NCR when no received quantity is known
+    pub ncr_penalty: f64,
+
+    /// Expected days from quote request to quote
+    pub target_response_days: u32,
+
+    /// Quoted lead time that still scores 100
+    pub target_lead_time_days: u32,
+
+    /// Certifications expiring within this many days are flagged
+    pub cert_warning_days: i64,
+
+    /// Minimum score for a suggested status of approved
+    pub approved_min: f64,
+
+    /// Minimum score for a suggested status of conditional
+    pub conditional_min: f64,
+}
+
+impl Default for ScorecardConfig {
+    fn default() -> Self {
+        Self {
+            weights: ScorecardWeights::default(),
+            window_days: Some(365),
+            ppm_limit: 10_000.0,
+            ncr_penalty: 10.0,
+            target_response_days: 14,
+            target_lead_time_days: 30,
+            cert_warning_days: 60,
+            approved_min: 80.0,
+            conditional_min: 60.0,
+        }
+    }
+}
+
+/// Quality performance
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityScore {
+    pub ncrs: usize,
+    pub defective_units: u64,
+    /// Units received, from lot material records (0 = unknown)
+    pub units_received: u64,
+    pub ppm: Option<f64>,
+    pub score: f64,
+}
+
+/// Quote responsiveness
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponsivenessScore {
+    pub quotes_requested: usize,
+    pub quotes_received: usize,
+    /// Pending quotes older than the target response time
+    pub quotes_overdue: usize,
+    pub response_rate: Option<f64>,
+    pub avg_response_days: Option<f64>,
+    pub score: Option<f64>,
+}
+
+/// Quoted lead times
+#[derive(Debug, Clone, Serialize)]
+pub struct LeadTimeScore {
+    pub quotes: usize,
+    pub avg_lead_time_days: Option<f64>,
+    pub max_lead_time_days: Option<u32>,
+    pub score: Option<f64>,
+}
+
+/// Certification validity
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificationScore {
+    pub total: usize,
+    pub valid: usize,
+    pub expiring: Vec<String>,
+    pub expired: Vec<String>,
+    pub score: f64,
+}
+
+/// Scorecard for one supplier
+#[derive(Debug, Clone, Serialize)]
+pub struct SupplierScorecard {
+    pub supplier: String,
+    pub name: String,
+    pub approval_status: Option<ApprovalStatus>,
+    pub quality: QualityScore,
+    pub responsiveness: ResponsivenessScore,
+    pub lead_time: LeadTimeScore,
+    pub certification: CertificationScore,
+    /// Weighted overall score (0-100)
+    pub score: f64,
+    pub suggested_status: ApprovalStatus,
+    /// Notable findings ("2 certification(s) expired", ...)
+    pub flags: Vec<String>,
+}
+
+/// Supplier IDs a component is sourced from
+fn component_supplier_ids(component: &Component) -> BTreeSet<&str> {
+    component
+        .suppliers
+        .iter()
+        .filter_map(|s| s.supplier_id.as_deref())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Component ID -> supplier ID, for components with exactly one linked supplier
+pub fn single_source_map(components: &[Component]) -> HashMap<String, String> {
+    components
+        .iter()
+        .filter_map(|c| {
+            let ids = component_supplier_ids(c);
+            (ids.len() == 1).then(|| {
+                (
+                    c.id.to_string(),
+                    ids.into_iter().next().unwrap().to_string(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Supplier responsible for an NCR: `links.supplier`, or for supplier NCRs the
+/// single source of the linked component
+pub fn ncr_supplier(ncr: &Ncr, single_source: &HashMap<String, String>) -> Option<String> {
+    if let Some(ref sup) = ncr.links.supplier {
+        return Some(sup.to_string());
+    }
+    if ncr.ncr_type != NcrType::Supplier {
+        return None;
+    }
+    let cmp = ncr.links.component.as_ref()?.to_string();
+    single_source.get(&cmp).cloned()
+}
+
+/// Units received per supplier, from purchased material recorded on lots
+///
+/// Only single-sourced components are attributed; material drawn from an
+/// in-house lot (`source_lot`) is not a receipt.
+pub fn units_received(
+    lots: &[Lot],
+    single_source: &HashMap<String, String>,
+    since: Option<NaiveDate>,
+) -> HashMap<String, u64> {
+    let mut received: HashMap<String, u64> = HashMap::new();
+    for lot in lots {
+        let date = lot.start_date.unwrap_or_else(|| lot.created.date_naive());
+        if since.is_some_and(|s| date < s) {
+            continue;
+        }
+        let materials = lot
+            .materials_used
+            .iter()
+            .chain(lot.serials.iter().flat_map(|u| u.materials.iter()));
+        for m in materials {
+            if m.source_lot.is_some() {
+                continue;
+            }
+            let Some(sup) = m.component.as_ref().and_then(|c| single_source.get(c)) else {
+                continue;
+            };
+            *received.entry(sup.clone()).or_default() += m.quantity.unwrap_or(1) as u64;
+        }
+    }
+    received
+}
+
+/// Shortest lead time on a quote (quote-level or any price break)
+fn quote_lead_time(quote: &Quote) -> Option<u32> {
+    quote
+        .lead_time_days
+        .into_iter()
+        .chain(quote.price_breaks.iter().filter_map(|b| b.lead_time_days))
+        .min()
+}
+
+fn suggest(score: f64, config: &ScorecardConfig) -> ApprovalStatus {
+    if score >= config.approved_min {
+        ApprovalStatus::Approved
+    } else if score >= config.conditional_min {
+        ApprovalStatus::Conditional
+    } else {
+        ApprovalStatus::Disqualified
+    }
+}
+
+/// Score one supplier
+pub fn score_supplier(
+    supplier: &Supplier,
+    ncrs: &[Ncr],
+    quotes: &[Quote],
+    units_received: u64,
+    single_source: &HashMap<String, String>,
+    config: &ScorecardConfig,
+    as_of: NaiveDate,
+) -> SupplierScorecard {
+    let sup_id = supplier.id.to_string();
+    let since = config.window_days.map(|d| as_of - Duration::days(d as i64));
+    let in_window = |date: NaiveDate| since.is_none_or(|s| date >= s) && date <= as_of;
+    let mut flags = Vec::new();
+
+    // Quality
+    let sup_ncrs: Vec<&Ncr> = ncrs
+        .iter()
+        .filter(|n| ncr_supplier(n, single_source).as_deref() == Some(sup_id.as_str()))
+        .filter(|n| in_window(ncr_date(n)))
+        .collect();
+    let defective_units: u64 = sup_ncrs
+        .iter()
+        .map(|n| {
+            n.affected_items
+                .as_ref()
+                .and_then(|a| a.quantity_affected)
+                .unwrap_or(1) as u64
+        })
+        .sum();
+    let ppm =
+        (units_received > 0).then(|| defective_units as f64 / units_received as f64 * 1_000_000.0);
+    let quality_score = match ppm {
+        Some(ppm) => (100.0 * (1.0 - ppm / config.ppm_limit)).max(0.0),
+        None => (100.0 - config.ncr_penalty * sup_ncrs.len() as f64).max(0.0),
+    };
+    if !sup_ncrs.is_empty() {
+        flags.push(format!("{} NCR(s)", sup_ncrs.len()));
+    }
+
+    // Responsiveness
+    let sup_quotes: Vec<&Quote> = quotes
+        .iter()
+        .filter(|q| q.supplier == sup_id)
+        .filter(|q| in_window(q.created.date_naive()))
+        .collect();
+    let received: Vec<&&Quote> = sup_quotes
+        .iter()
+        .filter(|q| q.quote_status != QuoteStatus::Pending)
+        .collect();
+    let overdue = sup_quotes
+        .iter()
+        .filter(|q| {
+            q.quote_status == QuoteStatus::Pending
+                && (as_of - q.created.date_naive()).num_days() > config.target_response_days as i64
+        })
+        .count();
+    let response_days: Vec<f64> = received
+        .iter()
+        .filter_map(|q| {
+            q.quote_date
+                .map(|d| (d - q.created.date_naive()).num_days())
+        })
+        .filter(|d| *d >= 0)
+        .map(|d| d as f64)
+        .collect();
+    let avg_response_days = (!response_days.is_empty())
+        .then(|| response_days.iter().sum::<f64>() / response_days.len() as f64);
+    let response_rate =
+        (!sup_quotes.is_empty()).then(|| received.len() as f64 / sup_quotes.len() as f64);
+    let responsiveness_score = response_rate.map(|rate| {
+        let speed = match avg_response_days {
+            Some(days) if days > config.target_response_days as f64 => {
+                config.target_response_days as f64 / days
+            }
+            _ => 1.0,
+        };
+        rate * speed * 100.0
+    });
+    if overdue > 0 {
+        flags.push(format!("{} quote(s) overdue", overdue));
+    }
+
+    // Lead time
+    let lead_times: Vec<u32> = sup_quotes
+        .iter()
+        .filter_map(|q| quote_lead_time(q))
+        .collect();
+    let avg_lead = (!lead_times.is_empty())
+        .then(|| lead_times.iter().sum::<u32>() as f64 / lead_times.len() as f64);
+    let lead_score = avg_lead.map(|avg| {
+        if avg <= config.target_lead_time_days as f64 {
+            100.0
+        } else {
+            100.0 * config.target_lead_time_days as f64 / avg
+        }
+    });
+
+    // Certifications
+    let warn_until = as_of + Duration::days(config.cert_warning_days);
+    let expired: Vec<String> = supplier
+        .certifications
+        .iter()
+        .filter(|c| c.expiry.is_some_and(|e| e < as_of))
+        .map(|c| c.name.clone())
+        .collect();
+    let expiring: Vec<String> = supplier
+        .certifications
+        .iter()
+        .filter(|c| c.expiry.is_some_and(|e| e >= as_of && e <= warn_until))
+        .map(|c| c.name.clone())
+        .collect();
+    let total = supplier.certifications.len();
+    let valid = total - expired.len();
+    let cert_score = if total == 0 {
+        flags.push("no certifications on file".to_string());
+        0.0
+    } else {
+        100.0 * valid as f64 / total as f64
+    };
+    if !expired.is_empty() {
+        flags.push(format!("{} certification(s) expired", expired.len()));
+    }
+    if !expiring.is_empty() {
+        flags.push(format!(
+            "{} certification(s) expiring within {} days",
+            expiring.len(),
+            config.cert_warning_days
+        ));
+    }
+
+    // Weighted score over the categories that have data
+    let w = &config.weights;
+    let parts = [
+        (Some(quality_score), w.quality),
+        (responsiveness_score, w.responsiveness),
+        (lead_score, w.lead_time),
+        (Some(cert_score), w.certification),
+    ];
+    let (sum, weight) = parts
+        .iter()
+        .filter_map(|(s, w)| s.map(|s| (s * w, *w)))
+        .fold((0.0, 0.0), |(a, b), (s, w)| (a + s, b + w));
+    let score = if weight > 0.0 { sum / weight } else { 0.0 };
+
+    SupplierScorecard {
+        supplier: sup_id,
+        name: supplier.name.clone(),
+        approval_status: supplier.approval_status,
+        quality: QualityScore {
+            ncrs: sup_ncrs.len(),
+            defective_units,
+            units_received,
+            ppm,
+            score: quality_score,
+        },
+        responsiveness: ResponsivenessScore {
+            quotes_requested: sup_quotes.len(),
+            quotes_received: received.len(),
+            quotes_overdue: overdue,
+            response_rate,
+            avg_response_days,
+            score: responsiveness_score,
+        },
+        lead_time: LeadTimeScore {
+            quotes: lead_times.len(),
+            avg_lead_time_days: avg_lead,
+            max_lead_time_days: lead_times.iter().max().copied(),
+            score: lead_score,
+        },
+        certification: CertificationScore {
+            total,
+            valid,
+            expiring,
+            expired,
+            score: cert_score,
+        },
+        score,
+        suggested_status: suggest(score, config),
+        flags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::identity::{EntityId, EntityPrefix};
+    use crate::entities::supplier::Certification;
+
+    fn ncr(yaml_extra: &str) -> Ncr {
+        serde_yml::from_str(&format!(
+            "id: {}\ntitle: Burr\ncreated: 2024-05-01T00:00:00Z\nauthor: qa\n{}",
+            EntityId::new(EntityPrefix::Ncr),
+            yaml_extra
+        ))
+        .unwrap()
+    }
+
+    fn as_of() -> NaiveDate {
+        "2024-06-01".parse().unwrap()
+    }
+
+    #[test]
+    fn test_quality_uses_ppm_when_receipts_known() {
+        let supplier = Supplier::new("Acme", "test");
+        let ncrs = vec![ncr(&format!(
+            "links:\n  supplier: {}\naffected_items:\n  quantity_affected: 5\n",
+            supplier.id
+        ))];
+        let config = ScorecardConfig::default();
+
+        let card = score_supplier(
+            &supplier,
+            &ncrs,
+            &[],
+            1000,
+            &HashMap::new(),
+            &config,
+            as_of(),
+        );
+        assert_eq!(card.quality.ncrs, 1);
+        assert_eq!(card.quality.ppm, Some(5000.0));
+        assert!((card.quality.score - 50.0).abs() < 1e-9);
+        // No quotes: only quality and certification are weighted
+        assert!(card.responsiveness.score.is_none());
+        assert!((card.score - (0.4 * 50.0) / 0.6).abs() < 1e-9);
+        assert_eq!(card.suggested_status, ApprovalStatus::Disqualified);
+
+        let card = score_supplier(&supplier, &ncrs, &[], 0, &HashMap::new(), &config, as_of());
+        assert!(card.quality.ppm.is_none());
+        assert!((card.quality.score - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_certifications_and_single_source_attribution() {
+        let mut supplier = Supplier::new("Acme", "test");
+        supplier.certifications = vec![
+            Certification {
+                name: "ISO 9001".into(),
+                expiry: Some("2025-01-01".parse().unwrap()),
+                certificate_number: None,
+            },
+            Certification {
+                name: "AS9100".into(),
+                expiry: Some("2024-01-01".parse().unwrap()),
+                certificate_number: None,
+            },
+        ];
+        let cmp = EntityId::new(EntityPrefix::Cmp);
+        let single: HashMap<String, String> = [(cmp.to_string(), supplier.id.to_string())].into();
+        let ncrs = vec![
+            ncr(&format!(
+                "ncr_type: supplier\nlinks:\n  component: {}\n",
+                cmp
+            )),
+            // Internal NCRs on the component are not the supplier's
+            ncr(&format!(
+                "ncr_type: internal\nlinks:\n  component: {}\n",
+                cmp
+            )),
+        ];
+
+        let card = score_supplier(
+            &supplier,
+            &ncrs,
+            &[],
+            0,
+            &single,
+            &ScorecardConfig::default(),
+            as_of(),
+        );
+        assert_eq!(card.quality.ncrs, 1);
+        assert_eq!(card.certification.valid, 1);
+        assert_eq!(card.certification.expired, vec!["AS9100".to_string()]);
+        assert!((card.certification.score - 50.0).abs() < 1e-9);
+        assert!(card
+            .flags
+            .contains(&"1 certification(s) expired".to_string()));
+    }
+}
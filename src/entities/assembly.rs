@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::entity::{Entity, Status};
 use crate::core::identity::{EntityId, EntityPrefix};
+use crate::entities::component::CoordinateSystem;
 use crate::entities::safety::{Asil, Dal, SwClass};
 
 /// BOM line item - references a component with quantity
@@ -23,6 +24,21 @@ pub struct BomItem {
     /// Assembly-specific notes (e.g., "Use thread locker")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+
+    /// Placement in the assembly frame - one for every instance, or one per instance
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placements: Vec<CoordinateSystem>,
+}
+
+/// Mass budget allocated to an assembly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MassBudget {
+    /// Maximum mass in kilograms
+    pub max_kg: f64,
+
+    /// Requirement the budget comes from (REQ-...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
 }
 
 /// Manufacturing configuration for product routing
@@ -116,6 +132,10 @@ pub struct Assembly {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub manufacturing: Option<ManufacturingConfig>,
 
+    /// Mass budget checked by `tdt asm mass`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mass_budget: Option<MassBudget>,
+
     /// Classification tags
     #[serde(default)]
     pub tags: Vec<String>,
@@ -188,6 +208,7 @@ impl Default for Assembly {
             dal: None,
             documents: Vec::new(),
             manufacturing: None,
+            mass_budget: None,
             tags: Vec::new(),
             status: Status::default(),
             links: AssemblyLinks::default(),
@@ -222,6 +243,7 @@ impl Assembly {
             quantity,
             reference_designators: Vec::new(),
             notes: None,
+            placements: Vec::new(),
         });
     }
 
@@ -320,6 +342,7 @@ mod tests {
                 "U4".to_string(),
             ],
             notes: Some("IC chips - handle with ESD precautions".to_string()),
+            placements: Vec::new(),
         };
 
         let yaml = serde_yml::to_string(&item).unwrap();
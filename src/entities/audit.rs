@@ -0,0 +1,467 @@
+//! AUD entity type - Internal / Supplier Audit
+//!
+//! Audits record a scheduled audit of the quality system (ISO 13485, ISO 9001)
+//! or of a supplier: scope, standard clauses, auditors and the findings.
+//! Major and minor findings are promoted into NCRs or CAPAs (with source type
+//! `audit`), and the finding records which entity it was promoted to.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::entity::{Entity, Status};
+use crate::core::identity::{EntityId, EntityPrefix};
+
+/// Audit type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditType {
+    /// Internal quality system audit
+    #[default]
+    Internal,
+    /// Audit of a supplier
+    Supplier,
+    /// Audit by a registrar, notified body or customer
+    External,
+}
+
+impl std::fmt::Display for AuditType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditType::Internal => write!(f, "internal"),
+            AuditType::Supplier => write!(f, "supplier"),
+            AuditType::External => write!(f, "external"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "internal" => Ok(AuditType::Internal),
+            "supplier" => Ok(AuditType::Supplier),
+            "external" => Ok(AuditType::External),
+            _ => Err(format!(
+                "Invalid audit type: {}. Use internal, supplier, or external",
+                s
+            )),
+        }
+    }
+}
+
+/// Audit workflow status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    #[default]
+    Planned,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl std::fmt::Display for AuditStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditStatus::Planned => write!(f, "planned"),
+            AuditStatus::InProgress => write!(f, "in_progress"),
+            AuditStatus::Completed => write!(f, "completed"),
+            AuditStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "planned" => Ok(AuditStatus::Planned),
+            "in_progress" | "inprogress" => Ok(AuditStatus::InProgress),
+            "completed" => Ok(AuditStatus::Completed),
+            "cancelled" | "canceled" => Ok(AuditStatus::Cancelled),
+            _ => Err(format!(
+                "Invalid audit status: {}. Use planned, in_progress, completed, or cancelled",
+                s
+            )),
+        }
+    }
+}
+
+/// Audit finding severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    /// Absence or breakdown of a required process
+    Major,
+    /// Isolated lapse against a requirement
+    #[default]
+    Minor,
+    /// Not a nonconformity, but could become one
+    Observation,
+    /// Opportunity for improvement
+    Opportunity,
+}
+
+impl std::fmt::Display for FindingSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FindingSeverity::Major => write!(f, "major"),
+            FindingSeverity::Minor => write!(f, "minor"),
+            FindingSeverity::Observation => write!(f, "observation"),
+            FindingSeverity::Opportunity => write!(f, "opportunity"),
+        }
+    }
+}
+
+impl std::str::FromStr for FindingSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "major" => Ok(FindingSeverity::Major),
+            "minor" => Ok(FindingSeverity::Minor),
+            "observation" => Ok(FindingSeverity::Observation),
+            "opportunity" | "ofi" => Ok(FindingSeverity::Opportunity),
+            _ => Err(format!(
+                "Invalid finding severity: {}. Use major, minor, observation, or opportunity",
+                s
+            )),
+        }
+    }
+}
+
+/// A single audit finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    /// Finding number within the audit (F1, F2, ...)
+    pub id: String,
+
+    /// Severity
+    #[serde(default)]
+    pub severity: FindingSeverity,
+
+    /// Standard clause the finding is raised against (e.g., "7.5.1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clause: Option<String>,
+
+    /// Statement of the finding
+    pub description: String,
+
+    /// Objective evidence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<String>,
+
+    /// NCR the finding was promoted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ncr: Option<EntityId>,
+
+    /// CAPA the finding was promoted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capa: Option<EntityId>,
+}
+
+impl AuditFinding {
+    /// Whether this is a nonconformity (major or minor)
+    pub fn is_nonconformity(&self) -> bool {
+        matches!(
+            self.severity,
+            FindingSeverity::Major | FindingSeverity::Minor
+        )
+    }
+
+    /// Whether this is a nonconformity not yet promoted to an NCR or CAPA
+    pub fn needs_action(&self) -> bool {
+        self.is_nonconformity() && self.ncr.is_none() && self.capa.is_none()
+    }
+}
+
+/// Links to other entities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLinks {
+    /// Supplier audited (supplier audits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<EntityId>,
+
+    /// Processes in scope
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub processes: Vec<EntityId>,
+
+    /// NCRs raised from findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ncrs: Vec<EntityId>,
+
+    /// CAPAs raised from findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capas: Vec<EntityId>,
+}
+
+/// An audit entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Audit {
+    /// Unique identifier (AUD-xxx)
+    pub id: EntityId,
+
+    /// Short descriptive title
+    pub title: String,
+
+    /// User-defined audit number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_number: Option<String>,
+
+    /// Audit type
+    #[serde(default)]
+    pub audit_type: AuditType,
+
+    /// Standard audited against (e.g., "ISO 13485:2016")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard: Option<String>,
+
+    /// Standard clauses in scope
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clauses: Vec<String>,
+
+    /// Scope of the audit (areas, processes, sites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// Date the audit is scheduled for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_date: Option<NaiveDate>,
+
+    /// Date the audit was completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_date: Option<NaiveDate>,
+
+    /// Months between audits of this scope (for the next audit's schedule)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_months: Option<u32>,
+
+    /// Lead auditor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lead_auditor: Option<String>,
+
+    /// Other auditors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auditors: Vec<String>,
+
+    /// People or functions audited
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auditees: Vec<String>,
+
+    /// Findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<AuditFinding>,
+
+    /// Summary of the audit outcome
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// Workflow status
+    #[serde(default)]
+    pub audit_status: AuditStatus,
+
+    /// Links to other entities
+    #[serde(default)]
+    pub links: AuditLinks,
+
+    /// Tags for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Document status
+    #[serde(default)]
+    pub status: Status,
+
+    /// Creation timestamp
+    pub created: DateTime<Utc>,
+
+    /// Author
+    pub author: String,
+
+    /// Entity revision number
+    #[serde(default = "default_revision")]
+    pub entity_revision: u32,
+}
+
+fn default_revision() -> u32 {
+    1
+}
+
+impl Entity for Audit {
+    const PREFIX: &'static str = "AUD";
+
+    fn id(&self) -> &EntityId {
+        &self.id
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn status(&self) -> &str {
+        match self.status {
+            Status::Draft => "draft",
+            Status::Review => "review",
+            Status::Approved => "approved",
+            Status::Released => "released",
+            Status::Obsolete => "obsolete",
+        }
+    }
+
+    fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    fn author(&self) -> &str {
+        &self.author
+    }
+}
+
+impl Audit {
+    /// Create a new audit
+    pub fn new(title: String, audit_type: AuditType, author: String) -> Self {
+        Self {
+            id: EntityId::new(EntityPrefix::Aud),
+            title,
+            audit_number: None,
+            audit_type,
+            standard: None,
+            clauses: Vec::new(),
+            scope: None,
+            scheduled_date: None,
+            completed_date: None,
+            interval_months: None,
+            lead_auditor: None,
+            auditors: Vec::new(),
+            auditees: Vec::new(),
+            findings: Vec::new(),
+            summary: None,
+            audit_status: AuditStatus::default(),
+            links: AuditLinks::default(),
+            tags: Vec::new(),
+            status: Status::Draft,
+            created: Utc::now(),
+            author,
+            entity_revision: 1,
+        }
+    }
+
+    /// Whether the audit is still to be performed or finished
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.audit_status,
+            AuditStatus::Planned | AuditStatus::InProgress
+        )
+    }
+
+    /// Whether a planned audit is past its scheduled date
+    pub fn is_overdue(&self, as_of: NaiveDate) -> bool {
+        self.audit_status == AuditStatus::Planned && self.scheduled_date.is_some_and(|d| d < as_of)
+    }
+
+    /// Date the next audit of this scope is due (completion date plus interval)
+    pub fn next_due(&self) -> Option<NaiveDate> {
+        let completed = self.completed_date?;
+        let months = self.interval_months?;
+        completed.checked_add_months(chrono::Months::new(months))
+    }
+
+    /// Next free finding number (F1, F2, ...)
+    pub fn next_finding_id(&self) -> String {
+        let max = self
+            .findings
+            .iter()
+            .filter_map(|f| f.id.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()))
+            .max()
+            .unwrap_or(0);
+        format!("F{}", max + 1)
+    }
+
+    /// Find a finding by its number (case-insensitive)
+    pub fn finding_mut(&mut self, id: &str) -> Option<&mut AuditFinding> {
+        self.findings
+            .iter_mut()
+            .find(|f| f.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Count findings of a severity
+    pub fn count_findings(&self, severity: FindingSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_creation() {
+        let audit = Audit::new(
+            "Design controls audit".to_string(),
+            AuditType::Internal,
+            "test".to_string(),
+        );
+        assert!(audit.id.to_string().starts_with("AUD-"));
+        assert_eq!(audit.audit_status, AuditStatus::Planned);
+        assert_eq!(audit.next_finding_id(), "F1");
+        assert!(audit.next_due().is_none());
+    }
+
+    #[test]
+    fn test_schedule_dates() {
+        let mut audit = Audit::new(
+            "Purchasing audit".to_string(),
+            AuditType::Internal,
+            "test".to_string(),
+        );
+        audit.scheduled_date = Some("2024-03-01".parse().unwrap());
+        assert!(audit.is_overdue("2024-03-02".parse().unwrap()));
+        assert!(!audit.is_overdue("2024-03-01".parse().unwrap()));
+
+        audit.audit_status = AuditStatus::Completed;
+        audit.completed_date = Some("2024-03-05".parse().unwrap());
+        audit.interval_months = Some(12);
+        assert!(!audit.is_overdue("2024-04-01".parse().unwrap()));
+        assert_eq!(audit.next_due(), Some("2025-03-05".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_audit_roundtrip_and_findings() {
+        let yaml = r#"
+id: AUD-01HC2JB7SMQX7RS1Y0GFKBHPTD
+title: Production controls
+audit_type: internal
+standard: ISO 13485:2016
+clauses: ["7.5.1", "7.5.2"]
+scheduled_date: 2024-05-10
+findings:
+  - id: F1
+    severity: major
+    clause: "7.5.1"
+    description: Work instructions not at point of use
+  - id: F3
+    severity: opportunity
+    description: Consider visual aids
+created: 2024-01-15T10:30:00Z
+author: test
+"#;
+        let mut audit: Audit = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(audit.clauses.len(), 2);
+        assert_eq!(audit.count_findings(FindingSeverity::Major), 1);
+        assert_eq!(audit.next_finding_id(), "F4");
+        assert!(audit.findings[0].needs_action());
+        assert!(!audit.findings[1].needs_action());
+
+        audit.finding_mut("f1").unwrap().capa = Some(EntityId::new(EntityPrefix::Capa));
+        assert!(!audit.findings[0].needs_action());
+        assert_eq!(
+            "ofi".parse::<FindingSeverity>().unwrap(),
+            FindingSeverity::Opportunity
+        );
+    }
+}
@@ -118,6 +118,23 @@ impl std::fmt::Display for RcaMethod {
     }
 }
 
+impl std::str::FromStr for RcaMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "five_why" | "5_why" | "5why" => Ok(RcaMethod::FiveWhy),
+            "fishbone" | "ishikawa" => Ok(RcaMethod::Fishbone),
+            "fault_tree" => Ok(RcaMethod::FaultTree),
+            "eight_d" | "8d" => Ok(RcaMethod::EightD),
+            _ => Err(format!(
+                "Invalid RCA method: {}. Use five_why, fishbone, fault_tree, or eight_d",
+                s
+            )),
+        }
+    }
+}
+
 /// Root cause analysis
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RootCauseAnalysis {
@@ -132,6 +149,180 @@ pub struct RootCauseAnalysis {
     /// Contributing factors
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub contributing_factors: Vec<String>,
+
+    /// 5-Why chain, from the problem down to the root cause
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub five_whys: Vec<WhyStep>,
+
+    /// Fishbone (Ishikawa) causes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fishbone: Vec<FishboneCause>,
+}
+
+/// One level of a 5-Why chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WhyStep {
+    /// Question asked at this level (defaults to "Why?")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub why: Option<String>,
+
+    /// Answer (the cause at this level)
+    pub because: String,
+
+    /// Evidence supporting the answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<String>,
+
+    /// Risks implicated by this finding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risks: Vec<EntityId>,
+
+    /// Processes implicated by this finding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub processes: Vec<EntityId>,
+}
+
+/// Fishbone (Ishikawa) cause category - the 6 Ms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FishboneCategory {
+    Man,
+    Machine,
+    Method,
+    Material,
+    Measurement,
+    Environment,
+}
+
+impl FishboneCategory {
+    pub const ALL: [FishboneCategory; 6] = [
+        FishboneCategory::Man,
+        FishboneCategory::Machine,
+        FishboneCategory::Method,
+        FishboneCategory::Material,
+        FishboneCategory::Measurement,
+        FishboneCategory::Environment,
+    ];
+}
+
+impl std::fmt::Display for FishboneCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FishboneCategory::Man => write!(f, "man"),
+            FishboneCategory::Machine => write!(f, "machine"),
+            FishboneCategory::Method => write!(f, "method"),
+            FishboneCategory::Material => write!(f, "material"),
+            FishboneCategory::Measurement => write!(f, "measurement"),
+            FishboneCategory::Environment => write!(f, "environment"),
+        }
+    }
+}
+
+impl std::str::FromStr for FishboneCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "man" | "people" | "manpower" => Ok(FishboneCategory::Man),
+            "machine" | "equipment" => Ok(FishboneCategory::Machine),
+            "method" => Ok(FishboneCategory::Method),
+            "material" | "materials" => Ok(FishboneCategory::Material),
+            "measurement" => Ok(FishboneCategory::Measurement),
+            "environment" | "mother_nature" => Ok(FishboneCategory::Environment),
+            _ => Err(format!(
+                "Invalid fishbone category: {}. Use man, machine, method, material, measurement, or environment",
+                s
+            )),
+        }
+    }
+}
+
+/// A cause on a fishbone diagram
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FishboneCause {
+    /// Category (bone) the cause belongs to
+    pub category: FishboneCategory,
+
+    /// Cause description
+    pub cause: String,
+
+    /// Sub-causes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_causes: Vec<String>,
+
+    /// Whether the cause was confirmed to contribute to the problem
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub confirmed: bool,
+
+    /// Risks implicated by this finding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risks: Vec<EntityId>,
+
+    /// Processes implicated by this finding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub processes: Vec<EntityId>,
+}
+
+/// An RCA finding that implicates a risk or process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RcaFinding {
+    /// Where the finding is recorded, e.g. "why 3" or "fishbone machine"
+    pub source: String,
+    /// The finding text
+    pub text: String,
+}
+
+impl RootCauseAnalysis {
+    /// Findings implicating each linked entity, in order of first appearance
+    ///
+    /// The selectors pick the risk or process IDs from each finding.
+    fn implicated<'a>(
+        &'a self,
+        select_why: impl Fn(&'a WhyStep) -> &'a [EntityId],
+        select_cause: impl Fn(&'a FishboneCause) -> &'a [EntityId],
+    ) -> Vec<(&'a EntityId, Vec<RcaFinding>)> {
+        let mut out: Vec<(&EntityId, Vec<RcaFinding>)> = Vec::new();
+        let mut add = |id: &'a EntityId, finding: RcaFinding| match out
+            .iter_mut()
+            .find(|(existing, _)| *existing == id)
+        {
+            Some((_, findings)) => findings.push(finding),
+            None => out.push((id, vec![finding])),
+        };
+        for (i, step) in self.five_whys.iter().enumerate() {
+            for id in select_why(step) {
+                add(
+                    id,
+                    RcaFinding {
+                        source: format!("why {}", i + 1),
+                        text: step.because.clone(),
+                    },
+                );
+            }
+        }
+        for cause in &self.fishbone {
+            for id in select_cause(cause) {
+                add(
+                    id,
+                    RcaFinding {
+                        source: format!("fishbone {}", cause.category),
+                        text: cause.cause.clone(),
+                    },
+                );
+            }
+        }
+        out
+    }
+
+    /// Risks implicated by 5-Why or fishbone findings
+    pub fn implicated_risks(&self) -> Vec<(&EntityId, Vec<RcaFinding>)> {
+        self.implicated(|w| &w.risks, |c| &c.risks)
+    }
+
+    /// Processes implicated by 5-Why or fishbone findings
+    pub fn implicated_processes(&self) -> Vec<(&EntityId, Vec<RcaFinding>)> {
+        self.implicated(|w| &w.processes, |c| &c.processes)
+    }
 }
 
 /// Action item status
@@ -495,6 +686,61 @@ mod tests {
         assert_eq!(capa.author(), "test_author");
     }
 
+    #[test]
+    fn test_structured_rca_roundtrip_and_implicated_risks() {
+        let risk = EntityId::new(crate::core::EntityPrefix::Risk);
+        let process = EntityId::new(crate::core::EntityPrefix::Proc);
+        let mut capa = Capa::new(
+            "Bore oversize".to_string(),
+            CapaType::Corrective,
+            "t".into(),
+        );
+        capa.root_cause_analysis = Some(RootCauseAnalysis {
+            method: RcaMethod::FiveWhy,
+            five_whys: vec![
+                WhyStep {
+                    because: "Boring bar deflected".to_string(),
+                    ..Default::default()
+                },
+                WhyStep {
+                    because: "Tool wear not monitored".to_string(),
+                    risks: vec![risk.clone()],
+                    processes: vec![process.clone()],
+                    ..Default::default()
+                },
+            ],
+            fishbone: vec![FishboneCause {
+                category: FishboneCategory::Machine,
+                cause: "Spindle runout".to_string(),
+                sub_causes: vec!["Worn bearings".to_string()],
+                confirmed: true,
+                risks: vec![risk.clone()],
+                processes: Vec::new(),
+            }],
+            ..Default::default()
+        });
+
+        let yaml = serde_yml::to_string(&capa).unwrap();
+        let parsed: Capa = serde_yml::from_str(&yaml).unwrap();
+        let rca = parsed.root_cause_analysis.unwrap();
+        assert_eq!(rca.five_whys.len(), 2);
+        assert_eq!(rca.fishbone[0].category, FishboneCategory::Machine);
+        assert!(rca.fishbone[0].confirmed);
+
+        let risks = rca.implicated_risks();
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].0, &risk);
+        assert_eq!(risks[0].1[0].source, "why 2");
+        assert_eq!(risks[0].1[1].source, "fishbone machine");
+        assert_eq!(rca.implicated_processes()[0].1.len(), 1);
+
+        assert_eq!(
+            "Ishikawa".parse::<RcaMethod>().unwrap(),
+            RcaMethod::Fishbone
+        );
+        assert!("weather".parse::<FishboneCategory>().is_err());
+    }
+
     #[test]
     fn test_capa_type_from_str() {
         assert_eq!(
@@ -0,0 +1,426 @@
+//! CMPL entity type - Customer Complaint / Field Return
+//!
+//! Complaints capture post-market feedback: customer complaints, returned
+//! units (RMA) and field failures. Key distinction from NCR: an NCR records
+//! nonconforming product found before or at delivery, a complaint records
+//! how product performs in the field. Confirmed failures linked to risks
+//! feed back into FMEA occurrence estimates.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::entity::{Entity, Status};
+use crate::core::identity::{EntityId, EntityPrefix};
+
+/// Complaint type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplaintType {
+    /// Customer complaint (no product returned)
+    #[default]
+    Complaint,
+    /// Product returned for analysis (RMA)
+    Return,
+    /// Failure reported from the field or service
+    FieldFailure,
+}
+
+impl std::fmt::Display for ComplaintType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComplaintType::Complaint => write!(f, "complaint"),
+            ComplaintType::Return => write!(f, "return"),
+            ComplaintType::FieldFailure => write!(f, "field_failure"),
+        }
+    }
+}
+
+impl std::str::FromStr for ComplaintType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "complaint" => Ok(ComplaintType::Complaint),
+            "return" | "rma" => Ok(ComplaintType::Return),
+            "field_failure" | "field" => Ok(ComplaintType::FieldFailure),
+            _ => Err(format!(
+                "Invalid complaint type: {}. Use complaint, return, or field_failure",
+                s
+            )),
+        }
+    }
+}
+
+/// Complaint workflow status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplaintStatus {
+    #[default]
+    Received,
+    Investigating,
+    Resolved,
+    Closed,
+}
+
+impl std::fmt::Display for ComplaintStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComplaintStatus::Received => write!(f, "received"),
+            ComplaintStatus::Investigating => write!(f, "investigating"),
+            ComplaintStatus::Resolved => write!(f, "resolved"),
+            ComplaintStatus::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+impl std::str::FromStr for ComplaintStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "received" => Ok(ComplaintStatus::Received),
+            "investigating" => Ok(ComplaintStatus::Investigating),
+            "resolved" => Ok(ComplaintStatus::Resolved),
+            "closed" => Ok(ComplaintStatus::Closed),
+            _ => Err(format!(
+                "Invalid complaint status: {}. Use received, investigating, resolved, or closed",
+                s
+            )),
+        }
+    }
+}
+
+/// Failure reporting details
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailureReport {
+    /// Failure mode as observed (ideally matching an FMEA failure mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_mode: Option<String>,
+
+    /// Date the failure occurred
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_date: Option<NaiveDate>,
+
+    /// Operating hours or cycles at failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operating_hours: Option<f64>,
+
+    /// Use environment / conditions at failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+
+    /// Whether anyone was injured
+    #[serde(default)]
+    pub injury: bool,
+
+    /// Failure confirmed by analysis (null = not yet analyzed, false = no fault found)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmed: Option<bool>,
+
+    /// Analysis findings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<String>,
+}
+
+/// Regulatory reportability assessment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Reportability {
+    /// Reportable to a regulator (null = not yet assessed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reportable: Option<bool>,
+
+    /// Regulation assessed against (e.g., "21 CFR 803", "EU MDR Art. 87")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulation: Option<String>,
+
+    /// Rationale for the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+
+    /// Who assessed reportability
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assessed_by: Option<String>,
+
+    /// Date of the assessment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assessed_date: Option<NaiveDate>,
+
+    /// Regulatory report due date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_due: Option<NaiveDate>,
+
+    /// Date the report was submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_date: Option<NaiveDate>,
+
+    /// Regulator's report reference number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_reference: Option<String>,
+}
+
+/// Links to other entities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplaintLinks {
+    /// Production lots of the affected units
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lots: Vec<EntityId>,
+
+    /// Components implicated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<EntityId>,
+
+    /// Assemblies / products implicated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assemblies: Vec<EntityId>,
+
+    /// FMEA risks whose failure mode this complaint exhibits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risks: Vec<EntityId>,
+
+    /// NCRs raised from the investigation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ncrs: Vec<EntityId>,
+
+    /// CAPA opened for this complaint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capa: Option<EntityId>,
+}
+
+/// A customer complaint / field return entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Complaint {
+    /// Unique identifier (CMPL-xxx)
+    pub id: EntityId,
+
+    /// Short descriptive title
+    pub title: String,
+
+    /// User-defined complaint number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complaint_number: Option<String>,
+
+    /// Return merchandise authorization number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rma_number: Option<String>,
+
+    /// Complaint type
+    #[serde(default)]
+    pub complaint_type: ComplaintType,
+
+    /// Customer or reporter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+
+    /// Date the complaint was received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received_date: Option<NaiveDate>,
+
+    /// Description as reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Number of units involved
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+
+    /// Serial numbers of the units involved
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serials: Vec<String>,
+
+    /// Failure reporting details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<FailureReport>,
+
+    /// Regulatory reportability assessment
+    #[serde(default)]
+    pub reportability: Reportability,
+
+    /// Workflow status
+    #[serde(default)]
+    pub complaint_status: ComplaintStatus,
+
+    /// Links to other entities
+    #[serde(default)]
+    pub links: ComplaintLinks,
+
+    /// Tags for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Document status
+    #[serde(default)]
+    pub status: Status,
+
+    /// Creation timestamp
+    pub created: DateTime<Utc>,
+
+    /// Author
+    pub author: String,
+
+    /// Entity revision number
+    #[serde(default = "default_revision")]
+    pub entity_revision: u32,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+fn default_revision() -> u32 {
+    1
+}
+
+impl Entity for Complaint {
+    const PREFIX: &'static str = "CMPL";
+
+    fn id(&self) -> &EntityId {
+        &self.id
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn status(&self) -> &str {
+        match self.status {
+            Status::Draft => "draft",
+            Status::Review => "review",
+            Status::Approved => "approved",
+            Status::Released => "released",
+            Status::Obsolete => "obsolete",
+        }
+    }
+
+    fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    fn author(&self) -> &str {
+        &self.author
+    }
+}
+
+impl Complaint {
+    /// Create a new complaint
+    pub fn new(title: String, complaint_type: ComplaintType, author: String) -> Self {
+        Self {
+            id: EntityId::new(EntityPrefix::Cmpl),
+            title,
+            complaint_number: None,
+            rma_number: None,
+            complaint_type,
+            customer: None,
+            received_date: None,
+            description: None,
+            quantity: 1,
+            serials: Vec::new(),
+            failure: None,
+            reportability: Reportability::default(),
+            complaint_status: ComplaintStatus::default(),
+            links: ComplaintLinks::default(),
+            tags: Vec::new(),
+            status: Status::Draft,
+            created: Utc::now(),
+            author,
+            entity_revision: 1,
+        }
+    }
+
+    /// Date the complaint was received (falls back to creation date)
+    pub fn date(&self) -> NaiveDate {
+        self.received_date
+            .unwrap_or_else(|| self.created.date_naive())
+    }
+
+    /// Whether this complaint counts as a field failure
+    ///
+    /// Requires a reported failure mode. Complaints whose analysis found no
+    /// fault (`failure.confirmed: false`) are excluded; unanalyzed failures
+    /// count until disproven.
+    pub fn counts_as_failure(&self) -> bool {
+        self.failure
+            .as_ref()
+            .is_some_and(|f| f.failure_mode.is_some() && f.confirmed != Some(false))
+    }
+
+    /// Whether reportability has not been assessed yet
+    pub fn needs_assessment(&self) -> bool {
+        self.reportability.reportable.is_none()
+    }
+
+    /// Whether a required regulatory report is past due
+    pub fn report_overdue(&self, as_of: NaiveDate) -> bool {
+        self.reportability.reportable == Some(true)
+            && self.reportability.submitted_date.is_none()
+            && self.reportability.report_due.is_some_and(|d| d < as_of)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complaint_creation() {
+        let cmpl = Complaint::new(
+            "Display flickers".to_string(),
+            ComplaintType::Return,
+            "test".to_string(),
+        );
+        assert!(cmpl.id.to_string().starts_with("CMPL-"));
+        assert_eq!(cmpl.quantity, 1);
+        assert_eq!(cmpl.complaint_status, ComplaintStatus::Received);
+        assert!(cmpl.needs_assessment());
+        assert!(!cmpl.counts_as_failure());
+    }
+
+    #[test]
+    fn test_failure_and_reportability() {
+        let mut cmpl = Complaint::new(
+            "Battery swelling".to_string(),
+            ComplaintType::FieldFailure,
+            "test".to_string(),
+        );
+        cmpl.failure = Some(FailureReport::default());
+        assert!(!cmpl.counts_as_failure());
+        cmpl.failure.as_mut().unwrap().failure_mode = Some("Swelling".to_string());
+        assert!(cmpl.counts_as_failure());
+        cmpl.failure.as_mut().unwrap().confirmed = Some(false);
+        assert!(!cmpl.counts_as_failure());
+
+        cmpl.reportability.reportable = Some(true);
+        cmpl.reportability.report_due = Some("2024-03-01".parse().unwrap());
+        assert!(cmpl.report_overdue("2024-03-02".parse().unwrap()));
+        cmpl.reportability.submitted_date = Some("2024-02-28".parse().unwrap());
+        assert!(!cmpl.report_overdue("2024-03-02".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_complaint_roundtrip() {
+        let yaml = r#"
+id: CMPL-01HC2JB7SMQX7RS1Y0GFKBHPTD
+title: Unit returned dead
+rma_number: RMA-1042
+complaint_type: return
+quantity: 2
+serials: [SN-001, SN-002]
+failure:
+  failure_mode: No power
+  confirmed: true
+reportability:
+  reportable: false
+  rationale: No injury, no malfunction likely to cause harm
+created: 2024-01-15T10:30:00Z
+author: test
+"#;
+        let cmpl: Complaint = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(cmpl.complaint_type, ComplaintType::Return);
+        assert_eq!(cmpl.serials.len(), 2);
+        assert_eq!(cmpl.reportability.reportable, Some(false));
+        assert!(cmpl.counts_as_failure());
+        assert_eq!(
+            "rma".parse::<ComplaintType>().unwrap(),
+            ComplaintType::Return
+        );
+    }
+}
@@ -1,11 +1,12 @@
 //! Component entity type - Individual parts (purchased or manufactured)
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::core::entity::{Entity, Status};
 use crate::core::identity::EntityId;
 use crate::entities::assembly::ManufacturingConfig;
+use crate::entities::quote::Currency;
 use crate::entities::safety::{Asil, Dal, SwClass};
 
 /// Make or buy decision
@@ -85,6 +86,332 @@ impl std::str::FromStr for ComponentCategory {
     }
 }
 
+/// Lifecycle state of a part or manufacturer part number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
+pub enum LifecycleStatus {
+    #[default]
+    Active,
+    /// Not recommended for new designs
+    Nrnd,
+    /// Last-time-buy announced
+    LastTimeBuy,
+    Obsolete,
+}
+
+impl LifecycleStatus {
+    /// Last-time-buy or obsolete
+    pub fn is_end_of_life(&self) -> bool {
+        matches!(
+            self,
+            LifecycleStatus::LastTimeBuy | LifecycleStatus::Obsolete
+        )
+    }
+}
+
+impl std::fmt::Display for LifecycleStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LifecycleStatus::Active => write!(f, "active"),
+            LifecycleStatus::Nrnd => write!(f, "nrnd"),
+            LifecycleStatus::LastTimeBuy => write!(f, "last_time_buy"),
+            LifecycleStatus::Obsolete => write!(f, "obsolete"),
+        }
+    }
+}
+
+impl std::str::FromStr for LifecycleStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "active" => Ok(LifecycleStatus::Active),
+            "nrnd" => Ok(LifecycleStatus::Nrnd),
+            "last_time_buy" | "ltb" => Ok(LifecycleStatus::LastTimeBuy),
+            "obsolete" | "eol" => Ok(LifecycleStatus::Obsolete),
+            _ => Err(format!(
+                "Invalid lifecycle status: {}. Use active, nrnd, last_time_buy, or obsolete",
+                s
+            )),
+        }
+    }
+}
+
+/// Lifecycle status with its key dates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lifecycle {
+    /// Current lifecycle state
+    pub status: LifecycleStatus,
+
+    /// Last date orders are accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_time_buy: Option<NaiveDate>,
+
+    /// Date the part is (or becomes) obsolete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_of_life: Option<NaiveDate>,
+
+    /// Source of the status (PCN number, distributor notice, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
+}
+
+/// Approval state of an AML entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[derive(Default)]
+pub enum AmlApproval {
+    Approved,
+    #[default]
+    Pending,
+    Disqualified,
+}
+
+impl std::fmt::Display for AmlApproval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmlApproval::Approved => write!(f, "approved"),
+            AmlApproval::Pending => write!(f, "pending"),
+            AmlApproval::Disqualified => write!(f, "disqualified"),
+        }
+    }
+}
+
+impl std::str::FromStr for AmlApproval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "approved" => Ok(AmlApproval::Approved),
+            "pending" => Ok(AmlApproval::Pending),
+            "disqualified" => Ok(AmlApproval::Disqualified),
+            _ => Err(format!(
+                "Invalid AML approval: {}. Use approved, pending, or disqualified",
+                s
+            )),
+        }
+    }
+}
+
+/// Approved manufacturer list entry - one manufacturer part number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmlEntry {
+    /// Manufacturer name
+    pub manufacturer: String,
+
+    /// Manufacturer part number
+    pub mpn: String,
+
+    /// Approval state for use on this part
+    #[serde(default)]
+    pub approval: AmlApproval,
+
+    /// Lifecycle of this manufacturer part number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<Lifecycle>,
+}
+
+impl AmlEntry {
+    /// Lifecycle status, active when not recorded
+    pub fn lifecycle_status(&self) -> LifecycleStatus {
+        self.lifecycle
+            .as_ref()
+            .map(|l| l.status)
+            .unwrap_or_default()
+    }
+}
+
+/// Regulation a material declaration is made against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Regulation {
+    /// EU RoHS (2011/65/EU and amendments)
+    Rohs,
+    /// EU REACH SVHC candidate list
+    Reach,
+    /// California Proposition 65
+    Prop65,
+    /// Conflict minerals (3TG: tin, tantalum, tungsten, gold)
+    ConflictMinerals,
+}
+
+impl Regulation {
+    /// All regulations, in report order
+    pub const ALL: [Regulation; 4] = [
+        Regulation::Rohs,
+        Regulation::Reach,
+        Regulation::Prop65,
+        Regulation::ConflictMinerals,
+    ];
+
+    /// Human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Regulation::Rohs => "RoHS",
+            Regulation::Reach => "REACH",
+            Regulation::Prop65 => "Prop 65",
+            Regulation::ConflictMinerals => "Conflict Minerals",
+        }
+    }
+}
+
+impl std::fmt::Display for Regulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Regulation::Rohs => write!(f, "rohs"),
+            Regulation::Reach => write!(f, "reach"),
+            Regulation::Prop65 => write!(f, "prop65"),
+            Regulation::ConflictMinerals => write!(f, "conflict_minerals"),
+        }
+    }
+}
+
+impl std::str::FromStr for Regulation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "rohs" => Ok(Regulation::Rohs),
+            "reach" => Ok(Regulation::Reach),
+            "prop65" | "prop_65" => Ok(Regulation::Prop65),
+            "conflict_minerals" | "3tg" | "cmrt" => Ok(Regulation::ConflictMinerals),
+            _ => Err(format!(
+                "Invalid regulation: {}. Use rohs, reach, prop65, or conflict_minerals",
+                s
+            )),
+        }
+    }
+}
+
+/// Declared compliance state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceStatus {
+    Compliant,
+    /// Compliant by way of listed exemptions
+    Exempt,
+    NonCompliant,
+}
+
+impl std::fmt::Display for ComplianceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComplianceStatus::Compliant => write!(f, "compliant"),
+            ComplianceStatus::Exempt => write!(f, "exempt"),
+            ComplianceStatus::NonCompliant => write!(f, "non_compliant"),
+        }
+    }
+}
+
+impl std::str::FromStr for ComplianceStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "compliant" => Ok(ComplianceStatus::Compliant),
+            "exempt" => Ok(ComplianceStatus::Exempt),
+            "non_compliant" | "noncompliant" => Ok(ComplianceStatus::NonCompliant),
+            _ => Err(format!(
+                "Invalid compliance status: {}. Use compliant, exempt, or non_compliant",
+                s
+            )),
+        }
+    }
+}
+
+/// A declared substance (REACH SVHC, Prop 65 listed chemical, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Substance {
+    /// Substance name
+    pub name: String,
+
+    /// CAS registry number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cas: Option<String>,
+
+    /// Concentration in the article, percent by weight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f64>,
+}
+
+/// Material compliance declaration for one regulation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceDeclaration {
+    /// Regulation declared against
+    pub regulation: Regulation,
+
+    /// Declared state
+    pub status: ComplianceStatus,
+
+    /// Exemptions claimed (e.g., RoHS Annex III "7(a)")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exemptions: Vec<String>,
+
+    /// Declared substances
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub substances: Vec<Substance>,
+
+    /// Supporting documents (certificates, full material declarations)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<String>,
+
+    /// Who issued the declaration (supplier, lab)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_by: Option<String>,
+
+    /// Date of the declaration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+
+    /// Date the declaration must be renewed by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl ComplianceDeclaration {
+    /// Whether the declaration has lapsed as of a date
+    pub fn is_expired(&self, as_of: NaiveDate) -> bool {
+        self.expires.is_some_and(|d| d < as_of)
+    }
+}
+
+/// Record of why the selected quote was chosen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteSelection {
+    /// Quote selected (QUOT-...)
+    pub quote: String,
+
+    /// Who made the selection
+    pub selected_by: String,
+
+    /// Date of the selection
+    pub date: NaiveDate,
+
+    /// Selection rationale
+    pub rationale: String,
+
+    /// Quantity the quotes were compared at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qty: Option<u32>,
+
+    /// Program volume NRE/tooling was amortized over
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<u32>,
+
+    /// Effective unit cost at the comparison quantity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_unit_cost: Option<f64>,
+
+    /// Currency of the effective unit cost
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+
+    /// Decision matrix score (0-100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
 /// Supplier information for a component
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ComponentSupplier {
@@ -156,6 +483,36 @@ impl Default for CoordinateSystem {
     }
 }
 
+// ===== Mass Properties =====
+
+/// Inertia tensor in kg·mm²
+///
+/// Products of inertia are tensor elements (Ixy = -∫xy dm); negate values
+/// from CAD tools that report ∫xy dm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InertiaTensor {
+    pub ixx: f64,
+    pub iyy: f64,
+    pub izz: f64,
+    #[serde(default)]
+    pub ixy: f64,
+    #[serde(default)]
+    pub ixz: f64,
+    #[serde(default)]
+    pub iyz: f64,
+}
+
+/// Center of gravity and inertia of a component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MassProperties {
+    /// Center of gravity [x, y, z] in mm, in the component frame
+    pub cg_mm: [f64; 3],
+
+    /// Inertia tensor about the CG, in the component frame
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertia: Option<InertiaTensor>,
+}
+
 /// Datum reference frame (DRF) for GD&T
 ///
 /// References the feature entities that define the component's datum frame.
@@ -251,6 +608,10 @@ pub struct Component {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mass_kg: Option<f64>,
 
+    /// Center of gravity and inertia (for mass properties rollup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mass_properties: Option<MassProperties>,
+
     /// Unit cost (manual override - prefer using selected_quote for pricing)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unit_cost: Option<f64>,
@@ -260,10 +621,26 @@ pub struct Component {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub selected_quote: Option<String>,
 
+    /// Why the selected quote was chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_selection: Option<QuoteSelection>,
+
     /// Supplier information
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub suppliers: Vec<ComponentSupplier>,
 
+    /// Approved manufacturer list (manufacturer part numbers)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aml: Vec<AmlEntry>,
+
+    /// Part lifecycle status (overrides the AML-derived status)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<Lifecycle>,
+
+    /// Material compliance declarations (one per regulation)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compliance: Vec<ComplianceDeclaration>,
+
     /// Associated documents
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub documents: Vec<Document>,
@@ -360,9 +737,14 @@ impl Component {
             dal: None,
             material: None,
             mass_kg: None,
+            mass_properties: None,
             unit_cost: None,
             selected_quote: None,
+            quote_selection: None,
             suppliers: Vec::new(),
+            aml: Vec::new(),
+            lifecycle: None,
+            compliance: Vec::new(),
             documents: Vec::new(),
             coordinate_system: None,
             datum_frame: None,
@@ -375,6 +757,32 @@ impl Component {
             entity_revision: 1,
         }
     }
+
+    /// Effective lifecycle status
+    ///
+    /// The part's own `lifecycle` when set, else the best status among its
+    /// approved manufacturer part numbers, else active.
+    pub fn lifecycle_status(&self) -> LifecycleStatus {
+        if let Some(ref lifecycle) = self.lifecycle {
+            return lifecycle.status;
+        }
+        self.approved_aml()
+            .map(|e| e.lifecycle_status())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// AML entries approved for use
+    pub fn approved_aml(&self) -> impl Iterator<Item = &AmlEntry> {
+        self.aml
+            .iter()
+            .filter(|e| e.approval == AmlApproval::Approved)
+    }
+
+    /// Compliance declaration for a regulation, if any
+    pub fn declaration(&self, regulation: Regulation) -> Option<&ComplianceDeclaration> {
+        self.compliance.iter().find(|d| d.regulation == regulation)
+    }
 }
 
 #[cfg(test)]
@@ -461,4 +869,14 @@ mod tests {
         assert_eq!(cmp.status(), "draft");
         assert_eq!(cmp.author(), "test_author");
     }
+
+    #[test]
+    fn test_quote_selection_currency_is_typed() {
+        let yaml = "quote: QUOT-1\nselected_by: test\ndate: 2026-01-01\nrationale: Cheapest\ncurrency: EUR\n";
+        let sel: QuoteSelection = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(sel.currency, Some(Currency::Eur));
+
+        let bad = yaml.replace("EUR", "EURO");
+        assert!(serde_yml::from_str::<QuoteSelection>(&bad).is_err());
+    }
 }
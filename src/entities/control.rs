@@ -152,6 +152,161 @@ pub struct Sampling {
     /// Sample size per check
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sample_size: Option<u32>,
+
+    /// Acceptance quality limit (percent nonconforming, or nonconformities
+    /// per hundred units above 10). When set, the sample size and
+    /// accept/reject numbers are computed from the lot quantity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aql: Option<f64>,
+
+    /// Inspection level (default: II)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspection_level: Option<InspectionLevel>,
+
+    /// Sampling scheme (default: single)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<SamplingScheme>,
+
+    /// Fixed inspection severity; when unset, the switching rules decide
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<InspectionSeverity>,
+}
+
+/// Inspection level (ANSI Z1.4 / ISO 2859-1 Table I)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InspectionLevel {
+    /// Special level S-1
+    #[serde(alias = "S1", alias = "S-1")]
+    S1,
+    /// Special level S-2
+    #[serde(alias = "S2", alias = "S-2")]
+    S2,
+    /// Special level S-3
+    #[serde(alias = "S3", alias = "S-3")]
+    S3,
+    /// Special level S-4
+    #[serde(alias = "S4", alias = "S-4")]
+    S4,
+    /// General level I (reduced discrimination)
+    #[serde(alias = "I")]
+    I,
+    /// General level II (normal)
+    #[default]
+    #[serde(alias = "II")]
+    Ii,
+    /// General level III (increased discrimination)
+    #[serde(alias = "III")]
+    Iii,
+}
+
+impl std::fmt::Display for InspectionLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InspectionLevel::S1 => write!(f, "S-1"),
+            InspectionLevel::S2 => write!(f, "S-2"),
+            InspectionLevel::S3 => write!(f, "S-3"),
+            InspectionLevel::S4 => write!(f, "S-4"),
+            InspectionLevel::I => write!(f, "I"),
+            InspectionLevel::Ii => write!(f, "II"),
+            InspectionLevel::Iii => write!(f, "III"),
+        }
+    }
+}
+
+impl std::str::FromStr for InspectionLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "s1" => Ok(InspectionLevel::S1),
+            "s2" => Ok(InspectionLevel::S2),
+            "s3" => Ok(InspectionLevel::S3),
+            "s4" => Ok(InspectionLevel::S4),
+            "i" | "1" => Ok(InspectionLevel::I),
+            "ii" | "2" => Ok(InspectionLevel::Ii),
+            "iii" | "3" => Ok(InspectionLevel::Iii),
+            _ => Err(format!(
+                "Invalid inspection level: {}. Use s1, s2, s3, s4, i, ii, or iii",
+                s
+            )),
+        }
+    }
+}
+
+/// Sampling scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingScheme {
+    /// Single sampling by attributes (Z1.4 Tables II-A/B/C)
+    #[default]
+    Single,
+    /// Zero acceptance number (c=0) by attributes
+    ZeroAcceptance,
+    /// Variables sampling, k-method with unknown standard deviation
+    Variables,
+}
+
+impl std::fmt::Display for SamplingScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SamplingScheme::Single => write!(f, "single"),
+            SamplingScheme::ZeroAcceptance => write!(f, "c=0"),
+            SamplingScheme::Variables => write!(f, "variables"),
+        }
+    }
+}
+
+impl std::str::FromStr for SamplingScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "single" | "attribute" | "attributes" => Ok(SamplingScheme::Single),
+            "zero_acceptance" | "c0" | "c=0" | "c_zero" => Ok(SamplingScheme::ZeroAcceptance),
+            "variables" | "variable" => Ok(SamplingScheme::Variables),
+            _ => Err(format!(
+                "Invalid sampling scheme: {}. Use single, c0, or variables",
+                s
+            )),
+        }
+    }
+}
+
+/// Inspection severity under the switching rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InspectionSeverity {
+    #[default]
+    Normal,
+    Tightened,
+    Reduced,
+}
+
+impl std::fmt::Display for InspectionSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InspectionSeverity::Normal => write!(f, "normal"),
+            InspectionSeverity::Tightened => write!(f, "tightened"),
+            InspectionSeverity::Reduced => write!(f, "reduced"),
+        }
+    }
+}
+
+impl std::str::FromStr for InspectionSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(InspectionSeverity::Normal),
+            "tightened" | "tight" => Ok(InspectionSeverity::Tightened),
+            "reduced" => Ok(InspectionSeverity::Reduced),
+            _ => Err(format!(
+                "Invalid inspection severity: {}. Use normal, tightened, or reduced",
+                s
+            )),
+        }
+    }
 }
 
 /// Statistical control limits (for SPC)
@@ -420,6 +420,36 @@ impl Dev {
         }
     }
 
+    /// Why the deviation cannot be used on `date`, or `None` if it is in effect
+    pub fn usability_issue(&self, date: NaiveDate) -> Option<String> {
+        match self.dev_status {
+            DevStatus::Pending => return Some("is pending approval".to_string()),
+            DevStatus::Rejected => return Some("was rejected".to_string()),
+            DevStatus::Expired | DevStatus::Closed => {
+                return Some(format!("is {}", self.dev_status))
+            }
+            DevStatus::Approved | DevStatus::Active => {}
+        }
+        if let Some(effective) = self.effective_date.filter(|d| *d > date) {
+            return Some(format!("is not effective until {}", effective));
+        }
+        if let Some(expired) = self.expiration_date.filter(|d| *d < date) {
+            return Some(format!("expired on {}", expired));
+        }
+        None
+    }
+
+    /// Whether the deviation is approved and within its effective window on `date`
+    pub fn is_in_effect_on(&self, date: NaiveDate) -> bool {
+        self.usability_issue(date).is_none()
+    }
+
+    /// Whether the deviation applies to a lot or to one of its processes
+    pub fn applies_to(&self, lot_id: &str, process_id: Option<&str>) -> bool {
+        self.links.lots.iter().any(|l| l == lot_id)
+            || process_id.is_some_and(|p| self.links.processes.iter().any(|dp| dp == p))
+    }
+
     /// Create a new Dev with deviation number
     pub fn with_deviation_number(title: String, deviation_number: String, author: String) -> Self {
         let mut dev = Self::new(title, author);
@@ -545,4 +575,26 @@ entity_revision: 1
         assert_eq!(dev.risk.mitigations.len(), 1);
         assert!(dev.approval.approved_by.is_some());
     }
+
+    #[test]
+    fn test_dev_usability() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let mut dev = Dev::new("Test Deviation".to_string(), "Test Author".to_string());
+        assert_eq!(
+            dev.usability_issue(date).as_deref(),
+            Some("is pending approval")
+        );
+
+        dev.dev_status = DevStatus::Approved;
+        assert!(dev.is_in_effect_on(date));
+
+        dev.expiration_date = NaiveDate::from_ymd_opt(2024, 5, 31);
+        assert_eq!(
+            dev.usability_issue(date).as_deref(),
+            Some("expired on 2024-05-31")
+        );
+
+        dev.dev_status = DevStatus::Rejected;
+        assert_eq!(dev.usability_issue(date).as_deref(), Some("was rejected"));
+    }
 }
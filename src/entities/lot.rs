@@ -12,6 +12,8 @@ use crate::core::identity::EntityId;
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
 pub enum LotStatus {
+    /// Scheduled but not started; drives material requirements planning
+    Planned,
     #[default]
     InProgress,
     OnHold,
@@ -22,6 +24,7 @@ pub enum LotStatus {
 impl std::fmt::Display for LotStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            LotStatus::Planned => write!(f, "planned"),
             LotStatus::InProgress => write!(f, "in_progress"),
             LotStatus::OnHold => write!(f, "on_hold"),
             LotStatus::Completed => write!(f, "completed"),
@@ -35,12 +38,13 @@ impl std::str::FromStr for LotStatus {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
+            "planned" => Ok(LotStatus::Planned),
             "in_progress" | "inprogress" => Ok(LotStatus::InProgress),
             "on_hold" | "onhold" => Ok(LotStatus::OnHold),
             "completed" => Ok(LotStatus::Completed),
             "scrapped" => Ok(LotStatus::Scrapped),
             _ => Err(format!(
-                "Invalid lot status: {}. Use in_progress, on_hold, completed, or scrapped",
+                "Invalid lot status: {}. Use planned, in_progress, on_hold, completed, or scrapped",
                 s
             )),
         }
@@ -110,6 +114,98 @@ impl std::fmt::Display for ApprovalStatus {
     }
 }
 
+/// Serialized unit status within a lot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
+pub enum UnitStatus {
+    #[default]
+    InProcess,
+    Completed,
+    Scrapped,
+}
+
+impl std::fmt::Display for UnitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnitStatus::InProcess => write!(f, "in_process"),
+            UnitStatus::Completed => write!(f, "completed"),
+            UnitStatus::Scrapped => write!(f, "scrapped"),
+        }
+    }
+}
+
+impl std::str::FromStr for UnitStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "in_process" | "inprocess" => Ok(UnitStatus::InProcess),
+            "completed" => Ok(UnitStatus::Completed),
+            "scrapped" => Ok(UnitStatus::Scrapped),
+            _ => Err(format!(
+                "Invalid unit status: {}. Use in_process, completed, or scrapped",
+                s
+            )),
+        }
+    }
+}
+
+/// How a lot was derived from other lots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenealogyOperation {
+    /// Lot was split off from a single parent lot
+    Split,
+    /// Lot was created by merging several parent lots
+    Merge,
+}
+
+impl std::fmt::Display for GenealogyOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenealogyOperation::Split => write!(f, "split"),
+            GenealogyOperation::Merge => write!(f, "merge"),
+        }
+    }
+}
+
+/// Origin record for lots created by a split or merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotOrigin {
+    /// Split or merge
+    pub operation: GenealogyOperation,
+
+    /// When the operation was performed
+    pub date: DateTime<Utc>,
+
+    /// Who performed the operation
+    pub performed_by: String,
+
+    /// Reason or notes for the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Serialized unit tracked within a lot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SerialUnit {
+    /// Unit serial number (unique within the project)
+    pub serial: String,
+
+    /// Unit production status
+    #[serde(default)]
+    pub unit_status: UnitStatus,
+
+    /// Materials consumed by this unit only (e.g., serialized subassemblies)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub materials: Vec<MaterialUsed>,
+
+    /// Notes about this unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
 /// Reference to a work instruction used during step execution
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkInstructionRef {
@@ -165,6 +261,18 @@ pub struct MaterialUsed {
     /// Quantity used
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quantity: Option<u32>,
+
+    /// In-house lot this material was taken from (LOT-xxx)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_lot: Option<String>,
+
+    /// Serial numbers of the consumed items (for serialized subassemblies)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serials: Vec<String>,
+
+    /// Receiving inspection record the material was drawn from (RCV-xxx)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<String>,
 }
 
 /// Execution step record (DHR compliant)
@@ -233,6 +341,10 @@ pub struct ExecutionStep {
     /// Measurement/inspection data (key-value pairs)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub data: HashMap<String, serde_json::Value>,
+
+    /// Deviation (DEV ID) that authorized a departure in this step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deviation: Option<String>,
 }
 
 /// Links for LOT entity
@@ -257,6 +369,14 @@ pub struct LotLinks {
     /// In-process inspection results
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub results: Vec<String>,
+
+    /// Lots this lot was split or merged from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_lots: Vec<String>,
+
+    /// Lots split or merged out of this lot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_lots: Vec<String>,
 }
 
 /// Production Lot / Batch entity (Device History Record)
@@ -292,6 +412,14 @@ pub struct Lot {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub materials_used: Vec<MaterialUsed>,
 
+    /// Serialized units in this lot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serials: Vec<SerialUnit>,
+
+    /// Split/merge origin (for lots derived from other lots)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<LotOrigin>,
+
     /// Process execution records
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub execution: Vec<ExecutionStep>,
@@ -373,6 +501,8 @@ impl Lot {
             start_date: None,
             completion_date: None,
             materials_used: Vec::new(),
+            serials: Vec::new(),
+            origin: None,
             execution: Vec::new(),
             notes: None,
             git_branch: None,
@@ -391,6 +521,18 @@ impl Lot {
         lot.lot_number = Some(lot_number);
         lot
     }
+
+    /// Find a serialized unit by serial number
+    pub fn find_serial(&self, serial: &str) -> Option<&SerialUnit> {
+        self.serials.iter().find(|u| u.serial == serial)
+    }
+
+    /// Display label (lot number if set, otherwise the ID)
+    pub fn label(&self) -> String {
+        self.lot_number
+            .clone()
+            .unwrap_or_else(|| self.id.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -417,6 +559,7 @@ mod tests {
             LotStatus::Completed
         );
         assert_eq!("on_hold".parse::<LotStatus>().unwrap(), LotStatus::OnHold);
+        assert_eq!("planned".parse::<LotStatus>().unwrap(), LotStatus::Planned);
         assert_eq!(
             "scrapped".parse::<LotStatus>().unwrap(),
             LotStatus::Scrapped
@@ -458,5 +601,33 @@ entity_revision: 1
         assert_eq!(lot.quantity, Some(25));
         assert_eq!(lot.lot_status, LotStatus::InProgress);
         assert_eq!(lot.materials_used.len(), 1);
+        assert!(lot.serials.is_empty());
+        assert!(lot.origin.is_none());
+    }
+
+    #[test]
+    fn test_lot_serials_roundtrip() {
+        let mut lot = Lot::new("Serialized Lot".to_string(), "Test Author".to_string());
+        lot.serials.push(SerialUnit {
+            serial: "SN-0001".to_string(),
+            unit_status: UnitStatus::Completed,
+            materials: vec![MaterialUsed {
+                component: Some("CMP-01HC2JB7SMQX7RS1Y0GFKBHPTE".to_string()),
+                supplier_lot: Some("ABC-123".to_string()),
+                quantity: Some(1),
+                ..Default::default()
+            }],
+            notes: None,
+        });
+        lot.links
+            .parent_lots
+            .push("LOT-01HC2JB7SMQX7RS1Y0GFKBHPTD".to_string());
+
+        let yaml = serde_yml::to_string(&lot).unwrap();
+        let parsed: Lot = serde_yml::from_str(&yaml).unwrap();
+        let unit = parsed.find_serial("SN-0001").unwrap();
+        assert_eq!(unit.unit_status, UnitStatus::Completed);
+        assert_eq!(unit.materials[0].supplier_lot.as_deref(), Some("ABC-123"));
+        assert_eq!(parsed.links.parent_lots.len(), 1);
     }
 }
@@ -21,7 +21,9 @@
 //! - [`Stackup`] - Tolerance chain analysis with worst-case, RSS, and Monte Carlo
 
 pub mod assembly;
+pub mod audit;
 pub mod capa;
+pub mod complaint;
 pub mod component;
 pub mod control;
 pub mod dev;
@@ -32,6 +34,7 @@ pub mod mate;
 pub mod ncr;
 pub mod process;
 pub mod quote;
+pub mod receipt;
 pub mod requirement;
 pub mod result;
 pub mod risk;
@@ -42,7 +45,9 @@ pub mod test;
 pub mod work_instruction;
 
 pub use assembly::Assembly;
+pub use audit::Audit;
 pub use capa::Capa;
+pub use complaint::Complaint;
 pub use component::{Component, ComponentSupplier};
 pub use control::Control;
 pub use dev::Dev;
@@ -53,6 +58,7 @@ pub use mate::Mate;
 pub use ncr::Ncr;
 pub use process::Process;
 pub use quote::Quote;
+pub use receipt::Receipt;
 pub use requirement::Requirement;
 pub use result::Result;
 pub use risk::Risk;
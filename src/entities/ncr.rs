@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::entity::{Entity, Status};
 use crate::core::identity::EntityId;
+use crate::core::workflow::ApprovalRecord;
 
 /// NCR type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -246,6 +247,8 @@ pub struct ContainmentAction {
 pub enum DispositionDecision {
     UseAsIs,
     Rework,
+    /// Restore function without meeting the original specification
+    Repair,
     #[default]
     Scrap,
     ReturnToSupplier,
@@ -256,6 +259,7 @@ impl std::fmt::Display for DispositionDecision {
         match self {
             DispositionDecision::UseAsIs => write!(f, "use_as_is"),
             DispositionDecision::Rework => write!(f, "rework"),
+            DispositionDecision::Repair => write!(f, "repair"),
             DispositionDecision::Scrap => write!(f, "scrap"),
             DispositionDecision::ReturnToSupplier => write!(f, "return_to_supplier"),
         }
@@ -284,6 +288,14 @@ pub struct Disposition {
     /// MRB (Material Review Board) required
     #[serde(default)]
     pub mrb_required: bool,
+
+    /// Deviation authorizing a use-as-is disposition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deviation: Option<EntityId>,
+
+    /// MRB sign-offs for this decision (cleared when the decision changes)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<ApprovalRecord>,
 }
 
 /// Cost impact
@@ -334,6 +346,10 @@ pub struct NcrLinks {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub component: Option<EntityId>,
 
+    /// Supplier responsible (supplier NCRs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<EntityId>,
+
     /// Process where found
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub process: Option<EntityId>,
@@ -349,6 +365,10 @@ pub struct NcrLinks {
     /// Test result that created this NCR (reciprocal of RSLT.created_ncr)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub from_result: Option<EntityId>,
+
+    /// Rejected receipt that created this NCR (reciprocal of RCV.ncr)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<EntityId>,
 }
 
 /// An NCR entity - Non-Conformance Report
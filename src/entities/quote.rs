@@ -79,6 +79,37 @@ impl std::fmt::Display for Currency {
     }
 }
 
+impl std::str::FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "USD" => Ok(Currency::Usd),
+            "EUR" => Ok(Currency::Eur),
+            "GBP" => Ok(Currency::Gbp),
+            "CNY" => Ok(Currency::Cny),
+            "JPY" => Ok(Currency::Jpy),
+            _ => Err(format!(
+                "Invalid currency: {}. Use USD, EUR, GBP, CNY, or JPY",
+                s
+            )),
+        }
+    }
+}
+
+impl Currency {
+    /// Symbol used when printing amounts
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Cny => "CN¥",
+            Currency::Jpy => "¥",
+        }
+    }
+}
+
 /// Price break for quantity-based pricing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceBreak {
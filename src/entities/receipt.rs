@@ -0,0 +1,323 @@
+//! RCV entity type - Receiving Inspection
+//!
+//! A receipt records a delivery of a purchased component: supplier, supplier
+//! lot, quantity and the incoming inspection performed against the
+//! component's controls. Lots may only consume material from accepted
+//! receipts; a rejected receipt opens a supplier NCR.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::entity::{Entity, Status};
+use crate::core::identity::{EntityId, EntityPrefix};
+use crate::entities::control::InspectionSeverity;
+
+/// Receipt disposition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptStatus {
+    /// Received, awaiting inspection
+    #[default]
+    Pending,
+    /// Inspected and released for use
+    Accepted,
+    /// Failed inspection
+    Rejected,
+}
+
+impl std::fmt::Display for ReceiptStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReceiptStatus::Pending => write!(f, "pending"),
+            ReceiptStatus::Accepted => write!(f, "accepted"),
+            ReceiptStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReceiptStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(ReceiptStatus::Pending),
+            "accepted" => Ok(ReceiptStatus::Accepted),
+            "rejected" => Ok(ReceiptStatus::Rejected),
+            _ => Err(format!(
+                "Invalid receipt status: {}. Use pending, accepted, or rejected",
+                s
+            )),
+        }
+    }
+}
+
+/// Result of one inspection check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckResult {
+    /// Not yet inspected
+    #[default]
+    Pending,
+    Pass,
+    Fail,
+}
+
+impl std::fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckResult::Pending => write!(f, "pending"),
+            CheckResult::Pass => write!(f, "pass"),
+            CheckResult::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// One characteristic inspected on receipt
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReceiptCheck {
+    /// Control plan item inspected (CTRL-xxx)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control: Option<EntityId>,
+
+    /// Characteristic inspected
+    pub characteristic: String,
+
+    /// Specification (e.g., "10.00 +0.05/-0.05 mm")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specification: Option<String>,
+
+    /// Units to inspect for this check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<u32>,
+
+    /// Acceptance number: most defects that still pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accept: Option<u32>,
+
+    /// Rejection number: fewest defects that fail
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reject: Option<u32>,
+
+    /// Acceptability constant for variables sampling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k: Option<f64>,
+
+    /// Inspection severity the plan was chosen at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<InspectionSeverity>,
+
+    /// Inspection result
+    #[serde(default)]
+    pub result: CheckResult,
+
+    /// Worst measured value in the sample
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measured: Option<f64>,
+
+    /// Sample mean (variables sampling)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+
+    /// Sample standard deviation (variables sampling)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std_dev: Option<f64>,
+
+    /// Number of defective samples found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defects: Option<u32>,
+
+    /// Notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Links to other entities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReceiptLinks {
+    /// Supplier the material was received from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<EntityId>,
+
+    /// Component received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<EntityId>,
+
+    /// Supplier NCR opened when the receipt was rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ncr: Option<EntityId>,
+}
+
+/// A receiving inspection record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Receipt {
+    /// Unique identifier
+    pub id: EntityId,
+
+    /// Receipt title
+    pub title: String,
+
+    /// Supplier lot / batch number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier_lot: Option<String>,
+
+    /// Purchase order number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub po_number: Option<String>,
+
+    /// Quantity received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+
+    /// Date received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received_date: Option<NaiveDate>,
+
+    /// Number of units inspected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<u32>,
+
+    /// Inspection checks, one per characteristic
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<ReceiptCheck>,
+
+    /// Inspector who dispositioned the receipt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspector: Option<String>,
+
+    /// Date the receipt was accepted or rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspected_date: Option<NaiveDate>,
+
+    /// Receipt disposition
+    #[serde(default)]
+    pub receipt_status: ReceiptStatus,
+
+    /// Reason for rejection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+
+    /// Links to other entities
+    #[serde(default)]
+    pub links: ReceiptLinks,
+
+    /// Tags for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Current status
+    #[serde(default)]
+    pub status: Status,
+
+    /// Creation timestamp
+    pub created: DateTime<Utc>,
+
+    /// Author
+    pub author: String,
+
+    /// Entity revision number
+    #[serde(default = "default_revision")]
+    pub entity_revision: u32,
+}
+
+fn default_revision() -> u32 {
+    1
+}
+
+impl Entity for Receipt {
+    const PREFIX: &'static str = "RCV";
+
+    fn id(&self) -> &EntityId {
+        &self.id
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn status(&self) -> &str {
+        match self.status {
+            Status::Draft => "draft",
+            Status::Review => "review",
+            Status::Approved => "approved",
+            Status::Released => "released",
+            Status::Obsolete => "obsolete",
+        }
+    }
+
+    fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    fn author(&self) -> &str {
+        &self.author
+    }
+}
+
+impl Receipt {
+    /// Create a new receipt
+    pub fn new(title: String, author: String) -> Self {
+        Self {
+            id: EntityId::new(EntityPrefix::Rcv),
+            title,
+            supplier_lot: None,
+            po_number: None,
+            quantity: None,
+            received_date: Some(chrono::Local::now().date_naive()),
+            sample_size: None,
+            checks: Vec::new(),
+            inspector: None,
+            inspected_date: None,
+            receipt_status: ReceiptStatus::default(),
+            rejection_reason: None,
+            links: ReceiptLinks::default(),
+            tags: Vec::new(),
+            status: Status::default(),
+            created: Utc::now(),
+            author,
+            entity_revision: 1,
+        }
+    }
+
+    /// Checks that failed
+    pub fn failed_checks(&self) -> impl Iterator<Item = &ReceiptCheck> {
+        self.checks.iter().filter(|c| c.result == CheckResult::Fail)
+    }
+
+    /// Number of checks not yet inspected
+    pub fn pending_checks(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| c.result == CheckResult::Pending)
+            .count()
+    }
+
+    /// Whether material from this receipt may be consumed by a lot
+    pub fn is_usable(&self) -> bool {
+        self.receipt_status == ReceiptStatus::Accepted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receipt_roundtrip() {
+        let mut rcv = Receipt::new("Bracket lot A12".to_string(), "test".to_string());
+        rcv.supplier_lot = Some("A12".to_string());
+        rcv.quantity = Some(500);
+        rcv.checks.push(ReceiptCheck {
+            characteristic: "Hole diameter".to_string(),
+            result: CheckResult::Fail,
+            measured: Some(6.12),
+            ..Default::default()
+        });
+
+        let yaml = serde_yml::to_string(&rcv).unwrap();
+        let parsed: Receipt = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.supplier_lot.as_deref(), Some("A12"));
+        assert_eq!(parsed.receipt_status, ReceiptStatus::Pending);
+        assert_eq!(parsed.failed_checks().count(), 1);
+        assert!(!parsed.is_usable());
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::entity::{Entity, Status};
 use crate::core::identity::{EntityId, EntityPrefix};
+pub use crate::entities::quote::Currency;
 
 /// Contact information for a person at the supplier
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -120,26 +121,40 @@ impl std::fmt::Display for Capability {
     }
 }
 
-/// Currency code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum Currency {
-    #[default]
-    Usd,
-    Eur,
-    Gbp,
-    Cny,
-    Jpy,
+/// Supplier approval status (approved supplier list)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Approved for use
+    Approved,
+    /// Approved with conditions (e.g., increased inspection)
+    Conditional,
+    /// Must not be used for new orders
+    Disqualified,
 }
 
-impl std::fmt::Display for Currency {
+impl std::fmt::Display for ApprovalStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Currency::Usd => write!(f, "USD"),
-            Currency::Eur => write!(f, "EUR"),
-            Currency::Gbp => write!(f, "GBP"),
-            Currency::Cny => write!(f, "CNY"),
-            Currency::Jpy => write!(f, "JPY"),
+            ApprovalStatus::Approved => write!(f, "approved"),
+            ApprovalStatus::Conditional => write!(f, "conditional"),
+            ApprovalStatus::Disqualified => write!(f, "disqualified"),
+        }
+    }
+}
+
+impl std::str::FromStr for ApprovalStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "approved" => Ok(ApprovalStatus::Approved),
+            "conditional" => Ok(ApprovalStatus::Conditional),
+            "disqualified" => Ok(ApprovalStatus::Disqualified),
+            _ => Err(format!(
+                "Invalid approval status: {}. Use approved, conditional, or disqualified",
+                s
+            )),
         }
     }
 }
@@ -193,6 +208,18 @@ pub struct Supplier {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub capabilities: Vec<Capability>,
 
+    /// Approval status on the approved supplier list (unset = not yet assessed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_status: Option<ApprovalStatus>,
+
+    /// Date the approval status was last set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_date: Option<NaiveDate>,
+
+    /// Conditions or reason for the approval status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_notes: Option<String>,
+
     /// Notes about the supplier
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
@@ -268,6 +295,9 @@ impl Supplier {
             currency: Currency::default(),
             certifications: Vec::new(),
             capabilities: Vec::new(),
+            approval_status: None,
+            approval_date: None,
+            approval_notes: None,
             notes: None,
             tags: Vec::new(),
             status: Status::default(),
@@ -314,6 +344,24 @@ impl Supplier {
             .any(|c| c.expiry.is_some_and(|exp| exp < today))
     }
 
+    /// Approval warning for selecting this supplier, unless it is approved
+    pub fn approval_warning(&self) -> Option<String> {
+        let Some(status) = self.approval_status else {
+            return Some(format!(
+                "Supplier '{}' has not been assessed (no approval status)",
+                self.display_name()
+            ));
+        };
+        if status == ApprovalStatus::Approved {
+            return None;
+        }
+        let mut msg = format!("Supplier '{}' is {}", self.display_name(), status);
+        if let Some(ref notes) = self.approval_notes {
+            msg.push_str(&format!(": {}", notes.trim()));
+        }
+        Some(msg)
+    }
+
     /// Get certifications expiring within N days
     pub fn certs_expiring_soon(&self, days: i64) -> Vec<&Certification> {
         let today = Utc::now().date_naive();
@@ -385,6 +433,26 @@ mod tests {
         assert_eq!(parsed.capabilities.len(), 2);
     }
 
+    #[test]
+    fn test_approval_warning() {
+        let mut supplier = Supplier::new("Acme Corp", "test");
+        assert_eq!(
+            supplier.approval_warning().unwrap(),
+            "Supplier 'Acme Corp' has not been assessed (no approval status)"
+        );
+
+        supplier.approval_status = Some(ApprovalStatus::Approved);
+        assert!(supplier.approval_warning().is_none());
+
+        supplier.approval_status = Some("conditional".parse().unwrap());
+        supplier.approval_notes = Some("100% incoming inspection".to_string());
+        assert_eq!(
+            supplier.approval_warning().unwrap(),
+            "Supplier 'Acme Corp' is conditional: 100% incoming inspection"
+        );
+        assert!("banned".parse::<ApprovalStatus>().is_err());
+    }
+
     #[test]
     fn test_entity_trait_implementation() {
         let supplier = Supplier::new("Acme Corp", "test_author");
@@ -100,6 +100,18 @@ pub struct QualityCheck {
     /// Specification/tolerance
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub specification: Option<String>,
+
+    /// Units of the recorded value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+
+    /// Minimum acceptable value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    /// Maximum acceptable value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
 }
 
 /// Links to other entities
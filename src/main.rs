@@ -38,13 +38,17 @@ fn main() -> Result<()> {
         Commands::Asm(cmd) => tdt::cli::commands::asm::run(cmd, &global),
         Commands::Quote(cmd) => tdt::cli::commands::quote::run(cmd, &global),
         Commands::Sup(cmd) => tdt::cli::commands::sup::run(cmd, &global),
+        Commands::Plan(args) => tdt::cli::commands::plan::run(args, &global),
         Commands::Proc(cmd) => tdt::cli::commands::proc::run(cmd, &global),
         Commands::Ctrl(cmd) => tdt::cli::commands::ctrl::run(cmd, &global),
         Commands::Work(cmd) => tdt::cli::commands::work::run(cmd, &global),
         Commands::Lot(cmd) => tdt::cli::commands::lot::run(cmd, &global),
         Commands::Dev(cmd) => tdt::cli::commands::dev::run(cmd, &global),
+        Commands::Rcv(cmd) => tdt::cli::commands::rcv::run(cmd, &global),
         Commands::Ncr(cmd) => tdt::cli::commands::ncr::run(cmd, &global),
         Commands::Capa(cmd) => tdt::cli::commands::capa::run(cmd, &global),
+        Commands::Cmpl(cmd) => tdt::cli::commands::cmpl::run(cmd, &global),
+        Commands::Audit(cmd) => tdt::cli::commands::audit::run(cmd, &global),
         Commands::Feat(cmd) => tdt::cli::commands::feat::run(cmd, &global),
         Commands::Mate(cmd) => tdt::cli::commands::mate::run(cmd, &global),
         Commands::Tol(cmd) => tdt::cli::commands::tol::run(cmd, &global),
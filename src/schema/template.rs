@@ -92,6 +92,17 @@ pub struct TemplateContext {
     // DEV (Deviation) fields
     pub dev_type: Option<String>,
     pub deviation_number: Option<String>,
+    // CMPL (Complaint) fields
+    pub complaint_type: Option<String>,
+    pub customer: Option<String>,
+    pub rma_number: Option<String>,
+    // AUD (Audit) fields
+    pub audit_type: Option<String>,
+    pub standard: Option<String>,
+    pub scheduled_date: Option<String>,
+    // RCV (Receipt) fields
+    pub supplier_lot: Option<String>,
+    pub po_number: Option<String>,
 }
 
 impl TemplateContext {
@@ -159,6 +170,14 @@ impl TemplateContext {
             quantity: None,
             dev_type: None,
             deviation_number: None,
+            complaint_type: None,
+            customer: None,
+            rma_number: None,
+            audit_type: None,
+            standard: None,
+            scheduled_date: None,
+            supplier_lot: None,
+            po_number: None,
         }
     }
 
@@ -450,6 +469,46 @@ impl TemplateContext {
         self.deviation_number = Some(deviation_number.into());
         self
     }
+
+    pub fn with_complaint_type(mut self, complaint_type: impl Into<String>) -> Self {
+        self.complaint_type = Some(complaint_type.into());
+        self
+    }
+
+    pub fn with_customer(mut self, customer: impl Into<String>) -> Self {
+        self.customer = Some(customer.into());
+        self
+    }
+
+    pub fn with_rma_number(mut self, rma_number: impl Into<String>) -> Self {
+        self.rma_number = Some(rma_number.into());
+        self
+    }
+
+    pub fn with_audit_type(mut self, audit_type: impl Into<String>) -> Self {
+        self.audit_type = Some(audit_type.into());
+        self
+    }
+
+    pub fn with_standard(mut self, standard: impl Into<String>) -> Self {
+        self.standard = Some(standard.into());
+        self
+    }
+
+    pub fn with_scheduled_date(mut self, scheduled_date: impl Into<String>) -> Self {
+        self.scheduled_date = Some(scheduled_date.into());
+        self
+    }
+
+    pub fn with_supplier_lot(mut self, supplier_lot: impl Into<String>) -> Self {
+        self.supplier_lot = Some(supplier_lot.into());
+        self
+    }
+
+    pub fn with_po_number(mut self, po_number: impl Into<String>) -> Self {
+        self.po_number = Some(po_number.into());
+        self
+    }
 }
 
 /// Template generator using Tera
@@ -1805,6 +1864,10 @@ capabilities: []
 # Options: machining, sheet_metal, casting, injection, extrusion, pcb,
 #          pcb_assembly, cable_assembly, assembly, testing, finishing, packaging
 
+# Approved supplier list status: approved, conditional, or disqualified
+# (set with: tdt sup approval SUP@N <status>)
+approval_status: null
+
 {notes_line}tags: []
 status: draft
 
@@ -2017,6 +2080,11 @@ sampling:
   type: continuous
   frequency: "5 parts"
   sample_size: 1
+  # AQL-based plan (ANSI Z1.4 / ISO 2859-1): sample size and accept/reject
+  # numbers then follow the lot quantity
+  # aql: 1.0
+  # inspection_level: ii      # s1, s2, s3, s4, i, ii, iii
+  # scheme: single            # single, zero_acceptance, variables
 
 # Control limits (for SPC)
 control_limits: null
@@ -2230,7 +2298,7 @@ containment: []
 
 # Disposition
 disposition:
-  decision: null  # use_as_is | rework | scrap | return_to_supplier
+  decision: null  # use_as_is | rework | repair | scrap | return_to_supplier
   decision_date: null
   decision_by: null
   justification: ""
@@ -2316,6 +2384,18 @@ root_cause_analysis:
   root_cause: |
     # Document the root cause
   contributing_factors: []
+  five_whys: []
+  # Example:
+  #   - because: "Boring bar deflected under load"
+  #     evidence: "Deflection measured at 0.02 mm"
+  #     risks: []       # RISK IDs implicated by this finding
+  #     processes: []   # PROC IDs implicated by this finding
+  fishbone: []
+  # Example:
+  #   - category: machine   # man | machine | method | material | measurement | environment
+  #     cause: "Spindle runout"
+  #     sub_causes: []
+  #     confirmed: false
 
 # Action items
 actions: []
@@ -2421,6 +2501,18 @@ materials_used: []
 #   - component: CMP@1
 #     supplier_lot: "SUP-ABC-123"
 #     quantity: 25
+#   - component: CMP@2
+#     source_lot: LOT@3      # in-house subassembly lot
+#     quantity: 25
+
+# Serialized units (use 'tdt lot serial' to add)
+serials: []
+# Example:
+#   - serial: "2024-001-0001"
+#     unit_status: in_process
+#     materials:             # unit-specific consumption
+#       - component: CMP@2
+#         serials: ["SUB-0042"]
 
 # Process execution records
 execution: []
@@ -2444,6 +2536,8 @@ links:
   work_instructions: []  # WORK entities
   ncrs: []          # NCRs raised during production
   results: []       # In-process inspection results
+  parent_lots: []   # Lots this lot was split/merged from
+  child_lots: []    # Lots split/merged out of this lot
 
 # Auto-managed metadata
 status: draft
@@ -2551,6 +2645,256 @@ entity_revision: 1
             author = ctx.author,
         )
     }
+
+    /// Generate a CMPL (complaint / field return) template
+    pub fn generate_complaint(&self, ctx: &TemplateContext) -> Result<String, TemplateError> {
+        Ok(self.hardcoded_complaint_template(ctx))
+    }
+
+    fn hardcoded_complaint_template(&self, ctx: &TemplateContext) -> String {
+        let title = ctx.title.clone().unwrap_or_default();
+        let complaint_type = ctx
+            .complaint_type
+            .clone()
+            .unwrap_or_else(|| "complaint".to_string());
+        let created = ctx.created.to_rfc3339();
+        let received_date = ctx.created.format("%Y-%m-%d");
+
+        let rma_number_line = match &ctx.rma_number {
+            Some(rma) => format!("rma_number: \"{}\"", rma),
+            None => "rma_number: null".to_string(),
+        };
+        let customer_line = match &ctx.customer {
+            Some(customer) => format!("customer: \"{}\"", customer),
+            None => "customer: null".to_string(),
+        };
+
+        format!(
+            r#"# CMPL: {title}
+# Created by TDT - Tessera Design Toolkit
+
+id: {id}
+title: "{title}"
+complaint_number: null
+{rma_number_line}
+
+complaint_type: {complaint_type}  # complaint | return | field_failure
+{customer_line}
+received_date: {received_date}
+
+description: |
+  # What did the customer report?
+
+quantity: 1
+serials: []  # Serial numbers of the affected units
+
+# Failure reporting (remove if no failure is involved)
+failure:
+  failure_mode: null     # Ideally matches an FMEA failure mode
+  event_date: null
+  operating_hours: null
+  environment: null
+  injury: false
+  confirmed: null        # true = confirmed by analysis, false = no fault found
+  analysis: null
+
+# Regulatory reportability (populated by 'tdt cmpl assess')
+reportability:
+  reportable: null       # null = not yet assessed
+  regulation: null       # e.g., "21 CFR 803", "EU MDR Art. 87"
+  rationale: null
+  assessed_by: null
+  assessed_date: null
+  report_due: null
+  submitted_date: null
+  report_reference: null
+
+complaint_status: received  # received | investigating | resolved | closed
+
+links:
+  lots: []           # LOT entities the affected units came from
+  components: []     # CMP entities implicated
+  assemblies: []     # ASM entities implicated
+  risks: []          # RISK entities whose failure mode this exhibits
+  ncrs: []           # NCRs raised from the investigation
+  capa: null         # CAPA opened for this complaint
+
+tags: []
+status: draft
+
+# Auto-managed metadata
+created: {created}
+author: {author}
+entity_revision: 1
+"#,
+            id = ctx.id,
+            title = title,
+            rma_number_line = rma_number_line,
+            complaint_type = complaint_type,
+            customer_line = customer_line,
+            received_date = received_date,
+            created = created,
+            author = ctx.author,
+        )
+    }
+
+    /// Generate an AUD (audit) template
+    pub fn generate_audit(&self, ctx: &TemplateContext) -> Result<String, TemplateError> {
+        Ok(self.hardcoded_audit_template(ctx))
+    }
+
+    fn hardcoded_audit_template(&self, ctx: &TemplateContext) -> String {
+        let title = ctx.title.clone().unwrap_or_default();
+        let audit_type = ctx
+            .audit_type
+            .clone()
+            .unwrap_or_else(|| "internal".to_string());
+        let created = ctx.created.to_rfc3339();
+
+        let standard_line = match &ctx.standard {
+            Some(standard) => format!("standard: \"{}\"", standard),
+            None => "standard: null  # e.g., \"ISO 13485:2016\"".to_string(),
+        };
+        let scheduled_line = match &ctx.scheduled_date {
+            Some(date) => format!("scheduled_date: {}", date),
+            None => "scheduled_date: null".to_string(),
+        };
+
+        format!(
+            r#"# AUD: {title}
+# Created by TDT - Tessera Design Toolkit
+
+id: {id}
+title: "{title}"
+audit_number: null
+
+audit_type: {audit_type}  # internal | supplier | external
+{standard_line}
+clauses: []  # Standard clauses in scope, e.g., ["7.4", "7.5.1"]
+
+scope: |
+  # Areas, processes and sites covered by this audit
+
+{scheduled_line}
+completed_date: null
+interval_months: null  # Months until the next audit of this scope
+
+lead_auditor: null
+auditors: []
+auditees: []
+
+# Findings (added with 'tdt audit finding')
+# - id: F1
+#   severity: minor  # major | minor | observation | opportunity
+#   clause: "7.5.1"
+#   description: "..."
+#   evidence: "..."
+findings: []
+
+summary: null
+
+audit_status: planned  # planned | in_progress | completed | cancelled
+
+links:
+  supplier: null     # SUP entity (supplier audits)
+  processes: []      # PROC entities in scope
+  ncrs: []           # NCRs raised from findings
+  capas: []          # CAPAs raised from findings
+
+tags: []
+status: draft
+
+# Auto-managed metadata
+created: {created}
+author: {author}
+entity_revision: 1
+"#,
+            id = ctx.id,
+            title = title,
+            audit_type = audit_type,
+            standard_line = standard_line,
+            scheduled_line = scheduled_line,
+            created = created,
+            author = ctx.author,
+        )
+    }
+
+    /// Generate an RCV (receiving inspection) template
+    pub fn generate_receipt(&self, ctx: &TemplateContext) -> Result<String, TemplateError> {
+        Ok(self.hardcoded_receipt_template(ctx))
+    }
+
+    fn hardcoded_receipt_template(&self, ctx: &TemplateContext) -> String {
+        let title = ctx.title.clone().unwrap_or_default();
+        let created = ctx.created.to_rfc3339();
+        let received_date = ctx.created.format("%Y-%m-%d");
+
+        let quoted = |value: &Option<String>| match value {
+            Some(v) => format!("\"{}\"", v),
+            None => "null".to_string(),
+        };
+        let quantity = ctx
+            .quantity
+            .map(|q| q.to_string())
+            .unwrap_or_else(|| "null".to_string());
+        let supplier = ctx.supplier.clone().unwrap_or_else(|| "null".to_string());
+        let component = ctx
+            .component_id
+            .clone()
+            .unwrap_or_else(|| "null".to_string());
+
+        format!(
+            r#"# RCV: {title}
+# Created by TDT - Tessera Design Toolkit
+
+id: {id}
+title: "{title}"
+
+supplier_lot: {supplier_lot}
+po_number: {po_number}
+quantity: {quantity}
+received_date: {received_date}
+
+# Incoming inspection (recorded with 'tdt rcv inspect')
+sample_size: null
+# - control: CTRL-...
+#   characteristic: "Bore diameter"
+#   specification: "10 [9.95, 10.05] mm"
+#   result: pending  # pending | pass | fail
+#   measured: null
+#   defects: null
+checks: []
+
+inspector: null
+inspected_date: null
+receipt_status: pending  # pending | accepted | rejected
+rejection_reason: null
+
+links:
+  supplier: {supplier}   # SUP entity
+  component: {component}  # CMP entity received
+  ncr: null              # Supplier NCR opened on rejection
+
+tags: []
+status: draft
+
+# Auto-managed metadata
+created: {created}
+author: {author}
+entity_revision: 1
+"#,
+            id = ctx.id,
+            title = title,
+            supplier_lot = quoted(&ctx.supplier_lot),
+            po_number = quoted(&ctx.po_number),
+            quantity = quantity,
+            received_date = received_date,
+            supplier = supplier,
+            component = component,
+            created = created,
+            author = ctx.author,
+        )
+    }
 }
 
 impl Default for TemplateGenerator {
@@ -891,6 +891,9 @@ mod tests {
             (EntityPrefix::Work, vec!["title"]),
             (EntityPrefix::Ncr, vec!["title"]),
             (EntityPrefix::Capa, vec!["title"]),
+            (EntityPrefix::Cmpl, vec!["title"]),
+            (EntityPrefix::Aud, vec!["title"]),
+            (EntityPrefix::Rcv, vec!["title"]),
             (EntityPrefix::Sup, vec!["name"]),
             (EntityPrefix::Quot, vec!["title"]),
             (EntityPrefix::Tol, vec!["title"]),
@@ -249,6 +249,118 @@ fn test_sup_show_by_short_id() {
         .stdout(predicate::str::contains("Test Supplier"));
 }
 
+#[test]
+fn test_sup_approval_and_scorecard() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "PN-SC", "Scored Component");
+    create_test_supplier(&tmp, "Good Supplier");
+    create_test_supplier(&tmp, "Bad Supplier");
+    for entity in ["cmp", "sup"] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([entity, "list"])
+            .assert()
+            .success();
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "sup",
+            "approval",
+            "SUP@2",
+            "disqualified",
+            "--notes",
+            "Repeated plating escapes",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is now disqualified"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "show", "SUP@2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Approval: disqualified"));
+
+    // Selecting the disqualified supplier warns but does not fail
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@2",
+            "--price",
+            "4.00",
+            "--no-edit",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Supplier 'Bad Supplier' is disqualified: Repeated plating escapes",
+        ));
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@1",
+            "--price",
+            "5.00",
+            "--lead-time",
+            "20",
+            "--no-edit",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Supplier 'Good Supplier' has not been assessed",
+        ));
+
+    // Once approved, the supplier no longer warns
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "approval", "SUP@1", "approved"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "compare", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'Bad Supplier' is disqualified"))
+        .stdout(predicate::str::contains("'Good Supplier'").not());
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "scorecard"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Good Supplier"))
+        .stdout(predicate::str::contains("Bad Supplier"))
+        .stdout(predicate::str::contains("no certifications on file"))
+        .stdout(predicate::str::contains("2 supplier(s) scored"));
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "scorecard", "SUP@1", "-o", "json"])
+        .output()
+        .unwrap();
+    let cards: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let card = &cards[0];
+    assert_eq!(card["name"], "Good Supplier");
+    assert_eq!(card["responsiveness"]["quotes_requested"], 1);
+    assert_eq!(card["lead_time"]["score"], 100.0);
+    assert_eq!(card["certification"]["score"], 0.0);
+    assert!(card["suggested_status"].is_string());
+}
+
 // ============================================================================
 // Quote Command Tests
 // ============================================================================
@@ -572,6 +684,132 @@ fn test_quote_compare_with_qty() {
         .stdout(predicate::str::contains("3.00"));
 }
 
+#[test]
+fn test_quote_compare_select_records_rationale() {
+    let tmp = setup_test_project();
+
+    create_test_component(&tmp, "PN-SRC", "Sourced Component");
+    create_test_supplier(&tmp, "Supplier A");
+    create_test_supplier(&tmp, "Supplier B");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "list"])
+        .output()
+        .unwrap();
+
+    // Cheaper piece price, but a MOQ of 500 and tooling
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@1",
+            "--title",
+            "MOQ Quote",
+            "--price",
+            "2.00",
+            "--moq",
+            "500",
+            "--tooling",
+            "400",
+            "--lead-time",
+            "20",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@2",
+            "--title",
+            "Flexible Quote",
+            "--price",
+            "3.00",
+            "--lead-time",
+            "10",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@2",
+            "--title",
+            "Expired Quote",
+            "--price",
+            "1.00",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+
+    // Expire the cheapest quote
+    let quote_dir = tmp.path().join("bom/quotes");
+    for entry in fs::read_dir(&quote_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let content = fs::read_to_string(&path).unwrap();
+        if content.contains("Expired Quote") {
+            fs::write(&path, format!("{}\nvalid_until: 2020-01-01\n", content)).unwrap();
+        }
+    }
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "list"])
+        .output()
+        .unwrap();
+
+    // At qty 100 the MOQ excess and tooling outweigh the lower piece price
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "compare", "CMP@1", "--qty", "100", "--select"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Best at qty 100"))
+        .stdout(predicate::str::contains("Flexible Quote"))
+        .stdout(predicate::str::contains("expired"));
+
+    let cmp_dir = tmp.path().join("bom/components");
+    let cmp_file = fs::read_dir(&cmp_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| e.path().to_string_lossy().ends_with(".tdt.yaml"))
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&cmp_file).unwrap();
+    assert!(content.contains("selected_quote: QUOT-"));
+    assert!(content.contains("quote_selection:"));
+    assert!(content.contains("Ranked 1 of 2 valid quotes at qty 100"));
+    assert!(content.contains("qty: 100"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "show", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Selected Quote:"))
+        .stdout(predicate::str::contains("Ranked 1 of 2"));
+}
+
 // ============================================================================
 // Assembly Command Tests
 // ============================================================================
@@ -869,62 +1107,1083 @@ fn test_asm_cost_no_nre_flag() {
         .stdout(predicate::str::contains("Total NRE:").not());
 }
 
-// ============================================================================
-// Component Supplier ID Tests
-// ============================================================================
-
 #[test]
-fn test_component_supplier_id_field() {
+fn test_asm_cost_converts_quote_currency() {
     let tmp = setup_test_project();
-
-    // Create a component
-    create_test_component(&tmp, "PN-SUPID", "Supplier ID Test");
-
+    create_test_component(&tmp, "PN-EUR", "Euro Component");
+    create_test_supplier(&tmp, "Euro Supplier");
     tdt()
         .current_dir(tmp.path())
         .args(["cmp", "list"])
         .output()
         .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@1",
+            "--title",
+            "Euro Quote",
+            "--price",
+            "10.00",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
 
-    // Get the component file and manually add supplier with supplier_id
-    let cmp_dir = tmp.path().join("bom/components");
-    let entries: Vec<_> = fs::read_dir(&cmp_dir)
+    // Quote priced in euros
+    let quote_dir = tmp.path().join("bom/quotes");
+    let quote = std::fs::read_dir(&quote_dir)
         .unwrap()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().to_string_lossy().ends_with(".tdt.yaml"))
-        .collect();
-
-    let cmp_path = entries[0].path();
-    let content = fs::read_to_string(&cmp_path).unwrap();
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = std::fs::read_to_string(&quote)
+        .unwrap()
+        .replace("currency: USD", "currency: EUR");
+    std::fs::write(&quote, content).unwrap();
 
-    // Add supplier with supplier_id field
-    let updated_content = format!(
-        r#"{}
-suppliers:
-  - supplier_id: SUP-TEST123
-    name: Test Supplier
-    supplier_pn: TS-001
-    lead_time_days: 14
-    moq: 100
-    unit_cost: 5.00
-"#,
-        content.trim()
+    let mut config = std::fs::read_to_string(tmp.path().join(".tdt/config.yaml")).unwrap();
+    config.push_str(
+        "\ncurrency:\n  reporting: USD\n  stale_after_days: 36500\n  rates:\n    \
+         - { from: EUR, to: USD, rate: 1.10, effective: 2020-01-01 }\n",
     );
-    fs::write(&cmp_path, updated_content).unwrap();
+    std::fs::write(tmp.path().join(".tdt/config.yaml"), config).unwrap();
 
-    // Validate should pass (schema accepts supplier_id)
     tdt()
         .current_dir(tmp.path())
-        .args(["validate"])
+        .args(["quote", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "set-quote", "CMP@1", "QUOT@1"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-EUR",
+            "--title",
+            "Euro Assembly",
+            "--bom",
+            "CMP@1:2",
+            "--no-edit",
+        ])
         .assert()
         .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
 
-    // Component should still parse correctly
     tdt()
         .current_dir(tmp.path())
-        .args(["cmp", "show", "CMP@1", "-o", "yaml"])
+        .args(["asm", "cost", "ASM@1"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("supplier_id: SUP-TEST123"))
-        .stdout(predicate::str::contains("name: Test Supplier"));
+        .stdout(predicate::str::contains("Total Cost: $22.00"))
+        .stdout(predicate::str::contains("Exchange rate").not());
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["report", "bom", "ASM@1", "--with-cost"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**Total Cost:** $22.00 (USD)"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "compare", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("11.00*"))
+        .stdout(predicate::str::contains("converted to USD"));
+
+    // No EUR→GBP rate: amounts are summed unconverted with a warning
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "cost", "ASM@1", "--currency", "GBP"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total Cost: £20.00"))
+        .stdout(predicate::str::contains("no EUR→GBP exchange rate"));
+}
+
+// ============================================================================
+// BOM Diff Tests
+// ============================================================================
+
+/// Create two components and an assembly using the first, keyed ASM@1
+fn setup_bom_diff_project() -> tempfile::TempDir {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "SCR-1", "Screw");
+    create_test_component(&tmp, "LED-1", "Red LED");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "TOP-1",
+            "--title",
+            "Widget",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "add", "ASM@1", "CMP@1:4"])
+        .assert()
+        .success();
+    tmp
+}
+
+fn git(tmp: &tempfile::TempDir, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(tmp.path())
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_asm_diff_between_assemblies() {
+    let tmp = setup_bom_diff_project();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "TOP-2",
+            "--title",
+            "Widget Mk2",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "add", "ASM@2", "CMP@1:6", "CMP@2:1"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "diff", "ASM@1", "ASM@2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4→6"))
+        .stdout(predicate::str::contains("LED-1"))
+        .stdout(predicate::str::contains(
+            "1 added, 0 removed, 0 replaced, 1 changed",
+        ));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "diff", "ASM@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to compare"));
+}
+
+#[test]
+fn test_asm_diff_against_baseline() {
+    let tmp = setup_bom_diff_project();
+    git(&tmp, &["init", "-q"]);
+    git(&tmp, &["config", "user.email", "test@example.com"]);
+    git(&tmp, &["config", "user.name", "Test User"]);
+    git(&tmp, &["add", "-A"]);
+    git(&tmp, &["commit", "-q", "-m", "Initial BOM"]);
+    git(&tmp, &["tag", "tdt-v1"]);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "rm", "ASM@1", "CMP@1"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "add", "ASM@1", "CMP@2", "-r", "D1"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "diff", "ASM@1", "--from", "v1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tdt-v1"))
+        .stdout(predicate::str::contains("1 added, 1 removed"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm", "diff", "ASM@1", "--from", "v1", "--flat", "-o", "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"view\": \"flat\""))
+        .stdout(predicate::str::contains("\"D1\""));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "diff", "ASM@1", "--from", "no-such-baseline"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown git ref or baseline"));
+}
+
+// ============================================================================
+// Component Supplier ID Tests
+// ============================================================================
+
+#[test]
+fn test_component_supplier_id_field() {
+    let tmp = setup_test_project();
+
+    // Create a component
+    create_test_component(&tmp, "PN-SUPID", "Supplier ID Test");
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+
+    // Get the component file and manually add supplier with supplier_id
+    let cmp_dir = tmp.path().join("bom/components");
+    let entries: Vec<_> = fs::read_dir(&cmp_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().to_string_lossy().ends_with(".tdt.yaml"))
+        .collect();
+
+    let cmp_path = entries[0].path();
+    let content = fs::read_to_string(&cmp_path).unwrap();
+
+    // Add supplier with supplier_id field
+    let updated_content = format!(
+        r#"{}
+suppliers:
+  - supplier_id: SUP-TEST123
+    name: Test Supplier
+    supplier_pn: TS-001
+    lead_time_days: 14
+    moq: 100
+    unit_cost: 5.00
+"#,
+        content.trim()
+    );
+    fs::write(&cmp_path, updated_content).unwrap();
+
+    // Validate should pass (schema accepts supplier_id)
+    tdt()
+        .current_dir(tmp.path())
+        .args(["validate"])
+        .assert()
+        .success();
+
+    // Component should still parse correctly
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "show", "CMP@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("supplier_id: SUP-TEST123"))
+        .stdout(predicate::str::contains("name: Test Supplier"));
+}
+
+// ============================================================================
+// BOM Import Profile Tests
+// ============================================================================
+
+#[test]
+fn test_import_kicad_bom_into_assembly() {
+    let tmp = setup_bom_diff_project();
+
+    // Give the LED a manufacturer part number to match against
+    let cmp_dir = tmp.path().join("bom/components");
+    let led = std::fs::read_dir(&cmp_dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| std::fs::read_to_string(p).unwrap().contains("LED-1"))
+        .unwrap();
+    let mut content = std::fs::read_to_string(&led).unwrap();
+    content.push_str("\nsuppliers:\n  - name: Lite-On\n    supplier_pn: LTST-C191KRKT\n");
+    std::fs::write(&led, content).unwrap();
+
+    let csv = tmp.path().join("board.csv");
+    std::fs::write(
+        &csv,
+        "Reference,Value,Footprint,MPN,Manufacturer,DNP\n\
+         D1 D2,Red,0603,LTST-C191KRKT,Lite-On,\n\
+         R1-R3,330R,0603,RC0603FR-07330RL,Yageo,\n\
+         R4,330R,0603,RC0603FR-07330RL,Yageo,DNP\n",
+    )
+    .unwrap();
+
+    // Dry run reports the reconciliation and writes nothing
+    tdt()
+        .current_dir(tmp.path())
+        .args(["import", "asm"])
+        .arg(&csv)
+        .args(["--profile", "kicad", "--assembly", "ASM@1", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("supplier PN"))
+        .stdout(predicate::str::contains("new component"))
+        .stdout(predicate::str::contains("not populated"))
+        .stdout(predicate::str::contains("removed"));
+    assert_eq!(std::fs::read_dir(&cmp_dir).unwrap().count(), 2);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["import", "asm"])
+        .arg(&csv)
+        .args(["--profile", "kicad", "--assembly", "ASM@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created"))
+        .stdout(predicate::str::contains("Updated BOM"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "show", "ASM@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- D1"))
+        .stdout(predicate::str::contains("- R3"))
+        .stdout(predicate::str::contains("quantity: 3"))
+        .stdout(predicate::str::contains("R4").not());
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("330R"))
+        .stdout(predicate::str::contains("electrical"));
+}
+
+#[test]
+fn test_import_bom_profile_requires_assembly() {
+    let tmp = setup_bom_diff_project();
+    let csv = tmp.path().join("bom.csv");
+    std::fs::write(
+        &csv,
+        "ITEM NO.,PART NUMBER,DESCRIPTION,QTY.\n1,SCR-1,Screw,2\n",
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["import", "asm"])
+        .arg(&csv)
+        .args(["--profile", "solidworks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--assembly is required"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["import", "cmp"])
+        .arg(&csv)
+        .args(["--profile", "solidworks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only applies to asm"));
+}
+
+// ============================================================================
+// Should-Cost Tests
+// ============================================================================
+
+#[test]
+fn test_should_cost_from_routing_feeds_asm_cost() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "new",
+            "--part-number",
+            "PN-MAKE",
+            "--title",
+            "Machined Bracket",
+            "--make-buy",
+            "make",
+            "--material",
+            "6061-T6",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    let cmp_file = fs::read_dir(tmp.path().join("bom/components"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&cmp_file).unwrap();
+    fs::write(
+        &cmp_file,
+        format!("{}mass_kg: 0.5\nunit_cost: 99.0\n", content),
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "new", "--title", "CNC Mill", "--no-edit"])
+        .assert()
+        .success();
+    let proc_file = fs::read_dir(tmp.path().join("manufacturing/processes"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&proc_file)
+        .unwrap()
+        .replace("cycle_time_minutes: null", "cycle_time_minutes: 10")
+        .replace("setup_time_minutes: null", "setup_time_minutes: 60")
+        .replace("equipment: []", "equipment:\n  - name: Haas VF-2")
+        .replace("operator_skill: intermediate", "operator_skill: advanced");
+    fs::write(&proc_file, content).unwrap();
+
+    let mut config = fs::read_to_string(tmp.path().join(".tdt/config.yaml")).unwrap();
+    config.push_str(
+        "\ncosting:\n  batch_size: 50\n  labor_rates:\n    advanced: 48\n  machine_rates:\n    \
+         - { equipment: Haas VF-2, rate: 72 }\n  materials:\n    \
+         - { material: 6061-T6, cost_per_kg: 8, scrap_factor: 0.25 }\n",
+    );
+    fs::write(tmp.path().join(".tdt/config.yaml"), config).unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "routing", "add", "CMP@1", "PROC@1"])
+        .assert()
+        .success();
+
+    // Supplier quote to compare against
+    create_test_supplier(&tmp, "Machine Shop");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "new",
+            "--component",
+            "CMP@1",
+            "--supplier",
+            "SUP@1",
+            "--title",
+            "Shop Quote",
+            "--price",
+            "30.00",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "list"])
+        .output()
+        .unwrap();
+
+    // Material 0.5 kg × $8 × 1.25 = $5.00; $120/h: cycle $20.00, setup $2.40
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "should-cost", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Material: $5.00"))
+        .stdout(predicate::str::contains("$2.40"))
+        .stdout(predicate::str::contains("Should-Cost: $27.40"))
+        .stdout(predicate::str::contains("Shop Quote"))
+        .stdout(predicate::str::contains("+9.5%"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-MAKE",
+            "--title",
+            "Make Assembly",
+            "--bom",
+            "CMP@1:2",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+
+    // Should-cost takes precedence over the typed-in unit_cost
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "cost", "ASM@1", "--breakdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("should-cost"))
+        .stdout(predicate::str::contains("Total Cost: $54.80"));
+
+    // Setup over a batch of 1: 5 + 20 + 120
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "cost", "ASM@1", "--batch", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total Cost: $290.00"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["report", "bom", "ASM@1", "--with-cost"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**Total Cost:** $54.80 (USD)"));
+}
+
+// ============================================================================
+// AML and Obsolescence Tests
+// ============================================================================
+
+#[test]
+fn test_obsolescence_report_flags_eol_and_single_source() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "U1", "Regulator");
+    create_test_component(&tmp, "R1", "Legacy Resistor");
+    create_test_component(&tmp, "C1", "Decoupling Cap");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+
+    let aml_add = |cmp: &str, mpn: &str, manufacturer: &str| {
+        tdt()
+            .current_dir(tmp.path())
+            .args([
+                "cmp",
+                "aml",
+                "add",
+                cmp,
+                mpn,
+                "--manufacturer",
+                manufacturer,
+            ])
+            .assert()
+            .success();
+    };
+    aml_add("CMP@1", "LM317T", "TI");
+    aml_add("CMP@1", "LM317-ST", "ST");
+    aml_add("CMP@3", "GRM188", "Murata");
+    aml_add("CMP@3", "C1608", "TDK");
+
+    // One of the regulator's two sources goes obsolete
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "lifecycle", "CMP@1", "obsolete", "--mpn", "LM317T"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Part lifecycle: active"));
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "lifecycle",
+            "CMP@2",
+            "last_time_buy",
+            "--last-time-buy",
+            "2020-01-31",
+        ])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "aml", "list", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LM317-ST"))
+        .stdout(predicate::str::contains("obsolete"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-PSU",
+            "--title",
+            "Power Supply",
+            "--bom",
+            "CMP@1:2,CMP@2:4,CMP@3:1",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["report", "obsolescence", "ASM@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Obsolescence Report: Power Supply",
+        ))
+        .stdout(predicate::str::contains("Parts checked: 3"))
+        .stdout(predicate::str::contains("single source, end-of-life MPN"))
+        .stdout(predicate::str::contains("ST LM317-ST"))
+        .stdout(predicate::str::contains("last_time_buy (LTB 2020-01-31)"))
+        .stdout(predicate::str::contains(
+            "End-of-Life Without Approved Alternates",
+        ))
+        .stdout(predicate::str::contains("last-time-buy passed"))
+        .stdout(predicate::str::contains("Decoupling Cap").not());
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["report", "obsolescence", "ASM@1", "-o", "json"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let parts = report["parts"].as_array().unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0]["part_number"], "R1");
+    assert_eq!(parts[0]["quantity"], 4);
+    assert_eq!(parts[1]["flags"][0], "single_source");
+}
+
+// ============================================================================
+// Material Compliance Tests
+// ============================================================================
+
+#[test]
+fn test_compliance_report_rolls_up_through_subassemblies() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "PCB-1", "Control Board");
+    create_test_component(&tmp, "CON-1", "Connector");
+    create_test_component(&tmp, "SCR-1", "Screw");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "compliance",
+            "set",
+            "CMP@1",
+            "rohs",
+            "exempt",
+            "--exemption",
+            "7(a)",
+            "--declared-by",
+            "Acme",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added RoHS declaration"));
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "compliance",
+            "set",
+            "CMP@1",
+            "reach",
+            "compliant",
+            "--substance",
+            "Lead:7439-92-1:0.2",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "compliance", "set", "CMP@2", "rohs", "non_compliant"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "compliance",
+            "set",
+            "CMP@3",
+            "rohs",
+            "compliant",
+            "--expires",
+            "2020-01-01",
+        ])
+        .assert()
+        .success();
+
+    // Exempt declarations must name the exemption
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "compliance", "set", "CMP@3", "reach", "exempt"])
+        .assert()
+        .failure();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "compliance", "list", "CMP@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RoHS"))
+        .stdout(predicate::str::contains("7(a)"))
+        .stdout(predicate::str::contains("Lead (CAS 7439-92-1) 0.2% w/w"));
+
+    // PCB-1 sits in a sub-assembly, the rest at the top level
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-CTRL",
+            "--title",
+            "Controller",
+            "--bom",
+            "CMP@1:1",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-TOP",
+            "--title",
+            "Top Level",
+            "--bom",
+            "ASM@1:2,CMP@2:1,CMP@3:4",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["report", "compliance", "ASM@2", "-r", "rohs", "-r", "reach"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Compliance Report: Top Level"))
+        .stdout(predicate::str::contains("Components checked: 3"))
+        .stdout(predicate::str::contains("non-compliant"))
+        .stdout(predicate::str::contains("expired (2020-01-01)"))
+        .stdout(predicate::str::contains("PCB-1 (RoHS): 7(a)"))
+        .stdout(predicate::str::contains("0.2 (> 0.1%)"))
+        .stdout(predicate::str::contains("Not compliant: 4 issue(s)"));
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["report", "compliance", "ASM@2", "-r", "rohs", "-o", "json"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["summaries"][0]["status"], "non_compliant");
+    assert_eq!(report["summaries"][0]["exempt"], 1);
+    let lines = report["lines"].as_array().unwrap();
+    assert_eq!(lines[0]["part_number"], "CON-1");
+    let pcb = lines.iter().find(|l| l["part_number"] == "PCB-1").unwrap();
+    assert_eq!(pcb["quantity"], 2);
+    assert_eq!(pcb["used_in"][0], "ASM-CTRL");
+}
+
+// ============================================================================
+// Mass Properties Tests
+// ============================================================================
+
+#[test]
+fn test_asm_mass_rolls_up_cg_inertia_and_budget() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "PLATE-1", "Base Plate");
+    create_test_component(&tmp, "FOOT-1", "Rubber Foot");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+
+    let append = |dir: &str, needle: &str, extra: &str| {
+        let path = fs::read_dir(tmp.path().join(dir))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| fs::read_to_string(p).unwrap().contains(needle))
+            .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, format!("{}{}", content, extra)).unwrap();
+        path
+    };
+    append(
+        "bom/components",
+        "PLATE-1",
+        "mass_kg: 1.0\nmass_properties:\n  cg_mm: [0.0, 0.0, 5.0]\n  inertia: { ixx: 100.0, iyy: 100.0, izz: 200.0 }\n",
+    );
+    append(
+        "bom/components",
+        "FOOT-1",
+        "mass_kg: 0.1\nmass_properties:\n  cg_mm: [0.0, 0.0, -5.0]\n",
+    );
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-BASE",
+            "--title",
+            "Base",
+            "--bom",
+            "CMP@1:1,CMP@2:4",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+
+    // One foot at each corner, and a 1.2 kg budget
+    let asm_file = append(
+        "bom/assemblies",
+        "ASM-BASE",
+        "mass_budget:\n  max_kg: 1.2\n",
+    );
+    let content = fs::read_to_string(&asm_file).unwrap();
+    let placements: String = [(-50, -50), (50, -50), (-50, 50), (50, 50)]
+        .iter()
+        .map(|(x, y)| {
+            format!(
+                "  - {{ origin: [{}, {}, 0], x_axis: [1, 0, 0], z_axis: [0, 0, 1] }}\n",
+                x, y
+            )
+        })
+        .collect();
+    fs::write(
+        &asm_file,
+        content.replace(
+            "  quantity: 4\n",
+            &format!("  quantity: 4\n  placements:\n{}", placements),
+        ),
+    )
+    .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "mass", "ASM@1", "--breakdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total Mass: 1.400 kg"))
+        .stdout(predicate::str::contains(
+            "Center of Gravity: (0.0, 0.0, 2.1) mm",
+        ))
+        .stdout(predicate::str::contains("OVER BUDGET, margin -0.200 kg"))
+        .stdout(predicate::str::contains("FOOT-1 - no inertia"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "mass", "ASM@1", "--budget", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("within budget"));
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "mass", "ASM@1", "-o", "json"])
+        .output()
+        .unwrap();
+    let rollup: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!((rollup["mass_kg"].as_f64().unwrap() - 1.4).abs() < 1e-9);
+    // Feet 50 mm out in x and y: 0.4 * 50² added to ixx and iyy, 0.4 * 2 * 50² to izz
+    let izz = rollup["inertia_origin"]["izz"].as_f64().unwrap();
+    assert!((izz - 2200.0).abs() < 1e-6);
+    assert_eq!(rollup["budget"]["within_budget"], false);
+}
+
+#[test]
+fn test_quote_rfq_package_and_import() {
+    let tmp = setup_test_project();
+
+    create_test_component(&tmp, "BRK-1", "Bracket");
+    create_test_component(&tmp, "SHF-1", "Shaft");
+    create_test_supplier(&tmp, "Acme Machining");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "list"])
+        .output()
+        .unwrap();
+
+    // Give the bracket a drawing to send along
+    fs::create_dir_all(tmp.path().join("drawings")).unwrap();
+    fs::write(tmp.path().join("drawings/BRK-1.pdf"), "drawing").unwrap();
+    for entry in fs::read_dir(tmp.path().join("bom/components")).unwrap() {
+        let path = entry.unwrap().path();
+        let content = fs::read_to_string(&path).unwrap();
+        if content.contains("BRK-1") {
+            fs::write(
+                &path,
+                format!(
+                    "{}\ndocuments:\n  - type: drawing\n    path: drawings/BRK-1.pdf\n    revision: B\n",
+                    content
+                ),
+            )
+            .unwrap();
+        }
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "quote",
+            "rfq",
+            "CMP@1",
+            "CMP@2",
+            "--supplier",
+            "SUP@1",
+            "--qty",
+            "100,500",
+            "--due",
+            "2030-01-15",
+            "--dir",
+            "out",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme Machining"))
+        .stdout(predicate::str::contains(
+            "2 item(s), 4 line(s), 1 document(s)",
+        ));
+
+    let package = fs::read_dir(tmp.path().join("out"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    assert!(package
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("RFQ-"));
+    assert!(package.join("documents/BRK-1/BRK-1.pdf").is_file());
+    let manifest = fs::read_to_string(package.join("manifest.json")).unwrap();
+    assert!(manifest.contains("\"respond_by\": \"2030-01-15\""));
+    let sheet = fs::read_to_string(package.join("rfq.csv")).unwrap();
+    assert!(sheet.starts_with("line,component,part_number"));
+    assert_eq!(sheet.lines().count(), 5);
+
+    // The supplier prices the bracket and declines the shaft
+    fs::write(
+        package.join("rfq.csv"),
+        "part_number,quantity,unit_price,lead_time_days,nre_description,nre_cost,valid_until\n\
+         BRK-1,100,2.50,21,Fixture,400,2030-03-31\n\
+         BRK-1,500,2.10,14,,,\n\
+         SHF-1,100,,,,,\n",
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["quote", "rfq-import"])
+        .arg(&package)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created 1 quote(s)"))
+        .stdout(predicate::str::contains("No price for: SHF-1"));
+
+    let quotes: Vec<_> = fs::read_dir(tmp.path().join("bom/quotes"))
+        .unwrap()
+        .map(|e| fs::read_to_string(e.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(quotes.len(), 1);
+    assert!(quotes[0].contains("min_qty: 500"));
+    assert!(quotes[0].contains("unit_price: 2.1"));
+    assert!(quotes[0].contains("Fixture"));
+    assert!(quotes[0].contains("2030-03-31"));
+    assert!(quotes[0].contains("quote_status: received"));
 }
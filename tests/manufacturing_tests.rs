@@ -2,7 +2,7 @@
 
 mod common;
 
-use common::{setup_test_project, tdt};
+use common::{create_test_component, create_test_supplier, setup_test_project, tdt};
 use predicates::prelude::*;
 use std::fs;
 
@@ -690,6 +690,101 @@ fn test_asm_routing_set() {
         .stdout(predicate::str::contains("Process C"));
 }
 
+#[test]
+fn test_asm_routing_analyze() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-001",
+            "--title",
+            "Test Assembly",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+
+    // Cycle/setup minutes: Cut 4/20, Weld 8/0, Paint 6/40
+    for (title, cycle, setup) in [("Cut", "4", "20"), ("Weld", "8", "0"), ("Paint", "6", "40")] {
+        tdt()
+            .current_dir(tmp.path())
+            .args(["proc", "new", "--title", title, "--no-edit"])
+            .assert()
+            .success();
+        let path = fs::read_dir(tmp.path().join("manufacturing/processes"))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| {
+                fs::read_to_string(p)
+                    .unwrap()
+                    .lines()
+                    .any(|l| l.starts_with("title:") && l.contains(title))
+            })
+            .unwrap();
+        let content = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .filter(|l| {
+                !l.starts_with("cycle_time_minutes") && !l.starts_with("setup_time_minutes")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(
+            &path,
+            format!(
+                "{}\ncycle_time_minutes: {}\nsetup_time_minutes: {}\n",
+                content, cycle, setup
+            ),
+        )
+        .unwrap();
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm", "routing", "set", "ASM@1", "PROC@1", "PROC@2", "PROC@3",
+        ])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm", "routing", "analyze", "ASM@1", "--demand", "45", "--batch", "10",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bottleneck:      Paint"))
+        .stdout(predicate::str::contains("Takt time:       10.00"))
+        .stdout(predicate::str::contains("Line efficiency: 80.0%"))
+        .stdout(predicate::str::contains("Setup Amortization"));
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm", "routing", "analyze", "ASM@1", "--demand", "60", "--batch", "10", "-o", "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["meets_takt"], false);
+    assert_eq!(json["stations"][2]["stations_needed"], 2);
+}
+
 // ============================================================================
 // Component Routing Command Tests
 // ============================================================================
@@ -1099,3 +1194,1155 @@ fn test_lot_step_show_wi() {
                 .or(predicate::str::contains("Work Instructions")),
         );
 }
+
+// ============================================================================
+// Lot Genealogy Tests
+// ============================================================================
+
+#[test]
+fn test_lot_serial_generate_and_list() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "new",
+            "--title",
+            "Serialized Lot",
+            "--lot-number",
+            "L100",
+            "--quantity",
+            "3",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "serial", "LOT@1", "--generate", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added 3 serials"));
+
+    // Duplicate serials are rejected
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "serial", "LOT@1", "--add", "L100-0001"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "serial", "LOT@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("L100-0003"));
+}
+
+#[test]
+fn test_lot_split_and_trace() {
+    let tmp = setup_test_project();
+
+    for (title, number) in [("Sub Lot", "SUB-1"), ("Top Lot", "TOP-1")] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([
+                "lot",
+                "new",
+                "--title",
+                title,
+                "--lot-number",
+                number,
+                "--quantity",
+                "2",
+                "--no-edit",
+            ])
+            .assert()
+            .success();
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "serial", "LOT@1", "--add", "S1,S2"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "serial", "LOT@2", "--add", "T1,T2"])
+        .assert()
+        .success();
+
+    // Material needs a component or source lot
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "material", "LOT@1", "--supplier-lot", "RES-42"])
+        .assert()
+        .failure();
+
+    // Sub lot consumed supplier lot RES-42; T1 got subassembly S1
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--component",
+            "CMP-01HC2JB7SMQX7RS1Y0GFKBHPTE",
+            "--supplier-lot",
+            "RES-42",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@2",
+            "--serial",
+            "T1",
+            "--source-lot",
+            "LOT@1",
+            "--source-serials",
+            "S1",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "serial",
+            "LOT@2",
+            "--status",
+            "completed",
+            "--serials",
+            "T1",
+        ])
+        .assert()
+        .success();
+
+    // Split T2 off into its own lot
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "split", "LOT@2", "--serials", "T2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TOP-1-1"));
+
+    // Forward: supplier lot reaches finished serial T1 but not T2
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "trace",
+            "RES-42",
+            "--forward",
+            "--finished",
+            "-o",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("T1,"))
+        .stdout(predicate::str::contains("T2,").not());
+
+    // Backward: T1 traces to the supplier lot
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "trace", "T1", "--backward"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Serial S1"))
+        .stdout(predicate::str::contains("RES-42"));
+
+    // Split child links back to its parent
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "trace", "T2", "--backward", "-o", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TOP-1 (split)").or(predicate::str::contains("TOP-1-1")));
+}
+
+#[test]
+fn test_lot_merge_rejects_different_products() {
+    let tmp = setup_test_project();
+
+    for (number, product) in [
+        ("A1", "ASM-01HC2JB7SMQX7RS1Y0GFKBHPTA"),
+        ("B1", "ASM-01HC2JB7SMQX7RS1Y0GFKBHPTB"),
+    ] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([
+                "lot",
+                "new",
+                "--title",
+                number,
+                "--lot-number",
+                number,
+                "--product",
+                product,
+                "--no-edit",
+            ])
+            .assert()
+            .success();
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "merge", "LOT@1", "LOT@2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("different products"));
+}
+
+#[test]
+fn test_lot_dhr_export() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-001",
+            "--title",
+            "Widget",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "new", "--title", "Final Assembly", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "routing", "set", "ASM@1", "PROC@1"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "new",
+            "--title",
+            "DHR Lot",
+            "--lot-number",
+            "LOT-900",
+            "--product",
+            "ASM@1",
+            "--from-routing",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "step",
+            "LOT@1",
+            "--process",
+            "0",
+            "--status",
+            "completed",
+            "--operator",
+            "Jane Doe",
+        ])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "dhr", "LOT@1", "--dir", "dhr", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported DHR"));
+
+    let doc = fs::read_to_string(tmp.path().join("dhr/DHR.md")).unwrap();
+    assert!(doc.contains("Device History Record: LOT-900"));
+    assert!(doc.contains("Final Assembly"));
+    assert!(doc.contains("Jane Doe"));
+    assert!(doc.contains("ASM-001"));
+
+    let manifest = fs::read_to_string(tmp.path().join("dhr/manifest.json")).unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    let records = manifest["records"].as_array().unwrap();
+    assert_eq!(records.len(), 3);
+    for record in records {
+        let path = record["path"].as_str().unwrap();
+        assert!(tmp.path().join("dhr/records").join(path).exists());
+        assert_eq!(record["sha256"].as_str().unwrap().len(), 64);
+    }
+    assert!(manifest["document"]["sha256"].is_string());
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "dhr",
+            "LOT@1",
+            "--format",
+            "html",
+            "--stdout",
+            "--no-verify",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<table>"))
+        .stdout(predicate::str::contains("Final Assembly"));
+}
+
+// ============================================================================
+// Step Data Validation Tests
+// ============================================================================
+
+/// Create a product with a one-process routing whose process has a torque limit
+fn setup_lot_with_torque_limit(tmp: &tempfile::TempDir) {
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-001",
+            "--title",
+            "Widget",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "new", "--title", "Fastening", "--no-edit"])
+        .assert()
+        .success();
+
+    let proc_dir = tmp.path().join("manufacturing/processes");
+    let proc_file = fs::read_dir(&proc_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&proc_file).unwrap();
+    let content = content.replace(
+        "parameters: []",
+        "parameters:\n  - name: Torque\n    value: 5.0\n    units: Nm\n    min: 4.5\n    max: 5.5",
+    );
+    fs::write(&proc_file, content).unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "routing", "set", "ASM@1", "PROC@1"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "new",
+            "--title",
+            "Torque Lot",
+            "--lot-number",
+            "LOT-100",
+            "--product",
+            "ASM@1",
+            "--from-routing",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_lot_step_data_in_spec() {
+    let tmp = setup_test_project();
+    setup_lot_with_torque_limit(&tmp);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "torque=5.1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Torque = 5.1"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "show", "LOT@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Torque: 5.1"));
+}
+
+#[test]
+fn test_lot_step_data_out_of_spec_blocks_completion() {
+    let tmp = setup_test_project();
+    setup_lot_with_torque_limit(&tmp);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "Torque=6.2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be completed"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "Torque=abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a number"));
+
+    // With --ncr the data is kept, an NCR is raised, and the step is held
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "Torque=6.2", "--ncr"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("held at in_progress"));
+
+    let ncr_count = fs::read_dir(tmp.path().join("manufacturing/ncrs"))
+        .unwrap()
+        .count();
+    assert_eq!(ncr_count, 1);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "show", "LOT@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("status: in_progress"))
+        .stdout(predicate::str::contains("NCR-"));
+}
+
+// ============================================================================
+// Deviation Enforcement Tests
+// ============================================================================
+
+#[test]
+fn test_lot_step_deviation_enforcement() {
+    let tmp = setup_test_project();
+    setup_lot_with_torque_limit(&tmp);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "new", "--title", "Alt torque", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "list"])
+        .output()
+        .unwrap();
+
+    // Pending deviations cannot authorize a step
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--deviation", "DEV@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pending approval"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "approve", "DEV@1", "--activate", "-y"])
+        .assert()
+        .success();
+
+    // Approved, but not linked to this lot or process
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--deviation", "DEV@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not apply"));
+
+    let proc_id = String::from_utf8(
+        tdt()
+            .current_dir(tmp.path())
+            .args(["proc", "list", "-o", "id"])
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap();
+    let dev_file = fs::read_dir(tmp.path().join("manufacturing/deviations"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&dev_file).unwrap();
+    let content = content.replace(
+        "links: {}",
+        &format!("links:\n  processes: [{}]", proc_id.trim()),
+    );
+    fs::write(&dev_file, content).unwrap();
+
+    // A second deviation on the same process makes the choice ambiguous
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "new", "--title", "Alt torque tool", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "approve", "DEV@2", "--activate", "-y"])
+        .assert()
+        .success();
+    let other_dev = fs::read_dir(tmp.path().join("manufacturing/deviations"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| *p != dev_file)
+        .unwrap();
+    let content = fs::read_to_string(&other_dev).unwrap().replace(
+        "links: {}",
+        &format!("links:\n  processes: [{}]", proc_id.trim()),
+    );
+    fs::write(&other_dev, content).unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "Torque=6.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Choose one with --deviation"));
+
+    // The cited deviation covers the out-of-spec torque and is recorded on the step
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "step",
+            "LOT@1",
+            "--data",
+            "Torque=6.0",
+            "--deviation",
+            "DEV@1",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("accepted under deviation"))
+        .stdout(predicate::str::contains("Deviation: DEV@1"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No lots processed"));
+
+    // Closing the deviation lists the lot for review, apart from violations
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "expire", "DEV@1", "-y"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "check", "DEV@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOT-100"))
+        .stdout(predicate::str::contains(
+            "processed in effect, since closed",
+        ))
+        .stdout(predicate::str::contains("0 violation(s), 1 usage(s)"));
+
+    // A step processed before the deviation took effect is a violation
+    let content = fs::read_to_string(&dev_file).unwrap();
+    let content = content
+        .lines()
+        .filter(|l| !l.starts_with("effective_date:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(
+        &dev_file,
+        format!("{}\neffective_date: 2099-01-01\n", content),
+    )
+    .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOT-100"))
+        .stdout(predicate::str::contains("outside effective window"))
+        .stdout(predicate::str::contains("1 violation(s)"));
+
+    // A prefix that matches more than one deviation is refused
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "new", "--title", "Alt fixture", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["dev", "check", "DEV-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Ambiguous"));
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "step",
+            "LOT@1",
+            "--process",
+            "1",
+            "--deviation",
+            "DEV-",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Ambiguous"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "step",
+            "LOT@1",
+            "--process",
+            "1",
+            "--deviation",
+            "DEV@1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is closed"));
+}
+
+// ============================================================================
+// Receiving Inspection Tests
+// ============================================================================
+
+#[test]
+fn test_rcv_gates_lot_consumption_and_rejection_opens_ncr() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "BRK-100", "Bracket");
+    create_test_supplier(&tmp, "Acme");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["sup", "list"])
+        .assert()
+        .success();
+
+    for lot in ["A12", "B7"] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([
+                "rcv",
+                "new",
+                "--supplier",
+                "SUP@1",
+                "--component",
+                "CMP@1",
+                "--supplier-lot",
+                lot,
+                "--quantity",
+                "100",
+                "--no-edit",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Created receipt"));
+    }
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("BRK-100 lot A12"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "inspect", "RCV@1", "Visual", "--pass"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "accept", "RCV@1"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "inspect", "RCV@2", "Visual", "--defects", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fail"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "accept", "RCV@2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("failed inspection"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["rcv", "reject", "RCV@2", "--reason", "Burrs on edges"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Opened supplier NCR NCR@1"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "show", "NCR@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ncr_type: supplier"))
+        .stdout(predicate::str::contains("receipt: RCV-"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "new", "--title", "Build 1", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .assert()
+        .success();
+
+    // A rejected supplier lot cannot be consumed
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--component",
+            "CMP@1",
+            "--supplier-lot",
+            "B7",
+            "--quantity",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only accepted receipts"));
+
+    // A supplier lot that was never received needs an explicit override
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--component",
+            "CMP@1",
+            "--supplier-lot",
+            "C9",
+            "--quantity",
+            "5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no accepted receipt"));
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--component",
+            "CMP@1",
+            "--supplier-lot",
+            "C9",
+            "--quantity",
+            "5",
+            "--no-receipt",
+        ])
+        .assert()
+        .success();
+
+    // Nor more than was received
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--receipt",
+            "RCV@1",
+            "--quantity",
+            "101",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("remaining"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "material",
+            "LOT@1",
+            "--receipt",
+            "RCV@1",
+            "--quantity",
+            "60",
+        ])
+        .assert()
+        .success();
+    let lot_dir = tmp.path().join("manufacturing/lots");
+    let lot_file = fs::read_dir(&lot_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(lot_file).unwrap();
+    assert!(content.contains("supplier_lot: A12"));
+    assert!(content.contains("receipt: RCV-"));
+}
+
+// ============================================================================
+// Sampling Plan Tests
+// ============================================================================
+
+#[test]
+fn test_ctrl_sampling_plan_and_oc_curve() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ctrl",
+            "new",
+            "--title",
+            "Thread gauge",
+            "--aql",
+            "1.0",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ctrl", "list"])
+        .output()
+        .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ctrl", "sampling", "CTRL@1", "--lot-size", "1000", "--oc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sample size:  80"))
+        .stdout(predicate::str::contains("Accept (Ac):  2"))
+        .stdout(predicate::str::contains("AOQL"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ctrl",
+            "sampling",
+            "--aql",
+            "1.0",
+            "--lot-size",
+            "1000",
+            "--severity",
+            "tightened",
+            "-o",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"accept\": 1"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ctrl", "sampling", "--aql", "0.7", "--lot-size", "1000"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_lot_step_sampling_defects_against_accept_number() {
+    let tmp = setup_test_project();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-001",
+            "--title",
+            "Widget",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "new", "--title", "Threading", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "list"])
+        .output()
+        .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ctrl",
+            "new",
+            "--title",
+            "Thread gauge",
+            "-p",
+            "PROC@1",
+            "--aql",
+            "0.65",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["asm", "routing", "set", "ASM@1", "PROC@1"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "new",
+            "--title",
+            "Threaded Lot",
+            "--lot-number",
+            "LOT-200",
+            "--product",
+            "ASM@1",
+            "--from-routing",
+            "--quantity",
+            "500",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list"])
+        .output()
+        .unwrap();
+
+    // 500 units at AQL 0.65: n=80 Ac 1 Re 2
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "step",
+            "LOT@1",
+            "--show-wi",
+            "--data",
+            "Thread gauge defects=2",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("n=80 Ac 1 Re 2"))
+        .stderr(predicate::str::contains("cannot be completed"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "step", "LOT@1", "--data", "Thread gauge defects=1"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_plan_nets_planned_lots_against_inventory() {
+    let tmp = setup_test_project();
+    create_test_component(&tmp, "BOLT-1", "Bolt");
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmp",
+            "new",
+            "--part-number",
+            "PCB-1",
+            "--title",
+            "Board",
+            "--make-buy",
+            "make",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmp", "list"])
+        .output()
+        .unwrap();
+
+    // Bolts come from one supplier: 14 days, MOQ 100
+    let bolt = fs::read_dir(tmp.path().join("bom/components"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| fs::read_to_string(p).unwrap().contains("BOLT-1"))
+        .unwrap();
+    let content = fs::read_to_string(&bolt).unwrap();
+    fs::write(
+        &bolt,
+        format!(
+            "{}suppliers:\n  - name: Acme Fasteners\n    lead_time_days: 14\n    moq: 100\n    unit_cost: 0.10\n",
+            content
+        ),
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "asm",
+            "new",
+            "--part-number",
+            "ASM-1",
+            "--title",
+            "Widget",
+            "--bom",
+            "CMP@1:8,CMP@2:1",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "lot",
+            "new",
+            "--title",
+            "Widget Batch",
+            "--product",
+            "ASM@1",
+            "--quantity",
+            "10",
+            "--planned",
+            "--start-date",
+            "2030-01-01",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["lot", "list", "--status", "planned"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Widget Batch"));
+
+    // 80 bolts needed, 40 available above safety stock: order the MOQ
+    fs::write(
+        tmp.path().join("stock.csv"),
+        "part_number,on_hand,safety_stock\nBOLT-1,50,10\n",
+    )
+    .unwrap();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "plan",
+            "--inventory",
+            "stock.csv",
+            "--as-of",
+            "2029-12-01",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Acme Fasteners"))
+        .stdout(predicate::str::contains(
+            "| 2029-12-18 | 2030-01-01 | CMP@1     | BOLT-1      | 14       | 40      | 100       | $0.10      | $10.00   | LOT@1 |",
+        ))
+        .stdout(predicate::str::contains("## Builds (make parts)"))
+        .stdout(predicate::str::contains("1 purchase order(s) totalling $10.00, 0 late"));
+
+    // A later planning date makes the order late
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "plan",
+            "--inventory",
+            "stock.csv",
+            "--as-of",
+            "2029-12-20",
+            "-o",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Acme Fasteners,2029-12-18,2030-01-01,CMP@1,BOLT-1,Bolt,14,40,100,0.1000,10.00,LOT@1,true",
+        ));
+}
@@ -200,6 +200,913 @@ fn test_capa_show_by_short_id() {
         .stdout(predicate::str::contains("Show CAPA"));
 }
 
+#[test]
+fn test_capa_rca_five_why_and_fishbone() {
+    let tmp = setup_test_project();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "new", "--title", "Bore oversize", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "risk",
+            "new",
+            "-T",
+            "Bore wear",
+            "-t",
+            "process",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["proc", "new", "--title", "Boring", "--no-edit"])
+        .assert()
+        .success();
+    for entity in ["capa", "risk", "proc"] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([entity, "list"])
+            .assert()
+            .success();
+    }
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "capa",
+            "rca",
+            "CAPA@1",
+            "--why",
+            "Boring bar deflected",
+            "--why",
+            "Tool wear not monitored",
+            "--risk",
+            "RISK@1",
+            "--process",
+            "PROC@1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("5-Why steps added: 2"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "capa",
+            "rca",
+            "CAPA@1",
+            "--method",
+            "fishbone",
+            "--cause",
+            "machine:Spindle runout",
+            "--confirmed",
+            "--root-cause",
+            "No tool-life control",
+        ])
+        .assert()
+        .success();
+
+    // Invalid category and wrong entity type are rejected
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "rca", "CAPA@1", "--cause", "weather:Rain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid fishbone category"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "rca", "CAPA@1", "--why", "x", "--risk", "PROC@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a RISK ID"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "show", "CAPA@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RCA Method: fishbone"))
+        .stdout(predicate::str::contains("2. Why? Tool wear not monitored"))
+        .stdout(predicate::str::contains("Spindle runout ✓ confirmed"))
+        .stdout(predicate::str::contains("FMEA Impact:"))
+        .stdout(predicate::str::contains("RISK@1 (Bore wear) RPN"))
+        .stdout(predicate::str::contains("why 1, why 2"));
+
+    let capa_dir = tmp.path().join("manufacturing/capas");
+    let file = fs::read_dir(&capa_dir).unwrap().next().unwrap().unwrap();
+    let content = fs::read_to_string(file.path()).unwrap();
+    assert!(content.contains("five_whys:"));
+    assert!(content.contains("category: machine"));
+    assert!(content.contains("confirmed: true"));
+    // The implicated risk is also linked to the CAPA
+    assert!(content.contains("links:\n  risks:\n  - RISK-"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["validate"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_capa_report_8d_flags_missing_disciplines() {
+    let tmp = setup_test_project();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "new", "--title", "Bore oversize", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "list"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "capa",
+            "rca",
+            "CAPA@1",
+            "--why",
+            "Tool wear not monitored",
+            "--root-cause",
+            "No tool-life control",
+        ])
+        .assert()
+        .success();
+
+    // Link an NCR back to the CAPA
+    let capa_file = fs::read_dir(tmp.path().join("manufacturing/capas"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let capa_id = capa_file
+        .file_stem()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .replace(".tdt", "");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "new", "--title", "Bore 0.05 over", "--no-edit"])
+        .assert()
+        .success();
+    let ncr_path = ncr_files(&tmp).pop().unwrap();
+    let content = fs::read_to_string(&ncr_path).unwrap();
+    fs::write(
+        &ncr_path,
+        content.replacen("capa: null", &format!("capa: {}", capa_id), 1),
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "report-8d", "CAPA@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# 8D Report"))
+        .stdout(predicate::str::contains("## D4 Root Cause Analysis"))
+        .stdout(predicate::str::contains(
+            "**Root Cause:** No tool-life control",
+        ))
+        .stdout(predicate::str::contains("Bore 0.05 over"))
+        .stdout(predicate::str::contains(
+            "| D4 Root Cause Analysis | complete |",
+        ))
+        .stdout(predicate::str::contains(
+            "**Missing:** no containment actions recorded on linked NCRs",
+        ))
+        .stderr(predicate::str::contains("D5 Permanent Corrective Actions"));
+
+    let html = tmp.path().join("8d.html");
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "capa",
+            "report-8d",
+            "CAPA@1",
+            "--format",
+            "html",
+            "-f",
+            "8d.html",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Wrote 8D report"));
+    let content = fs::read_to_string(html).unwrap();
+    assert!(content.contains("<h2>D8 Closure and Team Recognition</h2>"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "report-8d", "CAPA@1", "-o", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"disciplines\""));
+}
+
+// ============================================================================
+// MRB Disposition Tests
+// ============================================================================
+
+fn git_config(tmp: &tempfile::TempDir, key: &str, value: &str) {
+    std::process::Command::new("git")
+        .args(["config", key, value])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_ncr_mrb_signoff_required_to_close() {
+    let tmp = setup_test_project();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    git_config(&tmp, "user.name", "Quality Lead");
+    git_config(&tmp, "user.email", "quality@example.com");
+    fs::write(
+        tmp.path().join(".tdt/team.yaml"),
+        r#"version: 1
+members:
+  - name: Quality Lead
+    email: quality@example.com
+    username: Quality Lead
+    roles: [quality]
+  - name: Design Engineer
+    email: engineer@example.com
+    username: Design Engineer
+    roles: [engineering]
+"#,
+    )
+    .unwrap();
+
+    for title in ["Bore oversize", "Flash on parting line"] {
+        tdt()
+            .current_dir(tmp.path())
+            .args(["ncr", "new", "--title", title, "--no-edit"])
+            .assert()
+            .success();
+    }
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "list"])
+        .assert()
+        .success();
+
+    // A team roster alone does not turn on MRB sign-off
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ncr",
+            "close",
+            "NCR@2",
+            "-d",
+            "use-as-is",
+            "-r",
+            "Cosmetic only",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    // Nor can sign-offs be recorded
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("MRB sign-off is not configured"));
+
+    let config_path = tmp.path().join(".tdt/config.yaml");
+    let config = fs::read_to_string(&config_path).unwrap_or_default();
+    fs::write(
+        &config_path,
+        format!(
+            "{}\nmrb:\n  dispositions: [use_as_is, repair]\n  required_roles: [quality, engineering]\n",
+            config
+        ),
+    )
+    .unwrap();
+
+    // Use-as-is needs a deviation or engineering justification
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "disposition", "NCR@1", "-d", "use-as-is"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "deviation or engineering justification",
+        ));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ncr",
+            "disposition",
+            "NCR@1",
+            "-d",
+            "use-as-is",
+            "-r",
+            "Clearance fit unaffected per tolerance analysis",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("quality, engineering"));
+
+    // Closing without sign-offs is refused
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "close", "NCR@1", "-d", "use-as-is", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires MRB sign-off"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1", "-m", "Accepted"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("quality by Quality Lead"));
+
+    // The same person cannot also sign for engineering
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1", "--role", "engineering"])
+        .assert()
+        .failure();
+
+    git_config(&tmp, "user.name", "Design Engineer");
+    git_config(&tmp, "user.email", "engineer@example.com");
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MRB sign-off complete"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "close", "NCR@1", "-d", "use-as-is", "-y"])
+        .assert()
+        .success();
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "show", "NCR@1", "-o", "yaml"])
+        .output()
+        .unwrap();
+    let yaml = String::from_utf8_lossy(&output.stdout);
+    assert!(yaml.contains("ncr_status: closed"));
+    assert!(yaml.contains("mrb_required: true"));
+    assert!(yaml.contains("role: engineering"));
+}
+
+#[test]
+fn test_ncr_mrb_signed_signoff_is_verified() {
+    let tmp = setup_test_project();
+    let key = tmp.path().join("signing_key");
+    let keygen = std::process::Command::new("ssh-keygen")
+        .args([
+            "-q",
+            "-t",
+            "ed25519",
+            "-N",
+            "",
+            "-C",
+            "quality@example.com",
+            "-f",
+        ])
+        .arg(&key)
+        .output();
+    if !keygen.is_ok_and(|o| o.status.success()) {
+        return;
+    }
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    git_config(&tmp, "user.name", "Quality Lead");
+    git_config(&tmp, "user.email", "quality@example.com");
+    git_config(&tmp, "gpg.format", "ssh");
+    git_config(&tmp, "user.signingkey", key.to_str().unwrap());
+    fs::write(
+        tmp.path().join(".tdt/team.yaml"),
+        r#"version: 1
+members:
+  - name: Quality Lead
+    email: quality@example.com
+    username: Quality Lead
+    roles: [quality]
+"#,
+    )
+    .unwrap();
+    let config_path = tmp.path().join(".tdt/config.yaml");
+    let config = fs::read_to_string(&config_path).unwrap_or_default();
+    fs::write(
+        &config_path,
+        format!(
+            "{}\nmrb:\n  dispositions: [repair]\n  required_roles: [quality]\n  require_signature: true\n",
+            config
+        ),
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "new", "--title", "Scratched housing", "--no-edit"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "list"])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "disposition", "NCR@1", "-d", "repair"])
+        .assert()
+        .success();
+
+    // Unsigned sign-offs are refused outright
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be signed"));
+
+    // Without an allowed signers file the signature cannot be verified
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1", "--sign"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must carry a verified signature"));
+    let show = |tmp: &tempfile::TempDir| {
+        let output = tdt()
+            .current_dir(tmp.path())
+            .args(["ncr", "show", "NCR@1", "-o", "yaml"])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert!(!show(&tmp).contains("role: quality"));
+
+    let public_key = fs::read_to_string(tmp.path().join("signing_key.pub")).unwrap();
+    let signers = tmp.path().join("allowed_signers");
+    fs::write(&signers, format!("quality@example.com {}", public_key)).unwrap();
+    git_config(
+        &tmp,
+        "gpg.ssh.allowedSignersFile",
+        signers.to_str().unwrap(),
+    );
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "mrb-approve", "NCR@1", "--sign"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MRB sign-off complete"));
+    let yaml = show(&tmp);
+    assert!(yaml.contains("role: quality"));
+    assert!(yaml.contains("signature_verified: true"));
+
+    let log = std::process::Command::new("git")
+        .args(["log", "--format=%s"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let log = String::from_utf8_lossy(&log.stdout);
+    assert!(log.contains("Withdraw unverified MRB sign-off (quality)"));
+    assert!(log
+        .lines()
+        .next()
+        .unwrap()
+        .starts_with("MRB sign-off (quality)"));
+}
+
+// ============================================================================
+// Complaint Tests
+// ============================================================================
+
+#[test]
+fn test_cmpl_assess_and_risk_feedback() {
+    let tmp = setup_test_project();
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "risk",
+            "new",
+            "--title",
+            "Seal leak",
+            "--occurrence",
+            "2",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["risk", "list"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmpl",
+            "new",
+            "--title",
+            "Leaking pump",
+            "--type",
+            "return",
+            "--rma",
+            "RMA-7",
+            "--link",
+            "RISK@1",
+            "--no-edit",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created complaint"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmpl", "list", "--needs-assessment"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Leaking pump"));
+
+    // No failure mode reported yet, so nothing counts against the FMEA
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmpl", "risk-feedback", "--fielded", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("consistent"));
+
+    let cmpl_path = fs::read_dir(tmp.path().join("postmarket/complaints"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&cmpl_path).unwrap();
+    fs::write(
+        &cmpl_path,
+        content.replacen("failure_mode: null", "failure_mode: Seal leak", 1),
+    )
+    .unwrap();
+
+    // 1 failure in 100 units supports occurrence 5, contradicting the rating of 2
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmpl", "risk-feedback", "--fielded", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("understated"))
+        .stdout(predicate::str::contains(
+            "field data supports occurrence 5 but FMEA rates 2",
+        ));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "cmpl",
+            "assess",
+            "CMPL@1",
+            "--reportable",
+            "--regulation",
+            "21 CFR 803",
+            "--due",
+            "2020-01-31",
+        ])
+        .assert()
+        .success();
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmpl", "show", "CMPL@1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("21 CFR 803"))
+        .stdout(predicate::str::contains("2020-01-31 (overdue)"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["cmpl", "list", "--needs-assessment", "--count"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0"));
+}
+
+// ============================================================================
+// Audit Tests
+// ============================================================================
+
+#[test]
+fn test_audit_finding_promote_and_schedule() {
+    let tmp = setup_test_project();
+    for (title, date) in [
+        ("Production controls", "2024-05-01"),
+        ("Purchasing", "2024-05-20"),
+    ] {
+        tdt()
+            .current_dir(tmp.path())
+            .args([
+                "audit",
+                "new",
+                "--title",
+                title,
+                "--standard",
+                "ISO 13485:2016",
+                "-d",
+                date,
+                "--no-edit",
+            ])
+            .assert()
+            .success();
+    }
+    tdt()
+        .current_dir(tmp.path())
+        .args(["audit", "list"])
+        .assert()
+        .success();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "audit",
+            "finding",
+            "AUD@1",
+            "-s",
+            "major",
+            "-c",
+            "7.5.6",
+            "-d",
+            "Process not revalidated after tooling change",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("F1"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["audit", "promote", "AUD@1", "F1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created CAPA@1"));
+    tdt()
+        .current_dir(tmp.path())
+        .args(["capa", "show", "CAPA@1", "-o", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type: audit"));
+
+    // The same finding cannot be promoted twice
+    tdt()
+        .current_dir(tmp.path())
+        .args(["audit", "promote", "AUD@1", "F1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already promoted"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["audit", "schedule", "--as-of", "2024-06-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in progress"))
+        .stdout(predicate::str::contains("overdue"));
+}
+
+// ============================================================================
+// NCR Trend Tests
+// ============================================================================
+
+/// Create an NCR with the given category linked to a component, returning its file path
+fn create_ncr_on_component(
+    tmp: &tempfile::TempDir,
+    title: &str,
+    category: &str,
+    cmp_id: &str,
+) -> std::path::PathBuf {
+    let before: Vec<_> = ncr_files(tmp);
+    tdt()
+        .current_dir(tmp.path())
+        .args([
+            "ncr",
+            "new",
+            "--title",
+            title,
+            "--category",
+            category,
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+    let path = ncr_files(tmp)
+        .into_iter()
+        .find(|p| !before.contains(p))
+        .unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    fs::write(
+        &path,
+        content.replacen("component: null", &format!("component: {}", cmp_id), 1),
+    )
+    .unwrap();
+    path
+}
+
+/// Create a component and return its full ID
+fn create_component_id(tmp: &tempfile::TempDir, part_number: &str, title: &str) -> String {
+    create_test_component(tmp, part_number, title);
+    fs::read_dir(tmp.path().join("bom/components"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| fs::read_to_string(e.path()).unwrap())
+        .find(|c| c.contains(&format!("part_number: {}", part_number)))
+        .and_then(|c| {
+            c.lines()
+                .find_map(|l| l.strip_prefix("id: "))
+                .map(String::from)
+        })
+        .unwrap()
+}
+
+fn ncr_files(tmp: &tempfile::TempDir) -> Vec<std::path::PathBuf> {
+    fs::read_dir(tmp.path().join("manufacturing/ncrs"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".tdt.yaml"))
+        .collect()
+}
+
+#[test]
+fn test_ncr_trends_below_threshold() {
+    let tmp = setup_test_project();
+    let cmp = create_component_id(&tmp, "PN-100", "Bracket");
+    create_ncr_on_component(&tmp, "Hole oversize", "dimensional", &cmp);
+    create_ncr_on_component(&tmp, "Scratch", "cosmetic", &cmp);
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "trends"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No NCR trends require escalation"));
+}
+
+#[test]
+fn test_ncr_trends_creates_capa() {
+    let tmp = setup_test_project();
+    let cmp = create_component_id(&tmp, "PN-100", "Bracket");
+    let paths: Vec<_> = ["Hole oversize", "Hole position", "Flatness"]
+        .iter()
+        .map(|t| create_ncr_on_component(&tmp, t, "dimensional", &cmp))
+        .collect();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "trends"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("recurring-component-category"))
+        .stdout(predicate::str::contains("PN-100 Bracket / dimensional"))
+        .stdout(predicate::str::contains("Proposed corrective CAPA"));
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "trends", "--create"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created corrective CAPA"));
+
+    let capas: Vec<_> = fs::read_dir(tmp.path().join("manufacturing/capas"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .collect();
+    assert_eq!(capas.len(), 1);
+    let capa = fs::read_to_string(capas[0].path()).unwrap();
+    assert!(capa.contains("type: trend_analysis"));
+    assert!(capa.contains("reference: recurring-component-category"));
+    for path in &paths {
+        let ncr = fs::read_to_string(path).unwrap();
+        let ncr_id = ncr
+            .lines()
+            .find_map(|l| l.strip_prefix("id: "))
+            .unwrap()
+            .trim()
+            .to_string();
+        assert!(capa.contains(&ncr_id));
+        assert!(ncr.contains("capa: CAPA-"));
+    }
+
+    // Linked NCRs are not escalated again
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "trends"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No NCR trends require escalation"));
+}
+
+#[test]
+fn test_ncr_trends_custom_rule() {
+    let tmp = setup_test_project();
+    fs::write(
+        tmp.path().join(".tdt/config.yaml"),
+        "ncr_trends:\n  rules:\n    - name: any-cosmetic\n      categories: [cosmetic]\n      threshold: 1\n      capa_type: preventive\n",
+    )
+    .unwrap();
+    let cmp = create_component_id(&tmp, "PN-200", "Cover");
+    create_ncr_on_component(&tmp, "Scratch", "cosmetic", &cmp);
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args(["-o", "json", "ncr", "trends"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["rule"], "any-cosmetic");
+    assert_eq!(json[0]["capa_type"], "preventive");
+    assert_eq!(json[0]["source_type"], "ncr");
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["ncr", "trends", "--rule", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No escalation rule named"));
+}
+
+#[test]
+fn test_report_quality_pareto_and_copq() {
+    let tmp = setup_test_project();
+    let cmp = create_component_id(&tmp, "PN-300", "Housing");
+    let first = create_ncr_on_component(&tmp, "Bore oversize", "dimensional", &cmp);
+    let second = create_ncr_on_component(&tmp, "Field crack", "material", &cmp);
+    let content = fs::read_to_string(&first).unwrap();
+    fs::write(
+        &first,
+        content.replace("rework_cost: 0.0", "rework_cost: 150.0"),
+    )
+    .unwrap();
+    let content = fs::read_to_string(&second).unwrap();
+    fs::write(
+        &second,
+        content
+            .replace("scrap_cost: 0.0", "scrap_cost: 50.0")
+            .replace("found_at: in_process", "found_at: field"),
+    )
+    .unwrap();
+
+    tdt()
+        .current_dir(tmp.path())
+        .args(["report", "quality", "--months", "3", "--by-cost"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cost of Poor Quality (USD)"))
+        .stdout(predicate::str::is_match(r"\| Escape Rate +\| 50\.0%").unwrap())
+        .stdout(predicate::str::contains("## Pareto by Component"))
+        .stdout(predicate::str::contains("PN-300 Housing"))
+        .stdout(predicate::str::contains(
+            "| All           | 2    | 150.00 | 50.00 | 200.00 |",
+        ));
+
+    let output = tdt()
+        .current_dir(tmp.path())
+        .args([
+            "-o",
+            "csv",
+            "report",
+            "quality",
+            "--by",
+            "category",
+            "--by-cost",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let csv = String::from_utf8_lossy(&output.stdout);
+    let first_bar = csv
+        .lines()
+        .find(|l| l.starts_with("pareto_category,"))
+        .unwrap();
+    assert!(first_bar.starts_with("pareto_category,dimensional,"));
+    assert!(first_bar.ends_with(",75.0,75.0,,,150.00"));
+    assert!(!csv.contains("pareto_component"));
+}
+
 // ============================================================================
 // Validation Command Tests
 // ============================================================================